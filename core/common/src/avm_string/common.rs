@@ -70,6 +70,8 @@ ruffle_macros::define_common_strings! {
     "advanced",
     "album",
     "all",
+    "allowDomain",
+    "allowInsecureDomain",
     "alphaMultiplier",
     "alphaOffset",
     "always",
@@ -310,6 +312,7 @@ ruffle_macros::define_common_strings! {
     "rotate90",
     "rr",
     "save",
+    "securityError",
     "Selection",
     "separatorBefore",
     "smallCaps",
@@ -5,7 +5,7 @@
 //! provides. For example, audio calculations may need ~16 significant figures
 //! of precision, which f64 provides.
 
-use std::ops::{Add, AddAssign, Sub, SubAssign};
+use std::ops::{Add, AddAssign, Div, Mul, Sub, SubAssign};
 
 /// A duration of time represented in milliseconds with f64 precision.
 ///
@@ -102,6 +102,24 @@ impl SubAssign for FloatDuration {
     }
 }
 
+impl Mul<f64> for FloatDuration {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: f64) -> Self::Output {
+        Self(self.0 * rhs)
+    }
+}
+
+impl Div<f64> for FloatDuration {
+    type Output = Self;
+
+    #[inline]
+    fn div(self, rhs: f64) -> Self::Output {
+        Self(self.0 / rhs)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -138,6 +156,8 @@ mod tests {
 
         assert_eq!((a + b).as_millis(), 150.0);
         assert_eq!((a - b).as_millis(), 50.0);
+        assert_eq!((a * 2.0).as_millis(), 200.0);
+        assert_eq!((a / 4.0).as_millis(), 25.0);
     }
 
     #[test]
@@ -3,6 +3,7 @@
 pub mod avm_string;
 pub mod buffer;
 pub mod duration;
+pub mod policy_file;
 pub mod sandbox;
 pub mod tag_utils;
 pub mod utils;
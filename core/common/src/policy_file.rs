@@ -0,0 +1,351 @@
+//! Cross-domain policy files, see
+//! https://www.adobe.com/devnet-docs/acrobatetk/tools/AppSec/xdomain.html
+//!
+//! A policy file is served by a site (either over HTTP, usually as
+//! `/crossdomain.xml`, or by a socket policy server) to grant movies
+//! from other domains access to its data.
+
+use crate::sandbox::{SecurityDomain, domain_pattern_matches};
+use quick_xml::Reader;
+use quick_xml::events::{BytesStart, Event};
+use std::ops::RangeInclusive;
+use thiserror::Error;
+
+/// The null-terminated request that a socket policy server answers with a policy file.
+pub const SOCKET_POLICY_REQUEST: &[u8] = b"<policy-file-request/>\0";
+
+/// The port where socket policy servers are expected by default.
+pub const SOCKET_POLICY_PORT: u16 = 843;
+
+#[derive(Debug, Error)]
+pub enum PolicyFileError {
+    #[error("Invalid XML: {0}")]
+    Xml(#[from] quick_xml::Error),
+
+    #[error("Invalid attribute: {0}")]
+    Attribute(#[from] quick_xml::events::attributes::AttrError),
+
+    #[error("Root element is not <cross-domain-policy>")]
+    NotAPolicyFile,
+}
+
+/// The meta-policy of a site, declared by its master policy file.
+///
+/// It decides which other policy files on the same site are allowed to
+/// grant permissions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SiteControl {
+    /// No policy files are allowed, including the master policy file.
+    None,
+
+    /// Only the master policy file is allowed.
+    MasterOnly,
+
+    /// Only policy files served with `Content-Type: text/x-cross-domain-policy` are allowed.
+    ByContentType,
+
+    /// Only FTP policy files named `crossdomain.xml` are allowed.
+    ByFtpFilename,
+
+    /// All policy files are allowed.
+    All,
+}
+
+impl SiteControl {
+    fn from_attribute(value: &str) -> Option<Self> {
+        match value {
+            "none" | "none-this-response" => Some(Self::None),
+            "master-only" => Some(Self::MasterOnly),
+            "by-content-type" => Some(Self::ByContentType),
+            "by-ftp-filename" => Some(Self::ByFtpFilename),
+            "all" => Some(Self::All),
+            _ => None,
+        }
+    }
+}
+
+/// An `<allow-access-from>` entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AllowAccessFrom {
+    /// The domain pattern of movies that are granted access.
+    pub domain: String,
+
+    /// The ports that socket connections may be made to, if restricted.
+    ///
+    /// This is only meaningful for socket policy files.
+    pub to_ports: Option<Vec<RangeInclusive<u16>>>,
+
+    /// Whether movies served over plain HTTP are denied access to data
+    /// served over HTTPS.
+    pub secure: bool,
+}
+
+/// An `<allow-http-request-headers-from>` entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AllowHttpRequestHeadersFrom {
+    /// The domain pattern of movies that may send these headers.
+    pub domain: String,
+
+    /// The header names that may be sent, possibly ending in a `*` wildcard.
+    pub headers: Vec<String>,
+
+    /// Whether movies served over plain HTTP are denied sending headers to HTTPS.
+    pub secure: bool,
+}
+
+/// A parsed cross-domain policy file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PolicyFile {
+    /// The meta-policy of the site, if this file declared one.
+    pub site_control: Option<SiteControl>,
+
+    pub allow_access_from: Vec<AllowAccessFrom>,
+
+    pub allow_http_request_headers_from: Vec<AllowHttpRequestHeadersFrom>,
+}
+
+impl PolicyFile {
+    /// Parse a policy file.
+    ///
+    /// Unknown elements and malformed entries are ignored, like Flash Player does.
+    pub fn parse(data: &[u8]) -> Result<Self, PolicyFileError> {
+        // Socket policy servers terminate the file with a null byte.
+        let data = match data.iter().position(|&b| b == 0) {
+            Some(end) => &data[..end],
+            None => data,
+        };
+
+        let mut reader = Reader::from_reader(data);
+        reader.config_mut().trim_text(true);
+
+        let mut policy = Self::default();
+        let mut depth = 0;
+        let mut buf = Vec::new();
+        loop {
+            let event = reader.read_event_into(&mut buf)?;
+            match &event {
+                Event::Start(element) | Event::Empty(element) => {
+                    let name = element.name();
+                    if depth == 0 {
+                        if name.as_ref() != b"cross-domain-policy" {
+                            return Err(PolicyFileError::NotAPolicyFile);
+                        }
+                    } else if depth == 1 {
+                        policy.parse_entry(element)?;
+                    }
+
+                    if matches!(event, Event::Start(_)) {
+                        depth += 1;
+                    }
+                }
+                Event::End(_) => depth -= 1,
+                Event::Eof => break,
+                _ => {}
+            }
+            buf.clear();
+        }
+
+        Ok(policy)
+    }
+
+    fn parse_entry(&mut self, element: &BytesStart) -> Result<(), PolicyFileError> {
+        let attribute = |name: &[u8]| -> Result<Option<String>, PolicyFileError> {
+            match element.try_get_attribute(name)? {
+                Some(attribute) => Ok(Some(attribute.unescape_value()?.into_owned())),
+                None => Ok(None),
+            }
+        };
+        let secure = || -> Result<bool, PolicyFileError> {
+            Ok(attribute(b"secure")?.is_none_or(|secure| secure != "false"))
+        };
+
+        match element.name().as_ref() {
+            b"site-control" => {
+                if let Some(value) = attribute(b"permitted-cross-domain-policies")? {
+                    self.site_control = SiteControl::from_attribute(&value);
+                }
+            }
+            b"allow-access-from" => {
+                if let Some(domain) = attribute(b"domain")? {
+                    let to_ports = attribute(b"to-ports")?.map(|ports| parse_ports(&ports));
+                    self.allow_access_from.push(AllowAccessFrom {
+                        domain,
+                        to_ports,
+                        secure: secure()?,
+                    });
+                }
+            }
+            b"allow-http-request-headers-from" => {
+                if let Some(domain) = attribute(b"domain")?
+                    && let Some(headers) = attribute(b"headers")?
+                {
+                    self.allow_http_request_headers_from
+                        .push(AllowHttpRequestHeadersFrom {
+                            domain,
+                            headers: headers
+                                .split(',')
+                                .map(|header| header.trim().to_ascii_lowercase())
+                                .filter(|header| !header.is_empty())
+                                .collect(),
+                            secure: secure()?,
+                        });
+                }
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// Whether this policy grants a movie of the given domain access.
+    ///
+    /// `secure_policy` tells if the policy file itself was served over HTTPS,
+    /// and `port` is the destination of a socket connection.
+    pub fn allows_access(
+        &self,
+        requester: &SecurityDomain,
+        secure_policy: bool,
+        port: Option<u16>,
+    ) -> bool {
+        self.allow_access_from.iter().any(|entry| {
+            domain_pattern_matches(&entry.domain, requester.host())
+                && (!secure_policy || !entry.secure || requester.is_secure())
+                && match (port, &entry.to_ports) {
+                    (Some(port), Some(ranges)) => ranges.iter().any(|r| r.contains(&port)),
+                    // Socket policies have to list the ports they allow.
+                    (Some(_), None) => false,
+                    (None, _) => true,
+                }
+        })
+    }
+
+    /// Whether this policy allows a movie of the given domain to send the HTTP header.
+    pub fn allows_header(
+        &self,
+        requester: &SecurityDomain,
+        secure_policy: bool,
+        header: &str,
+    ) -> bool {
+        let header = header.to_ascii_lowercase();
+        self.allow_http_request_headers_from.iter().any(|entry| {
+            domain_pattern_matches(&entry.domain, requester.host())
+                && (!secure_policy || !entry.secure || requester.is_secure())
+                && entry
+                    .headers
+                    .iter()
+                    .any(|pattern| match pattern.strip_suffix('*') {
+                        Some(prefix) => header.starts_with(prefix),
+                        None => *pattern == header,
+                    })
+        })
+    }
+}
+
+/// Parse a `to-ports` attribute, like `*`, `507` or `507,516-523`.
+fn parse_ports(value: &str) -> Vec<RangeInclusive<u16>> {
+    value
+        .split(',')
+        .filter_map(|part| {
+            let part = part.trim();
+            if part == "*" {
+                return Some(0..=u16::MAX);
+            }
+            match part.split_once('-') {
+                Some((start, end)) => Some(start.trim().parse().ok()?..=end.trim().parse().ok()?),
+                None => {
+                    let port = part.parse().ok()?;
+                    Some(port..=port)
+                }
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sandbox::SandboxType;
+
+    fn remote(url: &str) -> SecurityDomain {
+        SecurityDomain::new(url, SandboxType::Remote)
+    }
+
+    #[test]
+    fn parse_http_policy() {
+        let policy = PolicyFile::parse(
+            br#"<?xml version="1.0"?>
+            <!DOCTYPE cross-domain-policy SYSTEM "http://www.adobe.com/xml/dtds/cross-domain-policy.dtd">
+            <cross-domain-policy>
+                <site-control permitted-cross-domain-policies="master-only"/>
+                <allow-access-from domain="*.example.com"/>
+                <allow-access-from domain="games.test" secure="false"/>
+                <allow-http-request-headers-from domain="*" headers="SOAPAction, X-Custom-*"/>
+                <unknown-element/>
+            </cross-domain-policy>"#,
+        )
+        .unwrap();
+
+        assert_eq!(policy.site_control, Some(SiteControl::MasterOnly));
+        assert_eq!(policy.allow_access_from.len(), 2);
+        assert!(policy.allow_access_from[0].secure);
+        assert!(!policy.allow_access_from[1].secure);
+
+        assert!(policy.allows_access(&remote("http://www.example.com/a.swf"), false, None));
+        assert!(!policy.allows_access(&remote("http://www.example.org/a.swf"), false, None));
+        // Only secure content is granted access to an HTTPS policy, unless `secure="false"`.
+        assert!(!policy.allows_access(&remote("http://example.com/a.swf"), true, None));
+        assert!(policy.allows_access(&remote("https://example.com/a.swf"), true, None));
+        assert!(policy.allows_access(&remote("http://games.test/a.swf"), true, None));
+        assert!(!policy.allows_access(
+            &SecurityDomain::Local(SandboxType::LocalWithNetwork),
+            false,
+            None
+        ));
+
+        let requester = remote("http://anywhere.test/a.swf");
+        assert!(policy.allows_header(&requester, false, "soapaction"));
+        assert!(policy.allows_header(&requester, false, "X-Custom-Header"));
+        assert!(!policy.allows_header(&requester, false, "Authorization"));
+    }
+
+    #[test]
+    fn parse_socket_policy() {
+        let policy = PolicyFile::parse(
+            b"<cross-domain-policy>\
+                <allow-access-from domain=\"*\" to-ports=\"507,516-523\"/>\
+            </cross-domain-policy>\0trailing garbage",
+        )
+        .unwrap();
+
+        let requester = SecurityDomain::Local(SandboxType::LocalWithNetwork);
+        assert_eq!(policy.site_control, None);
+        assert!(policy.allows_access(&requester, false, Some(507)));
+        assert!(policy.allows_access(&requester, false, Some(520)));
+        assert!(!policy.allows_access(&requester, false, Some(508)));
+        assert!(policy.allows_access(&requester, false, None));
+    }
+
+    #[test]
+    fn socket_policy_requires_ports() {
+        let policy = PolicyFile::parse(
+            b"<cross-domain-policy><allow-access-from domain=\"*\"/></cross-domain-policy>",
+        )
+        .unwrap();
+        assert!(!policy.allows_access(&remote("http://a.test/a.swf"), false, Some(1234)));
+    }
+
+    #[test]
+    fn reject_other_documents() {
+        assert!(matches!(
+            PolicyFile::parse(b"<html><body>Not Found</body></html>"),
+            Err(PolicyFileError::NotAPolicyFile)
+        ));
+        assert!(PolicyFile::parse(b"<cross-domain-policy><allow-access-from").is_err());
+    }
+
+    #[test]
+    fn ports() {
+        assert_eq!(parse_ports("*"), vec![0..=u16::MAX]);
+        assert_eq!(parse_ports("80, 1000-2000,bad"), vec![80..=80, 1000..=2000]);
+    }
+}
@@ -61,3 +61,202 @@ impl SandboxType {
         }
     }
 }
+
+/// The security domain a movie belongs to.
+///
+/// Movies in the same security domain may freely script each other and
+/// load each other's data, while crossing a domain boundary requires
+/// permission, either through `Security.allowDomain` or a policy file.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SecurityDomain {
+    /// Remote content, identified by the protocol and host it was served from.
+    Remote { scheme: String, host: String },
+
+    /// Local content, which shares a single domain per sandbox type.
+    Local(SandboxType),
+
+    /// Remote content without a host, such as from a `data:` URL, identified
+    /// by its whole URL so that it doesn't share its domain with anything else.
+    Opaque(String),
+}
+
+impl SecurityDomain {
+    /// Determine the security domain of content loaded from the given URL
+    /// into the given sandbox.
+    pub fn new(url: &str, sandbox_type: SandboxType) -> Self {
+        if sandbox_type == SandboxType::Remote
+            && let Ok(url) = Url::parse(url)
+            && let Some(host) = url.host_str()
+        {
+            return Self::Remote {
+                scheme: url.scheme().to_owned(),
+                host: host.to_ascii_lowercase(),
+            };
+        }
+
+        match sandbox_type {
+            // A remote movie without a host can't share its domain with anything.
+            SandboxType::Remote => Self::Opaque(url.to_owned()),
+            sandbox_type => Self::Local(sandbox_type),
+        }
+    }
+
+    /// Determine the security domain of a resource referenced by URL,
+    /// as seen by a movie in the given sandbox.
+    pub fn of_resource(url: &Url, requester: SandboxType) -> Self {
+        match url.host_str() {
+            Some(host) if url.scheme() != "file" => Self::Remote {
+                scheme: url.scheme().to_owned(),
+                host: host.to_ascii_lowercase(),
+            },
+            _ => match requester {
+                SandboxType::Remote => Self::Opaque(url.to_string()),
+                sandbox_type => Self::Local(sandbox_type),
+            },
+        }
+    }
+
+    /// The host of a remote domain.
+    pub fn host(&self) -> Option<&str> {
+        match self {
+            Self::Remote { host, .. } => Some(host),
+            Self::Local(_) | Self::Opaque(_) => None,
+        }
+    }
+
+    /// Whether content of this domain was served over HTTPS.
+    pub fn is_secure(&self) -> bool {
+        matches!(self, Self::Remote { scheme, .. } if scheme == "https")
+    }
+
+    /// Whether content of this domain has unrestricted access to
+    /// every other domain.
+    pub fn is_trusted(&self) -> bool {
+        matches!(
+            self,
+            Self::Local(SandboxType::LocalTrusted | SandboxType::Application)
+        )
+    }
+}
+
+/// Whether the domain pattern used by `Security.allowDomain` and policy
+/// files matches the given host.
+///
+/// Patterns are either `*` (any domain, including local content),
+/// an exact host name or IP address, or `*.example.com`, which matches
+/// `example.com` and all of its subdomains.
+pub fn domain_pattern_matches(pattern: &str, host: Option<&str>) -> bool {
+    let pattern = pattern.trim();
+    if pattern == "*" {
+        return true;
+    }
+
+    let Some(host) = host else {
+        return false;
+    };
+
+    // `allowDomain` also accepts URLs, of which only the host is relevant.
+    let pattern = match Url::parse(pattern) {
+        Ok(url) if url.has_host() => url.host_str().unwrap_or_default().to_ascii_lowercase(),
+        _ => pattern.to_ascii_lowercase(),
+    };
+
+    if let Some(suffix) = pattern.strip_prefix("*.") {
+        host == suffix
+            || host
+                .strip_suffix(suffix)
+                .is_some_and(|prefix| prefix.ends_with('.'))
+    } else {
+        host == pattern
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remote_domains() {
+        let domain = SecurityDomain::new("https://Example.com:8080/a.swf", SandboxType::Remote);
+        assert_eq!(
+            domain,
+            SecurityDomain::Remote {
+                scheme: "https".to_owned(),
+                host: "example.com".to_owned()
+            }
+        );
+        assert!(domain.is_secure());
+        assert_eq!(
+            domain,
+            SecurityDomain::new("https://example.com/b/c.swf", SandboxType::Remote)
+        );
+        assert_ne!(
+            domain,
+            SecurityDomain::new("http://example.com/a.swf", SandboxType::Remote)
+        );
+    }
+
+    #[test]
+    fn local_domains() {
+        assert_eq!(
+            SecurityDomain::new("file:///a.swf", SandboxType::LocalWithNetwork),
+            SecurityDomain::Local(SandboxType::LocalWithNetwork)
+        );
+        assert_ne!(
+            SecurityDomain::new("file:///a.swf", SandboxType::LocalWithNetwork),
+            SecurityDomain::new("file:///a.swf", SandboxType::LocalWithFile)
+        );
+        assert!(SecurityDomain::Local(SandboxType::LocalTrusted).is_trusted());
+    }
+
+    #[test]
+    fn hostless_remote_domains() {
+        let domain =
+            SecurityDomain::new("data:application/x-shockwave-flash,", SandboxType::Remote);
+        assert_eq!(domain.host(), None);
+        assert!(!domain.is_trusted());
+        assert_ne!(domain, SecurityDomain::Local(SandboxType::LocalWithFile));
+        assert_ne!(
+            domain,
+            SecurityDomain::new("data:application/x-shockwave-flash,a", SandboxType::Remote)
+        );
+        assert_eq!(
+            domain,
+            SecurityDomain::new("data:application/x-shockwave-flash,", SandboxType::Remote)
+        );
+        assert_ne!(
+            SecurityDomain::of_resource(
+                &Url::parse("file:///secret.txt").unwrap(),
+                SandboxType::Remote
+            ),
+            SecurityDomain::Local(SandboxType::LocalWithFile)
+        );
+    }
+
+    #[test]
+    fn domain_patterns() {
+        assert!(domain_pattern_matches("*", None));
+        assert!(domain_pattern_matches("*", Some("example.com")));
+        assert!(domain_pattern_matches("example.com", Some("example.com")));
+        assert!(domain_pattern_matches("EXAMPLE.com", Some("example.com")));
+        assert!(!domain_pattern_matches(
+            "example.com",
+            Some("www.example.com")
+        ));
+        assert!(!domain_pattern_matches("example.com", None));
+        assert!(domain_pattern_matches("*.example.com", Some("example.com")));
+        assert!(domain_pattern_matches(
+            "*.example.com",
+            Some("a.b.example.com")
+        ));
+        assert!(!domain_pattern_matches(
+            "*.example.com",
+            Some("badexample.com")
+        ));
+        assert!(domain_pattern_matches(
+            "http://www.example.com/game.swf",
+            Some("www.example.com")
+        ));
+        assert!(domain_pattern_matches("127.0.0.1", Some("127.0.0.1")));
+    }
+}
@@ -1,4 +1,4 @@
-use crate::sandbox::SandboxType;
+use crate::sandbox::{SandboxType, SecurityDomain};
 
 use gc_arena::Collect;
 use std::fmt::{Debug, Formatter};
@@ -348,6 +348,11 @@ impl SwfMovie {
     pub fn sandbox_type(&self) -> SandboxType {
         self.sandbox_type
     }
+
+    /// The security domain this movie belongs to.
+    pub fn security_domain(&self) -> SecurityDomain {
+        SecurityDomain::new(&self.url, self.sandbox_type)
+    }
 }
 
 impl Debug for SwfMovie {
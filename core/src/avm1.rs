@@ -14,6 +14,8 @@ mod amf;
 mod callable_value;
 mod clamp;
 mod debug;
+#[cfg(feature = "debugger")]
+pub(crate) mod debugger;
 mod error;
 mod flv;
 mod fscommand;
@@ -25,7 +25,6 @@ use std::fmt;
 use swf::avm1::read::Reader;
 use swf::avm1::types::*;
 use url::form_urlencoded;
-use web_time::Instant;
 
 use super::object_reference::MovieClipReference;
 
@@ -173,6 +172,20 @@ impl<'a> ActivationIdentifier<'a> {
     pub fn depth(&self) -> u16 {
         self.depth
     }
+
+    pub fn name(&self) -> &'a str {
+        self.name
+    }
+
+    pub fn parent(&self) -> Option<&'a ActivationIdentifier<'a>> {
+        self.parent
+    }
+
+    /// The number of function calls and special executions leading to this
+    /// activation, not counting nested blocks such as `with`.
+    pub fn call_depth(&self) -> usize {
+        self.function_count as usize + self.special_count as usize
+    }
 }
 
 /// Represents a single activation of a given AVM1 function or keyframe.
@@ -475,6 +488,11 @@ impl<'a, 'gc> Activation<'a, 'gc> {
             //Executing beyond the end of a function constitutes an implicit return.
             Ok(FrameControl::Return(ReturnType::Implicit))
         } else {
+            #[cfg(feature = "debugger")]
+            if self.context.debugger.is_attached() {
+                crate::avm1::debugger::before_action(self, data, reader);
+            }
+
             let action = reader.read_action()?;
             avm_debug!(
                 self.context.avm1,
@@ -1173,9 +1191,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
             *self.context.time_offset += 1;
         }
 
-        let time = Instant::now()
-            .duration_since(self.context.start_time)
-            .as_millis() as u32;
+        let time = self.context.clock.elapsed().as_millis() as u32;
         let result = time.wrapping_add(*self.context.time_offset);
         self.context.avm1.push(result.into());
         Ok(FrameControl::Continue)
@@ -1311,8 +1327,12 @@ impl<'a, 'gc> Activation<'a, 'gc> {
                         url,
                         NavigationMethod::from_send_vars_method(action.send_vars_method()),
                     );
-                    let future =
-                        crate::loader::load_form_into_object(self.context, target_obj, request);
+                    let future = crate::loader::load_form_into_object(
+                        self.context,
+                        target_obj,
+                        request,
+                        self.base_clip().movie(),
+                    );
                     self.context.navigator.spawn_future(future);
                 }
                 return Ok(FrameControl::Continue);
@@ -2381,6 +2401,12 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         Ok(FrameControl::Continue)
     }
 
+    /// The number of local registers, or `0` if this activation uses the
+    /// global register set.
+    pub fn local_register_count(&self) -> usize {
+        self.local_registers.len()
+    }
+
     /// Retrieve a given register value.
     ///
     /// If a given register does not exist, this function yields
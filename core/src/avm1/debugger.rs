@@ -0,0 +1,299 @@
+//! AVM1 support for the interactive debugger.
+
+use crate::avm1::activation::Activation;
+use crate::avm1::scope::Scope;
+use crate::avm1::{Object, Value};
+use crate::context::UpdateContext;
+use crate::debugger::{
+    Breakpoint, PausedState, ScopeInfo, StackFrameInfo, StopReason, VariableInfo,
+};
+use crate::display_object::{DisplayObject, TDisplayObject};
+use crate::tag_utils::SwfSlice;
+use std::sync::Arc;
+use swf::avm1::read::Reader;
+use web_time::Instant;
+
+/// Variable reference of `this` and the innermost scope.
+const LOCALS_REFERENCE: u32 = 1;
+
+/// Variable reference of the registers.
+const REGISTERS_REFERENCE: u32 = 2;
+
+/// Variable reference of the scope chain.
+const SCOPE_CHAIN_REFERENCE: u32 = 3;
+
+/// Variable references from this one onwards refer to objects.
+const FIRST_OBJECT_REFERENCE: u32 = 4;
+
+/// The size of the global register set, used outside of `DefineFunction2`
+/// functions.
+const GLOBAL_REGISTER_COUNT: usize = 4;
+
+/// Called before running the actions queued by a clip, to check for frame
+/// script breakpoints.
+pub fn before_frame_script(context: &mut UpdateContext<'_>, clip: DisplayObject<'_>) {
+    let debugger = &mut *context.debugger;
+    if !debugger.is_attached() {
+        return;
+    }
+
+    let Some(clip) = clip.as_movie_clip() else {
+        return;
+    };
+
+    let mut path = None;
+    let hit = debugger
+        .breakpoints()
+        .iter()
+        .position(|breakpoint| match breakpoint {
+            Breakpoint::FrameScript {
+                clip: clip_path,
+                frame,
+            } => {
+                *frame == clip.current_frame()
+                    && *clip_path == *path.get_or_insert_with(|| clip.path().to_string())
+            }
+            _ => false,
+        });
+
+    if let Some(hit) = hit {
+        debugger.report_hit(hit);
+        debugger.break_at_next_statement();
+    }
+}
+
+/// Called after running the actions queued by a clip, so that a frame script
+/// breakpoint doesn't carry over to other code when the script was empty.
+pub fn after_frame_script(context: &mut UpdateContext<'_>) {
+    context.debugger.cancel_break();
+}
+
+/// Called before executing every action while a debugger is attached, to
+/// process debugger commands and pause if needed.
+pub fn before_action<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    code: &SwfSlice,
+    reader: &Reader<'_>,
+) {
+    activation.context.debugger.poll();
+    if !activation.context.debugger.is_attached() {
+        return;
+    }
+
+    let offset = reader.get_ref().as_ptr() as usize - code.movie.data().as_ptr() as usize;
+    let depth = activation.id.call_depth();
+    let function = activation
+        .callee
+        .and_then(|callee| callee.as_function())
+        .and_then(|function| function.as_avm1_function());
+
+    let debugger = &*activation.context.debugger;
+    let hit = debugger
+        .breakpoints()
+        .iter()
+        .position(|breakpoint| match breakpoint {
+            Breakpoint::Action {
+                offset: breakpoint_offset,
+            } => *breakpoint_offset as usize == offset,
+            Breakpoint::Method {
+                name,
+                offset: breakpoint_offset,
+            } => function.is_some_and(|function| {
+                let data = function.data();
+                data.start + breakpoint_offset.unwrap_or(0) as usize == offset
+                    && Arc::ptr_eq(&data.movie, &code.movie)
+                    && function
+                        .name()
+                        .is_some_and(|function_name| function_name.to_utf8_lossy() == *name)
+            }),
+            _ => false,
+        });
+
+    // Every action is a statement, as there's no line information.
+    let reason = if hit.is_some() {
+        Some(StopReason::Breakpoint)
+    } else {
+        debugger.step_reason(depth, true)
+    };
+
+    if let Some(reason) = reason {
+        let mut debugger = std::mem::take(activation.context.debugger);
+        if let Some(hit) = hit {
+            debugger.report_hit(hit);
+        }
+        let mut state = Avm1PausedState {
+            activation: &mut *activation,
+            offset,
+            objects: Vec::new(),
+        };
+        debugger.pause(reason, depth, &mut state);
+        *activation.context.debugger = debugger;
+
+        // Time spent paused shouldn't count towards the script timeout.
+        activation.context.update_start = Instant::now();
+    }
+}
+
+struct Avm1PausedState<'a, 'b, 'gc> {
+    activation: &'a mut Activation<'b, 'gc>,
+
+    /// The offset of the action we're paused at.
+    offset: usize,
+
+    /// Objects that have been handed out as variable references.
+    objects: Vec<Object<'gc>>,
+}
+
+impl<'gc> Avm1PausedState<'_, '_, 'gc> {
+    fn variable(&mut self, name: String, value: Value<'gc>) -> VariableInfo {
+        let object = match value {
+            Value::Object(object) => Some(object),
+            Value::MovieClip(reference) => reference.coerce_to_object(self.activation),
+            _ => None,
+        };
+
+        // The type names are the ones returned by `typeof`.
+        let (value, type_name, reference) = match value {
+            Value::Undefined => ("undefined".to_string(), "undefined", 0),
+            Value::Null => ("null".to_string(), "null", 0),
+            Value::Bool(value) => (value.to_string(), "boolean", 0),
+            Value::Number(value) => (value.to_string(), "number", 0),
+            Value::String(value) => (format!("\"{value}\""), "string", 0),
+            Value::Object(_) | Value::MovieClip(_) => match object {
+                Some(object) => {
+                    self.objects.push(object);
+                    let reference = FIRST_OBJECT_REFERENCE + self.objects.len() as u32 - 1;
+                    if let Some(display_object) = object.as_display_object() {
+                        (display_object.path().to_string(), "movieclip", reference)
+                    } else if object.as_function().is_some() {
+                        ("[type Function]".to_string(), "function", reference)
+                    } else {
+                        ("[object Object]".to_string(), "object", reference)
+                    }
+                }
+                // A reference to a clip that doesn't exist anymore.
+                None => ("undefined".to_string(), "undefined", 0),
+            },
+        };
+
+        VariableInfo {
+            name,
+            value,
+            type_name: type_name.to_string(),
+            reference,
+        }
+    }
+
+    fn properties(&mut self, object: Object<'gc>) -> Vec<VariableInfo> {
+        let keys = object.get_keys(self.activation, false);
+        keys.into_iter()
+            .map(|key| {
+                // Inspecting must not run any code, so getters and `__resolve`
+                // are skipped.
+                match object.get_stored_property(self.activation, key) {
+                    Some(value) => self.variable(key.to_string(), value),
+                    None => VariableInfo {
+                        name: key.to_string(),
+                        value: "[getter/setter]".to_string(),
+                        type_name: String::new(),
+                        reference: 0,
+                    },
+                }
+            })
+            .collect()
+    }
+
+    fn locals(&mut self) -> Vec<VariableInfo> {
+        let this = self.activation.this_cell();
+        let mut variables = vec![self.variable("this".to_string(), this)];
+
+        let locals = self.activation.scope().locals_cell();
+        variables.extend(self.properties(locals));
+        variables
+    }
+
+    fn registers(&mut self) -> Vec<VariableInfo> {
+        let count = match self.activation.local_register_count() {
+            0 => GLOBAL_REGISTER_COUNT,
+            count => count,
+        };
+
+        (0..count)
+            .map(|index| {
+                let value = self.activation.current_register(index as u8);
+                self.variable(format!("register{index}"), value)
+            })
+            .collect()
+    }
+
+    fn scope_chain(&mut self) -> Vec<VariableInfo> {
+        // Innermost scope first, down to the global scope.
+        let scopes: Vec<_> = Scope::ancestors(self.activation.scope())
+            .map(|scope| (scope.class(), scope.locals_cell()))
+            .collect();
+
+        scopes
+            .into_iter()
+            .enumerate()
+            .map(|(index, (class, locals))| {
+                self.variable(format!("[{index}] {class:?}"), locals.into())
+            })
+            .collect()
+    }
+}
+
+impl PausedState for Avm1PausedState<'_, '_, '_> {
+    fn stack_trace(&mut self) -> Vec<StackFrameInfo> {
+        let mut frames = Vec::new();
+        let mut id = Some(&self.activation.id);
+        while let Some(current) = id {
+            frames.push(StackFrameInfo {
+                name: current.name().to_string(),
+                file: None,
+                line: 0,
+                // We only know where the innermost frame is.
+                offset: frames.is_empty().then_some(self.offset as u32),
+            });
+            id = current.parent();
+        }
+        frames
+    }
+
+    fn scopes(&mut self, frame: usize) -> Vec<ScopeInfo> {
+        // Only the innermost frame is accessible, the others have been
+        // suspended somewhere up the native stack.
+        if frame != 0 {
+            return Vec::new();
+        }
+
+        vec![
+            ScopeInfo {
+                name: "Locals".to_string(),
+                reference: LOCALS_REFERENCE,
+            },
+            ScopeInfo {
+                name: "Registers".to_string(),
+                reference: REGISTERS_REFERENCE,
+            },
+            ScopeInfo {
+                name: "Scope Chain".to_string(),
+                reference: SCOPE_CHAIN_REFERENCE,
+            },
+        ]
+    }
+
+    fn variables(&mut self, reference: u32) -> Vec<VariableInfo> {
+        match reference {
+            LOCALS_REFERENCE => self.locals(),
+            REGISTERS_REFERENCE => self.registers(),
+            SCOPE_CHAIN_REFERENCE => self.scope_chain(),
+            _ => {
+                let index = reference.saturating_sub(FIRST_OBJECT_REFERENCE) as usize;
+                match self.objects.get(index) {
+                    Some(object) => self.properties(*object),
+                    None => Vec::new(),
+                }
+            }
+        }
+    }
+}
@@ -138,6 +138,10 @@ impl<'gc> Avm1Function<'gc> {
         self.name
     }
 
+    pub fn data(&self) -> &SwfSlice {
+        &self.data
+    }
+
     pub fn scope(&self) -> Gc<'gc, Scope<'gc>> {
         self.scope
     }
@@ -344,6 +348,11 @@ impl<'gc> Avm1Function<'gc> {
 
         let name = if cfg!(feature = "avm_debug") {
             Cow::Owned(self.debug_string_for_call(activation, name, args))
+        } else if cfg!(feature = "debugger")
+            && let Some(name) = self.name
+        {
+            // Show named functions in the debugger's stack traces.
+            Cow::Owned(name.to_string())
         } else {
             Cow::Borrowed("[Anonymous]")
         };
@@ -592,6 +601,14 @@ impl<'gc> FunctionObject<'gc> {
         }
     }
 
+    /// Returns the bytecode function invoked by calling this object, if any.
+    pub fn as_avm1_function(&self) -> Option<Gc<'gc, Avm1Function<'gc>>> {
+        match self.function {
+            Executable::Action(function) => Some(function),
+            _ => None,
+        }
+    }
+
     /// Execute the given code.
     ///
     /// This is fairly low-level; prefer using other call methods if possible.
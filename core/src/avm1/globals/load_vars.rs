@@ -9,6 +9,7 @@ use crate::avm1::property_decl::{DeclContext, PropertyOrder, StaticDeclarations,
 use crate::avm1::{Object, Value};
 use crate::avm1_stub;
 use crate::backend::navigator::{NavigationMethod, Request};
+use crate::display_object::TDisplayObject;
 use crate::string::AvmString;
 use ruffle_macros::istr;
 use ruffle_wstr::WString;
@@ -262,8 +263,12 @@ fn spawn_load_var_fetch<'gc>(
         Request::get(url.to_utf8_lossy().into_owned())
     };
 
-    let future =
-        crate::loader::load_form_into_load_vars(activation.context, loader_object, request);
+    let future = crate::loader::load_form_into_load_vars(
+        activation.context,
+        loader_object,
+        request,
+        activation.base_clip().movie(),
+    );
     activation.context.navigator.spawn_future(future);
 
     // Create hidden properties on object.
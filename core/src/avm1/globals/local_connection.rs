@@ -49,19 +49,18 @@ impl<'gc> LocalConnection<'gc> {
             return false;
         }
 
-        let connection_handle = activation.context.local_connections.connect(
-            &LocalConnections::get_domain(activation.context.root_swf.url()),
-            this,
-            &name,
-        );
+        let domain = LocalConnections::get_domain(activation.context.root_swf.url());
+        let movie = activation.base_clip().movie();
+        let connection_handle =
+            LocalConnections::connect(activation.context, &domain, this, &name, movie);
         let result = connection_handle.is_some();
         *self.0.handle.borrow_mut() = connection_handle;
         result
     }
 
-    pub fn disconnect(self, activation: &mut Activation<'_, 'gc>) {
+    pub fn disconnect(self, context: &mut UpdateContext<'gc>) {
         if let Some(conn_handle) = self.0.handle.take() {
-            activation.context.local_connections.close(conn_handle);
+            LocalConnections::close(context, conn_handle);
         }
     }
 
@@ -93,6 +92,38 @@ impl<'gc> LocalConnection<'gc> {
         Ok(())
     }
 
+    /// Asks the `allowDomain` (or `allowInsecureDomain`) handler of this
+    /// connection whether messages from `sender_domain` are accepted.
+    pub fn allows_domain(
+        context: &mut UpdateContext<'gc>,
+        this: Object<'gc>,
+        sender_domain: &str,
+        is_insecure: bool,
+    ) -> Result<bool, Error<'gc>> {
+        let Some(root_clip) = context.stage.root_clip() else {
+            tracing::warn!("Ignored LocalConnection callback as there's no root movie");
+            return Ok(false);
+        };
+        let mut activation = Activation::from_nothing(
+            context,
+            ActivationIdentifier::root("[LocalConnection allowDomain]"),
+            root_clip,
+        );
+        let handler = if is_insecure {
+            istr!("allowInsecureDomain")
+        } else {
+            istr!("allowDomain")
+        };
+        let sender_domain = AvmString::new_utf8(activation.gc(), sender_domain);
+        let result = this.call_method(
+            handler,
+            &[sender_domain.into()],
+            &mut activation,
+            ExecutionReason::Special,
+        )?;
+        Ok(result.as_bool(activation.swf_version()))
+    }
+
     pub fn run_method(
         context: &mut UpdateContext<'gc>,
         this: Object<'gc>,
@@ -234,8 +265,10 @@ pub fn send<'gc>(
         amf_arguments.push(serialize(activation, *arg));
     }
 
-    activation.context.local_connections.send(
-        &LocalConnections::get_domain(activation.context.root_swf.url()),
+    let url = activation.context.root_swf.url().to_owned();
+    LocalConnections::send(
+        activation.context,
+        &url,
         this,
         *connection_name,
         *method_name,
@@ -250,7 +283,7 @@ pub fn close<'gc>(
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(local_connection) = LocalConnection::cast(this.into()) {
-        local_connection.disconnect(activation);
+        local_connection.disconnect(activation.context);
     }
     Ok(Value::Undefined)
 }
@@ -1632,7 +1632,12 @@ fn load_variables<'gc>(
     let method = NavigationMethod::from_method_str(&method.coerce_to_string(activation)?);
     let target = target.object1_or_bare(activation.gc());
     let request = activation.object_into_request(target, url, method);
-    let future = crate::loader::load_form_into_object(activation.context, target, request);
+    let future = crate::loader::load_form_into_object(
+        activation.context,
+        target,
+        request,
+        activation.base_clip().movie(),
+    );
     activation.context.navigator.spawn_future(future);
 
     Ok(Value::Undefined)
@@ -1,6 +1,7 @@
 use crate::avm1::property_decl::{DeclContext, PropertyOrder, StaticDeclarations, SystemClass};
 use crate::avm1::{Activation, Error, NativeObject, Object, Value};
 use crate::avm1_stub;
+use crate::display_object::TDisplayObject;
 use crate::streams::NetStream;
 
 pub fn constructor<'gc>(
@@ -8,7 +9,8 @@ pub fn constructor<'gc>(
     this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let netstream = NetStream::new_avm1(activation.gc(), this);
+    let movie = activation.base_clip().movie();
+    let netstream = NetStream::new_avm1(activation.gc(), this, movie);
     this.set_native(activation.gc(), NativeObject::NetStream(netstream));
 
     Ok(Value::Undefined)
@@ -51,14 +53,12 @@ pub fn create_class<'gc>(
 }
 
 fn get_buffer_length<'gc>(
-    activation: &mut Activation<'_, 'gc>,
+    _activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let NativeObject::NetStream(ns) = this.native() {
-        avm1_stub!(activation, "NetStream", "bufferLength");
-
-        return Ok(ns.buffer_time().into());
+        return Ok(ns.buffer_length().into());
     }
 
     Ok(Value::Undefined)
@@ -111,7 +111,10 @@ fn play<'gc>(
             Some(v) => Some(v.coerce_to_string(activation)?),
         };
 
-        ns.play(activation.context, name);
+        let requester = activation.base_clip().movie();
+        if let Err(url) = ns.play(activation.context, name, &requester) {
+            tracing::warn!("NetStream.play: {} may not load {url}", requester.url());
+        }
     }
 
     Ok(Value::Undefined)
@@ -162,8 +165,6 @@ fn set_buffer_time<'gc>(
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let NativeObject::NetStream(ns) = this.native() {
-        avm1_stub!(activation, "NetStream", "setBufferTime");
-
         let buffer_time = args
             .get(0)
             .cloned()
@@ -237,10 +238,13 @@ fn on_peer_connect<'gc>(
 
 fn close<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
+    this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm1_stub!(activation, "NetStream", "close");
+    if let NativeObject::NetStream(ns) = this.native() {
+        ns.close(activation.context);
+    }
+
     Ok(Value::Undefined)
 }
 
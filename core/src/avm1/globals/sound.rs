@@ -586,6 +586,7 @@ fn load_sound<'gc>(
             this,
             Request::get(url.to_utf8_lossy().into_owned()),
             is_streaming,
+            activation.base_clip().movie(),
         );
         activation.context.navigator.spawn_future(future);
     }
@@ -4,6 +4,7 @@ use crate::avm1::property_decl::{DeclContext, PropertyOrder, StaticDeclarations,
 use crate::avm1::{Activation, Error, Object, Value};
 use crate::avm1::{ArrayBuilder, ExecutionReason, NativeObject};
 use crate::backend::navigator::Request;
+use crate::display_object::TDisplayObject;
 use crate::html::{CssStream, StyleSheet, TextFormat, transform_dashes_to_camel_case};
 use crate::string::AvmString;
 use gc_arena::{Collect, Gc, Mutation};
@@ -185,7 +186,12 @@ fn load<'gc>(
 
     let request = Request::get(url.to_utf8_lossy().into_owned());
 
-    let future = crate::loader::load_stylesheet(activation.context, this, request);
+    let future = crate::loader::load_stylesheet(
+        activation.context,
+        this,
+        request,
+        activation.base_clip().movie(),
+    );
     activation.context.navigator.spawn_future(future);
 
     Ok(true.into())
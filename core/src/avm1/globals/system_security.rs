@@ -7,6 +7,7 @@ use crate::prelude::TDisplayObject;
 use crate::string::AvmString;
 
 use ruffle_common::sandbox::SandboxType;
+use url::Url;
 
 const OBJECT_DECLS: StaticDeclarations = declare_static_properties! {
     "allowDomain" => method(allow_domain);
@@ -29,25 +30,63 @@ fn allow_domain<'gc>(
     _this: Object<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm1_stub!(activation, "System.security", "allowDomain");
+    allow_domains(activation, args, false)?;
     Ok(Value::Bool(args.get(0).is_some()))
 }
 
 fn allow_insecure_domain<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm1_stub!(activation, "System.security", "allowInsecureDomain");
+    allow_domains(activation, args, true)?;
     Ok(Value::Undefined)
 }
 
+/// Grants the given domains access to the security domain of the calling movie.
+fn allow_domains<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    args: &[Value<'gc>],
+    insecure: bool,
+) -> Result<(), Error<'gc>> {
+    let granter = activation.base_clip().movie().security_domain();
+    for domain in args {
+        let domain = domain
+            .coerce_to_string(activation)?
+            .to_utf8_lossy()
+            .into_owned();
+        activation
+            .context
+            .security
+            .allow_domain(granter.clone(), domain, insecure);
+    }
+    Ok(())
+}
+
 fn load_policy_file<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm1_stub!(activation, "System.security", "loadPolicyFile");
+    let Some(url) = args.get(0) else {
+        return Ok(Value::Undefined);
+    };
+    let url = url
+        .coerce_to_string(activation)?
+        .to_utf8_lossy()
+        .into_owned();
+
+    // Relative URLs are resolved against the calling movie.
+    let movie = activation.base_clip().movie();
+    let url = match Url::parse(movie.url()).and_then(|base| base.join(&url)) {
+        Ok(url) => url.to_string(),
+        Err(_) => url,
+    };
+
+    if let Some(url) = activation.context.security.register_policy_file(&url) {
+        let future = crate::loader::load_policy_file(activation.context, url);
+        activation.context.navigator.spawn_future(future);
+    }
     Ok(Value::Undefined)
 }
 
@@ -8,6 +8,7 @@ use crate::avm1::property_decl::{DeclContext, PropertyOrder, StaticDeclarations,
 use crate::avm1::xml::{ELEMENT_NODE, TEXT_NODE, XmlNode};
 use crate::avm1::{Activation, Attribute, Error, NativeObject, Object, Value};
 use crate::backend::navigator::Request;
+use crate::display_object::TDisplayObject;
 use crate::string::{AvmString, StringContext, WStr, WString};
 use gc_arena::barrier::unlock;
 use gc_arena::lock::Lock;
@@ -577,8 +578,12 @@ fn spawn_xml_fetch<'gc>(
         this.set(loaded_string, false, activation)?;
     }
 
-    let future =
-        crate::loader::load_form_into_load_vars(activation.context, loader_object, request);
+    let future = crate::loader::load_form_into_load_vars(
+        activation.context,
+        loader_object,
+        request,
+        activation.base_clip().movie(),
+    );
     activation.context.navigator.spawn_future(future);
 
     Ok(true.into())
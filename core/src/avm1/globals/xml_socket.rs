@@ -150,11 +150,18 @@ pub fn connect<'gc>(
             .unwrap_or(&Value::Undefined)
             .coerce_to_u16(activation)?;
 
+        let host = host.to_utf8_lossy().into_owned();
+        let movie = activation.base_clip().movie();
+        let policy = activation
+            .context
+            .security
+            .socket_policy_request(&movie, &host);
+
         let UpdateContext {
             sockets, navigator, ..
         } = activation.context;
 
-        sockets.connect_avm1(*navigator, this, host.to_utf8_lossy().into_owned(), port);
+        sockets.connect_avm1(*navigator, this, host, port, policy, movie);
 
         // NOTE: At this point we do not know if the connection will succeed
         //       because connecting is an asynchronous process, so we just return true.
@@ -679,6 +679,7 @@ impl<'gc> Avm1<'gc> {
                 activation
                     .context
                     .avm_warning(&format!("Uncaught exception, {string}"));
+                activation.context.avm_uncaught_error(&string);
 
                 // Continue execution without halting.
                 return;
@@ -49,6 +49,8 @@ mod array;
 pub mod bytearray;
 mod call_stack;
 mod class;
+#[cfg(feature = "debugger")]
+mod debugger;
 mod domain;
 mod dynamic_map;
 mod e4x;
@@ -71,6 +73,7 @@ pub mod property;
 mod property_map;
 mod qname;
 mod regexp;
+mod sampler;
 mod scope;
 pub mod script;
 #[cfg(feature = "known_stubs")]
@@ -104,6 +107,7 @@ pub use crate::avm2::value::Value;
 
 use self::api_version::ApiVersion;
 use self::object::WeakObject;
+use self::sampler::Sampler;
 use self::scope::Scope;
 
 const BROADCAST_WHITELIST: [&[u8]; 4] =
@@ -129,6 +133,9 @@ pub struct Avm2<'gc> {
     /// The current call stack of the player.
     call_stack: GcRefLock<'gc, CallStack<'gc>>,
 
+    /// The state of `flash.sampler`.
+    sampler: GcRefLock<'gc, Sampler<'gc>>,
+
     /// This domain is used exclusively for classes from playerglobals
     playerglobals_domain: Domain<'gc>,
 
@@ -213,6 +220,7 @@ impl<'gc> Avm2<'gc> {
             stack: Stack::new(mc),
             scope_stack: Vec::new(),
             call_stack: GcRefLock::new(mc, CallStack::new().into()),
+            sampler: GcRefLock::new(mc, Sampler::default().into()),
             playerglobals_domain,
             stage_domain,
             system_classes: None,
@@ -398,6 +406,36 @@ impl<'gc> Avm2<'gc> {
         bucket.push(object.downgrade());
     }
 
+    /// Remove the event listeners that code of `movie` added to the stage,
+    /// and all of its listeners for broadcast events.
+    ///
+    /// This is used to stop an unloaded movie from running any more code.
+    pub fn remove_listeners_of_movie(context: &mut UpdateContext<'gc>, movie: &Arc<SwfMovie>) {
+        let defined_in_movie = |handler: FunctionObject<'gc>| {
+            let movie_of_handler = handler.executable().as_method().translation_unit().movie();
+            Arc::ptr_eq(&movie_of_handler, movie)
+        };
+
+        if let Some(stage) = context.stage.object2() {
+            events::retain_event_listeners(context.gc(), stage.into(), |_, handler| {
+                !defined_in_movie(handler)
+            });
+        }
+
+        let listeners: Vec<_> = context
+            .avm2
+            .broadcast_list
+            .values()
+            .flatten()
+            .filter_map(|object| object.upgrade(context.gc()))
+            .collect();
+        for object in listeners {
+            events::retain_event_listeners(context.gc(), object, |event, handler| {
+                !(BROADCAST_WHITELIST.iter().any(|x| *x == &event) && defined_in_movie(handler))
+            });
+        }
+    }
+
     /// Dispatch an event on all objects in the current execution list.
     ///
     /// `on_type` specifies a class or interface constructor whose instances,
@@ -622,18 +660,65 @@ impl<'gc> Avm2<'gc> {
 
     /// Pushes an executable on the call stack
     pub fn push_call(&self, mc: &Mutation<'gc>, method: Method<'gc>) {
-        self.call_stack.borrow_mut(mc).push(method)
+        self.call_stack.borrow_mut(mc).push(method);
+
+        if self.sampler.borrow().is_sampling() {
+            self.sampler.borrow_mut(mc).on_call(
+                &self.call_stack.borrow(),
+                mc.metrics().total_gc_allocation(),
+            );
+        }
     }
 
     /// Pops an executable off the call stack
     pub fn pop_call(&self, mc: &Mutation<'gc>) {
         self.call_stack.borrow_mut(mc).pop();
+        self.sample_stack(mc);
+    }
+
+    /// Records a sample of the call stack if sampling is active, and one
+    /// wasn't recorded recently.
+    pub fn sample_stack(&self, mc: &Mutation<'gc>) {
+        if self.sampler.borrow().is_sampling() {
+            self.sampler.borrow_mut(mc).sample_stack(
+                &self.call_stack.borrow(),
+                mc.metrics().total_gc_allocation(),
+            );
+        }
+    }
+
+    /// Reports the objects collected since the last frame to the sampler, and
+    /// calls the function set by `setSamplerCallback` if its buffer is full.
+    pub fn update_sampler(context: &mut UpdateContext<'gc>) {
+        let sampler = context.avm2.sampler;
+        if !sampler.borrow().is_sampling() {
+            return;
+        }
+
+        let mc = context.gc();
+        sampler
+            .borrow_mut(mc)
+            .record_deletions(mc, &context.avm2.call_stack.borrow());
+
+        let callback = sampler.borrow().pending_callback();
+        if let Some(callback) = callback {
+            let mut activation = Activation::from_nothing(context);
+            let result =
+                Value::from(callback).call(&mut activation, Value::Null, FunctionArgs::empty());
+            if let Err(e) = result {
+                Avm2::uncaught_error(&mut activation, None, e, "Error in sampler callback");
+            }
+        }
     }
 
     pub fn call_stack(&self) -> GcRefLock<'gc, CallStack<'gc>> {
         self.call_stack
     }
 
+    pub fn sampler(&self) -> GcRefLock<'gc, Sampler<'gc>> {
+        self.sampler
+    }
+
     pub fn capture_call_stack(&self) -> CallStack<'gc> {
         self.call_stack.borrow().clone()
     }
@@ -701,6 +786,7 @@ impl<'gc> Avm2<'gc> {
         // This will print the properly formatted error
         let stringified = error.to_string(activation);
         tracing::error!("{}: {}", extra_info, stringified);
+        activation.context.avm_uncaught_error(&stringified);
 
         // TODO: push the error onto `loaderInfo.uncaughtErrorEvents`
     }
@@ -470,6 +470,11 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         bound_superclass_object.call_init(receiver, args, self)
     }
 
+    /// The number of local registers of the method being executed.
+    pub fn num_locals(&self) -> usize {
+        self.num_locals
+    }
+
     /// Retrieve a local register.
     pub fn local_register(&mut self, id: u32) -> Value<'gc> {
         // Verification guarantees that this points to a local register
@@ -657,6 +662,11 @@ impl<'a, 'gc> Activation<'a, 'gc> {
             ip += 1;
             avm_debug!(self.avm2(), "Opcode: {op:?}");
 
+            #[cfg(feature = "debugger")]
+            if self.context.debugger.is_attached() {
+                crate::avm2::debugger::before_op(self, method, ip - 1, op);
+            }
+
             let result = match op {
                 Op::PushDouble { value } => self.op_push_double(*value),
                 Op::PushFalse => self.op_push_false(),
@@ -946,6 +956,9 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         *self.context.actions_since_timeout_check += 1;
         if *self.context.actions_since_timeout_check >= 10000 {
             *self.context.actions_since_timeout_check = 0;
+            // Long running loops wouldn't be sampled otherwise.
+            let mc = self.gc();
+            self.avm2().sample_stack(mc);
             if self.context.update_start.elapsed() >= self.context.max_execution_duration {
                 return Err(
                     "A script in this movie has taken too long to execute and has been terminated."
@@ -1800,7 +1813,10 @@ impl<'a, 'gc> Activation<'a, 'gc> {
     }
 
     fn op_new_object(&mut self, num_args: u32) -> Result<(), Error<'gc>> {
+        let allocated_before = self.gc().metrics().total_gc_allocation();
         let object = ScriptObject::new_object(self.context);
+        let object_class = self.avm2().classes().object;
+        object_class.sample_allocation(self, object, allocated_before);
 
         for _ in 0..num_args {
             let value = self.pop_stack();
@@ -1887,7 +1903,10 @@ impl<'a, 'gc> Activation<'a, 'gc> {
 
     fn op_new_array(&mut self, num_args: u32) -> Result<(), Error<'gc>> {
         let array = self.pop_stack_args(num_args);
+        let allocated_before = self.gc().metrics().total_gc_allocation();
         let array_obj = ArrayObject::from_storage(self.context, array);
+        let array_class = self.avm2().classes().array;
+        array_class.sample_allocation(self, array_obj.into(), allocated_before);
 
         self.push_stack(array_obj);
 
@@ -3008,21 +3027,27 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         Ok(())
     }
 
-    #[cfg(feature = "avm_debug")]
     fn op_debug_file(&mut self, file_name: AvmAtom<'gc>) -> Result<(), Error<'gc>> {
         avm_debug!(self.avm2(), "File: {file_name}");
 
-        Ok(())
-    }
+        let mc = self.gc();
+        self.avm2()
+            .call_stack()
+            .borrow_mut(mc)
+            .set_debug_file(file_name);
 
-    #[cfg(not(feature = "avm_debug"))]
-    fn op_debug_file(&mut self, _file_name: AvmAtom<'gc>) -> Result<(), Error<'gc>> {
         Ok(())
     }
 
     fn op_debug_line(&mut self, line_num: u32) -> Result<(), Error<'gc>> {
         avm_debug!(self.avm2(), "Line: {line_num}");
 
+        let mc = self.gc();
+        self.avm2()
+            .call_stack()
+            .borrow_mut(mc)
+            .set_debug_line(line_num);
+
         Ok(())
     }
 
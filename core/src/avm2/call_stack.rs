@@ -1,12 +1,57 @@
 use crate::avm2::function::display_function;
 use crate::avm2::method::Method;
-use crate::string::WString;
+use crate::string::{AvmAtom, WString};
 use gc_arena::Collect;
 
+#[derive(Collect, Clone, Copy)]
+#[collect(no_drop)]
+pub struct CallNode<'gc> {
+    method: Method<'gc>,
+
+    /// The source file most recently set by a `debugfile` op in this frame.
+    debug_file: Option<AvmAtom<'gc>>,
+
+    /// The line most recently set by a `debugline` op in this frame, or 0.
+    debug_line: u32,
+}
+
+impl<'gc> CallNode<'gc> {
+    pub fn method(&self) -> Method<'gc> {
+        self.method
+    }
+
+    pub fn debug_file(&self) -> Option<AvmAtom<'gc>> {
+        self.debug_file
+    }
+
+    pub fn debug_line(&self) -> u32 {
+        self.debug_line
+    }
+
+    /// Writes the name of this frame's method, as shown in stack traces.
+    pub fn display(&self, output: &mut WString) {
+        let method = self.method;
+        let bound_class = method.bound_class();
+
+        let is_global_init = bound_class.is_some_and(|c| {
+            // If the class is a script `global` class and its instance
+            // initializer is this method, then this is a script initializer
+            c.is_script_traits() && c.instance_init() == Some(method)
+        });
+
+        // Special-case the printed message for script initializers
+        if is_global_init {
+            output.push_utf8("global$init()");
+        } else {
+            display_function(output, method);
+        }
+    }
+}
+
 #[derive(Collect, Clone)]
 #[collect(no_drop)]
 pub struct CallStack<'gc> {
-    stack: Vec<Method<'gc>>,
+    stack: Vec<CallNode<'gc>>,
 }
 
 impl<'gc> CallStack<'gc> {
@@ -15,37 +60,50 @@ impl<'gc> CallStack<'gc> {
     }
 
     pub fn push(&mut self, method: Method<'gc>) {
-        self.stack.push(method)
+        self.stack.push(CallNode {
+            method,
+            debug_file: None,
+            debug_line: 0,
+        })
     }
 
     pub fn pop(&mut self) {
         self.stack.pop();
     }
 
-    pub fn display(&self, output: &mut WString) {
-        for method in self.stack.iter().rev() {
-            output.push_utf8("\n\tat ");
+    /// Records the source file of the currently executing frame.
+    pub fn set_debug_file(&mut self, file: AvmAtom<'gc>) {
+        if let Some(node) = self.stack.last_mut() {
+            node.debug_file = Some(file);
+        }
+    }
 
-            let bound_class = method.bound_class();
+    /// Records the source line of the currently executing frame.
+    pub fn set_debug_line(&mut self, line: u32) {
+        if let Some(node) = self.stack.last_mut() {
+            node.debug_line = line;
+        }
+    }
 
-            let is_global_init = bound_class.is_some_and(|c| {
-                // If the class is a script `global` class and its instance
-                // initializer is this method, then this is a script initializer
-                c.is_script_traits() && c.instance_init() == Some(*method)
-            });
+    /// Iterates over the frames of this call stack, innermost first.
+    pub fn frames(&self) -> impl Iterator<Item = &CallNode<'gc>> {
+        self.stack.iter().rev()
+    }
 
-            // Special-case the printed message for script initializers
-            if is_global_init {
-                output.push_utf8("global$init()");
-            } else {
-                display_function(output, *method);
-            }
+    pub fn display(&self, output: &mut WString) {
+        for node in self.frames() {
+            output.push_utf8("\n\tat ");
+            node.display(output);
         }
     }
 
     pub fn is_empty(&self) -> bool {
         self.stack.is_empty()
     }
+
+    pub fn len(&self) -> usize {
+        self.stack.len()
+    }
 }
 
 impl Default for CallStack<'_> {
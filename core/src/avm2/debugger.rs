@@ -0,0 +1,402 @@
+//! AVM2 support for the interactive debugger.
+
+use crate::avm2::activation::Activation;
+use crate::avm2::dynamic_map::DynamicKey;
+use crate::avm2::method::Method;
+use crate::avm2::object::TObject as _;
+use crate::avm2::op::Op;
+use crate::avm2::property::Property;
+use crate::avm2::value::Value;
+use crate::debugger::{
+    Breakpoint, Debugger, PausedState, ScopeInfo, StackFrameInfo, StopReason, VariableInfo,
+    source_matches,
+};
+use crate::string::{AvmAtom, WStr, WString};
+use web_time::Instant;
+
+/// Variable reference of the locals of the innermost frame.
+const LOCALS_REFERENCE: u32 = 1;
+
+/// Variable reference of the scope chain of the innermost frame.
+const SCOPE_CHAIN_REFERENCE: u32 = 2;
+
+/// Variable references from this one onwards refer to objects.
+const FIRST_OBJECT_REFERENCE: u32 = 3;
+
+/// The breakpoints that apply to a method, stored along with its verified
+/// code.
+///
+/// This is recomputed whenever the breakpoints change.
+#[derive(Default)]
+pub struct BreakpointCache {
+    generation: Option<u32>,
+
+    /// The ops at which a method breakpoint is set, along with the index of
+    /// that breakpoint.
+    ops: Vec<(usize, usize)>,
+
+    /// The `debugfile` that `lines` were computed for.
+    line_file: Option<WString>,
+
+    /// The lines of `line_file` at which a line breakpoint is set, along with
+    /// the index of that breakpoint.
+    lines: Vec<(u32, usize)>,
+}
+
+impl BreakpointCache {
+    fn update(&mut self, debugger: &Debugger, method: Method<'_>) {
+        let generation = debugger.breakpoints_generation();
+        if self.generation == Some(generation) {
+            return;
+        }
+
+        self.generation = Some(generation);
+        self.ops.clear();
+        self.line_file = None;
+        self.lines.clear();
+
+        if debugger.has_method_breakpoints() {
+            let mut name = WString::new();
+            crate::avm2::function::display_function(&mut name, method);
+            let name = name.to_string();
+            let byte_offsets = &method.get_verified_info().byte_offsets;
+
+            for (index, breakpoint) in debugger.breakpoints().iter().enumerate() {
+                let Breakpoint::Method {
+                    name: breakpoint_name,
+                    offset,
+                } = breakpoint
+                else {
+                    continue;
+                };
+
+                if *breakpoint_name != name {
+                    continue;
+                }
+
+                // Offsets that aren't on an op boundary resolve to the next op.
+                let op = match offset {
+                    Some(offset) => byte_offsets.iter().position(|o| *o >= *offset),
+                    None => Some(0),
+                };
+                self.ops.extend(op.map(|op| (op, index)));
+            }
+        }
+    }
+
+    /// Returns the index of the method breakpoint set at the given op.
+    fn method_hit(&mut self, debugger: &Debugger, method: Method<'_>, op: usize) -> Option<usize> {
+        self.update(debugger, method);
+        self.ops
+            .iter()
+            .find(|(breakpoint_op, _)| *breakpoint_op == op)
+            .map(|(_, index)| *index)
+    }
+
+    /// Returns the index of the line breakpoint set at the given line.
+    fn line_hit(
+        &mut self,
+        debugger: &Debugger,
+        method: Method<'_>,
+        debug_file: &WStr,
+        line: u32,
+    ) -> Option<usize> {
+        self.update(debugger, method);
+        if self.line_file.as_deref() != Some(debug_file) {
+            let file = debug_file.to_string();
+            self.lines = debugger
+                .breakpoints()
+                .iter()
+                .enumerate()
+                .filter_map(|(index, breakpoint)| match breakpoint {
+                    Breakpoint::Line {
+                        file: breakpoint_file,
+                        line,
+                    } if source_matches(&file, breakpoint_file) => Some((*line, index)),
+                    _ => None,
+                })
+                .collect();
+            self.line_file = Some(debug_file.to_owned());
+        }
+
+        self.lines
+            .iter()
+            .find(|(breakpoint_line, _)| *breakpoint_line == line)
+            .map(|(_, index)| *index)
+    }
+}
+
+/// Called before executing every op while a debugger is attached, to
+/// process debugger commands and pause if needed.
+pub fn before_op<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    method: Method<'gc>,
+    index: usize,
+    op: &Op<'gc>,
+) {
+    activation.context.debugger.poll();
+    if !activation.context.debugger.is_attached() {
+        return;
+    }
+
+    let call_stack = activation.avm2().call_stack();
+    let (depth, debug_file, debug_line) = {
+        let call_stack = call_stack.borrow();
+        let node = call_stack.frames().next();
+        (
+            call_stack.len(),
+            node.and_then(|node| node.debug_file()),
+            node.map_or(0, |node| node.debug_line()),
+        )
+    };
+
+    // `debugfile` and `debugline` update the location when they run, but
+    // we want to report the line we're about to execute when pausing on them.
+    let (debug_file, debug_line) = match op {
+        Op::DebugFile { file_name } => (Some(*file_name), debug_line),
+        Op::DebugLine { line_num } => (debug_file, *line_num),
+        _ => (debug_file, debug_line),
+    };
+
+    let debugger = &*activation.context.debugger;
+    let mut cache = method.get_verified_info().breakpoints.borrow_mut();
+    let mut hit = cache.method_hit(debugger, method, index);
+    if let (Op::DebugLine { line_num }, Some(debug_file)) = (op, debug_file) {
+        hit = hit.or_else(|| cache.line_hit(debugger, method, debug_file.as_wstr(), *line_num));
+    }
+    drop(cache);
+
+    // Without line information, every op is a statement.
+    let is_statement = matches!(op, Op::DebugLine { .. }) || debug_line == 0;
+    let reason = if hit.is_some() {
+        Some(StopReason::Breakpoint)
+    } else {
+        debugger.step_reason(depth, is_statement)
+    };
+
+    if let Some(reason) = reason {
+        let mut debugger = std::mem::take(activation.context.debugger);
+        if let Some(hit) = hit {
+            debugger.report_hit(hit);
+        }
+        let mut state = Avm2PausedState {
+            activation: &mut *activation,
+            method,
+            index,
+            debug_file,
+            debug_line,
+            objects: Vec::new(),
+        };
+        debugger.pause(reason, depth, &mut state);
+        *activation.context.debugger = debugger;
+
+        // Time spent paused shouldn't count towards the script timeout.
+        activation.context.update_start = Instant::now();
+    }
+}
+
+struct Avm2PausedState<'a, 'b, 'gc> {
+    activation: &'a mut Activation<'b, 'gc>,
+    method: Method<'gc>,
+
+    /// The index of the op we're paused at.
+    index: usize,
+
+    /// The source location of the op we're paused at.
+    debug_file: Option<AvmAtom<'gc>>,
+    debug_line: u32,
+
+    /// Values that have been handed out as variable references.
+    objects: Vec<Value<'gc>>,
+}
+
+impl<'gc> Avm2PausedState<'_, '_, 'gc> {
+    fn variable(&mut self, name: String, value: Value<'gc>) -> VariableInfo {
+        let (value, type_name, reference) = match value.normalize() {
+            Value::Undefined => ("undefined".to_string(), "void".to_string(), 0),
+            Value::Null => ("null".to_string(), "null".to_string(), 0),
+            Value::Bool(value) => (value.to_string(), "Boolean".to_string(), 0),
+            Value::Number(value) => (value.to_string(), "Number".to_string(), 0),
+            Value::Integer(value) => (value.to_string(), "int".to_string(), 0),
+            Value::String(value) => (format!("\"{value}\""), "String".to_string(), 0),
+            Value::Object(object) => {
+                let class_name = object.instance_class().name().local_name().to_string();
+                self.objects.push(value);
+                let reference = FIRST_OBJECT_REFERENCE + self.objects.len() as u32 - 1;
+                (format!("[object {class_name}]"), class_name, reference)
+            }
+        };
+
+        VariableInfo {
+            name,
+            value,
+            type_name,
+            reference,
+        }
+    }
+
+    fn locals(&mut self) -> Vec<VariableInfo> {
+        let method = self.method;
+        let abc = method.abc();
+        let params = &method.method().params;
+
+        let mut variables = Vec::new();
+        for index in 0..self.activation.num_locals() {
+            let param_name = index
+                .checked_sub(1)
+                .and_then(|param| params.get(param))
+                .and_then(|param| param.name)
+                .and_then(|name| name.0.checked_sub(1))
+                .and_then(|name| abc.constant_pool.strings.get(name as usize));
+
+            let name = match (index, param_name) {
+                (0, _) => "this".to_string(),
+                (_, Some(name)) => String::from_utf8_lossy(name).into_owned(),
+                (_, None) => format!("local{index}"),
+            };
+
+            let value = self.activation.local_register(index as u32);
+            variables.push(self.variable(name, value));
+        }
+        variables
+    }
+
+    fn scope_chain(&mut self) -> Vec<VariableInfo> {
+        // Innermost scope first, followed by the outer scopes down to the global scope.
+        let mut values: Vec<Value<'gc>> = self
+            .activation
+            .scope_frame()
+            .iter()
+            .rev()
+            .map(|scope| scope.values())
+            .collect();
+
+        let outer = self.activation.outer();
+        let mut outer_values = Vec::new();
+        while let Some(scope) = outer.get(outer_values.len()) {
+            outer_values.push(scope.values());
+        }
+        values.extend(outer_values.into_iter().rev());
+
+        values
+            .into_iter()
+            .enumerate()
+            .map(|(index, value)| self.variable(format!("[{index}]"), value))
+            .collect()
+    }
+
+    fn children(&mut self, value: Value<'gc>) -> Vec<VariableInfo> {
+        let Some(object) = value.as_object() else {
+            return Vec::new();
+        };
+
+        let mut entries = Vec::new();
+
+        if let Some(storage) = object.as_array_storage() {
+            for (index, element) in storage.iter().enumerate() {
+                entries.push((index.to_string(), element.unwrap_or(Value::Undefined)));
+            }
+        }
+
+        let mut slots: Vec<(String, Value<'gc>)> = object
+            .vtable()
+            .resolved_traits()
+            .iter()
+            .filter_map(|(name, _ns, prop)| match *prop {
+                Property::Slot { slot_id } | Property::ConstSlot { slot_id } => {
+                    Some((name.to_string(), object.get_slot(slot_id)))
+                }
+                _ => None,
+            })
+            .collect();
+        slots.sort_by(|a, b| a.0.cmp(&b.0));
+        entries.extend(slots);
+
+        let dynamic: Vec<(String, Value<'gc>)> = object
+            .base()
+            .values()
+            .iter()
+            .map(|(key, prop)| {
+                let name = match key {
+                    DynamicKey::String(name) => name.to_string(),
+                    DynamicKey::Uint(index) => index.to_string(),
+                    DynamicKey::Object(key) => format!("[object {:p}]", key.as_ptr()),
+                };
+                (name, prop.value)
+            })
+            .collect();
+        entries.extend(dynamic);
+
+        entries
+            .into_iter()
+            .map(|(name, value)| self.variable(name, value))
+            .collect()
+    }
+}
+
+impl PausedState for Avm2PausedState<'_, '_, '_> {
+    fn stack_trace(&mut self) -> Vec<StackFrameInfo> {
+        let offset = self
+            .method
+            .get_verified_info()
+            .byte_offsets
+            .get(self.index)
+            .copied();
+
+        let call_stack = self.activation.avm2().call_stack();
+        let call_stack = call_stack.borrow();
+        call_stack
+            .frames()
+            .enumerate()
+            .map(|(depth, node)| {
+                let mut name = WString::new();
+                node.display(&mut name);
+                let (file, line) = if depth == 0 {
+                    (self.debug_file, self.debug_line)
+                } else {
+                    (node.debug_file(), node.debug_line())
+                };
+                StackFrameInfo {
+                    name: name.to_string(),
+                    file: file.map(|file| file.to_string()),
+                    line,
+                    // We only know where the innermost frame is.
+                    offset: if depth == 0 { offset } else { None },
+                }
+            })
+            .collect()
+    }
+
+    fn scopes(&mut self, frame: usize) -> Vec<ScopeInfo> {
+        // Only the innermost frame is accessible, the others have been
+        // suspended somewhere up the native stack.
+        if frame != 0 {
+            return Vec::new();
+        }
+
+        vec![
+            ScopeInfo {
+                name: "Locals".to_string(),
+                reference: LOCALS_REFERENCE,
+            },
+            ScopeInfo {
+                name: "Scope Chain".to_string(),
+                reference: SCOPE_CHAIN_REFERENCE,
+            },
+        ]
+    }
+
+    fn variables(&mut self, reference: u32) -> Vec<VariableInfo> {
+        match reference {
+            LOCALS_REFERENCE => self.locals(),
+            SCOPE_CHAIN_REFERENCE => self.scope_chain(),
+            _ => {
+                let index = reference.saturating_sub(FIRST_OBJECT_REFERENCE) as usize;
+                match self.objects.get(index) {
+                    Some(value) => self.children(*value),
+                    None => Vec::new(),
+                }
+            }
+        }
+    }
+}
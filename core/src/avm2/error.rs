@@ -1096,6 +1096,51 @@ pub fn make_error_2109<'gc>(
     ))
 }
 
+#[inline(never)]
+#[cold]
+pub fn make_error_2121<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    operation: &str,
+    accessor: &str,
+    target: &str,
+) -> Error<'gc> {
+    make_error!(security_error(
+        activation,
+        error_message!(2121, operation, accessor, target),
+        2121,
+    ))
+}
+
+#[inline(never)]
+#[cold]
+pub fn make_error_2122<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    operation: &str,
+    accessor: &str,
+    target: &str,
+) -> Error<'gc> {
+    make_error!(security_error(
+        activation,
+        error_message!(2122, operation, accessor, target),
+        2122,
+    ))
+}
+
+#[inline(never)]
+#[cold]
+pub fn make_error_2123<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    operation: &str,
+    accessor: &str,
+    target: &str,
+) -> Error<'gc> {
+    make_error!(security_error(
+        activation,
+        error_message!(2123, operation, accessor, target),
+        2123,
+    ))
+}
+
 make_error_fn!(make_error_2126, 2126, argument_error);
 make_error_fn!(make_error_2130, 2130, error);
 
@@ -1106,6 +1151,20 @@ pub fn make_error_2136<'gc>(activation: &mut Activation<'_, 'gc>) -> Error<'gc>
     make_error!(error(activation, error_message!(2136, ""), 2136))
 }
 
+#[inline(never)]
+#[cold]
+pub fn make_error_2148<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    movie_url: &str,
+    resource_url: &str,
+) -> Error<'gc> {
+    make_error!(security_error(
+        activation,
+        error_message!(2148, movie_url, resource_url),
+        2148,
+    ))
+}
+
 make_error_fn!(make_error_2150, 2150, argument_error);
 make_error_fn!(make_error_2162, 2162, argument_error);
 
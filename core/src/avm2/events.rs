@@ -8,7 +8,7 @@ use crate::avm2::object::{EventObject, FunctionObject, Object, TObject as _};
 use crate::display_object::TDisplayObject;
 use crate::string::AvmString;
 use fnv::FnvHashMap;
-use gc_arena::Collect;
+use gc_arena::{Collect, Mutation};
 use std::collections::BTreeMap;
 use std::hash::{Hash, Hasher};
 
@@ -254,6 +254,18 @@ impl<'gc> DispatchList<'gc> {
         }
     }
 
+    /// Remove all event handlers for which `keep` returns `false`.
+    pub fn retain_event_listeners(
+        &mut self,
+        mut keep: impl FnMut(AvmString<'gc>, FunctionObject<'gc>) -> bool,
+    ) {
+        for (event, sheaf) in self.0.iter_mut() {
+            for set in sheaf.values_mut() {
+                set.retain(|handler| keep(*event, handler.handler));
+            }
+        }
+    }
+
     /// Determine if there are any event listeners in this dispatch list.
     pub fn has_event_listener(&self, event: AvmString<'gc>) -> bool {
         if let Some(event_sheaf) = self.get_event(event) {
@@ -333,6 +345,20 @@ impl Hash for EventHandler<'_> {
     }
 }
 
+/// Remove the event listeners of a dispatcher for which `keep` returns `false`.
+pub fn retain_event_listeners<'gc>(
+    mc: &Mutation<'gc>,
+    dispatcher: Object<'gc>,
+    keep: impl FnMut(AvmString<'gc>, FunctionObject<'gc>) -> bool,
+) {
+    if let Some(dispatch_list) = dispatcher.get_slot(slots::DISPATCH_LIST).as_object() {
+        dispatch_list
+            .as_dispatch_mut(mc)
+            .expect("Internal properties should have what I put in them")
+            .retain_event_listeners(keep);
+    }
+}
+
 /// Retrieve the parent of a given `EventDispatcher`.
 ///
 /// `EventDispatcher` does not provide a generic way for it's subclasses to
@@ -7,10 +7,12 @@ use crate::avm2::scope::ScopeChain;
 use crate::avm2::traits::TraitKind;
 use crate::avm2::value::Value;
 use crate::string::WString;
+use crate::telemetry;
 use gc_arena::{Collect, Gc};
 use std::borrow::Cow;
 use std::cell::Cell;
 use std::fmt;
+use web_time::Instant;
 
 /// Represents a bound method.
 #[derive(Clone, Collect)]
@@ -86,6 +88,14 @@ impl<'gc> BoundMethod<'gc> {
         self.method
     }
 
+    pub fn scope(&self) -> ScopeChain<'gc> {
+        self.scope
+    }
+
+    pub fn bound_receiver(&self) -> Option<Value<'gc>> {
+        self.bound_receiver
+    }
+
     pub fn debug_full_name(&self) -> WString {
         let mut output = WString::new();
         display_function(&mut output, self.as_method());
@@ -197,6 +207,7 @@ pub fn exec<'gc>(
     callee: Option<FunctionObject<'gc>>,
 ) -> Result<Value<'gc>, Error<'gc>> {
     let mc = activation.gc();
+    let telemetry_start = activation.context.telemetry.is_enabled().then(Instant::now);
 
     let caller_dxns = activation.default_xml_namespace();
 
@@ -294,6 +305,16 @@ pub fn exec<'gc>(
         }
     };
     activation.context.avm2.pop_call(mc);
+
+    if let Some(start) = telemetry_start {
+        let mut name = WString::new();
+        display_function(&mut name, method);
+        activation
+            .context
+            .telemetry
+            .span(telemetry::METHOD, start, Some(name.to_string().into()));
+    }
+
     ret
 }
 
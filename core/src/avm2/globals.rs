@@ -179,6 +179,10 @@ pub struct SystemClasses<'gc> {
     pub workerdomain: ClassObject<'gc>,
     pub messagechannel: ClassObject<'gc>,
     pub securitydomain: ClassObject<'gc>,
+    pub sample: ClassObject<'gc>,
+    pub newobjectsample: ClassObject<'gc>,
+    pub deleteobjectsample: ClassObject<'gc>,
+    pub stackframe: ClassObject<'gc>,
 }
 
 #[derive(Clone, Collect)]
@@ -358,6 +362,10 @@ impl<'gc> SystemClasses<'gc> {
             workerdomain: object,
             messagechannel: object,
             securitydomain: object,
+            sample: object,
+            newobjectsample: object,
+            deleteobjectsample: object,
+            stackframe: object,
         }
     }
 }
@@ -748,6 +756,10 @@ pub fn init_native_system_classes(activation: &mut Activation<'_, '_>) {
             ("flash.net", "FileReference", filereference),
             ("flash.net", "FileFilter", filefilter),
             ("flash.net", "SharedObject", sharedobject),
+            ("flash.sampler", "Sample", sample),
+            ("flash.sampler", "NewObjectSample", newobjectsample),
+            ("flash.sampler", "DeleteObjectSample", deleteobjectsample),
+            ("flash.sampler", "StackFrame", stackframe),
             ("flash.utils", "ByteArray", bytearray),
             ("flash.utils", "Dictionary", dictionary),
             ("flash.system", "ApplicationDomain", application_domain),
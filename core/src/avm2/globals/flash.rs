@@ -9,6 +9,8 @@ pub mod external;
 pub mod geom;
 pub mod media;
 pub mod net;
+pub mod profiler;
+pub mod sampler;
 pub mod system;
 pub mod text;
 pub mod ui;
@@ -5,17 +5,20 @@ use crate::avm2::activation::Activation;
 use crate::avm2::bytearray::ByteArrayStorage;
 use crate::avm2::error::{
     Error2004Type, make_error_2004, make_error_2005, make_error_2008, make_error_2015,
-    make_error_2027,
+    make_error_2027, make_error_2122, make_error_2123,
 };
 use crate::avm2::filters::FilterAvm2Ext;
 use crate::avm2::globals::flash::display::display_object::object_to_rectangle;
 use crate::avm2::globals::flash::geom::transform::object_to_color_transform;
 use crate::avm2::globals::flash::geom::transform::object_to_matrix;
 use crate::avm2::globals::slots::{
-    flash_geom_point as point_slots, flash_geom_rectangle as rectangle_slots,
+    flash_display_loader as loader_slots, flash_geom_point as point_slots,
+    flash_geom_rectangle as rectangle_slots,
 };
 pub use crate::avm2::object::bitmap_data_allocator;
-use crate::avm2::object::{BitmapDataObject, ByteArrayObject, Object, TObject, VectorObject};
+use crate::avm2::object::{
+    BitmapDataObject, ByteArrayObject, LoaderStream, Object, TObject, VectorObject,
+};
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
 use crate::avm2::vector::VectorStorage;
@@ -24,11 +27,14 @@ use crate::bitmap::bitmap_data::{BitmapData, ChannelOptions, ThresholdOperation}
 use crate::bitmap::bitmap_data::{BitmapDataDrawError, IBitmapDrawable};
 use crate::bitmap::{is_size_valid, operations};
 use crate::character::{Character, CompressedBitmap};
+use crate::display_object::{DisplayObject, TDisplayObject, TDisplayObjectContainer};
 use crate::ecma_conversions::round_to_even;
 use crate::swf::BlendMode;
+use crate::tag_utils::SwfMovie;
 use ruffle_render::filters::Filter;
 use ruffle_render::transform::Transform;
 use std::str::FromStr;
+use std::sync::Arc;
 use swf::{Rectangle, Twips};
 
 // Computes the integer x,y,width,height values from
@@ -907,6 +913,87 @@ pub fn hit_test<'gc>(
     }
 }
 
+/// Throws a `SecurityError` if the caller may not read the pixels of `source`,
+/// because it contains content of another security domain that neither
+/// `Security.allowDomain` nor a policy file grants access to.
+fn check_draw_access<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    operation: &str,
+    source: DisplayObject<'gc>,
+) -> Result<(), Error<'gc>> {
+    if !activation.context.security.is_enabled() {
+        return Ok(());
+    }
+
+    let caller = activation.caller_movie_or_root();
+
+    // The content of a `Loader` may be drawn directly.
+    if let Some(movie) = source.parent().and_then(loader_content_movie) {
+        check_movie_access(activation, operation, &caller, &movie)?;
+    }
+
+    let mut pending = vec![source];
+    while let Some(object) = pending.pop() {
+        check_movie_access(activation, operation, &caller, &object.movie())?;
+        if let Some(movie) = loader_content_movie(object) {
+            check_movie_access(activation, operation, &caller, &movie)?;
+        }
+
+        if let Some(container) = object.as_container() {
+            pending.extend(container.iter_render_list());
+        }
+    }
+
+    Ok(())
+}
+
+fn check_movie_access<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    operation: &str,
+    caller: &SwfMovie,
+    movie: &SwfMovie,
+) -> Result<(), Error<'gc>> {
+    let security = &activation.context.security;
+    if security.can_access(caller, movie) {
+        return Ok(());
+    }
+
+    match security.policy_allows(caller, movie.url()) {
+        Some(true) => Ok(()),
+        Some(false) => Err(make_error_2123(
+            activation,
+            operation,
+            caller.url(),
+            movie.url(),
+        )),
+        None => Err(make_error_2122(
+            activation,
+            operation,
+            caller.url(),
+            movie.url(),
+        )),
+    }
+}
+
+/// The movie that the content of a `Loader` was loaded from.
+///
+/// Loaded images are owned by the movie that loaded them, so this is the
+/// only way to tell where their pixels came from.
+fn loader_content_movie(object: DisplayObject<'_>) -> Option<Arc<SwfMovie>> {
+    let DisplayObject::LoaderDisplay(_) = object else {
+        return None;
+    };
+
+    let loader_info = object
+        .object2()?
+        .get_slot(loader_slots::_CONTENT_LOADER_INFO)
+        .as_object()?;
+    match &*loader_info.as_loader_info_object()?.loader_stream() {
+        LoaderStream::Swf(movie, _) => Some(movie.clone()),
+        LoaderStream::NotYetLoaded(_, _, _) => None,
+    }
+}
+
 /// Implements `BitmapData.draw`
 pub fn draw<'gc>(
     activation: &mut Activation<'_, 'gc>,
@@ -947,6 +1034,7 @@ pub fn draw<'gc>(
         let source = args.get_object(activation, 0, "source")?;
 
         let source = if let Some(source_object) = source.as_display_object() {
+            check_draw_access(activation, "BitmapData.draw", source_object)?;
             IBitmapDrawable::DisplayObject(source_object)
         } else if let Some(source_bitmap) = source.as_bitmap_data() {
             IBitmapDrawable::BitmapData(source_bitmap)
@@ -1020,6 +1108,7 @@ pub fn draw_with_quality<'gc>(
         let source = args.get_object(activation, 0, "source")?;
 
         let source = if let Some(source_object) = source.as_display_object() {
+            check_draw_access(activation, "BitmapData.drawWithQuality", source_object)?;
             IBitmapDrawable::DisplayObject(source_object)
         } else if let Some(source_bitmap) = source.as_bitmap_data() {
             IBitmapDrawable::BitmapData(source_bitmap)
@@ -4,26 +4,34 @@ use indexmap::IndexMap;
 
 use crate::avm2::ClassObject;
 use crate::avm2::activation::Activation;
-use crate::avm2::error::make_error_2007;
+use crate::avm2::error::{make_error_2007, make_error_2148};
+use crate::avm2::events;
 use crate::avm2::globals::flash::display::display_object::initialize_for_allocator;
+use crate::avm2::globals::flash::display::loader_info;
 use crate::avm2::globals::slots::flash_display_loader as loader_slots;
 use crate::avm2::globals::slots::flash_net_url_request as url_request_slots;
 use crate::avm2::globals::slots::flash_net_url_request_header as url_request_header_slots;
+use crate::avm2::globals::slots::flash_system_loader_context as loader_context_slots;
 use crate::avm2::object::LoaderInfoObject;
 use crate::avm2::object::LoaderStream;
 use crate::avm2::object::TObject as _;
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
-use crate::avm2::{Error, Object};
+use crate::avm2::{Avm2, Error, Object};
 use crate::avm2_stub_method;
 use crate::backend::navigator::{NavigationMethod, Request};
+use crate::context::UpdateContext;
 use crate::display_object::LoaderDisplay;
 use crate::display_object::MovieClip;
+use crate::display_object::{DisplayObject, TDisplayObject, TDisplayObjectContainer};
 use crate::loader::LoadManager;
 use crate::loader::MovieLoaderVMData;
+use crate::local_connection::LocalConnections;
+use crate::streams::StreamManager;
 use crate::tag_utils::SwfMovie;
 use ruffle_common::tag_utils::LoadBytesInfo;
 use std::sync::Arc;
+use url::Url;
 
 pub fn loader_allocator<'gc>(
     class: ClassObject<'gc>,
@@ -70,6 +78,36 @@ pub fn load<'gc>(
     let url_request = args.get_object(activation, 0, "request")?;
     let context = args.try_get_object(1);
 
+    let request = request_from_url_request(activation, url_request)?;
+    let caller_movie = activation.caller_movie_or_root();
+    if activation.context.security.is_enabled() {
+        if !activation
+            .context
+            .security
+            .can_load_content(&caller_movie, request.url())
+        {
+            return Err(make_error_2148(
+                activation,
+                caller_movie.url(),
+                request.url(),
+            ));
+        }
+
+        // Fetch the policy files now, so that `BitmapData.draw` can be
+        // allowed to read the loaded content later.
+        let check_policy_file = context.is_some_and(|context| {
+            context
+                .get_slot(loader_context_slots::CHECK_POLICY_FILE)
+                .coerce_to_boolean()
+        });
+        if check_policy_file && let Ok(url) = Url::parse(request.url()) {
+            for policy_url in activation.context.security.missing_policy_files(&url) {
+                let future = crate::loader::load_policy_file(activation.context, policy_url);
+                activation.context.navigator.spawn_future(future);
+            }
+        }
+    }
+
     let loader_info = this
         .get_slot(loader_slots::_CONTENT_LOADER_INFO)
         .as_object()
@@ -108,9 +146,7 @@ pub fn load<'gc>(
         activation.gc(),
     );
 
-    let request = request_from_url_request(activation, url_request)?;
-
-    let loader_url = activation.caller_movie_or_root().url().to_string();
+    let loader_url = caller_movie.url().to_string();
 
     let future = activation.context.load_manager.load_movie_into_clip(
         activation.context.player_handle(),
@@ -301,6 +337,24 @@ pub fn load_bytes<'gc>(
     Ok(Value::Undefined)
 }
 
+/// `Loader.content` getter
+pub fn get_content<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    let loader_info = this
+        .get_slot(loader_slots::_CONTENT_LOADER_INFO)
+        .as_object()
+        .unwrap();
+
+    let loader_info = loader_info.as_loader_info_object().unwrap();
+
+    loader_info::content(activation, loader_info, "Loader.content")
+}
+
 pub fn unload<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Value<'gc>,
@@ -322,3 +376,116 @@ pub fn unload<'gc>(
 
     Ok(Value::Undefined)
 }
+
+pub fn unload_and_stop<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    let loader_info = this
+        .get_slot(loader_slots::_CONTENT_LOADER_INFO)
+        .as_object()
+        .unwrap();
+
+    let loader_info = loader_info.as_loader_info_object().unwrap();
+
+    // The `gc` parameter is ignored, our garbage collector runs on its own.
+    activation
+        .context
+        .load_manager
+        .cancel_avm2_load(loader_info);
+
+    let content = this
+        .as_display_object()
+        .and_then(|loader| loader.as_container())
+        .and_then(|loader| loader.child_by_index(0));
+    if let Some(content) = content {
+        stop_loaded_content(activation.context, content);
+    }
+
+    loader_info.unload(activation.context);
+
+    Ok(Value::Undefined)
+}
+
+pub fn close<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    let loader_info = this
+        .get_slot(loader_slots::_CONTENT_LOADER_INFO)
+        .as_object()
+        .unwrap();
+
+    let loader_info = loader_info.as_loader_info_object().unwrap();
+
+    activation
+        .context
+        .load_manager
+        .cancel_avm2_load(loader_info);
+
+    Ok(Value::Undefined)
+}
+
+/// The events that content stops listening to when it gets unloaded with
+/// `unloadAndStop`.
+const STOPPED_EVENTS: [&[u8]; 6] = [
+    b"enterFrame",
+    b"exitFrame",
+    b"frameConstructed",
+    b"render",
+    b"activate",
+    b"deactivate",
+];
+
+/// Stop everything that loaded content keeps running on its own: its
+/// timelines, sounds, streams, timers, connections and event listeners.
+///
+/// This covers the movies of all content loaded within it as well.
+fn stop_loaded_content<'gc>(context: &mut UpdateContext<'gc>, content: DisplayObject<'gc>) {
+    let mut movies: Vec<Arc<SwfMovie>> = Vec::new();
+    let mut objects = vec![(content, true)];
+    while let Some((object, is_loaded_root)) = objects.pop() {
+        // Objects created by the code of another movie may have been added
+        // to the content, so only the roots of loaded content identify the
+        // movies to stop.
+        if is_loaded_root {
+            let movie = object.movie();
+            if !movies.iter().any(|other| Arc::ptr_eq(other, &movie)) {
+                movies.push(movie);
+            }
+        }
+
+        if let Some(clip) = object.as_movie_clip() {
+            clip.stop(context);
+        }
+
+        if let Some(object) = object.object2() {
+            events::retain_event_listeners(context.gc(), object.into(), |event, _| {
+                !STOPPED_EVENTS.iter().any(|x| *x == &event)
+            });
+        }
+
+        if let Some(container) = object.as_container() {
+            let is_loader = matches!(object, DisplayObject::LoaderDisplay(_));
+            objects.extend(container.iter_render_list().map(|child| (child, is_loader)));
+        }
+    }
+
+    for movie in &movies {
+        context
+            .audio_manager
+            .stop_sounds_of_movie(context.audio, movie);
+        StreamManager::close_streams_of_movie(context, movie);
+        let mc = context.gc();
+        context.timers.remove_all_of_movie(mc, movie);
+        context.sockets.close_all_of_movie(movie);
+        LocalConnections::close_all_of_movie(context, movie);
+        Avm2::remove_listeners_of_movie(context, movie);
+    }
+}
@@ -3,8 +3,10 @@
 use crate::avm2::Error;
 use crate::avm2::activation::Activation;
 use crate::avm2::bytearray::Endian;
-use crate::avm2::error::make_error_2099;
-use crate::avm2::object::{DomainObject, LoaderStream, ScriptObject, TObject as _};
+use crate::avm2::error::{make_error_2099, make_error_2121};
+use crate::avm2::object::{
+    DomainObject, LoaderInfoObject, LoaderStream, ScriptObject, TObject as _,
+};
 use crate::avm2::value::Value;
 use crate::display_object::TDisplayObject;
 use crate::loader::ContentType;
@@ -12,7 +14,6 @@ use crate::string::AvmString;
 use crate::{avm2_stub_getter, avm2_stub_method};
 use std::sync::Arc;
 use swf::{Compression, write_swf};
-use url::Url;
 
 /// `actionScriptVersion` getter
 pub fn get_action_script_version<'gc>(
@@ -130,24 +131,54 @@ pub fn get_bytes_loaded<'gc>(
 
 /// `content` getter
 pub fn get_content<'gc>(
-    _activation: &mut Activation<'_, 'gc>,
+    activation: &mut Activation<'_, 'gc>,
     this: Value<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     let this = this.as_object().unwrap();
 
     let loader_info = this.as_loader_info_object().unwrap();
+    content(activation, loader_info, "LoaderInfo.content")
+}
+
+/// The loaded content of a `LoaderInfo`, as seen by the calling movie.
+///
+/// A loaded movie has to allow being scripted by its caller, otherwise
+/// `operation` throws a `SecurityError`. Loaded images are only protected
+/// from having their pixels read.
+pub fn content<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    loader_info: LoaderInfoObject<'gc>,
+    operation: &str,
+) -> Result<Value<'gc>, Error<'gc>> {
     if !loader_info.expose_content() {
         return Ok(Value::Null);
     }
 
-    let loader_stream = loader_info.loader_stream();
-    match &*loader_stream {
-        LoaderStream::Swf(_, root) | LoaderStream::NotYetLoaded(_, Some(root), _) => {
-            Ok(root.object2_or_null())
+    let (movie, root) = match &*loader_info.loader_stream() {
+        LoaderStream::Swf(movie, root) | LoaderStream::NotYetLoaded(movie, Some(root), _) => {
+            (movie.clone(), *root)
         }
-        _ => Ok(Value::Null),
+        _ => return Ok(Value::Null),
+    };
+
+    let caller_movie = activation.caller_movie_or_root();
+    if movie.is_movie()
+        && activation.context.security.is_enabled()
+        && !activation
+            .context
+            .security
+            .can_access(&caller_movie, &movie)
+    {
+        return Err(make_error_2121(
+            activation,
+            operation,
+            caller_movie.url(),
+            movie.url(),
+        ));
     }
+
+    Ok(root.object2_or_null())
 }
 
 /// `contentType` getter
@@ -236,19 +267,19 @@ pub fn get_same_domain<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     let this = this.as_object().unwrap();
 
-    if let Some(loader_stream) = this.as_loader_info_object().map(|o| o.loader_stream()) {
-        match &*loader_stream {
-            LoaderStream::NotYetLoaded(_, _, _) => {
-                return Err(make_error_2099(activation));
-            }
-            LoaderStream::Swf(_root, _) => {
-                avm2_stub_getter!(activation, "flash.display.LoaderInfo", "sameDomain");
-                return Ok(false.into());
-            }
+    let Some(loader_info) = this.as_loader_info_object() else {
+        return Ok(Value::Undefined);
+    };
+    let loader_stream = loader_info.loader_stream();
+    match &*loader_stream {
+        LoaderStream::NotYetLoaded(_, _, _) => Err(make_error_2099(activation)),
+        LoaderStream::Swf(root, _) => {
+            let same_domain = loader_info.loader().is_none_or(|loader| {
+                loader.display_object().movie().security_domain() == root.security_domain()
+            });
+            Ok(same_domain.into())
         }
     }
-
-    Ok(Value::Undefined)
 }
 
 /// `childAllowsParent` getter
@@ -264,20 +295,13 @@ pub fn get_child_allows_parent<'gc>(
     match &*loader_stream {
         LoaderStream::NotYetLoaded(_, _, _) => Err(make_error_2099(activation)),
         LoaderStream::Swf(root, dobj) => {
-            // TODO: respect allowDomain() and polices.
-            avm2_stub_getter!(activation, "flash.display.LoaderInfo", "childAllowsParent");
-
             if let Some(loader) = loader_info.loader() {
-                let loader = loader.display_object();
-                let parent_movie = loader.movie();
-
-                if let Ok(child_url) = Url::parse(root.url())
-                    && let Ok(parent_url) = Url::parse(parent_movie.url())
-                    && child_url.host() == parent_url.host()
-                {
-                    return Ok(true.into());
-                }
-                Ok(false.into())
+                let parent_movie = loader.display_object().movie();
+                Ok(activation
+                    .context
+                    .security
+                    .can_access(&parent_movie, root)
+                    .into())
             } else {
                 // Only the root movie is LoaderStream::Swf but missing a loader.
                 // In that case, return true.
@@ -304,20 +328,13 @@ pub fn get_parent_allows_child<'gc>(
     match &*loader_stream {
         LoaderStream::NotYetLoaded(_, _, _) => Err(make_error_2099(activation)),
         LoaderStream::Swf(root, dobj) => {
-            // TODO: respect allowDomain() and polices.
-            avm2_stub_getter!(activation, "flash.display.LoaderInfo", "parentAllowsChild");
-
             if let Some(loader) = loader_info.loader() {
-                let loader = loader.display_object();
-                let parent_movie = loader.movie();
-
-                if let Ok(child_url) = Url::parse(root.url())
-                    && let Ok(parent_url) = Url::parse(parent_movie.url())
-                    && child_url.host() == parent_url.host()
-                {
-                    return Ok(true.into());
-                }
-                Ok(false.into())
+                let parent_movie = loader.display_object().movie();
+                Ok(activation
+                    .context
+                    .security
+                    .can_access(root, &parent_movie)
+                    .into())
             } else {
                 // See comment on childAllowsParent
                 assert!(
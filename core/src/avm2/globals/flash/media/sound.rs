@@ -3,7 +3,7 @@
 use crate::avm2::Avm2;
 use crate::avm2::Error;
 use crate::avm2::activation::Activation;
-use crate::avm2::error::{make_error_2037, make_error_2084};
+use crate::avm2::error::{make_error_2037, make_error_2084, make_error_2148};
 use crate::avm2::globals::methods::flash_media_sound as sound_methods;
 use crate::avm2::globals::slots::flash_net_url_request as url_request_slots;
 use crate::avm2::object::{
@@ -181,6 +181,7 @@ pub fn play<'gc>(
             sound_info,
             sound_transform,
             sound_channel,
+            movie: activation.caller_movie_or_root(),
         };
         if sound_object.play(queued_play, activation) {
             return Ok(sound_channel.into());
@@ -246,7 +247,18 @@ pub fn load<'gc>(
 
     let url = url_request
         .get_slot(url_request_slots::_URL)
-        .coerce_to_string(activation)?;
+        .coerce_to_string(activation)?
+        .to_string();
+
+    let caller_movie = activation.caller_movie_or_root();
+    if activation.context.security.is_enabled()
+        && !activation
+            .context
+            .security
+            .can_load_content(&caller_movie, &url)
+    {
+        return Err(make_error_2148(activation, caller_movie.url(), &url));
+    }
 
     // TODO: context parameter currently unused.
     let sound_context = args.try_get_object(1);
@@ -258,7 +270,7 @@ pub fn load<'gc>(
         activation.context,
         this,
         // FIXME: Set options from the `URLRequest`.
-        Request::get(url.to_string()),
+        Request::get(url),
     );
     activation.context.navigator.spawn_future(future);
     this.set_loading_state(SoundLoadingState::Loading);
@@ -56,9 +56,12 @@ pub fn send<'gc>(
     }
 
     if let Some(local_connection) = this.as_local_connection_object() {
-        activation.context.local_connections.send(
-            &LocalConnections::get_domain(activation.context.root_swf.url()),
-            (activation.domain(), local_connection),
+        let url = activation.context.root_swf.url().to_owned();
+        let source = (activation.domain(), local_connection);
+        LocalConnections::send(
+            activation.context,
+            &url,
+            source,
             connection_name,
             method_name,
             amf_arguments,
@@ -108,7 +111,43 @@ pub fn close<'gc>(
             return Err(make_error_2083(activation));
         }
 
-        local_connection.disconnect(activation);
+        local_connection.disconnect(activation.context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `LocalConnection.allowDomain`
+pub fn allow_domain<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    if let Some(local_connection) = this.as_local_connection_object() {
+        for domain in args {
+            let domain = domain.coerce_to_string(activation)?;
+            local_connection.allow_domain(domain.to_string());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `LocalConnection.allowInsecureDomain`
+pub fn allow_insecure_domain<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    if let Some(local_connection) = this.as_local_connection_object() {
+        for domain in args {
+            let domain = domain.coerce_to_string(activation)?;
+            local_connection.allow_insecure_domain(domain.to_string());
+        }
     }
 
     Ok(Value::Undefined)
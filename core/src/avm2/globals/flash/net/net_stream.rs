@@ -1,4 +1,4 @@
-use crate::avm2::error::{Error2004Type, make_error_2004};
+use crate::avm2::error::{Error2004Type, make_error_2004, make_error_2148};
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::{Activation, Error, Value};
 
@@ -32,6 +32,48 @@ pub fn get_bytes_total<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn get_buffer_length<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    if let Some(ns) = this.as_netstream() {
+        return Ok(ns.buffer_length().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_buffer_time<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    if let Some(ns) = this.as_netstream() {
+        return Ok(ns.buffer_time().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_buffer_time<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    if let Some(ns) = this.as_netstream() {
+        ns.set_buffer_time(args.get_f64(0));
+    }
+
+    Ok(Value::Undefined)
+}
+
 pub fn play<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Value<'gc>,
@@ -46,7 +88,24 @@ pub fn play<'gc>(
             .map(|v| v.coerce_to_string(activation))
             .transpose()?;
 
-        ns.play(activation.context, name);
+        let caller_movie = activation.caller_movie_or_root();
+        if let Err(url) = ns.play(activation.context, name, &caller_movie) {
+            return Err(make_error_2148(activation, caller_movie.url(), &url));
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn close<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    if let Some(ns) = this.as_netstream() {
+        ns.close(activation.context);
     }
 
     Ok(Value::Undefined)
@@ -42,11 +42,18 @@ pub fn connect<'gc>(
     let port = args.get_u32(1);
     let port: u16 = port.try_into().map_err(|_| make_error_2003(activation))?;
 
+    let host = host.to_utf8_lossy().into_owned();
+    let movie = activation.caller_movie_or_root();
+    let policy = activation
+        .context
+        .security
+        .socket_policy_request(&movie, &host);
+
     let UpdateContext {
         sockets, navigator, ..
     } = activation.context;
 
-    sockets.connect_avm2(*navigator, socket, host.to_utf8_lossy().into_owned(), port);
+    sockets.connect_avm2(*navigator, socket, host, port, policy, movie);
 
     Ok(Value::Undefined)
 }
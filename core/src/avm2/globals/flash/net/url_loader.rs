@@ -25,11 +25,13 @@ fn spawn_fetch<'gc>(
     url_request: Object<'gc>,
 ) -> Result<Value<'gc>, Error<'gc>> {
     let request = request_from_url_request(activation, url_request)?;
+    let requester = activation.caller_movie_or_root();
 
     let future = crate::loader::load_data_into_url_loader(
         activation.context,
         loader_object.as_script_object().unwrap(),
         request,
+        requester,
     );
     activation.context.navigator.spawn_future(future);
     Ok(Value::Undefined)
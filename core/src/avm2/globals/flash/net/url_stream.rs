@@ -0,0 +1,77 @@
+//! `flash.net.URLStream` native function definitions
+
+use crate::avm2::Error;
+use crate::avm2::activation::Activation;
+use crate::avm2::bytearray::{ByteArrayError, ObjectEncoding};
+use crate::avm2::globals::flash::display::loader::request_from_url_request;
+use crate::avm2::globals::slots::flash_net_url_stream as url_stream_slots;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use flash_lso::amf0::read::AMF0Decoder;
+use flash_lso::amf3::read::AMF3Decoder;
+
+/// Native function definition for `URLStream.beginLoad`
+pub fn begin_load<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    let url_request = args.get_object(activation, 0, "request")?;
+    let request = request_from_url_request(activation, url_request)?;
+    let load_count = this
+        .get_slot(url_stream_slots::_LOAD_COUNT)
+        .coerce_to_u32(activation)?;
+    let requester = activation.caller_movie_or_root();
+
+    let future = crate::loader::load_url_stream(
+        activation.context,
+        this.as_script_object().unwrap(),
+        request,
+        load_count,
+        requester,
+    );
+    activation.context.navigator.spawn_future(future);
+    Ok(Value::Undefined)
+}
+
+/// Native function definition for `URLStream.readObject`
+///
+/// Unlike `ByteArray.readObject`, an object that wasn't completely
+/// downloaded yet is an end of file error, and leaves the stream untouched.
+pub fn read_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    let buffer = this.get_slot(url_stream_slots::_BUFFER).as_object();
+    let Some(buffer) = buffer.as_ref().and_then(|buffer| buffer.as_bytearray()) else {
+        return Ok(Value::Undefined);
+    };
+
+    let bytes = buffer
+        .read_at(buffer.bytes_available(), buffer.position())
+        .map_err(|e| e.to_avm(activation))?;
+    let (bytes_left, amf) = match buffer.object_encoding() {
+        ObjectEncoding::Amf0 => {
+            let mut decoder = AMF0Decoder::default();
+            let Ok((extra, amf)) = decoder.parse_single_element(bytes) else {
+                return Err(ByteArrayError::EndOfFile.to_avm(activation));
+            };
+            (extra.len(), amf)
+        }
+        ObjectEncoding::Amf3 => {
+            let mut decoder = AMF3Decoder::default();
+            let Ok((extra, amf)) = decoder.parse_single_element(bytes) else {
+                return Err(ByteArrayError::EndOfFile.to_avm(activation));
+            };
+            (extra.len(), amf)
+        }
+    };
+
+    buffer.set_position(buffer.len() - bytes_left);
+    crate::avm2::amf::deserialize_value(activation, &amf)
+}
@@ -1,15 +1,11 @@
 package flash.profiler {
     [API("678")] // the docs say 682, that's wrong
     public final class Telemetry {
-        public static function get spanMarker():Number {
-            return 0;
-        }
-        public static function get connected():Boolean {
-            return false;
-        }
+        public static native function get spanMarker():Number;
+        public static native function get connected():Boolean;
 
-        public static function sendMetric(metric:String, value:*):void {}
-        public static function sendSpanMetric(metric:String, startSpanMarker:Number, value:* = null):void {}
+        public static native function sendMetric(metric:String, value:*):void;
+        public static native function sendSpanMetric(metric:String, startSpanMarker:Number, value:* = null):void;
 
         public static function registerCommandHandler(commandName:String, handler:Function):Boolean {
             return false;
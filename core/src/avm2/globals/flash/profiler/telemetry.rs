@@ -0,0 +1,76 @@
+//! `flash.profiler.Telemetry` native methods
+
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, Error, Value};
+use crate::telemetry::TelemetryValue;
+
+/// Implements `Telemetry.spanMarker`
+pub fn get_span_marker<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let telemetry = &activation.context.telemetry;
+    if !telemetry.is_enabled() {
+        return Ok(0.into());
+    }
+    Ok(telemetry.span_marker().into())
+}
+
+/// Implements `Telemetry.connected`
+pub fn get_connected<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation.context.telemetry.is_enabled().into())
+}
+
+/// Implements `Telemetry.sendMetric`
+pub fn send_metric<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if !activation.context.telemetry.is_enabled() {
+        return Ok(Value::Undefined);
+    }
+
+    let metric = args.get_string(activation, 0).to_string();
+    let value = to_telemetry_value(activation, args.get_value(1))?;
+    activation.context.telemetry.value(&metric, value);
+    Ok(Value::Undefined)
+}
+
+/// Implements `Telemetry.sendSpanMetric`
+pub fn send_span_metric<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if !activation.context.telemetry.is_enabled() {
+        return Ok(Value::Undefined);
+    }
+
+    let metric = args.get_string(activation, 0).to_string();
+    let start = activation.context.telemetry.marker_time(args.get_f64(1));
+    let value = match args.get_value(2) {
+        Value::Null | Value::Undefined => None,
+        value => Some(to_telemetry_value(activation, value)?),
+    };
+    activation.context.telemetry.span(&metric, start, value);
+    Ok(Value::Undefined)
+}
+
+fn to_telemetry_value<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    value: Value<'gc>,
+) -> Result<TelemetryValue, Error<'gc>> {
+    Ok(match value {
+        Value::Undefined | Value::Null => TelemetryValue::Null,
+        Value::Bool(value) => TelemetryValue::Bool(value),
+        Value::Integer(value) => TelemetryValue::Int(value),
+        Value::Number(value) => TelemetryValue::Number(value),
+        value => TelemetryValue::String(value.coerce_to_string(activation)?.to_string()),
+    })
+}
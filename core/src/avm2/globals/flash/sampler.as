@@ -1,82 +1,40 @@
 package flash.sampler {
     import __ruffle__.stub_method;
 
-    public function clearSamples():void {
-        stub_method("flash.sampler", "clearSamples");
-    }
+    public native function clearSamples():void;
 
-    public function getGetterInvocationCount(obj:Object, name:QName):Number {
-        stub_method("flash.sampler", "getGetterInvocationCount");
-        return -1;
-    }
+    public native function getGetterInvocationCount(obj:Object, name:QName):Number;
 
-    public function getInvocationCount(obj:Object, name:QName):Number {
-        stub_method("flash.sampler", "getInvocationCount");
-        return -1;
-    }
+    public native function getInvocationCount(obj:Object, name:QName):Number;
 
-    public function getLexicalScopes(fun:Function):Array {
-        stub_method("flash.sampler", "getLexicalScopes");
-        return null;
-    }
+    public native function getLexicalScopes(fun:Function):Array;
 
     public function getMasterString(str:String):String {
         stub_method("flash.sampler", "getMasterString");
         return null;
     }
 
-    public function getMemberNames(obj:Object, instanceNames:Boolean = false):Object {
-        stub_method("flash.sampler", "getMemberNames");
-        return null;
-    }
+    public native function getMemberNames(obj:Object, instanceNames:Boolean = false):Object;
 
-    public function getSampleCount():Number {
-        stub_method("flash.sampler", "getSampleCount");
-        return 0;
-    }
+    public native function getSampleCount():Number;
 
-    public function getSamples():Object {
-        stub_method("flash.sampler", "getSamples");
-        return {};
-    }
+    public native function getSamples():Object;
 
-    public function getSavedThis(fun:Function):Object {
-        stub_method("flash.sampler", "getSavedThis");
-        return undefined;
-    }
+    public native function getSavedThis(fun:Function):Object;
 
-    public function getSetterInvocationCount(obj:Object, name:QName):Number {
-        stub_method("flash.sampler", "getSetterInvocationCount");
-        return -1;
-    }
+    public native function getSetterInvocationCount(obj:Object, name:QName):Number;
 
-    public function getSize(param1:*):Number {
-        stub_method("flash.sampler", "getSize");
-        return 0;
-    }
+    public native function getSize(param1:*):Number;
 
-    public function isGetterSetter(obj:Object, name:QName):Boolean {
-        stub_method("flash.sampler", "isGetterSetter");
-        return false;
-    }
+    public native function isGetterSetter(obj:Object, name:QName):Boolean;
 
-    public function pauseSampling():void {
-        stub_method("flash.sampler", "pauseSampling");
-    }
+    public native function pauseSampling():void;
 
-    public function sampleInternalAllocs(everything:Boolean):void {
-        stub_method("flash.sampler", "sampleInternalAllocs");
-    }
+    public native function sampleInternalAllocs(everything:Boolean):void;
 
-    public function setSamplerCallback(fun:Function):void {
-        stub_method("flash.sampler", "setSamplerCallback");
-    }
+    public native function setSamplerCallback(fun:Function):void;
 
-    public function startSampling():void {
-        stub_method("flash.sampler", "startSampling");
-    }
+    public native function startSampling():void;
 
-    public function stopSampling():void {
-        stub_method("flash.sampler", "stopSampling");
-    }
+    public native function stopSampling():void;
 }
@@ -0,0 +1,454 @@
+//! `flash.sampler` namespace
+
+use crate::avm2::call_stack::CallNode;
+use crate::avm2::dynamic_map::DynamicKey;
+use crate::avm2::globals::slots::flash_sampler_delete_object_sample as delete_sample_slots;
+use crate::avm2::globals::slots::flash_sampler_new_object_sample as new_sample_slots;
+use crate::avm2::globals::slots::flash_sampler_sample as sample_slots;
+use crate::avm2::globals::slots::flash_sampler_stack_frame as stack_frame_slots;
+use crate::avm2::method::Method;
+use crate::avm2::object::{ArrayObject, Object, QNameObject, ScriptObjectData, TObject as _};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::property::Property;
+use crate::avm2::sampler::{Sample, SampleKind};
+use crate::avm2::{Activation, ArrayStorage, Error, Multiname, Value};
+use crate::string::{AvmString, WString};
+use std::mem::size_of;
+
+pub mod new_object_sample;
+
+/// Runs `f` with sampling paused, so that the objects and calls needed to
+/// report samples aren't sampled themselves.
+fn without_sampling<'gc, R>(
+    activation: &mut Activation<'_, 'gc>,
+    f: impl FnOnce(&mut Activation<'_, 'gc>) -> R,
+) -> R {
+    let sampler = activation.avm2().sampler();
+    let was_sampling = sampler.borrow().is_sampling();
+    sampler.borrow_mut(activation.gc()).pause();
+
+    let result = f(activation);
+
+    if was_sampling {
+        sampler.borrow_mut(activation.gc()).start();
+    }
+    result
+}
+
+/// Implements `flash.sampler.startSampling`
+pub fn start_sampling<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let sampler = activation.avm2().sampler();
+    sampler.borrow_mut(activation.gc()).start();
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.sampler.pauseSampling`
+pub fn pause_sampling<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let sampler = activation.avm2().sampler();
+    sampler.borrow_mut(activation.gc()).pause();
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.sampler.stopSampling`
+pub fn stop_sampling<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let sampler = activation.avm2().sampler();
+    sampler.borrow_mut(activation.gc()).stop();
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.sampler.clearSamples`
+pub fn clear_samples<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let sampler = activation.avm2().sampler();
+    sampler.borrow_mut(activation.gc()).clear();
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.sampler.sampleInternalAllocs`
+pub fn sample_internal_allocs<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let everything = args.get_bool(0);
+    let sampler = activation.avm2().sampler();
+    sampler
+        .borrow_mut(activation.gc())
+        .set_sample_internal_allocs(everything);
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.sampler.setSamplerCallback`
+pub fn set_sampler_callback<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let callback = args.try_get_object(0);
+    let sampler = activation.avm2().sampler();
+    sampler.borrow_mut(activation.gc()).set_callback(callback);
+    Ok(Value::Undefined)
+}
+
+/// Records deletions of sampled objects that were collected since the last
+/// time samples were requested.
+fn record_deletions(activation: &mut Activation<'_, '_>) {
+    let mc = activation.gc();
+    let sampler = activation.avm2().sampler();
+    let call_stack = activation.avm2().call_stack();
+    sampler
+        .borrow_mut(mc)
+        .record_deletions(mc, &call_stack.borrow());
+}
+
+/// Implements `flash.sampler.getSampleCount`
+pub fn get_sample_count<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    record_deletions(activation);
+    let sampler = activation.avm2().sampler();
+    let count = sampler.borrow().samples().len();
+    Ok((count as f64).into())
+}
+
+/// Implements `flash.sampler.getSamples`
+pub fn get_samples<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    record_deletions(activation);
+
+    // Constructing the sample objects runs ActionScript, which may touch the
+    // sampler, so don't keep it borrowed.
+    let sampler = activation.avm2().sampler();
+    let samples: Vec<Sample<'gc>> = sampler.borrow().samples().to_vec();
+
+    without_sampling(activation, |activation| {
+        let storage = samples
+            .iter()
+            .map(|sample| sample_to_object(activation, sample))
+            .collect::<Result<ArrayStorage<'gc>, Error<'gc>>>()?;
+        Ok(ArrayObject::from_storage(activation.context, storage).into())
+    })
+}
+
+fn sample_to_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    sample: &Sample<'gc>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let classes = activation.avm2().classes();
+    let class = match sample.kind {
+        SampleKind::Stack => classes.sample,
+        SampleKind::NewObject { .. } => classes.newobjectsample,
+        SampleKind::DeleteObject { .. } => classes.deleteobjectsample,
+    };
+    let object = class.construct(activation, &[])?;
+    let object = object.as_object().expect("Sample is an object");
+
+    match sample.kind {
+        SampleKind::Stack => {}
+        SampleKind::NewObject { id, class, size } => {
+            // Internal allocations don't have a type.
+            let class = class.map_or(Value::Null, Value::from);
+            object.set_slot(new_sample_slots::ID, id.into(), activation)?;
+            object.set_slot(new_sample_slots::TYPE, class, activation)?;
+            object.set_slot(new_sample_slots::_SIZE, (size as f64).into(), activation)?;
+        }
+        SampleKind::DeleteObject { id, size } => {
+            object.set_slot(delete_sample_slots::ID, id.into(), activation)?;
+            object.set_slot(delete_sample_slots::SIZE, (size as f64).into(), activation)?;
+        }
+    }
+
+    let stack = sample
+        .stack
+        .iter()
+        .map(|node| stack_frame_to_object(activation, node))
+        .collect::<Result<ArrayStorage<'gc>, Error<'gc>>>()?;
+    let stack = ArrayObject::from_storage(activation.context, stack);
+
+    object.set_slot(sample_slots::TIME, sample.time.into(), activation)?;
+    object.set_slot(sample_slots::STACK, stack.into(), activation)?;
+    Ok(object.into())
+}
+
+fn stack_frame_to_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    node: &CallNode<'gc>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let mut name = WString::new();
+    node.display(&mut name);
+    // `StackFrame.toString` adds the parentheses itself.
+    let name = name.to_string();
+    let name = name.strip_suffix("()").unwrap_or(&name);
+    let name = AvmString::new_utf8(activation.gc(), name);
+
+    let file: Value<'gc> = match node.debug_file() {
+        Some(file) => AvmString::from(file).into(),
+        None => Value::Null,
+    };
+
+    let frame = activation
+        .avm2()
+        .classes()
+        .stackframe
+        .construct(activation, &[])?;
+    let frame = frame.as_object().expect("StackFrame is an object");
+    frame.set_slot(stack_frame_slots::NAME, name.into(), activation)?;
+    frame.set_slot(stack_frame_slots::FILE, file, activation)?;
+    frame.set_slot(
+        stack_frame_slots::LINE,
+        node.debug_line().into(),
+        activation,
+    )?;
+    // We don't have a debugger session to identify scripts with.
+    frame.set_slot(stack_frame_slots::SCRIPT_ID, 0.into(), activation)?;
+    Ok(frame.into())
+}
+
+/// Implements `flash.sampler.getSize`
+pub fn get_size<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let size = match args.get_value(0) {
+        Value::Object(object) => {
+            let sampler = activation.avm2().sampler();
+            let sampled_size = sampler.borrow().object_size(activation.gc(), object);
+            sampled_size.unwrap_or_else(|| estimate_object_size(object))
+        }
+        Value::String(string) => {
+            let char_size = if string.is_wide() { 2 } else { 1 };
+            size_of::<AvmString>() + string.len() * char_size
+        }
+        _ => size_of::<Value>(),
+    };
+    Ok((size as f64).into())
+}
+
+/// Estimates the size of an object that wasn't allocated while sampling.
+fn estimate_object_size(object: Object<'_>) -> usize {
+    let slots = object.vtable().slot_count() * size_of::<Value>();
+    // Every dynamic property has a key and a value.
+    let dynamic = object.base().values().len() * 2 * size_of::<Value>();
+    size_of::<ScriptObjectData>() + slots + dynamic
+}
+
+/// Implements `flash.sampler.getLexicalScopes`
+pub fn get_lexical_scopes<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(function) = args
+        .try_get_object(0)
+        .and_then(|function| function.as_function_object())
+    else {
+        return Ok(Value::Null);
+    };
+
+    // Outermost scope first.
+    let scope = function.executable().scope();
+    let mut storage = ArrayStorage::new(0);
+    while let Some(scope) = scope.get(storage.length()) {
+        storage.push(scope.values());
+    }
+    Ok(ArrayObject::from_storage(activation.context, storage).into())
+}
+
+/// Implements `flash.sampler.getSavedThis`
+pub fn get_saved_this<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let saved_this = args
+        .try_get_object(0)
+        .and_then(|function| function.as_function_object())
+        .and_then(|function| function.executable().bound_receiver());
+    Ok(saved_this.unwrap_or(Value::Null))
+}
+
+/// Implements `flash.sampler.getMemberNames`
+pub fn get_member_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let object = args.get_object(activation, 0, "obj")?;
+    let instance_names = args.get_bool(1);
+
+    let vtable = match object.as_class_object() {
+        Some(class) if instance_names => class.instance_vtable(),
+        _ => object.vtable(),
+    };
+
+    let mut names: Vec<Multiname<'gc>> = vtable
+        .resolved_traits()
+        .iter()
+        .map(|(name, ns, _)| Multiname::new(ns, name))
+        .collect();
+
+    if !instance_names {
+        let public = activation.avm2().namespaces.public_all();
+        let dynamic_names: Vec<AvmString<'gc>> = object
+            .base()
+            .values()
+            .iter()
+            .filter_map(|(key, _)| match key {
+                DynamicKey::String(name) => Some(*name),
+                DynamicKey::Uint(index) => {
+                    Some(AvmString::new_utf8(activation.gc(), index.to_string()))
+                }
+                DynamicKey::Object(_) => None,
+            })
+            .collect();
+        names.extend(
+            dynamic_names
+                .into_iter()
+                .map(|name| Multiname::new(public, name)),
+        );
+    }
+
+    let storage: ArrayStorage<'gc> = names
+        .into_iter()
+        .map(|name| Value::from(QNameObject::from_name(activation, name)))
+        .collect();
+    Ok(ArrayObject::from_storage(activation.context, storage).into())
+}
+
+#[derive(Clone, Copy)]
+enum Accessor {
+    Method,
+    Getter,
+    Setter,
+}
+
+/// Finds the method that is called when accessing `name` on `object` in the
+/// given way.
+fn find_method<'gc>(
+    object: Object<'gc>,
+    name: Option<QNameObject<'gc>>,
+    accessor: Accessor,
+) -> Option<Method<'gc>> {
+    let Some(name) = name else {
+        // Without a name, we're asked about the constructor of a class.
+        return match accessor {
+            Accessor::Method => object.as_class_object()?.init_method(),
+            Accessor::Getter | Accessor::Setter => None,
+        };
+    };
+
+    let vtable = object.vtable();
+    let disp_id = match (vtable.get_trait(name.name()), accessor) {
+        (Some(Property::Method { disp_id }), Accessor::Method)
+        | (
+            Some(Property::Virtual {
+                get: Some(disp_id), ..
+            }),
+            Accessor::Getter,
+        )
+        | (
+            Some(Property::Virtual {
+                set: Some(disp_id), ..
+            }),
+            Accessor::Setter,
+        ) => disp_id,
+        (None, Accessor::Method) => {
+            // Functions stored in dynamic properties. These are read straight
+            // from storage, so that getters and `Proxy` don't run.
+            let name = name.name();
+            if !name.contains_public_namespace() {
+                return None;
+            }
+            let value = object.get_dynamic_property(name.local_name()?)?;
+            let function = value.as_object()?.as_function_object()?;
+            return Some(function.executable().as_method());
+        }
+        _ => return None,
+    };
+    vtable.get_method(disp_id)
+}
+
+fn invocation_count<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    args: &[Value<'gc>],
+    accessor: Accessor,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let object = args.get_object(activation, 0, "obj")?;
+    let name = args
+        .try_get_object(1)
+        .and_then(|name| name.as_qname_object());
+
+    let Some(method) = find_method(object, name, accessor) else {
+        return Ok((-1).into());
+    };
+
+    let sampler = activation.avm2().sampler();
+    let count = sampler.borrow().invocation_count(method);
+    Ok(count.into())
+}
+
+/// Implements `flash.sampler.getInvocationCount`
+pub fn get_invocation_count<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    invocation_count(activation, args, Accessor::Method)
+}
+
+/// Implements `flash.sampler.getGetterInvocationCount`
+pub fn get_getter_invocation_count<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    invocation_count(activation, args, Accessor::Getter)
+}
+
+/// Implements `flash.sampler.getSetterInvocationCount`
+pub fn get_setter_invocation_count<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    invocation_count(activation, args, Accessor::Setter)
+}
+
+/// Implements `flash.sampler.isGetterSetter`
+pub fn is_getter_setter<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let object = args.get_object(activation, 0, "obj")?;
+    let Some(name) = args
+        .try_get_object(1)
+        .and_then(|name| name.as_qname_object())
+    else {
+        return Ok(false.into());
+    };
+
+    let property = object.vtable().get_trait(name.name());
+    Ok(matches!(property, Some(Property::Virtual { .. })).into())
+}
@@ -1,11 +1,15 @@
 package flash.sampler {
     public final class StackFrame {
+        [Ruffle(NativeAccessible)]
         public const name:String;
 
+        [Ruffle(NativeAccessible)]
         public const file:String;
 
+        [Ruffle(NativeAccessible)]
         public const line:uint;
 
+        [Ruffle(NativeAccessible)]
         public const scriptID:Number;
 
         public function toString():String {
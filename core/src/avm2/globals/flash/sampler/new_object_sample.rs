@@ -0,0 +1,23 @@
+//! `flash.sampler.NewObjectSample` native methods
+
+use crate::avm2::Error;
+use crate::avm2::activation::Activation;
+use crate::avm2::globals::slots::flash_sampler_new_object_sample as slots;
+use crate::avm2::object::TObject;
+use crate::avm2::value::Value;
+
+/// Implements `NewObjectSample.object`
+pub fn get_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    let id = this.get_slot(slots::ID).as_u32();
+
+    // Once the object has been collected, it can't be returned anymore.
+    let sampler = activation.avm2().sampler();
+    let object = sampler.borrow().object(activation.gc(), id);
+    Ok(object.map_or(Value::Undefined, Value::from))
+}
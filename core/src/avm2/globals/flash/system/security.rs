@@ -2,6 +2,7 @@
 
 use crate::avm2::Error;
 use crate::avm2::activation::Activation;
+use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
 use crate::avm2_stub_method;
 use crate::string::AvmString;
@@ -55,27 +56,60 @@ pub fn get_sandbox_type<'gc>(
 pub fn allow_domain<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Value<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.system.Security", "allowDomain");
-    Ok(Value::Undefined)
+    allow_domains(activation, args, false)
 }
 
 pub fn allow_insecure_domain<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Value<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    allow_domains(activation, args, true)
+}
+
+/// Grants the given domains access to the security domain of the caller.
+fn allow_domains<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    args: &[Value<'gc>],
+    insecure: bool,
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.system.Security", "allowInsecureDomain");
+    let granter = activation.caller_movie_or_root().security_domain();
+    for domain in args {
+        let domain = domain
+            .coerce_to_string(activation)?
+            .to_utf8_lossy()
+            .into_owned();
+        activation
+            .context
+            .security
+            .allow_domain(granter.clone(), domain, insecure);
+    }
     Ok(Value::Undefined)
 }
 
 pub fn load_policy_file<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Value<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.system.Security", "loadPolicyFile");
+    let url = args
+        .get_string_non_null(activation, 0, "url")?
+        .to_utf8_lossy()
+        .into_owned();
+
+    // Relative URLs are resolved against the calling movie.
+    let movie = activation.caller_movie_or_root();
+    let url = match Url::parse(movie.url()).and_then(|base| base.join(&url)) {
+        Ok(url) => url.to_string(),
+        Err(_) => url,
+    };
+
+    if let Some(url) = activation.context.security.register_policy_file(&url) {
+        let future = crate::loader::load_policy_file(activation.context, url);
+        activation.context.navigator.spawn_future(future);
+    }
     Ok(Value::Undefined)
 }
 
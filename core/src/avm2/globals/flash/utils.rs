@@ -7,7 +7,6 @@ use crate::avm2::{Activation, Error, Value};
 use crate::string::AvmString;
 use crate::string::WString;
 use std::fmt::Write;
-use web_time::Instant;
 
 pub mod byte_array;
 pub mod dictionary;
@@ -20,10 +19,7 @@ pub fn get_timer<'gc>(
     _this: Value<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    Ok((Instant::now()
-        .duration_since(activation.context.start_time)
-        .as_millis() as u32)
-        .into())
+    Ok((activation.context.clock.elapsed().as_millis() as u32).into())
 }
 
 /// Implements `flash.utils.setInterval`
@@ -39,12 +35,14 @@ pub fn set_interval<'gc>(
     let callback = crate::timer::TimerCallback::Avm2Callback {
         closure,
         params: params.to_vec(),
+        timer_object: None,
     };
 
     Ok(Value::Integer(activation.context.timers.add_timer(
         callback,
         interval as i32,
         false,
+        activation.caller_movie_or_root(),
     )))
 }
 
@@ -72,12 +70,14 @@ pub fn set_timeout<'gc>(
     let callback = crate::timer::TimerCallback::Avm2Callback {
         closure,
         params: params.to_vec(),
+        timer_object: None,
     };
 
     Ok(Value::Integer(activation.context.timers.add_timer(
         callback,
         interval as i32,
         true,
+        activation.caller_movie_or_root(),
     )))
 }
 
@@ -51,9 +51,11 @@ pub fn start<'gc>(
             TimerCallback::Avm2Callback {
                 closure: Some(on_update),
                 params: vec![],
+                timer_object: Some(this),
             },
             delay,
             false,
+            activation.caller_movie_or_root(),
         );
         this.set_slot(slots::_TIMER_ID, id.into(), activation)?;
     }
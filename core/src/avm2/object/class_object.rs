@@ -717,7 +717,9 @@ impl<'gc> ClassObject<'gc> {
         } else {
             let instance_allocator = self.instance_allocator();
 
+            let allocated_before = activation.gc().metrics().total_gc_allocation();
             let instance = instance_allocator(self, activation)?;
+            self.sample_allocation(activation, instance, allocated_before);
 
             self.call_init(instance.into(), arguments, activation)?;
 
@@ -725,6 +727,33 @@ impl<'gc> ClassObject<'gc> {
         }
     }
 
+    /// Reports a new instance of this class to the sampler, if it's active.
+    ///
+    /// The size of the instance is what the GC arena allocated for it since
+    /// `allocated_before`. Anything allocated before that is reported as an
+    /// internal allocation if `sampleInternalAllocs` is enabled.
+    pub fn sample_allocation(
+        self,
+        activation: &mut Activation<'_, 'gc>,
+        instance: Object<'gc>,
+        allocated_before: usize,
+    ) {
+        let sampler = activation.avm2().sampler();
+        if !sampler.borrow().is_sampling() {
+            return;
+        }
+
+        let mc = activation.gc();
+        let allocated_after = mc.metrics().total_gc_allocation().max(allocated_before);
+        let call_stack = activation.avm2().call_stack();
+        sampler.borrow_mut(mc).on_new_object(
+            &call_stack.borrow(),
+            instance,
+            self,
+            allocated_before..allocated_after,
+        );
+    }
+
     pub fn translation_unit(self) -> TranslationUnit<'gc> {
         self.inner_class_definition()
             .translation_unit()
@@ -332,6 +332,27 @@ impl<'gc> EventObject<'gc> {
         )
     }
 
+    pub fn security_error_event(
+        activation: &mut Activation<'_, 'gc>,
+        error_msg: &str,
+        error_code: u32,
+    ) -> EventObject<'gc> {
+        let event_name = istr!("securityError");
+        let error_msg = AvmString::new_utf8(activation.gc(), error_msg);
+        let security_error_event_cls = activation.avm2().classes().securityerrorevent;
+        Self::from_class_and_args(
+            activation,
+            security_error_event_cls,
+            &[
+                event_name.into(),
+                false.into(),
+                false.into(),
+                error_msg.into(),
+                error_code.into(),
+            ],
+        )
+    }
+
     pub fn http_status_event(
         activation: &mut Activation<'_, 'gc>,
         status: u16,
@@ -29,6 +29,8 @@ pub fn local_connection_allocator<'gc>(
             base,
             connection_handle: RefCell::new(None),
             client: Lock::new(None),
+            allowed_domains: RefCell::new(Vec::new()),
+            allowed_insecure_domains: RefCell::new(Vec::new()),
         },
     ));
 
@@ -63,6 +65,14 @@ pub struct LocalConnectionObjectData<'gc> {
     connection_handle: RefCell<Option<LocalConnectionHandle>>,
 
     client: Lock<Option<Object<'gc>>>,
+
+    /// Domains added by `allowDomain`.
+    #[collect(require_static)]
+    allowed_domains: RefCell<Vec<String>>,
+
+    /// Domains added by `allowInsecureDomain`.
+    #[collect(require_static)]
+    allowed_insecure_domains: RefCell<Vec<String>>,
 }
 
 impl<'gc> LocalConnectionObject<'gc> {
@@ -83,11 +93,11 @@ impl<'gc> LocalConnectionObject<'gc> {
             return false;
         }
 
-        let connection_handle = activation.context.local_connections.connect(
-            &LocalConnections::get_domain(activation.context.root_swf.url()),
-            (activation.domain(), self),
-            &name,
-        );
+        let domain = LocalConnections::get_domain(activation.context.root_swf.url());
+        let connection = (activation.domain(), self);
+        let movie = activation.caller_movie_or_root();
+        let connection_handle =
+            LocalConnections::connect(activation.context, &domain, connection, &name, movie);
         let result = connection_handle.is_some();
 
         *self.0.connection_handle.borrow_mut() = connection_handle;
@@ -95,12 +105,29 @@ impl<'gc> LocalConnectionObject<'gc> {
         result
     }
 
-    pub fn disconnect(self, activation: &mut Activation<'_, 'gc>) {
+    pub fn disconnect(self, context: &mut UpdateContext<'gc>) {
         if let Some(conn_handle) = self.0.connection_handle.borrow_mut().take() {
-            activation.context.local_connections.close(conn_handle);
+            LocalConnections::close(context, conn_handle);
         }
     }
 
+    pub fn allow_domain(self, domain: String) {
+        self.0.allowed_domains.borrow_mut().push(domain);
+    }
+
+    pub fn allow_insecure_domain(self, domain: String) {
+        self.0.allowed_insecure_domains.borrow_mut().push(domain);
+    }
+
+    /// Whether messages from `sender_domain` were allowed by `allowDomain` or
+    /// `allowInsecureDomain`. Insecure senders can only be allowed by the latter.
+    pub fn allows_domain(self, sender_domain: &str, is_insecure: bool) -> bool {
+        let matches = |domain: &String| domain == "*" || domain.eq_ignore_ascii_case(sender_domain);
+
+        self.0.allowed_insecure_domains.borrow().iter().any(matches)
+            || (!is_insecure && self.0.allowed_domains.borrow().iter().any(matches))
+    }
+
     pub fn send_status(self, context: &mut UpdateContext<'gc>, status: AvmString<'gc>) {
         let mut activation = Activation::from_nothing(context);
 
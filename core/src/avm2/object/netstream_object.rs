@@ -15,7 +15,7 @@ pub fn netstream_allocator<'gc>(
 ) -> Result<Object<'gc>, Error<'gc>> {
     let base = ScriptObjectData::new(class);
 
-    let ns = NetStream::new_avm2(activation.gc());
+    let ns = NetStream::new_avm2(activation.gc(), activation.caller_movie_or_root());
     let this = NetStreamObject(Gc::new(activation.gc(), NetStreamObjectData { base, ns }));
 
     ns.set_avm2_object(activation.gc(), this);
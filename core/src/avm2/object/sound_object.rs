@@ -11,6 +11,7 @@ use crate::backend::audio::{AudioManager, SoundHandle};
 use crate::context::UpdateContext;
 use crate::display_object::SoundTransform;
 use crate::string::AvmString;
+use crate::tag_utils::SwfMovie;
 use core::fmt;
 use gc_arena::barrier::unlock;
 use gc_arena::{
@@ -21,6 +22,7 @@ use id3::{Tag, TagLike};
 use ruffle_common::utils::HasPrefixField;
 use std::cell::Cell;
 use std::io::Cursor;
+use std::sync::Arc;
 use swf::SoundInfo;
 
 use super::SoundChannelObject;
@@ -113,6 +115,10 @@ pub struct QueuedPlay<'gc> {
     pub sound_transform: Option<SoundTransform>,
     pub sound_channel: SoundChannelObject<'gc>,
     pub position: f64,
+
+    /// The movie that played the sound.
+    #[collect(require_static)]
+    pub movie: Arc<SwfMovie>,
 }
 
 #[derive(Clone, Copy, PartialEq, Eq)]
@@ -293,7 +299,7 @@ fn play_queued<'gc>(
     ) {
         queued.sound_channel.set_sound_instance(context, instance);
 
-        context.attach_avm2_sound_channel(instance, queued.sound_channel);
+        context.attach_avm2_sound_channel(instance, queued.sound_channel, queued.movie);
     }
 
     true
@@ -392,7 +392,9 @@ impl Op<'_> {
     }
 
     pub fn is_nop(&self) -> bool {
-        if cfg!(feature = "avm_debug") {
+        // Debug ops are kept around when they're needed for logging or for
+        // the debugger to track source locations.
+        if cfg!(any(feature = "avm_debug", feature = "debugger")) {
             matches!(self, Op::Nop | Op::CoerceA)
         } else {
             matches!(
@@ -22,6 +22,7 @@ pub fn optimize<'gc>(
     method: Method<'gc>,
     code: &mut Vec<Op<'gc>>,
     method_exceptions: &mut [Exception<'gc>],
+    byte_offsets: &mut Vec<usize>,
     resolved_parameters: &[ResolvedParamConfig<'gc>],
     mut jump_targets: HashSet<usize>,
 ) -> Result<(), Error<'gc>> {
@@ -48,7 +49,7 @@ pub fn optimize<'gc>(
 
     dce::eliminate_dead_code(code_slice, &jump_targets);
 
-    nop_remover::remove_nops(code, method_exceptions);
+    nop_remover::remove_nops(code, method_exceptions, byte_offsets);
 
     Ok(())
 }
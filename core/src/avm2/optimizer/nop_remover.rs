@@ -1,7 +1,11 @@
 use crate::avm2::op::Op;
 use crate::avm2::verify::Exception;
 
-pub fn remove_nops<'gc>(code: &mut Vec<Op<'gc>>, exceptions: &mut [Exception<'gc>]) {
+pub fn remove_nops<'gc>(
+    code: &mut Vec<Op<'gc>>,
+    exceptions: &mut [Exception<'gc>],
+    byte_offsets: &mut Vec<usize>,
+) {
     let mut offset_vec = vec![0; code.len()];
     let mut current_offset = 0;
 
@@ -14,6 +18,7 @@ pub fn remove_nops<'gc>(code: &mut Vec<Op<'gc>>, exceptions: &mut [Exception<'gc
         } else {
             // Shift the ops over the nops
             code[i - current_offset] = code[i];
+            byte_offsets[i - current_offset] = byte_offsets[i];
         }
 
         i += 1;
@@ -22,6 +27,7 @@ pub fn remove_nops<'gc>(code: &mut Vec<Op<'gc>>, exceptions: &mut [Exception<'gc
     // The ops have all been shifted over now, so remove the garbage ops left
     // at the end of the code Vec
     code.truncate(code.len() - current_offset);
+    byte_offsets.truncate(code.len());
 
     // Rewrite jump offsets
     for op in code {
@@ -0,0 +1,294 @@
+//! Memory and CPU sampling, as exposed by `flash.sampler`.
+
+use crate::avm2::call_stack::{CallNode, CallStack};
+use crate::avm2::method::Method;
+use crate::avm2::object::{ClassObject, Object, ObjectPtr, TObject as _, WeakObject};
+use fnv::FnvHashMap;
+use gc_arena::{Collect, Mutation};
+use std::ops::Range;
+use web_time::{Duration, Instant};
+
+/// How often the call stack is sampled while sampling is active.
+const STACK_SAMPLE_INTERVAL: Duration = Duration::from_millis(1);
+
+/// How many samples are buffered before the callback set by
+/// `setSamplerCallback` is called.
+const SAMPLE_BUFFER_SIZE: usize = 4096;
+
+/// A sample recorded by the sampler.
+#[derive(Clone, Collect)]
+#[collect(no_drop)]
+pub struct Sample<'gc> {
+    /// Microseconds since sampling was started.
+    pub time: f64,
+
+    /// The call stack when this sample was recorded, innermost frame first.
+    pub stack: Vec<CallNode<'gc>>,
+
+    pub kind: SampleKind<'gc>,
+}
+
+#[derive(Clone, Collect, Copy)]
+#[collect(no_drop)]
+pub enum SampleKind<'gc> {
+    /// A periodic sample of the call stack.
+    Stack,
+
+    /// An object was allocated, or other memory if `class` is `None`.
+    NewObject {
+        id: u32,
+        class: Option<ClassObject<'gc>>,
+        size: usize,
+    },
+
+    /// A previously allocated object was collected.
+    DeleteObject { id: u32, size: usize },
+}
+
+/// An object allocated while sampling, which hasn't been seen to be
+/// collected yet.
+#[derive(Collect)]
+#[collect(no_drop)]
+struct LiveObject<'gc> {
+    object: WeakObject<'gc>,
+    size: usize,
+}
+
+#[derive(Collect, Default)]
+#[collect(no_drop)]
+pub struct Sampler<'gc> {
+    /// Whether `startSampling` was called and `stopSampling` wasn't since.
+    is_started: bool,
+
+    /// Whether sampling was paused by `pauseSampling`.
+    is_paused: bool,
+
+    /// Whether allocations of memory other than objects are sampled, as set
+    /// by `sampleInternalAllocs`.
+    sample_internal_allocs: bool,
+
+    /// The total amount of GC memory that had been allocated when the last
+    /// allocation was attributed to a sample.
+    allocation_total: usize,
+
+    #[collect(require_static)]
+    start_time: Option<Instant>,
+
+    #[collect(require_static)]
+    last_stack_sample: Option<Instant>,
+
+    samples: Vec<Sample<'gc>>,
+
+    /// The objects allocated while sampling, by the id given in their
+    /// `NewObjectSample`.
+    live_objects: FnvHashMap<u32, LiveObject<'gc>>,
+
+    /// The ids of `live_objects`, by address.
+    #[collect(require_static)]
+    live_object_ids: FnvHashMap<*const ObjectPtr, u32>,
+
+    next_id: u32,
+
+    /// How many times every method was called while sampling.
+    invocation_counts: FnvHashMap<Method<'gc>, u32>,
+
+    /// The function set by `setSamplerCallback`.
+    callback: Option<Object<'gc>>,
+}
+
+impl<'gc> Sampler<'gc> {
+    /// Whether samples are currently being recorded.
+    pub fn is_sampling(&self) -> bool {
+        self.is_started && !self.is_paused
+    }
+
+    /// Starts or resumes sampling.
+    pub fn start(&mut self) {
+        if !self.is_started {
+            self.is_started = true;
+            self.start_time = Some(Instant::now());
+        }
+        self.is_paused = false;
+    }
+
+    pub fn pause(&mut self) {
+        self.is_paused = true;
+    }
+
+    /// Stops sampling, discarding all recorded data.
+    ///
+    /// The settings made by `sampleInternalAllocs` and `setSamplerCallback`
+    /// are kept.
+    pub fn stop(&mut self) {
+        *self = Self {
+            sample_internal_allocs: self.sample_internal_allocs,
+            callback: self.callback,
+            ..Self::default()
+        };
+    }
+
+    /// Discards the recorded samples, but keeps tracking the objects they
+    /// refer to so that their deletion is still reported.
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+
+    pub fn set_sample_internal_allocs(&mut self, sample_internal_allocs: bool) {
+        self.sample_internal_allocs = sample_internal_allocs;
+    }
+
+    pub fn set_callback(&mut self, callback: Option<Object<'gc>>) {
+        self.callback = callback;
+    }
+
+    /// Returns the callback set by `setSamplerCallback` if enough samples
+    /// were buffered that it should be called.
+    pub fn pending_callback(&self) -> Option<Object<'gc>> {
+        self.callback
+            .filter(|_| self.is_sampling() && self.samples.len() >= SAMPLE_BUFFER_SIZE)
+    }
+
+    pub fn samples(&self) -> &[Sample<'gc>] {
+        &self.samples
+    }
+
+    pub fn invocation_count(&self, method: Method<'gc>) -> u32 {
+        self.invocation_counts.get(&method).copied().unwrap_or(0)
+    }
+
+    /// Looks up an object from the id given in its `NewObjectSample`, if it
+    /// hasn't been collected yet.
+    pub fn object(&self, mc: &Mutation<'gc>, id: u32) -> Option<Object<'gc>> {
+        self.live_objects
+            .get(&id)
+            .and_then(|live| live.object.upgrade(mc))
+    }
+
+    /// The size recorded for `object` when it was allocated, if it was
+    /// allocated while sampling.
+    pub fn object_size(&self, mc: &Mutation<'gc>, object: Object<'gc>) -> Option<usize> {
+        let id = self.live_object_ids.get(&object.as_ptr())?;
+        let live = self.live_objects.get(id)?;
+        // The address may have been reused since the sampled object was
+        // collected.
+        live.object.upgrade(mc)?;
+        Some(live.size)
+    }
+
+    fn elapsed(&self) -> f64 {
+        self.start_time
+            .map_or(0.0, |start| start.elapsed().as_micros() as f64)
+    }
+
+    fn record(&mut self, call_stack: &CallStack<'gc>, kind: SampleKind<'gc>) {
+        self.samples.push(Sample {
+            time: self.elapsed(),
+            stack: call_stack.frames().copied().collect(),
+            kind,
+        });
+    }
+
+    fn next_id(&mut self) -> u32 {
+        let id = self.next_id;
+        self.next_id = self.next_id.wrapping_add(1);
+        id
+    }
+
+    /// Records the memory allocated since the last sample as an internal
+    /// allocation, if those are sampled.
+    fn record_internal_allocs(&mut self, call_stack: &CallStack<'gc>, allocation_total: usize) {
+        // The total goes down when memory is freed.
+        let size = allocation_total.saturating_sub(self.allocation_total);
+        self.allocation_total = allocation_total;
+
+        if self.sample_internal_allocs && size > 0 {
+            let id = self.next_id();
+            let kind = SampleKind::NewObject {
+                id,
+                class: None,
+                size,
+            };
+            self.record(call_stack, kind);
+        }
+    }
+
+    /// Records a sample of the call stack, unless one was recorded recently.
+    ///
+    /// This is called whenever the call stack changes, and periodically from
+    /// long running methods.
+    pub fn sample_stack(&mut self, call_stack: &CallStack<'gc>, allocation_total: usize) {
+        let now = Instant::now();
+        if self
+            .last_stack_sample
+            .is_none_or(|last| now.duration_since(last) >= STACK_SAMPLE_INTERVAL)
+        {
+            self.last_stack_sample = Some(now);
+            self.record_internal_allocs(call_stack, allocation_total);
+            self.record(call_stack, SampleKind::Stack);
+        }
+    }
+
+    /// Called after a method was pushed onto the call stack.
+    pub fn on_call(&mut self, call_stack: &CallStack<'gc>, allocation_total: usize) {
+        let Some(node) = call_stack.frames().next() else {
+            return;
+        };
+        *self.invocation_counts.entry(node.method()).or_default() += 1;
+
+        self.sample_stack(call_stack, allocation_total);
+    }
+
+    /// Called after an instance of `class` was allocated, with the total
+    /// amount of GC memory allocated before and after the allocation.
+    pub fn on_new_object(
+        &mut self,
+        call_stack: &CallStack<'gc>,
+        object: Object<'gc>,
+        class: ClassObject<'gc>,
+        allocated: Range<usize>,
+    ) {
+        self.record_internal_allocs(call_stack, allocated.start);
+        self.allocation_total = allocated.end;
+
+        let id = self.next_id();
+        let size = allocated.len();
+        self.live_objects.insert(
+            id,
+            LiveObject {
+                object: object.downgrade(),
+                size,
+            },
+        );
+        self.live_object_ids.insert(object.as_ptr(), id);
+        let kind = SampleKind::NewObject {
+            id,
+            class: Some(class),
+            size,
+        };
+        self.record(call_stack, kind);
+    }
+
+    /// Records a `DeleteObject` sample for every tracked object which has been
+    /// collected since the last call.
+    ///
+    /// This is called every frame, as well as before samples are requested.
+    pub fn record_deletions(&mut self, mc: &Mutation<'gc>, call_stack: &CallStack<'gc>) {
+        let mut deleted = Vec::new();
+        self.live_objects.retain(|id, live| {
+            let is_alive = live.object.upgrade(mc).is_some();
+            if !is_alive {
+                deleted.push((*id, live.object.as_ptr(), live.size));
+            }
+            is_alive
+        });
+
+        // Report deletions in allocation order, like the GC would.
+        deleted.sort_unstable_by_key(|(id, _, _)| *id);
+        for (id, ptr, size) in deleted {
+            if self.live_object_ids.get(&ptr) == Some(&id) {
+                self.live_object_ids.remove(&ptr);
+            }
+            self.record(call_stack, SampleKind::DeleteObject { id, size });
+        }
+    }
+}
@@ -1,4 +1,6 @@
 use crate::avm2::class::Class;
+#[cfg(feature = "debugger")]
+use crate::avm2::debugger::BreakpointCache;
 use crate::avm2::error::{
     Error1014Type, make_error_1011, make_error_1014, make_error_1015, make_error_1019,
     make_error_1020, make_error_1021, make_error_1025, make_error_1026, make_error_1043,
@@ -13,6 +15,8 @@ use crate::string::AvmString;
 
 use gc_arena::{Collect, Gc};
 use std::cell::Cell;
+#[cfg(feature = "debugger")]
+use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
 use swf::avm2::read::Reader;
 use swf::avm2::types::{
@@ -26,6 +30,16 @@ pub struct VerifiedMethodInfo<'gc> {
     pub parsed_code: Vec<Op<'gc>>,
 
     pub exceptions: Vec<Exception<'gc>>,
+
+    /// The byte offset in the original method body of each op in `parsed_code`.
+    #[cfg(feature = "debugger")]
+    #[collect(require_static)]
+    pub byte_offsets: Vec<u32>,
+
+    /// The breakpoints set in this method.
+    #[cfg(feature = "debugger")]
+    #[collect(require_static)]
+    pub breakpoints: RefCell<BreakpointCache>,
 }
 
 #[derive(Collect)]
@@ -515,6 +529,7 @@ pub fn verify_method<'gc>(
         method,
         &mut verified_code,
         &mut new_exceptions,
+        &mut idx_to_byte_offset,
         resolved_param_config,
         jump_targets,
     )?;
@@ -522,6 +537,13 @@ pub fn verify_method<'gc>(
     Ok(VerifiedMethodInfo {
         parsed_code: verified_code,
         exceptions: new_exceptions,
+        #[cfg(feature = "debugger")]
+        byte_offsets: idx_to_byte_offset
+            .into_iter()
+            .map(|offset| offset as u32)
+            .collect(),
+        #[cfg(feature = "debugger")]
+        breakpoints: Default::default(),
     })
 }
 
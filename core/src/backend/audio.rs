@@ -6,11 +6,13 @@ use crate::{
     context::UpdateContext,
     display_object::{self, DisplayObject, MovieClip, TDisplayObject},
     string::AvmString,
+    tag_utils::SwfMovie,
 };
 use gc_arena::Collect;
 pub use ruffle_common::buffer::Substream;
 use ruffle_common::duration::FloatDuration;
 use slotmap::{Key, SlotMap, new_key_type};
+use std::sync::Arc;
 
 #[cfg(feature = "audio")]
 pub mod decoders;
@@ -181,6 +183,12 @@ pub trait AudioBackend: Any {
     /// implementing it.
     fn set_frame_rate(&mut self, _frame_rate: f64) {}
 
+    /// Sets the speed at which sounds play, as a multiple of their normal speed.
+    ///
+    /// This is used when the player is fast-forwarded or slowed down.
+    /// Sounds are resampled, so their pitch changes along with their speed.
+    fn set_playback_rate(&mut self, _playback_rate: f64) {}
+
     /// The approximate interval that this backend updates a sound's position value. `None` if the
     /// value is unknown.
     ///
@@ -471,6 +479,7 @@ impl<'gc> AudioManager<'gc> {
                 sound: Some(sound),
                 instance: handle,
                 display_object,
+                movie: display_object.map(|display_object| display_object.movie()),
                 transform: display_object::SoundTransform::default(),
                 avm1_object,
                 avm2_object: None,
@@ -493,6 +502,7 @@ impl<'gc> AudioManager<'gc> {
         &mut self,
         instance: SoundInstanceHandle,
         avm2_object: SoundChannelObject<'gc>,
+        movie: Arc<SwfMovie>,
     ) {
         if let Some(i) = self
             .sounds
@@ -501,6 +511,7 @@ impl<'gc> AudioManager<'gc> {
         {
             let instance = &mut self.sounds[i];
             instance.avm2_object = Some(avm2_object);
+            instance.movie = Some(movie);
         }
     }
 
@@ -574,11 +585,32 @@ impl<'gc> AudioManager<'gc> {
         });
     }
 
+    /// Stops any sound started by the given movie, either on its timeline or by its scripts.
+    pub fn stop_sounds_of_movie(&mut self, audio: &mut dyn AudioBackend, movie: &Arc<SwfMovie>) {
+        self.sounds.retain(move |sound| {
+            if sound
+                .movie
+                .as_ref()
+                .is_some_and(|other| Arc::ptr_eq(other, movie))
+            {
+                audio.stop_sound(sound.instance);
+                false
+            } else {
+                true
+            }
+        });
+    }
+
     pub fn stop_all_sounds(&mut self, audio: &mut dyn AudioBackend) {
         self.sounds.clear();
         audio.stop_all_sounds();
     }
 
+    /// The number of sounds currently playing.
+    pub fn sound_count(&self) -> usize {
+        self.sounds.len()
+    }
+
     pub fn is_sound_playing(&self, sound: SoundInstanceHandle) -> bool {
         self.sounds.iter().any(|other| other.instance == sound)
     }
@@ -601,6 +633,7 @@ impl<'gc> AudioManager<'gc> {
                 sound: None,
                 instance: handle,
                 display_object: Some(movie_clip.into()),
+                movie: Some(movie_clip.movie()),
                 transform: display_object::SoundTransform::default(),
                 avm1_object: None,
                 avm2_object: None,
@@ -627,6 +660,7 @@ impl<'gc> AudioManager<'gc> {
                 sound: None,
                 instance: handle,
                 display_object: Some(movie_clip.into()),
+                movie: Some(movie_clip.movie()),
                 transform: display_object::SoundTransform::default(),
                 avm1_object: None,
                 avm2_object: None,
@@ -837,6 +871,10 @@ pub struct SoundInstance<'gc> {
     /// Used for volume mixing and `Sound.stop()`.
     display_object: Option<DisplayObject<'gc>>,
 
+    /// The movie that started this sound, if known.
+    #[collect(require_static)]
+    movie: Option<Arc<SwfMovie>>,
+
     /// The local sound transform of this sound.
     ///
     /// Only AVM2 sounds have a local sound transform. In AVM1, sound instances
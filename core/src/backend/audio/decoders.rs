@@ -9,6 +9,8 @@ mod mp3;
 #[cfg(feature = "nellymoser")]
 mod nellymoser;
 mod pcm;
+#[cfg(feature = "speex")]
+mod speex;
 
 pub use adpcm::AdpcmDecoder;
 pub use g711::{G711ALawDecoder, G711MuLawDecoder};
@@ -17,6 +19,8 @@ pub use mp3::{Mp3Decoder, mp3_metadata};
 #[cfg(feature = "nellymoser")]
 pub use nellymoser::NellymoserDecoder;
 pub use pcm::PcmDecoder;
+#[cfg(feature = "speex")]
+pub use speex::SpeexDecoder;
 
 use crate::backend::audio::{SoundStreamInfo, SoundStreamWrapping};
 use crate::tag_utils::{ControlFlow, SwfSlice};
@@ -88,6 +92,8 @@ pub fn make_decoder<R: 'static + Read + Send + Sync>(
         AudioCompression::Nellymoser => {
             Box::new(NellymoserDecoder::new(data, format.sample_rate.into()))
         }
+        #[cfg(feature = "speex")]
+        AudioCompression::Speex => Box::new(SpeexDecoder::new(data)),
         AudioCompression::G711ALawPCM => Box::new(G711ALawDecoder::new(data)),
         AudioCompression::G711MuLawPCM => Box::new(G711MuLawDecoder::new(data)),
         _ => return Err(Error::UnhandledCompression(format.compression)),
@@ -0,0 +1,290 @@
+use super::{Decoder, SeekableDecoder};
+use speex_sys as ffi;
+use std::ffi::{c_int, c_void};
+use std::io::{Cursor, Read};
+
+/// Flash always encodes Speex in wideband mode, regardless of the rate
+/// given in the sound format.
+const SAMPLE_RATE: u16 = 16000;
+
+/// An upper bound on the size of a single wideband frame.
+/// The largest mode (42.2 kbps) uses 106 bytes per 20ms frame.
+const MAX_FRAME_BYTES: usize = 128;
+
+/// Decodes wideband Speex, as recorded by `Microphone` and written into
+/// SWF and FLV files by Flash.
+///
+/// Speex frames are not byte-aligned, so the audio data is treated as a single
+/// continuous bitstream. Terminators that pad a packet to a byte boundary are skipped.
+pub struct SpeexDecoder<R: Read> {
+    inner: R,
+    state: SpeexState,
+    frame: Vec<i16>,
+    frame_pos: usize,
+}
+
+impl<R: Read> SpeexDecoder<R> {
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            state: SpeexState::new(),
+            frame: Vec::new(),
+            frame_pos: 0,
+        }
+    }
+
+    /// Makes sure that a full frame is buffered, unless the reader is out of data.
+    fn fill_buffer(&mut self) {
+        let mut buf = [0u8; MAX_FRAME_BYTES];
+        while self.state.bits_remaining() < MAX_FRAME_BYTES * 8 {
+            match self.inner.read(&mut buf) {
+                Ok(0) | Err(_) => break,
+                Ok(len) => self.state.append(&buf[..len]),
+            }
+        }
+    }
+
+    fn decode_frame(&mut self) -> Option<()> {
+        loop {
+            self.fill_buffer();
+            let bits_remaining = self.state.bits_remaining();
+            if bits_remaining == 0 {
+                return None;
+            }
+            match self.state.decode(&mut self.frame) {
+                0 => {
+                    self.frame_pos = 0;
+                    return Some(());
+                }
+                -1 => {
+                    // Terminator or not enough bits left for a frame.
+                    if !self.state.skip_to_byte_boundary() {
+                        return None;
+                    }
+                    // Nothing could be decoded or skipped, so trying again
+                    // would never get anywhere.
+                    if self.state.bits_remaining() == bits_remaining {
+                        tracing::warn!("SpeexDecoder: Undecodable data");
+                        return None;
+                    }
+                }
+                _ => {
+                    tracing::warn!("SpeexDecoder: Corrupt stream");
+                    return None;
+                }
+            }
+        }
+    }
+}
+
+impl<R: Read> Iterator for SpeexDecoder<R> {
+    type Item = [i16; 2];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.frame_pos >= self.frame.len() {
+            self.decode_frame()?;
+        }
+        let sample = self.frame[self.frame_pos];
+        self.frame_pos += 1;
+        Some([sample, sample])
+    }
+}
+
+impl<R: Read + Send + Sync> Decoder for SpeexDecoder<R> {
+    #[inline]
+    fn num_channels(&self) -> u8 {
+        1
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> u16 {
+        SAMPLE_RATE
+    }
+}
+
+impl<R: AsRef<[u8]> + Send + Sync> SeekableDecoder for SpeexDecoder<Cursor<R>> {
+    #[inline]
+    fn reset(&mut self) {
+        self.inner.set_position(0);
+        self.state.reset();
+        self.frame.clear();
+        self.frame_pos = 0;
+    }
+}
+
+/// Owns a libspeex wideband decoder and its bit buffer.
+struct SpeexState {
+    decoder: *mut c_void,
+    bits: Box<ffi::SpeexBits>,
+    frame_size: usize,
+}
+
+// SAFETY: The decoder state is owned exclusively by this struct and is only
+// accessed through `&mut self`.
+unsafe impl Send for SpeexState {}
+unsafe impl Sync for SpeexState {}
+
+impl SpeexState {
+    fn new() -> Self {
+        // SAFETY: `speex_bits_init` initializes the zeroed struct in place, and
+        // `speex_wb_mode` is a static mode definition provided by libspeex.
+        unsafe {
+            let mut bits = Box::new(std::mem::zeroed::<ffi::SpeexBits>());
+            ffi::speex_bits_init(&mut *bits);
+            let decoder = ffi::speex_decoder_init(&ffi::speex_wb_mode);
+            let mut frame_size: c_int = 0;
+            ffi::speex_decoder_ctl(
+                decoder,
+                ffi::SPEEX_GET_FRAME_SIZE as c_int,
+                (&mut frame_size as *mut c_int).cast(),
+            );
+            Self {
+                decoder,
+                bits,
+                frame_size: frame_size.max(0) as usize,
+            }
+        }
+    }
+
+    fn bits_remaining(&mut self) -> usize {
+        // SAFETY: `bits` was initialized by `speex_bits_init`.
+        unsafe { ffi::speex_bits_remaining(&mut *self.bits).max(0) as usize }
+    }
+
+    fn append(&mut self, data: &[u8]) {
+        // SAFETY: libspeex copies `data` into its own buffer, growing it as needed.
+        unsafe {
+            ffi::speex_bits_read_whole_bytes(
+                &mut *self.bits,
+                data.as_ptr().cast(),
+                data.len() as c_int,
+            );
+        }
+    }
+
+    /// Skips the padding after a terminator.
+    /// Returns `false` if there is nothing left to skip to.
+    fn skip_to_byte_boundary(&mut self) -> bool {
+        let remaining = self.bits_remaining();
+        if remaining < 8 {
+            return false;
+        }
+        // SAFETY: `bits` was initialized by `speex_bits_init`.
+        unsafe { ffi::speex_bits_advance(&mut *self.bits, (remaining % 8) as c_int) };
+        true
+    }
+
+    /// Decodes one frame into `out`, returning the libspeex status code.
+    fn decode(&mut self, out: &mut Vec<i16>) -> c_int {
+        out.resize(self.frame_size, 0);
+        // SAFETY: `out` holds `frame_size` samples, which is what the decoder writes.
+        unsafe { ffi::speex_decode_int(self.decoder, &mut *self.bits, out.as_mut_ptr()) }
+    }
+
+    fn reset(&mut self) {
+        // SAFETY: Both the bit buffer and the decoder were initialized in `new`.
+        unsafe {
+            ffi::speex_bits_reset(&mut *self.bits);
+            ffi::speex_decoder_ctl(
+                self.decoder,
+                ffi::SPEEX_RESET_STATE as c_int,
+                std::ptr::null_mut(),
+            );
+        }
+    }
+}
+
+impl Drop for SpeexState {
+    fn drop(&mut self) {
+        // SAFETY: Both were initialized in `new` and are not used after this.
+        unsafe {
+            ffi::speex_decoder_destroy(self.decoder);
+            ffi::speex_bits_destroy(&mut *self.bits);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::c_char;
+
+    /// Encodes 20ms frames of 16 kHz audio into a wideband Speex bitstream,
+    /// the way Flash does.
+    fn encode(frames: &[Vec<i16>]) -> Vec<u8> {
+        // SAFETY: The bit buffer and encoder are initialized before use and
+        // destroyed afterwards. Every frame holds the 320 samples the
+        // wideband encoder reads.
+        unsafe {
+            let mut bits = std::mem::zeroed::<ffi::SpeexBits>();
+            ffi::speex_bits_init(&mut bits);
+            let encoder = ffi::speex_encoder_init(&ffi::speex_wb_mode);
+            for frame in frames {
+                let mut frame = frame.clone();
+                ffi::speex_encode_int(encoder, frame.as_mut_ptr(), &mut bits);
+            }
+            ffi::speex_bits_insert_terminator(&mut bits);
+
+            let mut data = vec![0u8; ffi::speex_bits_nbytes(&mut bits) as usize];
+            let len = ffi::speex_bits_write(
+                &mut bits,
+                data.as_mut_ptr().cast::<c_char>(),
+                data.len() as c_int,
+            );
+            data.truncate(len.max(0) as usize);
+
+            ffi::speex_encoder_destroy(encoder);
+            ffi::speex_bits_destroy(&mut bits);
+            data
+        }
+    }
+
+    fn sine_frames(count: usize) -> Vec<Vec<i16>> {
+        (0..count)
+            .map(|frame| {
+                (0..320)
+                    .map(|i| {
+                        let t = (frame * 320 + i) as f64 / f64::from(SAMPLE_RATE);
+                        ((t * 440.0 * std::f64::consts::TAU).sin() * 8000.0) as i16
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn decode_frames() {
+        let data = encode(&sine_frames(5));
+        let samples: Vec<_> = SpeexDecoder::new(Cursor::new(data)).collect();
+        assert_eq!(samples.len(), 5 * 320);
+        assert!(samples.iter().all(|[left, right]| left == right));
+        assert!(
+            samples
+                .iter()
+                .any(|[sample, _]| sample.unsigned_abs() > 1000)
+        );
+    }
+
+    #[test]
+    fn reset() {
+        let mut decoder = SpeexDecoder::new(Cursor::new(encode(&sine_frames(3))));
+        let first: Vec<_> = decoder.by_ref().collect();
+        decoder.reset();
+        let second: Vec<_> = decoder.collect();
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn invalid_data_ends() {
+        let inputs = [
+            vec![0xFF; 64],
+            vec![0x00; 3],
+            vec![0xF0],
+            (0..=255).collect(),
+        ];
+        for data in inputs {
+            let count = SpeexDecoder::new(Cursor::new(data)).take(1_000_000).count();
+            assert!(count < 1_000_000);
+        }
+    }
+}
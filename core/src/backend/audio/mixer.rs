@@ -71,6 +71,9 @@ pub struct AudioMixer {
     /// The sample rate of the output stream in Hz.
     output_sample_rate: u32,
 
+    /// The speed at which sounds play, relative to their normal speed.
+    playback_rate: f64,
+
     /// The last two windows of output samples.
     output_memory: Arc<RwLock<CircBuf>>,
 }
@@ -88,6 +91,9 @@ trait Stream: dasp::signal::Signal<Frame = [i16; 2]> + Send + Sync {
     /// The sample rate of the underlying audio source of this stream. For example, this will return
     /// 22050 when playing a 22KHz audio file, even if the output rate is 44KHz.
     fn source_sample_rate(&self) -> u16;
+
+    /// Changes the speed at which this stream plays, if it resamples its source.
+    fn set_playback_rate(&mut self, _playback_rate: f64) {}
 }
 
 /// A stream that wraps a `Decoder`.
@@ -247,6 +253,7 @@ impl AudioMixer {
             volume: Arc::new(RwLock::new(1.0)),
             num_output_channels,
             output_sample_rate,
+            playback_rate: 1.0,
             output_memory: Arc::new(RwLock::new(CircBuf::new())),
         }
     }
@@ -329,6 +336,8 @@ impl AudioMixer {
                 data,
                 format.sample_rate.into(),
             )),
+            #[cfg(feature = "speex")]
+            AudioCompression::Speex => Box::new(decoders::SpeexDecoder::new(data)),
             AudioCompression::G711ALawPCM => Box::new(G711ALawDecoder::new(data)),
             AudioCompression::G711MuLawPCM => Box::new(G711MuLawDecoder::new(data)),
             _ => return Err(decoders::Error::UnhandledCompression(format.compression)),
@@ -342,13 +351,19 @@ impl AudioMixer {
         let left = stream.next();
         let right = stream.next();
         let interpolator = dasp::interpolate::linear::Linear::new(left, right);
-        let sample_rate = stream.source_sample_rate().into();
-        ConverterStream(dasp::signal::interpolate::Converter::from_hz_to_hz(
+        let sample_rate = f64::from(stream.source_sample_rate());
+        let output_sample_rate = self.output_sample_rate.into();
+        // Playing faster is the same as converting from a higher sample rate.
+        let converter = dasp::signal::interpolate::Converter::from_hz_to_hz(
             stream,
             interpolator,
-            sample_rate,
-            self.output_sample_rate.into(),
-        ))
+            sample_rate * self.playback_rate,
+            output_sample_rate,
+        );
+        ConverterStream {
+            converter,
+            output_sample_rate,
+        }
     }
 
     /// Creates a `Stream` for an "event" that decodes and resamples the audio stream to the
@@ -714,6 +729,20 @@ impl AudioMixer {
     pub fn set_volume(&mut self, volume: f32) {
         *self.volume.write().expect("Cannot be called reentrant") = volume
     }
+
+    /// Sets the speed at which sounds play, relative to their normal speed.
+    ///
+    /// This applies to both playing sounds and sounds started later.
+    pub fn set_playback_rate(&mut self, playback_rate: f64) {
+        self.playback_rate = playback_rate;
+        let mut sound_instances = self
+            .sound_instances
+            .lock()
+            .expect("Cannot be called reentrant");
+        for (_, instance) in sound_instances.iter_mut() {
+            instance.stream.set_playback_rate(playback_rate);
+        }
+    }
 }
 
 /// A thread-safe proxy to the main `AudioMixer`, allowing for mixing audio from a different thread.
@@ -880,10 +909,14 @@ impl Stream for EventSoundStream {
 }
 
 /// A stream that converts a source stream to a different sample rate.
-struct ConverterStream<S, I>(dasp::signal::interpolate::Converter<S, I>)
+struct ConverterStream<S, I>
 where
     S: Stream,
-    I: dasp::interpolate::Interpolator<Frame = [i16; 2]>;
+    I: dasp::interpolate::Interpolator<Frame = [i16; 2]>,
+{
+    converter: dasp::signal::interpolate::Converter<S, I>,
+    output_sample_rate: f64,
+}
 
 impl<S, I> Stream for ConverterStream<S, I>
 where
@@ -892,12 +925,19 @@ where
 {
     #[inline]
     fn source_position(&self) -> u32 {
-        self.0.source().source_position()
+        self.converter.source().source_position()
     }
 
     #[inline]
     fn source_sample_rate(&self) -> u16 {
-        self.0.source().source_sample_rate()
+        self.converter.source().source_sample_rate()
+    }
+
+    fn set_playback_rate(&mut self, playback_rate: f64) {
+        // This replaces the whole conversion ratio, so the source rate has to be included.
+        let sample_rate = f64::from(self.source_sample_rate());
+        self.converter
+            .set_hz_to_hz(sample_rate * playback_rate, self.output_sample_rate);
     }
 }
 
@@ -910,12 +950,12 @@ where
 
     #[inline]
     fn next(&mut self) -> [i16; 2] {
-        self.0.next()
+        self.converter.next()
     }
 
     #[inline]
     fn is_exhausted(&self) -> bool {
-        self.0.is_exhausted()
+        self.converter.is_exhausted()
     }
 }
 
@@ -953,6 +993,10 @@ where
     fn source_sample_rate(&self) -> u16 {
         self.stream.source_sample_rate()
     }
+
+    fn set_playback_rate(&mut self, playback_rate: f64) {
+        self.stream.set_playback_rate(playback_rate);
+    }
 }
 
 impl<S, E> dasp::signal::Signal for MulAmpStream<S, E>
@@ -1164,8 +1208,81 @@ macro_rules! impl_audio_mixer_backend {
             self.$mixer.set_volume(volume)
         }
 
+        fn set_playback_rate(&mut self, playback_rate: f64) {
+            self.$mixer.set_playback_rate(playback_rate)
+        }
+
         fn get_sample_history(&self) -> [[f32; 2]; 1024] {
             self.$mixer.get_sample_history()
         }
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dasp::signal::Signal;
+
+    /// A finite stream of silence at a given sample rate.
+    struct SilentStream {
+        sample_rate: u16,
+        remaining: u32,
+    }
+
+    impl Signal for SilentStream {
+        type Frame = [i16; 2];
+
+        fn next(&mut self) -> [i16; 2] {
+            self.remaining = self.remaining.saturating_sub(1);
+            [0, 0]
+        }
+
+        fn is_exhausted(&self) -> bool {
+            self.remaining == 0
+        }
+    }
+
+    impl Stream for SilentStream {
+        fn source_position(&self) -> u32 {
+            0
+        }
+
+        fn source_sample_rate(&self) -> u16 {
+            self.sample_rate
+        }
+    }
+
+    fn resampled_length(playback_rate: f64, change_rate_later: bool) -> usize {
+        let mut mixer = AudioMixer::new(2, 44100);
+        if !change_rate_later {
+            mixer.set_playback_rate(playback_rate);
+        }
+        let mut stream = mixer.make_resampler(SilentStream {
+            sample_rate: 22050,
+            remaining: 22050,
+        });
+        if change_rate_later {
+            stream.set_playback_rate(playback_rate);
+        }
+        let mut length = 0;
+        while !stream.is_exhausted() && length < 1_000_000 {
+            stream.next();
+            length += 1;
+        }
+        length
+    }
+
+    #[test]
+    fn resample_keeps_duration() {
+        let length = resampled_length(1.0, false);
+        assert!(length.abs_diff(44100) <= 8, "got {length} frames");
+    }
+
+    #[test]
+    fn resample_with_playback_rate() {
+        for change_rate_later in [false, true] {
+            let length = resampled_length(2.0, change_rate_later);
+            assert!(length.abs_diff(22050) <= 8, "got {length} frames");
+        }
+    }
+}
@@ -0,0 +1,197 @@
+use flash_lso::packet::{Header, Message, Packet};
+use flash_lso::types::{AMFVersion, ObjectId, Value as AmfValue};
+use std::rc::Rc;
+
+/// The largest encoded message that is sent to or accepted from another player.
+///
+/// Flash Player limits the arguments of a message to 40 KiB, the rest leaves
+/// room for the names and domain sent along with them.
+pub const MAX_MESSAGE_BYTES: usize = 44 * 1024;
+
+/// A `LocalConnection` message sent between two players.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocalConnectionMessage {
+    /// The full, lowercase name of the receiving connection,
+    /// such as `localhost:myConnection` or `_myConnection`.
+    pub connection_name: String,
+
+    pub method_name: String,
+
+    /// The domain of the movie that sent this message.
+    pub sender_domain: String,
+
+    /// Whether the movie that sent this message was loaded over HTTPS.
+    pub sender_is_secure: bool,
+
+    /// The AMF0 encoded arguments of the method call.
+    pub arguments: Vec<AmfValue>,
+}
+
+impl LocalConnectionMessage {
+    const DOMAIN_HEADER: &'static str = "domain";
+    const SECURE_HEADER: &'static str = "secure";
+
+    /// Encodes this message as an AMF0 packet, so that it can be sent to
+    /// another process.
+    ///
+    /// Returns `None` if it can't be encoded, or is over [`MAX_MESSAGE_BYTES`].
+    pub fn to_bytes(&self) -> Option<Vec<u8>> {
+        let packet = Packet {
+            version: AMFVersion::AMF0,
+            headers: vec![
+                Header {
+                    name: Self::DOMAIN_HEADER.to_string(),
+                    must_understand: false,
+                    value: Rc::new(AmfValue::String(self.sender_domain.clone())),
+                },
+                Header {
+                    name: Self::SECURE_HEADER.to_string(),
+                    must_understand: false,
+                    value: Rc::new(AmfValue::Bool(self.sender_is_secure)),
+                },
+            ],
+            messages: vec![Message {
+                target_uri: self.connection_name.clone(),
+                response_uri: self.method_name.clone(),
+                contents: Rc::new(AmfValue::StrictArray(
+                    ObjectId::INVALID,
+                    self.arguments.iter().cloned().map(Rc::new).collect(),
+                )),
+            }],
+        };
+        flash_lso::packet::write::write_to_bytes(&packet, true)
+            .ok()
+            .filter(|bytes| bytes.len() <= MAX_MESSAGE_BYTES)
+    }
+
+    /// Decodes a message previously encoded with [`Self::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let packet = flash_lso::packet::read::parse(bytes).ok()?;
+        let message = packet.messages.into_iter().next()?;
+
+        let mut sender_domain = None;
+        let mut sender_is_secure = false;
+        for header in packet.headers {
+            match (header.name.as_str(), &*header.value) {
+                (Self::DOMAIN_HEADER, AmfValue::String(domain)) => {
+                    sender_domain = Some(domain.clone())
+                }
+                (Self::SECURE_HEADER, AmfValue::Bool(secure)) => sender_is_secure = *secure,
+                _ => {}
+            }
+        }
+
+        let arguments = match &*message.contents {
+            AmfValue::StrictArray(_, values) => values.iter().map(|v| (**v).clone()).collect(),
+            _ => return None,
+        };
+
+        Some(Self {
+            connection_name: message.target_uri,
+            method_name: message.response_uri,
+            sender_domain: sender_domain?,
+            sender_is_secure,
+            arguments,
+        })
+    }
+}
+
+/// Shares `LocalConnection`s with other players, such as other instances of
+/// Ruffle running on the same machine.
+///
+/// Connections between movies inside of a single player are handled by the
+/// player itself; the backend is only consulted for names that aren't
+/// listened on locally.
+pub trait LocalConnectionBackend {
+    /// Claims ownership of the connection `name` for this player.
+    ///
+    /// Returns `false` if another player is already listening on it.
+    fn connect(&mut self, name: &str) -> bool;
+
+    /// Releases a connection previously claimed with [`Self::connect`].
+    fn close(&mut self, name: &str);
+
+    /// Returns whether another player is currently listening on `name`.
+    fn is_connected(&mut self, name: &str) -> bool;
+
+    /// Sends a message to the player that owns its connection.
+    ///
+    /// Returns `false` if no other player is listening on that connection.
+    fn send(&mut self, message: LocalConnectionMessage) -> bool;
+
+    /// Returns the messages other players sent to connections owned by this
+    /// player since the last call.
+    fn poll(&mut self) -> Vec<LocalConnectionMessage>;
+}
+
+/// A backend that doesn't share connections with anyone, limiting
+/// `LocalConnection`s to the movies of a single player.
+#[derive(Default)]
+pub struct NullLocalConnectionBackend;
+
+impl NullLocalConnectionBackend {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl LocalConnectionBackend for NullLocalConnectionBackend {
+    fn connect(&mut self, _name: &str) -> bool {
+        true
+    }
+
+    fn close(&mut self, _name: &str) {}
+
+    fn is_connected(&mut self, _name: &str) -> bool {
+        false
+    }
+
+    fn send(&mut self, _message: LocalConnectionMessage) -> bool {
+        false
+    }
+
+    fn poll(&mut self) -> Vec<LocalConnectionMessage> {
+        vec![]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn message_round_trip() {
+        let message = LocalConnectionMessage {
+            connection_name: "example.com:receiver".to_string(),
+            method_name: "onMessage".to_string(),
+            sender_domain: "example.com".to_string(),
+            sender_is_secure: true,
+            arguments: vec![
+                AmfValue::String("hello".to_string()),
+                AmfValue::Number(42.0),
+                AmfValue::Bool(false),
+                AmfValue::Null,
+            ],
+        };
+
+        let bytes = message.to_bytes().expect("message should encode");
+        assert_eq!(LocalConnectionMessage::from_bytes(&bytes), Some(message));
+    }
+
+    #[test]
+    fn oversized_message_is_not_encoded() {
+        let message = LocalConnectionMessage {
+            connection_name: "_receiver".to_string(),
+            method_name: "onMessage".to_string(),
+            sender_domain: "localhost".to_string(),
+            sender_is_secure: false,
+            arguments: vec![AmfValue::String("a".repeat(MAX_MESSAGE_BYTES))],
+        };
+        assert_eq!(message.to_bytes(), None);
+    }
+
+    #[test]
+    fn garbage_is_rejected() {
+        assert_eq!(LocalConnectionMessage::from_bytes(b"not a packet"), None);
+    }
+}
@@ -2,6 +2,9 @@ pub trait LogBackend {
     fn avm_trace(&self, message: &str);
 
     fn avm_warning(&self, message: &str);
+
+    /// Called when an error thrown by ActionScript isn't caught by the movie.
+    fn avm_uncaught_error(&self, _message: &str) {}
 }
 
 /// Logging backend that just reroutes traces to the log crate
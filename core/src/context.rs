@@ -10,6 +10,7 @@ use crate::avm2::api_version::ApiVersion;
 use crate::avm2::{Avm2, LoaderInfoObject, SharedObjectObject, SoundChannelObject};
 use crate::backend::{
     audio::{AudioBackend, AudioManager, SoundHandle, SoundInstanceHandle},
+    local_connection::LocalConnectionBackend,
     log::LogBackend,
     navigator::NavigatorBackend,
     storage::StorageBackend,
@@ -26,10 +27,12 @@ use crate::library::Library;
 use crate::loader::LoadManager;
 use crate::local_connection::LocalConnections;
 use crate::net_connection::NetConnections;
+use crate::network_log::NetworkLog;
 use crate::orphan_manager::OrphanManager;
 use crate::player::PostFrameCallback;
 use crate::player::{MouseData, Player};
 use crate::prelude::*;
+use crate::security::SecurityManager;
 use crate::socket::Sockets;
 use crate::streams::StreamManager;
 use crate::string::HasStringContext;
@@ -37,7 +40,8 @@ use crate::string::{AvmString, StringContext};
 use crate::stub::StubCollection;
 use crate::system_properties::SystemProperties;
 use crate::tag_utils::{SwfMovie, SwfSlice};
-use crate::timer::Timers;
+use crate::telemetry::Telemetry;
+use crate::timer::{MovieClock, Timers};
 use crate::vminterface::Instantiator;
 use async_channel::Sender;
 use core::fmt;
@@ -110,6 +114,9 @@ pub struct UpdateContext<'gc> {
     /// The storage backend, used for storing persistent state
     pub storage: &'gc mut dyn StorageBackend,
 
+    /// The local connection backend, used to talk to other players.
+    pub local_connection: &'gc mut dyn LocalConnectionBackend,
+
     /// The logging backend, used for trace output capturing.
     ///
     /// **DO NOT** use this field directly, use the `avm_trace` method instead.
@@ -178,8 +185,8 @@ pub struct UpdateContext<'gc> {
     /// External interface for (for example) JavaScript <-> ActionScript interaction
     pub external_interface: &'gc mut ExternalInterface<'gc>,
 
-    /// The instant at which the SWF was launched.
-    pub start_time: Instant,
+    /// The clock that `getTimer` reads, started when the SWF was launched.
+    pub clock: MovieClock,
 
     /// The instant at which the current update started.
     pub update_start: Instant,
@@ -235,6 +242,19 @@ pub struct UpdateContext<'gc> {
     // Movie clips whose frame scripts were registered during frame script phase
     // requires a separate clean-up pass when running frame-scripts instead of executing them in place
     pub frame_script_cleanup_queue: VecDeque<MovieClip<'gc>>,
+
+    /// The state of the attached script debugger.
+    #[cfg(feature = "debugger")]
+    pub debugger: &'gc mut crate::debugger::Debugger,
+
+    /// Where telemetry is written to, if enabled.
+    pub telemetry: &'gc mut Telemetry,
+
+    /// The record of fetches, socket traffic and `LocalConnection` messages.
+    pub network_log: &'gc NetworkLog,
+
+    /// The state of the security sandbox.
+    pub security: &'gc mut SecurityManager,
 }
 
 impl<'gc> HasStringContext<'gc> for UpdateContext<'gc> {
@@ -286,9 +306,10 @@ impl<'gc> UpdateContext<'gc> {
         &mut self,
         instance: SoundInstanceHandle,
         avm2_object: SoundChannelObject<'gc>,
+        movie: Arc<SwfMovie>,
     ) {
         self.audio_manager
-            .attach_avm2_sound_channel(instance, avm2_object);
+            .attach_avm2_sound_channel(instance, avm2_object, movie);
     }
 
     pub fn stop_sound(&mut self, instance: SoundInstanceHandle) {
@@ -357,6 +378,7 @@ impl<'gc> UpdateContext<'gc> {
 
         *self.root_swf = Arc::new(movie);
         *self.instance_counter = 0;
+        self.telemetry.movie_info(&self.root_swf, *self.frame_rate);
 
         if self.root_swf.is_action_script_3() {
             self.avm2.root_api_version =
@@ -465,6 +487,10 @@ impl<'gc> UpdateContext<'gc> {
         self.log.avm_warning(message);
     }
 
+    pub fn avm_uncaught_error(&self, message: &str) {
+        self.log.avm_uncaught_error(message);
+    }
+
     /// Obtain a strong reference to the current `Player`.
     pub fn player_handle(&self) -> Arc<Mutex<Player>> {
         self.player
@@ -1,10 +1,15 @@
 mod avm1;
 mod avm2;
 mod common;
+#[cfg(feature = "debugger")]
+mod debugger;
 mod display_object;
 mod domain;
 mod handle;
 mod movie;
+mod network;
+mod performance;
+mod variables;
 
 use crate::context::{RenderContext, UpdateContext};
 use crate::debug_ui::avm1::Avm1ObjectWindow;
@@ -15,6 +20,9 @@ use crate::debug_ui::handle::{
     AVM1ObjectHandle, AVM2ObjectHandle, DisplayObjectHandle, DomainHandle,
 };
 use crate::debug_ui::movie::{MovieListWindow, MovieWindow};
+use crate::debug_ui::network::NetworkWindow;
+use crate::debug_ui::performance::PerformanceHud;
+use crate::debug_ui::variables::VariableSearchWindow;
 use crate::display_object::TDisplayObject;
 use crate::prelude::DisplayObject;
 use crate::tag_utils::SwfMovie;
@@ -37,6 +45,11 @@ pub struct DebugUi {
     movie_list: Option<MovieListWindow>,
     domain_list: Option<DomainListWindow>,
     display_object_search: Option<DisplayObjectSearchWindow>,
+    variable_search: Option<VariableSearchWindow>,
+    network: Option<NetworkWindow>,
+    performance_hud: Option<PerformanceHud>,
+    #[cfg(feature = "debugger")]
+    debugger: Option<debugger::DebuggerWindow>,
 }
 
 #[derive(Debug)]
@@ -50,9 +63,14 @@ pub enum Message {
     TrackTopLevelMovie,
     ShowKnownMovies,
     ShowDomains,
+    ShowNetworkActivity,
+    TogglePerformanceHud,
     SaveFile(ItemToSave),
     SearchForDisplayObject,
+    SearchForVariable,
     TrackRootMovieClip,
+    #[cfg(feature = "debugger")]
+    ShowDebugger,
 }
 
 impl DebugUi {
@@ -100,6 +118,33 @@ impl DebugUi {
             self.display_object_search = Some(search);
         }
 
+        if let Some(mut search) = self.variable_search.take()
+            && search.show(egui_ctx, context, &mut messages)
+        {
+            self.variable_search = Some(search);
+        }
+
+        if let Some(mut network) = self.network.take()
+            && network.show(egui_ctx, context, &mut messages)
+        {
+            self.network = Some(network);
+        }
+        // Network activity is only worth recording while someone can see it.
+        context.network_log.set_recording(self.network.is_some());
+
+        if let Some(mut hud) = self.performance_hud.take()
+            && hud.show(egui_ctx)
+        {
+            self.performance_hud = Some(hud);
+        }
+
+        #[cfg(feature = "debugger")]
+        if let Some(mut debugger) = self.debugger.take()
+            && debugger.show(egui_ctx, context)
+        {
+            self.debugger = Some(debugger);
+        }
+
         for message in messages {
             match message {
                 Message::TrackDisplayObject(object) => {
@@ -133,9 +178,24 @@ impl DebugUi {
                 Message::ShowDomains => {
                     self.domain_list = Some(Default::default());
                 }
+                Message::ShowNetworkActivity => {
+                    self.network.get_or_insert_with(Default::default);
+                }
+                Message::TogglePerformanceHud => {
+                    if self.performance_hud.take().is_none() {
+                        self.performance_hud = Some(Default::default());
+                    }
+                }
                 Message::SearchForDisplayObject => {
                     self.display_object_search = Some(Default::default());
                 }
+                Message::SearchForVariable => {
+                    self.variable_search.get_or_insert_with(Default::default);
+                }
+                #[cfg(feature = "debugger")]
+                Message::ShowDebugger => {
+                    self.debugger.get_or_insert_with(Default::default);
+                }
                 Message::TrackRootMovieClip => {
                     // Convenience action to quickly access the root movie clip
                     if let Some(obj @ DisplayObject::MovieClip(_)) = context.stage.root_clip() {
@@ -150,9 +210,34 @@ impl DebugUi {
     }
 
     pub fn should_suspend_player(&self) -> bool {
+        #[cfg(feature = "debugger")]
+        if self
+            .debugger
+            .as_ref()
+            .is_some_and(|debugger| debugger.is_stopped())
+        {
+            return true;
+        }
+
         self.display_object_search.is_some()
     }
 
+    /// Re-applies the values frozen in the variable search window.
+    pub(crate) fn apply_frozen_variables(&self, context: &mut UpdateContext) {
+        if let Some(search) = &self.variable_search {
+            search.apply_frozen(context);
+        }
+    }
+
+    /// The performance HUD, if it is shown, to be updated with fresh statistics.
+    pub(crate) fn performance_hud_mut(&mut self) -> Option<&mut PerformanceHud> {
+        self.performance_hud.as_mut()
+    }
+
+    pub fn is_performance_hud_shown(&self) -> bool {
+        self.performance_hud.is_some()
+    }
+
     pub fn items_to_save(&mut self) -> Vec<ItemToSave> {
         std::mem::take(&mut self.items_to_save)
     }
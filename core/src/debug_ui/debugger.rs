@@ -0,0 +1,284 @@
+use crate::context::UpdateContext;
+use crate::debugger::{Breakpoint, DebuggerCommand, Snapshot, parse_offset};
+use egui::{CollapsingHeader, ComboBox, Grid, TextEdit, Ui, Window};
+
+#[derive(Debug, Default, Eq, PartialEq, Copy, Clone)]
+enum BreakpointKind {
+    #[default]
+    Function,
+    FrameScript,
+    Action,
+    Line,
+}
+
+impl BreakpointKind {
+    fn label(self) -> &'static str {
+        match self {
+            BreakpointKind::Function => "Function",
+            BreakpointKind::FrameScript => "Frame Script",
+            BreakpointKind::Action => "AVM1 Action Offset",
+            BreakpointKind::Line => "Source Line",
+        }
+    }
+}
+
+/// A script debugger that records what happens after stopping, since the
+/// player can't be blocked while the UI is shown.
+///
+/// Stepping moves through the recording first. Stepping past its end lets the
+/// player run until the step completes, which records anew.
+#[derive(Debug, Default)]
+pub struct DebuggerWindow {
+    kind: BreakpointKind,
+    name: String,
+    location: String,
+
+    /// The snapshot currently shown.
+    selected: usize,
+    is_stopped: bool,
+}
+
+impl DebuggerWindow {
+    pub fn is_stopped(&self) -> bool {
+        self.is_stopped
+    }
+
+    pub fn show(&mut self, egui_ctx: &egui::Context, context: &mut UpdateContext) -> bool {
+        let mut keep_open = true;
+        let debugger = &mut *context.debugger;
+        debugger.set_local(true);
+
+        Window::new("Script Debugger")
+            .open(&mut keep_open)
+            .scroll([true, true])
+            .show(egui_ctx, |ui| {
+                let snapshots = debugger.snapshots();
+                self.selected = self.selected.min(snapshots.len().saturating_sub(1));
+                let current = snapshots.get(self.selected);
+
+                let mut step_to = None;
+                let mut step_vm = None;
+                let mut resume = false;
+                let mut pause = false;
+                ui.horizontal(|ui| {
+                    if current.is_none() {
+                        pause = ui.button("Break").clicked();
+                    } else {
+                        resume = ui.button("Continue").clicked();
+                    }
+
+                    if let Some(current) = current {
+                        let following = || snapshots.iter().enumerate().skip(self.selected + 1);
+                        let step_in = following().next();
+                        let step_over = following().find(|(_, s)| s.depth <= current.depth);
+                        let step_out = following().find(|(_, s)| s.depth < current.depth);
+
+                        for (label, target, command) in [
+                            ("Step In", step_in, DebuggerCommand::StepIn),
+                            ("Step Over", step_over, DebuggerCommand::StepOver),
+                            ("Step Out", step_out, DebuggerCommand::StepOut),
+                        ] {
+                            if ui.button(label).clicked() {
+                                match target {
+                                    Some((index, _)) => step_to = Some(index),
+                                    None => step_vm = Some((command, current.depth)),
+                                }
+                            }
+                        }
+                    }
+                });
+
+                if let Some(current) = current {
+                    ui.label(format!(
+                        "Stopped ({:?}), showing statement {} of {} recorded",
+                        current.reason,
+                        self.selected + 1,
+                        snapshots.len()
+                    ));
+                    ui.separator();
+                    show_snapshot(ui, current);
+                } else {
+                    ui.weak("Running");
+                }
+
+                ui.separator();
+                if let Some(breakpoints) =
+                    self.show_breakpoints(ui, debugger.breakpoints().to_vec())
+                {
+                    debugger.set_breakpoints(breakpoints);
+                }
+
+                if let Some(index) = step_to {
+                    self.selected = index;
+                }
+                if pause {
+                    debugger.request_pause();
+                }
+                if resume {
+                    debugger.resume_local();
+                    self.selected = 0;
+                }
+                if let Some((command, depth)) = step_vm {
+                    debugger.step_local(&command, depth);
+                    self.selected = 0;
+                }
+            });
+
+        self.is_stopped = keep_open && !debugger.snapshots().is_empty();
+        if !keep_open {
+            debugger.set_local(false);
+        }
+        keep_open
+    }
+
+    /// Shows the breakpoint list, returning the new breakpoints if they changed.
+    fn show_breakpoints(
+        &mut self,
+        ui: &mut Ui,
+        mut breakpoints: Vec<Breakpoint>,
+    ) -> Option<Vec<Breakpoint>> {
+        let mut changed = false;
+
+        CollapsingHeader::new(format!("Breakpoints ({})", breakpoints.len()))
+            .id_salt(ui.id().with("breakpoints"))
+            .default_open(true)
+            .show(ui, |ui| {
+                let mut removed = None;
+                Grid::new(ui.id().with("breakpoint_list"))
+                    .num_columns(2)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for (index, breakpoint) in breakpoints.iter().enumerate() {
+                            ui.label(describe_breakpoint(breakpoint));
+                            if ui.button("Remove").clicked() {
+                                removed = Some(index);
+                            }
+                            ui.end_row();
+                        }
+                    });
+                if let Some(index) = removed {
+                    breakpoints.remove(index);
+                    changed = true;
+                }
+
+                ui.horizontal(|ui| {
+                    ComboBox::from_id_salt(ui.id().with("breakpoint_kind"))
+                        .selected_text(self.kind.label())
+                        .show_ui(ui, |ui| {
+                            for kind in [
+                                BreakpointKind::Function,
+                                BreakpointKind::FrameScript,
+                                BreakpointKind::Action,
+                                BreakpointKind::Line,
+                            ] {
+                                ui.selectable_value(&mut self.kind, kind, kind.label());
+                            }
+                        });
+
+                    let (name_hint, location_hint) = match self.kind {
+                        BreakpointKind::Function => ("Name", "Offset (optional)"),
+                        BreakpointKind::FrameScript => ("Clip path", "Frame"),
+                        BreakpointKind::Action => ("", "Offset"),
+                        BreakpointKind::Line => ("File", "Line"),
+                    };
+                    if !name_hint.is_empty() {
+                        ui.add(TextEdit::singleline(&mut self.name).hint_text(name_hint));
+                    }
+                    ui.add(
+                        TextEdit::singleline(&mut self.location)
+                            .hint_text(location_hint)
+                            .desired_width(80.0),
+                    );
+
+                    let breakpoint = self.new_breakpoint();
+                    if ui
+                        .add_enabled(breakpoint.is_some(), egui::Button::new("Add"))
+                        .clicked()
+                        && let Some(breakpoint) = breakpoint
+                    {
+                        breakpoints.push(breakpoint);
+                        changed = true;
+                    }
+                });
+            });
+
+        changed.then_some(breakpoints)
+    }
+
+    fn new_breakpoint(&self) -> Option<Breakpoint> {
+        let name = self.name.trim();
+        let location = self.location.trim();
+        match self.kind {
+            BreakpointKind::Function if !name.is_empty() => Some(Breakpoint::Method {
+                name: name.to_string(),
+                offset: if location.is_empty() {
+                    None
+                } else {
+                    Some(parse_offset(location)?)
+                },
+            }),
+            BreakpointKind::FrameScript if !name.is_empty() => Some(Breakpoint::FrameScript {
+                clip: name.to_string(),
+                frame: location.parse().ok()?,
+            }),
+            BreakpointKind::Action => Some(Breakpoint::Action {
+                offset: parse_offset(location)?,
+            }),
+            BreakpointKind::Line if !name.is_empty() => Some(Breakpoint::Line {
+                file: name.to_string(),
+                line: location.parse().ok()?,
+            }),
+            _ => None,
+        }
+    }
+}
+
+fn show_snapshot(ui: &mut Ui, snapshot: &Snapshot) {
+    CollapsingHeader::new("Call Stack")
+        .id_salt(ui.id().with("call_stack"))
+        .default_open(true)
+        .show(ui, |ui| {
+            for frame in &snapshot.stack {
+                let mut text = frame.name.clone();
+                if let Some(file) = &frame.file {
+                    text.push_str(&format!(" ({file}:{})", frame.line));
+                }
+                if let Some(offset) = frame.offset {
+                    text.push_str(&format!(" @ 0x{offset:x}"));
+                }
+                ui.label(text);
+            }
+        });
+
+    for (scope, variables) in &snapshot.scopes {
+        CollapsingHeader::new(&scope.name)
+            .id_salt(ui.id().with(("scope", scope.reference)))
+            .default_open(true)
+            .show(ui, |ui| {
+                Grid::new(ui.id().with("variables"))
+                    .num_columns(3)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for variable in variables {
+                            ui.label(&variable.name);
+                            ui.label(&variable.value);
+                            ui.weak(&variable.type_name);
+                            ui.end_row();
+                        }
+                    });
+            });
+    }
+}
+
+fn describe_breakpoint(breakpoint: &Breakpoint) -> String {
+    match breakpoint {
+        Breakpoint::Method { name, offset: None } => format!("Function {name}"),
+        Breakpoint::Method {
+            name,
+            offset: Some(offset),
+        } => format!("Function {name} + 0x{offset:x}"),
+        Breakpoint::Line { file, line } => format!("Line {file}:{line}"),
+        Breakpoint::Action { offset } => format!("AVM1 action at 0x{offset:x}"),
+        Breakpoint::FrameScript { clip, frame } => format!("Frame {frame} of {clip}"),
+    }
+}
@@ -1,7 +1,9 @@
 mod search;
+mod timeline;
 
 use ruffle_render::blend::ExtendedBlendMode;
 pub use search::DisplayObjectSearchWindow;
+use timeline::Timeline;
 
 use crate::avm2::object::TObject as _;
 use crate::bitmap::bitmap_data::DirtyState;
@@ -68,6 +70,7 @@ pub enum Panel {
     Children,
     Interactive,
     TypeSpecific,
+    Timeline,
 }
 
 #[derive(Debug)]
@@ -82,6 +85,11 @@ pub struct DisplayObjectWindow {
     /// Which frame to scroll to in the frame list, if any
     scroll_to_frame: Option<usize>,
 
+    /// The decoded tags of a movie clip, for the timeline panel
+    timeline: Option<Timeline>,
+    /// The frame whose details are shown in the timeline panel
+    timeline_frame: Option<u16>,
+
     /// A buffer for editing EditText
     html_text: String,
 }
@@ -102,6 +110,8 @@ impl Default for DisplayObjectWindow {
             search: Default::default(),
             track_current_frame: false,
             scroll_to_frame: None,
+            timeline: None,
+            timeline_frame: None,
             html_text: Default::default(),
         }
     }
@@ -167,6 +177,9 @@ impl DisplayObjectWindow {
                             display_object_type(object),
                         );
                     }
+                    if matches!(object, DisplayObject::MovieClip(_)) {
+                        ui.selectable_value(&mut self.open_panel, Panel::Timeline, "Timeline");
+                    }
                     if let Some(ctr) = object.as_container()
                         && !ctr.is_empty()
                     {
@@ -201,6 +214,11 @@ impl DisplayObjectWindow {
                             self.show_interactive(ui, context, int)
                         }
                     }
+                    Panel::Timeline => {
+                        if let DisplayObject::MovieClip(object) = object {
+                            self.show_timeline(ui, context, object)
+                        }
+                    }
                 }
             });
         keep_open
@@ -1180,6 +1198,177 @@ impl DisplayObjectWindow {
             });
     }
 
+    pub fn show_timeline<'gc>(
+        &mut self,
+        ui: &mut Ui,
+        context: &mut UpdateContext<'gc>,
+        object: MovieClip<'gc>,
+    ) {
+        if self
+            .timeline
+            .as_ref()
+            .is_none_or(|timeline| timeline.is_outdated(object))
+        {
+            self.timeline = Some(Timeline::decode(object));
+        }
+        let Some(timeline) = &self.timeline else {
+            return;
+        };
+        let current_frame = object.current_frame();
+        let selected_frame = self.timeline_frame.unwrap_or(current_frame);
+
+        // These only affect this clip; the rest of the movie keeps running.
+        ui.horizontal(|ui| {
+            if object.playing() {
+                if ui.button("Stop").clicked() {
+                    object.stop(context);
+                }
+            } else if ui.button("Play").clicked() {
+                object.play();
+            }
+            if ui.button("Step Back").clicked() {
+                object.prev_frame(context);
+                self.timeline_frame = None;
+            }
+            if ui.button("Step Forward").clicked() {
+                object.next_frame(context);
+                self.timeline_frame = None;
+            }
+            if ui
+                .add_enabled(
+                    selected_frame != current_frame,
+                    Button::new(format!("gotoAndStop({selected_frame})")),
+                )
+                .clicked()
+            {
+                object.goto_frame(context, selected_frame, true);
+            }
+        });
+        ui.separator();
+
+        let scenes = object.scenes();
+        ui.columns(2, |columns| {
+            let ui = &mut columns[0];
+            let row_h = ui.text_style_height(&egui::TextStyle::Body) + 2.0;
+            ui.style_mut().wrap_mode = Some(egui::TextWrapMode::Extend);
+            egui_extras::TableBuilder::new(ui)
+                .id_salt("timeline_frames")
+                .columns(egui_extras::Column::auto(), 5)
+                .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+                .sense(Sense::click())
+                .header(row_h, |mut row| {
+                    for header in ["#", "Scene", "Labels", "Scripts", "Display List"] {
+                        row.col(|ui| {
+                            ui.strong(header);
+                        });
+                    }
+                })
+                .body(|body| {
+                    body.rows(row_h, timeline.frames.len(), |mut row| {
+                        let frame_number = (row.index() + 1) as u16;
+                        let frame = &timeline.frames[row.index()];
+                        row.set_selected(frame_number == selected_frame);
+                        row.col(|ui| {
+                            if frame_number == current_frame {
+                                ui.strong(format!("{frame_number} ▶"));
+                            } else {
+                                ui.label(frame_number.to_string());
+                            }
+                        });
+                        row.col(|ui| {
+                            if let Some(scene) = scenes.iter().find(|s| s.start == frame_number) {
+                                ui.label(scene.name.to_string());
+                            }
+                        });
+                        row.col(|ui| {
+                            ui.label(frame.labels.join(", "));
+                        });
+                        row.col(|ui| {
+                            let mut scripts = vec![];
+                            if !frame.actions.is_empty() {
+                                scripts.push(format!("DoAction ×{}", frame.actions.len()));
+                            }
+                            if object.has_frame_script(frame_number) {
+                                scripts.push("AVM2".to_string());
+                            }
+                            ui.label(scripts.join(", "));
+                        });
+                        row.col(|ui| {
+                            if !frame.display_list_changes.is_empty() {
+                                ui.label(frame.display_list_changes.len().to_string());
+                            }
+                        });
+                        if row.response().clicked() {
+                            self.timeline_frame = Some(frame_number);
+                        }
+                    });
+                });
+
+            let ui = &mut columns[1];
+            let Some(frame) = usize::from(selected_frame)
+                .checked_sub(1)
+                .and_then(|index| timeline.frames.get(index))
+            else {
+                ui.label(format!("Frame {selected_frame} has not loaded yet"));
+                return;
+            };
+            ui.heading(format!("Frame {selected_frame}"));
+            if !frame.labels.is_empty() {
+                ui.label(format!("Labels: {}", frame.labels.join(", ")));
+            }
+
+            CollapsingHeader::new(format!("Scripts ({})", frame.actions.len()))
+                .id_salt("timeline_scripts")
+                .default_open(true)
+                .show(ui, |ui| {
+                    if object.has_frame_script(selected_frame) {
+                        ui.label("AVM2 frame script");
+                    }
+                    for (index, actions) in frame.actions.iter().enumerate() {
+                        CollapsingHeader::new(format!("DoAction #{}", index + 1))
+                            .id_salt(("timeline_do_action", index))
+                            .show(ui, |ui| {
+                                ui.add(Label::new(
+                                    egui::RichText::new(actions.join("\n")).monospace(),
+                                ));
+                            });
+                    }
+                });
+
+            CollapsingHeader::new(format!(
+                "Display List Changes ({})",
+                frame.display_list_changes.len()
+            ))
+            .id_salt("timeline_changes")
+            .default_open(true)
+            .show(ui, |ui| {
+                for change in &frame.display_list_changes {
+                    ui.label(change);
+                }
+            });
+
+            CollapsingHeader::new(format!("Depths ({})", frame.depths.len()))
+                .id_salt("timeline_depths")
+                .show(ui, |ui| {
+                    Grid::new("timeline_depths_grid")
+                        .num_columns(3)
+                        .striped(true)
+                        .show(ui, |ui| {
+                            ui.strong("Depth");
+                            ui.strong("Character");
+                            ui.strong("Name");
+                            ui.end_row();
+                            for (depth, timeline_object) in &frame.depths {
+                                ui.label(depth.to_string());
+                                ui.label(timeline_object.character_id.to_string());
+                                ui.label(timeline_object.name.as_deref().unwrap_or_default());
+                                ui.end_row();
+                            }
+                        });
+                });
+        });
+    }
+
     pub fn show_stage<'gc>(
         &mut self,
         ui: &mut Ui,
@@ -0,0 +1,151 @@
+use crate::display_object::MovieClip;
+use crate::tag_utils::SwfSlice;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use swf::avm1::read::Reader as Avm1Reader;
+use swf::{CharacterId, Depth, PlaceObjectAction, Tag};
+
+/// A display object placed on the timeline at some depth.
+#[derive(Debug, Clone)]
+pub struct TimelineObject {
+    pub character_id: CharacterId,
+    pub name: Option<String>,
+}
+
+/// Everything that a movie clip's tags do on a single frame.
+#[derive(Debug, Default)]
+pub struct TimelineFrame {
+    pub labels: Vec<String>,
+
+    /// The disassembly of each `DoAction` tag on this frame.
+    pub actions: Vec<Vec<String>>,
+
+    /// A description of each `PlaceObject` and `RemoveObject` tag on this frame.
+    pub display_list_changes: Vec<String>,
+
+    /// The objects on the timeline once this frame's tags have run.
+    pub depths: BTreeMap<Depth, TimelineObject>,
+}
+
+/// The decoded timeline of a movie clip, one entry per frame.
+#[derive(Debug)]
+pub struct Timeline {
+    /// The clip's tag stream, as far as it was loaded when this was decoded.
+    tag_stream: SwfSlice,
+    pub frames: Vec<TimelineFrame>,
+}
+
+impl Timeline {
+    pub fn decode(clip: MovieClip<'_>) -> Self {
+        let tag_stream = clip.tag_stream();
+        let mut reader = tag_stream.read_from(0);
+        let mut frames = vec![];
+        let mut frame = TimelineFrame::default();
+
+        while !reader.get_ref().is_empty() {
+            let tag = match reader.read_tag() {
+                Ok(tag) => tag,
+                Err(e) => {
+                    frame
+                        .display_list_changes
+                        .push(format!("<error reading tag: {e}>"));
+                    break;
+                }
+            };
+            match tag {
+                Tag::ShowFrame => {
+                    let depths = frame.depths.clone();
+                    frames.push(std::mem::replace(
+                        &mut frame,
+                        TimelineFrame {
+                            depths,
+                            ..Default::default()
+                        },
+                    ));
+                }
+                Tag::FrameLabel(label) => {
+                    frame
+                        .labels
+                        .push(label.label.to_string_lossy(reader.encoding()));
+                }
+                Tag::DoAction(data) => {
+                    frame.actions.push(disassemble(data, tag_stream.version()));
+                }
+                Tag::PlaceObject(place) => {
+                    let name = place
+                        .name
+                        .map(|name| name.to_string_lossy(reader.encoding()));
+                    let description = match place.action {
+                        PlaceObjectAction::Place(id) => {
+                            frame.depths.insert(
+                                place.depth,
+                                TimelineObject {
+                                    character_id: id,
+                                    name: name.clone(),
+                                },
+                            );
+                            format!("Place character {id} at depth {}", place.depth)
+                        }
+                        PlaceObjectAction::Replace(id) => {
+                            if let Some(object) = frame.depths.get_mut(&place.depth) {
+                                object.character_id = id;
+                            }
+                            format!("Replace depth {} with character {id}", place.depth)
+                        }
+                        PlaceObjectAction::Modify => format!("Modify depth {}", place.depth),
+                    };
+                    frame.display_list_changes.push(match name {
+                        Some(name) => format!("{description} as \"{name}\""),
+                        None => description,
+                    });
+                }
+                Tag::RemoveObject(remove) => {
+                    frame.depths.remove(&remove.depth);
+                    frame
+                        .display_list_changes
+                        .push(format!("Remove depth {}", remove.depth));
+                }
+                Tag::End => break,
+                _ => {}
+            }
+        }
+
+        // A clip that is still loading may end without a `ShowFrame`.
+        if frames.len() < usize::from(clip.header_frames())
+            && (!frame.labels.is_empty()
+                || !frame.actions.is_empty()
+                || !frame.display_list_changes.is_empty())
+        {
+            frames.push(frame);
+        }
+
+        Self { tag_stream, frames }
+    }
+
+    /// Whether this wasn't decoded from `clip` as it is now, because it is
+    /// another clip, the clip was replaced with another movie, or more of it
+    /// has loaded since.
+    pub fn is_outdated(&self, clip: MovieClip<'_>) -> bool {
+        let tag_stream = clip.tag_stream();
+        !Arc::ptr_eq(&self.tag_stream.movie, &tag_stream.movie)
+            || self.tag_stream.start != tag_stream.start
+            || self.tag_stream.end != tag_stream.end
+    }
+}
+
+/// Lists the actions of a `DoAction` tag, one per line, prefixed with their offset.
+fn disassemble(data: &[u8], version: u8) -> Vec<String> {
+    let mut reader = Avm1Reader::new(data, version);
+    let mut lines = vec![];
+    while !reader.get_ref().is_empty() {
+        let offset = reader.get_ref().as_ptr() as usize - data.as_ptr() as usize;
+        match reader.read_action() {
+            Ok(action) => lines.push(format!("{offset:>5}: {action:?}")),
+            Err(e) => {
+                lines.push(format!("{offset:>5}: <error: {e}>"));
+                break;
+            }
+        }
+    }
+    lines
+}
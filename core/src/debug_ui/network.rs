@@ -0,0 +1,402 @@
+use crate::backend::navigator::FetchReason;
+use crate::context::UpdateContext;
+use crate::debug_ui::{ItemToSave, Message};
+use crate::network_log::{
+    BodyPreview, FetchEntry, FetchStatus, NetworkEntry, NetworkEntryKind, SocketEvent,
+};
+use egui::{Align, CollapsingHeader, Grid, Layout, ScrollArea, TextEdit, Ui, Window};
+use egui_extras::{Column, TableBuilder};
+use std::fmt::Write as _;
+use web_time::Duration;
+
+/// How many bytes of a binary body are shown as a hex dump.
+const MAX_HEX_DUMP_BYTES: usize = 1024;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+enum EntryFilter {
+    #[default]
+    All,
+    Fetches,
+    Sockets,
+    LocalConnections,
+}
+
+impl EntryFilter {
+    fn matches(self, kind: &NetworkEntryKind) -> bool {
+        matches!(
+            (self, kind),
+            (EntryFilter::All, _)
+                | (EntryFilter::Fetches, NetworkEntryKind::Fetch(_))
+                | (EntryFilter::Sockets, NetworkEntryKind::Socket(_))
+                | (
+                    EntryFilter::LocalConnections,
+                    NetworkEntryKind::LocalConnection(_)
+                )
+        )
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct NetworkWindow {
+    filter: EntryFilter,
+    search: String,
+    selected: Option<u64>,
+}
+
+impl NetworkWindow {
+    pub fn show(
+        &mut self,
+        egui_ctx: &egui::Context,
+        context: &mut UpdateContext,
+        messages: &mut Vec<Message>,
+    ) -> bool {
+        let mut keep_open = true;
+        let log = context.network_log;
+
+        Window::new("Network Activity")
+            .open(&mut keep_open)
+            .default_width(700.0)
+            .show(egui_ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.selectable_value(&mut self.filter, EntryFilter::All, "All");
+                    ui.selectable_value(&mut self.filter, EntryFilter::Fetches, "Fetches");
+                    ui.selectable_value(&mut self.filter, EntryFilter::Sockets, "Sockets");
+                    ui.selectable_value(
+                        &mut self.filter,
+                        EntryFilter::LocalConnections,
+                        "LocalConnections",
+                    );
+                    ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                        if ui.button("Export HAR...").clicked() {
+                            match serde_json::to_vec_pretty(&log.to_har()) {
+                                Ok(data) => messages.push(Message::SaveFile(ItemToSave {
+                                    suggested_name: "network.har".to_string(),
+                                    data,
+                                })),
+                                Err(e) => tracing::error!("Couldn't create HAR: {e}"),
+                            }
+                        }
+                        if ui.button("Clear").clicked() {
+                            log.clear();
+                            self.selected = None;
+                        }
+                        ui.add_sized(
+                            ui.available_size(),
+                            TextEdit::singleline(&mut self.search).hint_text("Filter..."),
+                        );
+                    });
+                });
+                ui.separator();
+
+                log.with_entries(|entries| {
+                    let search = self.search.to_ascii_lowercase();
+                    let shown: Vec<&NetworkEntry> = entries
+                        .iter()
+                        .filter(|entry| self.filter.matches(&entry.kind))
+                        .filter(|entry| {
+                            search.is_empty()
+                                || summary(entry).to_ascii_lowercase().contains(&search)
+                        })
+                        .collect();
+
+                    let first = entries.front();
+                    ui.push_id("entries", |ui| {
+                        self.show_entries(ui, first, &shown);
+                    });
+
+                    if let Some(entry) = self
+                        .selected
+                        .and_then(|id| entries.iter().find(|entry| entry.id == id))
+                    {
+                        ui.separator();
+                        ScrollArea::vertical()
+                            .id_salt("details")
+                            .show(ui, |ui| show_details(ui, entry));
+                    }
+                });
+            });
+
+        keep_open
+    }
+
+    fn show_entries(
+        &mut self,
+        ui: &mut Ui,
+        first: Option<&NetworkEntry>,
+        entries: &[&NetworkEntry],
+    ) {
+        TableBuilder::new(ui)
+            .striped(true)
+            .resizable(true)
+            .sense(egui::Sense::click())
+            .max_scroll_height(250.0)
+            .column(Column::exact(60.0))
+            .column(Column::auto())
+            .column(Column::remainder().clip(true))
+            .column(Column::auto())
+            .column(Column::auto())
+            .column(Column::auto())
+            .auto_shrink([false, true])
+            .cell_layout(Layout::left_to_right(Align::Center))
+            .header(20.0, |mut header| {
+                for title in ["Time", "Type", "Summary", "Status", "Size", "Duration"] {
+                    header.col(|ui| {
+                        ui.strong(title);
+                    });
+                }
+            })
+            .body(|body| {
+                body.rows(18.0, entries.len(), |mut row| {
+                    let entry = entries[row.index()];
+                    row.set_selected(self.selected == Some(entry.id));
+                    row.col(|ui| {
+                        let offset = first.map_or(Duration::ZERO, |first| entry.offset_from(first));
+                        ui.label(format!("{:.2}s", offset.as_secs_f64()));
+                    });
+                    row.col(|ui| {
+                        ui.label(kind_name(&entry.kind));
+                    });
+                    row.col(|ui| {
+                        ui.label(summary(entry));
+                    });
+                    row.col(|ui| {
+                        ui.label(status(&entry.kind));
+                    });
+                    row.col(|ui| {
+                        ui.label(size(&entry.kind));
+                    });
+                    row.col(|ui| {
+                        if let NetworkEntryKind::Fetch(FetchEntry {
+                            duration: Some(duration),
+                            ..
+                        }) = &entry.kind
+                        {
+                            ui.label(format!("{} ms", duration.as_millis()));
+                        }
+                    });
+                    if row.response().clicked() {
+                        self.selected = Some(entry.id);
+                    }
+                });
+            });
+    }
+}
+
+fn kind_name(kind: &NetworkEntryKind) -> &'static str {
+    match kind {
+        NetworkEntryKind::Fetch(fetch) if fetch.is_amf() => "Remoting",
+        NetworkEntryKind::Fetch(FetchEntry {
+            reason: FetchReason::LoadSwf,
+            ..
+        }) => "Load SWF",
+        NetworkEntryKind::Fetch(FetchEntry {
+            reason: FetchReason::UrlLoader,
+            ..
+        }) => "URLLoader",
+        NetworkEntryKind::Fetch(_) => "Fetch",
+        NetworkEntryKind::Socket(_) => "Socket",
+        NetworkEntryKind::LocalConnection(_) => "LocalConnection",
+    }
+}
+
+fn summary(entry: &NetworkEntry) -> String {
+    match &entry.kind {
+        NetworkEntryKind::Fetch(fetch) => format!("{} {}", fetch.method, fetch.url),
+        NetworkEntryKind::Socket(socket) => {
+            let event = match &socket.event {
+                SocketEvent::Connecting => "connecting",
+                SocketEvent::Connected => "connected",
+                SocketEvent::ConnectionFailed => "connection failed",
+                SocketEvent::Sent(_) => "sent",
+                SocketEvent::Received(_) => "received",
+                SocketEvent::Closed => "closed",
+            };
+            format!("{} {event}", socket.destination)
+        }
+        NetworkEntryKind::LocalConnection(message) => format!(
+            "{} {}.{}",
+            if message.incoming { "<-" } else { "->" },
+            message.connection_name,
+            message.method_name
+        ),
+    }
+}
+
+fn status(kind: &NetworkEntryKind) -> String {
+    match kind {
+        NetworkEntryKind::Fetch(fetch) => match &fetch.status {
+            FetchStatus::Pending => "Pending".to_string(),
+            FetchStatus::Response(status) => status.to_string(),
+            FetchStatus::Failed(_) => "Failed".to_string(),
+        },
+        _ => String::new(),
+    }
+}
+
+fn size(kind: &NetworkEntryKind) -> String {
+    let size = match kind {
+        NetworkEntryKind::Fetch(fetch) => fetch.response_body.size,
+        NetworkEntryKind::Socket(socket) => match &socket.event {
+            SocketEvent::Sent(data) | SocketEvent::Received(data) => data.size,
+            _ => return String::new(),
+        },
+        NetworkEntryKind::LocalConnection(_) => return String::new(),
+    };
+    format!("{size} B")
+}
+
+fn show_details(ui: &mut Ui, entry: &NetworkEntry) {
+    match &entry.kind {
+        NetworkEntryKind::Fetch(fetch) => {
+            Grid::new("fetch").num_columns(2).show(ui, |ui| {
+                ui.label("URL");
+                ui.label(&fetch.url);
+                ui.end_row();
+
+                if let Some(response_url) = &fetch.response_url {
+                    ui.label("Final URL");
+                    ui.label(response_url);
+                    ui.end_row();
+                }
+
+                ui.label("Method");
+                ui.label(&fetch.method);
+                ui.end_row();
+
+                ui.label("Reason");
+                ui.label(format!("{:?}", fetch.reason));
+                ui.end_row();
+
+                ui.label("Status");
+                ui.label(match &fetch.status {
+                    FetchStatus::Failed(error) => error.clone(),
+                    _ => status(&entry.kind),
+                });
+                ui.end_row();
+
+                ui.label("Started");
+                ui.label(entry.started.format("%H:%M:%S%.3f").to_string());
+                ui.end_row();
+
+                if let Some(wait) = fetch.wait {
+                    ui.label("Waiting");
+                    ui.label(format!("{} ms", wait.as_millis()));
+                    ui.end_row();
+                }
+                if let Some(duration) = fetch.duration {
+                    ui.label("Total");
+                    ui.label(format!("{} ms", duration.as_millis()));
+                    ui.end_row();
+                }
+            });
+
+            if !fetch.request_headers.is_empty() {
+                CollapsingHeader::new("Request Headers").show(ui, |ui| {
+                    Grid::new("request_headers").num_columns(2).show(ui, |ui| {
+                        for (name, value) in &fetch.request_headers {
+                            ui.label(name);
+                            ui.label(value);
+                            ui.end_row();
+                        }
+                    });
+                });
+            }
+            if let Some(body) = &fetch.request_body {
+                let title = match &fetch.request_content_type {
+                    Some(mime_type) => format!("Request Body ({mime_type})"),
+                    None => "Request Body".to_string(),
+                };
+                CollapsingHeader::new(title).show(ui, |ui| {
+                    show_body(ui, body, fetch.is_amf());
+                });
+            }
+            if fetch.response_body.size > 0 {
+                CollapsingHeader::new("Response Body")
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        show_body(ui, &fetch.response_body, fetch.is_amf());
+                    });
+            }
+        }
+        NetworkEntryKind::Socket(socket) => {
+            ui.label(summary(entry));
+            if let SocketEvent::Sent(data) | SocketEvent::Received(data) = &socket.event {
+                show_body(ui, data, false);
+            }
+        }
+        NetworkEntryKind::LocalConnection(message) => {
+            ui.label(summary(entry));
+            for (index, argument) in message.arguments.iter().enumerate() {
+                CollapsingHeader::new(format!("Argument {index}"))
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        ui.monospace(argument);
+                    });
+            }
+        }
+    }
+}
+
+/// Shows a body as a decoded AMF packet, as text, or as a hex dump, whichever fits.
+fn show_body(ui: &mut Ui, body: &BodyPreview, is_amf: bool) {
+    if body.is_truncated() {
+        ui.weak(format!(
+            "Showing the first {} of {} bytes",
+            body.data.len(),
+            body.size
+        ));
+    }
+
+    if is_amf {
+        match flash_lso::packet::read::parse(&body.data) {
+            Ok(packet) => {
+                for header in &packet.headers {
+                    ui.monospace(format!("Header {}: {:#?}", header.name, header.value));
+                }
+                for message in &packet.messages {
+                    ui.monospace(format!(
+                        "{} -> {}: {:#?}",
+                        message.target_uri, message.response_uri, message.contents
+                    ));
+                }
+                return;
+            }
+            Err(e) => {
+                ui.weak(format!("Couldn't decode AMF packet: {e:?}"));
+            }
+        }
+    }
+
+    match std::str::from_utf8(&body.data) {
+        Ok(text) => {
+            ui.monospace(text);
+        }
+        Err(_) => {
+            ui.monospace(hex_dump(&body.data));
+        }
+    }
+}
+
+fn hex_dump(data: &[u8]) -> String {
+    let mut out = String::new();
+    for (line, chunk) in data.chunks(16).take(MAX_HEX_DUMP_BYTES / 16).enumerate() {
+        let _ = write!(out, "{:08x} ", line * 16);
+        for byte in chunk {
+            let _ = write!(out, " {byte:02x}");
+        }
+        out.push_str(&"   ".repeat(16 - chunk.len()));
+        out.push_str("  ");
+        out.extend(chunk.iter().map(|&byte| {
+            if byte.is_ascii_graphic() || byte == b' ' {
+                byte as char
+            } else {
+                '.'
+            }
+        }));
+        out.push('\n');
+    }
+    if data.len() > MAX_HEX_DUMP_BYTES {
+        out.push_str("...");
+    }
+    out
+}
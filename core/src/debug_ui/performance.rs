@@ -0,0 +1,96 @@
+use crate::stats::PlayerStats;
+use egui::{Align2, Color32, Grid, Ui, Window};
+use web_time::Duration;
+
+/// A small overlay with the performance statistics of the player.
+#[derive(Debug, Default)]
+pub struct PerformanceHud {
+    stats: PlayerStats,
+}
+
+impl PerformanceHud {
+    pub fn update(&mut self, stats: PlayerStats) {
+        self.stats = stats;
+    }
+
+    pub fn show(&mut self, egui_ctx: &egui::Context) -> bool {
+        let mut keep_open = true;
+        let stats = &self.stats;
+
+        Window::new("Performance")
+            .open(&mut keep_open)
+            .anchor(Align2::RIGHT_TOP, [-8.0, 32.0])
+            .resizable(false)
+            .show(egui_ctx, |ui| {
+                Grid::new("performance_hud_grid")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.label("FPS");
+                        ui.colored_label(
+                            fps_color(stats.fps, stats.target_fps),
+                            format!("{} / {:.0}", stats.fps, stats.target_fps),
+                        );
+                        ui.end_row();
+
+                        duration_row(ui, "AVM1", stats.avm1_time);
+                        duration_row(ui, "AVM2", stats.avm2_time);
+                        duration_row(ui, "Render", stats.render_time);
+                        duration_row(ui, "Tessellation", stats.tessellation_time);
+
+                        ui.label("Render Commands");
+                        ui.label(stats.render_commands.to_string());
+                        ui.end_row();
+
+                        ui.label("Display Objects");
+                        ui.label(stats.display_objects.to_string());
+                        ui.end_row();
+
+                        ui.label("Bitmap Memory");
+                        match stats.bitmap_memory {
+                            Some(bytes) => ui.label(format_bytes(bytes)),
+                            None => ui.weak("Unknown"),
+                        };
+                        ui.end_row();
+
+                        ui.label("Sounds");
+                        ui.label(stats.audio_instances.to_string());
+                        ui.end_row();
+
+                        ui.label("GC Heap");
+                        ui.label(format_bytes(stats.gc_heap_size as u64));
+                        ui.end_row();
+                    });
+            });
+
+        keep_open
+    }
+}
+
+fn duration_row(ui: &mut Ui, name: &str, duration: Duration) {
+    ui.label(name);
+    ui.label(format!("{:.2} ms", duration.as_secs_f64() * 1000.0));
+    ui.end_row();
+}
+
+fn fps_color(fps: u32, target_fps: f64) -> Color32 {
+    let ratio = fps as f64 / target_fps;
+    if ratio >= 0.9 {
+        Color32::GREEN
+    } else if ratio >= 0.5 {
+        Color32::YELLOW
+    } else {
+        Color32::RED
+    }
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const KIB: f64 = 1024.0;
+    const MIB: f64 = KIB * 1024.0;
+
+    let bytes = bytes as f64;
+    if bytes >= MIB {
+        format!("{:.1} MiB", bytes / MIB)
+    } else {
+        format!("{:.1} KiB", bytes / KIB)
+    }
+}
@@ -0,0 +1,795 @@
+use crate::avm1::{
+    Activation as Avm1Activation, ActivationIdentifier, Object as Avm1Object, Value as Avm1Value,
+};
+use crate::avm2::property::Property;
+use crate::avm2::{
+    Activation as Avm2Activation, Domain, Object as Avm2Object, TObject as _, Value as Avm2Value,
+};
+use crate::context::UpdateContext;
+use crate::debug_ui::Message;
+use crate::debug_ui::handle::{AVM1ObjectHandle, AVM2ObjectHandle};
+use crate::display_object::{DisplayObject, TDisplayObject, TDisplayObjectContainer};
+use crate::string::AvmString;
+use egui::{Align, Layout, TextEdit, Ui, Window};
+use egui_extras::{Column, TableBuilder};
+use fnv::FnvHashSet;
+use gc_arena::Mutation;
+use ruffle_wstr::{WStr, WString};
+use std::collections::VecDeque;
+
+/// How many objects a single scan may visit, so that huge heaps don't hang the player.
+const MAX_OBJECTS: usize = 100_000;
+
+/// How many matches a scan keeps. Narrow the search to see the rest.
+const MAX_RESULTS: usize = 5_000;
+
+/// A value that can be searched for, compared between scans and frozen.
+#[derive(Debug, Clone, PartialEq)]
+enum ScanValue {
+    Bool(bool),
+    Number(f64),
+    String(String),
+}
+
+impl ScanValue {
+    fn from_avm1(value: Avm1Value<'_>) -> Option<Self> {
+        match value {
+            Avm1Value::Bool(value) => Some(Self::Bool(value)),
+            Avm1Value::Number(value) => Some(Self::Number(value)),
+            Avm1Value::String(value) => Some(Self::String(value.to_string())),
+            _ => None,
+        }
+    }
+
+    fn from_avm2(value: Avm2Value<'_>) -> Option<Self> {
+        match value {
+            Avm2Value::Bool(value) => Some(Self::Bool(value)),
+            Avm2Value::Number(value) => Some(Self::Number(value)),
+            Avm2Value::Integer(value) => Some(Self::Number(value as f64)),
+            Avm2Value::String(value) => Some(Self::String(value.to_string())),
+            _ => None,
+        }
+    }
+
+    fn to_avm1<'gc>(&self, activation: &Avm1Activation<'_, 'gc>) -> Avm1Value<'gc> {
+        match self {
+            Self::Bool(value) => Avm1Value::Bool(*value),
+            Self::Number(value) => Avm1Value::Number(*value),
+            Self::String(value) => AvmString::new_utf8(activation.gc(), value).into(),
+        }
+    }
+
+    fn to_avm2<'gc>(&self, mc: &Mutation<'gc>) -> Avm2Value<'gc> {
+        match self {
+            Self::Bool(value) => Avm2Value::Bool(*value),
+            Self::Number(value) => Avm2Value::Number(*value),
+            Self::String(value) => AvmString::new_utf8(mc, value).into(),
+        }
+    }
+
+    fn as_number(&self) -> Option<f64> {
+        match self {
+            Self::Number(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Parses `text` as a value of the same type as `self`.
+    fn parse_like(&self, text: &str) -> Option<Self> {
+        match self {
+            Self::Bool(_) => text.parse().ok().map(Self::Bool),
+            Self::Number(_) => text.trim().parse().ok().map(Self::Number),
+            Self::String(_) => Some(Self::String(text.to_string())),
+        }
+    }
+}
+
+impl std::fmt::Display for ScanValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Bool(value) => write!(f, "{value}"),
+            Self::Number(value) => write!(f, "{value}"),
+            Self::String(value) => write!(f, "{value:?}"),
+        }
+    }
+}
+
+/// Where a variable lives, so that it can be read and written again after the scan.
+#[derive(Debug, Clone, PartialEq)]
+enum Location {
+    Avm1 {
+        object: AVM1ObjectHandle,
+        name: WString,
+    },
+    Avm2Slot {
+        object: AVM2ObjectHandle,
+        slot_id: usize,
+    },
+    Avm2Dynamic {
+        object: AVM2ObjectHandle,
+        name: WString,
+    },
+}
+
+impl Location {
+    fn read(&self, context: &mut UpdateContext<'_>) -> Option<ScanValue> {
+        match self {
+            Location::Avm1 { object, name } => {
+                let object = object.fetch(context.dynamic_root);
+                let name = AvmString::new(context.gc(), name.clone());
+                let base_clip = context.stage.into();
+                let mut activation = Avm1Activation::from_nothing(
+                    context,
+                    ActivationIdentifier::root("Debug"),
+                    base_clip,
+                );
+                ScanValue::from_avm1(object.get_local_stored(name, &mut activation)?)
+            }
+            Location::Avm2Slot { object, slot_id } => {
+                let object = object.fetch(context.dynamic_root);
+                ScanValue::from_avm2(object.get_slot(*slot_id))
+            }
+            Location::Avm2Dynamic { object, name } => {
+                // Read straight from storage, as getters and `Proxy` would run
+                // scripts every frame.
+                let object = object.fetch(context.dynamic_root);
+                let name = AvmString::new(context.gc(), name.clone());
+                let value = match (object.as_array_storage(), name.parse::<usize>()) {
+                    (Some(array), Ok(index)) => array.get(index),
+                    _ => object.get_dynamic_property(name),
+                };
+                ScanValue::from_avm2(value?)
+            }
+        }
+    }
+
+    fn write(&self, context: &mut UpdateContext<'_>, value: &ScanValue) {
+        let result = match self {
+            Location::Avm1 { object, name } => {
+                let object = object.fetch(context.dynamic_root);
+                let name = AvmString::new(context.gc(), name.clone());
+                let base_clip = context.stage.into();
+                let mut activation = Avm1Activation::from_nothing(
+                    context,
+                    ActivationIdentifier::root("Debug"),
+                    base_clip,
+                );
+                let value = value.to_avm1(&activation);
+                object
+                    .set(name, value, &mut activation)
+                    .map_err(|e| e.to_string())
+            }
+            Location::Avm2Slot { object, slot_id } => {
+                let object = object.fetch(context.dynamic_root);
+                let domain = context.avm2.stage_domain();
+                let mut activation = Avm2Activation::from_domain(context, domain);
+                let value = value.to_avm2(activation.gc());
+                object
+                    .set_slot(*slot_id, value, &mut activation)
+                    .map_err(|e| format!("{e:?}"))
+            }
+            Location::Avm2Dynamic { object, name } => {
+                // Like reading, this skips setters and `Proxy`. Properties
+                // that have been deleted since aren't brought back.
+                let object = object.fetch(context.dynamic_root);
+                let mc = context.gc();
+                let name = AvmString::new(mc, name.clone());
+                let value = value.to_avm2(mc);
+                if let (Some(mut array), Ok(index)) =
+                    (object.as_array_storage_mut(mc), name.parse::<usize>())
+                {
+                    array.set(index, value);
+                } else if object.get_dynamic_property(name).is_some() {
+                    object.set_dynamic_property(name, value, mc);
+                }
+                Ok(())
+            }
+        };
+        if let Err(e) = result {
+            tracing::error!("Failed to set variable: {e}");
+        }
+    }
+}
+
+/// A variable found during a scan, before it has been rooted.
+enum Target<'gc> {
+    Avm1(Avm1Object<'gc>, AvmString<'gc>),
+    Avm2Slot(Avm2Object<'gc>, usize),
+    Avm2Dynamic(Avm2Object<'gc>, AvmString<'gc>),
+}
+
+#[derive(Debug)]
+struct VariableResult {
+    path: String,
+    location: Location,
+    /// The value seen by the last scan, which narrowing compares against.
+    scanned: ScanValue,
+}
+
+#[derive(Debug)]
+struct FrozenVariable {
+    path: String,
+    location: Location,
+    value: ScanValue,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+enum SearchBy {
+    #[default]
+    Name,
+    Value,
+}
+
+/// How a narrowing scan compares each result to the value seen by the previous scan.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+enum Narrowing {
+    #[default]
+    Equals,
+    Changed,
+    Unchanged,
+    Increased,
+    Decreased,
+}
+
+impl Narrowing {
+    const ALL: [Narrowing; 5] = [
+        Narrowing::Equals,
+        Narrowing::Changed,
+        Narrowing::Unchanged,
+        Narrowing::Increased,
+        Narrowing::Decreased,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            Narrowing::Equals => "Equals search value",
+            Narrowing::Changed => "Changed",
+            Narrowing::Unchanged => "Unchanged",
+            Narrowing::Increased => "Increased",
+            Narrowing::Decreased => "Decreased",
+        }
+    }
+}
+
+/// Finds AVM1 and AVM2 variables by name or by value, and lets them be edited or frozen.
+#[derive(Debug, Default)]
+pub struct VariableSearchWindow {
+    search_by: SearchBy,
+    query: String,
+    narrowing: Narrowing,
+    results: Vec<VariableResult>,
+    /// Whether the last scan stopped at `MAX_RESULTS` or `MAX_OBJECTS`.
+    truncated: bool,
+    has_scanned: bool,
+    frozen: Vec<FrozenVariable>,
+    edited: Option<usize>,
+    value_edit_buf: String,
+}
+
+impl VariableSearchWindow {
+    pub fn show(
+        &mut self,
+        egui_ctx: &egui::Context,
+        context: &mut UpdateContext,
+        messages: &mut Vec<Message>,
+    ) -> bool {
+        let mut keep_open = true;
+
+        Window::new("Variable Search")
+            .open(&mut keep_open)
+            .default_width(500.0)
+            .show(egui_ctx, |ui| {
+                self.show_search_controls(ui, context);
+                if !self.frozen.is_empty() {
+                    ui.separator();
+                    self.show_frozen(ui, context);
+                }
+                if self.has_scanned {
+                    ui.separator();
+                    self.show_results(ui, context, messages);
+                }
+            });
+
+        keep_open
+    }
+
+    /// Writes every frozen value back, so that scripts can't change them.
+    pub fn apply_frozen(&self, context: &mut UpdateContext) {
+        for variable in &self.frozen {
+            if variable.location.read(context).as_ref() != Some(&variable.value) {
+                variable.location.write(context, &variable.value);
+            }
+        }
+    }
+
+    fn show_search_controls(&mut self, ui: &mut Ui, context: &mut UpdateContext) {
+        ui.horizontal(|ui| {
+            ui.selectable_value(&mut self.search_by, SearchBy::Name, "By Name");
+            ui.selectable_value(&mut self.search_by, SearchBy::Value, "By Value");
+            ui.add(
+                TextEdit::singleline(&mut self.query).hint_text(match self.search_by {
+                    SearchBy::Name => "Part of a property name",
+                    SearchBy::Value => "A number or string",
+                }),
+            );
+        });
+        ui.horizontal(|ui| {
+            if ui.button("New Scan").clicked() {
+                self.scan(context);
+            }
+            ui.add_enabled_ui(!self.results.is_empty(), |ui| {
+                if ui.button("Narrow").clicked() {
+                    self.narrow(context);
+                }
+                egui::ComboBox::from_id_salt("narrowing")
+                    .selected_text(self.narrowing.label())
+                    .show_ui(ui, |ui| {
+                        for narrowing in Narrowing::ALL {
+                            ui.selectable_value(&mut self.narrowing, narrowing, narrowing.label());
+                        }
+                    });
+            });
+        });
+    }
+
+    fn show_frozen(&mut self, ui: &mut Ui, context: &mut UpdateContext) {
+        ui.strong("Frozen");
+        self.frozen.retain_mut(|variable| {
+            let mut keep = true;
+            ui.horizontal(|ui| {
+                if ui.button("Unfreeze").clicked() {
+                    keep = false;
+                }
+                ui.label(&variable.path);
+                let mut text = variable.value.to_string();
+                if let ScanValue::String(value) = &variable.value {
+                    text = value.clone();
+                }
+                if ui
+                    .add(TextEdit::singleline(&mut text).desired_width(96.0))
+                    .changed()
+                    && let Some(value) = variable.value.parse_like(&text)
+                {
+                    variable.location.write(context, &value);
+                    variable.value = value;
+                }
+            });
+            keep
+        });
+    }
+
+    fn show_results(
+        &mut self,
+        ui: &mut Ui,
+        context: &mut UpdateContext,
+        messages: &mut Vec<Message>,
+    ) {
+        ui.label(format!("{} results", self.results.len()));
+        if self.truncated {
+            ui.label("The scan stopped early; narrow the search to see more.");
+        }
+
+        let mut freeze = None;
+        TableBuilder::new(ui)
+            .striped(true)
+            .resizable(true)
+            .column(Column::remainder())
+            .column(Column::auto())
+            .column(Column::auto())
+            .column(Column::exact(60.0))
+            .auto_shrink([true, true])
+            .cell_layout(Layout::left_to_right(Align::Center))
+            .header(20.0, |mut header| {
+                header.col(|ui| {
+                    ui.strong("Path");
+                });
+                header.col(|ui| {
+                    ui.strong("Previous");
+                });
+                header.col(|ui| {
+                    ui.strong("Current");
+                });
+                header.col(|_ui| {});
+            })
+            .body(|body| {
+                body.rows(18.0, self.results.len(), |mut row| {
+                    let index = row.index();
+                    let result = &self.results[index];
+                    let current = result.location.read(context);
+                    row.col(|ui| {
+                        if ui
+                            .link(&result.path)
+                            .on_hover_text("Inspect the object that holds this variable")
+                            .clicked()
+                        {
+                            messages.push(match &result.location {
+                                Location::Avm1 { object, .. } => {
+                                    Message::TrackAVM1Object(object.clone())
+                                }
+                                Location::Avm2Slot { object, .. }
+                                | Location::Avm2Dynamic { object, .. } => {
+                                    Message::TrackAVM2Object(object.clone())
+                                }
+                            });
+                        }
+                    });
+                    row.col(|ui| {
+                        ui.label(result.scanned.to_string());
+                    });
+                    row.col(|ui| {
+                        let Some(current) = &current else {
+                            ui.weak("(gone)");
+                            return;
+                        };
+                        if self.edited == Some(index) {
+                            ui.add(
+                                TextEdit::singleline(&mut self.value_edit_buf).desired_width(96.0),
+                            );
+                            if ui.button("Set").clicked() {
+                                if let Some(value) = current.parse_like(&self.value_edit_buf) {
+                                    result.location.write(context, &value);
+                                    if let Some(frozen) = self
+                                        .frozen
+                                        .iter_mut()
+                                        .find(|f| f.location == result.location)
+                                    {
+                                        frozen.value = value;
+                                    }
+                                }
+                                self.edited = None;
+                            }
+                            if ui.button("Cancel").clicked() {
+                                self.edited = None;
+                            }
+                        } else {
+                            ui.label(current.to_string());
+                            if ui.small_button("Edit").clicked() {
+                                self.edited = Some(index);
+                                self.value_edit_buf = match current {
+                                    ScanValue::String(value) => value.clone(),
+                                    value => value.to_string(),
+                                };
+                            }
+                        }
+                    });
+                    row.col(|ui| {
+                        let mut frozen = self.frozen.iter().any(|f| f.location == result.location);
+                        if ui.checkbox(&mut frozen, "Freeze").changed() {
+                            freeze = Some((index, frozen, current));
+                        }
+                    });
+                });
+            });
+
+        if let Some((index, frozen, current)) = freeze {
+            let result = &self.results[index];
+            if frozen {
+                if let Some(value) = current {
+                    self.frozen.push(FrozenVariable {
+                        path: result.path.clone(),
+                        location: result.location.clone(),
+                        value,
+                    });
+                }
+            } else {
+                self.frozen.retain(|f| f.location != result.location);
+            }
+        }
+    }
+
+    fn query_value(&self) -> Option<ScanValue> {
+        if self.query.is_empty() {
+            return None;
+        }
+        Some(match self.query.trim().parse::<f64>() {
+            Ok(number) => ScanValue::Number(number),
+            Err(_) => ScanValue::String(self.query.clone()),
+        })
+    }
+
+    fn value_matches(query: &ScanValue, text: &str, value: &ScanValue) -> bool {
+        match value {
+            ScanValue::String(value) => value == text,
+            value => value == query,
+        }
+    }
+
+    fn scan(&mut self, context: &mut UpdateContext) {
+        let name_query = self.query.to_lowercase();
+        let value_query = self.query_value();
+        let search_by = self.search_by;
+        let text = self.query.as_str();
+        let matches = |name: &str, value: &ScanValue| match search_by {
+            SearchBy::Name => !name_query.is_empty() && name.to_lowercase().contains(&name_query),
+            SearchBy::Value => value_query
+                .as_ref()
+                .is_some_and(|query| Self::value_matches(query, text, value)),
+        };
+
+        let mut scanner = Scanner {
+            matches: &matches,
+            found: Vec::new(),
+            truncated: false,
+        };
+
+        let mut display_objects = Vec::new();
+        collect_display_objects(context.stage.into(), &mut display_objects);
+
+        let avm1_roots = {
+            let mut roots: Vec<_> = display_objects
+                .iter()
+                .filter_map(|object| Some((object.path().to_string(), object.object1()?)))
+                .collect();
+            let base_clip = context.stage.into();
+            let activation = Avm1Activation::from_nothing(
+                context,
+                ActivationIdentifier::root("Debug"),
+                base_clip,
+            );
+            roots.push(("_global".to_string(), activation.global_object()));
+            roots
+        };
+        {
+            let base_clip = context.stage.into();
+            let mut activation = Avm1Activation::from_nothing(
+                context,
+                ActivationIdentifier::root("Debug"),
+                base_clip,
+            );
+            scanner.scan_avm1(&mut activation, avm1_roots);
+        }
+
+        let mut avm2_roots: Vec<(String, Avm2Object)> = display_objects
+            .iter()
+            .filter_map(|object| Some((object.path().to_string(), object.object2()?.into())))
+            .collect();
+        let mut domains = vec![context.avm2.stage_domain()];
+        while let Some(domain) = domains.pop() {
+            collect_class_statics(context, domain, &mut avm2_roots);
+            domains.extend(domain.children(context.gc()));
+        }
+        {
+            let domain = context.avm2.stage_domain();
+            let mut activation = Avm2Activation::from_domain(context, domain);
+            scanner.scan_avm2(&mut activation, avm2_roots);
+        }
+
+        let Scanner {
+            found, truncated, ..
+        } = scanner;
+        self.results = found
+            .into_iter()
+            .map(|(path, target, scanned)| VariableResult {
+                path,
+                location: match target {
+                    Target::Avm1(object, name) => Location::Avm1 {
+                        object: AVM1ObjectHandle::new(context, object),
+                        name: name.as_wstr().into(),
+                    },
+                    Target::Avm2Slot(object, slot_id) => Location::Avm2Slot {
+                        object: AVM2ObjectHandle::new(context, object),
+                        slot_id,
+                    },
+                    Target::Avm2Dynamic(object, name) => Location::Avm2Dynamic {
+                        object: AVM2ObjectHandle::new(context, object),
+                        name: name.as_wstr().into(),
+                    },
+                },
+                scanned,
+            })
+            .collect();
+        self.truncated = truncated;
+        self.has_scanned = true;
+        self.edited = None;
+    }
+
+    fn narrow(&mut self, context: &mut UpdateContext) {
+        let query = self.query_value();
+        let text = self.query.as_str();
+        let narrowing = self.narrowing;
+        self.results.retain_mut(|result| {
+            let Some(current) = result.location.read(context) else {
+                return false;
+            };
+            let keep = match narrowing {
+                Narrowing::Equals => query
+                    .as_ref()
+                    .is_some_and(|query| Self::value_matches(query, text, &current)),
+                Narrowing::Changed => current != result.scanned,
+                Narrowing::Unchanged => current == result.scanned,
+                Narrowing::Increased => current
+                    .as_number()
+                    .zip(result.scanned.as_number())
+                    .is_some_and(|(current, previous)| current > previous),
+                Narrowing::Decreased => current
+                    .as_number()
+                    .zip(result.scanned.as_number())
+                    .is_some_and(|(current, previous)| current < previous),
+            };
+            result.scanned = current;
+            keep
+        });
+        self.edited = None;
+    }
+}
+
+/// Walks objects breadth-first, so that each variable is reported under its shortest path.
+struct Scanner<'a, 'gc> {
+    matches: &'a dyn Fn(&str, &ScanValue) -> bool,
+    found: Vec<(String, Target<'gc>, ScanValue)>,
+    truncated: bool,
+}
+
+impl<'gc> Scanner<'_, 'gc> {
+    fn report(&mut self, path: String, name: &str, target: Target<'gc>, value: ScanValue) {
+        if !(self.matches)(name, &value) {
+            return;
+        }
+        if self.found.len() >= MAX_RESULTS {
+            self.truncated = true;
+            return;
+        }
+        self.found.push((path, target, value));
+    }
+
+    fn scan_avm1(
+        &mut self,
+        activation: &mut Avm1Activation<'_, 'gc>,
+        roots: Vec<(String, Avm1Object<'gc>)>,
+    ) {
+        let mut visited = FnvHashSet::default();
+        let mut queue = VecDeque::from(roots);
+        while let Some((path, object)) = queue.pop_front() {
+            if !visited.insert(object.as_ptr()) {
+                continue;
+            }
+            if visited.len() > MAX_OBJECTS {
+                self.truncated = true;
+                break;
+            }
+            for key in object.get_keys(activation, true) {
+                if key.as_wstr() == WStr::from_units(b"__proto__") {
+                    continue;
+                }
+                let Some(value) = object.get_local_stored(key, activation) else {
+                    continue;
+                };
+                let child_path = format!("{path}.{key}");
+                match value {
+                    // Clips are reached through the display list instead.
+                    Avm1Value::MovieClip(_) => {}
+                    Avm1Value::Object(child) => {
+                        if child.as_function().is_none() {
+                            queue.push_back((child_path, child));
+                        }
+                    }
+                    value => {
+                        if let Some(value) = ScanValue::from_avm1(value) {
+                            let name = key.to_string();
+                            self.report(child_path, &name, Target::Avm1(object, key), value);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn scan_avm2(
+        &mut self,
+        activation: &mut Avm2Activation<'_, 'gc>,
+        roots: Vec<(String, Avm2Object<'gc>)>,
+    ) {
+        let mut visited = FnvHashSet::default();
+        let mut queue = VecDeque::from(roots);
+        while let Some((path, object)) = queue.pop_front() {
+            if !visited.insert(object.as_ptr()) {
+                continue;
+            }
+            if visited.len() > MAX_OBJECTS {
+                self.truncated = true;
+                break;
+            }
+
+            for (name, _, property) in object.vtable().resolved_traits().iter() {
+                let (Property::Slot { slot_id } | Property::ConstSlot { slot_id }) = *property
+                else {
+                    continue;
+                };
+                let value = object.get_slot(slot_id);
+                self.visit_avm2_value(
+                    &mut queue,
+                    format!("{path}.{name}"),
+                    name,
+                    Target::Avm2Slot(object, slot_id),
+                    value,
+                );
+            }
+
+            let base = object.base();
+            let mut index = base.get_next_enumerant(0);
+            while index != 0 {
+                let name = match base.get_enumerant_name(index) {
+                    Some(Avm2Value::String(name)) => Some(name),
+                    Some(Avm2Value::Number(number)) => {
+                        Some(AvmString::new_utf8(activation.gc(), number.to_string()))
+                    }
+                    _ => None,
+                };
+                let value = base.values().value_at(index as usize).copied();
+                if let (Some(name), Some(value)) = (name, value) {
+                    self.visit_avm2_value(
+                        &mut queue,
+                        format!("{path}.{name}"),
+                        name,
+                        Target::Avm2Dynamic(object, name),
+                        value,
+                    );
+                }
+                index = base.get_next_enumerant(index);
+            }
+
+            let elements: Vec<_> = object
+                .as_array_storage()
+                .map(|array| array.iter().collect())
+                .unwrap_or_default();
+            for (index, value) in elements.into_iter().enumerate() {
+                if let Some(value) = value {
+                    let name = AvmString::new_utf8(activation.gc(), index.to_string());
+                    self.visit_avm2_value(
+                        &mut queue,
+                        format!("{path}[{index}]"),
+                        name,
+                        Target::Avm2Dynamic(object, name),
+                        value,
+                    );
+                }
+            }
+        }
+    }
+
+    fn visit_avm2_value(
+        &mut self,
+        queue: &mut VecDeque<(String, Avm2Object<'gc>)>,
+        path: String,
+        name: AvmString<'gc>,
+        target: Target<'gc>,
+        value: Avm2Value<'gc>,
+    ) {
+        match value {
+            Avm2Value::Object(child) => {
+                // Clips are reached through the display list instead.
+                if child.as_display_object().is_none() {
+                    queue.push_back((path, child));
+                }
+            }
+            value => {
+                if let Some(value) = ScanValue::from_avm2(value) {
+                    self.report(path, &name.to_string(), target, value);
+                }
+            }
+        }
+    }
+}
+
+fn collect_display_objects<'gc>(object: DisplayObject<'gc>, out: &mut Vec<DisplayObject<'gc>>) {
+    out.push(object);
+    if let Some(container) = object.as_container() {
+        for child in container.iter_render_list() {
+            collect_display_objects(child, out);
+        }
+    }
+}
+
+/// Adds the class objects of every class in `domain`, whose slots hold static variables.
+fn collect_class_statics<'gc>(
+    context: &mut UpdateContext<'gc>,
+    domain: Domain<'gc>,
+    out: &mut Vec<(String, Avm2Object<'gc>)>,
+) {
+    for (_, _, class) in domain.classes().iter() {
+        let class_name = class.name().to_qualified_name(context.gc()).to_string();
+        for class_object in class.class_objects().iter() {
+            out.push((class_name.clone(), (*class_object).into()));
+        }
+    }
+}
@@ -0,0 +1,835 @@
+//! Interactive script debugger.
+//!
+//! The player itself only knows when to pause and how to describe its own
+//! state while paused. Talking to an actual debugger frontend (for example a
+//! Debug Adapter Protocol client) is left to a [`DebuggerConnection`] provided
+//! by the embedder through `PlayerBuilder::with_debugger`.
+//!
+//! Pausing is implemented by blocking inside the interpreter loop until the
+//! connection tells us to resume, so the whole player (including rendering)
+//! is frozen while paused, just like in the Flash Player debugger.
+//!
+//! Frontends running on the same thread as the player (such as the debug UI)
+//! can't work that way. Instead, when no connection is attached, stopping
+//! records a [`Snapshot`] of every following statement, which can be stepped
+//! through once the player has been suspended.
+
+use std::collections::VecDeque;
+use std::sync::mpsc::{Receiver, RecvTimeoutError, Sender, TryRecvError, channel};
+use std::time::Duration;
+use web_time::Instant;
+
+/// Roughly how many bytes of snapshots are recorded after stopping without a
+/// connection.
+const MAX_SNAPSHOT_BYTES: usize = 16 * 1024 * 1024;
+
+/// A location where execution should pause.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Breakpoint {
+    /// Pause when entering a method, or at a specific byte offset inside of
+    /// its body.
+    ///
+    /// For AVM2, the name is matched against the name shown in stack traces,
+    /// such as `com.example::Main/update`. For AVM1, it is matched against the
+    /// name of functions declared with one (`function update() {}`).
+    Method { name: String, offset: Option<u32> },
+
+    /// Pause when reaching a line of a source file.
+    ///
+    /// This only works for AVM2 code that was compiled with debugging
+    /// information (`debugfile` and `debugline` instructions).
+    Line { file: String, line: u32 },
+
+    /// Pause at the AVM1 action at the given byte offset into the tag data of
+    /// a movie, which starts right after the SWF header.
+    Action { offset: u32 },
+
+    /// Pause at the start of an AVM1 frame script.
+    ///
+    /// The clip is identified by its target path, such as `_level0.menu`, and
+    /// frames are numbered from 1.
+    FrameScript { clip: String, frame: u16 },
+}
+
+/// Requests sent from a debugger frontend to the player.
+#[derive(Clone, Debug)]
+pub enum DebuggerCommand {
+    /// Replace all breakpoints with the given ones.
+    SetBreakpoints(Vec<Breakpoint>),
+
+    /// Pause as soon as possible.
+    Pause,
+
+    /// Resume execution.
+    Continue,
+
+    /// Pause at the next statement, entering called methods.
+    StepIn,
+
+    /// Pause at the next statement of the current method or its callers.
+    StepOver,
+
+    /// Pause at the next statement after the current method has returned.
+    StepOut,
+
+    /// Request the current call stack. Only answered while paused.
+    StackTrace,
+
+    /// Request the variable scopes of a stack frame. Only answered while paused.
+    Scopes { frame: usize },
+
+    /// Request the children of a variable reference. Only answered while paused.
+    Variables { reference: u32 },
+
+    /// Remove all breakpoints, resume execution and detach the debugger.
+    Disconnect,
+}
+
+/// Why the player has paused.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum StopReason {
+    Breakpoint,
+    Step,
+    Pause,
+}
+
+/// A single frame of the call stack, innermost first.
+#[derive(Clone, Debug)]
+pub struct StackFrameInfo {
+    pub name: String,
+    pub file: Option<String>,
+    pub line: u32,
+
+    /// The byte offset of the next instruction, if known.
+    ///
+    /// For AVM1 this is relative to the movie's tag data (see
+    /// [`Breakpoint::Action`]), for AVM2 to the method body.
+    pub offset: Option<u32>,
+}
+
+/// A group of variables belonging to a stack frame.
+#[derive(Clone, Debug)]
+pub struct ScopeInfo {
+    pub name: String,
+    pub reference: u32,
+}
+
+/// A single inspected value.
+#[derive(Clone, Debug)]
+pub struct VariableInfo {
+    pub name: String,
+    pub value: String,
+    pub type_name: String,
+
+    /// The reference to use for `DebuggerCommand::Variables` to inspect the
+    /// children of this value, or `0` if it has none.
+    pub reference: u32,
+}
+
+/// Messages sent from the player to a debugger frontend.
+///
+/// `BreakpointHit` is sent right before stopping at a breakpoint, showing that
+/// it refers to existing code.
+#[derive(Clone, Debug)]
+pub enum DebuggerEvent {
+    Stopped { reason: StopReason },
+    Continued,
+    StackTrace(Vec<StackFrameInfo>),
+    Scopes(Vec<ScopeInfo>),
+    Variables(Vec<VariableInfo>),
+    BreakpointHit(Breakpoint),
+}
+
+/// The state of a VM when it stopped, recorded when no connection is attached.
+#[derive(Clone, Debug)]
+pub struct Snapshot {
+    pub reason: StopReason,
+
+    /// The call depth, to allow stepping over and out of calls.
+    pub depth: usize,
+    pub stack: Vec<StackFrameInfo>,
+
+    /// The scopes of the innermost frame along with their variables.
+    ///
+    /// Variable references can't be used anymore once the VM has resumed.
+    pub scopes: Vec<(ScopeInfo, Vec<VariableInfo>)>,
+}
+
+impl Snapshot {
+    /// An estimate of the memory used by this snapshot.
+    fn size(&self) -> usize {
+        let stack: usize = self
+            .stack
+            .iter()
+            .map(|frame| {
+                size_of::<StackFrameInfo>()
+                    + frame.name.len()
+                    + frame.file.as_ref().map_or(0, String::len)
+            })
+            .sum();
+        let scopes: usize = self
+            .scopes
+            .iter()
+            .map(|(scope, variables)| {
+                size_of::<(ScopeInfo, Vec<VariableInfo>)>()
+                    + scope.name.len()
+                    + variables
+                        .iter()
+                        .map(|variable| {
+                            size_of::<VariableInfo>()
+                                + variable.name.len()
+                                + variable.value.len()
+                                + variable.type_name.len()
+                        })
+                        .sum::<usize>()
+            })
+            .sum();
+        size_of::<Self>() + stack + scopes
+    }
+}
+
+/// The transport between the player and a debugger frontend.
+pub trait DebuggerConnection {
+    /// Returns the next pending command, without blocking.
+    fn try_recv_command(&mut self) -> Option<DebuggerCommand>;
+
+    /// Waits for the next command. Returns `None` if the frontend went away.
+    fn recv_command(&mut self) -> Option<DebuggerCommand>;
+
+    fn send_event(&mut self, event: DebuggerEvent);
+}
+
+/// A [`DebuggerConnection`] backed by a pair of channels, for frontends
+/// running on another thread.
+pub struct ChannelDebuggerConnection {
+    commands: Receiver<DebuggerCommand>,
+    events: Sender<DebuggerEvent>,
+}
+
+impl ChannelDebuggerConnection {
+    pub fn new(commands: Receiver<DebuggerCommand>, events: Sender<DebuggerEvent>) -> Self {
+        Self { commands, events }
+    }
+}
+
+impl DebuggerConnection for ChannelDebuggerConnection {
+    fn try_recv_command(&mut self) -> Option<DebuggerCommand> {
+        match self.commands.try_recv() {
+            Ok(command) => Some(command),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => Some(DebuggerCommand::Disconnect),
+        }
+    }
+
+    fn recv_command(&mut self) -> Option<DebuggerCommand> {
+        self.commands.recv().ok()
+    }
+
+    fn send_event(&mut self, event: DebuggerEvent) {
+        let _ = self.events.send(event);
+    }
+}
+
+/// Controls the debugger of a player running on another thread, for
+/// scripting and automated tests. See `Player::attach_debugger`.
+///
+/// Inspection methods are only answered while the player is paused, so they
+/// should be called after [`DebuggerClient::wait_for_stop`].
+pub struct DebuggerClient {
+    commands: Sender<DebuggerCommand>,
+    events: Receiver<DebuggerEvent>,
+
+    /// Stops that happened while waiting for another event.
+    stops: VecDeque<StopReason>,
+}
+
+impl DebuggerClient {
+    /// Creates a client, along with the connection to attach to the player.
+    pub fn new() -> (Self, ChannelDebuggerConnection) {
+        let (commands, command_receiver) = channel();
+        let (event_sender, events) = channel();
+        let client = Self {
+            commands,
+            events,
+            stops: VecDeque::new(),
+        };
+        (
+            client,
+            ChannelDebuggerConnection::new(command_receiver, event_sender),
+        )
+    }
+
+    pub fn set_breakpoints(&self, breakpoints: Vec<Breakpoint>) {
+        self.send(DebuggerCommand::SetBreakpoints(breakpoints));
+    }
+
+    pub fn pause(&self) {
+        self.send(DebuggerCommand::Pause);
+    }
+
+    pub fn resume(&self) {
+        self.send(DebuggerCommand::Continue);
+    }
+
+    pub fn step_in(&self) {
+        self.send(DebuggerCommand::StepIn);
+    }
+
+    pub fn step_over(&self) {
+        self.send(DebuggerCommand::StepOver);
+    }
+
+    pub fn step_out(&self) {
+        self.send(DebuggerCommand::StepOut);
+    }
+
+    /// Waits for the player to stop. Returns `None` if it didn't stop in
+    /// time, or if it went away.
+    pub fn wait_for_stop(&mut self, timeout: Duration) -> Option<StopReason> {
+        if let Some(reason) = self.stops.pop_front() {
+            return Some(reason);
+        }
+
+        let deadline = Instant::now() + timeout;
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            match self.events.recv_timeout(remaining) {
+                Ok(DebuggerEvent::Stopped { reason }) => return Some(reason),
+                Ok(_) => continue,
+                Err(RecvTimeoutError::Timeout | RecvTimeoutError::Disconnected) => return None,
+            }
+        }
+    }
+
+    pub fn stack_trace(&mut self) -> Vec<StackFrameInfo> {
+        self.send(DebuggerCommand::StackTrace);
+        self.wait_for_answer(|event| match event {
+            DebuggerEvent::StackTrace(frames) => Ok(frames),
+            event => Err(event),
+        })
+    }
+
+    pub fn scopes(&mut self, frame: usize) -> Vec<ScopeInfo> {
+        self.send(DebuggerCommand::Scopes { frame });
+        self.wait_for_answer(|event| match event {
+            DebuggerEvent::Scopes(scopes) => Ok(scopes),
+            event => Err(event),
+        })
+    }
+
+    pub fn variables(&mut self, reference: u32) -> Vec<VariableInfo> {
+        self.send(DebuggerCommand::Variables { reference });
+        self.wait_for_answer(|event| match event {
+            DebuggerEvent::Variables(variables) => Ok(variables),
+            event => Err(event),
+        })
+    }
+
+    /// Removes all breakpoints and lets the player run freely.
+    pub fn disconnect(self) {
+        self.send(DebuggerCommand::Disconnect);
+    }
+
+    fn send(&self, command: DebuggerCommand) {
+        let _ = self.commands.send(command);
+    }
+
+    fn wait_for_answer<T: Default>(
+        &mut self,
+        answer: impl Fn(DebuggerEvent) -> Result<T, DebuggerEvent>,
+    ) -> T {
+        while let Ok(event) = self.events.recv() {
+            match answer(event) {
+                Ok(answer) => return answer,
+                Err(DebuggerEvent::Stopped { reason }) => self.stops.push_back(reason),
+                Err(_) => {}
+            }
+        }
+        T::default()
+    }
+}
+
+/// Access to the state of a paused VM, used to answer inspection commands.
+pub trait PausedState {
+    fn stack_trace(&mut self) -> Vec<StackFrameInfo>;
+
+    fn scopes(&mut self, frame: usize) -> Vec<ScopeInfo>;
+
+    fn variables(&mut self, reference: u32) -> Vec<VariableInfo>;
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+enum StepMode {
+    #[default]
+    Run,
+    Pause,
+    Break,
+    In,
+    Over {
+        depth: usize,
+    },
+    Out {
+        depth: usize,
+    },
+}
+
+/// The debugger state of a player.
+#[derive(Default)]
+pub struct Debugger {
+    connection: Option<Box<dyn DebuggerConnection>>,
+
+    /// Whether an in-process frontend wants to stop without a connection.
+    local: bool,
+    snapshots: Vec<Snapshot>,
+    snapshot_bytes: usize,
+
+    breakpoints: Vec<Breakpoint>,
+
+    /// Incremented every time the breakpoints change, so that VMs can cache
+    /// which breakpoints apply to the code they are running.
+    breakpoints_generation: u32,
+
+    step: StepMode,
+}
+
+impl Debugger {
+    pub fn new(connection: Option<Box<dyn DebuggerConnection>>) -> Self {
+        Self {
+            connection,
+            ..Default::default()
+        }
+    }
+
+    /// Whether VMs should check for breakpoints and steps.
+    #[inline(always)]
+    pub fn is_attached(&self) -> bool {
+        self.connection.is_some() || self.local
+    }
+
+    /// Attaches a frontend, replacing the previous one.
+    pub fn attach(&mut self, connection: Box<dyn DebuggerConnection>) {
+        self.connection = Some(connection);
+        self.step = StepMode::Run;
+    }
+
+    /// Enables or disables stopping without a connection, recording
+    /// snapshots instead.
+    pub fn set_local(&mut self, local: bool) {
+        self.local = local;
+        if !local {
+            self.resume_local();
+        }
+    }
+
+    /// Snapshots recorded since stopping without a connection, oldest first.
+    pub fn snapshots(&self) -> &[Snapshot] {
+        &self.snapshots
+    }
+
+    /// Discards the recorded snapshots, so that the next stop records anew.
+    pub fn resume_local(&mut self) {
+        self.snapshots.clear();
+        self.snapshot_bytes = 0;
+        if self.connection.is_none() {
+            self.step = StepMode::Run;
+        }
+    }
+
+    /// Discards the recorded snapshots and resumes without a connection,
+    /// stopping again at the next statement matching `command`, which must be
+    /// a step command. Steps over and out of calls are relative to `depth`.
+    ///
+    /// This is used to step past the end of the recording.
+    pub fn step_local(&mut self, command: &DebuggerCommand, depth: usize) {
+        let step = match command {
+            DebuggerCommand::StepIn => StepMode::In,
+            DebuggerCommand::StepOver => StepMode::Over { depth },
+            DebuggerCommand::StepOut => StepMode::Out { depth },
+            _ => return,
+        };
+        self.resume_local();
+        if self.connection.is_none() {
+            self.step = step;
+        }
+    }
+
+    pub fn breakpoints(&self) -> &[Breakpoint] {
+        &self.breakpoints
+    }
+
+    pub fn set_breakpoints(&mut self, breakpoints: Vec<Breakpoint>) {
+        self.breakpoints = breakpoints;
+        self.breakpoints_generation = self.breakpoints_generation.wrapping_add(1);
+    }
+
+    /// Pauses at the next statement.
+    pub fn request_pause(&mut self) {
+        self.step = StepMode::Pause;
+    }
+
+    /// Pauses at the next statement, reporting it as a breakpoint hit.
+    ///
+    /// This is used for breakpoints that are checked when entering a script
+    /// rather than at a specific instruction.
+    pub fn break_at_next_statement(&mut self) {
+        self.step = StepMode::Break;
+    }
+
+    /// Cancels a pending [`Debugger::break_at_next_statement`], so that it
+    /// doesn't apply to unrelated code if the script turned out to be empty.
+    pub fn cancel_break(&mut self) {
+        if self.step == StepMode::Break {
+            self.step = StepMode::Run;
+        }
+    }
+
+    pub fn breakpoints_generation(&self) -> u32 {
+        self.breakpoints_generation
+    }
+
+    pub fn has_method_breakpoints(&self) -> bool {
+        self.breakpoints
+            .iter()
+            .any(|bp| matches!(bp, Breakpoint::Method { .. }))
+    }
+
+    /// Tells the frontend that the breakpoint with the given index into
+    /// [`Debugger::breakpoints`] was hit.
+    pub fn report_hit(&mut self, index: usize) {
+        if self.connection.is_some()
+            && let Some(breakpoint) = self.breakpoints.get(index)
+        {
+            let breakpoint = breakpoint.clone();
+            self.send(DebuggerEvent::BreakpointHit(breakpoint));
+        }
+    }
+
+    /// Handles any commands that arrived while running.
+    pub fn poll(&mut self) {
+        while let Some(command) = self
+            .connection
+            .as_mut()
+            .and_then(|connection| connection.try_recv_command())
+        {
+            match command {
+                DebuggerCommand::Pause => self.step = StepMode::Pause,
+                // Inspection and stepping only make sense while paused.
+                DebuggerCommand::StackTrace => self.send(DebuggerEvent::StackTrace(vec![])),
+                DebuggerCommand::Scopes { .. } => self.send(DebuggerEvent::Scopes(vec![])),
+                DebuggerCommand::Variables { .. } => self.send(DebuggerEvent::Variables(vec![])),
+                command => {
+                    self.handle_common_command(command);
+                }
+            }
+        }
+    }
+
+    /// Determines whether we should pause at a statement boundary at the
+    /// given call depth, due to a pause request or a pending step.
+    pub fn step_reason(&self, depth: usize, is_statement: bool) -> Option<StopReason> {
+        match self.step {
+            StepMode::Run => None,
+            StepMode::Pause => Some(StopReason::Pause),
+            StepMode::Break => Some(StopReason::Breakpoint),
+            StepMode::In if is_statement => Some(StopReason::Step),
+            StepMode::Over { depth: from } if is_statement && depth <= from => {
+                Some(StopReason::Step)
+            }
+            StepMode::Out { depth: from } if is_statement && depth < from => Some(StopReason::Step),
+            _ => None,
+        }
+    }
+
+    /// Pauses execution until the frontend resumes it, answering any
+    /// inspection commands using `state`.
+    ///
+    /// Without a connection, a snapshot is recorded instead and execution
+    /// continues right away.
+    pub fn pause(&mut self, reason: StopReason, depth: usize, state: &mut dyn PausedState) {
+        self.step = StepMode::Run;
+        if self.connection.is_none() {
+            self.record(reason, depth, state);
+            return;
+        }
+
+        self.send(DebuggerEvent::Stopped { reason });
+
+        loop {
+            let Some(command) = self
+                .connection
+                .as_mut()
+                .and_then(|connection| connection.recv_command())
+            else {
+                self.detach();
+                return;
+            };
+
+            let step = match command {
+                DebuggerCommand::StackTrace => {
+                    let frames = state.stack_trace();
+                    self.send(DebuggerEvent::StackTrace(frames));
+                    continue;
+                }
+                DebuggerCommand::Scopes { frame } => {
+                    let scopes = state.scopes(frame);
+                    self.send(DebuggerEvent::Scopes(scopes));
+                    continue;
+                }
+                DebuggerCommand::Variables { reference } => {
+                    let variables = state.variables(reference);
+                    self.send(DebuggerEvent::Variables(variables));
+                    continue;
+                }
+                // We're already paused.
+                DebuggerCommand::Pause => continue,
+                DebuggerCommand::Continue => StepMode::Run,
+                DebuggerCommand::StepIn => StepMode::In,
+                DebuggerCommand::StepOver => StepMode::Over { depth },
+                DebuggerCommand::StepOut => StepMode::Out { depth },
+                command => {
+                    self.handle_common_command(command);
+                    if self.is_attached() {
+                        continue;
+                    }
+                    return;
+                }
+            };
+
+            self.step = step;
+            self.send(DebuggerEvent::Continued);
+            return;
+        }
+    }
+
+    fn record(&mut self, reason: StopReason, depth: usize, state: &mut dyn PausedState) {
+        if self.snapshot_bytes >= MAX_SNAPSHOT_BYTES {
+            return;
+        }
+
+        let stack = state.stack_trace();
+        let scopes = state
+            .scopes(0)
+            .into_iter()
+            .map(|scope| {
+                let variables = state.variables(scope.reference);
+                (scope, variables)
+            })
+            .collect();
+        let snapshot = Snapshot {
+            reason,
+            depth,
+            stack,
+            scopes,
+        };
+        self.snapshot_bytes += snapshot.size();
+        self.snapshots.push(snapshot);
+
+        // Keep recording, so that the frontend can step through what
+        // happened next.
+        if self.snapshot_bytes < MAX_SNAPSHOT_BYTES {
+            self.step = StepMode::In;
+        }
+    }
+
+    fn handle_common_command(&mut self, command: DebuggerCommand) {
+        match command {
+            DebuggerCommand::SetBreakpoints(breakpoints) => self.set_breakpoints(breakpoints),
+            DebuggerCommand::Disconnect => self.detach(),
+            _ => {}
+        }
+    }
+
+    fn detach(&mut self) {
+        self.connection = None;
+        self.set_breakpoints(vec![]);
+        self.step = StepMode::Run;
+    }
+
+    fn send(&mut self, event: DebuggerEvent) {
+        if let Some(connection) = &mut self.connection {
+            connection.send_event(event);
+        }
+    }
+}
+
+/// Parses a decimal or `0x`-prefixed hexadecimal offset, as entered in a
+/// debugger frontend.
+pub fn parse_offset(text: &str) -> Option<u32> {
+    match text.strip_prefix("0x") {
+        Some(hex) => u32::from_str_radix(hex, 16).ok(),
+        None => text.parse().ok(),
+    }
+}
+
+/// Checks whether a `debugfile` name refers to the file requested by a
+/// debugger frontend.
+///
+/// Compilers emit names like `C:\project\src;com\example;Main.as`, where the
+/// part after the first `;` is relative to the source root. Frontends usually
+/// send absolute paths, so we compare by suffix.
+pub fn source_matches(debug_file: &str, requested: &str) -> bool {
+    let debug_file = debug_file.replace('\\', "/");
+    let requested = requested.replace('\\', "/");
+
+    let relative = match debug_file.split_once(';') {
+        Some((_root, relative)) => relative.replace(';', "/"),
+        None => debug_file,
+    };
+
+    requested == relative || requested.ends_with(&format!("/{relative}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_source_matches() {
+        assert!(source_matches(
+            r"C:\project\src;com\example;Main.as",
+            "/home/user/project/src/com/example/Main.as"
+        ));
+        assert!(source_matches(
+            r"C:\project\src;com\example;Main.as",
+            r"D:\checkout\src\com\example\Main.as"
+        ));
+        assert!(source_matches("Main.as", "/src/Main.as"));
+        assert!(!source_matches(
+            r"C:\project\src;com\example;Main.as",
+            "/home/user/project/src/Main.as"
+        ));
+        assert!(!source_matches("Main.as", "/src/OtherMain.as"));
+    }
+
+    struct TestState;
+
+    impl PausedState for TestState {
+        fn stack_trace(&mut self) -> Vec<StackFrameInfo> {
+            vec![StackFrameInfo {
+                name: "[Frame]".to_string(),
+                file: None,
+                line: 0,
+                offset: Some(42),
+            }]
+        }
+
+        fn scopes(&mut self, _frame: usize) -> Vec<ScopeInfo> {
+            vec![ScopeInfo {
+                name: "Locals".to_string(),
+                reference: 1,
+            }]
+        }
+
+        fn variables(&mut self, _reference: u32) -> Vec<VariableInfo> {
+            vec![VariableInfo {
+                name: "x".to_string(),
+                value: "1".to_string(),
+                type_name: "number".to_string(),
+                reference: 0,
+            }]
+        }
+    }
+
+    #[test]
+    fn test_client_inspects_and_steps() {
+        let (mut client, connection) = DebuggerClient::new();
+
+        let player = std::thread::spawn(move || {
+            let mut debugger = Debugger::new(Some(Box::new(connection)));
+            debugger.pause(StopReason::Breakpoint, 1, &mut TestState);
+            debugger.step_reason(1, true)
+        });
+
+        let timeout = Duration::from_secs(10);
+        assert_eq!(client.wait_for_stop(timeout), Some(StopReason::Breakpoint));
+        assert_eq!(client.stack_trace()[0].offset, Some(42));
+        assert_eq!(client.scopes(0)[0].reference, 1);
+        assert_eq!(client.variables(1)[0].value, "1");
+        client.step_over();
+
+        assert_eq!(player.join().unwrap(), Some(StopReason::Step));
+    }
+
+    #[test]
+    fn test_report_hit() {
+        let (_commands, command_receiver) = channel();
+        let (event_sender, events) = channel();
+        let connection = ChannelDebuggerConnection::new(command_receiver, event_sender);
+        let mut debugger = Debugger::new(Some(Box::new(connection)));
+
+        let breakpoint = Breakpoint::Action { offset: 42 };
+        debugger.set_breakpoints(vec![breakpoint.clone()]);
+        debugger.report_hit(0);
+        debugger.report_hit(1);
+
+        assert!(matches!(
+            events.try_recv(),
+            Ok(DebuggerEvent::BreakpointHit(hit)) if hit == breakpoint
+        ));
+        assert!(events.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_local_recording() {
+        let mut debugger = Debugger::new(None);
+        assert!(!debugger.is_attached());
+
+        debugger.set_local(true);
+        assert!(debugger.is_attached());
+        assert_eq!(debugger.step_reason(0, true), None);
+
+        debugger.break_at_next_statement();
+        assert_eq!(debugger.step_reason(0, true), Some(StopReason::Breakpoint));
+        debugger.pause(StopReason::Breakpoint, 0, &mut TestState);
+
+        // Recording continues with every statement.
+        assert_eq!(debugger.step_reason(1, true), Some(StopReason::Step));
+        debugger.pause(StopReason::Step, 1, &mut TestState);
+
+        let snapshots = debugger.snapshots();
+        assert_eq!(snapshots.len(), 2);
+        assert_eq!(snapshots[0].reason, StopReason::Breakpoint);
+        assert_eq!(snapshots[1].depth, 1);
+        assert_eq!(snapshots[1].scopes[0].1[0].name, "x");
+
+        debugger.resume_local();
+        assert!(debugger.snapshots().is_empty());
+        assert_eq!(debugger.step_reason(1, true), None);
+    }
+
+    #[test]
+    fn test_local_step() {
+        let mut debugger = Debugger::new(None);
+        debugger.set_local(true);
+        debugger.request_pause();
+        debugger.pause(StopReason::Pause, 2, &mut TestState);
+
+        debugger.step_local(&DebuggerCommand::StepOut, 2);
+        assert!(debugger.snapshots().is_empty());
+        assert_eq!(debugger.step_reason(2, true), None);
+        assert_eq!(debugger.step_reason(1, true), Some(StopReason::Step));
+    }
+
+    #[test]
+    fn test_cancel_break() {
+        let mut debugger = Debugger::new(None);
+        debugger.set_local(true);
+
+        debugger.break_at_next_statement();
+        debugger.cancel_break();
+        assert_eq!(debugger.step_reason(0, true), None);
+
+        // Other steps are left alone.
+        debugger.request_pause();
+        debugger.cancel_break();
+        assert_eq!(debugger.step_reason(0, true), Some(StopReason::Pause));
+    }
+
+    #[test]
+    fn test_parse_offset() {
+        assert_eq!(parse_offset("42"), Some(42));
+        assert_eq!(parse_offset("0x2a"), Some(42));
+        assert_eq!(parse_offset("0xzz"), None);
+        assert_eq!(parse_offset(""), None);
+    }
+}
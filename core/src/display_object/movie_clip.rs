@@ -2278,6 +2278,11 @@ impl<'gc> MovieClip<'gc> {
         self.0.tag_stream_len()
     }
 
+    /// The tags making up this clip's timeline.
+    pub fn tag_stream(self) -> SwfSlice {
+        self.0.shared.get().swf.clone()
+    }
+
     pub fn forced_button_mode(self) -> bool {
         self.0.button_mode.get()
     }
@@ -3,6 +3,7 @@ use crate::input::InputEvent;
 use crate::string::{AvmString, StringContext};
 use enumset::EnumSetType;
 use ruffle_macros::istr;
+use std::fmt;
 use std::str::FromStr;
 use swf::ClipEventFlag;
 
@@ -39,6 +40,14 @@ pub enum PlayerEvent {
     GamepadButtonUp {
         button: GamepadButton,
     },
+    /// A key pressed through a gamepad mapping of the frontend, such as a
+    /// stick pushed past a threshold or a chord of buttons.
+    GamepadKeyDown {
+        key_code: KeyCode,
+    },
+    GamepadKeyUp {
+        key_code: KeyCode,
+    },
     TextInput {
         codepoint: char,
     },
@@ -884,6 +893,27 @@ impl FromStr for GamepadButton {
     }
 }
 
+impl fmt::Display for GamepadButton {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::South => "south",
+            Self::East => "east",
+            Self::North => "north",
+            Self::West => "west",
+            Self::LeftTrigger => "left-trigger",
+            Self::LeftTrigger2 => "left-trigger-2",
+            Self::RightTrigger => "right-trigger",
+            Self::RightTrigger2 => "right-trigger-2",
+            Self::Select => "select",
+            Self::Start => "start",
+            Self::DPadUp => "dpad-up",
+            Self::DPadDown => "dpad-down",
+            Self::DPadLeft => "dpad-left",
+            Self::DPadRight => "dpad-right",
+        })
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct KeyDescriptor {
     pub physical_key: PhysicalKey,
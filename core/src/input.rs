@@ -164,6 +164,16 @@ impl InputManager {
                     return None;
                 }
             }
+            PlayerEvent::GamepadKeyDown { key_code } => InputEvent::KeyDown {
+                key_code,
+                key_char: None,
+                key_location: KeyLocation::Standard,
+            },
+            PlayerEvent::GamepadKeyUp { key_code } => InputEvent::KeyUp {
+                key_code,
+                key_char: None,
+                key_location: KeyLocation::Standard,
+            },
 
             PlayerEvent::KeyDown { key } => {
                 self.keys_down_phys_loc
@@ -36,11 +36,14 @@ pub mod loader;
 mod local_connection;
 mod locale;
 mod net_connection;
+pub mod network_log;
 mod orphan_manager;
 pub mod pixel_bender;
 mod player;
 mod prelude;
+mod security;
 pub mod socket;
+pub mod stats;
 mod streams;
 pub mod string;
 mod system_properties;
@@ -55,9 +58,12 @@ pub mod compatibility_rules;
 pub mod config;
 #[cfg(feature = "egui")]
 pub mod debug_ui;
+#[cfg(feature = "debugger")]
+pub mod debugger;
 pub mod external;
 pub mod i18n;
 pub mod stub;
+pub mod telemetry;
 
 pub use context_menu::ContextMenuItem;
 pub use events::PlayerEvent;
@@ -33,7 +33,8 @@ use crate::display_object::{
 use crate::events::ClipEvent;
 use crate::limits::ExecutionLimit;
 use crate::player::{Player, PostFrameCallback};
-use crate::streams::{NetStream, NetStreamHandle};
+use crate::security::LoadPermission;
+use crate::streams::{FLV_HEADER_LENGTH, NetStream, NetStreamHandle};
 use crate::string::{AvmString, StringContext};
 use crate::tag_utils::SwfMovie;
 use crate::vminterface::Instantiator;
@@ -41,6 +42,7 @@ use chardetng::EncodingDetector;
 use encoding_rs::{UTF_8, WINDOWS_1252};
 use gc_arena::Collect;
 use indexmap::IndexMap;
+use ruffle_common::policy_file::PolicyFile;
 use ruffle_common::tag_utils::LoadBytesInfo;
 use ruffle_macros::istr;
 use ruffle_render::utils::{JpegTagFormat, determine_jpeg_tag_format};
@@ -208,6 +210,10 @@ pub enum Error {
 
     #[error("Cannot parse integer value: {0}")]
     ParseIntError(#[from] std::num::ParseIntError),
+
+    /// A load forbidden by the security sandbox, with the AVM2 error code and message.
+    #[error("{1}")]
+    SandboxViolation(u32, String),
 }
 
 impl From<crate::avm1::Error<'_>> for Error {
@@ -259,6 +265,31 @@ impl<'gc> LoadManager<'gc> {
         self.0.get_mut(handle)
     }
 
+    /// Cancel the download of a movie into the `Loader` of the given `LoaderInfo`.
+    ///
+    /// Returns `true` if a download was in progress.
+    pub fn cancel_avm2_load(&mut self, loader_info: LoaderInfoObject<'gc>) -> bool {
+        let handles: Vec<_> = self
+            .0
+            .iter()
+            .filter(|(_, loader)| {
+                matches!(loader.loader_status, LoaderStatus::Pending)
+                    && matches!(
+                        loader.vm_data,
+                        MovieLoaderVMData::Avm2 { loader_info: other, .. }
+                            if Avm2Object::ptr_eq(other, loader_info)
+                    )
+            })
+            .map(|(handle, _)| handle)
+            .collect();
+
+        for handle in &handles {
+            self.remove_loader(*handle);
+        }
+
+        !handles.is_empty()
+    }
+
     /// Checks if the target clip on the given handle's loader
     /// has been `avm1_removed()`. If so, it will set the `loader_status`
     /// to `Failed` and return `true`.
@@ -511,6 +542,122 @@ async fn wait_for_full_response(
     }
 }
 
+/// Fetch a cross-domain policy file, such as one announced with
+/// `Security.loadPolicyFile` or requested by `LoaderContext.checkPolicyFile`.
+pub fn load_policy_file(uc: &UpdateContext<'_>, url: String) -> OwnedFuture<(), Error> {
+    let player = uc.player_handle();
+
+    Box::pin(async move {
+        fetch_policy_file(&player, url).await;
+        Ok(())
+    })
+}
+
+/// Fetch a cross-domain policy file, and remember what it grants.
+async fn fetch_policy_file(player: &Arc<Mutex<Player>>, url: String) {
+    let fetch = player
+        .lock()
+        .unwrap()
+        .fetch(Request::get(url.clone()), FetchReason::Other);
+
+    let policy = match wait_for_full_response(fetch).await {
+        Ok((body, _, _, _)) => match PolicyFile::parse(&body) {
+            Ok(policy) => Some(policy),
+            Err(e) => {
+                tracing::warn!("Ignoring invalid policy file {url}: {e}");
+                None
+            }
+        },
+        Err(response) => {
+            tracing::info!("No policy file at {url}: {}", response.error);
+            None
+        }
+    };
+
+    player
+        .lock()
+        .unwrap()
+        .security_mut()
+        .store_policy_file(url, policy);
+}
+
+/// Check whether the `requester` movie may send `request`, fetching the
+/// policy files that may grant it access first.
+///
+/// This always succeeds when the security sandbox isn't enforced.
+async fn check_data_access(
+    player: &Arc<Mutex<Player>>,
+    requester: &SwfMovie,
+    request: &Request,
+) -> Result<(), Error> {
+    let url = request.url();
+    let denied = |code: u32| {
+        let requester_url = requester.url();
+        let message = crate::avm2::error_messages::try_error_message(code, &[&requester_url, &url])
+            .unwrap_or_else(|| format!("Error #{code}"));
+        Err(Error::SandboxViolation(code, message))
+    };
+
+    loop {
+        let permission = {
+            let mut player = player.lock().unwrap();
+            let security = player.security_mut();
+            if security.is_enabled() {
+                security.load_permission(requester, url)
+            } else {
+                LoadPermission::Allowed
+            }
+        };
+
+        match permission {
+            LoadPermission::Allowed => break,
+            LoadPermission::Denied(code) => return denied(code),
+            LoadPermission::NeedsPolicyFiles(policy_urls) => {
+                for policy_url in policy_urls {
+                    fetch_policy_file(player, policy_url).await;
+                }
+            }
+        }
+    }
+
+    let can_send_headers = {
+        let mut player = player.lock().unwrap();
+        let security = player.security_mut();
+        !security.is_enabled()
+            || security.can_send_headers(
+                requester,
+                url,
+                request.headers().keys().map(String::as_str),
+            )
+    };
+    if can_send_headers {
+        Ok(())
+    } else {
+        denied(2170)
+    }
+}
+
+/// Check whether the `requester` movie may load content, such as movies,
+/// images, sounds and videos, from `url`.
+///
+/// This always succeeds when the security sandbox isn't enforced.
+fn check_content_access(
+    player: &Arc<Mutex<Player>>,
+    requester: &SwfMovie,
+    url: &str,
+) -> Result<(), Error> {
+    let mut player = player.lock().unwrap();
+    let security = player.security_mut();
+    if !security.is_enabled() || security.can_load_content(requester, url) {
+        return Ok(());
+    }
+
+    let requester_url = requester.url();
+    let message = crate::avm2::error_messages::try_error_message(2148, &[&requester_url, &url])
+        .unwrap_or_else(|| "Error #2148".to_string());
+    Err(Error::SandboxViolation(2148, message))
+}
+
 /// The completion status of a `Loader` loading a movie.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum LoaderStatus {
@@ -693,6 +840,28 @@ impl<'gc> MovieLoader<'gc> {
             let request_url = request.url().to_string();
             let resolved_url = player.lock().unwrap().navigator().resolve_url(&request_url);
 
+            // AVM2 movies are checked as soon as `Loader.load` is called.
+            let avm1_requester =
+                player
+                    .lock()
+                    .unwrap()
+                    .update(|uc| match uc.load_manager.get_loader(handle) {
+                        Some(MovieLoader {
+                            vm_data: MovieLoaderVMData::Avm1 { base_clip, .. },
+                            ..
+                        }) => Some(base_clip.movie()),
+                        _ => None,
+                    });
+            if let Some(requester) = avm1_requester
+                && let Err(error) = check_content_access(&player, &requester, &request_url)
+            {
+                let response = ErrorResponse {
+                    url: request_url,
+                    error,
+                };
+                return Self::on_error(player.lock().unwrap(), handle, response);
+            }
+
             let fetch = player.lock().unwrap().fetch(request, FetchReason::LoadSwf);
 
             let mut replacing_root_movie = false;
@@ -982,11 +1151,16 @@ pub fn load_form_into_object<'gc>(
     uc: &UpdateContext<'gc>,
     target_object: Object<'gc>,
     request: Request,
+    requester: Arc<SwfMovie>,
 ) -> OwnedFuture<(), Error> {
     let player = uc.player_handle();
     let target_object = ObjectHandle::stash(uc, target_object);
 
     Box::pin(async move {
+        check_data_access(&player, &requester, &request)
+            .await
+            .inspect_err(|e| tracing::warn!("loadVariables blocked: {e}"))?;
+
         let fetch = player.lock().unwrap().fetch(request, FetchReason::Other);
 
         let response = fetch.await.map_err(|e| e.error)?;
@@ -1056,13 +1230,22 @@ pub fn load_form_into_load_vars<'gc>(
     uc: &UpdateContext<'gc>,
     target_object: Object<'gc>,
     request: Request,
+    requester: Arc<SwfMovie>,
 ) -> OwnedFuture<(), Error> {
     let player = uc.player_handle();
     let target_object = ObjectHandle::stash(uc, target_object);
 
     Box::pin(async move {
-        let fetch = player.lock().unwrap().fetch(request, FetchReason::Other);
-        let response = wait_for_full_response(fetch).await;
+        let response = match check_data_access(&player, &requester, &request).await {
+            Ok(()) => {
+                let fetch = player.lock().unwrap().fetch(request, FetchReason::Other);
+                wait_for_full_response(fetch).await
+            }
+            Err(error) => Err(ErrorResponse {
+                url: request.url().to_string(),
+                error,
+            }),
+        };
 
         // Fire the load handler.
         player.lock().unwrap().update(|uc| {
@@ -1152,13 +1335,22 @@ pub fn load_stylesheet<'gc>(
     uc: &UpdateContext<'gc>,
     target_object: Object<'gc>,
     request: Request,
+    requester: Arc<SwfMovie>,
 ) -> OwnedFuture<(), Error> {
     let player = uc.player_handle();
     let target_object = ObjectHandle::stash(uc, target_object);
 
     Box::pin(async move {
-        let fetch = player.lock().unwrap().fetch(request, FetchReason::Other);
-        let response = wait_for_full_response(fetch).await;
+        let response = match check_data_access(&player, &requester, &request).await {
+            Ok(()) => {
+                let fetch = player.lock().unwrap().fetch(request, FetchReason::Other);
+                wait_for_full_response(fetch).await
+            }
+            Err(error) => Err(ErrorResponse {
+                url: request.url().to_string(),
+                error,
+            }),
+        };
 
         // Fire the load handler.
         player.lock().unwrap().update(|uc| {
@@ -1215,11 +1407,26 @@ pub fn load_data_into_url_loader<'gc>(
     uc: &UpdateContext<'gc>,
     target: Avm2ScriptObject<'gc>,
     request: Request,
+    requester: Arc<SwfMovie>,
 ) -> OwnedFuture<(), Error> {
     let player = uc.player_handle();
     let target = Avm2ScriptObjectHandle::stash(uc, target);
 
     Box::pin(async move {
+        if let Err(Error::SandboxViolation(code, message)) =
+            check_data_access(&player, &requester, &request).await
+        {
+            tracing::warn!("URLLoader load blocked: {message}");
+            return player.lock().unwrap().update(|uc| {
+                let target = Avm2Object::from(target.fetch(uc));
+                let mut activation = Avm2Activation::from_nothing(uc);
+                let security_error_evt =
+                    Avm2EventObject::security_error_event(&mut activation, &message, code);
+                Avm2::dispatch_event(activation.context, security_error_evt, target);
+                Ok(())
+            });
+        }
+
         let fetch = player
             .lock()
             .unwrap()
@@ -1365,6 +1572,184 @@ pub fn load_data_into_url_loader<'gc>(
     })
 }
 
+/// Stream a download into a `URLStream`, making data available to read as
+/// it arrives.
+///
+/// `load_count` identifies the load of the stream that started this download.
+/// The download stops once the stream gets closed or starts another load.
+pub fn load_url_stream<'gc>(
+    uc: &UpdateContext<'gc>,
+    target: Avm2ScriptObject<'gc>,
+    request: Request,
+    load_count: u32,
+    requester: Arc<SwfMovie>,
+) -> OwnedFuture<(), Error> {
+    use crate::avm2::globals::slots::flash_net_url_stream as url_stream_slots;
+
+    fn is_current_load<'gc>(
+        activation: &mut Avm2Activation<'_, 'gc>,
+        target: Avm2Object<'gc>,
+        load_count: u32,
+    ) -> bool {
+        target
+            .get_slot(url_stream_slots::_LOAD_COUNT)
+            .coerce_to_u32(activation)
+            .is_ok_and(|count| count == load_count)
+    }
+
+    let player = uc.player_handle();
+    let target = Avm2ScriptObjectHandle::stash(uc, target);
+
+    Box::pin(async move {
+        if let Err(Error::SandboxViolation(code, message)) =
+            check_data_access(&player, &requester, &request).await
+        {
+            tracing::warn!("URLStream load blocked: {message}");
+            return player.lock().unwrap().update(|uc| {
+                let target = Avm2Object::from(target.fetch(uc));
+                let mut activation = Avm2Activation::from_nothing(uc);
+                if is_current_load(&mut activation, target, load_count) {
+                    let security_error_evt =
+                        Avm2EventObject::security_error_event(&mut activation, &message, code);
+                    Avm2::dispatch_event(activation.context, security_error_evt, target);
+                }
+                Ok(())
+            });
+        }
+
+        let fetch = player
+            .lock()
+            .unwrap()
+            .fetch(request, FetchReason::UrlLoader);
+
+        let mut response = match fetch.await {
+            Ok(response) => response,
+            Err(response) => {
+                tracing::error!(
+                    "Error during URLStream load of {:?}: {:?}",
+                    response.url,
+                    response.error
+                );
+
+                return player.lock().unwrap().update(|uc| {
+                    let target = Avm2Object::from(target.fetch(uc));
+                    let mut activation = Avm2Activation::from_nothing(uc);
+                    if !is_current_load(&mut activation, target, load_count) {
+                        return Ok(());
+                    }
+
+                    let (status_code, redirected) =
+                        if let Error::HttpNotOk(_, status_code, redirected, _) = response.error {
+                            (status_code, redirected)
+                        } else {
+                            (0, false)
+                        };
+                    let http_status_evt = Avm2EventObject::http_status_event(
+                        &mut activation,
+                        status_code,
+                        redirected,
+                    );
+                    Avm2::dispatch_event(activation.context, http_status_evt, target);
+
+                    let io_error_evt = Avm2EventObject::io_error_event(
+                        &mut activation,
+                        "Error #2032: Stream Error",
+                        2032,
+                    );
+                    Avm2::dispatch_event(activation.context, io_error_evt, target);
+                    Ok(())
+                });
+            }
+        };
+
+        let status = response.status();
+        let redirected = response.redirected();
+        let bytes_total = match response.expected_length() {
+            Ok(Some(len)) => len as usize,
+            _ => 0,
+        };
+        let mut bytes_loaded = 0;
+
+        let is_current = player.lock().unwrap().update(|uc| {
+            let target = Avm2Object::from(target.fetch(uc));
+            let mut activation = Avm2Activation::from_nothing(uc);
+            if !is_current_load(&mut activation, target, load_count) {
+                return false;
+            }
+
+            let open_evt = Avm2EventObject::bare_default_event(activation.context, "open");
+            Avm2::dispatch_event(activation.context, open_evt, target);
+
+            let http_status_evt =
+                Avm2EventObject::http_status_event(&mut activation, status, redirected);
+            Avm2::dispatch_event(activation.context, http_status_evt, target);
+            true
+        });
+
+        if !is_current {
+            return Ok(());
+        }
+
+        loop {
+            let chunk = response.next_chunk().await;
+            let is_done = player.lock().unwrap().update(|uc| {
+                let target = Avm2Object::from(target.fetch(uc));
+                let mut activation = Avm2Activation::from_nothing(uc);
+                if !is_current_load(&mut activation, target, load_count) {
+                    return true;
+                }
+
+                match chunk {
+                    Ok(Some(data)) => {
+                        let buffer = target.get_slot(url_stream_slots::_BUFFER).as_object();
+                        if let Some(mut buffer) = buffer.as_ref().and_then(|b| b.as_bytearray_mut())
+                        {
+                            // Data that was already read is of no use anymore.
+                            if buffer.bytes_available() == 0 {
+                                buffer.clear();
+                            }
+                            let len = buffer.len();
+                            if let Err(e) = buffer.write_at(&data, len) {
+                                tracing::error!("URLStream buffer overflowed: {:?}", e);
+                            }
+                        }
+
+                        bytes_loaded += data.len();
+                        let progress_evt = Avm2EventObject::progress_event(
+                            &mut activation,
+                            "progress",
+                            bytes_loaded,
+                            bytes_total.max(bytes_loaded),
+                        );
+                        Avm2::dispatch_event(activation.context, progress_evt, target);
+                        false
+                    }
+                    Ok(None) => {
+                        let complete_evt =
+                            Avm2EventObject::bare_default_event(activation.context, "complete");
+                        Avm2::dispatch_event(activation.context, complete_evt, target);
+                        true
+                    }
+                    Err(e) => {
+                        tracing::error!("Error during URLStream load: {:?}", e);
+                        let io_error_evt = Avm2EventObject::io_error_event(
+                            &mut activation,
+                            "Error #2032: Stream Error",
+                            2032,
+                        );
+                        Avm2::dispatch_event(activation.context, io_error_evt, target);
+                        true
+                    }
+                }
+            });
+
+            if is_done {
+                return Ok(());
+            }
+        }
+    })
+}
+
 /// Kick off an AVM1 audio load.
 ///
 /// Returns the loader's async process, which you will need to spawn.
@@ -1374,13 +1759,22 @@ pub fn load_sound_avm1<'gc>(
     sound_object: Object<'gc>,
     request: Request,
     is_streaming: bool,
+    requester: Arc<SwfMovie>,
 ) -> OwnedFuture<(), Error> {
     let player = uc.player_handle();
     let sound_object = ObjectHandle::stash(uc, sound_object);
 
     Box::pin(async move {
-        let fetch = player.lock().unwrap().fetch(request, FetchReason::Other);
-        let response = wait_for_full_response(fetch).await;
+        let response = match check_content_access(&player, &requester, request.url()) {
+            Ok(()) => {
+                let fetch = player.lock().unwrap().fetch(request, FetchReason::Other);
+                wait_for_full_response(fetch).await
+            }
+            Err(error) => Err(ErrorResponse {
+                url: request.url().to_string(),
+                error,
+            }),
+        };
 
         // Fire the load handler.
         player.lock().unwrap().update(|uc| {
@@ -1513,42 +1907,27 @@ pub fn load_netstream<'gc>(
     request: Request,
 ) -> OwnedFuture<(), Error> {
     let player = uc.player_handle();
+    let download_id = stream.download_id();
     let stream = NetStreamHandle::stash(uc, stream);
 
     Box::pin(async move {
         let fetch = player.lock().unwrap().fetch(request, FetchReason::Other);
         match fetch.await {
-            Ok(mut response) => {
-                let expected_length = response.expected_length();
-
-                player.lock().unwrap().update(|uc| -> Result<(), Error> {
-                    let stream = stream.fetch(uc);
-                    if let Ok(Some(len)) = expected_length {
-                        stream.set_expected_length(len as usize);
-                    }
-
-                    Ok(())
-                })?;
-
-                loop {
-                    let chunk = response.next_chunk().await;
-                    let is_end = matches!(chunk, Ok(None));
-                    player.lock().unwrap().update(|uc| -> Result<(), Error> {
-                        let stream = stream.fetch(uc);
-
-                        match chunk {
-                            Ok(Some(mut data)) => stream.load_buffer(uc, &mut data),
-                            Ok(None) => stream.finish_buffer(),
-                            Err(err) => stream.report_error(err),
-                        }
-                        Ok(())
-                    })?;
-
-                    if is_end {
-                        break;
-                    }
-                }
-
+            Ok(response) if response.status() < 400 => {
+                stream_netstream_response(player, stream, download_id, response, 0).await
+            }
+            Ok(response) => {
+                tracing::error!(
+                    "Error during netstream load of {:?}: HTTP status {}",
+                    response.url(),
+                    response.status()
+                );
+                netstream_load_failed(
+                    &player,
+                    &stream,
+                    download_id,
+                    "NetStream.Play.StreamNotFound",
+                );
                 Ok(())
             }
             Err(response) => {
@@ -1562,13 +1941,210 @@ pub fn load_netstream<'gc>(
                     let stream = stream.fetch(uc);
 
                     stream.report_error(response.error);
-                    Ok(())
-                })
+                });
+                netstream_load_failed(
+                    &player,
+                    &stream,
+                    download_id,
+                    "NetStream.Play.StreamNotFound",
+                );
+                Ok(())
+            }
+        }
+    })
+}
+
+/// Tell script code that a download of a `NetStream` failed, unless the
+/// stream moved on to something else in the meantime.
+fn netstream_load_failed(
+    player: &Mutex<Player>,
+    stream: &NetStreamHandle,
+    download_id: u32,
+    code: &str,
+) {
+    player.lock().unwrap().update(|uc| {
+        let stream = stream.fetch(uc);
+        if stream.download_id() == download_id {
+            stream.trigger_status_event(uc, [("code", code), ("level", "error")]);
+        }
+    });
+}
+
+/// Where the response to a range request for a file from `position` onwards
+/// lines up with the file, as its base offset, read offset and header length.
+///
+/// Returns `None` if the request failed.
+fn range_response_origin(status: u16, position: usize) -> Option<(usize, usize, usize)> {
+    match status {
+        206 => Some((position, 0, 0)),
+        400.. => None,
+        // The server ignored the range and sent the whole file.
+        _ => Some((0, position, 0)),
+    }
+}
+
+/// Returns true if the response to a pseudo-streaming request is the
+/// requested part of the file.
+///
+/// Servers without pseudo-streaming send the whole file instead, which can
+/// only be told apart by its length. Both lengths have to be known for that.
+fn is_pseudo_streaming_response(
+    status: u16,
+    expected_length: Option<u64>,
+    file_length: Option<usize>,
+) -> bool {
+    matches!(
+        (expected_length, file_length),
+        (Some(length), Some(file_length)) if status < 400 && length != file_length as u64
+    )
+}
+
+/// Download a `NetStream`'s file from `position` onwards, to serve a seek
+/// outside of the downloaded data.
+///
+/// This uses an HTTP range request. Servers that don't support those are
+/// asked for FLV pseudo-streaming (`?start=`) instead, if a
+/// `pseudo_streaming_start` is given. If neither works the whole file is
+/// downloaded again, and playback waits for it to reach `position`.
+pub fn load_netstream_range<'gc>(
+    uc: &UpdateContext<'gc>,
+    stream: NetStream<'gc>,
+    url: String,
+    position: usize,
+    pseudo_streaming_start: Option<usize>,
+    file_length: Option<usize>,
+) -> OwnedFuture<(), Error> {
+    let player = uc.player_handle();
+    let download_id = stream.download_id();
+    let stream = NetStreamHandle::stash(uc, stream);
+
+    Box::pin(async move {
+        let mut request = Request::get(url.clone());
+        request.set_headers(IndexMap::from([(
+            "Range".to_string(),
+            format!("bytes={position}-"),
+        )]));
+
+        let fetch = player.lock().unwrap().fetch(request, FetchReason::Other);
+        let mut response = match fetch.await {
+            Ok(response) => response,
+            Err(response) => {
+                tracing::error!(
+                    "Error during netstream seek request of {:?}: {:?}",
+                    response.url,
+                    response.error
+                );
+                netstream_load_failed(&player, &stream, download_id, "NetStream.Seek.Failed");
+                return Ok(());
+            }
+        };
+        let Some(mut origin) = range_response_origin(response.status(), position) else {
+            tracing::error!(
+                "Error during netstream seek request of {:?}: HTTP status {}",
+                response.url(),
+                response.status()
+            );
+            netstream_load_failed(&player, &stream, download_id, "NetStream.Seek.Failed");
+            return Ok(());
+        };
+
+        if response.status() != 206
+            && let Some(start) = pseudo_streaming_start
+            && let Ok(mut pseudo_streaming_url) = Url::parse(&url)
+        {
+            pseudo_streaming_url
+                .query_pairs_mut()
+                .append_pair("start", &start.to_string());
+            let request = Request::get(pseudo_streaming_url.into());
+            let fetch = player.lock().unwrap().fetch(request, FetchReason::Other);
+
+            if let Ok(pseudo_streaming_response) = fetch.await
+                && is_pseudo_streaming_response(
+                    pseudo_streaming_response.status(),
+                    pseudo_streaming_response.expected_length().ok().flatten(),
+                    file_length,
+                )
+            {
+                // The response is an FLV header, followed by the file from
+                // `start` onwards. Leaving out the header, the data lines up
+                // with the previous tag size in front of the tag at `start`.
+                response = pseudo_streaming_response;
+                origin = (position, 0, FLV_HEADER_LENGTH);
             }
         }
+
+        let (base_offset, read_offset, header_length) = origin;
+        player.lock().unwrap().update(|uc| -> Result<(), Error> {
+            let stream = stream.fetch(uc);
+            if stream.download_id() == download_id {
+                stream.set_download_origin(base_offset, read_offset);
+            }
+            Ok(())
+        })?;
+
+        stream_netstream_response(player, stream, download_id, response, header_length).await
     })
 }
 
+/// Feed the body of a response into a `NetStream` as it arrives.
+///
+/// The first `skip` bytes of the body are left out. This stops once the
+/// stream starts playing something else.
+async fn stream_netstream_response(
+    player: Arc<Mutex<Player>>,
+    stream: NetStreamHandle,
+    download_id: u32,
+    mut response: Box<dyn SuccessResponse>,
+    mut skip: usize,
+) -> Result<(), Error> {
+    let expected_length = response.expected_length();
+
+    let is_current = player.lock().unwrap().update(|uc| {
+        let stream = stream.fetch(uc);
+        if stream.download_id() != download_id {
+            return false;
+        }
+
+        if let Ok(Some(len)) = expected_length {
+            stream.set_expected_length((len as usize).saturating_sub(skip));
+        }
+        true
+    });
+
+    if !is_current {
+        return Ok(());
+    }
+
+    loop {
+        let chunk = response.next_chunk().await;
+        let is_end = matches!(chunk, Ok(None));
+        let is_current = player.lock().unwrap().update(|uc| {
+            let stream = stream.fetch(uc);
+            if stream.download_id() != download_id {
+                return false;
+            }
+
+            match chunk {
+                Ok(Some(mut data)) => {
+                    let skipped = skip.min(data.len());
+                    data.drain(..skipped);
+                    skip -= skipped;
+                    stream.load_buffer(uc, &mut data);
+                }
+                Ok(None) => stream.finish_buffer(uc),
+                Err(err) => stream.report_error(err),
+            }
+            true
+        });
+
+        if is_end || !is_current {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
 impl<'gc> MovieLoader<'gc> {
     /// Report a movie loader start event to script code.
     fn movie_loader_start(handle: LoaderHandle, uc: &mut UpdateContext<'gc>) -> Result<(), Error> {
@@ -2888,3 +3464,31 @@ pub fn upload_file<'gc>(
         })
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn range_responses() {
+        assert_eq!(range_response_origin(206, 1000), Some((1000, 0, 0)));
+        assert_eq!(range_response_origin(200, 1000), Some((0, 1000, 0)));
+        assert_eq!(range_response_origin(404, 1000), None);
+        assert_eq!(range_response_origin(416, 1000), None);
+        assert_eq!(range_response_origin(500, 1000), None);
+    }
+
+    #[test]
+    fn pseudo_streaming_responses() {
+        assert!(is_pseudo_streaming_response(200, Some(513), Some(2000)));
+
+        // The whole file, from a server without pseudo-streaming.
+        assert!(!is_pseudo_streaming_response(200, Some(2000), Some(2000)));
+
+        // Without both lengths, a whole file can't be ruled out.
+        assert!(!is_pseudo_streaming_response(200, None, Some(2000)));
+        assert!(!is_pseudo_streaming_response(200, Some(513), None));
+
+        assert!(!is_pseudo_streaming_response(404, Some(513), Some(2000)));
+    }
+}
@@ -2,8 +2,10 @@ use crate::avm1::Object as Avm1Object;
 use crate::avm1::globals::local_connection::LocalConnection as Avm1LocalConnectionObject;
 use crate::avm2::Domain as Avm2Domain;
 use crate::avm2::object::LocalConnectionObject;
+use crate::backend::local_connection::LocalConnectionMessage;
 use crate::context::UpdateContext;
 use crate::string::AvmString;
+use crate::tag_utils::SwfMovie;
 use flash_lso::types::Value as AmfValue;
 use fnv::FnvHashMap;
 use gc_arena::Collect;
@@ -11,6 +13,7 @@ use gc_arena::collect::Trace;
 use ruffle_macros::istr;
 use ruffle_wstr::{WStr, WString};
 use std::borrow::Cow;
+use std::sync::Arc;
 
 #[derive(Clone, Collect)]
 #[collect(no_drop)]
@@ -32,6 +35,18 @@ impl<'gc> From<Avm1Object<'gc>> for LocalConnectionKind<'gc> {
 }
 
 impl<'gc> LocalConnectionKind<'gc> {
+    /// Stop listening, as if the connection was closed by its owner.
+    fn disconnect(&self, context: &mut UpdateContext<'gc>) {
+        match self {
+            LocalConnectionKind::Avm2(_domain, object) => object.disconnect(context),
+            LocalConnectionKind::Avm1(object) => {
+                if let Some(local_connection) = Avm1LocalConnectionObject::cast((*object).into()) {
+                    local_connection.disconnect(context);
+                }
+            }
+        }
+    }
+
     pub fn send_status(&self, status: AvmString<'gc>, context: &mut UpdateContext<'gc>) {
         match self {
             LocalConnectionKind::Avm2(_domain, object) => {
@@ -45,6 +60,48 @@ impl<'gc> LocalConnectionKind<'gc> {
         }
     }
 
+    /// Whether this connection, opened by `movie`, accepts messages from
+    /// movies of `sender_domain`.
+    ///
+    /// Movies of the same domain can always talk to each other, unless the
+    /// receiver was loaded over HTTPS and the sender wasn't. Other senders
+    /// need to be allowed by `allowDomain` or `allowInsecureDomain`.
+    pub fn allows_sender(
+        &self,
+        context: &mut UpdateContext<'gc>,
+        movie: &SwfMovie,
+        sender_domain: &str,
+        sender_is_secure: bool,
+    ) -> bool {
+        let url = movie.url();
+        let is_insecure = LocalConnections::is_secure(url) && !sender_is_secure;
+        if !is_insecure && LocalConnections::get_domain(url).eq_ignore_ascii_case(sender_domain) {
+            return true;
+        }
+
+        match self {
+            LocalConnectionKind::Avm2(_domain, object) => {
+                object.allows_domain(sender_domain, is_insecure)
+            }
+            LocalConnectionKind::Avm1(object) => {
+                match Avm1LocalConnectionObject::allows_domain(
+                    context,
+                    *object,
+                    sender_domain,
+                    is_insecure,
+                ) {
+                    Ok(allowed) => allowed,
+                    Err(e) => {
+                        tracing::error!(
+                            "Unhandled AVM1 error during LocalConnection allowDomain: {e}"
+                        );
+                        false
+                    }
+                }
+            }
+        }
+    }
+
     pub fn run_method(
         &self,
         context: &mut UpdateContext<'gc>,
@@ -83,6 +140,9 @@ pub enum QueuedMessageKind<'gc> {
         method_name: AvmString<'gc>,
         #[collect(require_static)]
         arguments: Vec<AmfValue>,
+        #[collect(require_static)]
+        sender_domain: String,
+        sender_is_secure: bool,
     },
 }
 
@@ -96,12 +156,33 @@ impl<'gc> QueuedMessageKind<'gc> {
                 connection_name,
                 method_name,
                 arguments,
+                sender_domain,
+                sender_is_secure,
             } => {
-                if let Some(receiver) = context.local_connections.find_listener(&connection_name) {
-                    source.send_status(istr!(context, "status"), context);
-                    receiver.run_method(context, method_name, arguments);
+                if let Some((receiver, movie)) =
+                    context.local_connections.find_listener(&connection_name)
+                {
+                    if receiver.allows_sender(context, &movie, &sender_domain, sender_is_secure) {
+                        source.send_status(istr!(context, "status"), context);
+                        receiver.run_method(context, method_name, arguments);
+                    } else {
+                        source.send_status(istr!(context, "error"), context);
+                    }
                 } else {
-                    source.send_status(istr!(context, "error"), context);
+                    // Not listened on by this player, maybe another one is.
+                    // The receiving player is responsible for checking domains.
+                    let message = LocalConnectionMessage {
+                        connection_name: connection_name.to_utf8_lossy().into_owned(),
+                        method_name: method_name.to_utf8_lossy().into_owned(),
+                        sender_domain,
+                        sender_is_secure,
+                        arguments,
+                    };
+                    if context.local_connection.send(message) {
+                        source.send_status(istr!(context, "status"), context);
+                    } else {
+                        source.send_status(istr!(context, "error"), context);
+                    }
                 }
             }
         }
@@ -116,14 +197,15 @@ pub struct LocalConnectionHandle(WString);
 
 /// Manages the collection of local connections.
 pub struct LocalConnections<'gc> {
-    connections: FnvHashMap<WString, LocalConnectionKind<'gc>>,
+    /// The listening connections, and the movies that opened them.
+    connections: FnvHashMap<WString, (LocalConnectionKind<'gc>, Arc<SwfMovie>)>,
     messages: Vec<QueuedMessage<'gc>>,
 }
 
 // TODO(moulins): use gc_arena::Static to avoid unsafe impl?
 unsafe impl<'gc> Collect<'gc> for LocalConnections<'gc> {
     fn trace<C: Trace<'gc>>(&self, cc: &mut C) {
-        for v in self.connections.values() {
+        for (v, _movie) in self.connections.values() {
             cc.trace(v);
         }
         cc.trace(&self.messages);
@@ -139,10 +221,11 @@ impl<'gc> LocalConnections<'gc> {
     }
 
     pub fn connect<C: Into<LocalConnectionKind<'gc>>>(
-        &mut self,
+        context: &mut UpdateContext<'gc>,
         domain: &str,
         connection: C,
         name: &WStr,
+        movie: Arc<SwfMovie>,
     ) -> Option<LocalConnectionHandle> {
         let key = if name.starts_with(b'_') {
             name.to_ascii_lowercase()
@@ -154,21 +237,42 @@ impl<'gc> LocalConnections<'gc> {
             key
         };
 
-        if self.connections.contains_key(&key) {
+        // The name may also be owned by another player.
+        if context.local_connections.connections.contains_key(&key)
+            || !context.local_connection.connect(&key.to_utf8_lossy())
+        {
             None
         } else {
-            self.connections.insert(key.to_owned(), connection.into());
+            context
+                .local_connections
+                .connections
+                .insert(key.to_owned(), (connection.into(), movie));
             Some(LocalConnectionHandle(key))
         }
     }
 
-    pub fn close(&mut self, handle: LocalConnectionHandle) {
-        self.connections.remove(&handle.0);
+    pub fn close(context: &mut UpdateContext<'gc>, handle: LocalConnectionHandle) {
+        context.local_connections.connections.remove(&handle.0);
+        context.local_connection.close(&handle.0.to_utf8_lossy());
+    }
+
+    /// Close all connections opened by the given movie.
+    pub fn close_all_of_movie(context: &mut UpdateContext<'gc>, movie: &Arc<SwfMovie>) {
+        let connections: Vec<_> = context
+            .local_connections
+            .connections
+            .values()
+            .filter(|(_, owner)| Arc::ptr_eq(owner, movie))
+            .map(|(connection, _)| connection.clone())
+            .collect();
+        for connection in connections {
+            connection.disconnect(context);
+        }
     }
 
     pub fn send<C: Into<LocalConnectionKind<'gc>>>(
-        &mut self,
-        domain: &str,
+        context: &mut UpdateContext<'gc>,
+        url: &str,
         source: C,
         connection_name: AvmString<'gc>,
         method_name: AvmString<'gc>,
@@ -181,41 +285,95 @@ impl<'gc> LocalConnections<'gc> {
         // Similarly, if one becomes unavailable between send and update, it'll error
         // If something *else* takes its place between send and update, it'll use that instead
 
+        let domain = Self::get_domain(url);
         let mut connection_name = connection_name.to_ascii_lowercase();
         if !connection_name.contains(b':') && !connection_name.starts_with(b'_') {
-            let mut result = WString::from_utf8(Self::get_superdomain(domain));
+            let mut result = WString::from_utf8(Self::get_superdomain(&domain));
             result.push_char(':');
             result.push_str(&connection_name);
             connection_name = result;
         }
 
-        let kind = if self.find_listener(&connection_name).is_some() {
+        context.network_log.local_connection_message(
+            &connection_name.to_utf8_lossy(),
+            &method_name.to_string(),
+            &arguments,
+            false,
+        );
+
+        let is_listened = context
+            .local_connections
+            .find_listener(&connection_name)
+            .is_some()
+            || context
+                .local_connection
+                .is_connected(&connection_name.to_utf8_lossy());
+        let kind = if is_listened {
             QueuedMessageKind::Message {
                 connection_name,
                 method_name,
                 arguments,
+                sender_domain: domain.into_owned(),
+                sender_is_secure: Self::is_secure(url),
             }
         } else {
             QueuedMessageKind::Failure
         };
-        self.messages.push(QueuedMessage {
+        context.local_connections.messages.push(QueuedMessage {
             source: source.into(),
             kind,
         });
     }
 
-    fn find_listener(&self, name: &WStr) -> Option<LocalConnectionKind<'gc>> {
-        self.connections.get(name).cloned()
+    /// Finds the connection listening on `name`, and the movie that opened it.
+    fn find_listener(&self, name: &WStr) -> Option<(LocalConnectionKind<'gc>, Arc<SwfMovie>)> {
+        self.connections
+            .get(name)
+            .map(|(connection, movie)| (connection.clone(), movie.clone()))
     }
 
     pub fn update_connections(context: &mut UpdateContext<'gc>) {
-        if context.local_connections.messages.is_empty() {
-            return;
-        }
-
         for message in std::mem::take(&mut context.local_connections.messages) {
             message.kind.deliver(message.source, context);
         }
+
+        for message in context.local_connection.poll() {
+            Self::receive(context, message);
+        }
+    }
+
+    /// Delivers a message sent by another player.
+    fn receive(context: &mut UpdateContext<'gc>, message: LocalConnectionMessage) {
+        context.network_log.local_connection_message(
+            &message.connection_name,
+            &message.method_name,
+            &message.arguments,
+            true,
+        );
+
+        let connection_name = WString::from_utf8(&message.connection_name);
+        let Some((receiver, movie)) = context.local_connections.find_listener(&connection_name)
+        else {
+            // The connection was closed after the message was sent.
+            return;
+        };
+
+        if !receiver.allows_sender(
+            context,
+            &movie,
+            &message.sender_domain,
+            message.sender_is_secure,
+        ) {
+            tracing::warn!(
+                "LocalConnection: Rejected message to {} from domain {}",
+                message.connection_name,
+                message.sender_domain
+            );
+            return;
+        }
+
+        let method_name = AvmString::new_utf8(context.gc(), message.method_name);
+        receiver.run_method(context, method_name, message.arguments);
     }
 
     pub fn get_domain(url: &str) -> Cow<'static, str> {
@@ -234,6 +392,12 @@ impl<'gc> LocalConnections<'gc> {
         }
     }
 
+    /// Whether a movie loaded from `url` is considered secure, which is only
+    /// the case for movies loaded over HTTPS.
+    pub fn is_secure(url: &str) -> bool {
+        url::Url::parse(url).is_ok_and(|url| url.scheme() == "https")
+    }
+
     pub fn get_superdomain(domain: &str) -> &str {
         domain.rsplit_once('.').map(|(_, b)| b).unwrap_or(domain)
     }
@@ -0,0 +1,630 @@
+//! A record of the network activity of a movie.
+//!
+//! While recording, every fetch made through [`crate::Player::fetch`], every
+//! socket event and every `LocalConnection` message is kept here, so that the
+//! debug UI can show what a movie is talking to. Fetches can be exported as an
+//! HTTP Archive (HAR).
+
+use crate::backend::navigator::{FetchReason, OwnedFuture, Request, SuccessResponse};
+use crate::loader::Error;
+use crate::socket::SocketHandle;
+use chrono::{DateTime, Utc};
+use encoding_rs::Encoding;
+use flash_lso::types::Value as AmfValue;
+use serde_json::{Value as JsonValue, json};
+use std::borrow::Cow;
+use std::collections::{HashMap, VecDeque};
+use std::fmt::{self, Write};
+use std::sync::{Arc, Mutex, MutexGuard};
+use web_time::{Duration, Instant};
+
+/// How many entries are kept before the oldest ones are dropped.
+const MAX_ENTRIES: usize = 500;
+
+/// How many bytes of each request or response body, or of the arguments of a
+/// `LocalConnection` message, are kept.
+const MAX_PREVIEW_BYTES: usize = 16 * 1024;
+
+/// The start of a body, along with its full size.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct BodyPreview {
+    pub data: Vec<u8>,
+    pub size: u64,
+}
+
+impl BodyPreview {
+    pub(crate) fn new(data: &[u8]) -> Self {
+        let mut preview = Self::default();
+        preview.extend(data);
+        preview
+    }
+
+    fn extend(&mut self, data: &[u8]) {
+        let room = MAX_PREVIEW_BYTES.saturating_sub(self.data.len());
+        self.data.extend_from_slice(&data[..data.len().min(room)]);
+        self.size += data.len() as u64;
+    }
+
+    /// Whether only part of the body was kept.
+    pub fn is_truncated(&self) -> bool {
+        (self.data.len() as u64) < self.size
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FetchStatus {
+    Pending,
+    Response(u16),
+    Failed(String),
+}
+
+#[derive(Debug)]
+pub struct FetchEntry {
+    pub reason: FetchReason,
+    pub method: String,
+    pub url: String,
+    pub request_headers: Vec<(String, String)>,
+    pub request_content_type: Option<String>,
+    pub request_body: Option<BodyPreview>,
+    pub status: FetchStatus,
+
+    /// The final URL, if the request was redirected or rewritten.
+    pub response_url: Option<String>,
+    pub response_body: BodyPreview,
+
+    /// How long it took for the response to start.
+    pub wait: Option<Duration>,
+
+    /// How long it took for the whole response to arrive.
+    pub duration: Option<Duration>,
+}
+
+impl FetchEntry {
+    /// Whether this is a Flash Remoting call, whose bodies are AMF packets.
+    pub fn is_amf(&self) -> bool {
+        self.request_content_type.as_deref() == Some("application/x-amf")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SocketEvent {
+    Connecting,
+    Connected,
+    ConnectionFailed,
+    Sent(BodyPreview),
+    Received(BodyPreview),
+    Closed,
+}
+
+#[derive(Debug)]
+pub struct SocketEntry {
+    /// The `host:port` the socket is connected to.
+    pub destination: String,
+    pub event: SocketEvent,
+}
+
+#[derive(Debug)]
+pub struct LocalConnectionEntry {
+    pub connection_name: String,
+    pub method_name: String,
+
+    /// The arguments, formatted for display. Once `MAX_PREVIEW_BYTES` have been
+    /// used, the rest is cut off and ends with `…`.
+    pub arguments: Vec<String>,
+
+    /// Whether this message was sent by another player.
+    pub incoming: bool,
+}
+
+#[derive(Debug)]
+pub enum NetworkEntryKind {
+    Fetch(FetchEntry),
+    Socket(SocketEntry),
+    LocalConnection(LocalConnectionEntry),
+}
+
+#[derive(Debug)]
+pub struct NetworkEntry {
+    pub id: u64,
+
+    /// The wall-clock time this entry started at.
+    pub started: DateTime<Utc>,
+    started_at: Instant,
+    pub kind: NetworkEntryKind,
+}
+
+impl NetworkEntry {
+    /// How long after the start of `first` this entry started.
+    pub fn offset_from(&self, first: &NetworkEntry) -> Duration {
+        self.started_at.saturating_duration_since(first.started_at)
+    }
+}
+
+#[derive(Default)]
+struct NetworkLogData {
+    recording: bool,
+    next_id: u64,
+    entries: VecDeque<NetworkEntry>,
+    socket_destinations: HashMap<SocketHandle, String>,
+}
+
+/// A shared, bounded log of network activity.
+///
+/// Nothing is recorded until [`NetworkLog::set_recording`] is called, as the debug
+/// UI only needs it while its Network window is open.
+///
+/// Cloning this gives another handle to the same log, so that fetches can
+/// update their entry once their response arrives.
+#[derive(Clone, Default)]
+pub struct NetworkLog(Arc<Mutex<NetworkLogData>>);
+
+impl NetworkLog {
+    fn data(&self) -> MutexGuard<'_, NetworkLogData> {
+        self.0.lock().expect("Network log should not be poisoned")
+    }
+
+    /// Adds the entry made by `kind`, if recording.
+    fn push(&self, kind: impl FnOnce() -> NetworkEntryKind) -> Option<u64> {
+        let mut data = self.data();
+        if !data.recording {
+            return None;
+        }
+        let id = data.next_id;
+        data.next_id += 1;
+        if data.entries.len() >= MAX_ENTRIES {
+            data.entries.pop_front();
+        }
+        data.entries.push_back(NetworkEntry {
+            id,
+            started: Utc::now(),
+            started_at: Instant::now(),
+            kind: kind(),
+        });
+        Some(id)
+    }
+
+    fn update_fetch(&self, id: u64, f: impl FnOnce(&mut FetchEntry, Duration)) {
+        let mut data = self.data();
+        // Recent entries are the most likely to be updated.
+        let entry = data.entries.iter_mut().rev().find(|entry| entry.id == id);
+        if let Some(NetworkEntry {
+            started_at,
+            kind: NetworkEntryKind::Fetch(fetch),
+            ..
+        }) = entry
+        {
+            f(fetch, started_at.elapsed());
+        }
+    }
+
+    /// Calls `f` with every entry, oldest first.
+    pub fn with_entries<R>(&self, f: impl FnOnce(&VecDeque<NetworkEntry>) -> R) -> R {
+        f(&self.data().entries)
+    }
+
+    pub fn clear(&self) {
+        self.data().entries.clear();
+    }
+
+    /// Starts or stops recording new activity. Entries already recorded are kept.
+    pub fn set_recording(&self, recording: bool) {
+        self.data().recording = recording;
+    }
+
+    /// Records the start of a fetch, returning the id that its outcome is recorded under.
+    ///
+    /// Returns `None` if not recording, in which case its outcome is ignored too.
+    pub(crate) fn start_fetch(&self, request: &Request, reason: FetchReason) -> Option<u64> {
+        self.push(|| {
+            NetworkEntryKind::Fetch(FetchEntry {
+                reason,
+                method: request.method().to_string(),
+                url: request.url().to_string(),
+                request_headers: request
+                    .headers()
+                    .iter()
+                    .map(|(name, value)| (name.clone(), value.clone()))
+                    .collect(),
+                request_content_type: request.body().as_ref().map(|(_, mime)| mime.clone()),
+                request_body: request
+                    .body()
+                    .as_ref()
+                    .map(|(data, _)| BodyPreview::new(data)),
+                status: FetchStatus::Pending,
+                response_url: None,
+                response_body: BodyPreview::default(),
+                wait: None,
+                duration: None,
+            })
+        })
+    }
+
+    pub(crate) fn fail_fetch(&self, id: Option<u64>, error: &Error) {
+        if let Some(id) = id {
+            self.update_fetch(id, |fetch, elapsed| {
+                fetch.status = FetchStatus::Failed(error.to_string());
+                fetch.duration = Some(elapsed);
+            });
+        }
+    }
+
+    /// Records the start of a response, and wraps it so that its body is recorded as it's read.
+    pub(crate) fn track_response(
+        &self,
+        id: Option<u64>,
+        response: Box<dyn SuccessResponse>,
+    ) -> Box<dyn SuccessResponse> {
+        let Some(id) = id else {
+            return response;
+        };
+        let url = response.url().into_owned();
+        let status = response.status();
+        self.update_fetch(id, |fetch, elapsed| {
+            fetch.status = FetchStatus::Response(status);
+            if url != fetch.url {
+                fetch.response_url = Some(url);
+            }
+            fetch.wait = Some(elapsed);
+        });
+        Box::new(LoggedResponse {
+            inner: response,
+            log: self.clone(),
+            id,
+        })
+    }
+
+    pub(crate) fn socket_connect(&self, handle: SocketHandle, host: &str, port: u16) {
+        let destination = format!("{host}:{port}");
+        self.data()
+            .socket_destinations
+            .insert(handle, destination.clone());
+        self.push(|| {
+            NetworkEntryKind::Socket(SocketEntry {
+                destination,
+                event: SocketEvent::Connecting,
+            })
+        });
+    }
+
+    /// Records the event made by `event`, if recording.
+    pub(crate) fn socket_event(&self, handle: SocketHandle, event: impl FnOnce() -> SocketEvent) {
+        let destination = self.data().socket_destinations.get(&handle).cloned();
+        self.push_socket(destination, event);
+    }
+
+    /// Records that a socket was closed, and forgets its destination.
+    pub(crate) fn socket_closed(&self, handle: SocketHandle) {
+        let destination = self.data().socket_destinations.remove(&handle);
+        self.push_socket(destination, || SocketEvent::Closed);
+    }
+
+    fn push_socket(&self, destination: Option<String>, event: impl FnOnce() -> SocketEvent) {
+        self.push(|| {
+            NetworkEntryKind::Socket(SocketEntry {
+                destination: destination.unwrap_or_else(|| "<unknown>".to_string()),
+                event: event(),
+            })
+        });
+    }
+
+    pub(crate) fn local_connection_message(
+        &self,
+        connection_name: &str,
+        method_name: &str,
+        arguments: &[AmfValue],
+        incoming: bool,
+    ) {
+        self.push(|| {
+            NetworkEntryKind::LocalConnection(LocalConnectionEntry {
+                connection_name: connection_name.to_string(),
+                method_name: method_name.to_string(),
+                arguments: preview_arguments(arguments),
+                incoming,
+            })
+        });
+    }
+
+    /// Exports the recorded fetches as an HTTP Archive.
+    ///
+    /// Socket and `LocalConnection` traffic has no HAR equivalent and is left out.
+    pub fn to_har(&self) -> JsonValue {
+        let entries: Vec<_> = self.with_entries(|entries| {
+            entries
+                .iter()
+                .filter_map(|entry| match &entry.kind {
+                    NetworkEntryKind::Fetch(fetch) => Some(har_entry(entry.started, fetch)),
+                    _ => None,
+                })
+                .collect()
+        });
+        json!({
+            "log": {
+                "version": "1.2",
+                "creator": {
+                    "name": "Ruffle",
+                    "version": env!("CARGO_PKG_VERSION"),
+                },
+                "entries": entries,
+            }
+        })
+    }
+}
+
+/// Collects formatted text, failing once it has been given `room` bytes.
+struct PreviewWriter {
+    text: String,
+    room: usize,
+}
+
+impl Write for PreviewWriter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if s.len() <= self.room {
+            self.text.push_str(s);
+            self.room -= s.len();
+            return Ok(());
+        }
+        let mut end = self.room;
+        while !s.is_char_boundary(end) {
+            end -= 1;
+        }
+        self.text.push_str(&s[..end]);
+        self.room = 0;
+        Err(fmt::Error)
+    }
+}
+
+/// Formats each argument, stopping once `MAX_PREVIEW_BYTES` have been written in total.
+fn preview_arguments(arguments: &[AmfValue]) -> Vec<String> {
+    let mut room = MAX_PREVIEW_BYTES;
+    arguments
+        .iter()
+        .map(|argument| {
+            let mut writer = PreviewWriter {
+                text: String::new(),
+                room,
+            };
+            let truncated = write!(writer, "{argument:#?}").is_err();
+            room = writer.room;
+            let mut text = writer.text;
+            if truncated {
+                text.push('…');
+            }
+            text
+        })
+        .collect()
+}
+
+fn har_headers(headers: &[(String, String)]) -> JsonValue {
+    headers
+        .iter()
+        .map(|(name, value)| json!({ "name": name, "value": value }))
+        .collect()
+}
+
+fn har_entry(started: DateTime<Utc>, fetch: &FetchEntry) -> JsonValue {
+    let millis = |duration: Option<Duration>| duration.map_or(-1.0, |d| d.as_secs_f64() * 1000.0);
+    let wait = millis(fetch.wait);
+    let total = millis(fetch.duration.or(fetch.wait));
+    let status = match fetch.status {
+        FetchStatus::Response(status) => status,
+        _ => 0,
+    };
+
+    let mut request = json!({
+        "method": fetch.method,
+        "url": fetch.url,
+        "httpVersion": "HTTP/1.1",
+        "cookies": [],
+        "headers": har_headers(&fetch.request_headers),
+        "queryString": [],
+        "headersSize": -1,
+        "bodySize": fetch.request_body.as_ref().map_or(0, |body| body.size),
+    });
+    if let (Some(body), Some(mime_type)) = (&fetch.request_body, &fetch.request_content_type) {
+        request["postData"] = json!({
+            "mimeType": mime_type,
+            "text": String::from_utf8_lossy(&body.data),
+        });
+    }
+
+    let mut content = json!({
+        "size": fetch.response_body.size,
+        "mimeType": "",
+    });
+    if let Ok(text) = std::str::from_utf8(&fetch.response_body.data) {
+        content["text"] = text.into();
+    }
+
+    json!({
+        "startedDateTime": started.to_rfc3339(),
+        "time": total.max(0.0),
+        "request": request,
+        "response": {
+            "status": status,
+            "statusText": match &fetch.status {
+                FetchStatus::Failed(error) => error.as_str(),
+                _ => "",
+            },
+            "httpVersion": "HTTP/1.1",
+            "cookies": [],
+            "headers": [],
+            "content": content,
+            "redirectURL": fetch.response_url.as_deref().unwrap_or_default(),
+            "headersSize": -1,
+            "bodySize": fetch.response_body.size,
+        },
+        "cache": {},
+        "timings": {
+            "send": 0,
+            "wait": wait,
+            "receive": if total >= 0.0 && wait >= 0.0 { total - wait } else { -1.0 },
+        },
+    })
+}
+
+/// Forwards to a response, recording its body as it's read.
+struct LoggedResponse {
+    inner: Box<dyn SuccessResponse>,
+    log: NetworkLog,
+    id: u64,
+}
+
+impl SuccessResponse for LoggedResponse {
+    fn url(&self) -> Cow<'_, str> {
+        self.inner.url()
+    }
+
+    fn set_url(&mut self, url: String) {
+        self.inner.set_url(url)
+    }
+
+    fn body(self: Box<Self>) -> OwnedFuture<Vec<u8>, Error> {
+        let Self { inner, log, id } = *self;
+        let body = inner.body();
+        Box::pin(async move {
+            let result = body.await;
+            match &result {
+                Ok(body) => log.update_fetch(id, |fetch, elapsed| {
+                    fetch.response_body.extend(body);
+                    fetch.duration = Some(elapsed);
+                }),
+                Err(error) => log.fail_fetch(id, error),
+            }
+            result
+        })
+    }
+
+    fn text_encoding(&self) -> Option<&'static Encoding> {
+        self.inner.text_encoding()
+    }
+
+    fn status(&self) -> u16 {
+        self.inner.status()
+    }
+
+    fn redirected(&self) -> bool {
+        self.inner.redirected()
+    }
+
+    fn next_chunk(&mut self) -> OwnedFuture<Option<Vec<u8>>, Error> {
+        let chunk = self.inner.next_chunk();
+        let log = self.log.clone();
+        let id = self.id;
+        Box::pin(async move {
+            let result = chunk.await;
+            match &result {
+                Ok(Some(chunk)) => log.update_fetch(id, |fetch, _| {
+                    fetch.response_body.extend(chunk);
+                }),
+                Ok(None) => log.update_fetch(id, |fetch, elapsed| {
+                    fetch.duration = Some(elapsed);
+                }),
+                Err(error) => log.fail_fetch(id, error),
+            }
+            result
+        })
+    }
+
+    fn expected_length(&self) -> Result<Option<u64>, Error> {
+        self.inner.expected_length()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn body_preview_is_truncated() {
+        let mut preview = BodyPreview::new(&[1; MAX_PREVIEW_BYTES - 1]);
+        assert!(!preview.is_truncated());
+        preview.extend(&[2, 3]);
+        assert!(preview.is_truncated());
+        assert_eq!(preview.data.len(), MAX_PREVIEW_BYTES);
+        assert_eq!(preview.size, MAX_PREVIEW_BYTES as u64 + 1);
+        assert_eq!(preview.data.last(), Some(&2));
+    }
+
+    #[test]
+    fn oldest_entries_are_dropped() {
+        let log = NetworkLog::default();
+        log.set_recording(true);
+        for i in 0..MAX_ENTRIES + 10 {
+            log.local_connection_message(&format!("_conn{i}"), "method", &[], false);
+        }
+        log.with_entries(|entries| {
+            assert_eq!(entries.len(), MAX_ENTRIES);
+            assert_eq!(entries.front().map(|entry| entry.id), Some(10));
+        });
+    }
+
+    #[test]
+    fn nothing_is_recorded_until_enabled() {
+        let log = NetworkLog::default();
+        log.local_connection_message("_conn", "method", &[], false);
+        log.socket_connect(SocketHandle::default(), "example.com", 80);
+        log.with_entries(|entries| assert!(entries.is_empty()));
+
+        log.set_recording(true);
+        log.socket_event(SocketHandle::default(), || SocketEvent::Connected);
+        log.with_entries(|entries| {
+            let Some(NetworkEntryKind::Socket(socket)) = entries.front().map(|entry| &entry.kind)
+            else {
+                panic!("expected a socket entry");
+            };
+            assert_eq!(socket.destination, "example.com:80");
+        });
+    }
+
+    #[test]
+    fn local_connection_arguments_are_truncated() {
+        let log = NetworkLog::default();
+        log.set_recording(true);
+        let long = AmfValue::String("a".repeat(MAX_PREVIEW_BYTES));
+        log.local_connection_message(
+            "_conn",
+            "method",
+            &[AmfValue::Number(1.0), long.clone(), long],
+            false,
+        );
+        log.with_entries(|entries| {
+            let Some(NetworkEntryKind::LocalConnection(message)) =
+                entries.front().map(|entry| &entry.kind)
+            else {
+                panic!("expected a LocalConnection entry");
+            };
+            assert_eq!(message.arguments.len(), 3);
+            assert!(!message.arguments[0].ends_with('…'));
+            assert!(message.arguments[1].ends_with('…'));
+            assert_eq!(message.arguments[2], "…");
+            let total: usize = message.arguments.iter().map(String::len).sum();
+            assert!(total <= MAX_PREVIEW_BYTES + 2 * '…'.len_utf8());
+        });
+    }
+
+    #[test]
+    fn har_contains_fetches() {
+        let log = NetworkLog::default();
+        log.set_recording(true);
+        let request = Request::post(
+            "https://example.com/gateway".to_string(),
+            Some((b"hello".to_vec(), "text/plain".to_string())),
+        );
+        let id = log
+            .start_fetch(&request, FetchReason::Other)
+            .expect("recording");
+        log.update_fetch(id, |fetch, _| {
+            fetch.status = FetchStatus::Response(200);
+            fetch.response_body.extend(b"world");
+        });
+        log.local_connection_message("_conn", "method", &[], false);
+
+        let har = log.to_har();
+        let entries = har["log"]["entries"].as_array().expect("entries");
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0]["request"]["method"], "POST");
+        assert_eq!(entries[0]["request"]["postData"]["text"], "hello");
+        assert_eq!(entries[0]["response"]["status"], 200);
+        assert_eq!(entries[0]["response"]["content"]["text"], "world");
+    }
+}
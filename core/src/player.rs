@@ -15,6 +15,7 @@ use crate::backend::navigator::SuccessResponse;
 use crate::backend::ui::FontDefinition;
 use crate::backend::{
     audio::{AudioBackend, AudioManager},
+    local_connection::{LocalConnectionBackend, NullLocalConnectionBackend},
     log::LogBackend,
     navigator::{NavigatorBackend, Request},
     storage::StorageBackend,
@@ -47,15 +48,19 @@ use crate::limits::ExecutionLimit;
 use crate::loader::{LoadBehavior, LoadManager};
 use crate::local_connection::LocalConnections;
 use crate::net_connection::NetConnections;
+use crate::network_log::NetworkLog;
 use crate::orphan_manager::OrphanManager;
 use crate::prelude::*;
+use crate::security::SecurityManager;
 use crate::socket::Sockets;
+use crate::stats::{PlayerStats, StatsTracker, TIMING_WINDOW};
 use crate::streams::StreamManager;
 use crate::string::{AvmStringInterner, StringContext};
 use crate::stub::StubCollection;
 use crate::system_properties::SystemProperties;
 use crate::tag_utils::SwfMovie;
-use crate::timer::Timers;
+use crate::telemetry::{self, Telemetry};
+use crate::timer::{MovieClock, Timers};
 use crate::vminterface::Instantiator;
 use async_channel::Sender;
 use enumset::EnumSet;
@@ -64,6 +69,7 @@ use gc_arena::{Collect, DynamicRootSet, Mutation, Rootable};
 use ruffle_common::duration::FloatDuration;
 use ruffle_macros::istr;
 use ruffle_render::backend::{RenderBackend, ViewportDimensions, null::NullRenderer};
+use ruffle_render::bitmap::{Bitmap, BitmapFormat, PixelRegion};
 use ruffle_render::commands::CommandList;
 use ruffle_render::quality::StageQuality;
 use ruffle_render::transform::TransformStack;
@@ -79,6 +85,9 @@ use std::time::Duration;
 use tracing::instrument;
 use web_time::Instant;
 
+/// The slowest and fastest speeds a movie can be run at.
+pub const TIME_SCALE_RANGE: std::ops::RangeInclusive<f64> = 1.0 / 16.0..=16.0;
+
 #[cfg(feature = "default_font")]
 pub const FALLBACK_DEVICE_FONT: &[u8] = include_bytes!("../assets/notosans.subset.ttf.gz");
 
@@ -313,6 +322,7 @@ pub struct Player {
     audio: Box<dyn AudioBackend>,
     navigator: Box<dyn NavigatorBackend>,
     storage: Box<dyn StorageBackend>,
+    local_connection: Box<dyn LocalConnectionBackend>,
     log: Box<dyn LogBackend>,
     ui: Box<dyn UiBackend>,
     video: Box<dyn VideoBackend>,
@@ -360,8 +370,10 @@ pub struct Player {
     /// Time remaining until the next timer will fire.
     time_til_next_timer: Option<f64>,
 
-    /// The instant at which the SWF was launched.
-    start_time: Instant,
+    /// The clock that `getTimer` reads, started when the SWF was launched.
+    ///
+    /// This also holds the time scale, which speeds up or slows down the whole movie.
+    clock: MovieClock,
 
     /// The maximum amount of time that can be called before a `Error::ExecutionTimeout`
     /// is raised. This defaults to 15 seconds but can be changed.
@@ -394,6 +406,22 @@ pub struct Player {
     /// Debug UI windows
     #[cfg(feature = "egui")]
     debug_ui: Rc<RefCell<crate::debug_ui::DebugUi>>,
+
+    /// The state of the attached script debugger, if any.
+    #[cfg(feature = "debugger")]
+    debugger: crate::debugger::Debugger,
+
+    /// Where telemetry is written to, if enabled.
+    telemetry: Telemetry,
+
+    /// Timings collected for [`Player::stats`].
+    stats: StatsTracker,
+
+    /// The record of the movie's network activity.
+    network_log: NetworkLog,
+
+    /// The state of the security sandbox.
+    security: SecurityManager,
 }
 
 impl Player {
@@ -491,7 +519,8 @@ impl Player {
         if self.recent_run_frame_timings.is_empty() {
             5
         } else {
-            let frame_time = self.frame_time(1000.0);
+            // Frames are due more often in real time when the movie is sped up.
+            let frame_time = self.frame_time(1000.0) / self.clock.time_scale();
             let average_run_frame_time = self.recent_run_frame_timings.iter().sum::<f64>()
                 / self.recent_run_frame_timings.len() as f64;
             ((frame_time / average_run_frame_time) as u32).clamp(1, MAX_FRAMES_PER_TICK)
@@ -524,6 +553,10 @@ impl Player {
             return;
         }
 
+        // From here on, `dt` is in movie time, which passes faster or slower
+        // than real time when a time scale is set.
+        let dt = dt * self.clock.time_scale();
+
         self.frame_accumulator += dt;
         let frame_duration = self.frame_duration();
 
@@ -589,6 +622,7 @@ impl Player {
 
     pub fn time_til_next_timer(&self) -> Option<f64> {
         self.time_til_next_timer
+            .map(|time| time / self.clock.time_scale())
     }
 
     /// Returns the approximate duration of time until the next frame is due to run.
@@ -607,7 +641,9 @@ impl Player {
             time_til_next = time_til_next.min(FloatDuration::from_millis(time_til_next_timer));
         }
 
-        time_til_next.max(FloatDuration::ZERO).to_std()
+        (time_til_next / self.clock.time_scale())
+            .max(FloatDuration::ZERO)
+            .to_std()
     }
 
     pub fn is_playing(&self) -> bool {
@@ -1023,6 +1059,8 @@ impl Player {
             | PlayerEvent::MouseWheel { .. }
             | PlayerEvent::GamepadButtonDown { .. }
             | PlayerEvent::GamepadButtonUp { .. }
+            | PlayerEvent::GamepadKeyDown { .. }
+            | PlayerEvent::GamepadKeyUp { .. }
             | PlayerEvent::Ime { .. }
             | PlayerEvent::TextInput { .. }
             | PlayerEvent::TextControl { .. } => self.handle_input_event(event),
@@ -2020,10 +2058,19 @@ impl Player {
             return;
         }
 
-        self.update(|context| {
+        self.telemetry.time(telemetry::FRAME_ENTER);
+        let actions_start = self.telemetry.is_enabled().then(Instant::now);
+
+        #[cfg(feature = "egui")]
+        let debug_ui = self.debug_ui.clone();
+
+        let (avm1_time, avm2_time) = self.update(|context| {
+            let avm2_start = Instant::now();
             // TODO: Is this order correct?
             run_all_phases_avm2(context);
+            let avm1_start = Instant::now();
             Avm1::run_frame(context);
+            let avm1_end = Instant::now();
             AudioManager::update_sounds(context);
             LocalConnections::update_connections(context);
 
@@ -2032,13 +2079,47 @@ impl Player {
             for cb in std::mem::take(context.post_frame_callbacks) {
                 (cb.callback)(context, cb.data);
             }
+
+            Avm2::update_sampler(context);
+
+            #[cfg(feature = "egui")]
+            debug_ui.borrow().apply_frozen_variables(context);
+
+            (avm1_end - avm1_start, avm1_start - avm2_start)
         });
 
+        if let Some(actions_start) = actions_start {
+            self.telemetry
+                .span(telemetry::FRAME_ACTIONS, actions_start, None);
+            self.send_memory_telemetry();
+            self.telemetry.flush();
+        }
+
+        self.stats.add_avm1_time(avm1_time);
+        self.stats.add_avm2_time(avm2_time);
+        self.stats.end_frame(Instant::now());
+
         self.needs_render = true;
     }
 
+    fn send_memory_telemetry(&mut self) {
+        if !self.telemetry.is_enabled() {
+            return;
+        }
+
+        let gc_arena = self.gc_arena.borrow();
+        let metrics = gc_arena.metrics();
+        self.telemetry
+            .value(telemetry::MEMORY_MANAGED, metrics.total_gc_allocation());
+        self.telemetry
+            .value(telemetry::MEMORY_TOTAL, metrics.total_allocation());
+        self.telemetry
+            .value(telemetry::GC_DEBT, metrics.allocation_debt());
+    }
+
     #[instrument(level = "debug", skip_all)]
     pub fn render(&mut self) {
+        let render_start = Instant::now();
         let invalidated = self.enter_arena(|_, gc_root, _| gc_root.stage.invalidated());
 
         if invalidated {
@@ -2086,12 +2167,158 @@ impl Player {
             (cache_draws, commands)
         });
 
+        let command_count = commands.commands.len();
+        let cache_draw_count = cache_draws.len();
         self.renderer
             .submit_frame(background_color, commands, cache_draws);
 
+        if self.telemetry.is_enabled() {
+            self.telemetry.span(telemetry::RENDER, render_start, None);
+            self.telemetry
+                .value(telemetry::RENDER_COMMANDS, command_count);
+            self.telemetry
+                .value(telemetry::RENDER_CACHE_DRAWS, cache_draw_count);
+            self.telemetry.flush();
+        }
+
+        self.stats.end_render(
+            render_start.elapsed(),
+            self.renderer.stats().tessellation_time,
+            command_count,
+        );
+
         self.needs_render = false;
     }
 
+    /// Returns a snapshot of the performance of this player, with timings
+    /// averaged over the last few frames.
+    ///
+    /// This is what the performance HUD of the debug UI shows.
+    pub fn stats(&self) -> PlayerStats {
+        self.stats_over(TIMING_WINDOW)
+    }
+
+    /// Returns a snapshot of the performance of this player, with the timings
+    /// of the last frame and render alone.
+    pub fn frame_stats(&self) -> PlayerStats {
+        self.stats_over(1)
+    }
+
+    fn stats_over(&self, frames: usize) -> PlayerStats {
+        let mut stats = self.stats.snapshot(Instant::now(), frames);
+        stats.target_fps = self.frame_rate;
+        stats.bitmap_memory = self.renderer.stats().bitmap_memory;
+        (stats.display_objects, stats.audio_instances) = self.enter_arena(|_, gc_root, _| {
+            (
+                count_descendants(gc_root.stage.into()),
+                gc_root.audio_manager.sound_count(),
+            )
+        });
+        stats.gc_heap_size = self.gc_arena.borrow().metrics().total_gc_allocation();
+        stats
+    }
+
+    /// Renders the current state of the stage into a new bitmap, without
+    /// presenting it.
+    ///
+    /// With `native_size`, the stage is drawn unscaled at the size declared by
+    /// the movie. Otherwise it is drawn exactly as it appears in the viewport,
+    /// letterbox included.
+    ///
+    /// The returned bitmap is RGBA with premultiplied alpha. Returns `None` if
+    /// the render backend cannot render offscreen.
+    pub fn capture_frame(&mut self, native_size: bool) -> Option<Bitmap<'static>> {
+        if !self.renderer.is_offscreen_supported() {
+            return None;
+        }
+
+        let (width, height, background_color, quality) = self.enter_arena(|_, gc_root, this| {
+            let stage = gc_root.stage;
+            let (width, height) = if native_size {
+                stage.movie_size()
+            } else {
+                let dimensions = this.renderer.viewport_dimensions();
+                (dimensions.width, dimensions.height)
+            };
+            let background_color = if stage.window_mode() != WindowMode::Transparent {
+                stage.background_color().unwrap_or(Color::WHITE)
+            } else {
+                Color::from_rgba(0)
+            };
+            (width, height, background_color, stage.quality())
+        });
+        if width == 0 || height == 0 {
+            return None;
+        }
+
+        // The background is filled in beforehand, as offscreen rendering
+        // blends on top of the existing contents of the bitmap.
+        let a = background_color.a;
+        let [r, g, b] = [background_color.r, background_color.g, background_color.b]
+            .map(|c| (c as u16 * a as u16 / 255) as u8);
+        let pixels = [r, g, b, a].repeat((width * height) as usize);
+        let handle = match self.renderer.register_bitmap(Bitmap::new(
+            width,
+            height,
+            BitmapFormat::Rgba,
+            pixels,
+        )) {
+            Ok(handle) => handle,
+            Err(e) => {
+                tracing::error!("Couldn't create bitmap for frame capture: {e}");
+                return None;
+            }
+        };
+
+        let commands = self.enter_arena_mut(|gc_context, gc_root, this| {
+            let stage = gc_root.stage;
+
+            let mut cache_draws = vec![];
+            let mut render_context = RenderContext {
+                renderer: this.renderer.deref_mut(),
+                commands: CommandList::new(),
+                cache_draws: &mut cache_draws,
+                gc_context,
+                library: &gc_root.library,
+                transform_stack: &mut this.transform_stack,
+                is_offscreen: true,
+                use_bitmap_cache: false,
+                stage,
+            };
+
+            if native_size {
+                stage.render(&mut render_context);
+            } else {
+                stage.render_viewport(&mut render_context);
+            }
+
+            render_context.commands
+        });
+
+        let sync_handle = self.renderer.render_offscreen(
+            handle,
+            commands,
+            quality,
+            PixelRegion::for_whole_size(width, height),
+        )?;
+
+        let mut data = Vec::with_capacity((width * height * 4) as usize);
+        let result = self.renderer.resolve_sync_handle(
+            sync_handle,
+            Box::new(|buffer, buffer_width| {
+                for row in buffer.chunks(buffer_width as usize).take(height as usize) {
+                    data.extend_from_slice(&row[..(width * 4) as usize]);
+                }
+            }),
+        );
+        if let Err(e) = result {
+            tracing::error!("Couldn't read back captured frame: {e}");
+            return None;
+        }
+
+        Some(Bitmap::new(width, height, BitmapFormat::Rgba, data))
+    }
+
     /// The current frame of the main timeline, if available.
     /// The first frame is frame 1.
     pub fn current_frame(&self) -> Option<u16> {
@@ -2119,6 +2346,26 @@ impl Player {
         self.frame_rate
     }
 
+    /// The speed at which the movie runs, relative to real time.
+    pub fn time_scale(&self) -> f64 {
+        self.clock.time_scale()
+    }
+
+    /// Speeds up or slows down the whole movie.
+    ///
+    /// This scales the time passed to `tick`, so frames, timers, `getTimer`,
+    /// NetStreams and audio all stay in sync. `1.0` is normal speed.
+    /// Values that are not positive and finite are ignored, and the rest are
+    /// clamped to `TIME_SCALE_RANGE`.
+    pub fn set_time_scale(&mut self, time_scale: f64) {
+        if !time_scale.is_finite() || time_scale <= 0.0 {
+            return;
+        }
+        let time_scale = time_scale.clamp(*TIME_SCALE_RANGE.start(), *TIME_SCALE_RANGE.end());
+        self.clock.set_time_scale(time_scale);
+        self.audio.set_playback_rate(time_scale);
+    }
+
     pub fn renderer(&self) -> &dyn RenderBackend {
         &*self.renderer
     }
@@ -2163,7 +2410,11 @@ impl Player {
             match action.action_type {
                 // DoAction/clip event code.
                 ActionType::Normal { bytecode } | ActionType::Initialize { bytecode } => {
+                    #[cfg(feature = "debugger")]
+                    crate::avm1::debugger::before_frame_script(context, action.clip);
                     Avm1::run_stack_frame_for_action(action.clip, "[Frame]", bytecode, context);
+                    #[cfg(feature = "debugger")]
+                    crate::avm1::debugger::after_frame_script(context);
                 }
                 // Change the prototype of a MovieClip and run constructor events.
                 ActionType::Construct {
@@ -2287,6 +2538,7 @@ impl Player {
                 page_url: &mut this.page_url,
                 instance_counter: &mut this.instance_counter,
                 storage: this.storage.deref_mut(),
+                local_connection: this.local_connection.deref_mut(),
                 log: this.log.deref_mut(),
                 video: this.video.deref_mut(),
                 avm1_shared_objects,
@@ -2298,7 +2550,7 @@ impl Player {
                 avm1,
                 avm2,
                 external_interface,
-                start_time: this.start_time,
+                clock: this.clock,
                 update_start: Instant::now(),
                 max_execution_duration: this.max_execution_duration,
                 focus_tracker: stage.focus_tracker(),
@@ -2319,6 +2571,11 @@ impl Player {
                 post_frame_callbacks,
                 notification_sender: this.notification_sender.as_ref(),
                 frame_script_cleanup_queue: VecDeque::new(),
+                #[cfg(feature = "debugger")]
+                debugger: &mut this.debugger,
+                telemetry: &mut this.telemetry,
+                network_log: &this.network_log,
+                security: &mut this.security,
             };
 
             let prev_frame_rate = *update_context.frame_rate;
@@ -2349,6 +2606,9 @@ impl Player {
         // This method should only be called once and it will panic if it tries to recursively render.
         let debug_ui = self.debug_ui.clone();
         let mut debug_ui = debug_ui.borrow_mut();
+        if let Some(hud) = debug_ui.performance_hud_mut() {
+            hud.update(self.stats());
+        }
         self.mutate_with_update_context(|context| {
             debug_ui.show(egui_ctx, context, movie_offset);
         });
@@ -2359,6 +2619,23 @@ impl Player {
         self.debug_ui.borrow_mut()
     }
 
+    /// Attaches a debugger frontend controlled through the returned client,
+    /// replacing any previous one.
+    ///
+    /// The player blocks while paused, so the client has to be used from
+    /// another thread than the one running the player.
+    #[cfg(feature = "debugger")]
+    pub fn attach_debugger(&mut self) -> crate::debugger::DebuggerClient {
+        let (client, connection) = crate::debugger::DebuggerClient::new();
+        self.debugger.attach(Box::new(connection));
+        client
+    }
+
+    #[cfg(feature = "debugger")]
+    pub fn debugger(&mut self) -> &mut crate::debugger::Debugger {
+        &mut self.debugger
+    }
+
     /// Update the current state of the player.
     ///
     /// The given function will be called with the current stage root, current
@@ -2371,13 +2648,15 @@ impl Player {
     where
         F: for<'gc> FnOnce(&mut UpdateContext<'gc>) -> R,
     {
-        let rval = self.mutate_with_update_context(|context| {
+        let (rval, actions_time) = self.mutate_with_update_context(|context| {
             let rval = func(context);
 
+            let actions_start = Instant::now();
             Self::run_actions(context);
 
-            rval
+            (rval, actions_start.elapsed())
         });
+        self.stats.add_avm1_time(actions_time);
 
         // Update mouse state (check for new hovered button, etc.)
         self.mutate_with_update_context(|context| {
@@ -2479,10 +2758,19 @@ impl Player {
         &self.compatibility_rules
     }
 
+    pub(crate) fn security_mut(&mut self) -> &mut SecurityManager {
+        &mut self.security
+    }
+
     pub fn log_backend(&self) -> &dyn LogBackend {
         &*self.log
     }
 
+    /// The stubs that were encountered while running the movie.
+    pub fn stubs(&self) -> &StubCollection {
+        &self.stub_tracker
+    }
+
     pub fn max_execution_duration(&self) -> Duration {
         self.max_execution_duration
     }
@@ -2519,6 +2807,7 @@ impl Player {
         mut request: Request,
         fetch_reason: FetchReason,
     ) -> OwnedFuture<Box<dyn SuccessResponse>, ErrorResponse> {
+        let network_log = self.network_log.clone();
         match self.compatibility_rules.block_or_rewrite_swf_url(
             request.url().into(),
             UrlRewriteStage::BeforeRequest,
@@ -2526,36 +2815,48 @@ impl Player {
         ) {
             Ok(Some(new_url)) => request.set_url(new_url),
             Ok(None) => {}
-            Err(error) => return Box::pin(async move { Err(error) }),
+            Err(error) => {
+                let log_id = network_log.start_fetch(&request, fetch_reason);
+                network_log.fail_fetch(log_id, &error.error);
+                return Box::pin(async move { Err(error) });
+            }
         }
+        let log_id = network_log.start_fetch(&request, fetch_reason);
 
         let self_reference = self.self_reference.clone();
         let fetch = self.navigator.fetch(request);
         Box::pin(async move {
-            let response = fetch.await;
+            let response: Result<_, ErrorResponse> = async {
+                let mut response = fetch.await?;
 
-            let Ok(mut response) = response else {
-                return response;
-            };
+                let Some(player) = self_reference.upgrade() else {
+                    return Ok(response);
+                };
 
-            let Some(player) = self_reference.upgrade() else {
-                return Ok(response);
-            };
+                let new_url = player
+                    .lock()
+                    .unwrap()
+                    .compatibility_rules
+                    .block_or_rewrite_swf_url(
+                        response.url(),
+                        UrlRewriteStage::AfterResponse,
+                        fetch_reason,
+                    )?;
+                if let Some(new_url) = new_url {
+                    response.set_url(new_url);
+                }
 
-            let new_url = player
-                .lock()
-                .unwrap()
-                .compatibility_rules
-                .block_or_rewrite_swf_url(
-                    response.url(),
-                    UrlRewriteStage::AfterResponse,
-                    fetch_reason,
-                )?;
-            if let Some(new_url) = new_url {
-                response.set_url(new_url);
+                Ok(response)
             }
+            .await;
 
-            Ok(response)
+            match response {
+                Ok(response) => Ok(network_log.track_response(log_id, response)),
+                Err(error) => {
+                    network_log.fail_fetch(log_id, &error.error);
+                    Err(error)
+                }
+            }
         })
     }
 }
@@ -2576,6 +2877,7 @@ pub struct PlayerBuilder {
     navigator: Option<Box<dyn NavigatorBackend>>,
     renderer: Option<Box<dyn RenderBackend>>,
     storage: Option<Box<dyn StorageBackend>>,
+    local_connection: Option<Box<dyn LocalConnectionBackend>>,
     ui: Option<Box<dyn UiBackend>>,
     video: Option<Box<dyn VideoBackend>>,
 
@@ -2612,6 +2914,10 @@ pub struct PlayerBuilder {
     avm2_optimizer_enabled: bool,
     #[cfg(feature = "default_font")]
     default_font: bool,
+    #[cfg(feature = "debugger")]
+    debugger_connection: Option<Box<dyn crate::debugger::DebuggerConnection>>,
+    telemetry_output: Option<Box<dyn std::io::Write>>,
+    security_sandbox: bool,
 }
 
 impl PlayerBuilder {
@@ -2629,6 +2935,7 @@ impl PlayerBuilder {
             navigator: None,
             renderer: None,
             storage: None,
+            local_connection: None,
             ui: None,
             video: None,
 
@@ -2668,6 +2975,10 @@ impl PlayerBuilder {
             avm2_optimizer_enabled: true,
             #[cfg(feature = "default_font")]
             default_font: true,
+            #[cfg(feature = "debugger")]
+            debugger_connection: None,
+            telemetry_output: None,
+            security_sandbox: false,
         }
     }
 
@@ -2727,6 +3038,18 @@ impl PlayerBuilder {
         self
     }
 
+    /// Sets the backend used to share `LocalConnection`s with other players.
+    ///
+    /// By default, movies can only connect to other movies of the same player.
+    #[inline]
+    pub fn with_local_connection(
+        mut self,
+        local_connection: Box<dyn LocalConnectionBackend>,
+    ) -> Self {
+        self.local_connection = Some(local_connection);
+        self
+    }
+
     /// Sets the UI backend of the player.
     #[inline]
     pub fn with_ui(mut self, ui: impl 'static + UiBackend) -> Self {
@@ -2895,6 +3218,34 @@ impl PlayerBuilder {
         self
     }
 
+    #[cfg(feature = "debugger")]
+    /// Attaches a script debugger frontend to the player.
+    pub fn with_debugger(
+        mut self,
+        connection: Box<dyn crate::debugger::DebuggerConnection>,
+    ) -> Self {
+        self.debugger_connection = Some(connection);
+        self
+    }
+
+    /// Writes telemetry that can be read by Adobe Scout to the given output,
+    /// such as a socket or a file.
+    pub fn with_telemetry(mut self, output: Box<dyn std::io::Write>) -> Self {
+        self.telemetry_output = Some(output);
+        self
+    }
+
+    /// Enforces the Flash Player security sandbox.
+    ///
+    /// Movies are then isolated by the domain they were loaded from: they need
+    /// permission from `Security.allowDomain` to script each other, and from
+    /// cross-domain policy files to read data from other domains.
+    /// Socket connections perform the socket policy file handshake.
+    pub fn with_security_sandbox(mut self, enabled: bool) -> Self {
+        self.security_sandbox = enabled;
+        self
+    }
+
     fn create_gc_root<'gc>(
         gc_context: &'gc Mutation<'gc>,
         player_version: u8,
@@ -2903,6 +3254,7 @@ impl PlayerBuilder {
         fake_movie: Arc<SwfMovie>,
         external_interface_provider: Option<Box<dyn ExternalInterfaceProvider>>,
         fs_command_provider: Box<dyn FsCommandProvider>,
+        network_log: NetworkLog,
     ) -> GcRoot<'gc> {
         let mut interner = AvmStringInterner::new(gc_context);
         let (avm1, avm2) = {
@@ -2942,7 +3294,7 @@ impl PlayerBuilder {
             timers: Timers::new(),
             unbound_text_fields: Vec::new(),
             stream_manager: StreamManager::new(),
-            sockets: Sockets::empty(),
+            sockets: Sockets::empty(network_log),
             net_connections: NetConnections::default(),
             local_connections: LocalConnections::empty(),
             orphan_manager: OrphanManager::default(),
@@ -2979,6 +3331,9 @@ impl PlayerBuilder {
         let storage = self
             .storage
             .unwrap_or_else(|| Box::new(storage::MemoryStorageBackend::new()));
+        let local_connection = self
+            .local_connection
+            .unwrap_or_else(|| Box::new(NullLocalConnectionBackend::new()));
         let ui = self
             .ui
             .unwrap_or_else(|| Box::new(ui::NullUiBackend::new()));
@@ -2993,6 +3348,7 @@ impl PlayerBuilder {
         let fake_movie = Arc::new(SwfMovie::empty(player_version, None));
         let frame_rate = self.frame_rate.unwrap_or(12.0);
         let forced_frame_rate = self.frame_rate.is_some();
+        let network_log = NetworkLog::default();
         let player = Arc::new_cyclic(|self_ref| {
             Mutex::new(Player {
                 // Backends
@@ -3001,6 +3357,7 @@ impl PlayerBuilder {
                 navigator,
                 renderer,
                 storage,
+                local_connection,
                 ui,
                 video,
 
@@ -3014,7 +3371,7 @@ impl PlayerBuilder {
                 frame_phase: Default::default(),
                 frame_accumulator: FloatDuration::ZERO,
                 recent_run_frame_timings: VecDeque::with_capacity(10),
-                start_time: Instant::now(),
+                clock: MovieClock::new(),
                 time_offset: 0,
                 time_til_next_timer: None,
                 max_execution_duration: self.max_execution_duration,
@@ -3052,6 +3409,15 @@ impl PlayerBuilder {
                 notification_sender: self.notification_sender,
                 #[cfg(feature = "egui")]
                 debug_ui: Default::default(),
+                #[cfg(feature = "debugger")]
+                debugger: crate::debugger::Debugger::new(self.debugger_connection),
+                telemetry: self
+                    .telemetry_output
+                    .map(Telemetry::new)
+                    .unwrap_or_default(),
+                stats: Default::default(),
+                security: SecurityManager::new(self.security_sandbox),
+                network_log: network_log.clone(),
 
                 // GC data
                 gc_arena: Rc::new(RefCell::new(GcArena::new(|gc_context| {
@@ -3063,6 +3429,7 @@ impl PlayerBuilder {
                         fake_movie.clone(),
                         self.external_interface_provider,
                         self.fs_command_provider,
+                        network_log,
                     )
                 }))),
             })
@@ -3206,6 +3573,16 @@ fn run_mouse_pick<'gc>(
     })
 }
 
+/// Counts the display objects below the given one.
+fn count_descendants(object: DisplayObject<'_>) -> usize {
+    object.as_container().map_or(0, |container| {
+        container
+            .iter_render_list()
+            .map(|child| 1 + count_descendants(child))
+            .sum()
+    })
+}
+
 #[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
 #[derive(Default, Clone, Copy, Debug, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize))]
@@ -3249,3 +3626,28 @@ pub enum PlayerMode {
     /// Represents the debug version of Flash Player, i.e. flashplayerdebugger.
     Debug,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn time_scale_limits() {
+        let player = PlayerBuilder::new().build();
+        let mut player = player.lock().unwrap();
+
+        player.set_time_scale(2.0);
+        assert_eq!(player.time_scale(), 2.0);
+
+        player.set_time_scale(1000.0);
+        assert_eq!(player.time_scale(), 16.0);
+        player.set_time_scale(0.001);
+        assert_eq!(player.time_scale(), 1.0 / 16.0);
+
+        player.set_time_scale(0.0);
+        player.set_time_scale(-1.0);
+        player.set_time_scale(f64::NAN);
+        player.set_time_scale(f64::INFINITY);
+        assert_eq!(player.time_scale(), 1.0 / 16.0);
+    }
+}
@@ -0,0 +1,590 @@
+//! Flash Player security sandbox, see
+//! https://help.adobe.com/en_US/as3/dev/WS5b3ccc516d4fbf351e63e3d118a9b90204-7e3f.html
+//!
+//! Every movie belongs to a [`SecurityDomain`] determined by where it was
+//! loaded from. Movies may freely script and read data of their own domain,
+//! while other domains have to grant access explicitly: movies do so with
+//! `Security.allowDomain`, and servers do so with cross-domain policy files.
+//!
+//! The rules are only enforced when enabled through
+//! [`crate::PlayerBuilder::with_security_sandbox`], but the domain
+//! relationships are always reported to content, such as with
+//! `LoaderInfo.sameDomain`.
+//!
+//! Enforced are data loads of both AVMs, sockets, loading movies, images,
+//! sounds and videos, `BitmapData.draw`, and AVM2 access to loaded movies
+//! through `Loader.content`. AVM1 movies may still script movies of other
+//! domains, and AVM1 `BitmapData.draw` isn't checked either.
+
+use ruffle_common::policy_file::{PolicyFile, SOCKET_POLICY_PORT, SiteControl};
+use ruffle_common::sandbox::{SandboxType, SecurityDomain, domain_pattern_matches};
+use ruffle_common::tag_utils::SwfMovie;
+use std::collections::HashMap;
+use url::Url;
+
+/// A domain granted access by `Security.allowDomain`.
+#[derive(Debug, Clone)]
+struct DomainGrant {
+    pattern: String,
+
+    /// Granted by `allowInsecureDomain`, which also lets content served
+    /// over plain HTTP access content served over HTTPS.
+    insecure: bool,
+}
+
+/// Whether a movie may load data from a URL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LoadPermission {
+    Allowed,
+
+    /// The load is forbidden by the sandbox rules.
+    ///
+    /// Contains the AVM2 error code describing the violation.
+    Denied(u32),
+
+    /// The load is allowed if the policy files at these URLs grant access,
+    /// which have to be fetched first.
+    NeedsPolicyFiles(Vec<String>),
+}
+
+/// The security sandbox state of a player.
+#[derive(Debug, Default)]
+pub struct SecurityManager {
+    /// Whether the sandbox rules are enforced.
+    enabled: bool,
+
+    /// Domains granted access to each security domain by `Security.allowDomain`.
+    grants: HashMap<SecurityDomain, Vec<DomainGrant>>,
+
+    /// Policy files that were fetched, by URL.
+    ///
+    /// `None` means the policy file doesn't exist or couldn't be parsed.
+    policy_files: HashMap<String, Option<PolicyFile>>,
+
+    /// Policy files announced with `Security.loadPolicyFile`.
+    extra_policy_files: Vec<Url>,
+
+    /// Socket policy server ports announced with `Security.loadPolicyFile`, by host.
+    socket_policy_ports: HashMap<String, u16>,
+}
+
+impl SecurityManager {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            ..Default::default()
+        }
+    }
+
+    /// Whether the sandbox rules are enforced.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Grants movies of domains matching `pattern` access to `granter`,
+    /// as done by `Security.allowDomain` and `Security.allowInsecureDomain`.
+    pub fn allow_domain(&mut self, granter: SecurityDomain, pattern: String, insecure: bool) {
+        self.grants
+            .entry(granter)
+            .or_default()
+            .push(DomainGrant { pattern, insecure });
+    }
+
+    /// Whether code of the `accessor` movie may script the `target` movie.
+    pub fn can_access(&self, accessor: &SwfMovie, target: &SwfMovie) -> bool {
+        let accessor = accessor.security_domain();
+        let target = target.security_domain();
+        if accessor == target || accessor.is_trusted() {
+            return true;
+        }
+
+        self.grants.get(&target).is_some_and(|grants| {
+            grants.iter().any(|grant| {
+                domain_pattern_matches(&grant.pattern, accessor.host())
+                    && (grant.insecure || !target.is_secure() || accessor.is_secure())
+            })
+        })
+    }
+
+    /// Decides whether the `requester` movie may load data from `url`.
+    pub fn load_permission(&self, requester: &SwfMovie, url: &str) -> LoadPermission {
+        let Some(url) = resolve_url(requester, url) else {
+            return LoadPermission::Denied(2048);
+        };
+
+        let sandbox_type = requester.sandbox_type();
+        let is_local_resource = url.scheme() == "file";
+        match sandbox_type {
+            SandboxType::LocalTrusted | SandboxType::Application => {
+                return LoadPermission::Allowed;
+            }
+            SandboxType::LocalWithFile => {
+                return if is_local_resource {
+                    LoadPermission::Allowed
+                } else {
+                    LoadPermission::Denied(2048)
+                };
+            }
+            SandboxType::LocalWithNetwork | SandboxType::Remote if is_local_resource => {
+                return LoadPermission::Denied(2148);
+            }
+            SandboxType::LocalWithNetwork | SandboxType::Remote => {}
+        }
+
+        let requester = requester.security_domain();
+        if requester == SecurityDomain::of_resource(&url, sandbox_type) {
+            return LoadPermission::Allowed;
+        }
+
+        let missing = self.missing_policy_files(&url);
+        if !missing.is_empty() {
+            return LoadPermission::NeedsPolicyFiles(missing);
+        }
+
+        let secure_policy = url.scheme() == "https";
+        if self.policies_allow(&self.policy_file_urls(&url), |policy| {
+            policy.allows_access(&requester, secure_policy, None)
+        }) {
+            LoadPermission::Allowed
+        } else {
+            LoadPermission::Denied(2048)
+        }
+    }
+
+    /// Whether the `requester` movie may display content loaded from `url`,
+    /// such as movies and images loaded with `Loader.load`.
+    ///
+    /// Unlike data, such content may come from any domain, but only local
+    /// movies with file system access may load local files.
+    pub fn can_load_content(&self, requester: &SwfMovie, url: &str) -> bool {
+        matches!(
+            requester.sandbox_type(),
+            SandboxType::LocalWithFile | SandboxType::LocalTrusted | SandboxType::Application
+        ) || resolve_url(requester, url).is_some_and(|url| url.scheme() != "file")
+    }
+
+    /// The policy files that may grant access to `url` and weren't fetched yet.
+    pub fn missing_policy_files(&self, url: &Url) -> Vec<String> {
+        self.policy_file_urls(url)
+            .into_iter()
+            .filter(|policy_url| !self.policy_files.contains_key(policy_url))
+            .collect()
+    }
+
+    /// Whether policy files that were already fetched grant the `requester`
+    /// movie access to the resource at `url`.
+    ///
+    /// Returns `None` if no policy file was fetched for the resource yet.
+    pub fn policy_allows(&self, requester: &SwfMovie, url: &str) -> Option<bool> {
+        let url = resolve_url(requester, url)?;
+        let policy_urls = self.policy_file_urls(&url);
+        if !policy_urls
+            .iter()
+            .any(|policy_url| self.policy_files.contains_key(policy_url))
+        {
+            return None;
+        }
+
+        let requester = requester.security_domain();
+        let secure_policy = url.scheme() == "https";
+        Some(self.policies_allow(&policy_urls, |policy| {
+            policy.allows_access(&requester, secure_policy, None)
+        }))
+    }
+
+    /// Whether the `requester` movie may send the HTTP headers named `headers`
+    /// along with a request to `url`.
+    ///
+    /// Headers may always be sent within a domain, but another domain has to
+    /// allow them in a policy file, which has to be fetched already
+    /// (such as by [`Self::load_permission`]).
+    pub fn can_send_headers<'a>(
+        &self,
+        requester: &SwfMovie,
+        url: &str,
+        mut headers: impl Iterator<Item = &'a str>,
+    ) -> bool {
+        if requester.security_domain().is_trusted() {
+            return true;
+        }
+        let Some(url) = resolve_url(requester, url) else {
+            return false;
+        };
+
+        let sandbox_type = requester.sandbox_type();
+        let requester = requester.security_domain();
+        if requester == SecurityDomain::of_resource(&url, sandbox_type) {
+            return true;
+        }
+
+        let secure_policy = url.scheme() == "https";
+        let policy_urls = self.policy_file_urls(&url);
+        headers.all(|header| {
+            self.policies_allow(&policy_urls, |policy| {
+                policy.allows_header(&requester, secure_policy, header)
+            })
+        })
+    }
+
+    /// Whether any of the policy files at `policy_urls` that the master policy
+    /// file lets be considered satisfies `allows`.
+    fn policies_allow(&self, policy_urls: &[String], allows: impl Fn(&PolicyFile) -> bool) -> bool {
+        // The master policy decides which other policy files are considered.
+        // Without one, only the master policy file would be, so nothing is allowed.
+        let Some((master_url, others)) = policy_urls.split_first() else {
+            return false;
+        };
+        let Some(Some(master)) = self.policy_files.get(master_url) else {
+            return false;
+        };
+
+        let site_control = master.site_control.unwrap_or(SiteControl::MasterOnly);
+        let considered = match site_control {
+            SiteControl::None => return false,
+            SiteControl::MasterOnly => &[][..],
+            SiteControl::ByContentType | SiteControl::ByFtpFilename | SiteControl::All => others,
+        };
+
+        allows(master)
+            || considered.iter().any(|policy_url| {
+                matches!(self.policy_files.get(policy_url), Some(Some(policy)) if allows(policy))
+            })
+    }
+
+    /// The URLs of the policy files that may grant access to `url`,
+    /// starting with the master policy file of its site.
+    fn policy_file_urls(&self, url: &Url) -> Vec<String> {
+        let Ok(mut master) = url.join("/crossdomain.xml") else {
+            return vec![];
+        };
+        master.set_query(None);
+        master.set_fragment(None);
+
+        let mut urls = vec![master.to_string()];
+        for policy_url in &self.extra_policy_files {
+            // A policy file only applies to its own directory and below.
+            let path = policy_url.path();
+            let directory = &path[..path.rfind('/').map_or(0, |end| end + 1)];
+            if policy_url.origin() == url.origin()
+                && url.path().starts_with(directory)
+                && !urls.contains(&policy_url.to_string())
+            {
+                urls.push(policy_url.to_string());
+            }
+        }
+        urls
+    }
+
+    /// Announces the location of a policy file, as done by `Security.loadPolicyFile`.
+    ///
+    /// Returns the URL of the policy file if it has to be fetched over HTTP.
+    pub fn register_policy_file(&mut self, url: &str) -> Option<String> {
+        let url = Url::parse(url).ok()?;
+        if url.scheme() == "xmlsocket" {
+            if let Some(host) = url.host_str() {
+                let port = url.port().unwrap_or(SOCKET_POLICY_PORT);
+                self.socket_policy_ports
+                    .insert(host.to_ascii_lowercase(), port);
+            }
+            return None;
+        }
+
+        if !self.extra_policy_files.contains(&url) {
+            self.extra_policy_files.push(url.clone());
+        }
+        let url = url.to_string();
+        (!self.policy_files.contains_key(&url)).then_some(url)
+    }
+
+    /// Stores a fetched policy file, or `None` if it couldn't be loaded.
+    pub fn store_policy_file(&mut self, url: String, policy: Option<PolicyFile>) {
+        self.policy_files.insert(url, policy);
+    }
+
+    /// How a connection of the `requester` movie to a socket on `host` has
+    /// to be authorized, or `None` if it doesn't need a socket policy file.
+    pub fn socket_policy_request(
+        &self,
+        requester: &SwfMovie,
+        host: &str,
+    ) -> Option<SocketPolicyRequest> {
+        if !self.enabled
+            || matches!(
+                requester.sandbox_type(),
+                SandboxType::LocalTrusted | SandboxType::Application
+            )
+        {
+            return None;
+        }
+
+        let policy_port = self
+            .socket_policy_ports
+            .get(&host.to_ascii_lowercase())
+            .copied()
+            .unwrap_or(SOCKET_POLICY_PORT);
+        Some(SocketPolicyRequest {
+            requester: requester.security_domain(),
+            requester_url: requester.url().to_string(),
+            policy_port,
+        })
+    }
+}
+
+/// Resolves `url` the way the navigator will when fetching it for the
+/// `requester` movie, so that relative URLs are checked against where they
+/// actually lead.
+fn resolve_url(requester: &SwfMovie, url: &str) -> Option<Url> {
+    Url::parse(requester.url()).ok()?.join(url).ok()
+}
+
+/// How a socket connection gets authorized by a socket policy file.
+///
+/// Before connecting, the policy file is requested from the socket policy
+/// server of the host, and then from the destination port itself.
+#[derive(Debug, Clone)]
+pub struct SocketPolicyRequest {
+    /// The domain of the movie that wants to connect.
+    pub requester: SecurityDomain,
+
+    /// The URL of the movie that wants to connect, for error messages.
+    pub requester_url: String,
+
+    /// The port where the socket policy server is expected.
+    pub policy_port: u16,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn movie(url: &str) -> SwfMovie {
+        SwfMovie::from_loaded_image(url.to_string(), false, 0, 1, 1)
+    }
+
+    #[test]
+    fn allow_domain() {
+        let mut security = SecurityManager::new(true);
+        let parent = movie("http://portal.test/index.swf");
+        let child = movie("http://games.test/game.swf");
+
+        assert!(security.can_access(&parent, &movie("http://portal.test/other.swf")));
+        assert!(!security.can_access(&parent, &child));
+
+        security.allow_domain(child.security_domain(), "*.portal.test".to_string(), false);
+        assert!(security.can_access(&parent, &child));
+        assert!(!security.can_access(&child, &parent));
+    }
+
+    #[test]
+    fn allow_insecure_domain() {
+        let mut security = SecurityManager::new(true);
+        let insecure = movie("http://portal.test/index.swf");
+        let secure = movie("https://games.test/game.swf");
+
+        security.allow_domain(secure.security_domain(), "portal.test".to_string(), false);
+        assert!(!security.can_access(&insecure, &secure));
+
+        security.allow_domain(secure.security_domain(), "portal.test".to_string(), true);
+        assert!(security.can_access(&insecure, &secure));
+    }
+
+    #[test]
+    fn load_permission() {
+        let mut security = SecurityManager::new(true);
+        let requester = movie("http://portal.test/index.swf");
+
+        assert_eq!(
+            security.load_permission(&requester, "http://portal.test/data.xml"),
+            LoadPermission::Allowed
+        );
+        assert_eq!(
+            security.load_permission(&requester, "file:///etc/passwd"),
+            LoadPermission::Denied(2148)
+        );
+        assert!(!security.can_load_content(&requester, "file:///image.png"));
+        assert!(security.can_load_content(&requester, "http://cdn.test/image.png"));
+        assert_eq!(
+            security.load_permission(&requester, "http://api.test/v1/data?id=1"),
+            LoadPermission::NeedsPolicyFiles(vec!["http://api.test/crossdomain.xml".to_string()])
+        );
+
+        security.store_policy_file(
+            "http://api.test/crossdomain.xml".to_string(),
+            Some(
+                PolicyFile::parse(
+                    br#"<cross-domain-policy><allow-access-from domain="portal.test"/></cross-domain-policy>"#,
+                )
+                .unwrap(),
+            ),
+        );
+        assert_eq!(
+            security.load_permission(&requester, "http://api.test/v1/data?id=1"),
+            LoadPermission::Allowed
+        );
+        assert_eq!(
+            security.load_permission(&movie("http://other.test/a.swf"), "http://api.test/v1/data"),
+            LoadPermission::Denied(2048)
+        );
+    }
+
+    #[test]
+    fn http_headers() {
+        let mut security = SecurityManager::new(true);
+        let requester = movie("http://portal.test/index.swf");
+
+        assert!(security.can_send_headers(
+            &requester,
+            "http://portal.test/api",
+            ["X-Custom"].into_iter()
+        ));
+        assert!(!security.can_send_headers(
+            &requester,
+            "http://api.test/v1/data",
+            ["X-Custom"].into_iter()
+        ));
+
+        security.store_policy_file(
+            "http://api.test/crossdomain.xml".to_string(),
+            Some(
+                PolicyFile::parse(
+                    br#"<cross-domain-policy><allow-access-from domain="*"/><allow-http-request-headers-from domain="portal.test" headers="X-Custom,X-Api-*"/></cross-domain-policy>"#,
+                )
+                .unwrap(),
+            ),
+        );
+        assert!(security.can_send_headers(
+            &requester,
+            "http://api.test/v1/data",
+            ["X-Custom", "X-Api-Key"].into_iter()
+        ));
+        assert!(!security.can_send_headers(
+            &requester,
+            "http://api.test/v1/data",
+            ["X-Custom", "Authorization"].into_iter()
+        ));
+        assert!(!security.can_send_headers(
+            &movie("http://other.test/a.swf"),
+            "http://api.test/v1/data",
+            ["X-Custom"].into_iter()
+        ));
+    }
+
+    #[test]
+    fn relative_urls() {
+        let security = SecurityManager::new(true);
+        let requester = movie("http://portal.test/games/index.swf");
+
+        assert_eq!(
+            security.load_permission(&requester, "data.xml"),
+            LoadPermission::Allowed
+        );
+        assert_eq!(
+            security.load_permission(&requester, "../data.xml"),
+            LoadPermission::Allowed
+        );
+        // Protocol-relative URLs lead to another domain.
+        assert_eq!(
+            security.load_permission(&requester, "//evil.test/data"),
+            LoadPermission::NeedsPolicyFiles(vec!["http://evil.test/crossdomain.xml".to_string()])
+        );
+
+        let local = movie("file:///home/user/game.swf");
+        assert!(security.can_load_content(&local, "image.png"));
+        assert_eq!(
+            security.load_permission(&local, "../secret.txt"),
+            LoadPermission::Allowed
+        );
+
+        // A local movie with network access can't reach local files through relative paths.
+        let mut data = Vec::new();
+        swf::write_swf(
+            &swf::Header::default_with_swf_version(10),
+            &[swf::Tag::FileAttributes(
+                swf::FileAttributes::USE_NETWORK_SANDBOX,
+            )],
+            &mut data,
+        )
+        .unwrap();
+        let networked =
+            SwfMovie::from_data(&data, "file:///home/user/game.swf".to_string(), None, None)
+                .unwrap();
+        assert_eq!(networked.sandbox_type(), SandboxType::LocalWithNetwork);
+        assert!(!security.can_load_content(&networked, "image.png"));
+        assert_eq!(
+            security.load_permission(&networked, "data.txt"),
+            LoadPermission::Denied(2148)
+        );
+    }
+
+    #[test]
+    fn extra_policy_files() {
+        let mut security = SecurityManager::new(true);
+        let requester = movie("http://portal.test/index.swf");
+
+        assert_eq!(
+            security.register_policy_file("http://api.test/v1/policy.xml"),
+            Some("http://api.test/v1/policy.xml".to_string())
+        );
+        security.store_policy_file(
+            "http://api.test/crossdomain.xml".to_string(),
+            Some(
+                PolicyFile::parse(
+                    br#"<cross-domain-policy><site-control permitted-cross-domain-policies="all"/></cross-domain-policy>"#,
+                )
+                .unwrap(),
+            ),
+        );
+        assert_eq!(
+            security.load_permission(&requester, "http://api.test/v1/data"),
+            LoadPermission::NeedsPolicyFiles(vec!["http://api.test/v1/policy.xml".to_string()])
+        );
+
+        security.store_policy_file(
+            "http://api.test/v1/policy.xml".to_string(),
+            Some(
+                PolicyFile::parse(
+                    br#"<cross-domain-policy><allow-access-from domain="*"/></cross-domain-policy>"#,
+                )
+                .unwrap(),
+            ),
+        );
+        assert_eq!(
+            security.load_permission(&requester, "http://api.test/v1/data"),
+            LoadPermission::Allowed
+        );
+        // The policy file doesn't apply outside of its directory.
+        assert_eq!(
+            security.load_permission(&requester, "http://api.test/v2/data"),
+            LoadPermission::Denied(2048)
+        );
+    }
+
+    #[test]
+    fn socket_policy_ports() {
+        let mut security = SecurityManager::new(true);
+        assert_eq!(
+            security.register_policy_file("xmlsocket://chat.test:5000"),
+            None
+        );
+        let requester = movie("http://portal.test/index.swf");
+        assert_eq!(
+            security
+                .socket_policy_request(&requester, "chat.test")
+                .unwrap()
+                .policy_port,
+            5000
+        );
+        assert_eq!(
+            security
+                .socket_policy_request(&requester, "other.test")
+                .unwrap()
+                .policy_port,
+            SOCKET_POLICY_PORT
+        );
+        assert!(
+            SecurityManager::new(false)
+                .socket_policy_request(&requester, "chat.test")
+                .is_none()
+        );
+    }
+}
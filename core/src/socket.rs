@@ -2,18 +2,24 @@ use crate::avm1::{
     Activation as Avm1Activation, ActivationIdentifier, ExecutionReason, Object as Avm1Object,
     globals::xml_socket::XmlSocket,
 };
+use crate::avm2::error_messages::try_error_message;
 use crate::avm2::object::{EventObject, SocketObject};
 use crate::avm2::{Activation as Avm2Activation, Avm2};
 use crate::backend::navigator::NavigatorBackend;
 use crate::context::UpdateContext;
+use crate::network_log::{BodyPreview, NetworkLog, SocketEvent};
+use crate::security::SocketPolicyRequest;
 use crate::string::AvmString;
+use crate::tag_utils::SwfMovie;
 
 use async_channel::{Receiver, Sender, unbounded};
 use gc_arena::Collect;
+use ruffle_common::policy_file::{PolicyFile, SOCKET_POLICY_REQUEST};
 use ruffle_macros::istr;
 use slotmap::{SlotMap, new_key_type};
 use std::{
     cell::{Cell, RefCell},
+    sync::Arc,
     time::Duration,
 };
 
@@ -34,18 +40,53 @@ struct Socket<'gc> {
     target: SocketKind<'gc>,
     sender: RefCell<Sender<Vec<u8>>>,
     connected: Cell<bool>,
+
+    /// The socket policy file check this connection is made for, if any.
+    ///
+    /// While set, the connection goes to a policy server instead of the
+    /// destination requested by the movie.
+    #[collect(require_static)]
+    policy: RefCell<Option<PolicyCheck>>,
+
+    /// The movie whose code opened this connection.
+    #[collect(require_static)]
+    movie: Arc<SwfMovie>,
 }
 
 impl<'gc> Socket<'gc> {
-    fn new(target: SocketKind<'gc>, sender: Sender<Vec<u8>>) -> Self {
+    fn new(
+        target: SocketKind<'gc>,
+        sender: Sender<Vec<u8>>,
+        policy: Option<PolicyCheck>,
+        movie: Arc<SwfMovie>,
+    ) -> Self {
         Self {
             target,
             sender: RefCell::new(sender),
             connected: Cell::new(false),
+            policy: RefCell::new(policy),
+            movie,
         }
     }
 }
 
+/// A socket policy file being requested before connecting to a socket.
+struct PolicyCheck {
+    request: SocketPolicyRequest,
+
+    /// The destination the movie wants to connect to.
+    host: String,
+    port: u16,
+    timeout: Duration,
+
+    /// Whether the policy is requested from the destination port, after the
+    /// policy server didn't provide one.
+    on_destination_port: bool,
+
+    /// The data received so far.
+    response: Vec<u8>,
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub enum ConnectionState {
     Connected,
@@ -70,39 +111,45 @@ pub struct Sockets<'gc> {
     receiver: Receiver<SocketAction>,
     #[collect(require_static)]
     sender: Sender<SocketAction>,
+
+    #[collect(require_static)]
+    network_log: NetworkLog,
 }
 
 impl<'gc> Sockets<'gc> {
-    pub fn empty() -> Self {
+    pub fn empty(network_log: NetworkLog) -> Self {
         let (sender, receiver) = unbounded();
 
         Self {
             sockets: SlotMap::with_key(),
             receiver,
             sender,
+            network_log,
         }
     }
 
+    /// Connect an AVM2 `Socket` of `movie` to the given destination.
+    ///
+    /// With a `policy`, a socket policy file granting the connection is
+    /// requested first.
     pub fn connect_avm2(
         &mut self,
         backend: &mut dyn NavigatorBackend,
         target: SocketObject<'gc>,
         host: String,
         port: u16,
+        policy: Option<SocketPolicyRequest>,
+        movie: Arc<SwfMovie>,
     ) {
-        let (sender, receiver) = unbounded();
-
-        let socket = Socket::new(SocketKind::Avm2(target), sender);
-        let handle = self.sockets.insert(socket);
-
-        // NOTE: This call will send SocketAction::Connect to sender with connection status.
-        backend.connect_socket(
-            sanitize_host(&host).to_string(),
+        let timeout = Duration::from_millis(target.timeout().into());
+        let handle = self.connect(
+            backend,
+            SocketKind::Avm2(target),
+            host,
             port,
-            Duration::from_millis(target.timeout().into()),
-            handle,
-            receiver,
-            self.sender.clone(),
+            timeout,
+            policy,
+            movie,
         );
 
         if let Some(existing_handle) = target.set_handle(handle) {
@@ -112,31 +159,33 @@ impl<'gc> Sockets<'gc> {
         }
     }
 
+    /// Connect an AVM1 `XMLSocket` of `movie` to the given destination.
+    ///
+    /// With a `policy`, a socket policy file granting the connection is
+    /// requested first.
     pub fn connect_avm1(
         &mut self,
         backend: &mut dyn NavigatorBackend,
         target: Avm1Object<'gc>,
         host: String,
         port: u16,
+        policy: Option<SocketPolicyRequest>,
+        movie: Arc<SwfMovie>,
     ) {
-        let (sender, receiver) = unbounded();
-
         let xml_socket = match XmlSocket::cast(target.into()) {
             Some(xml_socket) => xml_socket,
             None => return,
         };
 
-        let socket = Socket::new(SocketKind::Avm1(target), sender);
-        let handle = self.sockets.insert(socket);
-
-        // NOTE: This call will send SocketAction::Connect to sender with connection status.
-        backend.connect_socket(
-            sanitize_host(&host).to_string(),
+        let timeout = Duration::from_millis(xml_socket.timeout().into());
+        let handle = self.connect(
+            backend,
+            SocketKind::Avm1(target),
+            host,
             port,
-            Duration::from_millis(xml_socket.timeout().into()),
-            handle,
-            receiver,
-            self.sender.clone(),
+            timeout,
+            policy,
+            movie,
         );
 
         if let Some(existing_handle) = xml_socket.set_handle(handle) {
@@ -146,6 +195,57 @@ impl<'gc> Sockets<'gc> {
         }
     }
 
+    fn connect(
+        &mut self,
+        backend: &mut dyn NavigatorBackend,
+        target: SocketKind<'gc>,
+        host: String,
+        port: u16,
+        timeout: Duration,
+        policy: Option<SocketPolicyRequest>,
+        movie: Arc<SwfMovie>,
+    ) -> SocketHandle {
+        let host = sanitize_host(&host).to_string();
+        let (connect_port, policy) = match policy {
+            Some(request) => (
+                request.policy_port,
+                Some(PolicyCheck {
+                    request,
+                    host: host.clone(),
+                    port,
+                    timeout,
+                    on_destination_port: false,
+                    response: Vec::new(),
+                }),
+            ),
+            None => (port, None),
+        };
+
+        self.connect_internal(backend, target, host, connect_port, timeout, policy, movie)
+    }
+
+    fn connect_internal(
+        &mut self,
+        backend: &mut dyn NavigatorBackend,
+        target: SocketKind<'gc>,
+        host: String,
+        port: u16,
+        timeout: Duration,
+        policy: Option<PolicyCheck>,
+        movie: Arc<SwfMovie>,
+    ) -> SocketHandle {
+        let (sender, receiver) = unbounded();
+
+        let socket = Socket::new(target, sender, policy, movie);
+        let handle = self.sockets.insert(socket);
+        self.network_log.socket_connect(handle, &host, port);
+
+        // NOTE: This call will send SocketAction::Connect to sender with connection status.
+        backend.connect_socket(host, port, timeout, handle, receiver, self.sender.clone());
+
+        handle
+    }
+
     pub fn is_connected(&self, handle: SocketHandle) -> bool {
         if let Some(socket) = self.sockets.get(handle) {
             socket.connected.get()
@@ -156,6 +256,8 @@ impl<'gc> Sockets<'gc> {
 
     pub fn send(&mut self, handle: SocketHandle, data: Vec<u8>) {
         if let Some(Socket { sender, .. }) = self.sockets.get_mut(handle) {
+            self.network_log
+                .socket_event(handle, || SocketEvent::Sent(BodyPreview::new(&data)));
             // We use an unbounded socket, so this should only ever error if the channel is closed
             // (the receiver was dropped)
             if let Err(e) = sender.borrow().try_send(data) {
@@ -165,22 +267,39 @@ impl<'gc> Sockets<'gc> {
     }
 
     pub fn close_all(&mut self) {
-        for (_, socket) in self.sockets.drain() {
+        for (handle, socket) in self.sockets.drain() {
+            self.network_log.socket_closed(handle);
             Self::close_internal(socket);
         }
     }
 
     pub fn close(&mut self, handle: SocketHandle) {
         if let Some(socket) = self.sockets.remove(handle) {
+            self.network_log.socket_closed(handle);
             Self::close_internal(socket);
         }
     }
 
+    /// Close all connections opened by the given movie.
+    pub fn close_all_of_movie(&mut self, movie: &Arc<SwfMovie>) {
+        let handles: Vec<_> = self
+            .sockets
+            .iter()
+            .filter(|(_, socket)| Arc::ptr_eq(&socket.movie, movie))
+            .map(|(handle, _)| handle)
+            .collect();
+        for handle in handles {
+            self.close(handle);
+        }
+    }
+
     fn close_internal(socket: Socket) {
         let Socket {
             sender,
             target,
             connected: _,
+            policy: _,
+            movie: _,
         } = socket;
 
         drop(sender); // NOTE: By dropping the sender, the reading task will close automatically.
@@ -207,6 +326,10 @@ impl<'gc> Sockets<'gc> {
         }
 
         for action in actions {
+            let Some(action) = Self::update_policy_check(context, action) else {
+                continue;
+            };
+
             match action {
                 SocketAction::Connect(handle, ConnectionState::Connected) => {
                     let target = match context.sockets.sockets.get(handle) {
@@ -356,6 +479,7 @@ impl<'gc> Sockets<'gc> {
                 SocketAction::Close(handle) => {
                     let target = match context.sockets.sockets.remove(handle) {
                         Some(socket) => {
+                            context.sockets.network_log.socket_closed(handle);
                             socket.connected.set(false);
                             socket.target
                         }
@@ -396,6 +520,168 @@ impl<'gc> Sockets<'gc> {
             }
         }
     }
+
+    fn log_action(&self, handle: SocketHandle, action: &SocketAction) {
+        let log = &self.network_log;
+        match action {
+            SocketAction::Connect(_, ConnectionState::Connected) => {
+                log.socket_event(handle, || SocketEvent::Connected)
+            }
+            SocketAction::Connect(..) => log.socket_event(handle, || SocketEvent::ConnectionFailed),
+            SocketAction::Data(_, data) => {
+                log.socket_event(handle, || SocketEvent::Received(BodyPreview::new(data)))
+            }
+            // Logged once the socket is removed, which also forgets its destination.
+            SocketAction::Close(_) => {}
+        }
+    }
+
+    /// Handle an action of a connection made for a socket policy file.
+    ///
+    /// Actions of sockets that are still open are logged here, before a policy check
+    /// can close its socket and forget where it was connected to.
+    ///
+    /// Returns the action back if the connection is a regular one.
+    fn update_policy_check(
+        context: &mut UpdateContext<'gc>,
+        action: SocketAction,
+    ) -> Option<SocketAction> {
+        let handle = match &action {
+            SocketAction::Connect(handle, _)
+            | SocketAction::Data(handle, _)
+            | SocketAction::Close(handle) => *handle,
+        };
+        let socket = context.sockets.sockets.get(handle)?;
+        context.sockets.log_action(handle, &action);
+        let mut policy = socket.policy.borrow_mut();
+        let Some(check) = policy.as_mut() else {
+            drop(policy);
+            return Some(action);
+        };
+
+        match action {
+            SocketAction::Connect(_, ConnectionState::Connected) => {
+                if let Err(e) = socket
+                    .sender
+                    .borrow()
+                    .try_send(SOCKET_POLICY_REQUEST.to_vec())
+                {
+                    tracing::error!("Failed to request socket policy file: {:?}", e);
+                }
+                return None;
+            }
+            SocketAction::Data(_, data) => {
+                check.response.extend(data);
+                if !check.response.contains(&0) {
+                    return None;
+                }
+            }
+            SocketAction::Connect(_, ConnectionState::Failed | ConnectionState::TimedOut)
+            | SocketAction::Close(_) => {}
+        }
+
+        let allowed = match PolicyFile::parse(&check.response) {
+            Ok(policy_file) => {
+                policy_file.allows_access(&check.request.requester, false, Some(check.port))
+            }
+            Err(e) => {
+                if !check.response.is_empty() {
+                    tracing::warn!("Invalid socket policy file from {}: {}", check.host, e);
+                }
+                false
+            }
+        };
+        let mut check = policy.take().expect("policy check should be in progress");
+        drop(policy);
+
+        let target = socket.target;
+        let movie = socket.movie.clone();
+        context.sockets.close(handle);
+
+        if allowed {
+            let handle = context.sockets.connect_internal(
+                context.navigator,
+                target,
+                check.host,
+                check.port,
+                check.timeout,
+                None,
+                movie,
+            );
+            Self::set_target_handle(target, handle);
+        } else if !check.on_destination_port {
+            // Without a policy server, the destination itself may serve a policy file.
+            check.on_destination_port = true;
+            check.response.clear();
+            let (host, port, timeout) = (check.host.clone(), check.port, check.timeout);
+            let handle = context.sockets.connect_internal(
+                context.navigator,
+                target,
+                host,
+                port,
+                timeout,
+                Some(check),
+                movie,
+            );
+            Self::set_target_handle(target, handle);
+        } else {
+            Self::deny_connection(context, target, &check);
+        }
+
+        None
+    }
+
+    fn set_target_handle(target: SocketKind<'gc>, handle: SocketHandle) {
+        match target {
+            SocketKind::Avm2(target) => {
+                target.set_handle(handle);
+            }
+            SocketKind::Avm1(target) => {
+                let xml_socket =
+                    XmlSocket::cast(target.into()).expect("target should be XmlSocket");
+                xml_socket.set_handle(handle);
+            }
+        }
+    }
+
+    fn deny_connection(
+        context: &mut UpdateContext<'gc>,
+        target: SocketKind<'gc>,
+        check: &PolicyCheck,
+    ) {
+        tracing::warn!(
+            "Socket connection to {}:{} denied: no socket policy file grants access to {}",
+            check.host,
+            check.port,
+            check.request.requester_url
+        );
+
+        match target {
+            SocketKind::Avm2(target) => {
+                let mut activation = Avm2Activation::from_nothing(context);
+
+                let destination = format!("{}:{}", check.host, check.port);
+                let message =
+                    try_error_message(2048, &[&check.request.requester_url, &destination])
+                        .unwrap_or_else(|| "Error #2048".to_string());
+                let security_error_evt =
+                    EventObject::security_error_event(&mut activation, &message, 2048);
+
+                Avm2::dispatch_event(activation.context, security_error_evt, target.into());
+            }
+            SocketKind::Avm1(target) => {
+                let mut activation =
+                    Avm1Activation::from_stub(context, ActivationIdentifier::root("[XMLSocket]"));
+
+                let _ = target.call_method(
+                    istr!("onConnect"),
+                    &[false.into()],
+                    &mut activation,
+                    ExecutionReason::Special,
+                );
+            }
+        }
+    }
 }
 
 /// Flash treats a socket host as a cstring, and stops reading at a null byte.
@@ -0,0 +1,230 @@
+//! Performance statistics of a running player.
+//!
+//! The player collects these as it runs and renders frames. They can be read
+//! with [`crate::Player::stats`], which is what the performance HUD of the
+//! debug UI shows, and what the exporter writes out.
+
+use std::collections::VecDeque;
+use web_time::{Duration, Instant};
+
+/// How many frames timings are averaged over.
+pub(crate) const TIMING_WINDOW: usize = 30;
+
+/// The period over which frames are counted to compute the frame rate.
+const FPS_PERIOD: Duration = Duration::from_secs(1);
+
+/// A snapshot of the performance of a player.
+///
+/// Timings are averaged over the last few frames by [`crate::Player::stats`],
+/// and are those of the last frame alone with [`crate::Player::frame_stats`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PlayerStats {
+    /// The number of frames run during the last second.
+    pub fps: u32,
+
+    /// The frame rate the movie is meant to run at.
+    pub target_fps: f64,
+
+    /// Time spent per frame running AVM1 frame scripts and queued actions.
+    pub avm1_time: Duration,
+
+    /// Time spent per frame in the AVM2 frame phases, which includes frame
+    /// scripts and `enterFrame`/`exitFrame` handlers.
+    pub avm2_time: Duration,
+
+    /// Time spent building and submitting a rendered frame.
+    pub render_time: Duration,
+
+    /// Time spent tessellating shapes per rendered frame.
+    pub tessellation_time: Duration,
+
+    /// The number of render commands in the last rendered frame.
+    pub render_commands: usize,
+
+    /// The number of display objects on the stage, not counting the stage itself.
+    pub display_objects: usize,
+
+    /// Memory used by bitmaps, in bytes, if the render backend keeps track of it.
+    pub bitmap_memory: Option<u64>,
+
+    /// The number of sounds currently playing.
+    pub audio_instances: usize,
+
+    /// The size of the garbage collected heap, in bytes.
+    pub gc_heap_size: usize,
+}
+
+#[derive(Clone, Copy, Default)]
+struct FrameTimings {
+    avm1: Duration,
+    avm2: Duration,
+}
+
+#[derive(Clone, Copy, Default)]
+struct RenderTimings {
+    render: Duration,
+    tessellation: Duration,
+}
+
+/// Collects the timings that make up [`PlayerStats`].
+#[derive(Default)]
+pub(crate) struct StatsTracker {
+    /// When the frames of the last [`FPS_PERIOD`] were run.
+    frame_ends: VecDeque<Instant>,
+
+    /// Timings of the frame currently being run.
+    current_frame: FrameTimings,
+
+    frames: VecDeque<FrameTimings>,
+    renders: VecDeque<RenderTimings>,
+
+    /// The total tessellation time reported by the renderer on the last render.
+    total_tessellation_time: Duration,
+
+    render_commands: usize,
+}
+
+impl StatsTracker {
+    pub fn add_avm1_time(&mut self, time: Duration) {
+        self.current_frame.avm1 += time;
+    }
+
+    pub fn add_avm2_time(&mut self, time: Duration) {
+        self.current_frame.avm2 += time;
+    }
+
+    pub fn end_frame(&mut self, now: Instant) {
+        self.frame_ends.push_back(now);
+        while self
+            .frame_ends
+            .front()
+            .is_some_and(|end| now.duration_since(*end) > FPS_PERIOD)
+        {
+            self.frame_ends.pop_front();
+        }
+
+        push_bounded(&mut self.frames, std::mem::take(&mut self.current_frame));
+    }
+
+    /// Records a rendered frame.
+    ///
+    /// `total_tessellation_time` is the cumulative time reported by the
+    /// renderer, so that shapes tessellated between renders are accounted for.
+    pub fn end_render(
+        &mut self,
+        render_time: Duration,
+        total_tessellation_time: Duration,
+        render_commands: usize,
+    ) {
+        let tessellation = total_tessellation_time.saturating_sub(self.total_tessellation_time);
+        self.total_tessellation_time = total_tessellation_time;
+        self.render_commands = render_commands;
+
+        push_bounded(
+            &mut self.renders,
+            RenderTimings {
+                render: render_time,
+                tessellation,
+            },
+        );
+    }
+
+    /// Returns the statistics tracked here, leaving the others at their defaults.
+    ///
+    /// Timings are averaged over the last `frames` frames and renders, at most [`TIMING_WINDOW`].
+    pub fn snapshot(&self, now: Instant, frames: usize) -> PlayerStats {
+        let fps = self
+            .frame_ends
+            .iter()
+            .filter(|end| now.duration_since(**end) <= FPS_PERIOD)
+            .count();
+
+        PlayerStats {
+            fps: fps as u32,
+            avm1_time: average(last(&self.frames, frames).map(|frame| frame.avm1)),
+            avm2_time: average(last(&self.frames, frames).map(|frame| frame.avm2)),
+            render_time: average(last(&self.renders, frames).map(|render| render.render)),
+            tessellation_time: average(
+                last(&self.renders, frames).map(|render| render.tessellation),
+            ),
+            render_commands: self.render_commands,
+            ..Default::default()
+        }
+    }
+}
+
+fn push_bounded<T>(timings: &mut VecDeque<T>, value: T) {
+    if timings.len() >= TIMING_WINDOW {
+        timings.pop_front();
+    }
+    timings.push_back(value);
+}
+
+fn last<T: Copy>(timings: &VecDeque<T>, count: usize) -> impl ExactSizeIterator<Item = T> {
+    timings
+        .range(timings.len().saturating_sub(count)..)
+        .copied()
+}
+
+fn average(durations: impl ExactSizeIterator<Item = Duration>) -> Duration {
+    let count = durations.len() as u32;
+    if count == 0 {
+        Duration::ZERO
+    } else {
+        durations.sum::<Duration>() / count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frame_rate() {
+        let start = Instant::now();
+        let mut tracker = StatsTracker::default();
+        for frame in 0..30 {
+            tracker.end_frame(start + Duration::from_millis(frame * 50));
+        }
+
+        // Frames from 0.45s to 1.45s.
+        let stats = tracker.snapshot(start + Duration::from_millis(1450), TIMING_WINDOW);
+        assert_eq!(stats.fps, 21);
+
+        let stats = tracker.snapshot(start + Duration::from_secs(10), TIMING_WINDOW);
+        assert_eq!(stats.fps, 0);
+    }
+
+    #[test]
+    fn timings() {
+        let start = Instant::now();
+        let mut tracker = StatsTracker::default();
+        assert_eq!(
+            tracker.snapshot(start, TIMING_WINDOW),
+            PlayerStats::default()
+        );
+
+        tracker.add_avm1_time(Duration::from_millis(1));
+        tracker.add_avm1_time(Duration::from_millis(2));
+        tracker.add_avm2_time(Duration::from_millis(4));
+        tracker.end_frame(start);
+        tracker.add_avm1_time(Duration::from_millis(1));
+        tracker.end_frame(start);
+
+        tracker.end_render(Duration::from_millis(6), Duration::from_millis(10), 5);
+        tracker.end_render(Duration::from_millis(2), Duration::from_millis(12), 3);
+
+        let stats = tracker.snapshot(start, TIMING_WINDOW);
+        assert_eq!(stats.avm1_time, Duration::from_millis(2));
+        assert_eq!(stats.avm2_time, Duration::from_millis(2));
+        assert_eq!(stats.render_time, Duration::from_millis(4));
+        assert_eq!(stats.tessellation_time, Duration::from_millis(6));
+        assert_eq!(stats.render_commands, 3);
+
+        let stats = tracker.snapshot(start, 1);
+        assert_eq!(stats.avm1_time, Duration::from_millis(1));
+        assert_eq!(stats.avm2_time, Duration::ZERO);
+        assert_eq!(stats.render_time, Duration::from_millis(2));
+        assert_eq!(stats.tessellation_time, Duration::from_millis(2));
+    }
+}
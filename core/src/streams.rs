@@ -1,5 +1,7 @@
 //! NetStream implementation
 
+pub mod mp4;
+
 use crate::avm1::{
     Activation as Avm1Activation, ActivationIdentifier as Avm1ActivationIdentifier,
     ExecutionReason as Avm1ExecutionReason, FlvValueAvm1Ext, Object as Avm1Object,
@@ -17,15 +19,17 @@ use crate::context::UpdateContext;
 use crate::display_object::{MovieClip, TDisplayObject};
 use crate::loader::Error;
 use crate::string::AvmString;
+use crate::tag_utils::SwfMovie;
 use flv_rs::{
     AudioData as FlvAudioData, AudioDataType as FlvAudioDataType, Error as FlvError, FlvReader,
     FrameType as FlvFrameType, Header as FlvHeader, ScriptData as FlvScriptData,
     SoundFormat as FlvSoundFormat, SoundRate as FlvSoundRate, SoundSize as FlvSoundSize,
     SoundType as FlvSoundType, Tag as FlvTag, TagData as FlvTagData, Value as FlvValue,
-    VideoData as FlvVideoData, VideoPacket as FlvVideoPacket,
+    Variable as FlvVariable, VideoData as FlvVideoData, VideoPacket as FlvVideoPacket,
 };
 use gc_arena::barrier::unlock;
-use gc_arena::{Collect, DynamicRoot, Gc, Lock, Mutation, Rootable};
+use gc_arena::{Collect, DynamicRoot, Gc, GcWeak, Lock, Mutation, Rootable};
+use mp4::{Mp4, Mp4Codec, Mp4Error, Mp4Sample};
 use ruffle_common::buffer::{Buffer, Slice, Substream, SubstreamError};
 use ruffle_common::duration::FloatDuration;
 use ruffle_macros::istr;
@@ -35,6 +39,8 @@ use ruffle_video::frame::EncodedFrame;
 use std::cell::{Cell, RefCell};
 use std::cmp::max;
 use std::io::{Seek, SeekFrom};
+use std::rc::Rc;
+use std::sync::Arc;
 use swf::{AudioCompression, SoundFormat, VideoCodec, VideoDeblocking};
 use thiserror::Error;
 use url::Url;
@@ -79,6 +85,11 @@ pub struct StreamManager<'gc> {
     /// A stream becomes active if it is either playing streaming media or is
     /// doing other tick-time processing such as seeking.
     active_streams: Vec<NetStream<'gc>>,
+
+    /// Streams that were told to play a file, for as long as they're alive.
+    ///
+    /// Paused streams aren't active, but may still be downloading.
+    playing_streams: Vec<GcWeak<'gc, NetStreamData<'gc>>>,
 }
 
 impl Default for StreamManager<'_> {
@@ -91,6 +102,7 @@ impl<'gc> StreamManager<'gc> {
     pub fn new() -> Self {
         StreamManager {
             active_streams: Vec::new(),
+            playing_streams: Vec::new(),
         }
     }
 
@@ -120,6 +132,40 @@ impl<'gc> StreamManager<'gc> {
         }
     }
 
+    /// Remember a `NetStream` that started playing a file.
+    fn add_playing(context: &mut UpdateContext<'gc>, stream: NetStream<'gc>) {
+        let mc = context.gc();
+        let streams = &mut context.stream_manager.playing_streams;
+        // Take this opportunity to clean up dead streams.
+        streams.retain(|weak| GcWeak::upgrade(*weak, mc).is_some());
+        if !streams
+            .iter()
+            .any(|weak| GcWeak::as_ptr(*weak) == Gc::as_ptr(stream.0))
+        {
+            streams.push(Gc::downgrade(stream.0));
+        }
+    }
+
+    /// Close all streams that were created by the given movie, including
+    /// paused ones.
+    pub fn close_streams_of_movie(context: &mut UpdateContext<'gc>, movie: &Arc<SwfMovie>) {
+        let mc = context.gc();
+        let mut streams = context.stream_manager.active_streams.clone();
+        for weak in &context.stream_manager.playing_streams {
+            if let Some(stream) = GcWeak::upgrade(*weak, mc).map(NetStream)
+                && !streams.contains(&stream)
+            {
+                streams.push(stream);
+            }
+        }
+
+        for stream in streams {
+            if Arc::ptr_eq(&stream.0.movie, movie) {
+                stream.close(context);
+            }
+        }
+    }
+
     /// Process all active media streams.
     ///
     /// This is an unlocked timestep; the `dt` parameter indicates how many
@@ -198,15 +244,59 @@ pub enum NetStreamType {
         /// onto a table of data buffers like `Video` does, so we must maintain
         /// frame IDs ourselves for various API related purposes.
         frame_id: u32,
+
+        /// The keyframe index of the `onMetaData` tag, if the FLV has one.
+        ///
+        /// This is needed to seek outside of the downloaded data.
+        keyframes: Vec<FlvKeyframe>,
+    },
+
+    /// The stream is an ISO base media file (MP4, F4V, M4A...).
+    Mp4 {
+        movie: Rc<Mp4>,
+
+        /// The video track's stream instance.
+        video_stream: Option<VideoStreamHandle>,
+
+        /// The index of the next video sample to decode.
+        video_sample: usize,
+
+        /// The index of the next audio sample to stream.
+        audio_sample: usize,
     },
 }
 
+/// An entry of the keyframe index that many FLV encoders add to `onMetaData`.
+#[derive(Clone, Copy, Debug)]
+pub struct FlvKeyframe {
+    /// The timestamp of the keyframe in milliseconds.
+    time: f64,
+
+    /// The file position of the keyframe's tag.
+    position: usize,
+}
+
+/// The length of an FLV header, which pseudo-streaming servers put in front
+/// of the requested tags.
+pub const FLV_HEADER_LENGTH: usize = 9;
+
+/// How far ahead of the playhead MP4 audio gets streamed, in milliseconds.
+///
+/// This avoids audio underruns, like the audio tag lookahead of FLVs does.
+const MP4_AUDIO_LOOKAHEAD: f64 = 250.0;
+
 #[derive(Clone, Debug, Collect)]
 #[collect(no_drop)]
 pub struct NetStreamSource {
     /// All data currently loaded in the stream.
     buffer: RefCell<Buffer>,
 
+    /// The file position of the first byte of the buffer.
+    ///
+    /// This is only nonzero if the buffer was downloaded to serve a seek
+    /// outside of previously downloaded data.
+    base_offset: Cell<usize>,
+
     /// The buffer position that we are currently seeking to.
     offset: Cell<usize>,
 
@@ -228,6 +318,16 @@ pub struct NetStreamSource {
     /// separate preload step for that given format.
     preload_offset: Cell<usize>,
 
+    /// The buffer position of the first FLV tag not yet scanned for its
+    /// timestamp.
+    scan_offset: Cell<usize>,
+
+    /// The timestamp of the last downloaded FLV tag in milliseconds.
+    buffered_time: Cell<f64>,
+
+    /// True while playback waits for `bufferTime` worth of data to arrive.
+    buffering: Cell<bool>,
+
     /// The current stream type, if known.
     stream_type: RefCell<Option<NetStreamType>>,
 
@@ -253,8 +353,8 @@ pub struct NetStreamData<'gc> {
     /// Stream source.
     source: Lock<Gc<'gc, NetStreamSource>>,
 
-    /// The number of seconds of video data that should be buffered. This is
-    /// currently unsupported and changing it has no effect.
+    /// The number of seconds of video data that should be buffered before
+    /// playback starts.
     buffer_time: Cell<f64>,
 
     /// The last decoded bitmap.
@@ -272,20 +372,33 @@ pub struct NetStreamData<'gc> {
     /// The URL of the requested FLV if one exists.
     url: RefCell<Option<String>>,
 
+    /// Identifies the download feeding the current source.
+    ///
+    /// Downloads stop once the source they were started for gets replaced.
+    download_id: Cell<u32>,
+
     /// The MovieClip this `NetStream` is attached to.
     attached_to: Lock<Option<MovieClip<'gc>>>,
 
     /// True if the stream should play when ticked.
     playing: Cell<bool>,
+
+    /// The movie whose code created this stream.
+    #[collect(require_static)]
+    movie: Arc<SwfMovie>,
 }
 
 impl Default for NetStreamSource {
     fn default() -> Self {
         Self {
             buffer: RefCell::new(Buffer::new()),
+            base_offset: Cell::new(0),
             offset: Cell::new(0),
             expected_length: Cell::new(Some(0)),
             preload_offset: Cell::new(0),
+            scan_offset: Cell::new(0),
+            buffered_time: Cell::new(0.0),
+            buffering: Cell::new(true),
             stream_type: RefCell::new(None),
             stream_time: Cell::new(0.0),
             queued_seek_time: Cell::new(None),
@@ -297,17 +410,25 @@ impl Default for NetStreamSource {
 
 impl<'gc> NetStream<'gc> {
     /// Create a `NetStream` for use in AVM1.
-    pub fn new_avm1(gc_context: &Mutation<'gc>, avm_object: Avm1Object<'gc>) -> Self {
-        Self::new(gc_context, Some(NetStreamKind::Avm1(avm_object)))
+    pub fn new_avm1(
+        gc_context: &Mutation<'gc>,
+        avm_object: Avm1Object<'gc>,
+        movie: Arc<SwfMovie>,
+    ) -> Self {
+        Self::new(gc_context, Some(NetStreamKind::Avm1(avm_object)), movie)
     }
 
     /// Create a `NetStream` for use in AVM2. The caller is expected to initialize
     /// the AVM side of the `NetStream` later, by using `set_avm2_object`.
-    pub fn new_avm2(gc_context: &Mutation<'gc>) -> Self {
-        Self::new(gc_context, None)
+    pub fn new_avm2(gc_context: &Mutation<'gc>, movie: Arc<SwfMovie>) -> Self {
+        Self::new(gc_context, None, movie)
     }
 
-    fn new(gc_context: &Mutation<'gc>, avm_object: Option<NetStreamKind<'gc>>) -> Self {
+    fn new(
+        gc_context: &Mutation<'gc>,
+        avm_object: Option<NetStreamKind<'gc>>,
+        movie: Arc<SwfMovie>,
+    ) -> Self {
         // IMPORTANT: When adding new fields consider if they need to be
         //     added here or to NetStreamSource.
         Self(Gc::new(
@@ -319,8 +440,10 @@ impl<'gc> NetStream<'gc> {
                 avm_object: Lock::new(avm_object),
                 avm2_client: Lock::new(None),
                 url: RefCell::new(None),
+                download_id: Cell::new(0),
                 attached_to: Lock::new(None),
                 playing: Cell::new(false),
+                movie,
             },
         ))
     }
@@ -364,8 +487,35 @@ impl<'gc> NetStream<'gc> {
             context.audio_manager.stop_sound(context.audio, instance);
         }
 
+        self.replace_source(context, Default::default());
+    }
+
+    /// Replace the stream source, stopping the download of the old one.
+    fn replace_source(self, context: &mut UpdateContext<'gc>, source: NetStreamSource) {
+        self.0
+            .download_id
+            .set(self.0.download_id.get().wrapping_add(1));
         unlock!(Gc::write(context.gc(), self.0), NetStreamData, source)
-            .set(Gc::new(context.gc(), Default::default()));
+            .set(Gc::new(context.gc(), source));
+    }
+
+    /// Identifies the download feeding the current source.
+    ///
+    /// Loaders must stop feeding data into the stream once this changes.
+    pub fn download_id(self) -> u32 {
+        self.0.download_id.get()
+    }
+
+    /// Set where the data of a seek-driven download is located in the file.
+    ///
+    /// `base_offset` is the file position of the first downloaded byte, and
+    /// `read_offset` the buffer position to continue playback at.
+    pub fn set_download_origin(self, base_offset: usize, read_offset: usize) {
+        let source = self.source();
+        source.base_offset.set(base_offset);
+        source.offset.set(read_offset);
+        source.preload_offset.set(read_offset);
+        source.scan_offset.set(read_offset);
     }
 
     /// Set the total number of bytes expected to be downloaded.
@@ -393,20 +543,106 @@ impl<'gc> NetStream<'gc> {
     /// separate streams is not mixed together.
     pub fn load_buffer(self, context: &mut UpdateContext<'gc>, data: &mut Vec<u8>) {
         self.source().buffer.borrow_mut().append(data);
+        self.scan_flv_tags();
 
         StreamManager::activate(context, self);
 
         // NOTE: The onMetaData event triggers before this event in Flash due to its streaming behavior.
-        self.trigger_status_event(
-            context,
-            [("code", "NetStream.Buffer.Full"), ("level", "status")],
-        );
+        self.check_buffer_full(context);
     }
 
     /// Indicate that the buffer has finished loading and that no further data
     /// is expected to be downloaded to it.
-    pub fn finish_buffer(self) {
+    pub fn finish_buffer(self, context: &mut UpdateContext<'gc>) {
         self.source().expected_length.set(None);
+        self.check_buffer_full(context);
+    }
+
+    /// Find the timestamp of the last complete FLV tag in the buffer.
+    ///
+    /// Only newly downloaded tags are scanned, as this runs for every chunk.
+    fn scan_flv_tags(self) {
+        let source = self.source();
+        let slice = source.buffer.borrow().to_full_slice();
+        let buffer = slice.data();
+
+        let is_flv = match &*source.stream_type.borrow() {
+            Some(NetStreamType::Flv { .. }) => true,
+            Some(_) => false,
+            None => buffer.starts_with(b"FLV"),
+        };
+        if !is_flv {
+            return;
+        }
+
+        let mut reader = FlvReader::from_parts(&buffer, source.scan_offset.get());
+        // Responses to range requests have no header and start right at a tag,
+        // in which case the reader is left where it is.
+        if source.scan_offset.get() == 0
+            && let Err(FlvError::EndOfData) = FlvHeader::parse(&mut reader)
+        {
+            return;
+        }
+
+        while let Ok(tag) = FlvTag::parse(&mut reader) {
+            let time = tag.timestamp as f64;
+            source
+                .buffered_time
+                .set(source.buffered_time.get().max(time));
+        }
+
+        let offset = reader
+            .stream_position()
+            .expect("FLV reader stream position");
+        source.scan_offset.set(offset as usize);
+    }
+
+    /// The timestamp up to which media data was downloaded, in milliseconds.
+    fn buffered_time(self) -> f64 {
+        let source = self.source();
+        match &*source.stream_type.borrow() {
+            Some(NetStreamType::Mp4 { movie, .. }) => {
+                let end = source.base_offset.get() + source.buffer.borrow().len();
+                [&movie.video, &movie.audio]
+                    .into_iter()
+                    .flatten()
+                    .map(|track| {
+                        // Samples are stored in playback order by any sane muxer.
+                        let downloaded = track.samples.partition_point(|s| s.end() <= end);
+                        track
+                            .samples
+                            .get(downloaded)
+                            .map_or(track.duration, |sample| sample.time)
+                    })
+                    .reduce(f64::min)
+                    .unwrap_or(0.0)
+            }
+            _ => source.buffered_time.get(),
+        }
+    }
+
+    /// The number of seconds of media buffered ahead of the playhead.
+    pub fn buffer_length(self) -> f64 {
+        let buffered = self.buffered_time() - self.source().stream_time.get();
+        buffered.max(0.0) / 1000.0
+    }
+
+    /// Resume playback once enough data was buffered, or nothing more will be
+    /// downloaded.
+    fn check_buffer_full(self, context: &mut UpdateContext<'gc>) {
+        let source = self.source();
+        if !source.buffering.get() {
+            return;
+        }
+
+        let is_downloaded = source.expected_length.get().is_none();
+        if is_downloaded || self.buffer_length() >= self.buffer_time() {
+            source.buffering.set(false);
+            self.trigger_status_event(
+                context,
+                [("code", "NetStream.Buffer.Full"), ("level", "status")],
+            );
+        }
     }
 
     pub fn report_error(self, _error: Error) {
@@ -414,13 +650,21 @@ impl<'gc> NetStream<'gc> {
     }
 
     pub fn bytes_loaded(self) -> usize {
-        self.source().buffer.borrow().len()
+        let source = self.source();
+        source.base_offset.get() + source.buffer.borrow().len()
     }
 
     pub fn bytes_total(self) -> usize {
         let source = self.source();
         let buflen = source.buffer.borrow().len();
-        std::cmp::max(source.expected_length.get().unwrap_or(buflen), buflen)
+        source.base_offset.get() + max(source.expected_length.get().unwrap_or(buflen), buflen)
+    }
+
+    /// Whether the data at a file position is part of the buffer.
+    fn is_buffered(self, position: usize) -> bool {
+        let source = self.source();
+        let base_offset = source.base_offset.get();
+        position >= base_offset && position < base_offset + source.buffer.borrow().len()
     }
 
     pub fn time(self) -> f64 {
@@ -462,10 +706,9 @@ impl<'gc> NetStream<'gc> {
     /// stream is playing then new tag processing will occur when the stream
     /// ticks next.
     ///
-    /// This always does an in-buffer seek. Seek-driven requests are not
-    /// currently supported. When progressive download is implemented this seek
-    /// algorithm will need to detect out-of-buffer seeks and trigger fresh
-    /// downloads.
+    /// Seeks to a keyframe that hasn't been downloaded (or was skipped by an
+    /// earlier seek) replace the buffer with a fresh download starting at that
+    /// keyframe. FLVs can only do this if their metadata has a keyframe index.
     ///
     /// `offset` is in milliseconds.
     ///
@@ -497,7 +740,29 @@ impl<'gc> NetStream<'gc> {
             source.audio_stream.replace(None);
         }
 
-        if matches!(
+        // The file position and pseudo-streaming start to download from, if
+        // the seek target isn't in the buffer.
+        let mut download_from = None;
+
+        let flv_keyframe = match &*source.stream_type.borrow() {
+            Some(NetStreamType::Flv { keyframes, .. }) => keyframes
+                .iter()
+                .rev()
+                .find(|keyframe| keyframe.time <= offset)
+                .or(keyframes.first())
+                .copied(),
+            _ => None,
+        };
+
+        // Tags start with the size of the previous tag.
+        if let Some(keyframe) = flv_keyframe
+            && self.0.url.borrow().is_some()
+            && !self.is_buffered(keyframe.position.saturating_sub(4))
+        {
+            source.stream_time.set(keyframe.time);
+            let position = keyframe.position.saturating_sub(4);
+            download_from = Some((position, Some(keyframe.position)));
+        } else if matches!(
             &*source.stream_type.borrow(),
             Some(NetStreamType::Flv { .. })
         ) {
@@ -571,6 +836,48 @@ impl<'gc> NetStream<'gc> {
                 .stream_position()
                 .expect("FLV reader stream position") as usize;
             source.offset.set(offset);
+        } else if let Some(NetStreamType::Mp4 {
+            movie,
+            video_sample,
+            audio_sample,
+            ..
+        }) = &mut *source.stream_type.borrow_mut()
+        {
+            // MP4 seeks always go back to the closest prior keyframe.
+            let stream_time = match &movie.video {
+                Some(video) => {
+                    *video_sample = video.keyframe_before(offset);
+                    video
+                        .samples
+                        .get(*video_sample)
+                        .map_or(0.0, |sample| sample.time.max(0.0))
+                }
+                None => offset,
+            };
+            if let Some(audio) = &movie.audio {
+                *audio_sample = audio.sample_at(stream_time);
+            }
+            source.stream_time.set(stream_time);
+
+            let video = movie
+                .video
+                .as_ref()
+                .and_then(|v| v.samples.get(*video_sample));
+            let audio = movie
+                .audio
+                .as_ref()
+                .and_then(|a| a.samples.get(*audio_sample));
+            download_from = video
+                .into_iter()
+                .chain(audio)
+                .map(|sample| sample.offset)
+                .min()
+                .filter(|position| !self.is_buffered(*position))
+                .map(|position| (position, None));
+        }
+
+        if let Some((position, pseudo_streaming_start)) = download_from {
+            self.download_from(context, position, pseudo_streaming_start);
         }
 
         if let Some(NetStreamKind::Avm2(_)) = self.0.avm_object.get() {
@@ -585,12 +892,61 @@ impl<'gc> NetStream<'gc> {
         }
     }
 
+    /// Replace the buffer with a download of the stream file from `position`
+    /// onwards.
+    ///
+    /// The stream type and playback position carry over to the new buffer.
+    /// `pseudo_streaming_start` is the `start` query parameter to fall back
+    /// to, for FLV servers that don't support range requests.
+    fn download_from(
+        self,
+        context: &mut UpdateContext<'gc>,
+        position: usize,
+        pseudo_streaming_start: Option<usize>,
+    ) {
+        let Some(url) = self.0.url.borrow().clone() else {
+            return;
+        };
+
+        let old_source = self.source();
+        // Until a download reports its length, the length of the file isn't
+        // known.
+        let file_length = (old_source.expected_length.get() != Some(0)).then(|| self.bytes_total());
+        self.replace_source(
+            context,
+            NetStreamSource {
+                base_offset: Cell::new(position),
+                stream_type: RefCell::new(old_source.stream_type.take()),
+                stream_time: Cell::new(old_source.stream_time.get()),
+                ..Default::default()
+            },
+        );
+
+        let future = crate::loader::load_netstream_range(
+            context,
+            self,
+            url,
+            position,
+            pseudo_streaming_start,
+            file_length,
+        );
+        context.navigator.spawn_future(future);
+    }
+
     /// Start playing media from this NetStream.
     ///
     /// If `name` is specified, this will also trigger streaming download of
     /// the given resource. Otherwise, the stream will play whatever data is
     /// available in the buffer.
-    pub fn play(self, context: &mut UpdateContext<'gc>, name: Option<AvmString<'gc>>) {
+    ///
+    /// Fails with the URL of the resource if the security sandbox doesn't
+    /// allow the `requester` movie to load it.
+    pub fn play(
+        self,
+        context: &mut UpdateContext<'gc>,
+        name: Option<AvmString<'gc>>,
+        requester: &SwfMovie,
+    ) -> Result<(), String> {
         if let Some(name) = name {
             let request = if let Ok(stream_url) = Url::parse(context.root_swf.url())
                 .and_then(|url| url.join(name.to_string().as_str()))
@@ -599,9 +955,16 @@ impl<'gc> NetStream<'gc> {
             } else {
                 Request::get(name.to_string())
             };
+            if context.security.is_enabled()
+                && !context.security.can_load_content(requester, request.url())
+            {
+                return Err(request.url().to_string());
+            }
+
             self.0.url.replace(Some(request.url().to_string()));
             self.source().preload_offset.set(0);
             self.reset_buffer(context);
+            StreamManager::add_playing(context, self);
 
             let future = crate::loader::load_netstream(context, self, request);
 
@@ -615,6 +978,7 @@ impl<'gc> NetStream<'gc> {
             context,
             [("code", "NetStream.Play.Start"), ("level", "status")],
         );
+        Ok(())
     }
 
     /// Pause stream playback.
@@ -635,6 +999,14 @@ impl<'gc> NetStream<'gc> {
         }
     }
 
+    /// Stop playback and downloading, discarding all buffered data.
+    pub fn close(self, context: &mut UpdateContext<'gc>) {
+        self.0.playing.set(false);
+        self.0.url.replace(None);
+        self.reset_buffer(context);
+        StreamManager::deactivate(context, self);
+    }
+
     /// Resume stream playback.
     pub fn resume(self, context: &mut UpdateContext<'gc>) {
         self.0.playing.set(true);
@@ -726,6 +1098,8 @@ impl<'gc> NetStream<'gc> {
                         (FlvSoundFormat::G711ALawPCM, _)
                         | (FlvSoundFormat::G711MuLawPCM, _)
                         | (FlvSoundFormat::MP38kHz, _) => 8_000,
+                        // Speex is always wideband, whatever rate the tag claims.
+                        (FlvSoundFormat::Speex, _) => 16_000,
                         (_, FlvSoundRate::R5_500) => 5_500,
                         (_, FlvSoundRate::R11_000) => 11_000,
                         (_, FlvSoundRate::R22_000) => 22_000,
@@ -860,6 +1234,18 @@ impl<'gc> NetStream<'gc> {
             return false;
         }
 
+        if mp4::is_mp4(&buffer) {
+            // An expected length of 0 means that the server didn't say.
+            let file_length = match source.expected_length.get() {
+                None => Some(buffer.len()),
+                Some(0) => None,
+                Some(length) => Some(length),
+            };
+            let movie = Mp4::parse(&buffer, file_length);
+            drop(buffer);
+            return self.mp4_header(context, movie);
+        }
+
         match buffer.get(0..3) {
             Some([0x46, 0x4C, 0x56]) => {
                 let mut reader = FlvReader::from_parts(&buffer, source.offset.get());
@@ -871,6 +1257,7 @@ impl<'gc> NetStream<'gc> {
                             header,
                             video_stream: None,
                             frame_id: 0,
+                            keyframes: vec![],
                         }));
                         true
                     }
@@ -883,6 +1270,8 @@ impl<'gc> NetStream<'gc> {
                     }
                 }
             }
+            // Possibly the size of the first box of an MP4, wait for its type.
+            Some([0, 0, _]) if buffer.len() < 8 => false,
             Some(magic) => {
                 //Unrecognized signature
                 //TODO: Fire an error event to AS & stop playing too
@@ -1122,6 +1511,15 @@ impl<'gc> NetStream<'gc> {
         let mut duration = None;
 
         for var in script_data.0 {
+            if var.name == b"onMetaData"
+                && tag_needs_preloading
+                && let Some(index) = flv_keyframe_index(&var.data)
+                && let Some(NetStreamType::Flv { keyframes, .. }) =
+                    &mut *source.stream_type.borrow_mut()
+            {
+                *keyframes = index;
+            }
+
             if var.name == b"onMetaData" && !has_stream_already {
                 match var.data.clone() {
                     FlvValue::Object(subvars) | FlvValue::EcmaArray(subvars) => {
@@ -1203,6 +1601,13 @@ impl<'gc> NetStream<'gc> {
             return;
         }
 
+        // Hold playback until enough data was buffered. The `Buffer.Full`
+        // handler may also have replaced the source.
+        self.check_buffer_full(context);
+        if source.buffering.get() || !Gc::ptr_eq(source, self.source()) {
+            return;
+        }
+
         self.cleanup_sound_stream(context);
         let slice = source.buffer.borrow().to_full_slice();
         let buffer = slice.data();
@@ -1282,46 +1687,279 @@ impl<'gc> NetStream<'gc> {
                         .set(max(source.offset.get(), source.preload_offset.get()));
                 }
             }
+        } else if matches!(
+            &*source.stream_type.borrow(),
+            Some(NetStreamType::Mp4 { .. })
+        ) {
+            buffer_underrun = self.mp4_tick(context, &slice, &buffer, max_time);
+        }
+
+        let is_waiting_for_data = buffer_underrun && source.expected_length.get().is_some();
+        if !is_waiting_for_data {
+            source.stream_time.set(max_time);
         }
 
-        source.stream_time.set(max_time);
         if let Err(e) = self.commit_sound_stream(context) {
             //TODO: Fire an error event at AS.
             tracing::error!("Error committing sound stream: {}", e);
         }
 
-        if buffer_underrun {
-            let is_end_of_video = source.expected_length.get().is_none();
-
+        if is_waiting_for_data {
+            // Playback caught up with the download, so hold it until enough
+            // data arrives again.
+            source.buffering.set(true);
+            self.trigger_status_event(
+                context,
+                [("code", "NetStream.Buffer.Empty"), ("level", "status")],
+            );
+        } else if buffer_underrun {
             self.trigger_status_event(
                 context,
                 [("code", "NetStream.Buffer.Flush"), ("level", "status")],
             );
+            self.trigger_status_event(
+                context,
+                [("code", "NetStream.Play.Stop"), ("level", "status")],
+            );
 
-            if is_end_of_video {
+            // Check if AVM code in the event handler invoked stream.play() and replaced the source.
+            if Gc::ptr_eq(source, self.source()) {
                 self.trigger_status_event(
                     context,
-                    [("code", "NetStream.Play.Stop"), ("level", "status")],
+                    [("code", "NetStream.Buffer.Empty"), ("level", "status")],
                 );
+                self.pause(context, false);
             }
+        }
 
-            // Check if AVM code in the event handler invoked stream.play() and replaced the source.
-            if Gc::ptr_eq(source, self.source()) {
+        if error {
+            //TODO: Fire an error event at AS.
+            self.pause(context, false);
+        }
+    }
+
+    /// Set up playback of an MP4 once its `moov` box was parsed.
+    ///
+    /// Returns true if the stream is ready to be played, like
+    /// `sniff_stream_type`.
+    fn mp4_header(self, context: &mut UpdateContext<'gc>, movie: Result<Mp4, Mp4Error>) -> bool {
+        let source = self.source();
+        let movie = match movie {
+            Ok(movie) => movie,
+            // The `moov` box can be at the end of the file, in which case
+            // playback has to wait for the whole file.
+            Err(Mp4Error::EndOfData) if source.expected_length.get().is_some() => return false,
+            Err(e) => {
+                tracing::error!("MP4 parsing failed: {}", e);
+                self.close(context);
                 self.trigger_status_event(
                     context,
-                    [("code", "NetStream.Buffer.Empty"), ("level", "status")],
+                    [
+                        ("code", "NetStream.Play.FileStructureInvalid"),
+                        ("level", "error"),
+                    ],
                 );
+                return false;
+            }
+        };
 
-                if is_end_of_video {
-                    self.pause(context, false);
+        let mut video_stream = None;
+        if let Some(video) = &movie.video
+            && let Mp4Codec::H264 {
+                width,
+                height,
+                config,
+            } = &video.codec
+        {
+            match context.video.register_video_stream(
+                video.samples.len() as u32,
+                (*width, *height),
+                VideoCodec::H264,
+                VideoDeblocking::UseVideoPacketValue,
+            ) {
+                Ok(handle) => {
+                    if let Err(e) = context.video.configure_video_stream_decoder(handle, config) {
+                        tracing::error!("Configuring MP4 video decoder failed: {}", e);
+                    }
+                    video_stream = Some(handle);
                 }
+                Err(e) => tracing::error!("Got error when registering MP4 video stream: {}", e),
             }
         }
 
-        if error {
-            //TODO: Fire an error event at AS.
-            self.pause(context, false);
+        let movie = Rc::new(movie);
+        source.stream_type.replace(Some(NetStreamType::Mp4 {
+            movie: movie.clone(),
+            video_stream,
+            video_sample: 0,
+            audio_sample: 0,
+        }));
+
+        let avm_object = self.0.avm_object.get();
+        let _ = self.handle_script_data(avm_object, context, b"onMetaData", mp4_metadata(&movie));
+        // Any errors while trying to lookup or call AVM2 properties are silently swallowed.
+
+        true
+    }
+
+    /// Decode and stream the MP4 samples due by `max_time`.
+    ///
+    /// `slice` and `buffer` must reference the whole stream buffer. Returns
+    /// true if the stream ran out of samples, either because they haven't
+    /// been downloaded yet or because the stream ended.
+    fn mp4_tick(
+        self,
+        context: &mut UpdateContext<'gc>,
+        slice: &Slice,
+        buffer: &[u8],
+        max_time: f64,
+    ) -> bool {
+        let source = self.source();
+        let Some(NetStreamType::Mp4 {
+            movie,
+            video_stream,
+            mut video_sample,
+            mut audio_sample,
+        }) = source.stream_type.borrow().clone()
+        else {
+            unreachable!()
+        };
+
+        let mut buffer_underrun = false;
+        let mut is_finished = true;
+
+        // Samples before the start of the buffer were skipped by a seek.
+        let base_offset = source.base_offset.get();
+        let buffer_range = |sample: &Mp4Sample| {
+            let start = sample.offset.checked_sub(base_offset)?;
+            Some(start..start + sample.size)
+        };
+
+        if let Some(video) = &movie.video {
+            while let Some(sample) = video.samples.get(video_sample) {
+                if sample.time >= max_time {
+                    break;
+                }
+                let Some(range) = buffer_range(sample) else {
+                    video_sample += 1;
+                    continue;
+                };
+                let Some(data) = buffer.get(range) else {
+                    buffer_underrun = true;
+                    break;
+                };
+
+                if let Some(video_handle) = video_stream {
+                    let encoded_frame = EncodedFrame {
+                        codec: VideoCodec::H264,
+                        data,
+                        frame_id: video_sample as u32,
+                    };
+
+                    match context.video.decode_video_stream_frame(
+                        video_handle,
+                        encoded_frame,
+                        context.renderer,
+                    ) {
+                        Ok(bitmap_info) => {
+                            self.0.last_decoded_bitmap.replace(Some(bitmap_info));
+                            if let Some(mc) = self.0.attached_to.get() {
+                                mc.invalidate_cached_bitmap();
+                                *context.needs_render = true;
+                            }
+                        }
+                        Err(e) => {
+                            tracing::error!("Decoding video frame {} failed: {}", video_sample, e);
+                        }
+                    }
+                }
+
+                video_sample += 1;
+            }
+            is_finished &= video_sample >= video.samples.len();
+        }
+
+        if let Some(audio) = &movie.audio {
+            while let Some(sample) = audio.samples.get(audio_sample) {
+                if sample.time >= max_time + MP4_AUDIO_LOOKAHEAD {
+                    break;
+                }
+                let Some(range) = buffer_range(sample) else {
+                    audio_sample += 1;
+                    continue;
+                };
+                let Some(data) = slice.get(range) else {
+                    buffer_underrun = true;
+                    break;
+                };
+
+                if let Err(e) = self.mp4_audio_sample(&audio.codec, data) {
+                    //TODO: Fire an error event at AS.
+                    tracing::error!("Error committing sound stream: {}", e);
+                }
+
+                audio_sample += 1;
+            }
+            is_finished &= audio_sample >= audio.samples.len();
         }
+
+        if let Some(NetStreamType::Mp4 {
+            video_sample: next_video_sample,
+            audio_sample: next_audio_sample,
+            ..
+        }) = &mut *source.stream_type.borrow_mut()
+        {
+            *next_video_sample = video_sample;
+            *next_audio_sample = audio_sample;
+        }
+
+        buffer_underrun || is_finished
+    }
+
+    /// Append an MP4 audio sample to the audio stream.
+    fn mp4_audio_sample(self, codec: &Mp4Codec, data: Slice) -> Result<(), NetstreamError> {
+        let source = self.source();
+        let audio_stream = &mut *source.audio_stream.borrow_mut();
+        let (substream, _) = match audio_stream {
+            Some(audio_stream) => audio_stream,
+            audio_stream => {
+                // None
+                let (compression, sample_rate, channels, extra_data) = match codec {
+                    Mp4Codec::Aac {
+                        sample_rate,
+                        channels,
+                        config,
+                    } => (
+                        AudioCompression::Aac,
+                        *sample_rate,
+                        *channels,
+                        Some(config.as_slice().into()),
+                    ),
+                    Mp4Codec::Mp3 {
+                        sample_rate,
+                        channels,
+                    } => (AudioCompression::Mp3, *sample_rate, *channels, None),
+                    Mp4Codec::H264 { .. } => return Err(NetstreamError::UnknownCodec),
+                };
+
+                let sound_stream_head = SoundStreamInfo {
+                    wrapping: SoundStreamWrapping::Unwrapped,
+                    stream_format: SoundFormat {
+                        compression,
+                        sample_rate: sample_rate as u16,
+                        is_stereo: channels > 1,
+                        is_16_bit: true,
+                    },
+                    num_samples_per_block: 0,
+                    latency_seek: 0,
+                    extra_data,
+                };
+
+                audio_stream.insert((Substream::new(data.buffer().clone()), sound_stream_head))
+            }
+        };
+
+        Ok(substream.append(data)?)
     }
 
     pub fn last_decoded_bitmap(self) -> Option<BitmapInfo> {
@@ -1432,3 +2070,106 @@ impl<'gc> NetStream<'gc> {
         Ok(())
     }
 }
+
+/// Read the keyframe index of an FLV's `onMetaData` object.
+///
+/// This is the `keyframes` object written by tools such as `yamdi` and
+/// `flvtool2` for HTTP pseudo-streaming, holding arrays of keyframe
+/// `times` in seconds and their `filepositions`.
+fn flv_keyframe_index(metadata: &FlvValue<'_>) -> Option<Vec<FlvKeyframe>> {
+    let (FlvValue::Object(vars) | FlvValue::EcmaArray(vars)) = metadata else {
+        return None;
+    };
+    let (FlvValue::Object(index) | FlvValue::EcmaArray(index)) =
+        &vars.iter().find(|var| var.name == b"keyframes")?.data
+    else {
+        return None;
+    };
+
+    let array = |name: &[u8]| match index.iter().find(|var| var.name == name) {
+        Some(FlvVariable {
+            data: FlvValue::StrictArray(values),
+            ..
+        }) => Some(values),
+        _ => None,
+    };
+    let keyframes = array(b"times")?
+        .iter()
+        .zip(array(b"filepositions")?)
+        .filter_map(|values| match values {
+            (FlvValue::Number(time), FlvValue::Number(position)) if *position >= 0.0 => {
+                Some(FlvKeyframe {
+                    time: time * 1000.0,
+                    position: *position as usize,
+                })
+            }
+            _ => None,
+        })
+        .collect();
+    Some(keyframes)
+}
+
+/// Build the `onMetaData` object of an MP4, like Flash Player does.
+fn mp4_metadata(movie: &Mp4) -> FlvValue<'static> {
+    let mut metadata = vec![
+        FlvVariable {
+            name: b"duration",
+            data: FlvValue::Number(movie.duration / 1000.0),
+        },
+        FlvVariable {
+            name: b"moovposition",
+            data: FlvValue::Number(movie.moov_position as f64),
+        },
+    ];
+
+    if let Some(video) = &movie.video {
+        if let Mp4Codec::H264 { width, height, .. } = video.codec {
+            metadata.push(FlvVariable {
+                name: b"width",
+                data: FlvValue::Number(width.into()),
+            });
+            metadata.push(FlvVariable {
+                name: b"height",
+                data: FlvValue::Number(height.into()),
+            });
+        }
+        metadata.push(FlvVariable {
+            name: b"videocodecid",
+            data: FlvValue::String(video.codec.name().as_bytes()),
+        });
+        if video.duration > 0.0 {
+            metadata.push(FlvVariable {
+                name: b"videoframerate",
+                data: FlvValue::Number(video.samples.len() as f64 * 1000.0 / video.duration),
+            });
+        }
+    }
+
+    if let Some(audio) = &movie.audio {
+        metadata.push(FlvVariable {
+            name: b"audiocodecid",
+            data: FlvValue::String(audio.codec.name().as_bytes()),
+        });
+        if let Mp4Codec::Aac {
+            sample_rate,
+            channels,
+            ..
+        }
+        | Mp4Codec::Mp3 {
+            sample_rate,
+            channels,
+        } = audio.codec
+        {
+            metadata.push(FlvVariable {
+                name: b"audiosamplerate",
+                data: FlvValue::Number(sample_rate.into()),
+            });
+            metadata.push(FlvVariable {
+                name: b"audiochannels",
+                data: FlvValue::Number(channels.into()),
+            });
+        }
+    }
+
+    FlvValue::EcmaArray(metadata)
+}
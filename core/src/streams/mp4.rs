@@ -0,0 +1,1130 @@
+//! ISO base media file format (MP4/F4V/M4A) demuxer.
+//!
+//! This only reads the sample tables out of the `moov` box; sample data is
+//! read by `NetStream` straight out of its buffer as it gets downloaded.
+
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum Mp4Error {
+    /// The `moov` box hasn't been fully downloaded yet.
+    #[error("Not enough data")]
+    EndOfData,
+
+    #[error("Invalid {0} box")]
+    InvalidBox(&'static str),
+
+    #[error("No playable tracks")]
+    NoTracks,
+}
+
+type FourCc = [u8; 4];
+
+/// Returns true if `data` looks like the start of an ISO base media file.
+pub fn is_mp4(data: &[u8]) -> bool {
+    matches!(
+        data.get(4..8),
+        Some(b"ftyp" | b"moov" | b"mdat" | b"free" | b"skip" | b"wide" | b"pdin")
+    )
+}
+
+/// A single sample (video frame or audio packet) of a track.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Mp4Sample {
+    /// The offset of the sample data, from the start of the file.
+    pub offset: usize,
+
+    pub size: usize,
+
+    /// The decoding time of this sample in milliseconds, with the track's
+    /// edit list applied.
+    pub time: f64,
+
+    /// The difference between the presentation and decoding time of this
+    /// sample in milliseconds.
+    pub composition_offset: f64,
+
+    pub is_keyframe: bool,
+}
+
+impl Mp4Sample {
+    /// The offset of the end of the sample data.
+    ///
+    /// Parsing rejects samples for which this would overflow.
+    pub fn end(&self) -> usize {
+        self.offset + self.size
+    }
+
+    /// The presentation time of this sample in milliseconds.
+    pub fn presentation_time(&self) -> f64 {
+        self.time + self.composition_offset
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Mp4Codec {
+    H264 {
+        width: u16,
+        height: u16,
+
+        /// The `AVCDecoderConfigurationRecord` from the `avcC` box.
+        config: Vec<u8>,
+    },
+    Aac {
+        sample_rate: u32,
+        channels: u16,
+
+        /// The `AudioSpecificConfig` from the `esds` box.
+        config: Vec<u8>,
+    },
+    Mp3 {
+        sample_rate: u32,
+        channels: u16,
+    },
+}
+
+impl Mp4Codec {
+    /// The codec name used in `onMetaData`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Mp4Codec::H264 { .. } => "avc1",
+            Mp4Codec::Aac { .. } => "mp4a",
+            Mp4Codec::Mp3 { .. } => ".mp3",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Mp4Track {
+    pub codec: Mp4Codec,
+
+    /// Duration of the track in milliseconds.
+    pub duration: f64,
+
+    /// All samples of the track in decoding order, as laid out by its edit
+    /// list.
+    pub samples: Vec<Mp4Sample>,
+}
+
+impl Mp4Track {
+    /// The index of the keyframe to start playing from to seek to `time`,
+    /// which is the last keyframe presented at or before that time.
+    pub fn keyframe_before(&self, time: f64) -> usize {
+        self.samples
+            .iter()
+            .enumerate()
+            .filter(|(_, sample)| sample.is_keyframe && sample.presentation_time() <= time)
+            .map(|(i, _)| i)
+            .last()
+            .unwrap_or(0)
+    }
+
+    /// The index of the first sample at or after `time`.
+    pub fn sample_at(&self, time: f64) -> usize {
+        self.samples.partition_point(|sample| sample.time < time)
+    }
+}
+
+/// The playable contents of an MP4 file.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Mp4 {
+    /// Duration of the movie in milliseconds.
+    pub duration: f64,
+
+    /// The offset of the `moov` box, from the start of the file.
+    pub moov_position: usize,
+
+    pub video: Option<Mp4Track>,
+    pub audio: Option<Mp4Track>,
+}
+
+impl Mp4 {
+    /// Finds and parses the `moov` box of a (possibly partially downloaded)
+    /// file.
+    ///
+    /// `mdat` boxes before the `moov` don't need to be downloaded yet, but
+    /// the `moov` itself does. Samples must fit within `file_length`, if the
+    /// length of the whole file is known.
+    pub fn parse(data: &[u8], file_length: Option<usize>) -> Result<Self, Mp4Error> {
+        let mut position = 0;
+        loop {
+            let mut reader = Reader::new(data.get(position..).unwrap_or_default());
+            let (kind, header_len, size) = reader.box_header()?;
+            if &kind == b"moov" {
+                let size = size.unwrap_or(data.len() - position);
+                let start = position
+                    .checked_add(header_len)
+                    .ok_or(Mp4Error::InvalidBox("moov"))?;
+                let end = position
+                    .checked_add(size)
+                    .ok_or(Mp4Error::InvalidBox("moov"))?;
+                let body = data.get(start..end).ok_or(Mp4Error::EndOfData)?;
+                return Self::parse_moov(body, position, file_length);
+            }
+
+            // A box extending to the end of the file, without a `moov` before it.
+            let size = size.ok_or(Mp4Error::EndOfData)?;
+            position = position.checked_add(size).ok_or(Mp4Error::EndOfData)?;
+        }
+    }
+
+    fn parse_moov(
+        data: &[u8],
+        moov_position: usize,
+        file_length: Option<usize>,
+    ) -> Result<Self, Mp4Error> {
+        let mut timescale = 1000;
+        let mut duration = 0.0;
+        let mut video = None;
+        let mut audio = None;
+
+        let mut reader = Reader::new(data);
+        while let Some((kind, mut body)) = reader.child_box()? {
+            match &kind {
+                b"mvhd" => {
+                    let (movie_timescale, movie_duration) = body.media_header("mvhd")?;
+                    timescale = movie_timescale;
+                    duration = movie_duration as f64 * 1000.0 / timescale as f64;
+                }
+                b"trak" => match Track::parse(body, timescale, file_length) {
+                    Ok(Some(track)) => {
+                        let slot = match track.codec {
+                            Mp4Codec::H264 { .. } => &mut video,
+                            Mp4Codec::Aac { .. } | Mp4Codec::Mp3 { .. } => &mut audio,
+                        };
+                        if slot.is_none() {
+                            *slot = Some(track);
+                        }
+                    }
+                    Ok(None) => {}
+                    Err(e) => tracing::warn!("Skipping invalid MP4 track: {e}"),
+                },
+                _ => {}
+            }
+        }
+
+        if video.is_none() && audio.is_none() {
+            return Err(Mp4Error::NoTracks);
+        }
+
+        if duration == 0.0 {
+            duration = video
+                .iter()
+                .chain(audio.iter())
+                .map(|track: &Mp4Track| track.duration)
+                .fold(0.0, f64::max);
+        }
+
+        Ok(Self {
+            duration,
+            moov_position,
+            video,
+            audio,
+        })
+    }
+}
+
+/// Helpers for building an `Mp4Track` out of a `trak` box.
+struct Track;
+
+impl Track {
+    /// Parses a `trak` box, returning `None` if it doesn't use a supported codec.
+    fn parse(
+        data: Reader<'_>,
+        movie_timescale: u32,
+        file_length: Option<usize>,
+    ) -> Result<Option<Mp4Track>, Mp4Error> {
+        let mut display_size = (0, 0);
+        let mut edits = Vec::new();
+        let mut media = None;
+
+        let mut reader = data;
+        while let Some((kind, body)) = reader.child_box()? {
+            match &kind {
+                b"tkhd" => display_size = Self::parse_tkhd(body)?,
+                b"edts" => {
+                    let mut edts = body;
+                    while let Some((kind, body)) = edts.child_box()? {
+                        if &kind == b"elst" {
+                            edits = Self::parse_elst(body)?;
+                        }
+                    }
+                }
+                b"mdia" => media = Some(body),
+                _ => {}
+            }
+        }
+
+        let Some(mut mdia) = media else {
+            return Err(Mp4Error::InvalidBox("trak"));
+        };
+
+        let mut timescale = 0;
+        let mut duration = 0;
+        let mut stbl = None;
+        while let Some((kind, mut body)) = mdia.child_box()? {
+            match &kind {
+                b"mdhd" => (timescale, duration) = body.media_header("mdhd")?,
+                b"minf" => {
+                    while let Some((kind, body)) = body.child_box()? {
+                        if &kind == b"stbl" {
+                            stbl = Some(body);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let Some(stbl) = stbl else {
+            return Err(Mp4Error::InvalidBox("mdia"));
+        };
+        if timescale == 0 {
+            return Err(Mp4Error::InvalidBox("mdhd"));
+        }
+
+        let Some((mut codec, samples)) = SampleTable::parse(stbl, timescale, file_length)? else {
+            return Ok(None);
+        };
+
+        if let Mp4Codec::H264 { width, height, .. } = &mut codec
+            && display_size.0 > 0
+            && display_size.1 > 0
+        {
+            (*width, *height) = display_size;
+        }
+
+        let samples = Self::apply_edits(samples, &edits, duration, timescale, movie_timescale);
+
+        Ok(Some(Mp4Track {
+            codec,
+            duration: duration as f64 * 1000.0 / timescale as f64,
+            samples,
+        }))
+    }
+
+    /// Maps the samples of a track onto the movie timeline according to its
+    /// edit list.
+    ///
+    /// Empty edits delay the rest of the track. Every other edit plays the
+    /// samples presented during its range of media time, starting with the
+    /// keyframe they depend on, so samples can be repeated or dropped.
+    /// Media rates other than 1 aren't supported.
+    fn apply_edits(
+        samples: Vec<Mp4Sample>,
+        edits: &[(u64, i64)],
+        media_duration: u64,
+        timescale: u32,
+        movie_timescale: u32,
+    ) -> Vec<Mp4Sample> {
+        // Without an edit list, the media plays as is.
+        let edits = if edits.is_empty() {
+            &[(0, 0)][..]
+        } else {
+            edits
+        };
+        let media_duration = media_duration as f64 * 1000.0 / timescale as f64;
+
+        let mut result = Vec::with_capacity(samples.len());
+        let mut edit_start = 0.0;
+        let mut last_time = f64::NEG_INFINITY;
+        for &(segment_duration, media_time) in edits {
+            // A zero duration lasts until the end of the media.
+            let segment_duration = match segment_duration {
+                0 => f64::INFINITY,
+                duration => duration as f64 * 1000.0 / movie_timescale as f64,
+            };
+
+            if media_time >= 0 {
+                let media_start = media_time as f64 * 1000.0 / timescale as f64;
+                let media_end = media_start + segment_duration;
+
+                // Samples still being presented when the edit starts.
+                let first = samples.iter().enumerate().position(|(i, sample)| {
+                    let next = samples.get(i + 1).map_or(media_duration, |next| next.time);
+                    sample.presentation_time() + next - sample.time > media_start
+                });
+                let last = samples
+                    .iter()
+                    .rposition(|sample| sample.presentation_time() < media_end);
+
+                if let (Some(first), Some(last)) = (first, last) {
+                    let first = samples[..=first]
+                        .iter()
+                        .rposition(|sample| sample.is_keyframe)
+                        .unwrap_or(0);
+                    for sample in samples.iter().take(last + 1).skip(first) {
+                        // Decoding times have to stay in order, and no
+                        // sample may be decoded before its edit starts.
+                        let time = (sample.time - media_start + edit_start)
+                            .max(edit_start)
+                            .max(last_time);
+                        let presentation_time =
+                            sample.presentation_time() - media_start + edit_start;
+                        result.push(Mp4Sample {
+                            time,
+                            composition_offset: presentation_time - time,
+                            ..sample.clone()
+                        });
+                        last_time = time;
+                    }
+                }
+            }
+
+            edit_start += segment_duration;
+        }
+        result
+    }
+
+    fn parse_tkhd(mut body: Reader<'_>) -> Result<(u16, u16), Mp4Error> {
+        // The display size are the last two fields, in 16.16 fixed point.
+        let len = body.remaining();
+        body.skip(len.checked_sub(8).ok_or(Mp4Error::InvalidBox("tkhd"))?)?;
+        let width = body.u32()? >> 16;
+        let height = body.u32()? >> 16;
+        Ok((width as u16, height as u16))
+    }
+
+    /// Returns the `(segment_duration, media_time)` of each edit.
+    fn parse_elst(mut body: Reader<'_>) -> Result<Vec<(u64, i64)>, Mp4Error> {
+        let (version, _flags) = body.full_box_header()?;
+        let count = body.u32()?;
+        let mut edits = Vec::new();
+        for _ in 0..count {
+            let edit = if version == 1 {
+                (body.u64()?, body.u64()? as i64)
+            } else {
+                (u64::from(body.u32()?), i64::from(body.u32()? as i32))
+            };
+            // media_rate_integer and media_rate_fraction
+            body.skip(4)?;
+            edits.push(edit);
+        }
+        Ok(edits)
+    }
+}
+
+/// Helpers for reading the `stbl` box of a track.
+struct SampleTable;
+
+impl SampleTable {
+    /// Builds the list of samples of a track.
+    ///
+    /// Their number is limited by the durations in `stts`, and each of them
+    /// has to end within `file_length` if it's known, so that a small file
+    /// can't declare billions of samples.
+    fn parse(
+        mut stbl: Reader<'_>,
+        timescale: u32,
+        file_length: Option<usize>,
+    ) -> Result<Option<(Mp4Codec, Vec<Mp4Sample>)>, Mp4Error> {
+        let mut codec = None;
+        let mut time_to_sample = Vec::new();
+        let mut sample_to_chunk = Vec::new();
+        let mut fixed_size = 0;
+        let mut sample_count = 0;
+        let mut sizes = Vec::new();
+        let mut chunk_offsets = Vec::new();
+        let mut sync_samples = None;
+        let mut composition_offsets = Vec::new();
+
+        while let Some((kind, mut body)) = stbl.child_box()? {
+            match &kind {
+                b"stsd" => {
+                    body.full_box_header()?;
+                    let _entry_count = body.u32()?;
+                    if let Some((kind, entry)) = body.child_box()? {
+                        codec = SampleEntry::parse(kind, entry)?;
+                    }
+                }
+                b"stts" => {
+                    body.full_box_header()?;
+                    for _ in 0..body.u32()? {
+                        time_to_sample.push((body.u32()?, body.u32()?));
+                    }
+                }
+                b"ctts" => {
+                    // Version 0 offsets are unsigned, but are commonly written as
+                    // signed anyway.
+                    body.full_box_header()?;
+                    for _ in 0..body.u32()? {
+                        composition_offsets.push((body.u32()?, body.u32()? as i32));
+                    }
+                }
+                b"stsc" => {
+                    body.full_box_header()?;
+                    for _ in 0..body.u32()? {
+                        let first_chunk = body.u32()?;
+                        let samples_per_chunk = body.u32()?;
+                        let _sample_description_index = body.u32()?;
+                        sample_to_chunk.push((first_chunk, samples_per_chunk));
+                    }
+                }
+                b"stsz" => {
+                    body.full_box_header()?;
+                    fixed_size = body.u32()? as usize;
+                    sample_count = body.u32()? as usize;
+                    if fixed_size == 0 {
+                        for _ in 0..sample_count {
+                            sizes.push(body.u32()? as usize);
+                        }
+                    }
+                }
+                b"stco" => {
+                    body.full_box_header()?;
+                    for _ in 0..body.u32()? {
+                        chunk_offsets.push(body.u32()? as usize);
+                    }
+                }
+                b"co64" => {
+                    body.full_box_header()?;
+                    for _ in 0..body.u32()? {
+                        chunk_offsets.push(body.u64()? as usize);
+                    }
+                }
+                b"stss" => {
+                    body.full_box_header()?;
+                    let mut samples = Vec::new();
+                    for _ in 0..body.u32()? {
+                        samples.push(body.u32()?);
+                    }
+                    sync_samples = Some(samples);
+                }
+                _ => {}
+            }
+        }
+
+        let Some(codec) = codec else {
+            return Ok(None);
+        };
+
+        let timed_samples: u64 = time_to_sample
+            .iter()
+            .map(|(count, _)| u64::from(*count))
+            .sum();
+        let sample_count = sample_count.min(timed_samples.try_into().unwrap_or(usize::MAX));
+
+        let mut samples = Vec::with_capacity(sizes.len());
+        let mut times = time_to_sample
+            .into_iter()
+            .flat_map(|(count, delta)| (0..count).map(move |_| delta));
+        let mut offsets = composition_offsets
+            .into_iter()
+            .flat_map(|(count, offset)| (0..count).map(move |_| offset));
+        let mut decode_time = 0u64;
+
+        for (chunk_index, &chunk_offset) in chunk_offsets.iter().enumerate() {
+            // `stsc` entries apply to all chunks until the next entry; chunks are 1-based.
+            let chunk_number = chunk_index as u32 + 1;
+            let samples_per_chunk = sample_to_chunk
+                .iter()
+                .take_while(|(first_chunk, _)| *first_chunk <= chunk_number)
+                .last()
+                .map_or(0, |(_, samples_per_chunk)| *samples_per_chunk);
+
+            let mut offset = chunk_offset;
+            for _ in 0..samples_per_chunk {
+                if samples.len() >= sample_count {
+                    break;
+                }
+                let size = match fixed_size {
+                    0 => sizes[samples.len()],
+                    size => size,
+                };
+                let end = offset
+                    .checked_add(size)
+                    .filter(|end| file_length.is_none_or(|length| *end <= length))
+                    .ok_or(Mp4Error::InvalidBox("stsz"))?;
+                let number = samples.len() as u32 + 1;
+                samples.push(Mp4Sample {
+                    offset,
+                    size,
+                    time: decode_time as f64 * 1000.0 / timescale as f64,
+                    composition_offset: f64::from(offsets.next().unwrap_or(0)) * 1000.0
+                        / timescale as f64,
+                    is_keyframe: sync_samples
+                        .as_ref()
+                        .is_none_or(|sync| sync.binary_search(&number).is_ok()),
+                });
+                offset = end;
+                decode_time += u64::from(times.next().unwrap_or(0));
+            }
+        }
+
+        Ok(Some((codec, samples)))
+    }
+}
+
+/// Helpers for reading the sample description of a track.
+struct SampleEntry;
+
+impl SampleEntry {
+    fn parse(kind: FourCc, mut entry: Reader<'_>) -> Result<Option<Mp4Codec>, Mp4Error> {
+        // reserved and data_reference_index
+        entry.skip(8)?;
+
+        match &kind {
+            b"avc1" | b"avc3" => {
+                // pre_defined and reserved
+                entry.skip(16)?;
+                let width = entry.u16()?;
+                let height = entry.u16()?;
+                // resolution, reserved, frame_count, compressorname, depth and pre_defined
+                entry.skip(50)?;
+
+                while let Some((kind, body)) = entry.child_box()? {
+                    if &kind == b"avcC" {
+                        return Ok(Some(Mp4Codec::H264 {
+                            width,
+                            height,
+                            config: body.data.to_vec(),
+                        }));
+                    }
+                }
+                Err(Mp4Error::InvalidBox("avc1"))
+            }
+            b"mp4a" | b".mp3" => {
+                let version = entry.u16()?;
+                // revision and vendor
+                entry.skip(6)?;
+                let channels = entry.u16()?;
+                // sample size, pre_defined and reserved
+                entry.skip(6)?;
+                let sample_rate = entry.u32()? >> 16;
+                // QuickTime sound description extensions
+                match version {
+                    1 => entry.skip(16)?,
+                    2 => entry.skip(36)?,
+                    _ => {}
+                }
+
+                if &kind == b".mp3" {
+                    return Ok(Some(Mp4Codec::Mp3 {
+                        sample_rate,
+                        channels,
+                    }));
+                }
+
+                let Some(esds) = Self::find_esds(entry)? else {
+                    return Err(Mp4Error::InvalidBox("mp4a"));
+                };
+                Ok(Self::parse_esds(esds)?.map(|(object_type, config)| {
+                    if matches!(object_type, 0x69 | 0x6B) {
+                        Mp4Codec::Mp3 {
+                            sample_rate,
+                            channels,
+                        }
+                    } else {
+                        Mp4Codec::Aac {
+                            sample_rate,
+                            channels,
+                            config,
+                        }
+                    }
+                }))
+            }
+            _ => {
+                tracing::warn!(
+                    "Unsupported MP4 codec {}",
+                    String::from_utf8_lossy(&kind).trim()
+                );
+                Ok(None)
+            }
+        }
+    }
+
+    /// Finds the `esds` box, which QuickTime files nest inside of a `wave` box.
+    fn find_esds(mut entry: Reader<'_>) -> Result<Option<Reader<'_>>, Mp4Error> {
+        while let Some((kind, body)) = entry.child_box()? {
+            match &kind {
+                b"esds" => return Ok(Some(body)),
+                b"wave" => {
+                    if let Some(esds) = Self::find_esds(body)? {
+                        return Ok(Some(esds));
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(None)
+    }
+
+    /// Returns the object type indication and decoder specific info of an
+    /// `esds` box, or `None` if it isn't an audio format we support.
+    fn parse_esds(mut esds: Reader<'_>) -> Result<Option<(u8, Vec<u8>)>, Mp4Error> {
+        esds.full_box_header()?;
+
+        // ES_Descriptor
+        if esds.descriptor_header()? != 0x03 {
+            return Err(Mp4Error::InvalidBox("esds"));
+        }
+        let _es_id = esds.u16()?;
+        let flags = esds.u8()?;
+        if flags & 0x80 != 0 {
+            // dependsOn_ES_ID
+            esds.skip(2)?;
+        }
+        if flags & 0x40 != 0 {
+            let url_length = esds.u8()?;
+            esds.skip(url_length.into())?;
+        }
+        if flags & 0x20 != 0 {
+            // OCR_ES_Id
+            esds.skip(2)?;
+        }
+
+        // DecoderConfigDescriptor
+        if esds.descriptor_header()? != 0x04 {
+            return Err(Mp4Error::InvalidBox("esds"));
+        }
+        let object_type = esds.u8()?;
+        // streamType, bufferSizeDB, maxBitrate and avgBitrate
+        esds.skip(12)?;
+        if !matches!(object_type, 0x40 | 0x66..=0x69 | 0x6B) {
+            tracing::warn!("Unsupported MP4 audio object type {object_type:#x}");
+            return Ok(None);
+        }
+
+        // DecoderSpecificInfo, which is optional for MP3.
+        let mut config = Vec::new();
+        if esds.remaining() > 0 && esds.descriptor_header()? == 0x05 {
+            config = esds.data.to_vec();
+        }
+        Ok(Some((object_type, config)))
+    }
+}
+
+/// A cursor over the big-endian contents of a box.
+#[derive(Clone, Copy)]
+struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    fn remaining(&self) -> usize {
+        self.data.len()
+    }
+
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8], Mp4Error> {
+        if len > self.data.len() {
+            return Err(Mp4Error::EndOfData);
+        }
+        let (bytes, rest) = self.data.split_at(len);
+        self.data = rest;
+        Ok(bytes)
+    }
+
+    fn skip(&mut self, len: usize) -> Result<(), Mp4Error> {
+        self.bytes(len).map(|_| ())
+    }
+
+    fn u8(&mut self) -> Result<u8, Mp4Error> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, Mp4Error> {
+        Ok(u16::from_be_bytes(
+            self.bytes(2)?.try_into().unwrap_or_default(),
+        ))
+    }
+
+    fn u32(&mut self) -> Result<u32, Mp4Error> {
+        Ok(u32::from_be_bytes(
+            self.bytes(4)?.try_into().unwrap_or_default(),
+        ))
+    }
+
+    fn u64(&mut self) -> Result<u64, Mp4Error> {
+        Ok(u64::from_be_bytes(
+            self.bytes(8)?.try_into().unwrap_or_default(),
+        ))
+    }
+
+    /// Reads a box header, returning its type, the length of the header,
+    /// and the total size of the box (`None` if it extends to the end of the file).
+    fn box_header(&mut self) -> Result<(FourCc, usize, Option<usize>), Mp4Error> {
+        let size = self.u32()?;
+        let kind = self.bytes(4)?.try_into().unwrap_or_default();
+        match size {
+            0 => Ok((kind, 8, None)),
+            1 => {
+                let size = self.u64()? as usize;
+                if size < 16 {
+                    return Err(Mp4Error::InvalidBox("large"));
+                }
+                Ok((kind, 16, Some(size)))
+            }
+            size if size < 8 => Err(Mp4Error::InvalidBox("small")),
+            size => Ok((kind, 8, Some(size as usize))),
+        }
+    }
+
+    /// Reads the next box nested in this one.
+    fn child_box(&mut self) -> Result<Option<(FourCc, Reader<'a>)>, Mp4Error> {
+        if self.data.len() < 8 {
+            return Ok(None);
+        }
+        let (kind, header_len, size) = self.box_header()?;
+        let body_len = size.map_or(self.data.len(), |size| size - header_len);
+        let body = self
+            .bytes(body_len)
+            .map_err(|_| Mp4Error::InvalidBox("truncated"))?;
+        Ok(Some((kind, Reader::new(body))))
+    }
+
+    /// Reads the version and flags of a full box.
+    fn full_box_header(&mut self) -> Result<(u8, u32), Mp4Error> {
+        let version_and_flags = self.u32()?;
+        Ok((
+            (version_and_flags >> 24) as u8,
+            version_and_flags & 0xFFFFFF,
+        ))
+    }
+
+    /// Reads the timescale and duration of an `mvhd` or `mdhd` box.
+    fn media_header(&mut self, name: &'static str) -> Result<(u32, u64), Mp4Error> {
+        let (version, _flags) = self.full_box_header()?;
+        let result = if version == 1 {
+            // creation_time and modification_time
+            self.skip(16)?;
+            (self.u32()?, self.u64()?)
+        } else {
+            self.skip(8)?;
+            (self.u32()?, u64::from(self.u32()?))
+        };
+        if result.0 == 0 {
+            return Err(Mp4Error::InvalidBox(name));
+        }
+        Ok(result)
+    }
+
+    /// Reads the tag of an MPEG-4 descriptor, limiting this reader to its
+    /// contents.
+    fn descriptor_header(&mut self) -> Result<u8, Mp4Error> {
+        let tag = self.u8()?;
+        let mut len = 0usize;
+        for _ in 0..4 {
+            let byte = self.u8()?;
+            len = (len << 7) | usize::from(byte & 0x7F);
+            if byte & 0x80 == 0 {
+                break;
+            }
+        }
+        let len = len.min(self.data.len());
+        self.data = &self.data[..len];
+        Ok(tag)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mp4_box(kind: &[u8; 4], contents: &[&[u8]]) -> Vec<u8> {
+        let body = contents.concat();
+        let mut data = ((body.len() + 8) as u32).to_be_bytes().to_vec();
+        data.extend_from_slice(kind);
+        data.extend(body);
+        data
+    }
+
+    fn full_box(kind: &[u8; 4], version: u8, contents: &[&[u8]]) -> Vec<u8> {
+        let mut data = vec![version, 0, 0, 0];
+        data.extend(contents.concat());
+        mp4_box(kind, &[&data])
+    }
+
+    fn u32s(values: &[u32]) -> Vec<u8> {
+        values.iter().flat_map(|v| v.to_be_bytes()).collect()
+    }
+
+    fn media_header(kind: &[u8; 4], timescale: u32, duration: u32) -> Vec<u8> {
+        full_box(kind, 0, &[&u32s(&[0, 0, timescale, duration])])
+    }
+
+    fn avc1_entry() -> Vec<u8> {
+        let mut avc1 = vec![0; 24];
+        avc1.extend([0x01, 0x40, 0x00, 0xF0]);
+        avc1.extend([0; 50]);
+        avc1.extend(mp4_box(b"avcC", &[&[1, 0x42, 0, 0x1E]]));
+        full_box(b"stsd", 0, &[&u32s(&[1]), &mp4_box(b"avc1", &[&avc1])])
+    }
+
+    fn video_trak() -> Vec<u8> {
+        let mut tkhd = vec![0; 76];
+        tkhd.extend(u32s(&[320 << 16, 240 << 16]));
+
+        let stbl = mp4_box(
+            b"stbl",
+            &[
+                &avc1_entry(),
+                &full_box(b"stts", 0, &[&u32s(&[1, 4, 1000])]),
+                &full_box(b"stsc", 0, &[&u32s(&[1, 1, 2, 1])]),
+                &full_box(b"stsz", 0, &[&u32s(&[0, 4, 10, 20, 30, 40])]),
+                &full_box(b"stco", 0, &[&u32s(&[2, 100, 500])]),
+                &full_box(b"stss", 0, &[&u32s(&[2, 1, 3])]),
+            ],
+        );
+        let elst = full_box(
+            b"elst",
+            0,
+            &[&u32s(&[2, 500, u32::MAX, 1 << 16, 4000, 1000, 1 << 16])],
+        );
+
+        mp4_box(
+            b"trak",
+            &[
+                &full_box(b"tkhd", 0, &[&tkhd]),
+                &mp4_box(b"edts", &[&elst]),
+                &mp4_box(
+                    b"mdia",
+                    &[
+                        &media_header(b"mdhd", 10000, 40000),
+                        &mp4_box(b"minf", &[&stbl]),
+                    ],
+                ),
+            ],
+        )
+    }
+
+    fn audio_trak() -> Vec<u8> {
+        let mut mp4a = vec![0; 8];
+        mp4a.extend(u32s(&[0, 0]));
+        mp4a.extend([0, 2, 0, 16, 0, 0, 0, 0]);
+        mp4a.extend(u32s(&[44100 << 16]));
+        let descriptors = [
+            &[0x03, 22, 0, 1, 0][..],
+            &[0x04, 17, 0x40, 0x15, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0][..],
+            &[0x05, 2, 0x12, 0x10][..],
+        ]
+        .concat();
+        mp4a.extend(full_box(b"esds", 0, &[&descriptors]));
+
+        let stbl = mp4_box(
+            b"stbl",
+            &[
+                &full_box(b"stsd", 0, &[&u32s(&[1]), &mp4_box(b"mp4a", &[&mp4a])]),
+                &full_box(b"stts", 0, &[&u32s(&[1, 3, 1024])]),
+                &full_box(b"stsc", 0, &[&u32s(&[1, 1, 3, 1])]),
+                &full_box(b"stsz", 0, &[&u32s(&[5, 3])]),
+                &full_box(b"co64", 0, &[&u32s(&[1, 0, 1000])]),
+            ],
+        );
+        mp4_box(
+            b"trak",
+            &[&mp4_box(
+                b"mdia",
+                &[
+                    &media_header(b"mdhd", 44100, 3072),
+                    &mp4_box(b"minf", &[&stbl]),
+                ],
+            )],
+        )
+    }
+
+    fn movie() -> Vec<u8> {
+        let moov = mp4_box(
+            b"moov",
+            &[
+                &media_header(b"mvhd", 1000, 4500),
+                &video_trak(),
+                &audio_trak(),
+            ],
+        );
+        [
+            mp4_box(b"ftyp", &[b"isom", &[0, 0, 0, 1], b"isomavc1"]),
+            // The movie data before the `moov`, as progressive downloads would see it.
+            u32s(&[16, u32::from_be_bytes(*b"mdat"), 0, 0]),
+            moov,
+        ]
+        .concat()
+    }
+
+    #[test]
+    fn parse_movie() {
+        let mp4 = Mp4::parse(&movie(), None).expect("valid MP4");
+        assert_eq!(mp4.duration, 4500.0);
+        assert_eq!(mp4.moov_position, 40);
+
+        let video = mp4.video.expect("video track");
+        assert_eq!(
+            video.codec,
+            Mp4Codec::H264 {
+                width: 320,
+                height: 240,
+                config: vec![1, 0x42, 0, 0x1E],
+            }
+        );
+        assert_eq!(video.duration, 4000.0);
+        // Delayed by 500ms by the empty edit, and starting at 100ms of media
+        // time. The first sample is only decoded for the second to depend on.
+        let expected = [
+            (100, 10, 500.0, -100.0, true),
+            (110, 20, 500.0, 0.0, false),
+            (500, 30, 600.0, 0.0, true),
+            (530, 40, 700.0, 0.0, false),
+        ];
+        for (sample, (offset, size, time, composition_offset, is_keyframe)) in
+            video.samples.iter().zip(expected)
+        {
+            assert_eq!(
+                sample,
+                &Mp4Sample {
+                    offset,
+                    size,
+                    time,
+                    composition_offset,
+                    is_keyframe
+                }
+            );
+        }
+        assert_eq!(video.samples.len(), 4);
+        assert_eq!(video.keyframe_before(550.0), 0);
+        assert_eq!(video.keyframe_before(650.0), 2);
+        assert_eq!(video.sample_at(550.0), 2);
+
+        let audio = mp4.audio.expect("audio track");
+        assert_eq!(
+            audio.codec,
+            Mp4Codec::Aac {
+                sample_rate: 44100,
+                channels: 2,
+                config: vec![0x12, 0x10],
+            }
+        );
+        let offsets: Vec<_> = audio.samples.iter().map(|s| s.offset).collect();
+        assert_eq!(offsets, [1000, 1005, 1010]);
+        assert!(audio.samples.iter().all(|s| s.is_keyframe));
+    }
+
+    #[test]
+    fn composition_offsets() {
+        let stbl = mp4_box(
+            b"stbl",
+            &[
+                &avc1_entry(),
+                &full_box(b"stts", 0, &[&u32s(&[1, 3, 100])]),
+                &full_box(b"ctts", 1, &[&u32s(&[2, 2, 200, 1, (-100i32) as u32])]),
+                &full_box(b"stsc", 0, &[&u32s(&[1, 1, 3, 1])]),
+                &full_box(b"stsz", 0, &[&u32s(&[0, 3, 1, 2, 3])]),
+                &full_box(b"stco", 0, &[&u32s(&[1, 10])]),
+            ],
+        );
+        let (_, samples) = SampleTable::parse(Reader::new(&stbl[8..]), 1000, None)
+            .expect("valid stbl")
+            .expect("supported codec");
+        let times: Vec<_> = samples
+            .iter()
+            .map(|s| (s.time, s.presentation_time()))
+            .collect();
+        assert_eq!(times, [(0.0, 200.0), (100.0, 300.0), (200.0, 100.0)]);
+    }
+
+    #[test]
+    fn sample_offset_overflow() {
+        let stbl = mp4_box(
+            b"stbl",
+            &[
+                &avc1_entry(),
+                &full_box(b"stts", 0, &[&u32s(&[1, 2, 100])]),
+                &full_box(b"stsc", 0, &[&u32s(&[1, 1, 2, 1])]),
+                &full_box(b"stsz", 0, &[&u32s(&[16, 2])]),
+                &full_box(b"co64", 0, &[&u32s(&[1, u32::MAX, u32::MAX - 20])]),
+            ],
+        );
+        if usize::BITS == 64 {
+            assert_eq!(
+                SampleTable::parse(Reader::new(&stbl[8..]), 1000, None),
+                Err(Mp4Error::InvalidBox("stsz"))
+            );
+        }
+
+        let mut data = mp4_box(b"ftyp", &[b"isom"]);
+        data.extend(u32s(&[1, u32::from_be_bytes(*b"moov"), u32::MAX, u32::MAX]));
+        data.extend([0; 8]);
+        assert_eq!(Mp4::parse(&data, None), Err(Mp4Error::InvalidBox("moov")));
+    }
+
+    #[test]
+    fn samples_outside_file() {
+        // A tiny file declaring about 4 billion samples of 16 bytes each.
+        let stbl = mp4_box(
+            b"stbl",
+            &[
+                &avc1_entry(),
+                &full_box(b"stts", 0, &[&u32s(&[1, u32::MAX, 100])]),
+                &full_box(b"stsc", 0, &[&u32s(&[1, 1, u32::MAX, 1])]),
+                &full_box(b"stsz", 0, &[&u32s(&[16, u32::MAX])]),
+                &full_box(b"stco", 0, &[&u32s(&[1, 0])]),
+            ],
+        );
+        assert_eq!(
+            SampleTable::parse(Reader::new(&stbl[8..]), 1000, Some(stbl.len())),
+            Err(Mp4Error::InvalidBox("stsz"))
+        );
+
+        // Only as many samples as `stts` gives durations to.
+        let stbl = mp4_box(
+            b"stbl",
+            &[
+                &avc1_entry(),
+                &full_box(b"stts", 0, &[&u32s(&[1, 2, 100])]),
+                &full_box(b"stsc", 0, &[&u32s(&[1, 1, u32::MAX, 1])]),
+                &full_box(b"stsz", 0, &[&u32s(&[16, u32::MAX])]),
+                &full_box(b"stco", 0, &[&u32s(&[1, 0])]),
+            ],
+        );
+        let (_, samples) = SampleTable::parse(Reader::new(&stbl[8..]), 1000, None)
+            .expect("valid stbl")
+            .expect("supported codec");
+        assert_eq!(samples.len(), 2);
+    }
+
+    #[test]
+    fn multiple_edits() {
+        // An I, P and two B frames, presented as I B B P.
+        let samples: Vec<_> = [(0.0, 100.0, true), (100.0, 300.0, false)]
+            .into_iter()
+            .chain([(200.0, 0.0, false), (300.0, 0.0, false)])
+            .enumerate()
+            .map(|(i, (time, composition_offset, is_keyframe))| Mp4Sample {
+                offset: i,
+                size: 1,
+                time,
+                composition_offset,
+                is_keyframe,
+            })
+            .collect();
+
+        // Plays the first 300ms of the media, then the first 200ms again.
+        let samples = Track::apply_edits(samples, &[(300, 100), (200, 100)], 400, 1000, 1000);
+        let timeline: Vec<_> = samples
+            .iter()
+            .map(|s| (s.offset, s.time, s.presentation_time()))
+            .collect();
+        assert_eq!(
+            timeline,
+            [
+                (0, 0.0, 0.0),
+                (1, 0.0, 300.0),
+                (2, 100.0, 100.0),
+                (3, 200.0, 200.0),
+                (0, 300.0, 300.0),
+                (1, 300.0, 600.0),
+                (2, 400.0, 400.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn moov_not_downloaded() {
+        let movie = movie();
+        assert!(is_mp4(&movie));
+        assert_eq!(Mp4::parse(&movie[..100], None), Err(Mp4Error::EndOfData));
+        assert_eq!(Mp4::parse(&movie[..30], None), Err(Mp4Error::EndOfData));
+    }
+}
@@ -0,0 +1,415 @@
+//! Telemetry output that can be read by Adobe Scout and compatible tools.
+//!
+//! The stream is a sequence of AMF3 objects of three kinds:
+//!
+//! * `.value`, a named value,
+//! * `.span`, a named duration ending at the time it's sent, with an optional value,
+//! * `.time`, a named point in time.
+//!
+//! Every message carries the time elapsed since the previous message (its
+//! `delta`), in microseconds.
+
+use crate::tag_utils::SwfMovie;
+use chrono::Utc;
+use std::io::Write;
+use web_time::{Duration, Instant};
+
+/// The version of the telemetry protocol we're writing.
+const PROTOCOL_VERSION: &str = "3,2";
+
+/// Marks the start of a frame.
+pub const FRAME_ENTER: &str = ".enter";
+
+/// The time spent running scripts and updating the display list in a frame.
+pub const FRAME_ACTIONS: &str = ".as.actions";
+
+/// The time spent executing an ActionScript method, with its name as value.
+pub const METHOD: &str = ".as.method";
+
+/// The time spent rendering the stage.
+pub const RENDER: &str = ".rend.screen";
+
+/// The number of draw commands that the stage was rendered with.
+pub const RENDER_COMMANDS: &str = ".rend.commands";
+
+/// The number of `cacheAsBitmap` surfaces that were redrawn.
+pub const RENDER_CACHE_DRAWS: &str = ".rend.cachedraws";
+
+/// Bytes allocated in the garbage collected heap.
+pub const MEMORY_MANAGED: &str = ".mem.managed";
+
+/// Bytes allocated in total, including memory owned by managed objects.
+pub const MEMORY_TOTAL: &str = ".mem.total";
+
+/// Collection work that is owed and will be performed soon, in bytes.
+pub const GC_DEBT: &str = ".gc.debt";
+
+/// A value sent in a telemetry message.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TelemetryValue {
+    Null,
+    Bool(bool),
+    Int(i32),
+    Number(f64),
+    String(String),
+}
+
+impl From<bool> for TelemetryValue {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl From<i32> for TelemetryValue {
+    fn from(value: i32) -> Self {
+        Self::Int(value)
+    }
+}
+
+impl From<u32> for TelemetryValue {
+    fn from(value: u32) -> Self {
+        Self::Number(value.into())
+    }
+}
+
+impl From<usize> for TelemetryValue {
+    fn from(value: usize) -> Self {
+        Self::Number(value as f64)
+    }
+}
+
+impl From<f64> for TelemetryValue {
+    fn from(value: f64) -> Self {
+        Self::Number(value)
+    }
+}
+
+impl From<String> for TelemetryValue {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<&str> for TelemetryValue {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_string())
+    }
+}
+
+/// Writes telemetry messages, if telemetry output was configured.
+///
+/// Messages are buffered until `flush` is called, which the player does after
+/// every frame.
+pub struct Telemetry {
+    output: Option<Box<dyn Write>>,
+
+    /// When telemetry was started, which span markers are relative to.
+    start: Instant,
+
+    /// When the last message was sent, which the next delta is relative to.
+    last_message: Instant,
+
+    buffer: Vec<u8>,
+}
+
+impl Default for Telemetry {
+    fn default() -> Self {
+        let now = Instant::now();
+        Self {
+            output: None,
+            start: now,
+            last_message: now,
+            buffer: Vec::new(),
+        }
+    }
+}
+
+impl Telemetry {
+    /// Starts writing telemetry to `output`.
+    pub fn new(output: Box<dyn Write>) -> Self {
+        let mut telemetry = Self {
+            output: Some(output),
+            ..Default::default()
+        };
+
+        telemetry.value(".tlm.version", PROTOCOL_VERSION);
+        telemetry.value(".tlm.date", Utc::now().timestamp_millis() as f64);
+        telemetry.value(".player.type", "Ruffle");
+        telemetry.value(".player.version", env!("CARGO_PKG_VERSION"));
+        telemetry
+    }
+
+    /// Whether telemetry is being written anywhere.
+    pub fn is_enabled(&self) -> bool {
+        self.output.is_some()
+    }
+
+    /// Sends the properties of a newly loaded root movie.
+    pub fn movie_info(&mut self, movie: &SwfMovie, frame_rate: f64) {
+        if !self.is_enabled() {
+            return;
+        }
+
+        self.value(".swf.name", movie.url());
+        self.value(".swf.version", u32::from(movie.version()));
+        self.value(".swf.vm", if movie.is_action_script_3() { 3 } else { 2 });
+        self.value(".swf.rate", frame_rate);
+        self.value(".swf.width", movie.width().to_pixels());
+        self.value(".swf.height", movie.height().to_pixels());
+        self.value(".swf.size", movie.uncompressed_len());
+    }
+
+    /// The current time, in microseconds since telemetry was started.
+    ///
+    /// This is what `Telemetry.spanMarker` returns.
+    pub fn span_marker(&self) -> f64 {
+        self.start.elapsed().as_micros() as f64
+    }
+
+    /// Converts a span marker back into the time it was taken at.
+    pub fn marker_time(&self, marker: f64) -> Instant {
+        self.start + Duration::from_micros(marker.max(0.0) as u64)
+    }
+
+    /// Sends a named value.
+    pub fn value(&mut self, name: &str, value: impl Into<TelemetryValue>) {
+        if !self.is_enabled() {
+            return;
+        }
+
+        let value = value.into();
+        write_object(
+            &mut self.buffer,
+            ".value",
+            &[("name", &name.into()), ("value", &value)],
+        );
+    }
+
+    /// Sends a named point in time.
+    pub fn time(&mut self, name: &str) {
+        if !self.is_enabled() {
+            return;
+        }
+
+        let delta = self.delta(Instant::now());
+        write_object(
+            &mut self.buffer,
+            ".time",
+            &[("name", &name.into()), ("delta", &delta)],
+        );
+    }
+
+    /// Sends a named duration from `start` until now, with an optional value.
+    pub fn span(&mut self, name: &str, start: Instant, value: Option<TelemetryValue>) {
+        if !self.is_enabled() {
+            return;
+        }
+
+        let now = Instant::now();
+        let span = micros(now.saturating_duration_since(start));
+        let delta = self.delta(now);
+        let name = name.into();
+        match value {
+            Some(value) => write_object(
+                &mut self.buffer,
+                ".span",
+                &[
+                    ("name", &name),
+                    ("span", &span),
+                    ("delta", &delta),
+                    ("value", &value),
+                ],
+            ),
+            None => write_object(
+                &mut self.buffer,
+                ".span",
+                &[("name", &name), ("span", &span), ("delta", &delta)],
+            ),
+        }
+    }
+
+    /// Writes out all buffered messages.
+    ///
+    /// If the output fails, telemetry is disabled.
+    pub fn flush(&mut self) {
+        let Some(output) = &mut self.output else {
+            return;
+        };
+
+        let result = output.write_all(&self.buffer).and_then(|_| output.flush());
+        self.buffer.clear();
+
+        if let Err(e) = result {
+            tracing::warn!("Couldn't write telemetry, disabling it: {e}");
+            self.output = None;
+        }
+    }
+
+    /// Returns the time since the last message, and makes `now` the time of
+    /// the last message.
+    fn delta(&mut self, now: Instant) -> TelemetryValue {
+        let delta = now.saturating_duration_since(self.last_message);
+        self.last_message = now;
+        micros(delta)
+    }
+}
+
+fn micros(duration: Duration) -> TelemetryValue {
+    match i32::try_from(duration.as_micros()) {
+        Ok(micros) => TelemetryValue::Int(micros),
+        Err(_) => TelemetryValue::Number(duration.as_micros() as f64),
+    }
+}
+
+/// Writes a sealed, typed AMF3 object with the given members.
+///
+/// Traits and strings are always sent inline, so the output doesn't depend on
+/// reference tables.
+fn write_object(output: &mut Vec<u8>, class_name: &str, members: &[(&str, &TelemetryValue)]) {
+    const OBJECT_MARKER: u8 = 0x0A;
+
+    output.push(OBJECT_MARKER);
+    // Inline object (1), inline traits (1), not externalizable (0), not
+    // dynamic (0), followed by the number of sealed members.
+    write_u29(output, ((members.len() as u32) << 4) | 0b0011);
+    write_string(output, class_name);
+    for (name, _) in members {
+        write_string(output, name);
+    }
+    for (_, value) in members {
+        write_value(output, value);
+    }
+}
+
+fn write_value(output: &mut Vec<u8>, value: &TelemetryValue) {
+    const NULL_MARKER: u8 = 0x01;
+    const FALSE_MARKER: u8 = 0x02;
+    const TRUE_MARKER: u8 = 0x03;
+    const INTEGER_MARKER: u8 = 0x04;
+    const DOUBLE_MARKER: u8 = 0x05;
+    const STRING_MARKER: u8 = 0x06;
+
+    /// The range of integers that can be stored in an AMF3 integer.
+    const INTEGER_RANGE: std::ops::RangeInclusive<i32> = -(1 << 28)..=(1 << 28) - 1;
+
+    match value {
+        TelemetryValue::Null => output.push(NULL_MARKER),
+        TelemetryValue::Bool(false) => output.push(FALSE_MARKER),
+        TelemetryValue::Bool(true) => output.push(TRUE_MARKER),
+        TelemetryValue::Int(value) if INTEGER_RANGE.contains(value) => {
+            output.push(INTEGER_MARKER);
+            write_u29(output, (*value as u32) & 0x1FFF_FFFF);
+        }
+        TelemetryValue::Int(value) => {
+            output.push(DOUBLE_MARKER);
+            output.extend_from_slice(&f64::from(*value).to_be_bytes());
+        }
+        TelemetryValue::Number(value) => {
+            output.push(DOUBLE_MARKER);
+            output.extend_from_slice(&value.to_be_bytes());
+        }
+        TelemetryValue::String(value) => {
+            output.push(STRING_MARKER);
+            write_string(output, value);
+        }
+    }
+}
+
+/// Writes an inline string, without a type marker.
+fn write_string(output: &mut Vec<u8>, value: &str) {
+    write_u29(output, ((value.len() as u32) << 1) | 1);
+    output.extend_from_slice(value.as_bytes());
+}
+
+/// Writes a variable length 29-bit unsigned integer.
+fn write_u29(output: &mut Vec<u8>, value: u32) {
+    let value = value & 0x1FFF_FFFF;
+    if value < 0x80 {
+        output.push(value as u8);
+    } else if value < 0x4000 {
+        output.push(((value >> 7) | 0x80) as u8);
+        output.push((value & 0x7F) as u8);
+    } else if value < 0x20_0000 {
+        output.push(((value >> 14) | 0x80) as u8);
+        output.push(((value >> 7) | 0x80) as u8);
+        output.push((value & 0x7F) as u8);
+    } else {
+        output.push(((value >> 22) | 0x80) as u8);
+        output.push(((value >> 15) | 0x80) as u8);
+        output.push(((value >> 8) | 0x80) as u8);
+        output.push(value as u8);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Rc<RefCell<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.borrow_mut().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_u29() {
+        for (value, expected) in [
+            (0, vec![0x00]),
+            (0x7F, vec![0x7F]),
+            (0x80, vec![0x81, 0x00]),
+            (0x3FFF, vec![0xFF, 0x7F]),
+            (0x4000, vec![0x81, 0x80, 0x00]),
+            (0x1F_FFFF, vec![0xFF, 0xFF, 0x7F]),
+            (0x20_0000, vec![0x80, 0xC0, 0x80, 0x00]),
+            (0x1FFF_FFFF, vec![0xFF, 0xFF, 0xFF, 0xFF]),
+        ] {
+            let mut output = Vec::new();
+            write_u29(&mut output, value);
+            assert_eq!(output, expected, "encoding {value:#x}");
+        }
+    }
+
+    #[test]
+    fn test_value_message() {
+        let buffer = SharedBuffer::default();
+        let mut telemetry = Telemetry {
+            output: Some(Box::new(buffer.clone())),
+            ..Default::default()
+        };
+        telemetry.value("m", -1);
+        assert!(buffer.0.borrow().is_empty(), "messages should be buffered");
+        telemetry.flush();
+
+        let expected = [
+            &[0x0A, 0x23][..],
+            &[0x0D][..],
+            b".value",
+            &[0x09][..],
+            b"name",
+            &[0x0B][..],
+            b"value",
+            &[0x06, 0x03, b'm'][..],
+            &[0x04, 0xFF, 0xFF, 0xFF, 0xFF][..],
+        ]
+        .concat();
+        assert_eq!(*buffer.0.borrow(), expected);
+    }
+
+    #[test]
+    fn test_disabled() {
+        let mut telemetry = Telemetry::default();
+        telemetry.value("m", 1);
+        telemetry.span("s", Instant::now(), None);
+        assert!(telemetry.buffer.is_empty());
+    }
+}
@@ -3,20 +3,28 @@
 //! We tick the timers during our normal frame loop for deterministic operation.
 //! The timers are stored in a priority queue, where we check if the nearest timer
 //! is ready to tick each frame.
+//!
+//! This also holds the `MovieClock` that `getTimer` reads from.
 
 use crate::avm1::ExecutionReason;
 use crate::avm1::{Activation, ActivationIdentifier, Object as Avm1Object, Value as Avm1Value};
 use crate::avm2::error::make_null_or_undefined_error;
-use crate::avm2::object::FunctionObject as Avm2FunctionObject;
+use crate::avm2::globals::slots::flash_utils_timer as timer_slots;
+use crate::avm2::object::{FunctionObject as Avm2FunctionObject, TObject as _};
 use crate::avm2::{
-    Activation as Avm2Activation, Avm2, Error as Avm2Error, FunctionArgs, Value as Avm2Value,
+    Activation as Avm2Activation, Avm2, Error as Avm2Error, FunctionArgs, Object as Avm2Object,
+    Value as Avm2Value,
 };
 use crate::context::UpdateContext;
 use crate::display_object::{DisplayObject, TDisplayObject};
 use crate::string::AvmString;
-use gc_arena::Collect;
+use crate::tag_utils::SwfMovie;
+use gc_arena::{Collect, Mutation};
 use ruffle_common::duration::FloatDuration;
 use std::collections::{BinaryHeap, binary_heap::PeekMut};
+use std::sync::Arc;
+use std::time::Duration;
+use web_time::Instant;
 
 /// Manages the collection of timers.
 #[derive(Collect)]
@@ -140,7 +148,9 @@ impl<'gc> Timers<'gc> {
                         true
                     }
                 }
-                TimerCallback::Avm2Callback { closure, params } => {
+                TimerCallback::Avm2Callback {
+                    closure, params, ..
+                } => {
                     let domain = context.avm2.stage_domain();
                     let mut avm2_activation = Avm2Activation::from_domain(context, domain);
 
@@ -243,12 +253,13 @@ impl<'gc> Timers<'gc> {
         self.timers.iter().any(|t| t.id == id)
     }
 
-    /// Registers a new timer and returns the timer ID.
+    /// Registers a new timer created by `movie` and returns the timer ID.
     pub fn add_timer(
         &mut self,
         callback: TimerCallback<'gc>,
         interval: i32,
         is_timeout: bool,
+        movie: Arc<SwfMovie>,
     ) -> i32 {
         // SANITY: Set a minimum interval so we don't spam too much.
         let interval = interval.max(Self::MIN_INTERVAL) as u64 * (Self::TIMER_SCALE as u64);
@@ -263,6 +274,7 @@ impl<'gc> Timers<'gc> {
             tick_time: self.cur_time + interval,
             interval,
             is_timeout,
+            movie,
         };
         self.timers.push(timer);
         id
@@ -282,6 +294,26 @@ impl<'gc> Timers<'gc> {
         self.timers.clear()
     }
 
+    /// Removes all timers created by the given movie.
+    ///
+    /// The `flash.utils.Timer`s among them stop running.
+    pub fn remove_all_of_movie(&mut self, mc: &Mutation<'gc>, movie: &Arc<SwfMovie>) {
+        self.timers.retain(|t| {
+            if !Arc::ptr_eq(&t.movie, movie) {
+                return true;
+            }
+
+            if let TimerCallback::Avm2Callback {
+                timer_object: Some(timer_object),
+                ..
+            } = &t.callback
+            {
+                timer_object.set_slot_no_coerce(timer_slots::_TIMER_ID, (-1).into(), mc);
+            }
+            false
+        });
+    }
+
     /// Changes the delay of a timer.
     pub fn set_delay(&mut self, id: i32, interval: i32) {
         // SANITY: Set a minimum interval so we don't spam too much.
@@ -345,6 +377,10 @@ pub struct Timer<'gc> {
 
     /// This timer only fires once if `is_timeout` is true.
     is_timeout: bool,
+
+    /// The movie whose code created this timer.
+    #[collect(require_static)]
+    movie: Arc<SwfMovie>,
 }
 
 // Implement `Ord` so that timers can be stored in the BinaryHeap (as a min-heap).
@@ -387,5 +423,60 @@ pub enum TimerCallback<'gc> {
     Avm2Callback {
         closure: Option<Avm2FunctionObject<'gc>>,
         params: Vec<Avm2Value<'gc>>,
+
+        /// The `flash.utils.Timer` this ticks, which has to be told when the
+        /// timer gets removed behind its back.
+        timer_object: Option<Avm2Object<'gc>>,
     },
 }
+
+/// The clock read by `getTimer`.
+///
+/// It normally follows real time, but runs faster or slower when the player's
+/// time scale is changed, so that scripts see time pass at the same rate as
+/// frames and timers do.
+#[derive(Clone, Copy, Debug)]
+pub struct MovieClock {
+    /// The instant at which the time scale was last changed.
+    anchor: Instant,
+
+    /// The movie time that had passed at `anchor`.
+    elapsed_at_anchor: Duration,
+
+    /// How fast movie time passes relative to real time.
+    time_scale: f64,
+}
+
+impl MovieClock {
+    /// Creates a clock starting at zero and running at normal speed.
+    pub fn new() -> Self {
+        Self {
+            anchor: Instant::now(),
+            elapsed_at_anchor: Duration::ZERO,
+            time_scale: 1.0,
+        }
+    }
+
+    /// The movie time that has passed since the clock was started.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed_at_anchor + self.anchor.elapsed().mul_f64(self.time_scale)
+    }
+
+    pub fn time_scale(&self) -> f64 {
+        self.time_scale
+    }
+
+    /// Changes the speed of the clock without making it jump.
+    pub fn set_time_scale(&mut self, time_scale: f64) {
+        let now = Instant::now();
+        self.elapsed_at_anchor += now.duration_since(self.anchor).mul_f64(self.time_scale);
+        self.anchor = now;
+        self.time_scale = time_scale;
+    }
+}
+
+impl Default for MovieClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
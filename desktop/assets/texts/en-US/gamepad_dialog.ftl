@@ -0,0 +1,29 @@
+gamepad-dialog = Gamepad Profiles
+
+gamepad-dialog-active = Active profile
+gamepad-dialog-edit = Edit profile
+gamepad-dialog-none = None
+gamepad-dialog-add-profile = New Profile
+gamepad-dialog-new-profile-name = New profile
+gamepad-dialog-name = Name
+gamepad-dialog-add = Add
+gamepad-dialog-key-code-tooltip = Key code sent to the movie
+
+gamepad-dialog-cursor = Cursor
+gamepad-dialog-cursor-enabled = Move the cursor with a stick
+gamepad-dialog-cursor-stick = Stick
+gamepad-dialog-cursor-stick-left = Left
+gamepad-dialog-cursor-stick-right = Right
+gamepad-dialog-cursor-deadzone = Deadzone
+gamepad-dialog-cursor-speed = Speed
+gamepad-dialog-cursor-acceleration = Acceleration
+gamepad-dialog-cursor-acceleration-tooltip = Higher values give finer control when the stick is pushed only slightly
+
+gamepad-dialog-buttons = Buttons to Keys
+gamepad-dialog-mouse-buttons = Buttons to Mouse Buttons
+gamepad-dialog-mouse-left = Left button
+gamepad-dialog-mouse-right = Right button
+gamepad-dialog-mouse-middle = Middle button
+gamepad-dialog-axis-keys = Axes to Keys
+gamepad-dialog-threshold-tooltip = The key is held while the axis is past this value, away from zero
+gamepad-dialog-chords = Chords
@@ -15,15 +15,32 @@ file-menu-close = Close
 file-menu-reload = Reload
 file-menu-recents = Recents
 file-menu-recents-empty = No recent entries
+file-menu-shared-objects = Manage Saved Data...
 file-menu-preferences = Preferences...
 file-menu-exit = Exit
 file-menu-export = Export...
+file-menu-save-screenshot = Save Screenshot
+file-menu-start-recording = Start Recording
+file-menu-stop-recording = Stop Recording
+
+# Shown in the menu bar while a clip is being recorded
+recording-indicator = Recording
 
 controls-menu = Controls
 controls-menu-suspend = Suspend
 controls-menu-resume = Resume
 controls-menu-step-once = Step Once
+controls-menu-speed = Playback Speed
+controls-menu-speed-quarter = 0.25×
+controls-menu-speed-half = 0.5×
+controls-menu-speed-normal = Normal
+controls-menu-speed-double = 2×
+controls-menu-speed-quadruple = 4×
+controls-menu-speed-faster = Faster
+controls-menu-speed-slower = Slower
+controls-menu-speed-reset = Reset Speed
 controls-menu-volume = Volume controls
+controls-menu-gamepad = Gamepad profiles
 
 help-menu = Help
 help-menu-join-discord = Join Discord
@@ -42,7 +59,11 @@ debug-menu-open-root-movie-clip = View Root MovieClip
 debug-menu-open-movie = View Movie
 debug-menu-open-movie-list = Show Known Movies
 debug-menu-open-domain-list = Show Domains
+debug-menu-open-network = Show Network Activity
+debug-menu-performance-hud = Performance HUD
 debug-menu-search-display-objects = Search Display Objects...
+debug-menu-search-variables = Search Variables...
+debug-menu-open-debugger = Script Debugger
 
 view-menu = View
 view-menu-fullscreen = Full Screen
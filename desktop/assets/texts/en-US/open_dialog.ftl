@@ -5,3 +5,7 @@ open-dialog-path = File or URL
 open-dialog-add-parameter = Add
 open-dialog-remove-parameter = Remove
 open-dialog-remove-parameters = Remove all
+
+open-dialog-remember-settings = Remember settings for this movie
+open-dialog-remember-settings-tooltip = Use these settings automatically the next time this movie is opened
+open-dialog-edit-profile = Edit Profile...
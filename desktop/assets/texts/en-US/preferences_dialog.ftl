@@ -26,6 +26,9 @@ storage-backend = Save-File Location
 storage-backend-disk = Disk
 storage-backend-memory = Memory
 
+speed-hotkeys = Playback Speed Shortcuts
+speed-hotkeys-tooltip = Ctrl+], Ctrl+[ and Ctrl+Backspace change the playback speed instead of being passed to the movie.
+
 recent-limit = Recent Limit
 recent-clear = Clear
 
@@ -47,3 +50,15 @@ ime-enabled = Input Method
 ime-enabled-experimental = (experimental)
 ime-enabled-tooltip = An input method allows inputting characters that are not available on the keyboard, for instance Chinese, Japanese, or Korean characters.
 ime-enabled-default = Default
+
+capture-directory = Screenshot & Recording Folder
+capture-size = Capture Size
+capture-size-window = As Displayed
+capture-size-native = Movie Size
+recording-format = Recording Format
+recording-format-apng = Animated PNG
+recording-format-gif = GIF
+recording-format-png-sequence = PNG Sequence
+screenshot-hotkey = Screenshot Shortcut
+record-hotkey = Recording Shortcut
+hotkey-invalid = Not a valid shortcut, for example "Ctrl+Shift+F12"
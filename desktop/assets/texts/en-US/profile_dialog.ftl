@@ -0,0 +1,17 @@
+profile-dialog = Movie Profile
+
+profile-dialog-name = Name
+profile-dialog-match = Applies to
+profile-dialog-match-url = This location
+profile-dialog-match-hash = This movie, wherever it's opened from
+profile-dialog-no-hash = Only available for local files
+profile-dialog-volume = Volume
+
+profile-dialog-gamepad = Gamepad Buttons
+profile-dialog-gamepad-add = Add
+profile-dialog-key-code-tooltip = Key code sent to the movie when the button is pressed
+
+profile-dialog-player-settings = Player Settings
+profile-dialog-player-settings-description = Settings are remembered when opening this movie with "Remember settings for this movie" checked.
+profile-dialog-player-settings-none = No settings remembered
+profile-dialog-player-settings-clear = Forget Settings
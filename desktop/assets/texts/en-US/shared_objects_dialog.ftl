@@ -0,0 +1,25 @@
+shared-objects-dialog = Manage Saved Data
+
+shared-objects-dialog-search = Search...
+shared-objects-dialog-refresh = Refresh
+shared-objects-dialog-empty = No saved data was found
+shared-objects-dialog-not-selected = Nothing to show
+shared-objects-dialog-size = { $bytes } bytes
+shared-objects-dialog-in-use-warning = The running movie may be using this data. Close it to make changes.
+shared-objects-dialog-no-properties = This shared object is empty
+shared-objects-dialog-invalid = This file could not be read as a shared object
+
+shared-objects-dialog-revert = Revert
+shared-objects-dialog-export = Export...
+shared-objects-dialog-import = Import...
+shared-objects-dialog-import-hint = Replace this shared object with a .sol file
+shared-objects-dialog-import-flash = Import from Flash Player...
+shared-objects-dialog-import-flash-hint = Copy saved data from a Flash Player "#SharedObjects" directory, replacing existing data with the same name
+shared-objects-dialog-import-flash-title = Select a Flash Player "#SharedObjects" directory
+
+shared-objects-dialog-exported = Exported successfully
+shared-objects-dialog-imported = Imported { $count ->
+    [one] 1 shared object
+   *[other] { $count } shared objects
+}
+shared-objects-dialog-operation-failed = The operation failed, see the log for details
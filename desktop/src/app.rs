@@ -3,8 +3,9 @@ use crate::gui::{GuiController, MENU_HEIGHT};
 use crate::player::{LaunchOptions, PlayerController};
 use crate::preferences::GlobalPreferences;
 use crate::util::{
-    get_screen_size, gilrs_button_to_gamepad_button, plot_stats_in_tracy,
-    winit_input_to_ruffle_key_descriptor, winit_to_ruffle_text_control,
+    get_screen_size, gilrs_axis_to_gamepad_axis, gilrs_button_to_gamepad_button,
+    gilrs_trigger_to_gamepad_axis, plot_stats_in_tracy, winit_input_to_ruffle_key_descriptor,
+    winit_to_ruffle_text_control,
 };
 use anyhow::Error;
 use gilrs::{Event, EventType, Gilrs};
@@ -13,9 +14,10 @@ use ruffle_core::PlayerEvent;
 use ruffle_core::events::{ImeEvent, ImeNotification, PlayerNotification};
 use ruffle_core::swf::HeaderExt;
 use ruffle_frontend_utils::content::ContentDescriptor;
+use ruffle_frontend_utils::gamepad::GamepadMapper;
 use ruffle_render::backend::ViewportDimensions;
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use winit::application::ApplicationHandler;
 use winit::dpi::{LogicalSize, PhysicalPosition, PhysicalSize, Size};
 use winit::event::{ElementState, Ime, KeyEvent, Modifiers, StartCause, WindowEvent};
@@ -23,6 +25,9 @@ use winit::event_loop::{ActiveEventLoop, ControlFlow, EventLoop, EventLoopProxy}
 use winit::keyboard::{Key, NamedKey};
 use winit::window::{Fullscreen, Icon, WindowAttributes, WindowId};
 
+/// How often the cursor is moved while a gamepad stick is driving it.
+const GAMEPAD_CURSOR_INTERVAL: Duration = Duration::from_millis(16);
+
 struct MainWindow {
     preferences: GlobalPreferences,
     gui: GuiController,
@@ -40,6 +45,8 @@ struct MainWindow {
     time: Instant,
     next_frame_time: Option<Instant>,
     event_loop_proxy: EventLoopProxy<RuffleEvent>,
+    gamepad: GamepadMapper,
+    gamepad_cursor_time: Instant,
 }
 
 impl MainWindow {
@@ -101,6 +108,7 @@ impl MainWindow {
 
                 self.mouse_pos = position;
                 let (x, y) = self.gui.window_to_movie_position(position);
+                self.gamepad.set_cursor_position(x, y);
                 let event = PlayerEvent::MouseMove { x, y };
                 self.player.handle_event(event);
                 self.check_redraw();
@@ -349,25 +357,12 @@ impl MainWindow {
     }
 
     fn about_to_wait(&mut self, gilrs: Option<&mut Gilrs>) {
-        if let Some(Event { event, .. }) = gilrs.and_then(|gilrs| gilrs.next_event()) {
-            match event {
-                EventType::ButtonPressed(button, _) => {
-                    if let Some(button) = gilrs_button_to_gamepad_button(button) {
-                        self.player
-                            .handle_event(PlayerEvent::GamepadButtonDown { button });
-                        self.check_redraw();
-                    }
-                }
-                EventType::ButtonReleased(button, _) => {
-                    if let Some(button) = gilrs_button_to_gamepad_button(button) {
-                        self.player
-                            .handle_event(PlayerEvent::GamepadButtonUp { button });
-                        self.check_redraw();
-                    }
-                }
-                _ => {}
+        if let Some(gilrs) = gilrs {
+            while let Some(Event { event, .. }) = gilrs.next_event() {
+                self.handle_gamepad_event(event);
             }
         }
+        self.move_gamepad_cursor();
 
         // Core loop
         // [NA] This used to be called `MainEventsCleared`, but I think the behaviour is different now.
@@ -383,6 +378,85 @@ impl MainWindow {
                 });
                 self.check_redraw();
             }
+
+            // Keep waking up while a stick is moving the cursor, even if the
+            // movie runs at a low frame rate.
+            if let Some(next_frame_time) = &mut self.next_frame_time
+                && self.gamepad.is_moving_cursor()
+            {
+                *next_frame_time = (*next_frame_time).min(new_time + GAMEPAD_CURSOR_INTERVAL);
+            }
+        }
+    }
+
+    fn handle_gamepad_event(&mut self, event: EventType) {
+        if !self.gamepad.is_moving_cursor() {
+            // Don't count the time the stick was at rest once it starts moving.
+            self.gamepad_cursor_time = Instant::now();
+        }
+
+        let events = match event {
+            EventType::ButtonPressed(button, _) => gilrs_button_to_gamepad_button(button)
+                .map(|button| self.gamepad.button_down(button)),
+            EventType::ButtonReleased(button, _) => {
+                gilrs_button_to_gamepad_button(button).map(|button| self.gamepad.button_up(button))
+            }
+            EventType::ButtonChanged(button, value, _) => gilrs_trigger_to_gamepad_axis(button)
+                .map(|axis| self.gamepad.axis_changed(axis, value as f64)),
+            EventType::AxisChanged(axis, value, _) => gilrs_axis_to_gamepad_axis(axis)
+                .map(|axis| self.gamepad.axis_changed(axis, value as f64)),
+            _ => None,
+        };
+
+        let events = events.unwrap_or_default();
+        if !events.is_empty() {
+            for event in events {
+                self.player.handle_event(event);
+            }
+            self.check_redraw();
+        }
+    }
+
+    fn move_gamepad_cursor(&mut self) {
+        let now = Instant::now();
+        let dt = now.duration_since(self.gamepad_cursor_time);
+        self.gamepad_cursor_time = now;
+
+        let Some(mut player) = self.player.get() else {
+            return;
+        };
+        let viewport = player.viewport_dimensions();
+        drop(player);
+
+        let Some(event) = self
+            .gamepad
+            .tick(dt, viewport.width as f64, viewport.height as f64)
+        else {
+            return;
+        };
+
+        // Move the system cursor along, rounded so that the resulting
+        // `CursorMoved` doesn't reset the sub-pixel position of the mapper.
+        let (x, y) = self.gamepad.cursor_position();
+        let position = self.gui.movie_to_window_position(x, y);
+        let position = PhysicalPosition::new(position.x.round(), position.y.round());
+        if position != self.mouse_pos {
+            self.mouse_pos = position;
+            // Not every platform supports this, the movie still gets the events.
+            let _ = self.gui.window().set_cursor_position(position);
+        }
+
+        self.player.handle_event(event);
+        self.check_redraw();
+    }
+
+    fn reload_gamepad_profile(&mut self) {
+        let profile = self
+            .preferences
+            .active_gamepad_profile()
+            .unwrap_or_default();
+        for event in self.gamepad.set_profile(profile) {
+            self.player.handle_event(event);
         }
     }
 
@@ -531,6 +605,9 @@ impl ApplicationHandler<RuffleEvent> for App {
                 loaded = LoadingState::Loaded;
             }
 
+            let gamepad =
+                GamepadMapper::new(preferences.active_gamepad_profile().unwrap_or_default());
+
             self.main_window = Some(MainWindow {
                 preferences,
                 gui,
@@ -548,6 +625,8 @@ impl ApplicationHandler<RuffleEvent> for App {
                 time: Instant::now(),
                 next_frame_time: None,
                 event_loop_proxy,
+                gamepad,
+                gamepad_cursor_time: Instant::now(),
             });
         }
     }
@@ -607,6 +686,10 @@ impl ApplicationHandler<RuffleEvent> for App {
                 main_window.gui.close_movie(&mut main_window.player);
             }
 
+            (Some(main_window), RuffleEvent::GamepadProfilesChanged) => {
+                main_window.reload_gamepad_profile();
+            }
+
             (Some(main_window), RuffleEvent::ExportBundle) => {
                 main_window.gui.export_bundle();
             }
@@ -1,10 +1,14 @@
 mod external_interface;
 mod fscommand;
+#[cfg(unix)]
+mod local_connection;
 mod navigator;
 mod ui;
 
 pub use external_interface::DesktopExternalInterfaceProvider;
 pub use fscommand::DesktopFSCommandProvider;
+#[cfg(unix)]
+pub use local_connection::DesktopLocalConnectionBackend;
 pub use navigator::DesktopNavigatorInterface;
 pub use navigator::PathAllowList;
 pub use ui::DesktopUiBackend;
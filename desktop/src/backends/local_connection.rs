@@ -0,0 +1,303 @@
+use ruffle_core::backend::local_connection::{
+    LocalConnectionBackend, LocalConnectionMessage, MAX_MESSAGE_BYTES,
+};
+use std::collections::HashMap;
+use std::io::{ErrorKind, Read, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::PathBuf;
+use std::sync::mpsc::{Receiver, Sender, channel};
+use std::time::Duration;
+
+/// How long we wait for a sender to finish writing a message.
+const READ_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// Shares `LocalConnection`s with other Ruffle processes of the same user.
+///
+/// Every connection listened on is a Unix socket in a shared directory,
+/// named after the connection. Owning the socket means owning the name.
+/// A message is sent by connecting to the receiver's socket and writing it
+/// as an AMF packet, one message per stream. Streams are read on a separate
+/// thread, so that a slow sender can't block the player.
+///
+/// This is only available on Unix, as the standard library doesn't support
+/// Unix sockets on Windows. There, `LocalConnection`s only work between the
+/// movies of a single player.
+pub struct DesktopLocalConnectionBackend {
+    directory: PathBuf,
+    listeners: HashMap<String, UnixListener>,
+
+    /// Accepted streams, along with the name of the connection they were
+    /// sent to, to be read by the reader thread.
+    streams: Sender<(String, UnixStream)>,
+
+    /// Messages decoded by the reader thread.
+    messages: Receiver<LocalConnectionMessage>,
+}
+
+impl DesktopLocalConnectionBackend {
+    pub fn new() -> Self {
+        let directory = dirs::runtime_dir()
+            .unwrap_or_else(std::env::temp_dir)
+            .join("ruffle")
+            .join("local_connections");
+        Self::with_directory(directory)
+    }
+
+    pub fn with_directory(directory: PathBuf) -> Self {
+        let (streams, stream_receiver) = channel();
+        let (message_sender, messages) = channel();
+        let spawned = std::thread::Builder::new()
+            .name("LocalConnection reader".to_string())
+            .spawn(move || Self::read_messages(stream_receiver, message_sender));
+        if let Err(e) = spawned {
+            // Accepted streams are dropped, so senders will see their
+            // messages fail instead of hanging.
+            tracing::error!("Couldn't start reading LocalConnection messages: {e}");
+        }
+
+        Self {
+            directory,
+            listeners: HashMap::new(),
+            streams,
+            messages,
+        }
+    }
+
+    /// The socket path for a connection.
+    ///
+    /// Connection names can be arbitrarily long and contain anything, while
+    /// socket paths can't, so the name is hashed. The full name is part of
+    /// every message, so a collision can't deliver a message to the wrong
+    /// connection.
+    fn socket_path(&self, name: &str) -> PathBuf {
+        // FNV-1a, as the hash needs to be stable across Ruffle versions.
+        let hash = name.bytes().fold(0xcbf29ce484222325u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+        });
+        self.directory.join(format!("{hash:016x}.sock"))
+    }
+
+    fn bind(&self, name: &str) -> std::io::Result<UnixListener> {
+        std::fs::create_dir_all(&self.directory)?;
+        let path = self.socket_path(name);
+        match UnixListener::bind(&path) {
+            Err(e) if e.kind() == ErrorKind::AddrInUse => {
+                // Either another process owns this name, or one which did
+                // exited without cleaning up after itself.
+                if UnixStream::connect(&path).is_ok() {
+                    return Err(e);
+                }
+                std::fs::remove_file(&path)?;
+                UnixListener::bind(&path)
+            }
+            result => result,
+        }
+    }
+
+    /// Reads accepted streams until the backend is dropped.
+    fn read_messages(
+        streams: Receiver<(String, UnixStream)>,
+        messages: Sender<LocalConnectionMessage>,
+    ) {
+        for (name, stream) in streams {
+            match Self::read_message(stream) {
+                // Empty streams are just other players checking whether we're listening.
+                Ok(bytes) if bytes.is_empty() => {}
+                Ok(bytes) => match LocalConnectionMessage::from_bytes(&bytes) {
+                    Some(message) if message.connection_name == name => {
+                        if messages.send(message).is_err() {
+                            return;
+                        }
+                    }
+                    Some(_) => {}
+                    None => tracing::warn!("Received invalid LocalConnection message"),
+                },
+                Err(e) => tracing::warn!("Couldn't read LocalConnection message: {e}"),
+            }
+        }
+    }
+
+    fn read_message(stream: UnixStream) -> std::io::Result<Vec<u8>> {
+        stream.set_nonblocking(false)?;
+        stream.set_read_timeout(Some(READ_TIMEOUT))?;
+        let mut bytes = Vec::new();
+        stream
+            .take(MAX_MESSAGE_BYTES as u64 + 1)
+            .read_to_end(&mut bytes)?;
+        if bytes.len() > MAX_MESSAGE_BYTES {
+            return Err(std::io::Error::new(
+                ErrorKind::InvalidData,
+                "message is too large",
+            ));
+        }
+        Ok(bytes)
+    }
+}
+
+impl Default for DesktopLocalConnectionBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LocalConnectionBackend for DesktopLocalConnectionBackend {
+    fn connect(&mut self, name: &str) -> bool {
+        match self.bind(name) {
+            Ok(listener) => {
+                if let Err(e) = listener.set_nonblocking(true) {
+                    tracing::error!("Couldn't set up LocalConnection '{name}': {e}");
+                    return false;
+                }
+                self.listeners.insert(name.to_owned(), listener);
+                true
+            }
+            Err(e) if e.kind() == ErrorKind::AddrInUse => false,
+            Err(e) => {
+                // Still let movies of this player talk to each other.
+                tracing::warn!("Couldn't share LocalConnection '{name}' with other players: {e}");
+                true
+            }
+        }
+    }
+
+    fn close(&mut self, name: &str) {
+        if self.listeners.remove(name).is_some() {
+            let _ = std::fs::remove_file(self.socket_path(name));
+        }
+    }
+
+    fn is_connected(&mut self, name: &str) -> bool {
+        !self.listeners.contains_key(name) && UnixStream::connect(self.socket_path(name)).is_ok()
+    }
+
+    fn send(&mut self, message: LocalConnectionMessage) -> bool {
+        if self.listeners.contains_key(&message.connection_name) {
+            return false;
+        }
+
+        let Some(bytes) = message.to_bytes() else {
+            tracing::error!("Couldn't encode LocalConnection message");
+            return false;
+        };
+        let path = self.socket_path(&message.connection_name);
+        UnixStream::connect(path)
+            .and_then(|mut stream| stream.write_all(&bytes))
+            .is_ok()
+    }
+
+    fn poll(&mut self) -> Vec<LocalConnectionMessage> {
+        for (name, listener) in &self.listeners {
+            loop {
+                let stream = match listener.accept() {
+                    Ok((stream, _)) => stream,
+                    Err(e) if e.kind() == ErrorKind::WouldBlock => break,
+                    Err(e) => {
+                        tracing::error!("Couldn't accept LocalConnection message: {e}");
+                        break;
+                    }
+                };
+                let _ = self.streams.send((name.clone(), stream));
+            }
+        }
+
+        // Messages still being read are picked up by a later call.
+        self.messages.try_iter().collect()
+    }
+}
+
+impl Drop for DesktopLocalConnectionBackend {
+    fn drop(&mut self) {
+        for name in self.listeners.keys() {
+            let _ = std::fs::remove_file(self.socket_path(name));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(connection_name: &str) -> LocalConnectionMessage {
+        LocalConnectionMessage {
+            connection_name: connection_name.to_owned(),
+            method_name: "hello".to_owned(),
+            sender_domain: "localhost".to_owned(),
+            sender_is_secure: false,
+            arguments: vec![],
+        }
+    }
+
+    /// Polls until the reader thread has decoded at least one message.
+    fn poll_until_received(
+        backend: &mut DesktopLocalConnectionBackend,
+    ) -> Vec<LocalConnectionMessage> {
+        for _ in 0..100 {
+            let messages = backend.poll();
+            if !messages.is_empty() {
+                return messages;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        vec![]
+    }
+
+    #[test]
+    fn messages_between_players() {
+        let directory = std::env::temp_dir().join(format!(
+            "ruffle-local-connection-test-{}",
+            std::process::id()
+        ));
+        let mut receiver = DesktopLocalConnectionBackend::with_directory(directory.clone());
+        let mut sender = DesktopLocalConnectionBackend::with_directory(directory.clone());
+
+        assert!(!sender.is_connected("localhost:test"));
+        assert!(!sender.send(message("localhost:test")));
+
+        assert!(receiver.connect("localhost:test"));
+        assert!(!sender.connect("localhost:test"), "name is already owned");
+        assert!(sender.is_connected("localhost:test"));
+        assert!(!receiver.is_connected("localhost:test"));
+
+        assert!(sender.send(message("localhost:test")));
+        assert_eq!(
+            poll_until_received(&mut receiver),
+            vec![message("localhost:test")]
+        );
+        assert_eq!(receiver.poll(), vec![]);
+
+        receiver.close("localhost:test");
+        assert!(!sender.is_connected("localhost:test"));
+        assert!(sender.connect("localhost:test"));
+
+        drop(sender);
+        let _ = std::fs::remove_dir(directory);
+    }
+    #[test]
+    fn oversized_messages_are_dropped() {
+        let directory = std::env::temp_dir().join(format!(
+            "ruffle-local-connection-size-test-{}",
+            std::process::id()
+        ));
+        let mut receiver = DesktopLocalConnectionBackend::with_directory(directory.clone());
+        let mut sender = DesktopLocalConnectionBackend::with_directory(directory.clone());
+        assert!(receiver.connect("localhost:test"));
+
+        // Written in the background, as it only gets read once the receiver polls.
+        // The receiver stops reading early, so writing may fail.
+        let stream = UnixStream::connect(receiver.socket_path("localhost:test"));
+        let writer = std::thread::spawn(move || {
+            if let Ok(mut stream) = stream {
+                let _ = stream.write_all(&vec![0; MAX_MESSAGE_BYTES * 2]);
+            }
+        });
+        assert!(sender.send(message("localhost:test")));
+        assert_eq!(
+            poll_until_received(&mut receiver),
+            vec![message("localhost:test")]
+        );
+
+        writer.join().expect("writer shouldn't panic");
+        receiver.close("localhost:test");
+        let _ = std::fs::remove_dir(directory);
+    }
+}
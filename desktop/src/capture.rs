@@ -0,0 +1,512 @@
+use crate::preferences::GlobalPreferences;
+use anyhow::{Context, Error, anyhow};
+use chrono::Local;
+use egui::{Key, KeyboardShortcut, Modifiers};
+use image::imageops::FilterType;
+use image::{ImageFormat, RgbaImage};
+use ruffle_core::Player;
+use std::fmt;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Cursor, Read, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::mpsc::{Receiver, SyncSender, TrySendError, sync_channel};
+use std::time::{Duration, Instant};
+
+/// How many captured frames may wait for the encoder before new ones are dropped.
+const RECORDING_QUEUE_SIZE: usize = 8;
+
+#[derive(Copy, Clone, Hash, Eq, PartialEq, Debug, Default)]
+pub enum RecordingFormat {
+    #[default]
+    Apng,
+    Gif,
+    PngSequence,
+}
+
+impl FromStr for RecordingFormat {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "apng" => Ok(RecordingFormat::Apng),
+            "gif" => Ok(RecordingFormat::Gif),
+            "png_sequence" => Ok(RecordingFormat::PngSequence),
+            _ => Err(()),
+        }
+    }
+}
+
+impl RecordingFormat {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RecordingFormat::Apng => "apng",
+            RecordingFormat::Gif => "gif",
+            RecordingFormat::PngSequence => "png_sequence",
+        }
+    }
+}
+
+/// A user-configurable keyboard shortcut, written like `Ctrl+Shift+F12`.
+///
+/// `Ctrl` stands for the platform's command key, so it means `Cmd` on macOS.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct Hotkey(pub KeyboardShortcut);
+
+impl Hotkey {
+    pub const fn new(modifiers: Modifiers, key: Key) -> Self {
+        Self(KeyboardShortcut::new(modifiers, key))
+    }
+}
+
+impl FromStr for Hotkey {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split('+').map(str::trim);
+        let key = parts
+            .next_back()
+            .and_then(|key| Key::from_name(key).or_else(|| Key::from_name(&key.to_uppercase())))
+            .ok_or(())?;
+
+        let mut modifiers = Modifiers::NONE;
+        for part in parts {
+            modifiers = modifiers.plus(match part.to_ascii_lowercase().as_str() {
+                "ctrl" | "cmd" | "command" => Modifiers::COMMAND,
+                "alt" | "option" => Modifiers::ALT,
+                "shift" => Modifiers::SHIFT,
+                _ => return Err(()),
+            });
+        }
+
+        Ok(Self::new(modifiers, key))
+    }
+}
+
+impl fmt::Display for Hotkey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let KeyboardShortcut {
+            modifiers,
+            logical_key,
+        } = self.0;
+        if modifiers.command || modifiers.ctrl || modifiers.mac_cmd {
+            write!(f, "Ctrl+")?;
+        }
+        if modifiers.alt {
+            write!(f, "Alt+")?;
+        }
+        if modifiers.shift {
+            write!(f, "Shift+")?;
+        }
+        write!(f, "{}", logical_key.name())
+    }
+}
+
+/// Takes screenshots and records clips of the running movie.
+///
+/// Requests made through the menus or hotkeys are carried out in [`Capture::update`],
+/// which is called once for every frame rendered by the player.
+pub struct Capture {
+    preferences: GlobalPreferences,
+    screenshot_requested: bool,
+    recording: Option<Recording>,
+}
+
+struct Recording {
+    sender: SyncSender<(RgbaImage, Instant)>,
+    native_size: bool,
+}
+
+impl Capture {
+    pub fn new(preferences: GlobalPreferences) -> Self {
+        Self {
+            preferences,
+            screenshot_requested: false,
+            recording: None,
+        }
+    }
+
+    pub fn request_screenshot(&mut self) {
+        self.screenshot_requested = true;
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recording.is_some()
+    }
+
+    pub fn toggle_recording(&mut self) {
+        if self.is_recording() {
+            self.stop_recording();
+        } else {
+            self.start_recording();
+        }
+    }
+
+    pub fn start_recording(&mut self) {
+        let format = self.preferences.recording_format();
+        let path = self
+            .preferences
+            .capture_directory()
+            .join(file_name(match format {
+                RecordingFormat::Apng => "png",
+                RecordingFormat::Gif => "gif",
+                RecordingFormat::PngSequence => "",
+            }));
+
+        let (sender, receiver) = sync_channel(RECORDING_QUEUE_SIZE);
+        std::thread::spawn(move || match encode_recording(format, &path, receiver) {
+            Ok(()) => tracing::info!("Saved recording to {}", path.display()),
+            Err(e) => tracing::error!("Couldn't save recording to {}: {e:#}", path.display()),
+        });
+
+        self.recording = Some(Recording {
+            sender,
+            native_size: self.preferences.capture_native_size(),
+        });
+    }
+
+    /// Stops the current recording, if any.
+    /// The clip is finished writing in the background.
+    pub fn stop_recording(&mut self) {
+        self.recording = None;
+    }
+
+    pub fn update(&mut self, player: &mut Player) {
+        if std::mem::take(&mut self.screenshot_requested) {
+            self.save_screenshot(player);
+        }
+
+        if let Some(recording) = &self.recording
+            && let Some(image) = capture_image(player, recording.native_size)
+        {
+            match recording.sender.try_send((image, Instant::now())) {
+                Ok(()) => {}
+                Err(TrySendError::Full(_)) => {
+                    // The previous frame is shown for longer instead.
+                    tracing::debug!("Recording can't keep up, dropping a frame");
+                }
+                Err(TrySendError::Disconnected(_)) => {
+                    // The encoder gave up, and has already reported why.
+                    self.recording = None;
+                }
+            }
+        }
+    }
+
+    fn save_screenshot(&self, player: &mut Player) {
+        let Some(image) = capture_image(player, self.preferences.capture_native_size()) else {
+            tracing::warn!("Couldn't take a screenshot: the renderer doesn't support it");
+            return;
+        };
+
+        let path = self.preferences.capture_directory().join(file_name("png"));
+        tokio::task::spawn_blocking(move || {
+            let result = create_parent(&path).and_then(|()| {
+                image
+                    .save_with_format(&path, ImageFormat::Png)
+                    .context("Couldn't encode image")
+            });
+            match result {
+                Ok(()) => tracing::info!("Saved screenshot to {}", path.display()),
+                Err(e) => tracing::error!("Couldn't save screenshot to {}: {e:#}", path.display()),
+            }
+        });
+    }
+}
+
+fn capture_image(player: &mut Player, native_size: bool) -> Option<RgbaImage> {
+    let bitmap = player.capture_frame(native_size)?;
+    let mut data = bitmap.data().to_vec();
+    ruffle_render::utils::unmultiply_alpha_rgba(&mut data);
+    RgbaImage::from_raw(bitmap.width(), bitmap.height(), data)
+}
+
+/// A timestamped file name, with the given extension if it's not empty.
+fn file_name(extension: &str) -> String {
+    let mut name = Local::now().format("ruffle_%F_%H-%M-%S-%3f").to_string();
+    if !extension.is_empty() {
+        name.push('.');
+        name.push_str(extension);
+    }
+    name
+}
+
+fn create_parent(path: &Path) -> Result<(), Error> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Couldn't create directory {}", parent.display()))?;
+    }
+    Ok(())
+}
+
+/// Writes frames as they arrive from the player, until the recording is stopped.
+///
+/// Every frame is written along with how long it was on screen, so the clip
+/// plays back in real time regardless of how often the player rendered.
+fn encode_recording(
+    format: RecordingFormat,
+    path: &Path,
+    receiver: Receiver<(RgbaImage, Instant)>,
+) -> Result<(), Error> {
+    let Ok((mut pending, mut shown_at)) = receiver.recv() else {
+        return Err(anyhow!("No frames were recorded"));
+    };
+    // The clip keeps the size of its first frame, even if the window is resized.
+    let size = pending.dimensions();
+
+    let mut writer: Box<dyn FrameWriter> = match format {
+        RecordingFormat::Apng => Box::new(ApngWriter::new(path, size)?),
+        RecordingFormat::Gif => Box::new(GifWriter::new(path, size)?),
+        RecordingFormat::PngSequence => Box::new(PngSequenceWriter::new(path)?),
+    };
+
+    while let Ok((frame, next_shown_at)) = receiver.recv() {
+        let frame = fit_frame(frame, size);
+        writer.write_frame(pending, next_shown_at - shown_at)?;
+        pending = frame;
+        shown_at = next_shown_at;
+    }
+    writer.write_frame(pending, shown_at.elapsed())?;
+
+    writer.finish()
+}
+
+/// Scales a frame down or up to `size`, keeping its aspect ratio and
+/// leaving the rest transparent.
+fn fit_frame(frame: RgbaImage, (width, height): (u32, u32)) -> RgbaImage {
+    if frame.dimensions() == (width, height) {
+        return frame;
+    }
+
+    let scale = f64::min(
+        width as f64 / frame.width() as f64,
+        height as f64 / frame.height() as f64,
+    );
+    let scaled_width = ((frame.width() as f64 * scale).round() as u32).clamp(1, width);
+    let scaled_height = ((frame.height() as f64 * scale).round() as u32).clamp(1, height);
+    let scaled = image::imageops::resize(&frame, scaled_width, scaled_height, FilterType::Triangle);
+
+    let mut fitted = RgbaImage::new(width, height);
+    image::imageops::replace(
+        &mut fitted,
+        &scaled,
+        ((width - scaled_width) / 2).into(),
+        ((height - scaled_height) / 2).into(),
+    );
+    fitted
+}
+
+trait FrameWriter {
+    /// Writes a frame that stays on screen for the given duration.
+    fn write_frame(&mut self, frame: RgbaImage, duration: Duration) -> Result<(), Error>;
+
+    fn finish(self: Box<Self>) -> Result<(), Error>;
+}
+
+struct PngSequenceWriter {
+    directory: PathBuf,
+    next_index: usize,
+}
+
+impl PngSequenceWriter {
+    fn new(directory: &Path) -> Result<Self, Error> {
+        std::fs::create_dir_all(directory)
+            .with_context(|| format!("Couldn't create directory {}", directory.display()))?;
+        Ok(Self {
+            directory: directory.to_owned(),
+            next_index: 1,
+        })
+    }
+}
+
+impl FrameWriter for PngSequenceWriter {
+    fn write_frame(&mut self, frame: RgbaImage, _duration: Duration) -> Result<(), Error> {
+        let path = self
+            .directory
+            .join(format!("frame_{:05}.png", self.next_index));
+        frame
+            .save_with_format(&path, ImageFormat::Png)
+            .with_context(|| format!("Couldn't write {}", path.display()))?;
+        self.next_index += 1;
+        Ok(())
+    }
+
+    fn finish(self: Box<Self>) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+struct GifWriter {
+    encoder: gif::Encoder<BufWriter<File>>,
+}
+
+impl GifWriter {
+    fn new(path: &Path, (width, height): (u32, u32)) -> Result<Self, Error> {
+        let width = u16::try_from(width).context("Frame is too wide for a GIF")?;
+        let height = u16::try_from(height).context("Frame is too tall for a GIF")?;
+        create_parent(path)?;
+        let file = BufWriter::new(File::create(path)?);
+        let mut encoder = gif::Encoder::new(file, width, height, &[])?;
+        encoder.set_repeat(gif::Repeat::Infinite)?;
+        Ok(Self { encoder })
+    }
+}
+
+impl FrameWriter for GifWriter {
+    fn write_frame(&mut self, frame: RgbaImage, duration: Duration) -> Result<(), Error> {
+        let (width, height) = frame.dimensions();
+        let mut pixels = frame.into_raw();
+        let mut frame = gif::Frame::from_rgba_speed(width as u16, height as u16, &mut pixels, 10);
+        // GIF delays are in hundredths of a second, and most viewers
+        // don't respect delays shorter than two of them.
+        frame.delay = (duration.as_millis() / 10).clamp(2, u16::MAX as u128) as u16;
+        self.encoder.write_frame(&frame)?;
+        Ok(())
+    }
+
+    fn finish(self: Box<Self>) -> Result<(), Error> {
+        self.encoder.into_inner()?.flush()?;
+        Ok(())
+    }
+}
+
+/// APNG needs the frame count up front, so frames are spooled to a file next
+/// to the clip (compressed as individual PNGs) until the recording is
+/// finished. Only their lengths and durations are kept in memory.
+struct ApngWriter {
+    path: PathBuf,
+    spool_path: PathBuf,
+    spool: BufWriter<File>,
+    frames: Vec<(usize, Duration)>,
+    size: (u32, u32),
+}
+
+impl ApngWriter {
+    fn new(path: &Path, size: (u32, u32)) -> Result<Self, Error> {
+        create_parent(path)?;
+        let spool_path = path.with_extension("png.part");
+        let spool = File::create(&spool_path)
+            .with_context(|| format!("Couldn't create {}", spool_path.display()))?;
+        Ok(Self {
+            path: path.to_owned(),
+            spool_path,
+            spool: BufWriter::new(spool),
+            frames: vec![],
+            size,
+        })
+    }
+
+    fn write_apng(&mut self) -> Result<(), Error> {
+        self.spool.flush()?;
+        let mut spool = BufReader::new(File::open(&self.spool_path)?);
+
+        let (width, height) = self.size;
+        let file = BufWriter::new(File::create(&self.path)?);
+        let mut encoder = png::Encoder::new(file, width, height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.set_animated(self.frames.len() as u32, 0)?;
+
+        let mut writer = encoder.write_header()?;
+        let mut png = vec![];
+        for &(len, duration) in &self.frames {
+            png.resize(len, 0);
+            spool.read_exact(&mut png)?;
+            let frame = image::load_from_memory_with_format(&png, ImageFormat::Png)?.into_rgba8();
+            writer.set_frame_delay(duration.as_millis().min(u16::MAX as u128) as u16, 1000)?;
+            writer.write_image_data(frame.as_raw())?;
+        }
+        writer.finish()?;
+        Ok(())
+    }
+}
+
+impl FrameWriter for ApngWriter {
+    fn write_frame(&mut self, frame: RgbaImage, duration: Duration) -> Result<(), Error> {
+        let mut png = Cursor::new(vec![]);
+        fit_frame(frame, self.size).write_to(&mut png, ImageFormat::Png)?;
+        let png = png.into_inner();
+        self.spool
+            .write_all(&png)
+            .with_context(|| format!("Couldn't write {}", self.spool_path.display()))?;
+        self.frames.push((png.len(), duration));
+        Ok(())
+    }
+
+    fn finish(mut self: Box<Self>) -> Result<(), Error> {
+        self.write_apng()
+    }
+}
+
+impl Drop for ApngWriter {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.spool_path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_hotkey() {
+        assert_eq!(
+            Ok(Hotkey::new(Modifiers::NONE, Key::F12)),
+            "F12".parse::<Hotkey>()
+        );
+        assert_eq!(
+            Ok(Hotkey::new(
+                Modifiers::COMMAND.plus(Modifiers::SHIFT),
+                Key::S
+            )),
+            "Ctrl + Shift + S".parse::<Hotkey>()
+        );
+        assert_eq!(Err(()), "Hyper+S".parse::<Hotkey>());
+        assert_eq!(Err(()), "Ctrl+".parse::<Hotkey>());
+    }
+
+    #[test]
+    fn fit_frames() {
+        let frame = RgbaImage::from_pixel(40, 10, image::Rgba([255, 0, 0, 255]));
+        assert_eq!(fit_frame(frame.clone(), (40, 10)), frame);
+
+        // Letterboxed to keep the aspect ratio.
+        let fitted = fit_frame(frame, (20, 20));
+        assert_eq!(fitted.dimensions(), (20, 20));
+        assert_eq!(fitted.get_pixel(10, 0).0[3], 0);
+        assert_eq!(fitted.get_pixel(10, 10).0, [255, 0, 0, 255]);
+        assert_eq!(fitted.get_pixel(10, 19).0[3], 0);
+    }
+
+    #[test]
+    fn write_apng() {
+        let directory = std::env::temp_dir().join(format!("ruffle_capture_{}", std::process::id()));
+        let path = directory.join("clip.png");
+
+        let mut writer = Box::new(ApngWriter::new(&path, (8, 8)).expect("writer"));
+        for (i, size) in [(8, 8), (16, 16), (4, 8)].into_iter().enumerate() {
+            let frame = RgbaImage::from_pixel(size.0, size.1, image::Rgba([i as u8, 0, 0, 255]));
+            writer
+                .write_frame(frame, Duration::from_millis(100))
+                .expect("frame");
+        }
+        assert!(path.with_extension("png.part").exists());
+        writer.finish().expect("finished clip");
+        assert!(!path.with_extension("png.part").exists());
+
+        let decoder = png::Decoder::new(BufReader::new(File::open(&path).expect("clip")));
+        let reader = decoder.read_info().expect("valid PNG");
+        let info = reader.info();
+        assert_eq!((info.width, info.height), (8, 8));
+        assert_eq!(info.animation_control.map(|a| a.num_frames), Some(3));
+
+        std::fs::remove_dir_all(directory).expect("cleaned up");
+    }
+
+    #[test]
+    fn format_hotkey() {
+        let hotkey = Hotkey::new(Modifiers::COMMAND.plus(Modifiers::ALT), Key::F9);
+        assert_eq!("Ctrl+Alt+F9", hotkey.to_string());
+        assert_eq!(Ok(hotkey), hotkey.to_string().parse::<Hotkey>());
+    }
+}
@@ -8,7 +8,11 @@ use ruffle_core::events::{GamepadButton, KeyCode};
 use ruffle_core::{LoadBehavior, PlayerRuntime, StageAlign, StageScaleMode};
 use ruffle_render::quality::StageQuality;
 use ruffle_render_wgpu::clap::{GraphicsBackend, PowerPreference};
-use std::path::Path;
+use std::fmt;
+use std::fs::File;
+use std::io::Write;
+use std::net::TcpStream;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::time::Duration;
 use url::Url;
@@ -243,6 +247,28 @@ pub struct Opt {
     /// (like inlining constant pool entries) can't be disabled.
     #[clap(long)]
     pub no_avm2_optimizer: bool,
+
+    /// Listen for Debug Adapter Protocol clients on this local port.
+    ///
+    /// Line breakpoints require movies compiled with debugging information.
+    /// Function breakpoints use the names shown in stack traces, optionally
+    /// followed by a byte offset into the method body, e.g. `Main/update+0x1a`.
+    /// AVM1 frame scripts can be given as `clip path@frame`, e.g. `_level0@1`,
+    /// and instruction breakpoints refer to offsets of AVM1 actions.
+    #[cfg(feature = "debugger")]
+    #[clap(long)]
+    pub dap_port: Option<u16>,
+
+    /// Send telemetry that can be analyzed with Adobe Scout or compatible tools.
+    ///
+    /// This is either the `host:port` address of a listening tool (Scout listens
+    /// on `localhost:7934` by default), or a path of a file to write the telemetry to.
+    #[clap(
+        long,
+        value_parser(parse_telemetry_target),
+        value_name = "ADDRESS OR PATH"
+    )]
+    pub telemetry: Option<TelemetryTarget>,
 }
 
 fn parse_movie_file_or_url(path: &str) -> Result<Url, Error> {
@@ -295,6 +321,51 @@ fn parse_gamepad_button(mapping: &str) -> Result<(GamepadButton, KeyCode), Error
     Ok((button, KeyCode::from_code(key_code as u32)))
 }
 
+fn parse_telemetry_target(value: &str) -> Result<TelemetryTarget, Error> {
+    if value.is_empty() {
+        return Err(anyhow!("Telemetry target must not be empty"));
+    }
+
+    // Anything that ends with a port number and isn't a path is an address.
+    let is_address = value.rsplit_once(':').is_some_and(|(host, port)| {
+        !host.is_empty() && !host.contains(['/', '\\']) && port.parse::<u16>().is_ok()
+    });
+
+    Ok(if is_address {
+        TelemetryTarget::Address(value.to_string())
+    } else {
+        TelemetryTarget::File(value.into())
+    })
+}
+
+/// Where telemetry is sent to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TelemetryTarget {
+    /// A `host:port` address to connect to.
+    Address(String),
+
+    /// A file to write to.
+    File(PathBuf),
+}
+
+impl TelemetryTarget {
+    pub fn open(&self) -> std::io::Result<Box<dyn Write>> {
+        Ok(match self {
+            TelemetryTarget::Address(address) => Box::new(TcpStream::connect(address)?),
+            TelemetryTarget::File(path) => Box::new(File::create(path)?),
+        })
+    }
+}
+
+impl fmt::Display for TelemetryTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TelemetryTarget::Address(address) => write!(f, "{address}"),
+            TelemetryTarget::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
 impl Opt {
     pub fn parameters(&self) -> impl '_ + Iterator<Item = (String, String)> {
         self.parameters.iter().map(|parameter| {
@@ -501,3 +572,39 @@ pub enum FilesystemAccessMode {
     /// Ask the user before accessing the filesystem non-interactively.
     Ask,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_telemetry_target() {
+        for (value, expected) in [
+            (
+                "localhost:7934",
+                TelemetryTarget::Address("localhost:7934".to_string()),
+            ),
+            (
+                "127.0.0.1:7934",
+                TelemetryTarget::Address("127.0.0.1:7934".to_string()),
+            ),
+            ("capture.flm", TelemetryTarget::File("capture.flm".into())),
+            (
+                "C:\\captures\\capture.flm",
+                TelemetryTarget::File("C:\\captures\\capture.flm".into()),
+            ),
+            (
+                "./captures:1234",
+                TelemetryTarget::File("./captures:1234".into()),
+            ),
+        ] {
+            assert_eq!(
+                parse_telemetry_target(value).expect("valid target"),
+                expected,
+                "parsing {value}"
+            );
+        }
+
+        assert!(parse_telemetry_target("").is_err());
+    }
+}
@@ -46,6 +46,9 @@ pub enum RuffleEvent {
     /// Ruffle core has a notification to handle.
     PlayerNotification(PlayerNotification),
 
+    /// The gamepad profiles were edited, or another one was made active.
+    GamepadProfilesChanged,
+
     /// Export Ruffle Bundle from currently playing content and open save dialog.
     ExportBundle,
 }
@@ -0,0 +1,640 @@
+//! A Debug Adapter Protocol server for the script debugger.
+//!
+//! Only one client can be connected at a time. The server outlives individual
+//! players: whenever a new movie is opened, its player is attached to the same
+//! server and the breakpoints set by the client are carried over.
+
+use anyhow::{Context, Error};
+use ruffle_core::debugger::{
+    Breakpoint, ChannelDebuggerConnection, DebuggerCommand, DebuggerEvent, StopReason, parse_offset,
+};
+use serde_json::{Value, json};
+use std::collections::{HashMap, VecDeque};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{Ipv4Addr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, AtomicI64, Ordering};
+use std::sync::mpsc::{Receiver, RecvTimeoutError, Sender, channel};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::thread;
+use std::time::Duration;
+
+/// ActionScript runs on a single thread, so that's all we report.
+const THREAD_ID: i64 = 1;
+
+/// The key of instruction breakpoints, which don't belong to a source.
+const INSTRUCTION_BREAKPOINTS: &str = "<instructions>";
+
+/// The largest message accepted from a client, far above anything the protocol needs.
+const MAX_MESSAGE_BYTES: usize = 16 * 1024 * 1024;
+
+static SERVER: OnceLock<DapServer> = OnceLock::new();
+
+struct DapServer {
+    /// Commands for the current player, if there is one.
+    commands: Mutex<Option<Sender<DebuggerCommand>>>,
+
+    /// Events from all players, which are forwarded to the current client.
+    events: Mutex<Receiver<DebuggerEvent>>,
+    events_sender: Sender<DebuggerEvent>,
+
+    /// Breakpoints set by the client, by source path.
+    /// Function breakpoints are stored under `None`.
+    breakpoints: Mutex<HashMap<Option<String>, Vec<ClientBreakpoint>>>,
+    next_breakpoint_id: AtomicI64,
+}
+
+/// A breakpoint set by the client.
+struct ClientBreakpoint {
+    id: i64,
+    breakpoint: Breakpoint,
+
+    /// Whether the breakpoint was hit, showing that it refers to existing
+    /// code. Until then, we can't tell whether it will ever be hit.
+    verified: bool,
+}
+
+/// Starts listening for debugger clients on the given local port.
+pub fn start(port: u16) -> Result<(), Error> {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))
+        .with_context(|| format!("Couldn't listen for debugger clients on port {port}"))?;
+
+    let (events_sender, events) = channel();
+    let server = DapServer {
+        commands: Mutex::new(None),
+        events: Mutex::new(events),
+        events_sender,
+        breakpoints: Mutex::new(HashMap::new()),
+        next_breakpoint_id: AtomicI64::new(1),
+    };
+    if SERVER.set(server).is_err() {
+        anyhow::bail!("The debugger server is already running");
+    }
+
+    thread::Builder::new()
+        .name("DAP server".to_string())
+        .spawn(move || {
+            let Some(server) = SERVER.get() else {
+                return;
+            };
+            for stream in listener.incoming() {
+                let result = stream
+                    .map_err(Error::from)
+                    .and_then(|stream| server.run_session(stream));
+                if let Err(e) = result {
+                    tracing::warn!("Debugger session failed: {e}");
+                }
+            }
+        })?;
+
+    tracing::info!("Listening for debugger clients on 127.0.0.1:{port}");
+    Ok(())
+}
+
+/// Creates the debugger connection of a new player, if the server is running.
+///
+/// The previous player, if any, is detached.
+pub fn connect_player() -> Option<ChannelDebuggerConnection> {
+    let server = SERVER.get()?;
+    let (commands_sender, commands) = channel();
+    let _ = commands_sender.send(DebuggerCommand::SetBreakpoints(server.all_breakpoints()));
+    *server.commands.lock().expect("Commands lock") = Some(commands_sender);
+    Some(ChannelDebuggerConnection::new(
+        commands,
+        server.events_sender.clone(),
+    ))
+}
+
+impl DapServer {
+    fn all_breakpoints(&self) -> Vec<Breakpoint> {
+        let breakpoints = self.breakpoints.lock().expect("Breakpoints lock");
+        breakpoints
+            .values()
+            .flatten()
+            .map(|breakpoint| breakpoint.breakpoint.clone())
+            .collect()
+    }
+
+    /// Sends a command to the current player, returning whether there is one.
+    fn send_command(&self, command: DebuggerCommand) -> bool {
+        let mut commands = self.commands.lock().expect("Commands lock");
+        let sent = commands
+            .as_ref()
+            .is_some_and(|commands| commands.send(command).is_ok());
+        if !sent {
+            *commands = None;
+        }
+        sent
+    }
+
+    /// Replaces the breakpoints of a source, returning them in the same
+    /// order in the format of a `setBreakpoints` response.
+    fn set_breakpoints(&self, source: Option<String>, breakpoints: Vec<Breakpoint>) -> Vec<Value> {
+        let mut all_breakpoints = self.breakpoints.lock().expect("Breakpoints lock");
+        let previous = all_breakpoints.remove(&source).unwrap_or_default();
+        let breakpoints: Vec<ClientBreakpoint> = breakpoints
+            .into_iter()
+            .map(|breakpoint| {
+                // Keep the identity of breakpoints that didn't change.
+                match previous.iter().find(|old| old.breakpoint == breakpoint) {
+                    Some(old) => ClientBreakpoint {
+                        id: old.id,
+                        breakpoint,
+                        verified: old.verified,
+                    },
+                    None => ClientBreakpoint {
+                        id: self.next_breakpoint_id.fetch_add(1, Ordering::Relaxed),
+                        breakpoint,
+                        verified: false,
+                    },
+                }
+            })
+            .collect();
+        let response = breakpoints.iter().map(ClientBreakpoint::to_json).collect();
+        all_breakpoints.insert(source, breakpoints);
+        drop(all_breakpoints);
+
+        self.send_command(DebuggerCommand::SetBreakpoints(self.all_breakpoints()));
+        response
+    }
+
+    /// Marks a breakpoint as verified, returning it if it wasn't already.
+    fn verify_breakpoint(&self, hit: &Breakpoint) -> Option<Value> {
+        let mut breakpoints = self.breakpoints.lock().expect("Breakpoints lock");
+        let breakpoint = breakpoints
+            .values_mut()
+            .flatten()
+            .find(|breakpoint| breakpoint.breakpoint == *hit && !breakpoint.verified)?;
+        breakpoint.verified = true;
+        Some(breakpoint.to_json())
+    }
+
+    fn run_session(&'static self, stream: TcpStream) -> Result<(), Error> {
+        tracing::info!("Debugger client connected");
+
+        let session = Arc::new(Session {
+            writer: Mutex::new(stream.try_clone()?),
+            seq: AtomicI64::new(1),
+            pending: Mutex::new(VecDeque::new()),
+        });
+
+        let closed = Arc::new(AtomicBool::new(false));
+        let event_pump = {
+            let session = session.clone();
+            let closed = closed.clone();
+            thread::Builder::new()
+                .name("DAP events".to_string())
+                .spawn(move || -> io::Result<()> {
+                    let events = self.events.lock().expect("Events lock");
+                    while !closed.load(Ordering::Relaxed) {
+                        match events.recv_timeout(Duration::from_millis(100)) {
+                            Ok(DebuggerEvent::BreakpointHit(breakpoint)) => {
+                                if let Some(breakpoint) = self.verify_breakpoint(&breakpoint) {
+                                    session.event(
+                                        "breakpoint",
+                                        json!({ "reason": "changed", "breakpoint": breakpoint }),
+                                    )?;
+                                }
+                            }
+                            Ok(event) => session.forward_event(event)?,
+                            Err(RecvTimeoutError::Timeout) => continue,
+                            Err(RecvTimeoutError::Disconnected) => break,
+                        }
+                    }
+                    Ok(())
+                })?
+        };
+
+        let result = self.handle_requests(&session, stream);
+
+        closed.store(true, Ordering::Relaxed);
+        let _ = event_pump.join();
+
+        // Let the movie run freely once the client is gone.
+        self.breakpoints.lock().expect("Breakpoints lock").clear();
+        self.send_command(DebuggerCommand::SetBreakpoints(vec![]));
+        self.send_command(DebuggerCommand::Continue);
+
+        tracing::info!("Debugger client disconnected");
+        result
+    }
+
+    fn handle_requests(&self, session: &Session, stream: TcpStream) -> Result<(), Error> {
+        let mut reader = BufReader::new(stream);
+        while let Some(request) = read_message(&mut reader)? {
+            let seq = request["seq"].as_i64().unwrap_or_default();
+            let command = request["command"].as_str().unwrap_or_default();
+            let arguments = &request["arguments"];
+
+            match command {
+                "initialize" => {
+                    session.respond(
+                        seq,
+                        command,
+                        json!({
+                            "supportsConfigurationDoneRequest": true,
+                            "supportsFunctionBreakpoints": true,
+                            "supportsInstructionBreakpoints": true,
+                        }),
+                    )?;
+                    session.event("initialized", json!({}))?;
+                }
+                "launch" | "attach" | "configurationDone" => {
+                    session.respond(seq, command, json!({}))?;
+                }
+                "threads" => {
+                    session.respond(
+                        seq,
+                        command,
+                        json!({ "threads": [{ "id": THREAD_ID, "name": "ActionScript" }] }),
+                    )?;
+                }
+                "setBreakpoints" => {
+                    let path = arguments["source"]["path"]
+                        .as_str()
+                        .unwrap_or_default()
+                        .to_string();
+                    let lines: Vec<u32> = arguments["breakpoints"]
+                        .as_array()
+                        .into_iter()
+                        .flatten()
+                        .filter_map(|breakpoint| breakpoint["line"].as_u64())
+                        .map(|line| line as u32)
+                        .collect();
+
+                    let breakpoints = lines
+                        .iter()
+                        .map(|line| Breakpoint::Line {
+                            file: path.clone(),
+                            line: *line,
+                        })
+                        .collect();
+                    let breakpoints = self.set_breakpoints(Some(path), breakpoints);
+                    session.respond(seq, command, json!({ "breakpoints": breakpoints }))?;
+                }
+                "setFunctionBreakpoints" => {
+                    let breakpoints: Vec<Breakpoint> = arguments["breakpoints"]
+                        .as_array()
+                        .into_iter()
+                        .flatten()
+                        .filter_map(|breakpoint| breakpoint["name"].as_str())
+                        .map(parse_function_breakpoint)
+                        .collect();
+
+                    let breakpoints = self.set_breakpoints(None, breakpoints);
+                    session.respond(seq, command, json!({ "breakpoints": breakpoints }))?;
+                }
+                "setInstructionBreakpoints" => {
+                    // Instruction references are offsets of AVM1 actions.
+                    let breakpoints: Vec<Option<Breakpoint>> = arguments["breakpoints"]
+                        .as_array()
+                        .into_iter()
+                        .flatten()
+                        .map(|breakpoint| {
+                            let offset = breakpoint["instructionReference"].as_str()?;
+                            Some(Breakpoint::Action {
+                                offset: parse_offset(offset)?,
+                            })
+                        })
+                        .collect();
+
+                    let mut set = self
+                        .set_breakpoints(
+                            Some(INSTRUCTION_BREAKPOINTS.to_string()),
+                            breakpoints.iter().flatten().cloned().collect(),
+                        )
+                        .into_iter();
+                    let breakpoints: Vec<Value> = breakpoints
+                        .iter()
+                        .map(|breakpoint| match breakpoint {
+                            Some(_) => set.next().unwrap_or_default(),
+                            None => json!({
+                                "verified": false,
+                                "message": "Invalid instruction reference",
+                            }),
+                        })
+                        .collect();
+                    session.respond(seq, command, json!({ "breakpoints": breakpoints }))?;
+                }
+                "continue" => {
+                    self.send_command(DebuggerCommand::Continue);
+                    session.respond(seq, command, json!({ "allThreadsContinued": true }))?;
+                }
+                "next" | "stepIn" | "stepOut" | "pause" => {
+                    self.send_command(match command {
+                        "next" => DebuggerCommand::StepOver,
+                        "stepIn" => DebuggerCommand::StepIn,
+                        "stepOut" => DebuggerCommand::StepOut,
+                        _ => DebuggerCommand::Pause,
+                    });
+                    session.respond(seq, command, json!({}))?;
+                }
+                "stackTrace" | "scopes" | "variables" => {
+                    let player_command = match command {
+                        "stackTrace" => DebuggerCommand::StackTrace,
+                        "scopes" => DebuggerCommand::Scopes {
+                            frame: arguments["frameId"].as_u64().unwrap_or_default() as usize,
+                        },
+                        _ => DebuggerCommand::Variables {
+                            reference: arguments["variablesReference"].as_u64().unwrap_or_default()
+                                as u32,
+                        },
+                    };
+
+                    // The player answers these in order, and the response is
+                    // sent once its answer arrives.
+                    session
+                        .pending
+                        .lock()
+                        .expect("Pending lock")
+                        .push_back((seq, command.to_string()));
+                    if !self.send_command(player_command) {
+                        session.pending.lock().expect("Pending lock").pop_back();
+                        session.respond(seq, command, empty_body(command))?;
+                    }
+                }
+                "disconnect" => {
+                    session.respond(seq, command, json!({}))?;
+                    break;
+                }
+                _ => {
+                    session.send(json!({
+                        "type": "response",
+                        "request_seq": seq,
+                        "success": false,
+                        "command": command,
+                        "message": format!("Unsupported request: {command}"),
+                    }))?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+struct Session {
+    writer: Mutex<TcpStream>,
+    seq: AtomicI64,
+
+    /// Requests waiting for an answer from the player, oldest first.
+    pending: Mutex<VecDeque<(i64, String)>>,
+}
+
+impl Session {
+    fn send(&self, mut message: Value) -> io::Result<()> {
+        message["seq"] = json!(self.seq.fetch_add(1, Ordering::Relaxed));
+        let body = message.to_string();
+
+        let mut writer = self.writer.lock().expect("Writer lock");
+        write!(writer, "Content-Length: {}\r\n\r\n{body}", body.len())?;
+        writer.flush()
+    }
+
+    fn respond(&self, request_seq: i64, command: &str, body: Value) -> io::Result<()> {
+        self.send(json!({
+            "type": "response",
+            "request_seq": request_seq,
+            "success": true,
+            "command": command,
+            "body": body,
+        }))
+    }
+
+    fn event(&self, event: &str, body: Value) -> io::Result<()> {
+        self.send(json!({
+            "type": "event",
+            "event": event,
+            "body": body,
+        }))
+    }
+
+    fn forward_event(&self, event: DebuggerEvent) -> io::Result<()> {
+        let body = match event {
+            DebuggerEvent::Stopped { reason } => {
+                let reason = match reason {
+                    StopReason::Breakpoint => "breakpoint",
+                    StopReason::Step => "step",
+                    StopReason::Pause => "pause",
+                };
+                return self.event(
+                    "stopped",
+                    json!({
+                        "reason": reason,
+                        "threadId": THREAD_ID,
+                        "allThreadsStopped": true,
+                    }),
+                );
+            }
+            // Handled by the server, as it knows which breakpoints were set.
+            DebuggerEvent::BreakpointHit(_) => return Ok(()),
+            DebuggerEvent::Continued => {
+                return self.event(
+                    "continued",
+                    json!({ "threadId": THREAD_ID, "allThreadsContinued": true }),
+                );
+            }
+            DebuggerEvent::StackTrace(frames) => {
+                let frames: Vec<Value> = frames
+                    .iter()
+                    .enumerate()
+                    .map(|(id, frame)| {
+                        let mut value = json!({
+                            "id": id,
+                            "name": frame.name,
+                            "line": frame.line,
+                            "column": 0,
+                        });
+                        if let Some(file) = &frame.file {
+                            let path = debug_file_path(file);
+                            let name = path.rsplit(['/', '\\']).next().unwrap_or_default();
+                            value["source"] = json!({ "name": name, "path": path });
+                        }
+                        value
+                    })
+                    .collect();
+                json!({ "stackFrames": frames, "totalFrames": frames.len() })
+            }
+            DebuggerEvent::Scopes(scopes) => {
+                let scopes: Vec<Value> = scopes
+                    .iter()
+                    .map(|scope| {
+                        json!({
+                            "name": scope.name,
+                            "variablesReference": scope.reference,
+                            "expensive": false,
+                        })
+                    })
+                    .collect();
+                json!({ "scopes": scopes })
+            }
+            DebuggerEvent::Variables(variables) => {
+                let variables: Vec<Value> = variables
+                    .iter()
+                    .map(|variable| {
+                        json!({
+                            "name": variable.name,
+                            "value": variable.value,
+                            "type": variable.type_name,
+                            "variablesReference": variable.reference,
+                        })
+                    })
+                    .collect();
+                json!({ "variables": variables })
+            }
+        };
+
+        let pending = self.pending.lock().expect("Pending lock").pop_front();
+        match pending {
+            Some((request_seq, command)) => self.respond(request_seq, &command, body),
+            None => Ok(()),
+        }
+    }
+}
+
+impl ClientBreakpoint {
+    fn to_json(&self) -> Value {
+        let mut value = json!({ "id": self.id, "verified": self.verified });
+        if let Breakpoint::Line { line, .. } = self.breakpoint {
+            value["line"] = json!(line);
+        }
+        if !self.verified {
+            value["message"] = json!("Not reached yet");
+        }
+        value
+    }
+}
+
+fn empty_body(command: &str) -> Value {
+    match command {
+        "stackTrace" => json!({ "stackFrames": [], "totalFrames": 0 }),
+        "scopes" => json!({ "scopes": [] }),
+        _ => json!({ "variables": [] }),
+    }
+}
+
+/// Reads a single message, or `None` if the client closed the connection.
+fn read_message(reader: &mut impl BufRead) -> Result<Option<Value>, Error> {
+    let mut content_length = None;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':')
+            && name.eq_ignore_ascii_case("Content-Length")
+        {
+            content_length = Some(value.trim().parse::<usize>()?);
+        }
+    }
+
+    let content_length = content_length.context("Message without Content-Length")?;
+    if content_length > MAX_MESSAGE_BYTES {
+        anyhow::bail!("Message of {content_length} bytes is too large");
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+    Ok(Some(serde_json::from_slice(&body)?))
+}
+
+/// Parses a function breakpoint name, with an optional byte offset into the
+/// method body, such as `com.example::Main/update+0x1a`.
+///
+/// AVM1 frame scripts are referred to by clip path and frame number, such as
+/// `_level0.menu@3`.
+fn parse_function_breakpoint(name: &str) -> Breakpoint {
+    if let Some((clip, frame)) = name.rsplit_once('@')
+        && let Ok(frame) = frame.trim().parse()
+    {
+        return Breakpoint::FrameScript {
+            clip: clip.trim().to_string(),
+            frame,
+        };
+    }
+
+    if let Some((method, offset)) = name.rsplit_once('+') {
+        let offset = parse_offset(offset.trim());
+        if offset.is_some() {
+            return Breakpoint::Method {
+                name: method.trim().to_string(),
+                offset,
+            };
+        }
+    }
+
+    Breakpoint::Method {
+        name: name.trim().to_string(),
+        offset: None,
+    }
+}
+
+/// Turns a `debugfile` name such as `C:\project\src;com\example;Main.as`
+/// into a path.
+fn debug_file_path(debug_file: &str) -> String {
+    let separator = if debug_file.contains('\\') { "\\" } else { "/" };
+    debug_file.replace(';', separator)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_function_breakpoint() {
+        assert_eq!(
+            parse_function_breakpoint("com.example::Main/update"),
+            Breakpoint::Method {
+                name: "com.example::Main/update".to_string(),
+                offset: None,
+            }
+        );
+        assert_eq!(
+            parse_function_breakpoint("com.example::Main/update+0x1a"),
+            Breakpoint::Method {
+                name: "com.example::Main/update".to_string(),
+                offset: Some(0x1a),
+            }
+        );
+        assert_eq!(
+            parse_function_breakpoint("_level0.menu@3"),
+            Breakpoint::FrameScript {
+                clip: "_level0.menu".to_string(),
+                frame: 3,
+            }
+        );
+        assert_eq!(
+            parse_function_breakpoint("global$init() + 12"),
+            Breakpoint::Method {
+                name: "global$init()".to_string(),
+                offset: Some(12),
+            }
+        );
+    }
+
+    #[test]
+    fn test_read_message() {
+        let mut input = &b"Content-Length: 13\r\n\r\n{\"seq\": 1234}"[..];
+        let message = read_message(&mut input).expect("valid message");
+        assert_eq!(message, Some(json!({ "seq": 1234 })));
+        assert_eq!(read_message(&mut input).expect("end of input"), None);
+
+        let mut input = &b"Content-Length: 99999999999\r\n\r\n{}"[..];
+        assert!(read_message(&mut input).is_err());
+    }
+
+    #[test]
+    fn test_debug_file_path() {
+        assert_eq!(
+            debug_file_path(r"C:\project\src;com\example;Main.as"),
+            r"C:\project\src\com\example\Main.as"
+        );
+        assert_eq!(
+            debug_file_path("/project/src;com/example;Main.as"),
+            "/project/src/com/example/Main.as"
+        );
+    }
+}
@@ -18,8 +18,10 @@ pub use locale::text_with_args;
 pub use movie::MovieView;
 pub use picker::FilePicker;
 use ruffle_frontend_utils::content::ContentDescriptor;
+use ruffle_frontend_utils::profiles::Profile;
 pub use theme::ThemePreference;
 
+use crate::capture::Capture;
 use crate::custom_event::RuffleEvent;
 use crate::gui::context_menu::ContextMenu;
 use crate::player::LaunchOptions;
@@ -45,6 +47,7 @@ pub struct RuffleGui {
     context_menu: Option<ContextMenu>,
     dialogs: Dialogs,
     menu_bar: MenuBar,
+    capture: Capture,
 
     was_suspended_before_debug: bool,
     preferences: GlobalPreferences,
@@ -74,6 +77,7 @@ impl RuffleGui {
                 default_launch_options,
                 preferences.clone(),
             ),
+            capture: Capture::new(preferences.clone()),
 
             event_loop,
             preferences,
@@ -90,11 +94,24 @@ impl RuffleGui {
     ) {
         let locale = self.preferences.language();
 
-        self.menu_bar
-            .consume_shortcuts(egui_ctx, &mut self.dialogs, player.as_deref_mut());
+        self.menu_bar.consume_shortcuts(
+            egui_ctx,
+            &mut self.dialogs,
+            &mut self.capture,
+            player.as_deref_mut(),
+        );
         if show_menu {
-            self.menu_bar
-                .show(&locale, egui_ctx, &mut self.dialogs, player.as_deref_mut());
+            self.menu_bar.show(
+                &locale,
+                egui_ctx,
+                &mut self.dialogs,
+                &mut self.capture,
+                player.as_deref_mut(),
+            );
+        }
+
+        if let Some(player) = player.as_deref_mut() {
+            self.capture.update(player);
         }
 
         self.dialogs.show(&locale, egui_ctx, player.as_deref_mut());
@@ -161,6 +178,8 @@ impl RuffleGui {
     /// Notifies the GUI that the player has been destroyed.
     fn on_player_destroyed(&mut self) {
         self.dialogs.close_dialogs_with_notifiers();
+        self.dialogs.set_loaded_movie(None);
+        self.capture.stop_recording();
     }
 
     /// Notifies the GUI that a new player was created.
@@ -169,13 +188,16 @@ impl RuffleGui {
         opt: LaunchOptions,
         content_descriptor: ContentDescriptor,
         mut player: MutexGuard<Player>,
+        profile: Option<Profile>,
     ) {
         self.menu_bar.currently_opened = Some((content_descriptor.clone(), opt.clone()));
+        self.dialogs.set_loaded_movie(Some(&content_descriptor.url));
 
         // Update dialog state to reflect the newly-opened movie's options.
         self.dialogs
             .recreate_open_dialog(opt, Some(content_descriptor), self.event_loop.clone());
 
+        self.dialogs.volume_controls.set_profile(profile.as_ref());
         player.set_volume(self.dialogs.volume_controls.get_volume());
     }
 }
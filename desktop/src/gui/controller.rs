@@ -267,6 +267,13 @@ impl GuiController {
     ) {
         tracing::info!("Opening {}", content_descriptor.describe());
 
+        let mut opt = opt;
+        let profile = self.gui.preferences.find_profile(&content_descriptor);
+        if let Some((_, profile)) = &profile {
+            tracing::info!("Applying saved settings from profile '{}'", profile.name);
+            opt.apply_profile(profile);
+        }
+
         self.close_movie(player);
         let movie_view = MovieView::new(
             self.movie_view_renderer.clone(),
@@ -281,6 +288,7 @@ impl GuiController {
             player
                 .get()
                 .expect("Player must exist after being created."),
+            profile.map(|(_, profile)| profile),
         );
     }
 
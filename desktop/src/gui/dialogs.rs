@@ -2,12 +2,15 @@ mod about_dialog;
 mod bookmarks_dialog;
 pub mod export_bundle_dialog;
 pub mod filesystem_access_dialog;
+mod gamepad_dialog;
 pub mod message_dialog;
 pub mod network_access_dialog;
 mod open_dialog;
 mod open_url_dialog;
 mod preferences_dialog;
+mod profile_dialog;
 pub mod select_path_dialog;
+mod shared_objects_dialog;
 mod volume_controls;
 
 use crate::custom_event::RuffleEvent;
@@ -16,16 +19,20 @@ use crate::preferences::GlobalPreferences;
 use bookmarks_dialog::{BookmarkAddDialog, BookmarksDialog};
 use export_bundle_dialog::{ExportBundleDialog, ExportBundleDialogConfiguration};
 use filesystem_access_dialog::{FilesystemAccessDialog, FilesystemAccessDialogConfiguration};
+use gamepad_dialog::GamepadDialog;
 use message_dialog::{MessageDialog, MessageDialogConfiguration};
 use network_access_dialog::{NetworkAccessDialog, NetworkAccessDialogConfiguration};
 use open_dialog::OpenDialog;
 use open_url_dialog::OpenUrlDialog;
 use preferences_dialog::PreferencesDialog;
+use profile_dialog::ProfileDialog;
 use ruffle_core::Player;
 use ruffle_frontend_utils::content::ContentDescriptor;
 use select_path_dialog::{SelectPathDialog, SelectPathDialogConfiguration};
-use std::{collections::VecDeque, sync::Weak};
+use shared_objects_dialog::{SharedObjectsDialog, shared_object_domain};
+use std::{collections::VecDeque, path::PathBuf, sync::Weak};
 use unic_langid::LanguageIdentifier;
+use url::Url;
 use volume_controls::VolumeControls;
 use winit::event_loop::EventLoopProxy;
 
@@ -42,6 +49,9 @@ pub struct Dialogs {
     message_dialog: Option<MessageDialog>,
     export_bundle_dialog: Option<ExportBundleDialog>,
     pick_path_dialog: Option<SelectPathDialog>,
+    shared_objects_dialog: Option<SharedObjectsDialog>,
+    profile_dialog: Option<ProfileDialog>,
+    gamepad_dialog: Option<GamepadDialog>,
 
     // Use a queue for the following dialogs in order to:
     //  1. support handling multiple instances of them,
@@ -59,7 +69,11 @@ pub struct Dialogs {
 
     is_about_visible: bool,
 
+    save_directory: PathBuf,
     preferences: GlobalPreferences,
+
+    /// The domain whose shared objects the loaded movie may be using.
+    loaded_movie_domain: Option<String>,
 }
 
 pub enum DialogDescriptor {
@@ -69,6 +83,7 @@ pub enum DialogDescriptor {
     FilesystemAccess(FilesystemAccessDialogConfiguration),
     ExportBundle(Box<ExportBundleDialogConfiguration>),
     SelectPath(SelectPathDialogConfiguration),
+    EditProfile(ContentDescriptor),
 }
 
 impl Dialogs {
@@ -88,16 +103,22 @@ impl Dialogs {
             message_dialog: None,
             export_bundle_dialog: None,
             pick_path_dialog: None,
+            shared_objects_dialog: None,
+            profile_dialog: None,
+            gamepad_dialog: None,
 
             network_access_dialog_queue: VecDeque::new(),
             filesystem_access_dialog: None,
             filesystem_access_dialog_queue: VecDeque::new(),
 
+            save_directory: player_options.save_directory.clone(),
+            loaded_movie_domain: None,
             open_dialog: OpenDialog::new(
                 player_options,
                 default_content,
                 picker.clone(),
                 event_loop.clone(),
+                preferences.clone(),
             ),
             is_open_dialog_visible: false,
 
@@ -133,8 +154,13 @@ impl Dialogs {
         event_loop: EventLoopProxy<RuffleEvent>,
     ) {
         self.is_open_dialog_visible = false;
-        self.open_dialog =
-            OpenDialog::new(opt, content_descriptor, self.picker.clone(), event_loop);
+        self.open_dialog = OpenDialog::new(
+            opt,
+            content_descriptor,
+            self.picker.clone(),
+            event_loop,
+            self.preferences.clone(),
+        );
     }
 
     pub fn open_file_advanced(&mut self) {
@@ -161,6 +187,25 @@ impl Dialogs {
         ))
     }
 
+    /// Sets the URL of the loaded movie, so that its shared objects aren't edited under it.
+    pub fn set_loaded_movie(&mut self, url: Option<&Url>) {
+        self.loaded_movie_domain = url.map(shared_object_domain);
+    }
+
+    pub fn open_shared_objects(&mut self) {
+        self.shared_objects_dialog = Some(SharedObjectsDialog::new(
+            self.save_directory.clone(),
+            self.picker.clone(),
+        ));
+    }
+
+    pub fn open_gamepad_profiles(&mut self) {
+        self.gamepad_dialog = Some(GamepadDialog::new(
+            self.preferences.clone(),
+            self.event_loop.clone(),
+        ));
+    }
+
     pub fn open_volume_controls(&mut self) {
         self.is_volume_visible = true;
     }
@@ -198,6 +243,12 @@ impl Dialogs {
             DialogDescriptor::SelectPath(config) => {
                 self.pick_path_dialog = Some(SelectPathDialog::new(config));
             }
+            DialogDescriptor::EditProfile(content_descriptor) => {
+                self.profile_dialog = Some(ProfileDialog::new(
+                    self.preferences.clone(),
+                    content_descriptor,
+                ));
+            }
         }
     }
 
@@ -219,6 +270,9 @@ impl Dialogs {
         self.show_filesystem_access_dialog(locale, egui_ctx);
         self.show_export_bundle_dialog(locale, egui_ctx);
         self.show_pick_path_dialog(locale, egui_ctx);
+        self.show_shared_objects_dialog(locale, egui_ctx);
+        self.show_profile_dialog(locale, egui_ctx);
+        self.show_gamepad_dialog(locale, egui_ctx);
     }
 
     fn show_open_dialog(&mut self, locale: &LanguageIdentifier, egui_ctx: &egui::Context) {
@@ -362,4 +416,41 @@ impl Dialogs {
             self.pick_path_dialog = None;
         }
     }
+
+    fn show_shared_objects_dialog(
+        &mut self,
+        locale: &LanguageIdentifier,
+        egui_ctx: &egui::Context,
+    ) {
+        let keep_open = if let Some(dialog) = &mut self.shared_objects_dialog {
+            dialog.show(locale, egui_ctx, self.loaded_movie_domain.as_deref())
+        } else {
+            true
+        };
+        if !keep_open {
+            self.shared_objects_dialog = None;
+        }
+    }
+
+    fn show_profile_dialog(&mut self, locale: &LanguageIdentifier, egui_ctx: &egui::Context) {
+        let keep_open = if let Some(dialog) = &mut self.profile_dialog {
+            dialog.show(locale, egui_ctx)
+        } else {
+            true
+        };
+        if !keep_open {
+            self.profile_dialog = None;
+        }
+    }
+
+    fn show_gamepad_dialog(&mut self, locale: &LanguageIdentifier, egui_ctx: &egui::Context) {
+        let keep_open = if let Some(dialog) = &mut self.gamepad_dialog {
+            dialog.show(locale, egui_ctx)
+        } else {
+            true
+        };
+        if !keep_open {
+            self.gamepad_dialog = None;
+        }
+    }
 }
@@ -0,0 +1,423 @@
+use crate::custom_event::RuffleEvent;
+use crate::gui::text;
+use crate::preferences::GlobalPreferences;
+use clap::ValueEnum;
+use egui::{
+    Align2, Button, ComboBox, DragValue, Grid, Layout, Slider, TextEdit, Ui, Widget, Window,
+};
+use ruffle_core::events::{GamepadButton, KeyCode, MouseButton};
+use ruffle_frontend_utils::gamepad::{
+    AxisKey, Chord, GamepadAxis, GamepadProfile, MOUSE_BUTTONS, Stick, StickMouse,
+};
+use unic_langid::LanguageIdentifier;
+use winit::event_loop::EventLoopProxy;
+
+/// Editor for the gamepad profiles, and which one of them is in use.
+pub struct GamepadDialog {
+    preferences: GlobalPreferences,
+    event_loop: EventLoopProxy<RuffleEvent>,
+
+    profiles: Vec<GamepadProfile>,
+
+    /// How many profiles were saved when the dialog was opened.
+    saved_count: usize,
+
+    active: Option<usize>,
+    selected: Option<usize>,
+}
+
+impl GamepadDialog {
+    pub fn new(preferences: GlobalPreferences, event_loop: EventLoopProxy<RuffleEvent>) -> Self {
+        let (profiles, active) = preferences.gamepad_profiles(|profiles| {
+            let active = profiles
+                .active
+                .as_ref()
+                .and_then(|active| profiles.profiles.iter().position(|p| &p.name == active));
+            (profiles.profiles.clone(), active)
+        });
+
+        Self {
+            preferences,
+            event_loop,
+            saved_count: profiles.len(),
+            selected: active.or((!profiles.is_empty()).then_some(0)),
+            profiles,
+            active,
+        }
+    }
+
+    fn save(&self) {
+        let result = self.preferences.write_gamepad_profiles(|writer| {
+            for (index, profile) in self.profiles.iter().enumerate() {
+                if index < self.saved_count {
+                    writer.set_profile(index, profile.clone());
+                } else {
+                    writer.add(profile.clone());
+                }
+            }
+            for index in (self.profiles.len()..self.saved_count).rev() {
+                writer.remove(index);
+            }
+            writer.set_active(self.active.map(|index| self.profiles[index].name.clone()));
+        });
+        if let Err(e) = result {
+            tracing::warn!("Couldn't update gamepad profiles: {e}");
+        }
+        let _ = self
+            .event_loop
+            .send_event(RuffleEvent::GamepadProfilesChanged);
+    }
+
+    pub fn show(&mut self, locale: &LanguageIdentifier, egui_ctx: &egui::Context) -> bool {
+        let mut keep_open = true;
+        let mut should_close = false;
+
+        Window::new(text(locale, "gamepad-dialog"))
+            .open(&mut keep_open)
+            .anchor(Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .collapsible(false)
+            .resizable(false)
+            .show(egui_ctx, |ui| {
+                Grid::new("gamepad-dialog-grid")
+                    .num_columns(2)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        self.profile_selection(locale, ui);
+                    });
+
+                if let Some(index) = self.selected {
+                    ui.separator();
+                    Self::profile_settings(locale, ui, &mut self.profiles[index]);
+                }
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                        if Button::new(text(locale, "save")).ui(ui).clicked() {
+                            self.save();
+                            should_close = true;
+                        }
+                        if Button::new(text(locale, "cancel")).ui(ui).clicked() {
+                            should_close = true;
+                        }
+                    });
+                });
+            });
+
+        keep_open && !should_close
+    }
+
+    fn profile_name(&self, locale: &LanguageIdentifier, index: Option<usize>) -> String {
+        match index {
+            Some(index) => self.profiles[index].name.clone(),
+            None => text(locale, "gamepad-dialog-none").into_owned(),
+        }
+    }
+
+    fn profile_selection(&mut self, locale: &LanguageIdentifier, ui: &mut Ui) {
+        ui.label(text(locale, "gamepad-dialog-active"));
+        ComboBox::from_id_salt("gamepad-dialog-active")
+            .selected_text(self.profile_name(locale, self.active))
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut self.active, None, text(locale, "gamepad-dialog-none"));
+                for (index, profile) in self.profiles.iter().enumerate() {
+                    ui.selectable_value(&mut self.active, Some(index), profile.name.as_str());
+                }
+            });
+        ui.end_row();
+
+        ui.label(text(locale, "gamepad-dialog-edit"));
+        ui.horizontal(|ui| {
+            ComboBox::from_id_salt("gamepad-dialog-edit")
+                .selected_text(self.profile_name(locale, self.selected))
+                .show_ui(ui, |ui| {
+                    for (index, profile) in self.profiles.iter().enumerate() {
+                        ui.selectable_value(&mut self.selected, Some(index), profile.name.as_str());
+                    }
+                });
+
+            if ui
+                .button(text(locale, "gamepad-dialog-add-profile"))
+                .clicked()
+            {
+                let name = text(locale, "gamepad-dialog-new-profile-name").into_owned();
+                self.profiles.push(GamepadProfile::new(name));
+                self.selected = Some(self.profiles.len() - 1);
+            }
+
+            if ui
+                .add_enabled(self.selected.is_some(), Button::new(text(locale, "remove")))
+                .clicked()
+                && let Some(index) = self.selected
+            {
+                self.profiles.remove(index);
+                self.active = match self.active {
+                    Some(active) if active == index => None,
+                    Some(active) if active > index => Some(active - 1),
+                    active => active,
+                };
+                self.selected = index
+                    .checked_sub(1)
+                    .or((!self.profiles.is_empty()).then_some(0));
+            }
+        });
+        ui.end_row();
+    }
+
+    fn profile_settings(locale: &LanguageIdentifier, ui: &mut Ui, profile: &mut GamepadProfile) {
+        ui.horizontal(|ui| {
+            ui.label(text(locale, "gamepad-dialog-name"));
+            TextEdit::singleline(&mut profile.name).ui(ui);
+        });
+
+        ui.collapsing(text(locale, "gamepad-dialog-cursor"), |ui| {
+            Self::cursor_settings(locale, ui, &mut profile.stick_mouse);
+        });
+        ui.collapsing(text(locale, "gamepad-dialog-buttons"), |ui| {
+            Self::button_settings(locale, ui, &mut profile.buttons);
+        });
+        ui.collapsing(text(locale, "gamepad-dialog-mouse-buttons"), |ui| {
+            Self::mouse_button_settings(locale, ui, &mut profile.mouse_buttons);
+        });
+        ui.collapsing(text(locale, "gamepad-dialog-axis-keys"), |ui| {
+            Self::axis_key_settings(locale, ui, &mut profile.axis_keys);
+        });
+        ui.collapsing(text(locale, "gamepad-dialog-chords"), |ui| {
+            Self::chord_settings(locale, ui, &mut profile.chords);
+        });
+    }
+
+    fn cursor_settings(
+        locale: &LanguageIdentifier,
+        ui: &mut Ui,
+        stick_mouse: &mut Option<StickMouse>,
+    ) {
+        let mut enabled = stick_mouse.is_some();
+        if ui
+            .checkbox(&mut enabled, text(locale, "gamepad-dialog-cursor-enabled"))
+            .changed()
+        {
+            *stick_mouse = enabled.then(StickMouse::default);
+        }
+
+        let Some(stick_mouse) = stick_mouse else {
+            return;
+        };
+        Grid::new("gamepad-dialog-cursor-grid")
+            .num_columns(2)
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label(text(locale, "gamepad-dialog-cursor-stick"));
+                ui.horizontal(|ui| {
+                    ui.radio_value(
+                        &mut stick_mouse.stick,
+                        Stick::Left,
+                        text(locale, "gamepad-dialog-cursor-stick-left"),
+                    );
+                    ui.radio_value(
+                        &mut stick_mouse.stick,
+                        Stick::Right,
+                        text(locale, "gamepad-dialog-cursor-stick-right"),
+                    );
+                });
+                ui.end_row();
+
+                ui.label(text(locale, "gamepad-dialog-cursor-deadzone"));
+                ui.add(Slider::new(&mut stick_mouse.deadzone, 0.0..=0.9));
+                ui.end_row();
+
+                ui.label(text(locale, "gamepad-dialog-cursor-speed"));
+                ui.add(
+                    DragValue::new(&mut stick_mouse.speed)
+                        .range(10.0..=5000.0)
+                        .suffix(" px/s"),
+                );
+                ui.end_row();
+
+                ui.label(text(locale, "gamepad-dialog-cursor-acceleration"));
+                ui.add(Slider::new(&mut stick_mouse.acceleration, 1.0..=4.0))
+                    .on_hover_text(text(locale, "gamepad-dialog-cursor-acceleration-tooltip"));
+                ui.end_row();
+            });
+    }
+
+    fn button_settings(
+        locale: &LanguageIdentifier,
+        ui: &mut Ui,
+        buttons: &mut Vec<(GamepadButton, KeyCode)>,
+    ) {
+        let mut removed = None;
+        Grid::new("gamepad-dialog-buttons-grid")
+            .num_columns(3)
+            .striped(true)
+            .show(ui, |ui| {
+                for (index, (button, key_code)) in buttons.iter_mut().enumerate() {
+                    button_combo_box(ui, ("gamepad-dialog-button", index), button);
+                    key_code_edit(locale, ui, key_code);
+                    if ui.button(text(locale, "remove")).clicked() {
+                        removed = Some(index);
+                    }
+                    ui.end_row();
+                }
+            });
+        if let Some(index) = removed {
+            buttons.remove(index);
+        }
+
+        if ui.button(text(locale, "gamepad-dialog-add")).clicked() {
+            buttons.push((GamepadButton::South, KeyCode::SPACE));
+        }
+    }
+
+    fn mouse_button_settings(
+        locale: &LanguageIdentifier,
+        ui: &mut Ui,
+        mouse_buttons: &mut Vec<(GamepadButton, MouseButton)>,
+    ) {
+        let mut removed = None;
+        Grid::new("gamepad-dialog-mouse-buttons-grid")
+            .num_columns(3)
+            .striped(true)
+            .show(ui, |ui| {
+                for (index, (button, mouse_button)) in mouse_buttons.iter_mut().enumerate() {
+                    button_combo_box(ui, ("gamepad-dialog-mouse-button", index), button);
+                    ComboBox::from_id_salt(("gamepad-dialog-mouse-button-target", index))
+                        .selected_text(mouse_button_text(locale, *mouse_button))
+                        .show_ui(ui, |ui| {
+                            for variant in MOUSE_BUTTONS {
+                                ui.selectable_value(
+                                    mouse_button,
+                                    variant,
+                                    mouse_button_text(locale, variant),
+                                );
+                            }
+                        });
+                    if ui.button(text(locale, "remove")).clicked() {
+                        removed = Some(index);
+                    }
+                    ui.end_row();
+                }
+            });
+        if let Some(index) = removed {
+            mouse_buttons.remove(index);
+        }
+
+        if ui.button(text(locale, "gamepad-dialog-add")).clicked() {
+            mouse_buttons.push((GamepadButton::RightTrigger2, MouseButton::Left));
+        }
+    }
+
+    fn axis_key_settings(locale: &LanguageIdentifier, ui: &mut Ui, axis_keys: &mut Vec<AxisKey>) {
+        let mut removed = None;
+        Grid::new("gamepad-dialog-axis-keys-grid")
+            .num_columns(4)
+            .striped(true)
+            .show(ui, |ui| {
+                for (index, axis_key) in axis_keys.iter_mut().enumerate() {
+                    ComboBox::from_id_salt(("gamepad-dialog-axis", index))
+                        .selected_text(axis_key.axis.to_string())
+                        .show_ui(ui, |ui| {
+                            for variant in GamepadAxis::ALL {
+                                ui.selectable_value(
+                                    &mut axis_key.axis,
+                                    variant,
+                                    variant.to_string(),
+                                );
+                            }
+                        });
+                    ui.add(Slider::new(&mut axis_key.threshold, -1.0..=1.0))
+                        .on_hover_text(text(locale, "gamepad-dialog-threshold-tooltip"));
+                    key_code_edit(locale, ui, &mut axis_key.key_code);
+                    if ui.button(text(locale, "remove")).clicked() {
+                        removed = Some(index);
+                    }
+                    ui.end_row();
+                }
+            });
+        if let Some(index) = removed {
+            axis_keys.remove(index);
+        }
+
+        if ui.button(text(locale, "gamepad-dialog-add")).clicked() {
+            axis_keys.push(AxisKey {
+                axis: GamepadAxis::LeftStickY,
+                threshold: 0.5,
+                key_code: KeyCode::UP,
+            });
+        }
+    }
+
+    fn chord_settings(locale: &LanguageIdentifier, ui: &mut Ui, chords: &mut Vec<Chord>) {
+        let mut removed = None;
+        Grid::new("gamepad-dialog-chords-grid")
+            .num_columns(3)
+            .striped(true)
+            .show(ui, |ui| {
+                for (index, chord) in chords.iter_mut().enumerate() {
+                    let buttons = chord
+                        .buttons
+                        .iter()
+                        .map(|button| button.to_string())
+                        .collect::<Vec<_>>()
+                        .join(" + ");
+                    ui.menu_button(buttons, |ui| {
+                        for variant in GamepadButton::value_variants() {
+                            let mut checked = chord.buttons.contains(variant);
+                            if ui.checkbox(&mut checked, variant.to_string()).changed() {
+                                if checked {
+                                    chord.buttons.push(*variant);
+                                } else if chord.buttons.len() > 1 {
+                                    chord.buttons.retain(|button| button != variant);
+                                }
+                            }
+                        }
+                    });
+                    key_code_edit(locale, ui, &mut chord.key_code);
+                    if ui.button(text(locale, "remove")).clicked() {
+                        removed = Some(index);
+                    }
+                    ui.end_row();
+                }
+            });
+        if let Some(index) = removed {
+            chords.remove(index);
+        }
+
+        if ui.button(text(locale, "gamepad-dialog-add")).clicked() {
+            chords.push(Chord {
+                buttons: vec![GamepadButton::Select, GamepadButton::Start],
+                key_code: KeyCode::ESCAPE,
+            });
+        }
+    }
+}
+
+fn button_combo_box(ui: &mut Ui, id_salt: impl std::hash::Hash, button: &mut GamepadButton) {
+    ComboBox::from_id_salt(id_salt)
+        .selected_text(button.to_string())
+        .show_ui(ui, |ui| {
+            for variant in GamepadButton::value_variants() {
+                ui.selectable_value(button, *variant, variant.to_string());
+            }
+        });
+}
+
+fn key_code_edit(locale: &LanguageIdentifier, ui: &mut Ui, key_code: &mut KeyCode) {
+    let mut value = key_code.value();
+    if ui
+        .add(DragValue::new(&mut value).range(0..=255))
+        .on_hover_text(text(locale, "gamepad-dialog-key-code-tooltip"))
+        .changed()
+    {
+        *key_code = KeyCode::from_code(value);
+    }
+}
+
+fn mouse_button_text(locale: &LanguageIdentifier, button: MouseButton) -> String {
+    let id = match button {
+        MouseButton::Right => "gamepad-dialog-mouse-right",
+        MouseButton::Middle => "gamepad-dialog-mouse-middle",
+        MouseButton::Left | MouseButton::Unknown => "gamepad-dialog-mouse-left",
+    };
+    text(locale, id).into_owned()
+}
@@ -1,7 +1,9 @@
 use crate::custom_event::RuffleEvent;
+use crate::gui::DialogDescriptor;
 use crate::gui::widgets::path_or_url_field::PathOrUrlField;
 use crate::gui::{FilePicker, LocalizableText, text};
 use crate::player::LaunchOptions;
+use crate::preferences::GlobalPreferences;
 use egui::{
     Align2, Button, Checkbox, ComboBox, Grid, Layout, Slider, TextEdit, Ui, Widget, Window, emath,
 };
@@ -12,6 +14,7 @@ use ruffle_core::{
     StageScaleMode,
 };
 use ruffle_frontend_utils::content::ContentDescriptor;
+use ruffle_frontend_utils::profiles::{Profile, ProfileKey};
 use ruffle_render::quality::StageQuality;
 use std::borrow::Cow;
 use std::ops::RangeInclusive;
@@ -23,6 +26,7 @@ use winit::event_loop::EventLoopProxy;
 pub struct OpenDialog {
     options: LaunchOptions,
     event_loop: EventLoopProxy<RuffleEvent>,
+    preferences: GlobalPreferences,
 
     // These are outside of PlayerOptions as it can be an invalid value (ie URL) during typing,
     // and we don't want to clear the value if the user, ie, toggles the checkbox.
@@ -47,6 +51,8 @@ pub struct OpenDialog {
     player_runtime: OptionalField<EnumDropdownField<PlayerRuntime>>,
     dummy_external_interface: OptionalField<BooleanDropdownField>,
     upgrade_to_https: OptionalField<BooleanDropdownField>,
+
+    remember_settings: bool,
 }
 
 impl OpenDialog {
@@ -55,7 +61,12 @@ impl OpenDialog {
         default_content: Option<ContentDescriptor>,
         picker: FilePicker,
         event_loop: EventLoopProxy<RuffleEvent>,
+        preferences: GlobalPreferences,
     ) -> Self {
+        // Keep remembering the settings of movies which already have a profile.
+        let remember_settings = default_content
+            .as_ref()
+            .is_some_and(|content| preferences.find_profile(content).is_some());
         let spoof_url = OptionalField::new(
             defaults.player.spoof_url.as_ref().map(Url::to_string),
             UrlField::new("https://example.org/game.swf"),
@@ -269,6 +280,7 @@ impl OpenDialog {
         Self {
             options: defaults,
             event_loop,
+            preferences,
             spoof_url,
             referer,
             cookie,
@@ -288,6 +300,7 @@ impl OpenDialog {
             player_runtime,
             dummy_external_interface,
             upgrade_to_https,
+            remember_settings,
         }
     }
 
@@ -307,6 +320,10 @@ impl OpenDialog {
         }
 
         if let Some(result) = self.path.result() {
+            if self.remember_settings {
+                self.save_profile(&result);
+            }
+
             let launch_options = self.options.clone();
             if self
                 .event_loop
@@ -320,6 +337,37 @@ impl OpenDialog {
         false
     }
 
+    /// Remember the current options for the given content, so that they're used next time.
+    fn save_profile(&self, content_descriptor: &ContentDescriptor) {
+        let player = self.options.player.clone();
+        let mut gamepad_button_mapping: Vec<_> = self
+            .options
+            .gamepad_button_mapping
+            .iter()
+            .map(|(button, key_code)| (*button, *key_code))
+            .collect();
+        gamepad_button_mapping.sort_by_key(|(button, _)| button.to_string());
+
+        let existing = self.preferences.find_profile(content_descriptor);
+        if let Err(e) = self.preferences.write_profiles(|writer| match existing {
+            Some((index, _)) => {
+                writer.set_player_options(index, player);
+                writer.set_gamepad_button_mapping(index, gamepad_button_mapping);
+            }
+            None => writer.add(Profile {
+                player,
+                gamepad_button_mapping,
+                ..Profile::new(
+                    ProfileKey::Url(content_descriptor.url.clone()),
+                    ruffle_frontend_utils::url_to_readable_name(&content_descriptor.url)
+                        .into_owned(),
+                )
+            }),
+        }) {
+            tracing::warn!("Couldn't update profiles: {e}");
+        }
+    }
+
     pub fn show(&mut self, locale: &LanguageIdentifier, egui_ctx: &egui::Context) -> bool {
         let mut keep_open = true;
         let mut should_close = false;
@@ -354,6 +402,26 @@ impl OpenDialog {
                 });
 
                 ui.horizontal(|ui| {
+                    ui.checkbox(
+                        &mut self.remember_settings,
+                        text(locale, "open-dialog-remember-settings"),
+                    )
+                    .on_hover_text(text(locale, "open-dialog-remember-settings-tooltip"));
+
+                    let content_descriptor = self.path.result();
+                    if ui
+                        .add_enabled(
+                            content_descriptor.is_some(),
+                            Button::new(text(locale, "open-dialog-edit-profile")),
+                        )
+                        .clicked()
+                        && let Some(content_descriptor) = content_descriptor
+                    {
+                        let _ = self.event_loop.send_event(RuffleEvent::OpenDialog(
+                            DialogDescriptor::EditProfile(content_descriptor),
+                        ));
+                    }
+
                     ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                         if ui
                             .add_enabled(is_valid, Button::new(text(locale, "start")))
@@ -1,12 +1,16 @@
+use crate::capture::{Hotkey, RecordingFormat};
 use crate::cli::{GameModePreference, OpenUrlMode};
 use crate::gui::{ThemePreference, available_languages, optional_text, text};
 use crate::log::FilenamePattern;
-use crate::preferences::{GlobalPreferences, storage::StorageBackend};
+use crate::preferences::{
+    GlobalPreferences, get_default_capture_directory, storage::StorageBackend,
+};
 use cpal::traits::{DeviceTrait, HostTrait};
-use egui::{Align2, Button, Checkbox, ComboBox, DragValue, Grid, Ui, Widget, Window};
+use egui::{Align2, Button, Checkbox, ComboBox, DragValue, Grid, TextEdit, Ui, Widget, Window};
 use ruffle_render_wgpu::backend::create_wgpu_instance;
 use ruffle_render_wgpu::clap::{GraphicsBackend, PowerPreference};
 use std::borrow::Cow;
+use std::path::PathBuf;
 use unic_langid::LanguageIdentifier;
 
 pub struct PreferencesDialog {
@@ -36,6 +40,9 @@ pub struct PreferencesDialog {
     enable_openh264_changed: bool,
     openh264_license_visible: bool,
 
+    speed_hotkeys: bool,
+    speed_hotkeys_changed: bool,
+
     recent_limit: usize,
     recent_limit_changed: bool,
 
@@ -55,6 +62,22 @@ pub struct PreferencesDialog {
 
     ime_enabled: Option<bool>,
     ime_enabled_changed: bool,
+
+    /// Empty for the default directory.
+    capture_directory: String,
+    capture_directory_changed: bool,
+
+    capture_native_size: bool,
+    capture_native_size_changed: bool,
+
+    recording_format: RecordingFormat,
+    recording_format_changed: bool,
+
+    screenshot_hotkey: String,
+    screenshot_hotkey_changed: bool,
+
+    record_hotkey: String,
+    record_hotkey_changed: bool,
 }
 
 impl PreferencesDialog {
@@ -71,6 +94,8 @@ impl PreferencesDialog {
             }
         }
 
+        let capture_directory = preferences.capture_directory();
+
         Self {
             available_backends,
             graphics_backend: preferences.graphics_backends(),
@@ -96,6 +121,9 @@ impl PreferencesDialog {
             enable_openh264_changed: false,
             openh264_license_visible: false,
 
+            speed_hotkeys: preferences.speed_hotkeys_enabled(),
+            speed_hotkeys_changed: false,
+
             recent_limit: preferences.recent_limit(),
             recent_limit_changed: false,
 
@@ -116,6 +144,25 @@ impl PreferencesDialog {
             ime_enabled: preferences.ime_enabled(),
             ime_enabled_changed: false,
 
+            capture_directory: if capture_directory == get_default_capture_directory() {
+                String::new()
+            } else {
+                capture_directory.to_string_lossy().into_owned()
+            },
+            capture_directory_changed: false,
+
+            capture_native_size: preferences.capture_native_size(),
+            capture_native_size_changed: false,
+
+            recording_format: preferences.recording_format(),
+            recording_format_changed: false,
+
+            screenshot_hotkey: preferences.screenshot_hotkey().to_string(),
+            screenshot_hotkey_changed: false,
+
+            record_hotkey: preferences.record_hotkey().to_string(),
+            record_hotkey_changed: false,
+
             preferences,
         }
     }
@@ -158,6 +205,8 @@ impl PreferencesDialog {
 
                             self.show_storage_preferences(locale, &locked_text, ui);
 
+                            self.show_capture_preferences(locale, ui);
+
                             self.show_misc_preferences(locale, ui);
                         });
 
@@ -542,7 +591,84 @@ impl PreferencesDialog {
         ui.end_row();
     }
 
+    fn show_capture_preferences(&mut self, locale: &LanguageIdentifier, ui: &mut Ui) {
+        ui.label(text(locale, "capture-directory"));
+        let default_directory = get_default_capture_directory();
+        if TextEdit::singleline(&mut self.capture_directory)
+            .hint_text(default_directory.to_string_lossy())
+            .ui(ui)
+            .changed()
+        {
+            self.capture_directory_changed = true;
+        }
+        ui.end_row();
+
+        ui.label(text(locale, "capture-size"));
+        let previous = self.capture_native_size;
+        ComboBox::from_id_salt("capture-size")
+            .selected_text(capture_size_name(locale, self.capture_native_size))
+            .show_ui(ui, |ui| {
+                ui.selectable_value(
+                    &mut self.capture_native_size,
+                    false,
+                    capture_size_name(locale, false),
+                );
+                ui.selectable_value(
+                    &mut self.capture_native_size,
+                    true,
+                    capture_size_name(locale, true),
+                );
+            });
+        if self.capture_native_size != previous {
+            self.capture_native_size_changed = true;
+        }
+        ui.end_row();
+
+        ui.label(text(locale, "recording-format"));
+        let previous = self.recording_format;
+        ComboBox::from_id_salt("recording-format")
+            .selected_text(recording_format_name(locale, self.recording_format))
+            .show_ui(ui, |ui| {
+                for format in [
+                    RecordingFormat::Apng,
+                    RecordingFormat::Gif,
+                    RecordingFormat::PngSequence,
+                ] {
+                    ui.selectable_value(
+                        &mut self.recording_format,
+                        format,
+                        recording_format_name(locale, format),
+                    );
+                }
+            });
+        if self.recording_format != previous {
+            self.recording_format_changed = true;
+        }
+        ui.end_row();
+
+        ui.label(text(locale, "screenshot-hotkey"));
+        if hotkey_edit(locale, ui, &mut self.screenshot_hotkey) {
+            self.screenshot_hotkey_changed = true;
+        }
+        ui.end_row();
+
+        ui.label(text(locale, "record-hotkey"));
+        if hotkey_edit(locale, ui, &mut self.record_hotkey) {
+            self.record_hotkey_changed = true;
+        }
+        ui.end_row();
+    }
+
     fn show_misc_preferences(&mut self, locale: &LanguageIdentifier, ui: &mut Ui) {
+        ui.label(text(locale, "speed-hotkeys"));
+        let previous = self.speed_hotkeys;
+        ui.add(Checkbox::without_text(&mut self.speed_hotkeys))
+            .on_hover_text(text(locale, "speed-hotkeys-tooltip"));
+        if self.speed_hotkeys != previous {
+            self.speed_hotkeys_changed = true;
+        }
+        ui.end_row();
+
         ui.label(text(locale, "recent-limit"));
 
         ui.horizontal(|ui| {
@@ -589,6 +715,9 @@ impl PreferencesDialog {
             if self.storage_backend_changed {
                 preferences.set_storage_backend(self.storage_backend);
             }
+            if self.speed_hotkeys_changed {
+                preferences.set_speed_hotkeys(self.speed_hotkeys);
+            }
             if self.recent_limit_changed {
                 preferences.set_recent_limit(self.recent_limit);
             }
@@ -604,6 +733,28 @@ impl PreferencesDialog {
             if self.ime_enabled_changed {
                 preferences.set_ime_enabled(self.ime_enabled);
             }
+            if self.capture_directory_changed {
+                let directory = self.capture_directory.trim();
+                preferences.set_capture_directory(
+                    (!directory.is_empty()).then(|| PathBuf::from(directory)),
+                );
+            }
+            if self.capture_native_size_changed {
+                preferences.set_capture_native_size(self.capture_native_size);
+            }
+            if self.recording_format_changed {
+                preferences.set_recording_format(self.recording_format);
+            }
+            if self.screenshot_hotkey_changed
+                && let Ok(hotkey) = self.screenshot_hotkey.parse()
+            {
+                preferences.set_screenshot_hotkey(hotkey);
+            }
+            if self.record_hotkey_changed
+                && let Ok(hotkey) = self.record_hotkey.parse()
+            {
+                preferences.set_record_hotkey(hotkey);
+            }
         }) {
             // [NA] TODO: Better error handling... everywhere in desktop, really
             tracing::error!("Could not save preferences: {e}");
@@ -700,6 +851,37 @@ fn ime_enabled_name(locale: &LanguageIdentifier, ime_enabled: Option<bool>) -> C
     }
 }
 
+fn capture_size_name(locale: &LanguageIdentifier, native_size: bool) -> Cow<'_, str> {
+    if native_size {
+        text(locale, "capture-size-native")
+    } else {
+        text(locale, "capture-size-window")
+    }
+}
+
+fn recording_format_name(locale: &LanguageIdentifier, format: RecordingFormat) -> Cow<'_, str> {
+    match format {
+        RecordingFormat::Apng => text(locale, "recording-format-apng"),
+        RecordingFormat::Gif => text(locale, "recording-format-gif"),
+        RecordingFormat::PngSequence => text(locale, "recording-format-png-sequence"),
+    }
+}
+
+/// A text field for a [`Hotkey`], highlighted while it doesn't parse.
+fn hotkey_edit(locale: &LanguageIdentifier, ui: &mut Ui, hotkey: &mut String) -> bool {
+    let valid = hotkey.parse::<Hotkey>().is_ok();
+    let mut edit = TextEdit::singleline(hotkey);
+    if !valid {
+        edit = edit.text_color(ui.visuals().error_fg_color);
+    }
+    let response = edit.ui(ui);
+    let changed = response.changed();
+    if !valid {
+        response.on_hover_text(text(locale, "hotkey-invalid"));
+    }
+    changed
+}
+
 fn backend_availability(instance: &wgpu::Instance, backend: wgpu::Backends) -> wgpu::Backends {
     if instance.enumerate_adapters(backend).is_empty() {
         wgpu::Backends::empty()
@@ -0,0 +1,269 @@
+use crate::gui::text;
+use crate::preferences::GlobalPreferences;
+use clap::ValueEnum;
+use egui::{
+    Align2, Button, Checkbox, ComboBox, DragValue, Grid, Layout, Slider, TextEdit, Ui, Widget,
+    Window,
+};
+use ruffle_core::events::{GamepadButton, KeyCode};
+use ruffle_frontend_utils::content::ContentDescriptor;
+use ruffle_frontend_utils::parse::DocumentHolder;
+use ruffle_frontend_utils::player_options::{
+    PlayerOptions, PlayerOptionsWriter, write_player_options,
+};
+use ruffle_frontend_utils::profiles::{Profile, ProfileKey, local_content_hash};
+use unic_langid::LanguageIdentifier;
+
+/// Editor for the settings remembered for a single movie.
+pub struct ProfileDialog {
+    preferences: GlobalPreferences,
+    content_descriptor: ContentDescriptor,
+
+    /// Index of the edited profile, `None` if it doesn't exist yet.
+    index: Option<usize>,
+
+    name: String,
+    match_by_hash: bool,
+    hash: Option<String>,
+    volume_enabled: bool,
+    volume: f32,
+    gamepad_button_mapping: Vec<(GamepadButton, u32)>,
+    player: PlayerOptions,
+    player_summary: String,
+}
+
+impl ProfileDialog {
+    pub fn new(preferences: GlobalPreferences, content_descriptor: ContentDescriptor) -> Self {
+        let hash = local_content_hash(&content_descriptor.url);
+        let (index, profile) = match preferences.find_profile(&content_descriptor) {
+            Some((index, profile)) => (Some(index), profile),
+            None => (
+                None,
+                Profile::new(
+                    ProfileKey::Url(content_descriptor.url.clone()),
+                    ruffle_frontend_utils::url_to_readable_name(&content_descriptor.url)
+                        .into_owned(),
+                ),
+            ),
+        };
+
+        Self {
+            preferences,
+            content_descriptor,
+            index,
+            name: profile.name,
+            match_by_hash: matches!(profile.key, ProfileKey::Hash(_)),
+            hash,
+            volume_enabled: profile.volume.is_some(),
+            volume: profile.volume.unwrap_or(1.0) * 100.0,
+            gamepad_button_mapping: profile
+                .gamepad_button_mapping
+                .into_iter()
+                .map(|(button, key_code)| (button, key_code.value()))
+                .collect(),
+            player_summary: Self::summarize(&profile.player),
+            player: profile.player,
+        }
+    }
+
+    fn summarize(player: &PlayerOptions) -> String {
+        let mut document: DocumentHolder<PlayerOptions> = Default::default();
+        let mut writer = PlayerOptionsWriter::new(&mut document);
+        write_player_options(&mut writer, player);
+        document.serialize()
+    }
+
+    fn key(&self) -> ProfileKey {
+        match (&self.hash, self.match_by_hash) {
+            (Some(hash), true) => ProfileKey::Hash(hash.clone()),
+            _ => ProfileKey::Url(self.content_descriptor.url.clone()),
+        }
+    }
+
+    fn volume(&self) -> Option<f32> {
+        self.volume_enabled.then_some(self.volume / 100.0)
+    }
+
+    fn gamepad_button_mapping(&self) -> Vec<(GamepadButton, KeyCode)> {
+        self.gamepad_button_mapping
+            .iter()
+            .map(|(button, key_code)| (*button, KeyCode::from_code(*key_code)))
+            .collect()
+    }
+
+    fn save(&self) {
+        let result = self.preferences.write_profiles(|writer| match self.index {
+            Some(index) => {
+                writer.set_name(index, self.name.clone());
+                writer.set_key(index, self.key());
+                writer.set_volume(index, self.volume());
+                writer.set_gamepad_button_mapping(index, self.gamepad_button_mapping());
+                writer.set_player_options(index, self.player.clone());
+            }
+            None => writer.add(Profile {
+                key: self.key(),
+                name: self.name.clone(),
+                player: self.player.clone(),
+                gamepad_button_mapping: self.gamepad_button_mapping(),
+                volume: self.volume(),
+            }),
+        });
+        if let Err(e) = result {
+            tracing::warn!("Couldn't update profiles: {e}");
+        }
+    }
+
+    fn remove(&self) {
+        if let Some(index) = self.index
+            && let Err(e) = self
+                .preferences
+                .write_profiles(|writer| writer.remove(index))
+        {
+            tracing::warn!("Couldn't update profiles: {e}");
+        }
+    }
+
+    pub fn show(&mut self, locale: &LanguageIdentifier, egui_ctx: &egui::Context) -> bool {
+        let mut keep_open = true;
+        let mut should_close = false;
+
+        Window::new(text(locale, "profile-dialog"))
+            .open(&mut keep_open)
+            .anchor(Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .collapsible(false)
+            .resizable(false)
+            .show(egui_ctx, |ui| {
+                Grid::new("profile-dialog-grid")
+                    .num_columns(2)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        self.general_settings(locale, ui);
+                    });
+
+                ui.collapsing(text(locale, "profile-dialog-gamepad"), |ui| {
+                    self.gamepad_settings(locale, ui);
+                });
+
+                ui.collapsing(text(locale, "profile-dialog-player-settings"), |ui| {
+                    self.player_settings(locale, ui);
+                });
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(self.index.is_some(), Button::new(text(locale, "remove")))
+                        .clicked()
+                    {
+                        self.remove();
+                        should_close = true;
+                    }
+
+                    ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                        if Button::new(text(locale, "save")).ui(ui).clicked() {
+                            self.save();
+                            should_close = true;
+                        }
+                        if Button::new(text(locale, "cancel")).ui(ui).clicked() {
+                            should_close = true;
+                        }
+                    });
+                });
+            });
+
+        keep_open && !should_close
+    }
+
+    fn general_settings(&mut self, locale: &LanguageIdentifier, ui: &mut Ui) {
+        ui.label(text(locale, "profile-dialog-name"));
+        TextEdit::singleline(&mut self.name).ui(ui);
+        ui.end_row();
+
+        ui.label(text(locale, "profile-dialog-match"));
+        ui.vertical(|ui| {
+            ui.radio_value(
+                &mut self.match_by_hash,
+                false,
+                text(locale, "profile-dialog-match-url"),
+            )
+            .on_hover_text(self.content_descriptor.url.as_str());
+            ui.add_enabled_ui(self.hash.is_some(), |ui| {
+                let response = ui.radio_value(
+                    &mut self.match_by_hash,
+                    true,
+                    text(locale, "profile-dialog-match-hash"),
+                );
+                match &self.hash {
+                    Some(hash) => response.on_hover_text(hash),
+                    None => response.on_disabled_hover_text(text(locale, "profile-dialog-no-hash")),
+                };
+            });
+        });
+        ui.end_row();
+
+        ui.label(text(locale, "profile-dialog-volume"));
+        ui.horizontal(|ui| {
+            Checkbox::without_text(&mut self.volume_enabled).ui(ui);
+            ui.add_enabled(
+                self.volume_enabled,
+                Slider::new(&mut self.volume, 0.0..=100.0),
+            );
+        });
+        ui.end_row();
+    }
+
+    fn gamepad_settings(&mut self, locale: &LanguageIdentifier, ui: &mut Ui) {
+        let mut removed = None;
+        Grid::new("profile-dialog-gamepad-grid")
+            .num_columns(3)
+            .striped(true)
+            .show(ui, |ui| {
+                for (index, (button, key_code)) in
+                    self.gamepad_button_mapping.iter_mut().enumerate()
+                {
+                    ComboBox::from_id_salt(("profile-dialog-gamepad-button", index))
+                        .selected_text(button.to_string())
+                        .show_ui(ui, |ui| {
+                            for variant in GamepadButton::value_variants() {
+                                ui.selectable_value(button, *variant, variant.to_string());
+                            }
+                        });
+                    ui.add(DragValue::new(key_code).range(0..=255))
+                        .on_hover_text(text(locale, "profile-dialog-key-code-tooltip"));
+                    if ui.button(text(locale, "remove")).clicked() {
+                        removed = Some(index);
+                    }
+                    ui.end_row();
+                }
+            });
+        if let Some(index) = removed {
+            self.gamepad_button_mapping.remove(index);
+        }
+
+        if ui
+            .button(text(locale, "profile-dialog-gamepad-add"))
+            .clicked()
+        {
+            self.gamepad_button_mapping
+                .push((GamepadButton::South, KeyCode::SPACE.value()));
+        }
+    }
+
+    fn player_settings(&mut self, locale: &LanguageIdentifier, ui: &mut Ui) {
+        ui.label(text(locale, "profile-dialog-player-settings-description"));
+        if self.player_summary.is_empty() {
+            ui.weak(text(locale, "profile-dialog-player-settings-none"));
+        } else {
+            ui.monospace(&self.player_summary);
+        }
+        if ui
+            .add_enabled(
+                self.player != PlayerOptions::default(),
+                Button::new(text(locale, "profile-dialog-player-settings-clear")),
+            )
+            .clicked()
+        {
+            self.player = PlayerOptions::default();
+            self.player_summary.clear();
+        }
+    }
+}
@@ -0,0 +1,758 @@
+use crate::gui::{FilePicker, text, text_with_args};
+use egui::{Align2, CollapsingHeader, DragValue, Layout, TextEdit, Ui, Window};
+use flash_lso::types::{Element, Lso, Value};
+use fluent_templates::fluent_bundle::FluentValue;
+use rfd::{AsyncFileDialog, FileHandle};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Component, Path, PathBuf};
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use unic_langid::LanguageIdentifier;
+use url::Url;
+use walkdir::WalkDir;
+
+/// The directory name used by Flash Player to store shared objects.
+///
+/// Its layout is `#SharedObjects/<random id>/<domain>/<path>/<name>.sol`,
+/// while Ruffle stores the same file as `<domain>/<path>/<name>.sol`.
+const FLASH_SHARED_OBJECTS_DIR: &str = "#SharedObjects";
+
+/// The directory that the shared objects of a movie loaded from `url` are stored in.
+///
+/// This matches how `SharedObject.getLocal` names them.
+pub fn shared_object_domain(url: &Url) -> String {
+    if url.scheme() == "file" {
+        "localhost".to_string()
+    } else {
+        url.host_str().unwrap_or_default().to_string()
+    }
+}
+
+#[derive(Clone)]
+struct SharedObjectFile {
+    path: PathBuf,
+    domain: String,
+    /// Path of the shared object relative to its domain, without the extension.
+    local_name: String,
+    size: u64,
+}
+
+struct OpenedSharedObject {
+    file: SharedObjectFile,
+    lso: Option<Lso>,
+    modified: bool,
+}
+
+impl OpenedSharedObject {
+    fn load(file: SharedObjectFile) -> Self {
+        let lso = std::fs::read(&file.path)
+            .ok()
+            .and_then(|bytes| flash_lso::read::Reader::default().parse(&bytes).ok());
+        Self {
+            file,
+            lso,
+            modified: false,
+        }
+    }
+
+    fn save(&mut self) -> Result<(), ()> {
+        let Some(lso) = &mut self.lso else {
+            return Err(());
+        };
+        let bytes = flash_lso::write::write_to_bytes(lso).map_err(|_| ())?;
+        std::fs::write(&self.file.path, bytes).map_err(|e| {
+            tracing::warn!(
+                "Couldn't write shared object {}: {e}",
+                self.file.path.to_string_lossy()
+            );
+        })?;
+        self.modified = false;
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum OperationResult {
+    Exported,
+    Imported(usize),
+    UserCanceled,
+    Failed,
+}
+
+pub struct SharedObjectsDialog {
+    picker: FilePicker,
+    save_directory: PathBuf,
+
+    /// All shared objects found in the save directory, grouped by domain.
+    shared_objects: BTreeMap<String, Vec<SharedObjectFile>>,
+    selected: Option<OpenedSharedObject>,
+    search: String,
+
+    /// Result of the last import or export, which run in the background.
+    pending_result: Arc<Mutex<Option<OperationResult>>>,
+    last_result: Option<OperationResult>,
+}
+
+impl SharedObjectsDialog {
+    pub fn new(save_directory: PathBuf, picker: FilePicker) -> Self {
+        let mut dialog = Self {
+            picker,
+            save_directory,
+            shared_objects: BTreeMap::new(),
+            selected: None,
+            search: String::new(),
+            pending_result: Default::default(),
+            last_result: None,
+        };
+        dialog.refresh();
+        dialog
+    }
+
+    fn refresh(&mut self) {
+        self.shared_objects.clear();
+
+        for entry in WalkDir::new(&self.save_directory)
+            .into_iter()
+            .filter_map(Result::ok)
+            .filter(|e| e.file_type().is_file())
+        {
+            let path = entry.path();
+            if path.extension().is_none_or(|ext| ext != "sol") {
+                continue;
+            }
+            let Ok(relative) = path.strip_prefix(&self.save_directory) else {
+                continue;
+            };
+            let relative = relative.with_extension("");
+            let mut components = relative.iter();
+            let Some(domain) = components.next() else {
+                continue;
+            };
+            let local_name = components
+                .map(|c| c.to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            if local_name.is_empty() {
+                continue;
+            }
+
+            let domain = domain.to_string_lossy().into_owned();
+            self.shared_objects
+                .entry(domain.clone())
+                .or_default()
+                .push(SharedObjectFile {
+                    path: path.to_path_buf(),
+                    domain,
+                    local_name,
+                    size: entry.metadata().map(|m| m.len()).unwrap_or_default(),
+                });
+        }
+
+        for files in self.shared_objects.values_mut() {
+            files.sort_by(|a, b| a.local_name.cmp(&b.local_name));
+        }
+
+        if let Some(selected) = &self.selected
+            && !selected.file.path.exists()
+        {
+            self.selected = None;
+        }
+    }
+
+    /// Shows the dialog. Shared objects of `loaded_movie_domain` are read-only, as the
+    /// loaded movie could overwrite any changes made to them.
+    pub fn show(
+        &mut self,
+        locale: &LanguageIdentifier,
+        egui_ctx: &egui::Context,
+        loaded_movie_domain: Option<&str>,
+    ) -> bool {
+        let mut keep_open = true;
+
+        if let Some(result) = self.pending_result.lock().expect("Non-poisoned").take() {
+            if matches!(result, OperationResult::Imported(_)) {
+                self.refresh();
+                if let Some(selected) = self.selected.take() {
+                    self.selected = Some(OpenedSharedObject::load(selected.file));
+                }
+            }
+            self.last_result = Some(result);
+        }
+
+        Window::new(text(locale, "shared-objects-dialog"))
+            .open(&mut keep_open)
+            .anchor(Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .collapsible(false)
+            .default_width(700.0)
+            .default_height(450.0)
+            .show(egui_ctx, |ui| {
+                egui::TopBottomPanel::top("shared-objects-dialog-top-panel").show_inside(
+                    ui,
+                    |ui| {
+                        self.show_toolbar(locale, ui, loaded_movie_domain);
+                    },
+                );
+                egui::SidePanel::left("shared-objects-dialog-list")
+                    .resizable(true)
+                    .default_width(250.0)
+                    .show_inside(ui, |ui| {
+                        self.show_list(locale, ui);
+                    });
+                egui::CentralPanel::default().show_inside(ui, |ui| {
+                    self.show_selected(locale, ui, loaded_movie_domain);
+                });
+            });
+
+        keep_open
+    }
+
+    fn show_toolbar(
+        &mut self,
+        locale: &LanguageIdentifier,
+        ui: &mut Ui,
+        loaded_movie_domain: Option<&str>,
+    ) {
+        ui.horizontal(|ui| {
+            ui.add(
+                TextEdit::singleline(&mut self.search)
+                    .hint_text(text(locale, "shared-objects-dialog-search")),
+            );
+            if ui
+                .button(text(locale, "shared-objects-dialog-refresh"))
+                .clicked()
+            {
+                self.refresh();
+            }
+            if ui
+                .button(text(locale, "shared-objects-dialog-import-flash"))
+                .on_hover_text(text(locale, "shared-objects-dialog-import-flash-hint"))
+                .clicked()
+            {
+                self.import_flash_directory(locale, loaded_movie_domain.map(str::to_owned));
+            }
+        });
+
+        let Some(result) = self.last_result else {
+            return;
+        };
+        let message = match result {
+            OperationResult::Exported => text(locale, "shared-objects-dialog-exported"),
+            OperationResult::Imported(count) => text_with_args(
+                locale,
+                "shared-objects-dialog-imported",
+                &HashMap::from([("count".into(), FluentValue::from(count))]),
+            ),
+            OperationResult::UserCanceled => return,
+            OperationResult::Failed => text(locale, "shared-objects-dialog-operation-failed"),
+        };
+        ui.label(message);
+    }
+
+    fn show_list(&mut self, locale: &LanguageIdentifier, ui: &mut Ui) {
+        if self.shared_objects.is_empty() {
+            ui.centered_and_justified(|ui| {
+                ui.label(text(locale, "shared-objects-dialog-empty"));
+            });
+            return;
+        }
+
+        let search = self.search.to_lowercase();
+        let mut clicked = None;
+        egui::ScrollArea::both()
+            .auto_shrink([false, false])
+            .show(ui, |ui| {
+                for (domain, files) in &self.shared_objects {
+                    let domain_matches = domain.to_lowercase().contains(&search);
+                    let matching: Vec<_> = files
+                        .iter()
+                        .filter(|f| domain_matches || f.local_name.to_lowercase().contains(&search))
+                        .collect();
+                    if matching.is_empty() {
+                        continue;
+                    }
+
+                    CollapsingHeader::new(domain)
+                        .default_open(true)
+                        .show(ui, |ui| {
+                            for file in matching {
+                                let is_selected = self
+                                    .selected
+                                    .as_ref()
+                                    .is_some_and(|s| s.file.path == file.path);
+                                if ui
+                                    .selectable_label(is_selected, &file.local_name)
+                                    .on_hover_text(file.path.to_string_lossy())
+                                    .clicked()
+                                {
+                                    clicked = Some(file.clone());
+                                }
+                            }
+                        });
+                }
+            });
+
+        if let Some(file) = clicked {
+            self.selected = Some(OpenedSharedObject::load(file));
+        }
+    }
+
+    fn show_selected(
+        &mut self,
+        locale: &LanguageIdentifier,
+        ui: &mut Ui,
+        loaded_movie_domain: Option<&str>,
+    ) {
+        let Some(selected) = &mut self.selected else {
+            ui.centered_and_justified(|ui| {
+                ui.label(text(locale, "shared-objects-dialog-not-selected"));
+            });
+            return;
+        };
+
+        ui.horizontal(|ui| {
+            ui.strong(&selected.file.local_name);
+            ui.weak(text_with_args(
+                locale,
+                "shared-objects-dialog-size",
+                &HashMap::from([("bytes".into(), FluentValue::from(selected.file.size))]),
+            ));
+        });
+        let in_use = loaded_movie_domain == Some(selected.file.domain.as_str());
+        if in_use {
+            ui.colored_label(
+                ui.visuals().warn_fg_color,
+                text(locale, "shared-objects-dialog-in-use-warning"),
+            );
+        }
+        ui.separator();
+
+        let mut save = false;
+        let mut revert = false;
+        let mut export = false;
+        let mut import = false;
+        let mut delete = false;
+        ui.horizontal(|ui| {
+            let can_save = !in_use && selected.modified && selected.lso.is_some();
+            save = ui
+                .add_enabled(can_save, egui::Button::new(text(locale, "save")))
+                .clicked();
+            revert = ui
+                .add_enabled(
+                    selected.modified,
+                    egui::Button::new(text(locale, "shared-objects-dialog-revert")),
+                )
+                .clicked();
+            ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                delete = ui
+                    .add_enabled(!in_use, egui::Button::new(text(locale, "remove")))
+                    .clicked();
+                import = ui
+                    .add_enabled(
+                        !in_use,
+                        egui::Button::new(text(locale, "shared-objects-dialog-import")),
+                    )
+                    .on_hover_text(text(locale, "shared-objects-dialog-import-hint"))
+                    .clicked();
+                export = ui
+                    .button(text(locale, "shared-objects-dialog-export"))
+                    .clicked();
+            });
+        });
+        ui.separator();
+
+        egui::ScrollArea::both()
+            .auto_shrink([false, false])
+            .show(ui, |ui| match &mut selected.lso {
+                Some(lso) => {
+                    if lso.body.is_empty() {
+                        ui.label(text(locale, "shared-objects-dialog-no-properties"));
+                    }
+                    let changed = ui
+                        .add_enabled_ui(!in_use, |ui| elements_ui(ui, "root", &mut lso.body))
+                        .inner;
+                    if changed {
+                        selected.modified = true;
+                    }
+                }
+                None => {
+                    ui.label(text(locale, "shared-objects-dialog-invalid"));
+                }
+            });
+
+        if save && selected.save().is_err() {
+            self.last_result = Some(OperationResult::Failed);
+        }
+        if revert {
+            *selected = OpenedSharedObject::load(selected.file.clone());
+        }
+
+        let file = selected.file.clone();
+        if export {
+            self.export(file.clone());
+        }
+        if import {
+            self.import(file.clone());
+        }
+        if delete {
+            if let Err(e) = std::fs::remove_file(&file.path) {
+                tracing::warn!(
+                    "Couldn't remove shared object {}: {e}",
+                    file.path.to_string_lossy()
+                );
+            }
+            self.selected = None;
+            self.refresh();
+        }
+    }
+
+    fn export(&mut self, file: SharedObjectFile) {
+        let file_name = file
+            .path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let dialog = AsyncFileDialog::new()
+            .add_filter("SOL", &["sol"])
+            .set_file_name(file_name);
+        let Some(selected_file) = self.picker.show_dialog(dialog, |d| d.save_file()) else {
+            return;
+        };
+
+        let pending_result = self.pending_result.clone();
+        tokio::spawn(async move {
+            let result = Self::perform_export(selected_file.await, &file.path);
+            *pending_result.lock().expect("Non-poisoned") = Some(result);
+        });
+    }
+
+    fn perform_export(handle: Option<FileHandle>, source: &Path) -> OperationResult {
+        let Some(handle) = handle else {
+            return OperationResult::UserCanceled;
+        };
+        match std::fs::copy(source, handle.path()) {
+            Ok(_) => OperationResult::Exported,
+            Err(e) => {
+                tracing::warn!("Couldn't export shared object: {e}");
+                OperationResult::Failed
+            }
+        }
+    }
+
+    fn import(&mut self, file: SharedObjectFile) {
+        let dialog = AsyncFileDialog::new().add_filter("SOL", &["sol"]);
+        let Some(selected_file) = self.picker.show_dialog(dialog, |d| d.pick_file()) else {
+            return;
+        };
+
+        let pending_result = self.pending_result.clone();
+        tokio::spawn(async move {
+            let result = match selected_file.await {
+                Some(handle) => Self::import_file(handle.path(), &file.path)
+                    .map_or(OperationResult::Failed, |_| OperationResult::Imported(1)),
+                None => OperationResult::UserCanceled,
+            };
+            *pending_result.lock().expect("Non-poisoned") = Some(result);
+        });
+    }
+
+    fn import_flash_directory(
+        &mut self,
+        locale: &LanguageIdentifier,
+        loaded_movie_domain: Option<String>,
+    ) {
+        let dialog = AsyncFileDialog::new()
+            .set_title(text(locale, "shared-objects-dialog-import-flash-title"));
+        let Some(selected_dir) = self.picker.show_dialog(dialog, |d| d.pick_folder()) else {
+            return;
+        };
+
+        let save_directory = self.save_directory.clone();
+        let pending_result = self.pending_result.clone();
+        tokio::spawn(async move {
+            let result = match selected_dir.await {
+                Some(handle) => OperationResult::Imported(Self::import_directory(
+                    handle.path(),
+                    &save_directory,
+                    loaded_movie_domain.as_deref(),
+                )),
+                None => OperationResult::UserCanceled,
+            };
+            *pending_result.lock().expect("Non-poisoned") = Some(result);
+        });
+    }
+
+    /// Copy all valid shared objects from a Flash Player directory into the save directory,
+    /// returning how many were imported.
+    ///
+    /// Shared objects of `skipped_domain` are left alone, as the loaded movie may be using them.
+    fn import_directory(
+        source: &Path,
+        save_directory: &Path,
+        skipped_domain: Option<&str>,
+    ) -> usize {
+        let mut imported = 0;
+        for entry in WalkDir::new(source)
+            .into_iter()
+            .filter_map(Result::ok)
+            .filter(|e| e.file_type().is_file())
+        {
+            let path = entry.path();
+            if path.extension().is_none_or(|ext| ext != "sol") {
+                continue;
+            }
+            let Some(relative) = map_flash_path(source, path) else {
+                tracing::warn!(
+                    "Skipping shared object with unexpected path: {}",
+                    path.to_string_lossy()
+                );
+                continue;
+            };
+            if let Some(domain) = skipped_domain
+                && relative.iter().next().is_some_and(|first| first == domain)
+            {
+                tracing::warn!(
+                    "Not importing {}, the loaded movie may be using it",
+                    path.to_string_lossy()
+                );
+                continue;
+            }
+            if Self::import_file(path, &save_directory.join(relative)).is_ok() {
+                imported += 1;
+            }
+        }
+        imported
+    }
+
+    fn import_file(source: &Path, destination: &Path) -> Result<(), ()> {
+        let bytes = std::fs::read(source).map_err(|e| {
+            tracing::warn!("Couldn't read {}: {e}", source.to_string_lossy());
+        })?;
+        if flash_lso::read::Reader::default().parse(&bytes).is_err() {
+            tracing::warn!(
+                "Not importing {}, it's not a valid shared object",
+                source.to_string_lossy()
+            );
+            return Err(());
+        }
+        if let Some(parent) = destination.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| {
+                tracing::warn!("Couldn't create {}: {e}", parent.to_string_lossy());
+            })?;
+        }
+        std::fs::write(destination, bytes).map_err(|e| {
+            tracing::warn!("Couldn't write {}: {e}", destination.to_string_lossy());
+        })
+    }
+}
+
+/// Map a shared object stored by Flash Player onto a path relative to Ruffle's save directory.
+///
+/// `root` is the directory picked by the user, which may be `#SharedObjects` itself,
+/// the random id directory inside it, or a directory containing domains directly.
+fn map_flash_path(root: &Path, path: &Path) -> Option<PathBuf> {
+    let components: Vec<_> = path.components().collect();
+    let relative: Vec<_> = match components
+        .iter()
+        .rposition(|c| c.as_os_str() == FLASH_SHARED_OBJECTS_DIR)
+    {
+        // Skip `#SharedObjects` and the random id following it.
+        Some(index) => components[index + 2..].to_vec(),
+        None => path.strip_prefix(root).ok()?.components().collect(),
+    };
+
+    // There must be at least a domain and a file name.
+    if relative.len() < 2 || !relative.iter().all(|c| matches!(c, Component::Normal(_))) {
+        return None;
+    }
+    Some(relative.iter().collect())
+}
+
+/// Show an editable list of AMF properties, returning whether anything changed.
+fn elements_ui(ui: &mut Ui, id: &str, elements: &mut [Element]) -> bool {
+    let mut changed = false;
+    for element in elements.iter_mut() {
+        let id = format!("{id}.{}", element.name);
+        value_ui(ui, &id, &element.name, &mut element.value, &mut changed);
+    }
+    changed
+}
+
+/// Show an editable list of unnamed AMF values, returning whether anything changed.
+fn values_ui(ui: &mut Ui, id: &str, values: &mut [Rc<Value>]) -> bool {
+    let mut changed = false;
+    for (index, value) in values.iter_mut().enumerate() {
+        value_ui(
+            ui,
+            &format!("{id}[{index}]"),
+            &index.to_string(),
+            value,
+            &mut changed,
+        );
+    }
+    changed
+}
+
+fn value_ui(ui: &mut Ui, id: &str, name: &str, value: &mut Rc<Value>, changed: &mut bool) {
+    if is_container(value) {
+        let header = format!("{name}: {}", type_name(value));
+        CollapsingHeader::new(header).id_salt(id).show(ui, |ui| {
+            *changed |= container_ui(ui, id, Rc::make_mut(value));
+        });
+        return;
+    }
+
+    ui.horizontal(|ui| {
+        ui.label(format!("{name}:"));
+        *changed |= primitive_ui(ui, Rc::make_mut(value));
+        ui.weak(type_name(value));
+    });
+}
+
+fn is_container(value: &Value) -> bool {
+    match value {
+        Value::AMF3(inner) => is_container(inner),
+        Value::Object(..)
+        | Value::ECMAArray(..)
+        | Value::StrictArray(..)
+        | Value::VectorInt(..)
+        | Value::VectorUInt(..)
+        | Value::VectorDouble(..)
+        | Value::VectorObject(..)
+        | Value::Dictionary(..) => true,
+        _ => false,
+    }
+}
+
+fn container_ui(ui: &mut Ui, id: &str, value: &mut Value) -> bool {
+    match value {
+        Value::AMF3(inner) => container_ui(ui, id, Rc::make_mut(inner)),
+        Value::Object(_, elements, _) => elements_ui(ui, id, elements),
+        Value::ECMAArray(_, dense, associative, _) => {
+            let changed = values_ui(ui, id, dense);
+            elements_ui(ui, id, associative) || changed
+        }
+        Value::StrictArray(_, values) | Value::VectorObject(_, values, _, _) => {
+            values_ui(ui, id, values)
+        }
+        Value::VectorInt(values, _) => numbers_ui(ui, values),
+        Value::VectorUInt(values, _) => numbers_ui(ui, values),
+        Value::VectorDouble(values, _) => numbers_ui(ui, values),
+        Value::Dictionary(_, entries, _) => {
+            let mut changed = false;
+            for (index, (key, value)) in entries.iter_mut().enumerate() {
+                let name = summary(key);
+                value_ui(ui, &format!("{id}{{{index}}}"), &name, value, &mut changed);
+            }
+            changed
+        }
+        _ => false,
+    }
+}
+
+fn numbers_ui<T: egui::emath::Numeric>(ui: &mut Ui, values: &mut [T]) -> bool {
+    let mut changed = false;
+    for (index, value) in values.iter_mut().enumerate() {
+        ui.horizontal(|ui| {
+            ui.label(format!("{index}:"));
+            changed |= ui.add(DragValue::new(value)).changed();
+        });
+    }
+    changed
+}
+
+fn primitive_ui(ui: &mut Ui, value: &mut Value) -> bool {
+    match value {
+        Value::AMF3(inner) => primitive_ui(ui, Rc::make_mut(inner)),
+        Value::Number(n) => ui.add(DragValue::new(n)).changed(),
+        Value::Integer(n) => ui.add(DragValue::new(n)).changed(),
+        Value::Bool(b) => ui.checkbox(b, "").changed(),
+        Value::String(s) => ui.text_edit_singleline(s).changed(),
+        Value::XML(s, _) => ui.text_edit_multiline(s).changed(),
+        Value::Date(time, _) => {
+            let response = ui.add(DragValue::new(time));
+            if let Some(date) = chrono::DateTime::from_timestamp_millis(*time as i64) {
+                ui.weak(date.to_rfc3339());
+            }
+            response.changed()
+        }
+        Value::ByteArray(bytes) => {
+            ui.label(bytes.len().to_string());
+            false
+        }
+        _ => {
+            ui.label(summary(value));
+            false
+        }
+    }
+}
+
+fn summary(value: &Value) -> String {
+    match value {
+        Value::AMF3(inner) => summary(inner),
+        Value::Number(n) => n.to_string(),
+        Value::Integer(n) => n.to_string(),
+        Value::Bool(b) => b.to_string(),
+        Value::String(s) => format!("{s:?}"),
+        Value::Null => "null".to_string(),
+        Value::Undefined => "undefined".to_string(),
+        other => type_name(other).to_string(),
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::AMF3(inner) => type_name(inner),
+        Value::Number(_) => "Number",
+        Value::Integer(_) => "int",
+        Value::Bool(_) => "Boolean",
+        Value::String(_) => "String",
+        Value::Null => "null",
+        Value::Undefined => "undefined",
+        Value::Object(..) => "Object",
+        Value::ECMAArray(..) | Value::StrictArray(..) => "Array",
+        Value::Date(..) => "Date",
+        Value::XML(..) => "XML",
+        Value::ByteArray(_) => "ByteArray",
+        Value::VectorInt(..) => "Vector.<int>",
+        Value::VectorUInt(..) => "Vector.<uint>",
+        Value::VectorDouble(..) => "Vector.<Number>",
+        Value::VectorObject(..) => "Vector.<Object>",
+        Value::Dictionary(..) => "Dictionary",
+        Value::Custom(..) => "Custom",
+        Value::Reference(_) | Value::Amf3ObjectReference(_) => "Reference",
+        Value::Unsupported => "Unsupported",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn map_flash_path_from_shared_objects_dir() {
+        let root = Path::new("/home/user/.macromedia/Flash_Player/#SharedObjects");
+        assert_eq!(
+            map_flash_path(root, &root.join("ABCD1234/example.com/game.swf/save.sol")),
+            Some(PathBuf::from("example.com/game.swf/save.sol"))
+        );
+    }
+
+    #[test]
+    fn map_flash_path_from_id_dir() {
+        let root = Path::new("/flash/#SharedObjects/ABCD1234");
+        assert_eq!(
+            map_flash_path(root, &root.join("localhost/games/game.swf/save.sol")),
+            Some(PathBuf::from("localhost/games/game.swf/save.sol"))
+        );
+    }
+
+    #[test]
+    fn map_flash_path_from_plain_dir() {
+        let root = Path::new("/backup");
+        assert_eq!(
+            map_flash_path(root, &root.join("example.com/save.sol")),
+            Some(PathBuf::from("example.com/save.sol"))
+        );
+        assert_eq!(map_flash_path(root, &root.join("save.sol")), None);
+    }
+}
@@ -2,12 +2,17 @@ use crate::gui::text;
 use crate::preferences::GlobalPreferences;
 use egui::{Align2, Slider};
 use ruffle_core::Player;
+use ruffle_frontend_utils::profiles::{Profile, ProfileKey};
 use unic_langid::LanguageIdentifier;
 
 /// The volume controls of the Ruffle GUI.
 pub struct VolumeControls {
     is_muted: bool,
     volume: f32,
+
+    /// The profile of the current movie and the volume it remembers, if it does.
+    /// The slider shows and changes this volume instead while it's set.
+    profile: Option<(ProfileKey, f32)>,
 }
 
 impl VolumeControls {
@@ -15,6 +20,22 @@ impl VolumeControls {
         Self {
             is_muted: preferences.mute(),
             volume: preferences.preferred_volume() * 100.0,
+            profile: None,
+        }
+    }
+
+    /// Uses the volume remembered by the profile of a newly opened movie, if any.
+    pub fn set_profile(&mut self, profile: Option<&Profile>) {
+        self.profile = profile.and_then(|profile| {
+            let volume = profile.volume?;
+            Some((profile.key.clone(), volume * 100.0))
+        });
+    }
+
+    fn volume_mut(&mut self) -> &mut f32 {
+        match &mut self.profile {
+            Some((_, volume)) => volume,
+            None => &mut self.volume,
         }
     }
 
@@ -42,8 +63,9 @@ impl VolumeControls {
                 ui.add_enabled_ui(!self.is_muted, |ui| {
                     ui.horizontal(|ui| {
                         ui.label(text(locale, "volume-controls-volume"));
-                        changed_slider =
-                            ui.add(Slider::new(&mut self.volume, 0.0..=100.0)).changed();
+                        changed_slider = ui
+                            .add(Slider::new(self.volume_mut(), 0.0..=100.0))
+                            .changed();
                     });
                 });
 
@@ -51,13 +73,17 @@ impl VolumeControls {
                     if let Some(player) = player {
                         player.set_volume(self.get_volume());
                     }
+                    if changed_slider && let Some((key, volume)) = &self.profile {
+                        save_profile_volume(preferences, key, *volume / 100.0);
+                    }
                     // Don't update persisted volume if the CLI set it
                     if preferences.cli.volume.is_none()
+                        && (changed_checkbox || self.profile.is_none())
                         && let Err(e) = preferences.write_preferences(|writer| {
                             if changed_checkbox {
                                 writer.set_mute(self.is_muted);
                             }
-                            if changed_slider {
+                            if changed_slider && self.profile.is_none() {
                                 writer.set_volume(self.volume / 100.0);
                             }
                         })
@@ -74,9 +100,24 @@ impl VolumeControls {
     /// checkbox and the slider).
     pub fn get_volume(&self) -> f32 {
         if !self.is_muted {
-            self.volume / 100.0
+            self.profile
+                .as_ref()
+                .map_or(self.volume, |(_, volume)| *volume)
+                / 100.0
         } else {
             0.0
         }
     }
 }
+
+fn save_profile_volume(preferences: &GlobalPreferences, key: &ProfileKey, volume: f32) {
+    // Look the profile up again, as it may have been edited or removed since the movie was opened.
+    let Some(index) =
+        preferences.profiles(|profiles| profiles.iter().position(|profile| profile.key == *key))
+    else {
+        return;
+    };
+    if let Err(e) = preferences.write_profiles(|writer| writer.set_volume(index, Some(volume))) {
+        tracing::warn!("Couldn't update profile volume: {e}");
+    }
+}
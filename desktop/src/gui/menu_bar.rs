@@ -1,3 +1,4 @@
+use crate::capture::Capture;
 use crate::custom_event::{OpenType, RuffleEvent};
 use crate::gui::dialogs::Dialogs;
 use crate::gui::{DebugMessage, text};
@@ -32,6 +33,21 @@ impl MenuBar {
     const SHORTCUT_PAUSE: KeyboardShortcut = KeyboardShortcut::new(Modifiers::COMMAND, Key::P);
     const SHORTCUT_STEP: KeyboardShortcut = KeyboardShortcut::new(Modifiers::COMMAND, Key::Space);
     const SHORTCUT_QUIT: KeyboardShortcut = KeyboardShortcut::new(Modifiers::COMMAND, Key::Q);
+    const SHORTCUT_SPEED_UP: KeyboardShortcut =
+        KeyboardShortcut::new(Modifiers::COMMAND, Key::CloseBracket);
+    const SHORTCUT_SLOW_DOWN: KeyboardShortcut =
+        KeyboardShortcut::new(Modifiers::COMMAND, Key::OpenBracket);
+    const SHORTCUT_NORMAL_SPEED: KeyboardShortcut =
+        KeyboardShortcut::new(Modifiers::COMMAND, Key::Backspace);
+
+    /// The playback speeds offered in the menu, from slowest to fastest.
+    const PLAYBACK_SPEEDS: [(&str, f64); 5] = [
+        ("controls-menu-speed-quarter", 0.25),
+        ("controls-menu-speed-half", 0.5),
+        ("controls-menu-speed-normal", 1.0),
+        ("controls-menu-speed-double", 2.0),
+        ("controls-menu-speed-quadruple", 4.0),
+    ];
 
     pub fn new(
         event_loop: EventLoopProxy<RuffleEvent>,
@@ -51,6 +67,7 @@ impl MenuBar {
         &self,
         egui_ctx: &egui::Context,
         dialogs: &mut Dialogs,
+        capture: &mut Capture,
         mut player: Option<&mut Player>,
     ) {
         // TODO(mike): Make some MenuItem struct with shortcut info to handle this more cleanly.
@@ -73,6 +90,29 @@ impl MenuBar {
             {
                 player.suspend_after_next_frame();
             }
+            // These are common shortcuts in movies too, so they're only
+            // taken away from the movie when asked for.
+            if self.preferences.speed_hotkeys_enabled() {
+                if egui_ctx.input_mut(|input| input.consume_shortcut(&Self::SHORTCUT_SPEED_UP)) {
+                    Self::change_speed(player, true);
+                }
+                if egui_ctx.input_mut(|input| input.consume_shortcut(&Self::SHORTCUT_SLOW_DOWN)) {
+                    Self::change_speed(player, false);
+                }
+                if egui_ctx.input_mut(|input| input.consume_shortcut(&Self::SHORTCUT_NORMAL_SPEED))
+                {
+                    player.set_time_scale(1.0);
+                }
+            }
+
+            let screenshot_hotkey = self.preferences.screenshot_hotkey();
+            if egui_ctx.input_mut(|input| input.consume_shortcut(&screenshot_hotkey.0)) {
+                capture.request_screenshot();
+            }
+            let record_hotkey = self.preferences.record_hotkey();
+            if egui_ctx.input_mut(|input| input.consume_shortcut(&record_hotkey.0)) {
+                capture.toggle_recording();
+            }
         }
 
         let mut fullscreen_pressed =
@@ -95,11 +135,12 @@ impl MenuBar {
         locale: &LanguageIdentifier,
         egui_ctx: &egui::Context,
         dialogs: &mut Dialogs,
+        capture: &mut Capture,
         mut player: Option<&mut Player>,
     ) {
         egui::TopBottomPanel::top("menu_bar").show(egui_ctx, |ui| {
              egui::MenuBar::new().ui(ui, |ui| {
-                self.file_menu(locale, ui, dialogs, player.is_some());
+                self.file_menu(locale, ui, dialogs, capture, player.is_some());
                 self.view_menu(locale, ui, &mut player);
                 self.controls_menu(locale, ui, dialogs, &mut player);
                 ui.menu_button( text(locale, "bookmarks-menu"), |ui| {
@@ -168,6 +209,19 @@ impl MenuBar {
                                 player.debug_ui().queue_message(DebugMessage::ShowDomains);
                             }
                         }
+                        if Button::new(text(locale, "debug-menu-open-network")).ui(ui).clicked() {
+                            ui.close();
+                            if let Some(player) = &mut player {
+                                player.debug_ui().queue_message(DebugMessage::ShowNetworkActivity);
+                            }
+                        }
+                        if let Some(player) = &mut player {
+                            let mut shown = player.debug_ui().is_performance_hud_shown();
+                            if ui.checkbox(&mut shown, text(locale, "debug-menu-performance-hud")).clicked() {
+                                ui.close();
+                                player.debug_ui().queue_message(DebugMessage::TogglePerformanceHud);
+                            }
+                        }
                         ui.separator();
                         if Button::new(text(locale, "debug-menu-search-display-objects")).ui(ui).clicked() {
                             ui.close();
@@ -175,6 +229,19 @@ impl MenuBar {
                                 player.debug_ui().queue_message(DebugMessage::SearchForDisplayObject);
                             }
                         }
+                        if Button::new(text(locale, "debug-menu-search-variables")).ui(ui).clicked() {
+                            ui.close();
+                            if let Some(player) = &mut player {
+                                player.debug_ui().queue_message(DebugMessage::SearchForVariable);
+                            }
+                        }
+                        #[cfg(feature = "debugger")]
+                        if Button::new(text(locale, "debug-menu-open-debugger")).ui(ui).clicked() {
+                            ui.close();
+                            if let Some(player) = &mut player {
+                                player.debug_ui().queue_message(DebugMessage::ShowDebugger);
+                            }
+                        }
                     });
                 });
                 ui.menu_button(text(locale, "help-menu"), |ui| {
@@ -196,6 +263,10 @@ impl MenuBar {
                         ui.close();
                     }
                 });
+                if capture.is_recording() {
+                    let color = ui.visuals().error_fg_color;
+                    ui.colored_label(color, text(locale, "recording-indicator"));
+                }
             });
         });
     }
@@ -205,6 +276,7 @@ impl MenuBar {
         locale: &LanguageIdentifier,
         ui: &mut egui::Ui,
         dialogs: &mut Dialogs,
+        capture: &mut Capture,
         player_exists: bool,
     ) {
         ui.menu_button(text(locale, "file-menu"), |ui| {
@@ -302,8 +374,39 @@ impl MenuBar {
             {
                 self.export_bundle(ui);
             }
+
+            let screenshot_button = Button::new(text(locale, "file-menu-save-screenshot"))
+                .shortcut_text(
+                    ui.ctx()
+                        .format_shortcut(&self.preferences.screenshot_hotkey().0),
+                );
+            if ui.add_enabled(player_exists, screenshot_button).clicked() {
+                ui.close();
+                capture.request_screenshot();
+            }
+
+            let record_text = if capture.is_recording() {
+                "file-menu-stop-recording"
+            } else {
+                "file-menu-start-recording"
+            };
+            let record_button = Button::new(text(locale, record_text)).shortcut_text(
+                ui.ctx()
+                    .format_shortcut(&self.preferences.record_hotkey().0),
+            );
+            if ui.add_enabled(player_exists, record_button).clicked() {
+                ui.close();
+                capture.toggle_recording();
+            }
             ui.separator();
 
+            if Button::new(text(locale, "file-menu-shared-objects"))
+                .ui(ui)
+                .clicked()
+            {
+                ui.close();
+                dialogs.open_shared_objects();
+            }
             if Button::new(text(locale, "file-menu-preferences"))
                 .ui(ui)
                 .clicked()
@@ -484,6 +587,64 @@ impl MenuBar {
                         }
                     }
                 });
+
+                ui.menu_button(text(locale, "controls-menu-speed"), |ui| {
+                    let current_speed = player.as_ref().map(|player| player.time_scale());
+                    for (id, speed) in Self::PLAYBACK_SPEEDS {
+                        let clicked = if Some(speed) == current_speed {
+                            ui.checkbox(&mut true, text(locale, id)).clicked()
+                        } else {
+                            ui.button(text(locale, id)).clicked()
+                        };
+                        if clicked {
+                            ui.close();
+                            if let Some(player) = player {
+                                player.set_time_scale(speed);
+                            }
+                        }
+                    }
+                    ui.separator();
+
+                    let speed_hotkeys = self.preferences.speed_hotkeys_enabled();
+                    let ctx = ui.ctx().clone();
+                    let shortcut_text = |shortcut: &KeyboardShortcut| {
+                        if speed_hotkeys {
+                            ctx.format_shortcut(shortcut)
+                        } else {
+                            String::new()
+                        }
+                    };
+                    if Button::new(text(locale, "controls-menu-speed-faster"))
+                        .shortcut_text(shortcut_text(&Self::SHORTCUT_SPEED_UP))
+                        .ui(ui)
+                        .clicked()
+                    {
+                        ui.close();
+                        if let Some(player) = player {
+                            Self::change_speed(player, true);
+                        }
+                    }
+                    if Button::new(text(locale, "controls-menu-speed-slower"))
+                        .shortcut_text(shortcut_text(&Self::SHORTCUT_SLOW_DOWN))
+                        .ui(ui)
+                        .clicked()
+                    {
+                        ui.close();
+                        if let Some(player) = player {
+                            Self::change_speed(player, false);
+                        }
+                    }
+                    if Button::new(text(locale, "controls-menu-speed-reset"))
+                        .shortcut_text(shortcut_text(&Self::SHORTCUT_NORMAL_SPEED))
+                        .ui(ui)
+                        .clicked()
+                    {
+                        ui.close();
+                        if let Some(player) = player {
+                            player.set_time_scale(1.0);
+                        }
+                    }
+                });
             });
             if Button::new(text(locale, "controls-menu-volume"))
                 .ui(ui)
@@ -492,9 +653,34 @@ impl MenuBar {
                 dialogs.open_volume_controls();
                 ui.close();
             }
+            if Button::new(text(locale, "controls-menu-gamepad"))
+                .ui(ui)
+                .clicked()
+            {
+                dialogs.open_gamepad_profiles();
+                ui.close();
+            }
         });
     }
 
+    /// Moves to the next faster or slower entry in `PLAYBACK_SPEEDS`.
+    fn change_speed(player: &mut Player, faster: bool) {
+        let current_speed = player.time_scale();
+        let speeds = Self::PLAYBACK_SPEEDS.iter().map(|(_, speed)| *speed);
+        let new_speed = if faster {
+            speeds
+                .filter(|speed| *speed > current_speed)
+                .reduce(f64::min)
+        } else {
+            speeds
+                .filter(|speed| *speed < current_speed)
+                .reduce(f64::max)
+        };
+        if let Some(new_speed) = new_speed {
+            player.set_time_scale(new_speed);
+        }
+    }
+
     fn browse_and_open(&self, open_type: OpenType) {
         let _ = self.event_loop.send_event(RuffleEvent::BrowseAndOpen(
             Box::new(self.default_launch_options.clone()),
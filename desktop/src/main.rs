@@ -8,8 +8,11 @@
 
 mod app;
 mod backends;
+mod capture;
 mod cli;
 mod custom_event;
+#[cfg(feature = "debugger")]
+mod dap;
 mod dbus;
 mod gui;
 mod log;
@@ -185,6 +188,11 @@ fn main() -> Result<(), Error> {
 
     subscriber.init();
 
+    #[cfg(feature = "debugger")]
+    if let Some(port) = preferences.cli.dap_port {
+        dap::start(port)?;
+    }
+
     let result = App::new(preferences).and_then(|(mut app, event_loop)| {
         event_loop.run_app(&mut app).context("Event loop failure")
     });
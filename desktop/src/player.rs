@@ -4,6 +4,7 @@ use crate::backends::{
 };
 use crate::cli::FilesystemAccessMode;
 use crate::cli::GameModePreference;
+use crate::cli::TelemetryTarget;
 use crate::custom_event::RuffleEvent;
 use crate::gui::{FilePicker, MovieView};
 use crate::preferences::GlobalPreferences;
@@ -20,6 +21,7 @@ use ruffle_frontend_utils::bundle::source::BundleSourceError;
 use ruffle_frontend_utils::bundle::{Bundle, BundleError};
 use ruffle_frontend_utils::content::{ContentDescriptor, PlayingContent};
 use ruffle_frontend_utils::player_options::PlayerOptions;
+use ruffle_frontend_utils::profiles::Profile;
 use ruffle_frontend_utils::recents::Recent;
 use ruffle_render::backend::RenderBackend;
 use ruffle_render::quality::StageQuality;
@@ -51,6 +53,7 @@ pub struct LaunchOptions {
     pub filesystem_access_mode: FilesystemAccessMode,
     pub gamepad_button_mapping: HashMap<GamepadButton, KeyCode>,
     pub avm2_optimizer_enabled: bool,
+    pub telemetry: Option<TelemetryTarget>,
 }
 
 impl From<&GlobalPreferences> for LaunchOptions {
@@ -101,10 +104,34 @@ impl From<&GlobalPreferences> for LaunchOptions {
             tcp_connections: value.cli.tcp_connections,
             gamepad_button_mapping: HashMap::from_iter(value.cli.gamepad_button.iter().cloned()),
             avm2_optimizer_enabled: !value.cli.no_avm2_optimizer,
+            telemetry: value.cli.telemetry.clone(),
         }
     }
 }
 
+impl LaunchOptions {
+    /// Apply the settings remembered for a movie on top of these options.
+    pub fn apply_profile(&mut self, profile: &Profile) {
+        let player = profile.player.or(&self.player);
+
+        // Parameters from both sides are combined, let the profile override duplicates.
+        let mut parameters: Vec<(String, String)> = Vec::with_capacity(player.parameters.len());
+        for (key, value) in player.parameters.iter().cloned() {
+            match parameters.iter_mut().find(|(existing, _)| *existing == key) {
+                Some(parameter) => parameter.1 = value,
+                None => parameters.push((key, value)),
+            }
+        }
+
+        self.player = PlayerOptions {
+            parameters,
+            ..player
+        };
+        self.gamepad_button_mapping
+            .extend(profile.gamepad_button_mapping.iter().copied());
+    }
+}
+
 /// Represents a current Player and any associated state with that player,
 /// which may be lost when this Player is closed (dropped)
 struct ActivePlayer {
@@ -199,6 +226,7 @@ impl ActivePlayer {
                     filesystem_access_mode: opt.filesystem_access_mode,
                     gamepad_button_mapping: opt.gamepad_button_mapping.clone(),
                     avm2_optimizer_enabled: opt.avm2_optimizer_enabled,
+                    telemetry: opt.telemetry.clone(),
                 })
             }
         };
@@ -286,6 +314,27 @@ impl ActivePlayer {
             builder = builder.with_gamepad_button_mapping(opt.gamepad_button_mapping.clone());
         }
 
+        if let Some(target) = &opt.telemetry {
+            match target.open() {
+                Ok(output) => builder = builder.with_telemetry(output),
+                Err(e) => tracing::error!("Couldn't send telemetry to {target}: {e}"),
+            }
+        }
+
+        // Elsewhere, `LocalConnection`s only work between the movies of this
+        // player. See `DesktopLocalConnectionBackend`.
+        #[cfg(unix)]
+        {
+            builder = builder.with_local_connection(Box::new(
+                crate::backends::DesktopLocalConnectionBackend::new(),
+            ));
+        }
+
+        #[cfg(feature = "debugger")]
+        if let Some(connection) = crate::dap::connect_player() {
+            builder = builder.with_debugger(Box::new(connection));
+        }
+
         let (notification_sender, notification_recv) = async_channel::unbounded();
 
         let event_loop2 = event_loop.clone();
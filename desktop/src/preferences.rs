@@ -3,17 +3,27 @@ mod write;
 
 pub mod storage;
 
+use crate::capture::{Hotkey, RecordingFormat};
 use crate::cli::{GameModePreference, OpenUrlMode, Opt};
 use crate::gui::ThemePreference;
 use crate::log::FilenamePattern;
 use crate::preferences::read::read_preferences;
 use crate::preferences::write::PreferencesWriter;
 use anyhow::{Context, Error};
+use egui::{Key, Modifiers};
 use ruffle_core::backend::ui::US_ENGLISH;
 use ruffle_frontend_utils::bookmarks::{Bookmarks, BookmarksWriter, read_bookmarks};
+use ruffle_frontend_utils::content::ContentDescriptor;
+use ruffle_frontend_utils::gamepad::{
+    GamepadProfile, GamepadProfiles, GamepadProfilesWriter, read_gamepad_profiles,
+};
 use ruffle_frontend_utils::parse::DocumentHolder;
+use ruffle_frontend_utils::profiles::{
+    Profile, Profiles, ProfilesWriter, find_profile, local_content_hash, read_profiles,
+};
 use ruffle_frontend_utils::recents::{Recents, RecentsWriter, read_recents};
 use ruffle_render_wgpu::clap::{GraphicsBackend, PowerPreference};
+use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 use sys_locale::get_locale;
 use tokio::sync::broadcast;
@@ -26,7 +36,7 @@ use unic_langid::LanguageIdentifier;
 ///
 /// The general priority order for preferences should look as follows, where top is "highest priority":
 /// - User-selected movie-specific setting (if applicable, such as through Open Advanced)
-/// - Movie-specific settings (if applicable and we implement this, stored on disk)
+/// - Movie-specific settings (if applicable, stored on disk as profiles)
 /// - CLI (if applicable)
 /// - Persisted preferences (if applicable, saved to toml)
 /// - Ruffle defaults
@@ -46,6 +56,10 @@ pub struct GlobalPreferences {
 
     recents: Arc<Mutex<DocumentHolder<Recents>>>,
 
+    profiles: Arc<Mutex<DocumentHolder<Profiles>>>,
+
+    gamepad_profiles: Arc<Mutex<DocumentHolder<GamepadProfiles>>>,
+
     watchers: GlobalPreferencesWatchers,
 }
 
@@ -92,11 +106,39 @@ impl GlobalPreferences {
             Default::default()
         };
 
+        let profiles_path = cli.config.join("profiles.toml");
+        let profiles = if profiles_path.exists() {
+            let contents =
+                std::fs::read_to_string(&profiles_path).context("Failed to read saved profiles")?;
+            let result = read_profiles(&contents);
+            for warning in result.warnings {
+                tracing::warn!("{warning}");
+            }
+            result.result
+        } else {
+            Default::default()
+        };
+
+        let gamepad_profiles_path = cli.config.join("gamepad.toml");
+        let gamepad_profiles = if gamepad_profiles_path.exists() {
+            let contents = std::fs::read_to_string(&gamepad_profiles_path)
+                .context("Failed to read saved gamepad profiles")?;
+            let result = read_gamepad_profiles(&contents);
+            for warning in result.warnings {
+                tracing::warn!("{warning}");
+            }
+            result.result
+        } else {
+            Default::default()
+        };
+
         Ok(Self {
             cli,
             preferences: Arc::new(Mutex::new(preferences)),
             bookmarks: Arc::new(Mutex::new(bookmarks)),
             recents: Arc::new(Mutex::new(recents)),
+            profiles: Arc::new(Mutex::new(profiles)),
+            gamepad_profiles: Arc::new(Mutex::new(gamepad_profiles)),
             watchers: Default::default(),
         })
     }
@@ -167,6 +209,15 @@ impl GlobalPreferences {
             .enable_openh264
     }
 
+    /// Whether the playback speed shortcuts are handled, rather than passed
+    /// on to the movie.
+    pub fn speed_hotkeys_enabled(&self) -> bool {
+        self.preferences
+            .lock()
+            .expect("Preferences is not reentrant")
+            .speed_hotkeys
+    }
+
     pub fn log_filename_pattern(&self) -> FilenamePattern {
         self.preferences
             .lock()
@@ -229,10 +280,79 @@ impl GlobalPreferences {
             .ime_enabled
     }
 
+    /// Where screenshots and recordings are saved.
+    pub fn capture_directory(&self) -> PathBuf {
+        self.preferences
+            .lock()
+            .expect("Preferences is not reentrant")
+            .capture
+            .directory
+            .clone()
+            .unwrap_or_else(get_default_capture_directory)
+    }
+
+    /// Whether captures use the movie's own size, rather than the size it's displayed at.
+    pub fn capture_native_size(&self) -> bool {
+        self.preferences
+            .lock()
+            .expect("Preferences is not reentrant")
+            .capture
+            .native_size
+    }
+
+    pub fn recording_format(&self) -> RecordingFormat {
+        self.preferences
+            .lock()
+            .expect("Preferences is not reentrant")
+            .capture
+            .recording_format
+    }
+
+    pub fn screenshot_hotkey(&self) -> Hotkey {
+        self.preferences
+            .lock()
+            .expect("Preferences is not reentrant")
+            .capture
+            .screenshot_hotkey
+    }
+
+    pub fn record_hotkey(&self) -> Hotkey {
+        self.preferences
+            .lock()
+            .expect("Preferences is not reentrant")
+            .capture
+            .record_hotkey
+    }
+
     pub fn recents<R>(&self, fun: impl FnOnce(&Recents) -> R) -> R {
         fun(&self.recents.lock().expect("Recents is not reentrant"))
     }
 
+    pub fn profiles<R>(&self, fun: impl FnOnce(&Profiles) -> R) -> R {
+        fun(&self.profiles.lock().expect("Profiles is not reentrant"))
+    }
+
+    /// Find the profile remembered for the given content, along with its index.
+    pub fn find_profile(&self, content_descriptor: &ContentDescriptor) -> Option<(usize, Profile)> {
+        self.profiles(|profiles| {
+            find_profile(profiles, &content_descriptor.url, || {
+                local_content_hash(&content_descriptor.url)
+            })
+            .map(|(index, profile)| (index, profile.clone()))
+        })
+    }
+
+    pub fn gamepad_profiles<R>(&self, fun: impl FnOnce(&GamepadProfiles) -> R) -> R {
+        fun(&self
+            .gamepad_profiles
+            .lock()
+            .expect("Gamepad profiles is not reentrant"))
+    }
+
+    pub fn active_gamepad_profile(&self) -> Option<GamepadProfile> {
+        self.gamepad_profiles(|profiles| profiles.active_profile().cloned())
+    }
+
     pub fn write_preferences(&self, fun: impl FnOnce(&mut PreferencesWriter)) -> Result<(), Error> {
         let mut preferences = self
             .preferences
@@ -269,6 +389,34 @@ impl GlobalPreferences {
         std::fs::write(self.cli.config.join("recents.toml"), serialized)
             .context("Could not write recents to disk")
     }
+
+    pub fn write_profiles(&self, fun: impl FnOnce(&mut ProfilesWriter)) -> Result<(), Error> {
+        let mut profiles = self.profiles.lock().expect("Profiles is not reentrant");
+
+        let mut writer = ProfilesWriter::new(&mut profiles);
+        fun(&mut writer);
+
+        let serialized = profiles.serialize();
+        std::fs::write(self.cli.config.join("profiles.toml"), serialized)
+            .context("Could not write profiles to disk")
+    }
+
+    pub fn write_gamepad_profiles(
+        &self,
+        fun: impl FnOnce(&mut GamepadProfilesWriter),
+    ) -> Result<(), Error> {
+        let mut gamepad_profiles = self
+            .gamepad_profiles
+            .lock()
+            .expect("Gamepad profiles is not reentrant");
+
+        let mut writer = GamepadProfilesWriter::new(&mut gamepad_profiles);
+        fun(&mut writer);
+
+        let serialized = gamepad_profiles.serialize();
+        std::fs::write(self.cli.config.join("gamepad.toml"), serialized)
+            .context("Could not write gamepad profiles to disk")
+    }
 }
 
 #[derive(PartialEq, Debug)]
@@ -281,12 +429,14 @@ pub struct SavedGlobalPreferences {
     pub mute: bool,
     pub volume: f32,
     pub enable_openh264: bool,
+    pub speed_hotkeys: bool,
     pub recent_limit: usize,
     pub log: LogPreferences,
     pub storage: StoragePreferences,
     pub theme_preference: ThemePreference,
     pub open_url_mode: OpenUrlMode,
     pub ime_enabled: Option<bool>,
+    pub capture: CapturePreferences,
 }
 
 impl Default for SavedGlobalPreferences {
@@ -305,12 +455,14 @@ impl Default for SavedGlobalPreferences {
             mute: false,
             volume: 1.0,
             enable_openh264: true,
+            speed_hotkeys: false,
             recent_limit: 10,
             log: Default::default(),
             storage: Default::default(),
             theme_preference: Default::default(),
             open_url_mode: Default::default(),
             ime_enabled: None,
+            capture: Default::default(),
         }
     }
 }
@@ -325,6 +477,35 @@ pub struct StoragePreferences {
     pub backend: storage::StorageBackend,
 }
 
+#[derive(PartialEq, Debug)]
+pub struct CapturePreferences {
+    /// `None` uses the default directory, see [`get_default_capture_directory`].
+    pub directory: Option<PathBuf>,
+    pub native_size: bool,
+    pub recording_format: RecordingFormat,
+    pub screenshot_hotkey: Hotkey,
+    pub record_hotkey: Hotkey,
+}
+
+impl Default for CapturePreferences {
+    fn default() -> Self {
+        Self {
+            directory: None,
+            native_size: false,
+            recording_format: Default::default(),
+            screenshot_hotkey: Hotkey::new(Modifiers::NONE, Key::F12),
+            record_hotkey: Hotkey::new(Modifiers::SHIFT, Key::F12),
+        }
+    }
+}
+
+pub fn get_default_capture_directory() -> PathBuf {
+    dirs::picture_dir()
+        .or_else(dirs::home_dir)
+        .unwrap_or_default()
+        .join("Ruffle")
+}
+
 #[derive(Clone)]
 pub struct GlobalPreferencesWatchers {
     theme_preference_watcher: Arc<Sender<ThemePreference>>,
@@ -55,6 +55,10 @@ pub fn read_preferences(input: &str) -> ParseDetails<SavedGlobalPreferences> {
         result.enable_openh264 = value;
     };
 
+    if let Some(value) = document.get_bool(&mut cx, "speed_hotkeys") {
+        result.speed_hotkeys = value;
+    };
+
     if let Some(value) = document.get_integer(&mut cx, "recent_limit") {
         result.recent_limit = value as usize;
     }
@@ -87,6 +91,24 @@ pub fn read_preferences(input: &str) -> ParseDetails<SavedGlobalPreferences> {
         result.ime_enabled = ime.get_bool(cx, "enabled");
     });
 
+    document.get_table_like(&mut cx, "capture", |cx, capture| {
+        if let Some(value) = capture.parse_from_str(cx, "directory") {
+            result.capture.directory = Some(value);
+        }
+        if let Some(value) = capture.get_bool(cx, "native_size") {
+            result.capture.native_size = value;
+        }
+        if let Some(value) = capture.parse_from_str(cx, "recording_format") {
+            result.capture.recording_format = value;
+        }
+        if let Some(value) = capture.parse_from_str(cx, "screenshot_hotkey") {
+            result.capture.screenshot_hotkey = value;
+        }
+        if let Some(value) = capture.parse_from_str(cx, "record_hotkey") {
+            result.capture.record_hotkey = value;
+        }
+    });
+
     ParseDetails {
         warnings: cx.warnings,
         result: DocumentHolder::new(result, document),
@@ -96,12 +118,17 @@ pub fn read_preferences(input: &str) -> ParseDetails<SavedGlobalPreferences> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::capture::{Hotkey, RecordingFormat};
     use crate::cli::{GameModePreference, OpenUrlMode};
     use crate::gui::ThemePreference;
     use crate::log::FilenamePattern;
-    use crate::preferences::{LogPreferences, StoragePreferences, storage::StorageBackend};
+    use crate::preferences::{
+        CapturePreferences, LogPreferences, StoragePreferences, storage::StorageBackend,
+    };
+    use egui::{Key, Modifiers};
     use fluent_templates::loader::langid;
     use ruffle_render_wgpu::clap::{GraphicsBackend, PowerPreference};
+    use std::path::PathBuf;
 
     #[test]
     fn invalid_toml() {
@@ -764,4 +791,53 @@ mod tests {
             result.warnings
         );
     }
+
+    #[test]
+    fn capture() {
+        let result = read_preferences(
+            r#"
+            [capture]
+            directory = "/home/user/Captures"
+            native_size = true
+            recording_format = "gif"
+            screenshot_hotkey = "Ctrl+Shift+S"
+            record_hotkey = "F9"
+            "#,
+        );
+        assert_eq!(
+            &SavedGlobalPreferences {
+                capture: CapturePreferences {
+                    directory: Some(PathBuf::from("/home/user/Captures")),
+                    native_size: true,
+                    recording_format: RecordingFormat::Gif,
+                    screenshot_hotkey: Hotkey::new(
+                        Modifiers::COMMAND.plus(Modifiers::SHIFT),
+                        Key::S
+                    ),
+                    record_hotkey: Hotkey::new(Modifiers::NONE, Key::F9),
+                },
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+
+        let result = read_preferences(
+            "capture = { recording_format = \"mp4\", screenshot_hotkey = \"Hyper+S\" }",
+        );
+        assert_eq!(&SavedGlobalPreferences::default(), result.values());
+        assert_eq!(
+            vec![
+                ParseWarning::UnsupportedValue {
+                    value: "mp4".to_string(),
+                    path: "capture.recording_format".to_string()
+                },
+                ParseWarning::UnsupportedValue {
+                    value: "Hyper+S".to_string(),
+                    path: "capture.screenshot_hotkey".to_string()
+                },
+            ],
+            result.warnings
+        );
+    }
 }
@@ -1,3 +1,4 @@
+use crate::capture::{Hotkey, RecordingFormat};
 use crate::cli::{GameModePreference, OpenUrlMode};
 use crate::gui::ThemePreference;
 use crate::log::FilenamePattern;
@@ -5,6 +6,7 @@ use crate::preferences::storage::StorageBackend;
 use crate::preferences::{GlobalPreferencesWatchers, SavedGlobalPreferences};
 use ruffle_frontend_utils::parse::DocumentHolder;
 use ruffle_render_wgpu::clap::{GraphicsBackend, PowerPreference};
+use std::path::PathBuf;
 use toml_edit::value;
 use unic_langid::LanguageIdentifier;
 
@@ -75,6 +77,13 @@ impl<'a> PreferencesWriter<'a> {
         })
     }
 
+    pub fn set_speed_hotkeys(&mut self, enable: bool) {
+        self.0.edit(|values, toml_document| {
+            toml_document["speed_hotkeys"] = value(enable);
+            values.speed_hotkeys = enable;
+        })
+    }
+
     pub fn set_log_filename_pattern(&mut self, pattern: FilenamePattern) {
         self.0.edit(|values, toml_document| {
             toml_document["log"]["filename_pattern"] = value(pattern.as_str());
@@ -142,6 +151,46 @@ impl<'a> PreferencesWriter<'a> {
             values.ime_enabled = ime_enabled;
         });
     }
+
+    pub fn set_capture_directory(&mut self, directory: Option<PathBuf>) {
+        self.0.edit(|values, toml_document| {
+            if let Some(directory) = &directory {
+                toml_document["capture"]["directory"] =
+                    value(directory.to_string_lossy().into_owned());
+            } else {
+                toml_document["capture"]["directory"] = toml_edit::Item::None;
+            }
+            values.capture.directory = directory;
+        });
+    }
+
+    pub fn set_capture_native_size(&mut self, native_size: bool) {
+        self.0.edit(|values, toml_document| {
+            toml_document["capture"]["native_size"] = value(native_size);
+            values.capture.native_size = native_size;
+        });
+    }
+
+    pub fn set_recording_format(&mut self, format: RecordingFormat) {
+        self.0.edit(|values, toml_document| {
+            toml_document["capture"]["recording_format"] = value(format.as_str());
+            values.capture.recording_format = format;
+        });
+    }
+
+    pub fn set_screenshot_hotkey(&mut self, hotkey: Hotkey) {
+        self.0.edit(|values, toml_document| {
+            toml_document["capture"]["screenshot_hotkey"] = value(hotkey.to_string());
+            values.capture.screenshot_hotkey = hotkey;
+        });
+    }
+
+    pub fn set_record_hotkey(&mut self, hotkey: Hotkey) {
+        self.0.edit(|values, toml_document| {
+            toml_document["capture"]["record_hotkey"] = value(hotkey.to_string());
+            values.capture.record_hotkey = hotkey;
+        });
+    }
 }
 
 #[cfg(test)]
@@ -245,6 +294,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn set_speed_hotkeys() {
+        test(
+            "",
+            |writer| writer.set_speed_hotkeys(true),
+            "speed_hotkeys = true\n",
+        );
+        test(
+            "speed_hotkeys = true",
+            |writer| writer.set_speed_hotkeys(false),
+            "speed_hotkeys = false\n",
+        );
+    }
+
     #[test]
     fn set_log_filename_pattern() {
         test(
@@ -357,4 +420,47 @@ mod tests {
             "",
         );
     }
+
+    #[test]
+    fn set_capture_directory() {
+        test(
+            "",
+            |writer| writer.set_capture_directory(Some(PathBuf::from("/home/user/Captures"))),
+            "capture = { directory = \"/home/user/Captures\" }\n",
+        );
+        test(
+            "[capture]\ndirectory = \"/home/user/Captures\"\nnative_size = true\n",
+            |writer| writer.set_capture_directory(None),
+            "[capture]\nnative_size = true\n",
+        );
+    }
+
+    #[test]
+    fn set_capture_settings() {
+        test(
+            "",
+            |writer| {
+                writer.set_capture_native_size(true);
+                writer.set_recording_format(RecordingFormat::PngSequence);
+            },
+            "capture = { native_size = true, recording_format = \"png_sequence\" }\n",
+        );
+        test(
+            "[capture]\nrecording_format = \"png_sequence\"\n",
+            |writer| writer.set_recording_format(RecordingFormat::Gif),
+            "[capture]\nrecording_format = \"gif\"\n",
+        );
+    }
+
+    #[test]
+    fn set_capture_hotkeys() {
+        test(
+            "[capture]\n",
+            |writer| {
+                writer.set_screenshot_hotkey("ctrl+p".parse().expect("valid hotkey"));
+                writer.set_record_hotkey("Alt + Shift + R".parse().expect("valid hotkey"));
+            },
+            "[capture]\nscreenshot_hotkey = \"Ctrl+P\"\nrecord_hotkey = \"Alt+Shift+R\"\n",
+        );
+    }
 }
@@ -1,9 +1,10 @@
 use anyhow::{Error, anyhow};
-use gilrs::Button;
+use gilrs::{Axis, Button};
 use ruffle_core::events::{
     GamepadButton, KeyDescriptor, KeyLocation, LogicalKey, NamedKey as RuffleNamedKey, PhysicalKey,
     TextControlCode,
 };
+use ruffle_frontend_utils::gamepad::GamepadAxis;
 use std::path::Path;
 use url::Url;
 use winit::dpi::PhysicalSize;
@@ -355,6 +356,28 @@ pub fn gilrs_button_to_gamepad_button(button: Button) -> Option<GamepadButton> {
     }
 }
 
+/// Maps a gilrs axis to a gamepad axis, for axes that are reported as such.
+///
+/// Analog triggers are reported as button value changes instead, see
+/// [`gilrs_trigger_to_gamepad_axis`].
+pub fn gilrs_axis_to_gamepad_axis(axis: Axis) -> Option<GamepadAxis> {
+    match axis {
+        Axis::LeftStickX => Some(GamepadAxis::LeftStickX),
+        Axis::LeftStickY => Some(GamepadAxis::LeftStickY),
+        Axis::RightStickX => Some(GamepadAxis::RightStickX),
+        Axis::RightStickY => Some(GamepadAxis::RightStickY),
+        _ => None,
+    }
+}
+
+pub fn gilrs_trigger_to_gamepad_axis(button: Button) -> Option<GamepadAxis> {
+    match button {
+        Button::LeftTrigger2 => Some(GamepadAxis::LeftTrigger2),
+        Button::RightTrigger2 => Some(GamepadAxis::RightTrigger2),
+        _ => None,
+    }
+}
+
 pub fn get_screen_size(window: &Window) -> PhysicalSize<u32> {
     let mut min_x = 0;
     let mut min_y = 0;
@@ -99,6 +99,12 @@ pub struct Opt {
     #[clap(long)]
     pub force_play: bool,
 
+    /// Also save performance statistics of every captured frame as a CSV file.
+    /// It is written next to the captured image, or inside the output directory
+    /// as "stats.csv" when capturing multiple frames.
+    #[clap(long)]
+    pub stats: bool,
+
     /// Type of graphics backend to use. Not all options may be supported by your current system.
     /// Default will attempt to pick the most supported graphics backend.
     #[clap(long, short, default_value = "default")]
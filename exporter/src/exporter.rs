@@ -7,6 +7,7 @@ use image::RgbaImage;
 use ruffle_core::Player;
 use ruffle_core::PlayerBuilder;
 use ruffle_core::limits::ExecutionLimit;
+use ruffle_core::stats::PlayerStats;
 use ruffle_core::tag_utils::movie_from_path;
 use ruffle_render_wgpu::backend::{
     WgpuRenderBackend, create_wgpu_instance, request_adapter_and_device,
@@ -115,6 +116,10 @@ impl MovieExport {
         self.player.lock().unwrap().run_frame();
     }
 
+    pub fn frame_stats(&self) -> PlayerStats {
+        self.player.lock().unwrap().frame_stats()
+    }
+
     pub fn capture_frame(&self) -> Result<RgbaImage> {
         let image = || {
             self.player.lock().unwrap().render();
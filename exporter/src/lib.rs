@@ -7,8 +7,9 @@ use anyhow::{Result, anyhow};
 use image::RgbaImage;
 use indicatif::ProgressBar;
 use rayon::prelude::*;
-use std::fs::create_dir_all;
-use std::io::{self, Write};
+use ruffle_core::stats::PlayerStats;
+use std::fs::{File, create_dir_all};
+use std::io::{self, BufWriter, Write};
 use std::path::{Path, PathBuf};
 use walkdir::{DirEntry, WalkDir};
 
@@ -17,16 +18,20 @@ use crate::exporter::Exporter;
 use crate::progress::ExporterProgress;
 
 /// Captures a screenshot. The resulting image uses straight alpha
+///
+/// The performance statistics of each captured frame are returned alongside it,
+/// with the number of that frame.
 fn take_screenshot(
     exporter: &Exporter,
     swf_path: &Path,
     frames: FrameSelection, // TODO Figure out a way to get framecount before calling take_screenshot, so that we can have accurate progress bars when using --frames all
     skipframes: u32,
     progress: &ExporterProgress,
-) -> Result<Vec<RgbaImage>> {
+) -> Result<(Vec<RgbaImage>, Vec<(u32, PlayerStats)>)> {
     let movie_export = exporter.start_exporting_movie(swf_path)?;
 
     let mut result = Vec::new();
+    let mut stats = Vec::new();
     let totalframes = movie_export.total_frames();
 
     for i in 0..totalframes {
@@ -40,7 +45,10 @@ fn take_screenshot(
 
         if i >= skipframes {
             match movie_export.capture_frame() {
-                Ok(image) => result.push(image),
+                Ok(image) => {
+                    result.push(image);
+                    stats.push((i, movie_export.frame_stats()));
+                }
                 Err(e) => {
                     return Err(anyhow!(
                         "Unable to capture frame {} of {:?}: {:?}",
@@ -56,7 +64,37 @@ fn take_screenshot(
             progress.inc(1);
         }
     }
-    Ok(result)
+    Ok((result, stats))
+}
+
+/// Saves the statistics of captured frames as CSV.
+fn save_stats(path: &Path, stats: &[(u32, PlayerStats)]) -> Result<()> {
+    let mut file = BufWriter::new(File::create(path)?);
+    writeln!(
+        file,
+        "frame,avm1_ms,avm2_ms,render_ms,tessellation_ms,render_commands,display_objects,bitmap_bytes,sounds,gc_heap_bytes"
+    )?;
+    for (frame, stats) in stats {
+        writeln!(
+            file,
+            "{},{:.3},{:.3},{:.3},{:.3},{},{},{},{},{}",
+            frame,
+            stats.avm1_time.as_secs_f64() * 1000.0,
+            stats.avm2_time.as_secs_f64() * 1000.0,
+            stats.render_time.as_secs_f64() * 1000.0,
+            stats.tessellation_time.as_secs_f64() * 1000.0,
+            stats.render_commands,
+            stats.display_objects,
+            stats
+                .bitmap_memory
+                .map(|bytes| bytes.to_string())
+                .unwrap_or_default(),
+            stats.audio_instances,
+            stats.gc_heap_size,
+        )?;
+    }
+    file.flush()?;
+    Ok(())
 }
 
 fn find_files(root: &Path, with_progress: bool) -> Vec<DirEntry> {
@@ -106,7 +144,8 @@ fn capture_single_swf(exporter: &Exporter, opt: &Opt) -> Result<()> {
 
     let progress = ExporterProgress::new(opt, 1);
 
-    let frames = take_screenshot(exporter, &opt.swf, opt.frames, opt.skipframes, &progress)?;
+    let (frames, stats) =
+        take_screenshot(exporter, &opt.swf, opt.frames, opt.skipframes, &progress)?;
 
     progress.set_message(opt.swf.file_stem().unwrap().to_string_lossy().into_owned());
 
@@ -122,6 +161,9 @@ fn capture_single_swf(exporter: &Exporter, opt: &Opt) -> Result<()> {
                 .expect("Writing to stdout failed");
         } else {
             image.save(&output)?;
+            if opt.stats {
+                save_stats(&output.with_extension("csv"), &stats)?;
+            }
         }
     } else {
         let digits = frames.len().to_string().len();
@@ -130,6 +172,9 @@ fn capture_single_swf(exporter: &Exporter, opt: &Opt) -> Result<()> {
             path.push(format!("{frame:0digits$}.png"));
             image.save(&path)?;
         }
+        if opt.stats {
+            save_stats(&output.join("stats.csv"), &stats)?;
+        }
     }
 
     let message = if frames.len() == 1 {
@@ -172,7 +217,7 @@ fn capture_multiple_swfs(exporter: &Exporter, opt: &Opt) -> Result<()> {
                 .to_string_lossy()
                 .into_owned(),
         );
-        if let Ok(frames) =
+        if let Ok((frames, stats)) =
             take_screenshot(exporter, file.path(), opt.frames, opt.skipframes, &progress)
         {
             let mut relative_path = file
@@ -189,6 +234,9 @@ fn capture_multiple_swfs(exporter: &Exporter, opt: &Opt) -> Result<()> {
                     let _ = create_dir_all(parent);
                 }
                 frames.first().unwrap().save(&destination)?;
+                if opt.stats {
+                    save_stats(&destination.with_extension("csv"), &stats)?;
+                }
             } else {
                 let mut parent: PathBuf = (&output).into();
                 relative_path.set_extension("");
@@ -200,6 +248,9 @@ fn capture_multiple_swfs(exporter: &Exporter, opt: &Opt) -> Result<()> {
                     destination.push(format!("{frame:0digits$}.png"));
                     image.save(&destination)?;
                 }
+                if opt.stats {
+                    save_stats(&parent.join("stats.csv"), &stats)?;
+                }
             }
         }
 
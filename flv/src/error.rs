@@ -53,6 +53,15 @@ pub enum Error {
     #[error("the FLV contains a tag with unknown type {0}")]
     UnknownTagType(u8),
 
+    #[error("the FLV tag cannot be written as its data is invalid")]
+    InvalidTagData,
+
+    #[error("the FLV tag cannot be written as its data is longer than 16MiB")]
+    TagTooLarge,
+
+    #[error("the FLV script data contains a string that is too long for its type")]
+    StringTooLong,
+
     #[error("IO error ({0}, {1})")]
     IoError(IoErrorKind, String),
 }
@@ -82,6 +91,9 @@ impl PartialEq for Error {
             (Self::UnknownVideoCommandType(s), Self::UnknownVideoCommandType(o)) => s == o,
             (Self::UnknownAvcPacketType(s), Self::UnknownAvcPacketType(o)) => s == o,
             (Self::UnknownTagType(s), Self::UnknownTagType(o)) => s == o,
+            (Self::InvalidTagData, Self::InvalidTagData) => true,
+            (Self::TagTooLarge, Self::TagTooLarge) => true,
+            (Self::StringTooLong, Self::StringTooLong) => true,
             (Self::IoError(sk, ss), Self::IoError(ok, os)) => sk == ok && ss == os,
             _ => false,
         }
@@ -1,17 +1,21 @@
 mod header;
+mod metadata;
 mod script;
 mod sound;
 mod tag;
 mod video;
 
 mod reader;
+mod writer;
 
 mod error;
 
 pub use error::Error;
-pub use header::Header;
+pub use header::{Header, TypeFlags};
+pub use metadata::{Keyframe, MetaData};
 pub use reader::FlvReader;
 pub use script::{ScriptData, Value, Variable};
 pub use sound::{AudioData, AudioDataType, SoundFormat, SoundRate, SoundSize, SoundType};
 pub use tag::{Tag, TagData};
 pub use video::{CodecId, CommandFrame, FrameType, VideoData, VideoPacket};
+pub use writer::FlvWriter;
@@ -0,0 +1,266 @@
+use crate::error::Error;
+use crate::header::Header;
+use crate::script::{ScriptData, Value, Variable};
+use crate::sound::SoundFormat;
+use crate::tag::{Tag, TagData};
+use crate::video::{CodecId, FrameType};
+use crate::writer::FlvWriter;
+
+/// An entry of the keyframe index in `onMetaData`.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct Keyframe {
+    /// The timestamp of the keyframe in seconds.
+    pub time: f64,
+
+    /// The file position of the keyframe's tag, just past its back pointer.
+    pub file_position: u64,
+}
+
+/// The stream properties that encoders put in an `onMetaData` script tag.
+///
+/// Properties that are `None` are left out of the tag.
+#[derive(PartialEq, Debug, Clone, Default)]
+pub struct MetaData {
+    /// The duration of the stream in seconds.
+    pub duration: Option<f64>,
+    pub width: Option<f64>,
+    pub height: Option<f64>,
+    pub frame_rate: Option<f64>,
+    pub video_codec: Option<CodecId>,
+    pub audio_codec: Option<SoundFormat>,
+    pub audio_sample_rate: Option<f64>,
+    pub audio_sample_size: Option<f64>,
+    pub stereo: Option<bool>,
+    pub file_size: Option<f64>,
+
+    /// The keyframe index, which allows players to seek without reading the
+    /// whole file.
+    pub keyframes: Vec<Keyframe>,
+}
+
+impl MetaData {
+    /// Build the `onMetaData` script data for these properties.
+    pub fn to_script_data(&self) -> ScriptData<'static> {
+        let numbers = [
+            (&b"duration"[..], self.duration),
+            (b"width", self.width),
+            (b"height", self.height),
+            (b"framerate", self.frame_rate),
+            (b"videocodecid", self.video_codec.map(|c| c as u8 as f64)),
+            (b"audiosamplerate", self.audio_sample_rate),
+            (b"audiosamplesize", self.audio_sample_size),
+            (b"audiocodecid", self.audio_codec.map(|c| c as u8 as f64)),
+            (b"filesize", self.file_size),
+        ];
+        let mut properties: Vec<_> = numbers
+            .into_iter()
+            .filter_map(|(name, value)| {
+                Some(Variable {
+                    name,
+                    data: Value::Number(value?),
+                })
+            })
+            .collect();
+
+        if let Some(stereo) = self.stereo {
+            properties.push(Variable {
+                name: b"stereo",
+                data: Value::Boolean(stereo),
+            });
+        }
+
+        if !self.keyframes.is_empty() {
+            let (times, positions) = self
+                .keyframes
+                .iter()
+                .map(|keyframe| {
+                    (
+                        Value::Number(keyframe.time),
+                        Value::Number(keyframe.file_position as f64),
+                    )
+                })
+                .unzip();
+            properties.push(Variable {
+                name: b"keyframes",
+                data: Value::Object(vec![
+                    Variable {
+                        name: b"times",
+                        data: Value::StrictArray(times),
+                    },
+                    Variable {
+                        name: b"filepositions",
+                        data: Value::StrictArray(positions),
+                    },
+                ]),
+            });
+        }
+
+        ScriptData(vec![Variable {
+            name: b"onMetaData",
+            data: Value::EcmaArray(properties),
+        }])
+    }
+
+    /// Build the `onMetaData` tag for these properties.
+    pub fn to_tag(&self) -> Tag<'static> {
+        Tag {
+            timestamp: 0,
+            stream_id: 0,
+            data: TagData::Script(self.to_script_data()),
+        }
+    }
+
+    /// Fill in the keyframe index and file size for a file made of the
+    /// given header, the `onMetaData` tag and then the given tags.
+    ///
+    /// All numbers in the metadata take up the same space, so the size of the
+    /// metadata tag is known before the final positions are.
+    pub fn index_keyframes(&mut self, header: &Header, tags: &[Tag<'_>]) -> Result<(), Error> {
+        self.keyframes = tags
+            .iter()
+            .filter(|tag| {
+                matches!(&tag.data, TagData::Video(video) if video.frame_type == FrameType::Keyframe)
+            })
+            .map(|tag| Keyframe {
+                time: f64::from(tag.timestamp) / 1000.0,
+                file_position: 0,
+            })
+            .collect();
+        self.file_size = Some(0.0);
+
+        let mut writer = FlvWriter::new(std::io::sink());
+        header.write(&mut writer)?;
+        self.to_tag().write(&mut writer)?;
+
+        let mut keyframes = self.keyframes.iter_mut();
+        for tag in tags {
+            if matches!(&tag.data, TagData::Video(video) if video.frame_type == FrameType::Keyframe)
+                && let Some(keyframe) = keyframes.next()
+            {
+                // Skip the back pointer in front of the tag.
+                keyframe.file_position = writer.position() + 4;
+            }
+            tag.write(&mut writer)?;
+        }
+
+        // The file ends with a back pointer to the last tag.
+        self.file_size = Some((writer.position() + 4) as f64);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::header::{Header, TypeFlags};
+    use crate::metadata::{Keyframe, MetaData};
+    use crate::reader::FlvReader;
+    use crate::script::{Value, Variable};
+    use crate::tag::{Tag, TagData};
+    use crate::video::{CodecId, FrameType, VideoData, VideoPacket};
+    use crate::writer::FlvWriter;
+
+    fn video_tag(timestamp: i32, frame_type: FrameType, data: &[u8]) -> Tag<'_> {
+        Tag {
+            timestamp,
+            stream_id: 0,
+            data: TagData::Video(VideoData {
+                frame_type,
+                codec_id: CodecId::SorensonH263,
+                data: VideoPacket::Data(data),
+            }),
+        }
+    }
+
+    #[test]
+    fn write_metadata() {
+        let metadata = MetaData {
+            duration: Some(1.5),
+            video_codec: Some(CodecId::On2Vp6),
+            stereo: Some(true),
+            keyframes: vec![Keyframe {
+                time: 0.5,
+                file_position: 100,
+            }],
+            ..Default::default()
+        };
+
+        let mut writer = FlvWriter::new(Vec::new());
+        metadata.to_tag().write(&mut writer).unwrap();
+        let data = writer.into_inner();
+        let tag = Tag::parse(&mut FlvReader::from_source(&data)).unwrap();
+
+        assert_eq!(
+            tag.data,
+            TagData::Script(crate::ScriptData(vec![Variable {
+                name: b"onMetaData",
+                data: Value::EcmaArray(vec![
+                    Variable {
+                        name: b"duration",
+                        data: Value::Number(1.5)
+                    },
+                    Variable {
+                        name: b"videocodecid",
+                        data: Value::Number(4.0)
+                    },
+                    Variable {
+                        name: b"stereo",
+                        data: Value::Boolean(true)
+                    },
+                    Variable {
+                        name: b"keyframes",
+                        data: Value::Object(vec![
+                            Variable {
+                                name: b"times",
+                                data: Value::StrictArray(vec![Value::Number(0.5)])
+                            },
+                            Variable {
+                                name: b"filepositions",
+                                data: Value::StrictArray(vec![Value::Number(100.0)])
+                            },
+                        ])
+                    },
+                ])
+            }]))
+        );
+    }
+
+    #[test]
+    fn index_keyframes() {
+        let header = Header {
+            version: 1,
+            type_flags: TypeFlags::HAS_VIDEO,
+            data_offset: 9,
+        };
+        let tags = [
+            video_tag(0, FrameType::Keyframe, &[1, 2, 3]),
+            video_tag(40, FrameType::Interframe, &[4, 5]),
+            video_tag(80, FrameType::Interframe, &[6]),
+            video_tag(120, FrameType::Keyframe, &[7, 8, 9, 10]),
+        ];
+
+        let mut metadata = MetaData {
+            duration: Some(0.16),
+            ..Default::default()
+        };
+        metadata.index_keyframes(&header, &tags).unwrap();
+
+        let mut writer = FlvWriter::new(Vec::new());
+        header.write(&mut writer).unwrap();
+        metadata.to_tag().write(&mut writer).unwrap();
+        for tag in &tags {
+            tag.write(&mut writer).unwrap();
+        }
+        let data = writer.finish().unwrap();
+
+        assert_eq!(metadata.file_size, Some(data.len() as f64));
+        assert_eq!(metadata.keyframes.len(), 2);
+        for (keyframe, tag) in metadata.keyframes.iter().zip([&tags[0], &tags[3]]) {
+            assert_eq!(keyframe.time, f64::from(tag.timestamp) / 1000.0);
+
+            // Tags are parsed starting from their back pointer.
+            let position = keyframe.file_position as usize - 4;
+            let mut reader = FlvReader::from_parts(&data, position);
+            assert_eq!(&Tag::parse(&mut reader).unwrap(), tag);
+        }
+    }
+}
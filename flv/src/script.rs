@@ -1,6 +1,7 @@
 use crate::error::Error;
 use crate::reader::FlvReader;
-use std::io::Seek;
+use crate::writer::FlvWriter;
+use std::io::{Seek, Write};
 
 fn parse_string_u32<'a>(reader: &mut FlvReader<'a>) -> Result<&'a [u8], Error> {
     let length = reader.read_u32()?;
@@ -12,6 +13,29 @@ fn parse_string_u16<'a>(reader: &mut FlvReader<'a>) -> Result<&'a [u8], Error> {
     reader.read(length as usize)
 }
 
+fn write_string_u32<W: Write>(writer: &mut FlvWriter<W>, string: &[u8]) -> Result<(), Error> {
+    let length = u32::try_from(string.len()).map_err(|_| Error::StringTooLong)?;
+    writer.write_u32(length)?;
+    writer.write(string)
+}
+
+fn write_string_u16<W: Write>(writer: &mut FlvWriter<W>, string: &[u8]) -> Result<(), Error> {
+    let length = u16::try_from(string.len()).map_err(|_| Error::StringTooLong)?;
+    writer.write_u16(length)?;
+    writer.write(string)
+}
+
+/// Write the variables of an object, followed by the object end marker.
+fn write_object<W: Write>(
+    writer: &mut FlvWriter<W>,
+    variables: &[Variable<'_>],
+) -> Result<(), Error> {
+    for variable in variables {
+        variable.write(writer)?;
+    }
+    writer.write_u24(9)
+}
+
 #[repr(u8)]
 #[derive(PartialEq, Debug, Clone)]
 pub enum Value<'a> {
@@ -98,6 +122,63 @@ impl<'a> Value<'a> {
             _ => Err(Error::UnknownValueType),
         }
     }
+
+    /// Write a script value, including its type marker.
+    pub fn write<W: Write>(&self, writer: &mut FlvWriter<W>) -> Result<(), Error> {
+        match self {
+            Self::Number(value) => {
+                writer.write_u8(0)?;
+                writer.write_f64(*value)
+            }
+            Self::Boolean(value) => {
+                writer.write_u8(1)?;
+                writer.write_u8(*value as u8)
+            }
+            Self::String(string) => {
+                writer.write_u8(2)?;
+                write_string_u16(writer, string)
+            }
+            Self::Object(variables) => {
+                writer.write_u8(3)?;
+                write_object(writer, variables)
+            }
+            Self::MovieClip(path) => {
+                writer.write_u8(4)?;
+                write_string_u16(writer, path)
+            }
+            Self::Null => writer.write_u8(5),
+            Self::Undefined => writer.write_u8(6),
+            Self::Reference(index) => {
+                writer.write_u8(7)?;
+                writer.write_u16(*index)
+            }
+            Self::EcmaArray(variables) => {
+                writer.write_u8(8)?;
+                writer.write_u32(variables.len() as u32)?;
+                write_object(writer, variables)
+            }
+            Self::StrictArray(values) => {
+                writer.write_u8(10)?;
+                writer.write_u32(values.len() as u32)?;
+                for value in values {
+                    value.write(writer)?;
+                }
+                Ok(())
+            }
+            Self::Date {
+                unix_time,
+                local_offset,
+            } => {
+                writer.write_u8(11)?;
+                writer.write_f64(*unix_time)?;
+                writer.write_i16(*local_offset)
+            }
+            Self::LongString(string) => {
+                writer.write_u8(12)?;
+                write_string_u32(writer, string)
+            }
+        }
+    }
 }
 
 /// An individual object in a ScriptData tag.
@@ -118,6 +199,11 @@ impl<'a> Variable<'a> {
             data: Value::parse(reader)?,
         })
     }
+
+    pub fn write<W: Write>(&self, writer: &mut FlvWriter<W>) -> Result<(), Error> {
+        write_string_u16(writer, self.name)?;
+        self.data.write(writer)
+    }
 }
 
 #[derive(PartialEq, Debug, Clone)]
@@ -149,6 +235,20 @@ impl<'a> ScriptData<'a> {
             vars.push(Variable::parse(reader)?);
         }
     }
+
+    /// Write a script data structure.
+    ///
+    /// The first variable name is written as a string value, like the
+    /// `onMetaData` tags of common encoders. No terminator is written, as the
+    /// end of the structure is given by the size of its tag.
+    pub fn write<W: Write>(&self, writer: &mut FlvWriter<W>) -> Result<(), Error> {
+        // The string type marker that `parse` skips over.
+        writer.write_u8(2)?;
+        for variable in &self.0 {
+            variable.write(writer)?;
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
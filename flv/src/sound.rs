@@ -1,6 +1,7 @@
 use crate::FlvReader;
 use crate::error::Error;
-use std::io::Seek;
+use crate::writer::FlvWriter;
+use std::io::{Seek, Write};
 
 #[repr(u8)]
 #[derive(PartialEq, Eq, Debug, Copy, Clone)]
@@ -174,6 +175,28 @@ impl<'a> AudioData<'a> {
             data,
         })
     }
+
+    /// Write an audio data structure, including its header.
+    pub fn write<W: Write>(&self, writer: &mut FlvWriter<W>) -> Result<(), Error> {
+        writer.write_u8(
+            ((self.format as u8) << 4)
+                | ((self.rate as u8) << 2)
+                | ((self.size as u8) << 1)
+                | self.sound_type as u8,
+        )?;
+
+        match self.data {
+            AudioDataType::Raw(data) => writer.write(data),
+            AudioDataType::AacSequenceHeader(data) => {
+                writer.write_u8(0)?;
+                writer.write(data)
+            }
+            AudioDataType::AacRaw(data) => {
+                writer.write_u8(1)?;
+                writer.write(data)
+            }
+        }
+    }
 }
 
 #[cfg(test)]
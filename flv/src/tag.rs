@@ -3,8 +3,9 @@ use crate::reader::FlvReader;
 use crate::script::ScriptData;
 use crate::sound::AudioData;
 use crate::video::VideoData;
+use crate::writer::FlvWriter;
 
-use std::io::{Seek, SeekFrom};
+use std::io::{Seek, SeekFrom, Write};
 
 #[repr(u8)]
 #[derive(PartialEq, Debug, Clone)]
@@ -136,6 +137,48 @@ impl<'a> Tag<'a> {
             Ok(())
         }
     }
+
+    /// Write a single FLV tag structure.
+    ///
+    /// Like `parse`, this starts with the back pointer to the prior tag, which
+    /// the writer keeps track of. Tags with `TagData::Invalid` cannot be
+    /// written.
+    pub fn write<W: Write>(&self, writer: &mut FlvWriter<W>) -> Result<(), Error> {
+        let mut data = FlvWriter::new(Vec::new());
+        let tag_type = match &self.data {
+            TagData::Audio(audio) => {
+                audio.write(&mut data)?;
+                8
+            }
+            TagData::Video(video) => {
+                video.write(&mut data)?;
+                9
+            }
+            TagData::Script(script) => {
+                script.write(&mut data)?;
+                18
+            }
+            TagData::Invalid(_) => return Err(Error::InvalidTagData),
+        };
+        let data = data.into_inner();
+        let data_size = u32::try_from(data.len())
+            .ok()
+            .filter(|size| *size <= 0xFFFFFF)
+            .ok_or(Error::TagTooLarge)?;
+
+        writer.write_u32(writer.previous_tag_size)?;
+        writer.write_u8(tag_type)?;
+        writer.write_u24(data_size)?;
+        writer.write_u24(self.timestamp as u32)?;
+        writer.write_u8((self.timestamp as u32 >> 24) as u8)?;
+        writer.write_u24(self.stream_id)?;
+        writer.write(&data)?;
+
+        // The back pointer covers the 11 byte tag header and the data.
+        writer.previous_tag_size = data_size + 11;
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -146,6 +189,32 @@ mod tests {
     use crate::sound::{AudioData, AudioDataType, SoundFormat, SoundRate, SoundSize, SoundType};
     use crate::tag::{Tag, TagData};
     use crate::video::{CodecId, FrameType, VideoData, VideoPacket};
+    use crate::writer::FlvWriter;
+
+    /// An `onMetaData` tag written by FFmpeg.
+    const ONMETADATA_TAG: [u8; 308] = [
+        0x00, 0x00, 0x00, 0x00, 0x12, 0x00, 0x01, 0x25, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x02, 0x00, 0x0A, 0x6F, 0x6E, 0x4D, 0x65, 0x74, 0x61, 0x44, 0x61, 0x74, 0x61, 0x08, 0x00,
+        0x00, 0x00, 0x0D, 0x00, 0x08, 0x64, 0x75, 0x72, 0x61, 0x74, 0x69, 0x6F, 0x6E, 0x00, 0x3F,
+        0xF1, 0x1E, 0xB8, 0x51, 0xEB, 0x85, 0x1F, 0x00, 0x05, 0x77, 0x69, 0x64, 0x74, 0x68, 0x00,
+        0x40, 0x74, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x06, 0x68, 0x65, 0x69, 0x67, 0x68,
+        0x74, 0x00, 0x40, 0x6D, 0x20, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x0D, 0x76, 0x69, 0x64,
+        0x65, 0x6F, 0x64, 0x61, 0x74, 0x61, 0x72, 0x61, 0x74, 0x65, 0x00, 0x40, 0x68, 0x6A, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x09, 0x66, 0x72, 0x61, 0x6D, 0x65, 0x72, 0x61, 0x74, 0x65,
+        0x00, 0x40, 0x28, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x0C, 0x76, 0x69, 0x64, 0x65,
+        0x6F, 0x63, 0x6F, 0x64, 0x65, 0x63, 0x69, 0x64, 0x00, 0x40, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x0D, 0x61, 0x75, 0x64, 0x69, 0x6F, 0x64, 0x61, 0x74, 0x61, 0x72, 0x61,
+        0x74, 0x65, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x0F, 0x61, 0x75,
+        0x64, 0x69, 0x6F, 0x73, 0x61, 0x6D, 0x70, 0x6C, 0x65, 0x72, 0x61, 0x74, 0x65, 0x00, 0x40,
+        0xE5, 0x88, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x0F, 0x61, 0x75, 0x64, 0x69, 0x6F, 0x73,
+        0x61, 0x6D, 0x70, 0x6C, 0x65, 0x73, 0x69, 0x7A, 0x65, 0x00, 0x40, 0x30, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x06, 0x73, 0x74, 0x65, 0x72, 0x65, 0x6F, 0x01, 0x00, 0x00, 0x0C,
+        0x61, 0x75, 0x64, 0x69, 0x6F, 0x63, 0x6F, 0x64, 0x65, 0x63, 0x69, 0x64, 0x00, 0x40, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x07, 0x65, 0x6E, 0x63, 0x6F, 0x64, 0x65, 0x72,
+        0x02, 0x00, 0x0D, 0x4C, 0x61, 0x76, 0x66, 0x35, 0x38, 0x2E, 0x32, 0x31, 0x2E, 0x31, 0x30,
+        0x30, 0x00, 0x08, 0x66, 0x69, 0x6C, 0x65, 0x73, 0x69, 0x7A, 0x65, 0x00, 0x40, 0xCC, 0x73,
+        0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x09,
+    ];
 
     #[test]
     fn read_tag_sounddata() {
@@ -241,30 +310,7 @@ mod tests {
 
     #[test]
     fn read_tag_onmetadata() {
-        let data = [
-            0x00, 0x00, 0x00, 0x00, 0x12, 0x00, 0x01, 0x25, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-            0x00, 0x02, 0x00, 0x0A, 0x6F, 0x6E, 0x4D, 0x65, 0x74, 0x61, 0x44, 0x61, 0x74, 0x61,
-            0x08, 0x00, 0x00, 0x00, 0x0D, 0x00, 0x08, 0x64, 0x75, 0x72, 0x61, 0x74, 0x69, 0x6F,
-            0x6E, 0x00, 0x3F, 0xF1, 0x1E, 0xB8, 0x51, 0xEB, 0x85, 0x1F, 0x00, 0x05, 0x77, 0x69,
-            0x64, 0x74, 0x68, 0x00, 0x40, 0x74, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x06,
-            0x68, 0x65, 0x69, 0x67, 0x68, 0x74, 0x00, 0x40, 0x6D, 0x20, 0x00, 0x00, 0x00, 0x00,
-            0x00, 0x00, 0x0D, 0x76, 0x69, 0x64, 0x65, 0x6F, 0x64, 0x61, 0x74, 0x61, 0x72, 0x61,
-            0x74, 0x65, 0x00, 0x40, 0x68, 0x6A, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x09, 0x66,
-            0x72, 0x61, 0x6D, 0x65, 0x72, 0x61, 0x74, 0x65, 0x00, 0x40, 0x28, 0x00, 0x00, 0x00,
-            0x00, 0x00, 0x00, 0x00, 0x0C, 0x76, 0x69, 0x64, 0x65, 0x6F, 0x63, 0x6F, 0x64, 0x65,
-            0x63, 0x69, 0x64, 0x00, 0x40, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x0D,
-            0x61, 0x75, 0x64, 0x69, 0x6F, 0x64, 0x61, 0x74, 0x61, 0x72, 0x61, 0x74, 0x65, 0x00,
-            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x0F, 0x61, 0x75, 0x64, 0x69,
-            0x6F, 0x73, 0x61, 0x6D, 0x70, 0x6C, 0x65, 0x72, 0x61, 0x74, 0x65, 0x00, 0x40, 0xE5,
-            0x88, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x0F, 0x61, 0x75, 0x64, 0x69, 0x6F, 0x73,
-            0x61, 0x6D, 0x70, 0x6C, 0x65, 0x73, 0x69, 0x7A, 0x65, 0x00, 0x40, 0x30, 0x00, 0x00,
-            0x00, 0x00, 0x00, 0x00, 0x00, 0x06, 0x73, 0x74, 0x65, 0x72, 0x65, 0x6F, 0x01, 0x00,
-            0x00, 0x0C, 0x61, 0x75, 0x64, 0x69, 0x6F, 0x63, 0x6F, 0x64, 0x65, 0x63, 0x69, 0x64,
-            0x00, 0x40, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x07, 0x65, 0x6E, 0x63,
-            0x6F, 0x64, 0x65, 0x72, 0x02, 0x00, 0x0D, 0x4C, 0x61, 0x76, 0x66, 0x35, 0x38, 0x2E,
-            0x32, 0x31, 0x2E, 0x31, 0x30, 0x30, 0x00, 0x08, 0x66, 0x69, 0x6C, 0x65, 0x73, 0x69,
-            0x7A, 0x65, 0x00, 0x40, 0xCC, 0x73, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x09,
-        ];
+        let data = ONMETADATA_TAG;
         let mut reader = FlvReader::from_source(&data);
 
         assert_eq!(
@@ -332,4 +378,43 @@ mod tests {
             })
         )
     }
+
+    #[test]
+    fn write_tag_onmetadata() {
+        let mut reader = FlvReader::from_source(&ONMETADATA_TAG);
+        let tag = Tag::parse(&mut reader).unwrap();
+
+        let mut writer = FlvWriter::new(Vec::new());
+        tag.write(&mut writer).unwrap();
+        assert_eq!(writer.into_inner(), ONMETADATA_TAG);
+    }
+
+    #[test]
+    fn write_tag_sequence() {
+        let data = [
+            0x00, 0x00, 0x00, 0x00, 0x08, 0x00, 0x00, 0x02, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00,
+            0x00, 0x2E, 0x12, 0x00, 0x00, 0x00, 0x0D, 0x09, 0x00, 0x00, 0x01, 0x56, 0x78, 0x9A,
+            0x12, 0x00, 0x00, 0x00, 0x22, 0x00, 0x00, 0x00, 0x0C,
+        ];
+        let mut reader = FlvReader::from_source(&data);
+        let first = Tag::parse(&mut reader).unwrap();
+        let second = Tag::parse(&mut reader).unwrap();
+        assert_eq!(second.timestamp, 0x1256_789A);
+
+        let mut writer = FlvWriter::new(Vec::new());
+        first.write(&mut writer).unwrap();
+        second.write(&mut writer).unwrap();
+        assert_eq!(writer.finish().unwrap(), data);
+    }
+
+    #[test]
+    fn write_tag_invalid() {
+        let tag = Tag {
+            timestamp: 0,
+            stream_id: 0,
+            data: TagData::Invalid(Error::UnknownTagType(0x20)),
+        };
+        let mut writer = FlvWriter::new(Vec::new());
+        assert_eq!(tag.write(&mut writer), Err(Error::InvalidTagData));
+    }
 }
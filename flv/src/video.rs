@@ -1,6 +1,7 @@
 use crate::error::Error;
 use crate::reader::FlvReader;
-use std::io::Seek;
+use crate::writer::FlvWriter;
+use std::io::{Seek, Write};
 
 #[repr(u8)]
 #[derive(PartialEq, Eq, Debug, Copy, Clone)]
@@ -161,6 +162,41 @@ impl<'a> VideoData<'a> {
             data: packet,
         })
     }
+
+    /// Write a video data structure, including its header.
+    pub fn write<W: Write>(&self, writer: &mut FlvWriter<W>) -> Result<(), Error> {
+        writer.write_u8(((self.frame_type as u8) << 4) | self.codec_id as u8)?;
+
+        match self.data {
+            VideoPacket::Data(data) => writer.write(data),
+            VideoPacket::Vp6Data {
+                hadjust,
+                vadjust,
+                data,
+            } => {
+                writer.write_u8(((vadjust & 0x0F) << 4) | (hadjust & 0x0F))?;
+                writer.write(data)
+            }
+            VideoPacket::AvcSequenceHeader(data) => {
+                writer.write_u8(0)?;
+                writer.write_u24(0)?;
+                writer.write(data)
+            }
+            VideoPacket::AvcNalu {
+                composition_time_offset,
+                data,
+            } => {
+                writer.write_u8(1)?;
+                writer.write_u24(composition_time_offset as u32)?;
+                writer.write(data)
+            }
+            VideoPacket::AvcEndOfSequence => {
+                writer.write_u8(2)?;
+                writer.write_u24(0)
+            }
+            VideoPacket::CommandFrame(command) => writer.write_u8(command as u8),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,299 @@
+use crate::error::Error as FlvError;
+use std::io::Write;
+
+/// A writer that allows muxing an FLV container.
+///
+/// Write a `Header` first, followed by any number of `Tag`s, and then call
+/// `finish` to terminate the file.
+pub struct FlvWriter<W: Write> {
+    output: W,
+
+    /// The number of bytes written so far.
+    position: u64,
+
+    /// The size of the last tag written, which is stored in front of the
+    /// next tag.
+    pub(crate) previous_tag_size: u32,
+}
+
+impl<W: Write> FlvWriter<W> {
+    pub fn new(output: W) -> Self {
+        FlvWriter {
+            output,
+            position: 0,
+            previous_tag_size: 0,
+        }
+    }
+
+    /// The number of bytes written so far.
+    ///
+    /// Taken right before writing a tag, this is the file position that
+    /// `Tag::parse` expects to start reading from.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Write the size of the final tag and return the underlying output.
+    ///
+    /// FLV files end with a back pointer to the last tag, which allows
+    /// players to seek backwards from the end of the file.
+    pub fn finish(mut self) -> Result<W, FlvError> {
+        self.write_u32(self.previous_tag_size)?;
+        self.output.flush()?;
+        Ok(self.output)
+    }
+
+    /// Return the underlying output without terminating the file.
+    pub fn into_inner(self) -> W {
+        self.output
+    }
+
+    pub fn write(&mut self, data: &[u8]) -> Result<(), FlvError> {
+        self.output.write_all(data)?;
+        self.position += data.len() as u64;
+        Ok(())
+    }
+
+    pub fn write_u8(&mut self, value: u8) -> Result<(), FlvError> {
+        self.write(&[value])
+    }
+
+    pub fn write_u16(&mut self, value: u16) -> Result<(), FlvError> {
+        self.write(&value.to_be_bytes())
+    }
+
+    pub fn write_i16(&mut self, value: i16) -> Result<(), FlvError> {
+        self.write(&value.to_be_bytes())
+    }
+
+    /// Write the lower 24 bits of a value.
+    pub fn write_u24(&mut self, value: u32) -> Result<(), FlvError> {
+        self.write(&value.to_be_bytes()[1..])
+    }
+
+    pub fn write_u32(&mut self, value: u32) -> Result<(), FlvError> {
+        self.write(&value.to_be_bytes())
+    }
+
+    pub fn write_f64(&mut self, value: f64) -> Result<(), FlvError> {
+        self.write(&value.to_be_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Error;
+    use crate::header::{Header, TypeFlags};
+    use crate::reader::FlvReader;
+    use crate::script::{ScriptData, Value, Variable};
+    use crate::sound::{AudioData, AudioDataType, SoundFormat, SoundRate, SoundSize, SoundType};
+    use crate::tag::{Tag, TagData};
+    use crate::video::{CodecId, CommandFrame, FrameType, VideoData, VideoPacket};
+    use crate::writer::FlvWriter;
+
+    fn write_flv(header: &Header, tags: &[Tag<'_>]) -> Vec<u8> {
+        let mut writer = FlvWriter::new(Vec::new());
+        header.write(&mut writer).unwrap();
+        for tag in tags {
+            tag.write(&mut writer).unwrap();
+        }
+        writer.finish().unwrap()
+    }
+
+    fn read_flv(data: &[u8]) -> (Header, Vec<Tag<'_>>) {
+        let mut reader = FlvReader::from_source(data);
+        let header = Header::parse(&mut reader).unwrap();
+        let mut tags = vec![];
+        loop {
+            match Tag::parse(&mut reader) {
+                Ok(tag) => tags.push(tag),
+                Err(Error::EndOfData) => break,
+                Err(e) => panic!("Unexpected error {e}"),
+            }
+        }
+        (header, tags)
+    }
+
+    #[test]
+    fn round_trip_file() {
+        let header = Header {
+            version: 1,
+            type_flags: TypeFlags::HAS_AUDIO | TypeFlags::HAS_VIDEO,
+            data_offset: 9,
+        };
+        let tags = [
+            Tag {
+                timestamp: 0,
+                stream_id: 0,
+                data: TagData::Script(ScriptData(vec![Variable {
+                    name: b"onCuePoint",
+                    data: Value::Object(vec![
+                        Variable {
+                            name: b"number",
+                            data: Value::Number(1.5),
+                        },
+                        Variable {
+                            name: b"flag",
+                            data: Value::Boolean(true),
+                        },
+                        Variable {
+                            name: b"string",
+                            data: Value::String(b"test"),
+                        },
+                        Variable {
+                            name: b"clip",
+                            data: Value::MovieClip(b"_root.clip"),
+                        },
+                        Variable {
+                            name: b"null",
+                            data: Value::Null,
+                        },
+                        Variable {
+                            name: b"undefined",
+                            data: Value::Undefined,
+                        },
+                        Variable {
+                            name: b"reference",
+                            data: Value::Reference(3),
+                        },
+                        Variable {
+                            name: b"array",
+                            data: Value::EcmaArray(vec![Variable {
+                                name: b"0",
+                                data: Value::Number(1.0),
+                            }]),
+                        },
+                        Variable {
+                            name: b"strict",
+                            data: Value::StrictArray(vec![Value::Null, Value::Number(2.0)]),
+                        },
+                        Variable {
+                            name: b"date",
+                            data: Value::Date {
+                                unix_time: 1234567890.0,
+                                local_offset: -60,
+                            },
+                        },
+                        Variable {
+                            name: b"long",
+                            data: Value::LongString(b"long string"),
+                        },
+                    ]),
+                }])),
+            },
+            Tag {
+                timestamp: 0,
+                stream_id: 0,
+                data: TagData::Video(VideoData {
+                    frame_type: FrameType::Keyframe,
+                    codec_id: CodecId::SorensonH263,
+                    data: VideoPacket::Data(&[1, 2, 3]),
+                }),
+            },
+            Tag {
+                timestamp: 10,
+                stream_id: 0,
+                data: TagData::Audio(AudioData {
+                    format: SoundFormat::MP3,
+                    rate: SoundRate::R44_000,
+                    size: SoundSize::Bits16,
+                    sound_type: SoundType::Stereo,
+                    data: AudioDataType::Raw(&[4, 5, 6]),
+                }),
+            },
+            Tag {
+                timestamp: 20,
+                stream_id: 0,
+                data: TagData::Audio(AudioData {
+                    format: SoundFormat::Aac,
+                    rate: SoundRate::R44_000,
+                    size: SoundSize::Bits16,
+                    sound_type: SoundType::Stereo,
+                    data: AudioDataType::AacSequenceHeader(&[0x12, 0x10]),
+                }),
+            },
+            Tag {
+                timestamp: 30,
+                stream_id: 0,
+                data: TagData::Audio(AudioData {
+                    format: SoundFormat::Aac,
+                    rate: SoundRate::R44_000,
+                    size: SoundSize::Bits16,
+                    sound_type: SoundType::Stereo,
+                    data: AudioDataType::AacRaw(&[7, 8]),
+                }),
+            },
+            Tag {
+                timestamp: 40,
+                stream_id: 0,
+                data: TagData::Video(VideoData {
+                    frame_type: FrameType::Interframe,
+                    codec_id: CodecId::On2Vp6,
+                    data: VideoPacket::Vp6Data {
+                        hadjust: 1,
+                        vadjust: 2,
+                        data: &[9],
+                    },
+                }),
+            },
+            Tag {
+                timestamp: 50,
+                stream_id: 0,
+                data: TagData::Video(VideoData {
+                    frame_type: FrameType::Keyframe,
+                    codec_id: CodecId::Avc,
+                    data: VideoPacket::AvcSequenceHeader(&[1, 0x64]),
+                }),
+            },
+            Tag {
+                timestamp: 60,
+                stream_id: 0,
+                data: TagData::Video(VideoData {
+                    frame_type: FrameType::Interframe,
+                    codec_id: CodecId::Avc,
+                    data: VideoPacket::AvcNalu {
+                        composition_time_offset: -33,
+                        data: &[0, 0, 0, 1],
+                    },
+                }),
+            },
+            Tag {
+                timestamp: 70,
+                stream_id: 0,
+                data: TagData::Video(VideoData {
+                    frame_type: FrameType::Keyframe,
+                    codec_id: CodecId::Avc,
+                    data: VideoPacket::AvcEndOfSequence,
+                }),
+            },
+            Tag {
+                // Needs the extended timestamp byte.
+                timestamp: 0x1234_5678,
+                stream_id: 0x10,
+                data: TagData::Video(VideoData {
+                    frame_type: FrameType::CommandFrame,
+                    codec_id: CodecId::SorensonH263,
+                    data: VideoPacket::CommandFrame(CommandFrame::EndOfClientSideSeek),
+                }),
+            },
+        ];
+
+        let data = write_flv(&header, &tags);
+        let (read_header, read_tags) = read_flv(&data);
+        assert_eq!(read_header, header);
+        assert_eq!(read_tags, tags);
+
+        // The back pointers allow reading the file backwards.
+        let mut position = data.len() - 4;
+        for tag in tags.iter().rev() {
+            let mut reader = FlvReader::from_parts(&data, position);
+            Tag::skip_back(&mut reader).unwrap();
+            position = reader.into_parts().1;
+
+            let mut reader = FlvReader::from_parts(&data, position);
+            assert_eq!(&Tag::parse(&mut reader).unwrap(), tag);
+        }
+        let mut reader = FlvReader::from_parts(&data, position);
+        assert_eq!(Tag::skip_back(&mut reader), Err(Error::EndOfData));
+    }
+}
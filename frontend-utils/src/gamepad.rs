@@ -0,0 +1,199 @@
+mod mapper;
+mod read;
+mod write;
+
+pub use mapper::GamepadMapper;
+pub use read::read_gamepad_profiles;
+pub use write::GamepadProfilesWriter;
+
+use ruffle_core::events::{GamepadButton, KeyCode, MouseButton};
+use std::fmt;
+use std::str::FromStr;
+
+/// An analog input of a gamepad.
+///
+/// Stick axes range from -1.0 to 1.0, with vertical axes being positive upwards.
+/// Triggers range from 0.0 (released) to 1.0 (fully pressed).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GamepadAxis {
+    LeftStickX,
+    LeftStickY,
+    RightStickX,
+    RightStickY,
+    LeftTrigger2,
+    RightTrigger2,
+}
+
+impl GamepadAxis {
+    pub const ALL: [GamepadAxis; 6] = [
+        GamepadAxis::LeftStickX,
+        GamepadAxis::LeftStickY,
+        GamepadAxis::RightStickX,
+        GamepadAxis::RightStickY,
+        GamepadAxis::LeftTrigger2,
+        GamepadAxis::RightTrigger2,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            GamepadAxis::LeftStickX => "left-stick-x",
+            GamepadAxis::LeftStickY => "left-stick-y",
+            GamepadAxis::RightStickX => "right-stick-x",
+            GamepadAxis::RightStickY => "right-stick-y",
+            GamepadAxis::LeftTrigger2 => "left-trigger-2",
+            GamepadAxis::RightTrigger2 => "right-trigger-2",
+        }
+    }
+}
+
+impl FromStr for GamepadAxis {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|axis| axis.as_str() == s)
+            .ok_or(())
+    }
+}
+
+impl fmt::Display for GamepadAxis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Stick {
+    #[default]
+    Left,
+    Right,
+}
+
+impl Stick {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Stick::Left => "left",
+            Stick::Right => "right",
+        }
+    }
+
+    /// The horizontal and vertical axes of this stick.
+    pub fn axes(self) -> (GamepadAxis, GamepadAxis) {
+        match self {
+            Stick::Left => (GamepadAxis::LeftStickX, GamepadAxis::LeftStickY),
+            Stick::Right => (GamepadAxis::RightStickX, GamepadAxis::RightStickY),
+        }
+    }
+}
+
+impl FromStr for Stick {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "left" => Ok(Stick::Left),
+            "right" => Ok(Stick::Right),
+            _ => Err(()),
+        }
+    }
+}
+
+/// The mouse buttons a gamepad button can be mapped to.
+pub const MOUSE_BUTTONS: [MouseButton; 3] =
+    [MouseButton::Left, MouseButton::Right, MouseButton::Middle];
+
+pub fn mouse_button_name(button: MouseButton) -> &'static str {
+    match button {
+        MouseButton::Left => "left",
+        MouseButton::Right => "right",
+        MouseButton::Middle => "middle",
+        MouseButton::Unknown => "unknown",
+    }
+}
+
+pub fn parse_mouse_button(name: &str) -> Option<MouseButton> {
+    MOUSE_BUTTONS
+        .into_iter()
+        .find(|button| mouse_button_name(*button) == name)
+}
+
+/// Moves the mouse cursor with an analog stick.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StickMouse {
+    pub stick: Stick,
+
+    /// How far the stick has to be pushed before the cursor moves, from 0.0 to 1.0.
+    pub deadzone: f64,
+
+    /// How fast the cursor moves with the stick pushed all the way, in pixels per second.
+    pub speed: f64,
+
+    /// The exponent of the response curve. 1.0 is linear, higher values give
+    /// finer control when the stick is pushed only slightly.
+    pub acceleration: f64,
+}
+
+impl Default for StickMouse {
+    fn default() -> Self {
+        Self {
+            stick: Stick::Left,
+            deadzone: 0.15,
+            speed: 800.0,
+            acceleration: 2.0,
+        }
+    }
+}
+
+/// Holds a key while an axis is pushed past a threshold.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AxisKey {
+    pub axis: GamepadAxis,
+
+    /// Positive thresholds are reached when the axis goes above them,
+    /// negative ones when it goes below them.
+    pub threshold: f64,
+
+    pub key_code: KeyCode,
+}
+
+/// Holds a key while all of the buttons are held together.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Chord {
+    pub buttons: Vec<GamepadButton>,
+    pub key_code: KeyCode,
+}
+
+/// A set of gamepad mappings that can be switched between.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GamepadProfile {
+    pub name: String,
+    pub stick_mouse: Option<StickMouse>,
+    pub buttons: Vec<(GamepadButton, KeyCode)>,
+    pub mouse_buttons: Vec<(GamepadButton, MouseButton)>,
+    pub axis_keys: Vec<AxisKey>,
+    pub chords: Vec<Chord>,
+}
+
+impl GamepadProfile {
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GamepadProfiles {
+    /// The name of the profile in use, if any.
+    pub active: Option<String>,
+    pub profiles: Vec<GamepadProfile>,
+}
+
+impl GamepadProfiles {
+    pub fn active_profile(&self) -> Option<&GamepadProfile> {
+        let active = self.active.as_ref()?;
+        self.profiles.iter().find(|profile| &profile.name == active)
+    }
+}
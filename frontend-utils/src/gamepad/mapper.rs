@@ -0,0 +1,424 @@
+use crate::gamepad::{GamepadAxis, GamepadProfile};
+use ruffle_core::PlayerEvent;
+use ruffle_core::events::GamepadButton;
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+/// Turns gamepad input into player events, following a [`GamepadProfile`].
+///
+/// Buttons that the profile doesn't map are passed on as gamepad button events,
+/// so that the button mapping of the player still applies to them.
+///
+/// The mapper keeps its own idea of where the cursor is, in movie viewport
+/// pixels. The frontend should keep it up to date with
+/// [`GamepadMapper::set_cursor_position`] when the real mouse moves.
+#[derive(Debug, Default)]
+pub struct GamepadMapper {
+    profile: GamepadProfile,
+    axes: HashMap<GamepadAxis, f64>,
+    buttons_down: HashSet<GamepadButton>,
+
+    /// Whether each axis key of the profile is currently held.
+    axis_keys_down: Vec<bool>,
+
+    /// Whether each chord of the profile is currently held.
+    chords_down: Vec<bool>,
+
+    cursor: (f64, f64),
+}
+
+impl GamepadMapper {
+    pub fn new(profile: GamepadProfile) -> Self {
+        Self {
+            axis_keys_down: vec![false; profile.axis_keys.len()],
+            chords_down: vec![false; profile.chords.len()],
+            profile,
+            ..Default::default()
+        }
+    }
+
+    pub fn profile(&self) -> &GamepadProfile {
+        &self.profile
+    }
+
+    /// Switches to another profile, returning the events that release
+    /// everything held through the previous one.
+    pub fn set_profile(&mut self, profile: GamepadProfile) -> Vec<PlayerEvent> {
+        let events = self.release_all();
+        *self = Self {
+            cursor: self.cursor,
+            ..Self::new(profile)
+        };
+        events
+    }
+
+    pub fn cursor_position(&self) -> (f64, f64) {
+        self.cursor
+    }
+
+    pub fn set_cursor_position(&mut self, x: f64, y: f64) {
+        self.cursor = (x, y);
+    }
+
+    pub fn button_down(&mut self, button: GamepadButton) -> Vec<PlayerEvent> {
+        if !self.buttons_down.insert(button) {
+            return vec![];
+        }
+
+        let mut events = vec![self.button_event(button, true)];
+        for (chord, down) in self.profile.chords.iter().zip(&mut self.chords_down) {
+            if !*down
+                && chord.buttons.contains(&button)
+                && chord
+                    .buttons
+                    .iter()
+                    .all(|button| self.buttons_down.contains(button))
+            {
+                *down = true;
+                events.push(PlayerEvent::GamepadKeyDown {
+                    key_code: chord.key_code,
+                });
+            }
+        }
+        events
+    }
+
+    pub fn button_up(&mut self, button: GamepadButton) -> Vec<PlayerEvent> {
+        if !self.buttons_down.remove(&button) {
+            return vec![];
+        }
+
+        let mut events = vec![];
+        for (chord, down) in self.profile.chords.iter().zip(&mut self.chords_down) {
+            if *down && chord.buttons.contains(&button) {
+                *down = false;
+                events.push(PlayerEvent::GamepadKeyUp {
+                    key_code: chord.key_code,
+                });
+            }
+        }
+        events.push(self.button_event(button, false));
+        events
+    }
+
+    fn button_event(&self, button: GamepadButton, down: bool) -> PlayerEvent {
+        let (x, y) = self.cursor;
+        if let Some((_, key_code)) = self.profile.buttons.iter().find(|(b, _)| *b == button) {
+            let key_code = *key_code;
+            if down {
+                PlayerEvent::GamepadKeyDown { key_code }
+            } else {
+                PlayerEvent::GamepadKeyUp { key_code }
+            }
+        } else if let Some((_, mouse_button)) = self
+            .profile
+            .mouse_buttons
+            .iter()
+            .find(|(b, _)| *b == button)
+        {
+            let button = *mouse_button;
+            if down {
+                PlayerEvent::MouseDown {
+                    x,
+                    y,
+                    button,
+                    index: None,
+                }
+            } else {
+                PlayerEvent::MouseUp { x, y, button }
+            }
+        } else if down {
+            PlayerEvent::GamepadButtonDown { button }
+        } else {
+            PlayerEvent::GamepadButtonUp { button }
+        }
+    }
+
+    pub fn axis_changed(&mut self, axis: GamepadAxis, value: f64) -> Vec<PlayerEvent> {
+        self.axes.insert(axis, value);
+
+        let mut events = vec![];
+        for (axis_key, down) in self.profile.axis_keys.iter().zip(&mut self.axis_keys_down) {
+            if axis_key.axis != axis {
+                continue;
+            }
+
+            let pushed = if axis_key.threshold >= 0.0 {
+                value >= axis_key.threshold
+            } else {
+                value <= axis_key.threshold
+            };
+            if pushed != *down {
+                *down = pushed;
+                let key_code = axis_key.key_code;
+                events.push(if pushed {
+                    PlayerEvent::GamepadKeyDown { key_code }
+                } else {
+                    PlayerEvent::GamepadKeyUp { key_code }
+                });
+            }
+        }
+        events
+    }
+
+    /// Whether a stick is currently moving the cursor, in which case
+    /// [`GamepadMapper::tick`] should be called regularly.
+    pub fn is_moving_cursor(&self) -> bool {
+        self.cursor_velocity().is_some()
+    }
+
+    /// The velocity of the cursor in pixels per second, if it's moving.
+    fn cursor_velocity(&self) -> Option<(f64, f64)> {
+        let stick_mouse = self.profile.stick_mouse.as_ref()?;
+        let (x_axis, y_axis) = stick_mouse.stick.axes();
+        let x = self.axes.get(&x_axis).copied().unwrap_or_default();
+        let y = self.axes.get(&y_axis).copied().unwrap_or_default();
+
+        let magnitude = x.hypot(y);
+        if magnitude <= stick_mouse.deadzone {
+            return None;
+        }
+
+        // Rescale so that movement starts from zero at the edge of the deadzone.
+        let deflection = ((magnitude - stick_mouse.deadzone)
+            / (1.0 - stick_mouse.deadzone).max(f64::EPSILON))
+        .min(1.0);
+        let speed = stick_mouse.speed * deflection.powf(stick_mouse.acceleration.max(0.0));
+
+        // The vertical axis points up, unlike the viewport.
+        Some((x / magnitude * speed, -y / magnitude * speed))
+    }
+
+    /// Moves the cursor by the time that has passed, keeping it within a
+    /// viewport of the given size.
+    ///
+    /// Returns the event to send to the player if the cursor is moving.
+    pub fn tick(&mut self, dt: Duration, width: f64, height: f64) -> Option<PlayerEvent> {
+        let (dx, dy) = self.cursor_velocity()?;
+        let dt = dt.as_secs_f64();
+        let x = (self.cursor.0 + dx * dt).clamp(0.0, (width - 1.0).max(0.0));
+        let y = (self.cursor.1 + dy * dt).clamp(0.0, (height - 1.0).max(0.0));
+        self.cursor = (x, y);
+        Some(PlayerEvent::MouseMove { x, y })
+    }
+
+    /// Releases all buttons and keys held through the profile.
+    pub fn release_all(&mut self) -> Vec<PlayerEvent> {
+        let buttons: Vec<_> = self.buttons_down.iter().copied().collect();
+        let mut events: Vec<_> = buttons
+            .into_iter()
+            .flat_map(|button| self.button_up(button))
+            .collect();
+
+        for (axis_key, down) in self.profile.axis_keys.iter().zip(&mut self.axis_keys_down) {
+            if std::mem::take(down) {
+                events.push(PlayerEvent::GamepadKeyUp {
+                    key_code: axis_key.key_code,
+                });
+            }
+        }
+        self.axes.clear();
+
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gamepad::{AxisKey, Chord, Stick, StickMouse};
+    use ruffle_core::events::{KeyCode, MouseButton};
+
+    fn stick_mouse_profile() -> GamepadProfile {
+        GamepadProfile {
+            stick_mouse: Some(StickMouse {
+                stick: Stick::Left,
+                deadzone: 0.2,
+                speed: 100.0,
+                acceleration: 2.0,
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn position(event: Option<PlayerEvent>) -> (f64, f64) {
+        match event {
+            Some(PlayerEvent::MouseMove { x, y }) => (x, y),
+            event => panic!("expected a mouse move, got {event:?}"),
+        }
+    }
+
+    fn assert_near(expected: (f64, f64), actual: (f64, f64)) {
+        assert!(
+            (expected.0 - actual.0).abs() < 1e-9 && (expected.1 - actual.1).abs() < 1e-9,
+            "expected {expected:?}, got {actual:?}"
+        );
+    }
+
+    #[test]
+    fn stick_deadzone() {
+        let mut mapper = GamepadMapper::new(stick_mouse_profile());
+        mapper.set_cursor_position(50.0, 50.0);
+
+        mapper.axis_changed(GamepadAxis::LeftStickX, 0.1);
+        mapper.axis_changed(GamepadAxis::LeftStickY, 0.1);
+        assert!(!mapper.is_moving_cursor());
+        assert!(mapper.tick(Duration::from_secs(1), 100.0, 100.0).is_none());
+
+        // The other stick doesn't move the cursor.
+        mapper.axis_changed(GamepadAxis::RightStickX, 1.0);
+        assert!(!mapper.is_moving_cursor());
+    }
+
+    #[test]
+    fn stick_movement() {
+        let mut mapper = GamepadMapper::new(stick_mouse_profile());
+        mapper.set_cursor_position(50.0, 50.0);
+
+        // Full deflection moves at full speed, and up is towards the top.
+        mapper.axis_changed(GamepadAxis::LeftStickY, 1.0);
+        assert!(mapper.is_moving_cursor());
+        let event = mapper.tick(Duration::from_millis(100), 100.0, 100.0);
+        assert_near((50.0, 40.0), position(event));
+
+        // Halfway between the deadzone and the edge is a quarter of the speed.
+        mapper.axis_changed(GamepadAxis::LeftStickY, 0.0);
+        mapper.axis_changed(GamepadAxis::LeftStickX, 0.6);
+        let event = mapper.tick(Duration::from_millis(100), 100.0, 100.0);
+        assert_near((52.5, 40.0), position(event));
+
+        // The cursor stays within the viewport.
+        mapper.axis_changed(GamepadAxis::LeftStickX, 1.0);
+        let event = mapper.tick(Duration::from_secs(10), 100.0, 100.0);
+        assert_near((99.0, 40.0), position(event));
+    }
+
+    #[test]
+    fn axis_keys() {
+        let mut mapper = GamepadMapper::new(GamepadProfile {
+            axis_keys: vec![
+                AxisKey {
+                    axis: GamepadAxis::LeftStickY,
+                    threshold: 0.5,
+                    key_code: KeyCode::UP,
+                },
+                AxisKey {
+                    axis: GamepadAxis::LeftStickY,
+                    threshold: -0.5,
+                    key_code: KeyCode::DOWN,
+                },
+            ],
+            ..Default::default()
+        });
+
+        assert!(mapper.axis_changed(GamepadAxis::LeftStickY, 0.3).is_empty());
+        assert!(matches!(
+            mapper.axis_changed(GamepadAxis::LeftStickY, 0.7).as_slice(),
+            [PlayerEvent::GamepadKeyDown {
+                key_code: KeyCode::UP
+            }]
+        ));
+        assert!(mapper.axis_changed(GamepadAxis::LeftStickY, 0.9).is_empty());
+        assert!(matches!(
+            mapper
+                .axis_changed(GamepadAxis::LeftStickY, -0.8)
+                .as_slice(),
+            [
+                PlayerEvent::GamepadKeyUp {
+                    key_code: KeyCode::UP
+                },
+                PlayerEvent::GamepadKeyDown {
+                    key_code: KeyCode::DOWN
+                }
+            ]
+        ));
+        assert!(matches!(
+            mapper.release_all().as_slice(),
+            [PlayerEvent::GamepadKeyUp {
+                key_code: KeyCode::DOWN
+            }]
+        ));
+    }
+
+    #[test]
+    fn buttons() {
+        let mut mapper = GamepadMapper::new(GamepadProfile {
+            buttons: vec![(GamepadButton::Start, KeyCode::ENTER)],
+            mouse_buttons: vec![(GamepadButton::RightTrigger2, MouseButton::Left)],
+            ..Default::default()
+        });
+        mapper.set_cursor_position(10.0, 20.0);
+
+        assert!(matches!(
+            mapper.button_down(GamepadButton::Start).as_slice(),
+            [PlayerEvent::GamepadKeyDown {
+                key_code: KeyCode::ENTER
+            }]
+        ));
+        match mapper.button_down(GamepadButton::RightTrigger2).as_slice() {
+            [
+                PlayerEvent::MouseDown {
+                    x,
+                    y,
+                    button: MouseButton::Left,
+                    index: None,
+                },
+            ] => assert_near((10.0, 20.0), (*x, *y)),
+            events => panic!("expected a mouse down, got {events:?}"),
+        }
+        assert!(matches!(
+            mapper.button_up(GamepadButton::RightTrigger2).as_slice(),
+            [PlayerEvent::MouseUp {
+                button: MouseButton::Left,
+                ..
+            }]
+        ));
+        assert!(matches!(
+            mapper.button_down(GamepadButton::South).as_slice(),
+            [PlayerEvent::GamepadButtonDown {
+                button: GamepadButton::South
+            }]
+        ));
+
+        // Repeated presses are ignored.
+        assert!(mapper.button_down(GamepadButton::South).is_empty());
+    }
+
+    #[test]
+    fn chords() {
+        let mut mapper = GamepadMapper::new(GamepadProfile {
+            chords: vec![Chord {
+                buttons: vec![GamepadButton::Select, GamepadButton::Start],
+                key_code: KeyCode::ESCAPE,
+            }],
+            ..Default::default()
+        });
+
+        assert!(matches!(
+            mapper.button_down(GamepadButton::Select).as_slice(),
+            [PlayerEvent::GamepadButtonDown { .. }]
+        ));
+        assert!(matches!(
+            mapper.button_down(GamepadButton::Start).as_slice(),
+            [
+                PlayerEvent::GamepadButtonDown { .. },
+                PlayerEvent::GamepadKeyDown {
+                    key_code: KeyCode::ESCAPE
+                }
+            ]
+        ));
+        assert!(matches!(
+            mapper.button_up(GamepadButton::Select).as_slice(),
+            [
+                PlayerEvent::GamepadKeyUp {
+                    key_code: KeyCode::ESCAPE
+                },
+                PlayerEvent::GamepadButtonUp { .. }
+            ]
+        ));
+        assert!(matches!(
+            mapper.button_up(GamepadButton::Start).as_slice(),
+            [PlayerEvent::GamepadButtonUp { .. }]
+        ));
+    }
+}
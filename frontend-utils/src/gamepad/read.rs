@@ -0,0 +1,291 @@
+use crate::gamepad::{
+    AxisKey, Chord, GamepadProfile, GamepadProfiles, StickMouse, parse_mouse_button,
+};
+use crate::parse::{DocumentHolder, ItemExt, ParseContext, ParseDetails, ParseWarning, ReadExt};
+use ruffle_core::events::{GamepadButton, KeyCode};
+use toml_edit::{DocumentMut, Table};
+
+pub fn read_gamepad_profiles(input: &str) -> ParseDetails<GamepadProfiles> {
+    let document = match input.parse::<DocumentMut>() {
+        Ok(document) => document,
+        Err(e) => {
+            return ParseDetails {
+                result: Default::default(),
+                warnings: vec![ParseWarning::InvalidToml(e)],
+            };
+        }
+    };
+
+    let mut result = GamepadProfiles::default();
+    let mut cx = ParseContext::default();
+
+    result.active = document.parse_from_str(&mut cx, "active");
+
+    document.get_array_of_tables(&mut cx, "profile", |cx, profiles| {
+        for profile in profiles.iter() {
+            result.profiles.push(read_profile(cx, profile));
+        }
+    });
+
+    ParseDetails {
+        warnings: cx.warnings,
+        result: DocumentHolder::new(result, document),
+    }
+}
+
+fn read_profile<'a>(cx: &mut ParseContext<'a>, profile: &'a Table) -> GamepadProfile {
+    let name = profile.parse_from_str(cx, "name").unwrap_or_default();
+
+    let stick_mouse = profile.get_table_like(cx, "stick_mouse", |cx, table| {
+        let defaults = StickMouse::default();
+        StickMouse {
+            stick: table.parse_from_str(cx, "stick").unwrap_or(defaults.stick),
+            deadzone: table
+                .get_float_like(cx, "deadzone")
+                .unwrap_or(defaults.deadzone),
+            speed: table.get_float_like(cx, "speed").unwrap_or(defaults.speed),
+            acceleration: table
+                .get_float_like(cx, "acceleration")
+                .unwrap_or(defaults.acceleration),
+        }
+    });
+
+    let mut buttons = Vec::new();
+    profile.get_table_like(cx, "buttons", |cx, table| {
+        for (button, key_code) in table.iter() {
+            cx.push_key(button);
+
+            if let Ok(button) = button.parse::<GamepadButton>() {
+                if let Some(key_code) = key_code.as_integer_or_warn(cx) {
+                    buttons.push((button, KeyCode::from_code(key_code as u32)));
+                }
+            } else {
+                cx.unsupported_value(button.to_owned());
+            }
+
+            cx.pop_key();
+        }
+    });
+
+    let mut mouse_buttons = Vec::new();
+    profile.get_table_like(cx, "mouse_buttons", |cx, table| {
+        for (button, mouse_button) in table.iter() {
+            cx.push_key(button);
+
+            if let Ok(button) = button.parse::<GamepadButton>() {
+                if let Some(name) = mouse_button.as_str_or_warn(cx) {
+                    if let Some(mouse_button) = parse_mouse_button(name) {
+                        mouse_buttons.push((button, mouse_button));
+                    } else {
+                        cx.unsupported_value(name.to_owned());
+                    }
+                }
+            } else {
+                cx.unsupported_value(button.to_owned());
+            }
+
+            cx.pop_key();
+        }
+    });
+
+    let mut axis_keys = Vec::new();
+    profile.get_array_of_tables(cx, "axis_key", |cx, tables| {
+        for table in tables.iter() {
+            let axis = table.parse_from_str(cx, "axis");
+            let threshold = table.get_float_like(cx, "threshold");
+            let key_code = table.get_integer(cx, "key");
+            if let (Some(axis), Some(threshold), Some(key_code)) = (axis, threshold, key_code) {
+                axis_keys.push(AxisKey {
+                    axis,
+                    threshold,
+                    key_code: KeyCode::from_code(key_code as u32),
+                });
+            }
+        }
+    });
+
+    let mut chords = Vec::new();
+    profile.get_array_of_tables(cx, "chord", |cx, tables| {
+        for table in tables.iter() {
+            let buttons = read_buttons(cx, table);
+            if let Some(key_code) = table.get_integer(cx, "key")
+                && !buttons.is_empty()
+            {
+                chords.push(Chord {
+                    buttons,
+                    key_code: KeyCode::from_code(key_code as u32),
+                });
+            }
+        }
+    });
+
+    GamepadProfile {
+        name,
+        stick_mouse,
+        buttons,
+        mouse_buttons,
+        axis_keys,
+        chords,
+    }
+}
+
+fn read_buttons(cx: &mut ParseContext, table: &Table) -> Vec<GamepadButton> {
+    let mut result = Vec::new();
+    let Some(item) = table.get("buttons") else {
+        return result;
+    };
+
+    cx.push_key("buttons");
+    if let Some(array) = item.as_array() {
+        for value in array.iter() {
+            if let Some(name) = value.as_str() {
+                match name.parse::<GamepadButton>() {
+                    Ok(button) => result.push(button),
+                    Err(_) => cx.unsupported_value(name.to_owned()),
+                }
+            } else {
+                cx.unexpected_type("string", value.type_name());
+            }
+        }
+    } else {
+        cx.unexpected_type("array", item.type_name());
+    }
+    cx.pop_key();
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gamepad::{GamepadAxis, Stick};
+    use ruffle_core::events::MouseButton;
+
+    #[test]
+    fn empty() {
+        let result = read_gamepad_profiles("");
+        assert_eq!(&GamepadProfiles::default(), result.values());
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+    }
+
+    #[test]
+    fn full_profile() {
+        let result = read_gamepad_profiles(
+            r#"
+            active = "Point and click"
+
+            [[profile]]
+            name = "Point and click"
+
+            [profile.stick_mouse]
+            stick = "right"
+            deadzone = 0.2
+            speed = 1000
+
+            [profile.buttons]
+            start = 13
+
+            [profile.mouse_buttons]
+            right-trigger-2 = "left"
+            left-trigger-2 = "right"
+
+            [[profile.axis_key]]
+            axis = "left-stick-y"
+            threshold = 0.5
+            key = 38
+
+            [[profile.chord]]
+            buttons = ["select", "start"]
+            key = 27
+
+            [[profile]]
+            name = "Empty"
+            "#,
+        );
+        assert_eq!(
+            &GamepadProfiles {
+                active: Some("Point and click".to_string()),
+                profiles: vec![
+                    GamepadProfile {
+                        name: "Point and click".to_string(),
+                        stick_mouse: Some(StickMouse {
+                            stick: Stick::Right,
+                            deadzone: 0.2,
+                            speed: 1000.0,
+                            acceleration: 2.0,
+                        }),
+                        buttons: vec![(GamepadButton::Start, KeyCode::ENTER)],
+                        mouse_buttons: vec![
+                            (GamepadButton::RightTrigger2, MouseButton::Left),
+                            (GamepadButton::LeftTrigger2, MouseButton::Right),
+                        ],
+                        axis_keys: vec![AxisKey {
+                            axis: GamepadAxis::LeftStickY,
+                            threshold: 0.5,
+                            key_code: KeyCode::UP,
+                        }],
+                        chords: vec![Chord {
+                            buttons: vec![GamepadButton::Select, GamepadButton::Start],
+                            key_code: KeyCode::ESCAPE,
+                        }],
+                    },
+                    GamepadProfile::new("Empty".to_string()),
+                ],
+            },
+            result.values()
+        );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+    }
+
+    #[test]
+    fn invalid_values() {
+        let result = read_gamepad_profiles(
+            r#"
+            [[profile]]
+            name = "Broken"
+
+            [profile.mouse_buttons]
+            south = "back"
+
+            [[profile.axis_key]]
+            axis = "wheel"
+            threshold = 0.5
+            key = 38
+
+            [[profile.chord]]
+            buttons = ["select", "jump"]
+            key = 27
+            "#,
+        );
+        assert_eq!(
+            &GamepadProfiles {
+                active: None,
+                profiles: vec![GamepadProfile {
+                    chords: vec![Chord {
+                        buttons: vec![GamepadButton::Select],
+                        key_code: KeyCode::ESCAPE,
+                    }],
+                    ..GamepadProfile::new("Broken".to_string())
+                }],
+            },
+            result.values()
+        );
+        assert_eq!(
+            vec![
+                ParseWarning::UnsupportedValue {
+                    value: "back".to_string(),
+                    path: "profile.mouse_buttons.south".to_string()
+                },
+                ParseWarning::UnsupportedValue {
+                    value: "wheel".to_string(),
+                    path: "profile.axis_key.axis".to_string()
+                },
+                ParseWarning::UnsupportedValue {
+                    value: "jump".to_string(),
+                    path: "profile.chord.buttons".to_string()
+                },
+            ],
+            result.warnings
+        );
+    }
+}
@@ -0,0 +1,215 @@
+use crate::gamepad::{GamepadProfile, GamepadProfiles, mouse_button_name};
+use crate::parse::DocumentHolder;
+use crate::write::TableExt;
+use toml_edit::{Array, ArrayOfTables, Item, Table, value};
+
+pub struct GamepadProfilesWriter<'a>(&'a mut DocumentHolder<GamepadProfiles>);
+
+impl<'a> GamepadProfilesWriter<'a> {
+    pub fn new(profiles: &'a mut DocumentHolder<GamepadProfiles>) -> Self {
+        Self(profiles)
+    }
+
+    fn with_underlying_table(
+        &mut self,
+        fun: impl FnOnce(&mut GamepadProfiles, &mut ArrayOfTables),
+    ) {
+        self.0.edit(|values, toml_document| {
+            let table = toml_document.get_or_create_array_of_tables("profile");
+            fun(values, table)
+        })
+    }
+
+    pub fn set_active(&mut self, active: Option<String>) {
+        self.0.edit(|values, toml_document| {
+            if let Some(active) = &active {
+                toml_document["active"] = value(active);
+            } else {
+                toml_document.remove("active");
+            }
+            values.active = active;
+        })
+    }
+
+    pub fn add(&mut self, profile: GamepadProfile) {
+        self.with_underlying_table(|values, table| {
+            let mut profile_table = Table::new();
+            write_profile(&mut profile_table, &profile);
+            table.push(profile_table);
+            values.profiles.push(profile);
+        })
+    }
+
+    pub fn set_profile(&mut self, index: usize, profile: GamepadProfile) {
+        self.with_underlying_table(|values, array_of_tables| {
+            let table = array_of_tables
+                .get_mut(index)
+                .expect("invalid profile index");
+            write_profile(table, &profile);
+            values.profiles[index] = profile;
+        })
+    }
+
+    pub fn remove(&mut self, index: usize) {
+        self.with_underlying_table(|values, table| {
+            table.remove(index);
+            values.profiles.remove(index);
+        })
+    }
+}
+
+fn write_profile(table: &mut Table, profile: &GamepadProfile) {
+    table["name"] = value(&profile.name);
+
+    if let Some(stick_mouse) = &profile.stick_mouse {
+        let mut stick_mouse_table = Table::new();
+        stick_mouse_table["stick"] = value(stick_mouse.stick.as_str());
+        stick_mouse_table["deadzone"] = value(stick_mouse.deadzone);
+        stick_mouse_table["speed"] = value(stick_mouse.speed);
+        stick_mouse_table["acceleration"] = value(stick_mouse.acceleration);
+        table["stick_mouse"] = Item::Table(stick_mouse_table);
+    } else {
+        table.remove("stick_mouse");
+    }
+
+    if profile.buttons.is_empty() {
+        table.remove("buttons");
+    } else {
+        let mut buttons = Table::new();
+        for (button, key_code) in &profile.buttons {
+            buttons[&button.to_string()] = value(key_code.value() as i64);
+        }
+        table["buttons"] = Item::Table(buttons);
+    }
+
+    if profile.mouse_buttons.is_empty() {
+        table.remove("mouse_buttons");
+    } else {
+        let mut mouse_buttons = Table::new();
+        for (button, mouse_button) in &profile.mouse_buttons {
+            mouse_buttons[&button.to_string()] = value(mouse_button_name(*mouse_button));
+        }
+        table["mouse_buttons"] = Item::Table(mouse_buttons);
+    }
+
+    if profile.axis_keys.is_empty() {
+        table.remove("axis_key");
+    } else {
+        let mut axis_keys = ArrayOfTables::new();
+        for axis_key in &profile.axis_keys {
+            let mut axis_key_table = Table::new();
+            axis_key_table["axis"] = value(axis_key.axis.as_str());
+            axis_key_table["threshold"] = value(axis_key.threshold);
+            axis_key_table["key"] = value(axis_key.key_code.value() as i64);
+            axis_keys.push(axis_key_table);
+        }
+        table["axis_key"] = Item::ArrayOfTables(axis_keys);
+    }
+
+    if profile.chords.is_empty() {
+        table.remove("chord");
+    } else {
+        let mut chords = ArrayOfTables::new();
+        for chord in &profile.chords {
+            let mut chord_table = Table::new();
+            chord_table["buttons"] = value(
+                chord
+                    .buttons
+                    .iter()
+                    .map(|button| button.to_string())
+                    .collect::<Array>(),
+            );
+            chord_table["key"] = value(chord.key_code.value() as i64);
+            chords.push(chord_table);
+        }
+        table["chord"] = Item::ArrayOfTables(chords);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gamepad::{AxisKey, Chord, GamepadAxis, StickMouse, read_gamepad_profiles};
+    use ruffle_core::events::{GamepadButton, KeyCode, MouseButton};
+
+    crate::define_serialization_test_helpers!(
+        read_gamepad_profiles,
+        GamepadProfiles,
+        GamepadProfilesWriter
+    );
+
+    #[test]
+    fn set_active() {
+        test(
+            "",
+            |writer| writer.set_active(Some("Mouse".to_string())),
+            "active = \"Mouse\"\n",
+        );
+        test("active = \"Mouse\"\n", |writer| writer.set_active(None), "");
+    }
+
+    #[test]
+    fn add_profile() {
+        test(
+            "",
+            |writer| writer.add(GamepadProfile::new("Empty".to_string())),
+            "[[profile]]\nname = \"Empty\"\n",
+        );
+    }
+
+    #[test]
+    fn add_full_profile() {
+        let mut profiles = read_gamepad_profiles("").result;
+        let mut writer = GamepadProfilesWriter::new(&mut profiles);
+        writer.add(GamepadProfile {
+            name: "Point and click".to_string(),
+            stick_mouse: Some(StickMouse::default()),
+            buttons: vec![(GamepadButton::Start, KeyCode::ENTER)],
+            mouse_buttons: vec![
+                (GamepadButton::RightTrigger2, MouseButton::Left),
+                (GamepadButton::LeftTrigger2, MouseButton::Right),
+            ],
+            axis_keys: vec![AxisKey {
+                axis: GamepadAxis::RightStickY,
+                threshold: -0.5,
+                key_code: KeyCode::DOWN,
+            }],
+            chords: vec![Chord {
+                buttons: vec![GamepadButton::Select, GamepadButton::Start],
+                key_code: KeyCode::ESCAPE,
+            }],
+        });
+        writer.set_active(Some("Point and click".to_string()));
+        check_roundtrip(&profiles);
+    }
+
+    #[test]
+    fn modify_profile() {
+        let mut profiles = read_gamepad_profiles(
+            "[[profile]]\nname = \"Old\"\ncomment = \"kept\"\n\n[profile.buttons]\nsouth = 32\n",
+        )
+        .result;
+        GamepadProfilesWriter::new(&mut profiles).set_profile(
+            0,
+            GamepadProfile {
+                stick_mouse: Some(StickMouse::default()),
+                ..GamepadProfile::new("New".to_string())
+            },
+        );
+        check_roundtrip(&profiles);
+
+        // Keys the profile doesn't know about are left alone.
+        let serialized = profiles.serialize();
+        assert!(serialized.contains("comment = \"kept\""));
+        assert!(!serialized.contains("[profile.buttons]"));
+    }
+
+    #[test]
+    fn remove_profile() {
+        test(
+            "[[profile]]\nname = \"A\"\n\n[[profile]]\nname = \"B\"\n",
+            |writer| writer.remove(0),
+            "[[profile]]\nname = \"B\"\n",
+        );
+    }
+}
@@ -1,6 +1,8 @@
 pub mod bookmarks;
 pub mod bundle;
+pub mod gamepad;
 pub mod parse;
+pub mod profiles;
 pub mod recents;
 pub mod write;
 
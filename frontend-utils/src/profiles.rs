@@ -0,0 +1,156 @@
+mod read;
+mod write;
+
+pub use read::read_profiles;
+pub use write::ProfilesWriter;
+
+use crate::player_options::PlayerOptions;
+use ruffle_core::events::{GamepadButton, KeyCode};
+use sha2::{Digest, Sha256};
+use std::fmt::Write;
+use url::Url;
+
+/// What a profile is matched against when content is opened.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ProfileKey {
+    /// Content opened from exactly this URL.
+    Url(Url),
+
+    /// A SWF with this SHA-256 hash (as lowercase hex), wherever it was opened from.
+    Hash(String),
+}
+
+/// Settings remembered for a specific movie, applied whenever it's opened.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Profile {
+    pub key: ProfileKey,
+    pub name: String,
+    pub player: PlayerOptions,
+    pub gamepad_button_mapping: Vec<(GamepadButton, KeyCode)>,
+    pub volume: Option<f32>,
+}
+
+impl Profile {
+    pub fn new(key: ProfileKey, name: String) -> Self {
+        Self {
+            key,
+            name,
+            player: Default::default(),
+            gamepad_button_mapping: Vec::new(),
+            volume: None,
+        }
+    }
+
+    pub fn is_invalid(&self) -> bool {
+        matches!(&self.key, ProfileKey::Url(url) if url.as_str() == crate::INVALID_URL)
+    }
+}
+
+pub type Profiles = Vec<Profile>;
+
+/// Find the profile for the given content.
+///
+/// Profiles matching the URL take priority over ones matching the hash.
+/// The hash is only calculated if there's a profile that needs it.
+pub fn find_profile<'a>(
+    profiles: &'a Profiles,
+    url: &Url,
+    hash: impl FnOnce() -> Option<String>,
+) -> Option<(usize, &'a Profile)> {
+    let by_url = profiles
+        .iter()
+        .enumerate()
+        .find(|(_, profile)| matches!(&profile.key, ProfileKey::Url(key) if key == url));
+    if by_url.is_some() {
+        return by_url;
+    }
+
+    if !profiles
+        .iter()
+        .any(|profile| matches!(profile.key, ProfileKey::Hash(_)))
+    {
+        return None;
+    }
+    let hash = hash()?;
+    profiles
+        .iter()
+        .enumerate()
+        .find(|(_, profile)| matches!(&profile.key, ProfileKey::Hash(key) if *key == hash))
+}
+
+/// Calculate the hash of a local file, for matching against [`ProfileKey::Hash`].
+///
+/// Returns `None` for remote content and directories, such as bundles.
+#[cfg(feature = "fs")]
+pub fn local_content_hash(url: &Url) -> Option<String> {
+    let path = url.to_file_path().ok()?;
+    if !path.is_file() {
+        return None;
+    }
+    std::fs::read(path).ok().map(|data| content_hash(&data))
+}
+
+/// Calculate the hash used by [`ProfileKey::Hash`].
+pub fn content_hash(data: &[u8]) -> String {
+    Sha256::digest(data)
+        .iter()
+        .fold(String::with_capacity(64), |mut hash, byte| {
+            let _ = write!(hash, "{byte:02x}");
+            hash
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn url(url: &str) -> Url {
+        Url::parse(url).expect("url in test should parse")
+    }
+
+    #[test]
+    fn hash() {
+        assert_eq!(
+            content_hash(b"FWS"),
+            "2a44829a6f3aa6ac5c2752e7de248663c2593a0855acb60cfe1d4c450fd77953"
+        );
+    }
+
+    #[test]
+    fn find_by_url() {
+        let profiles = vec![
+            Profile::new(
+                ProfileKey::Url(url("https://example.com/a.swf")),
+                "A".into(),
+            ),
+            Profile::new(
+                ProfileKey::Url(url("https://example.com/b.swf")),
+                "B".into(),
+            ),
+        ];
+        let found = find_profile(&profiles, &url("https://example.com/b.swf"), || {
+            panic!("hash shouldn't be needed")
+        });
+        assert_eq!(found.map(|(index, _)| index), Some(1));
+        assert_eq!(
+            find_profile(&profiles, &url("https://example.com/c.swf"), || {
+                panic!("hash shouldn't be needed")
+            }),
+            None
+        );
+    }
+
+    #[test]
+    fn find_by_hash() {
+        let profiles = vec![
+            Profile::new(ProfileKey::Hash("1234".into()), "Hash".into()),
+            Profile::new(ProfileKey::Url(url("file:///game.swf")), "Url".into()),
+        ];
+        let found = find_profile(&profiles, &url("file:///game.swf"), || Some("1234".into()));
+        assert_eq!(found.map(|(index, _)| index), Some(1));
+        let found = find_profile(&profiles, &url("file:///copy.swf"), || Some("1234".into()));
+        assert_eq!(found.map(|(index, _)| index), Some(0));
+        let found = find_profile(&profiles, &url("file:///other.swf"), || Some("5678".into()));
+        assert_eq!(found, None);
+    }
+}
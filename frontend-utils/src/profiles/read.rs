@@ -0,0 +1,187 @@
+use crate::parse::{DocumentHolder, ItemExt, ParseContext, ParseDetails, ParseWarning, ReadExt};
+use crate::player_options::read_player_options;
+use crate::profiles::{Profile, ProfileKey, Profiles};
+use ruffle_core::events::{GamepadButton, KeyCode};
+use toml_edit::DocumentMut;
+use url::Url;
+
+pub fn read_profiles(input: &str) -> ParseDetails<Profiles> {
+    let document = match input.parse::<DocumentMut>() {
+        Ok(document) => document,
+        Err(e) => {
+            return ParseDetails {
+                result: Default::default(),
+                warnings: vec![ParseWarning::InvalidToml(e)],
+            };
+        }
+    };
+
+    let mut result = Vec::new();
+    let mut cx = ParseContext::default();
+
+    document.get_array_of_tables(&mut cx, "profile", |cx, profiles| {
+        for profile in profiles.iter() {
+            let key = if let Some(hash) = profile.parse_from_str::<String>(cx, "hash") {
+                ProfileKey::Hash(hash)
+            } else {
+                ProfileKey::Url(match profile.parse_from_str(cx, "url") {
+                    Some(value) => value,
+                    None => Url::parse(crate::INVALID_URL).expect("Url is constant and valid"),
+                })
+            };
+
+            let name = profile.parse_from_str(cx, "name").unwrap_or_default();
+
+            let player = profile
+                .get_table_like(cx, "player", |cx, table| read_player_options(cx, table))
+                .unwrap_or_default();
+
+            let mut gamepad_button_mapping = Vec::new();
+            profile.get_table_like(cx, "gamepad", |cx, gamepad| {
+                for (button, key_code) in gamepad.iter() {
+                    cx.push_key(button);
+
+                    if let Ok(button) = button.parse::<GamepadButton>() {
+                        if let Some(key_code) = key_code.as_integer_or_warn(cx) {
+                            gamepad_button_mapping
+                                .push((button, KeyCode::from_code(key_code as u32)));
+                        }
+                    } else {
+                        cx.unsupported_value(button.to_owned());
+                    }
+
+                    cx.pop_key();
+                }
+            });
+
+            let volume = profile.get_float_like(cx, "volume").map(|v| v as f32);
+
+            result.push(Profile {
+                key,
+                name,
+                player,
+                gamepad_button_mapping,
+                volume,
+            });
+        }
+    });
+
+    ParseDetails {
+        warnings: cx.warnings,
+        result: DocumentHolder::new(result, document),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::player_options::PlayerOptions;
+    use ruffle_core::StageScaleMode;
+
+    fn url(url: &str) -> Url {
+        Url::parse(url).expect("url in test should parse")
+    }
+
+    #[test]
+    fn empty() {
+        let result = read_profiles("");
+        assert_eq!(&Vec::<Profile>::new(), result.values());
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+    }
+
+    #[test]
+    fn invalid_key() {
+        let result = read_profiles("[[profile]]\nname = \"Nothing\"");
+        assert_eq!(
+            &vec![Profile::new(
+                ProfileKey::Url(url(crate::INVALID_URL)),
+                "Nothing".to_string()
+            )],
+            result.values()
+        );
+        assert!(result.values()[0].is_invalid());
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+    }
+
+    #[test]
+    fn full_profile() {
+        let result = read_profiles(
+            r#"
+            [[profile]]
+            url = "https://example.com/game.swf"
+            name = "Game"
+            volume = 0.5
+
+            [profile.player]
+            scale_mode = "no_scale"
+
+            [profile.player.parameters]
+            level = "3"
+
+            [profile.gamepad]
+            south = 32
+            start = 13
+
+            [[profile]]
+            hash = "abcdef"
+            name = "Other"
+            "#,
+        );
+        assert_eq!(
+            &vec![
+                Profile {
+                    key: ProfileKey::Url(url("https://example.com/game.swf")),
+                    name: "Game".to_string(),
+                    player: PlayerOptions {
+                        parameters: vec![("level".to_string(), "3".to_string())],
+                        scale: Some(StageScaleMode::NoScale),
+                        ..Default::default()
+                    },
+                    gamepad_button_mapping: vec![
+                        (GamepadButton::South, KeyCode::from_code(32)),
+                        (GamepadButton::Start, KeyCode::from_code(13)),
+                    ],
+                    volume: Some(0.5),
+                },
+                Profile::new(ProfileKey::Hash("abcdef".to_string()), "Other".to_string()),
+            ],
+            result.values()
+        );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+    }
+
+    #[test]
+    fn invalid_gamepad_button() {
+        let result = read_profiles(
+            r#"
+            [[profile]]
+            url = "https://example.com/game.swf"
+
+            [profile.gamepad]
+            jump = 32
+            south = "space"
+            "#,
+        );
+        assert_eq!(
+            &vec![Profile::new(
+                ProfileKey::Url(url("https://example.com/game.swf")),
+                String::new()
+            )],
+            result.values()
+        );
+        assert_eq!(
+            vec![
+                ParseWarning::UnsupportedValue {
+                    value: "jump".to_string(),
+                    path: "profile.gamepad.jump".to_string()
+                },
+                ParseWarning::UnexpectedType {
+                    expected: "integer",
+                    actual: "string",
+                    path: "profile.gamepad.south".to_string()
+                },
+            ],
+            result.warnings
+        );
+    }
+}
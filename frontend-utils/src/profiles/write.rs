@@ -0,0 +1,246 @@
+use crate::parse::DocumentHolder;
+use crate::player_options::{PlayerOptions, PlayerOptionsWriter, write_player_options};
+use crate::profiles::{Profile, ProfileKey, Profiles};
+use crate::write::TableExt;
+use ruffle_core::events::{GamepadButton, KeyCode};
+use toml_edit::{ArrayOfTables, DocumentMut, Item, Table, value};
+
+pub struct ProfilesWriter<'a>(&'a mut DocumentHolder<Profiles>);
+
+impl<'a> ProfilesWriter<'a> {
+    pub fn new(profiles: &'a mut DocumentHolder<Profiles>) -> Self {
+        Self(profiles)
+    }
+
+    fn with_underlying_table(&mut self, fun: impl FnOnce(&mut Profiles, &mut ArrayOfTables)) {
+        self.0.edit(|values, toml_document| {
+            let table = toml_document.get_or_create_array_of_tables("profile");
+            fun(values, table)
+        })
+    }
+
+    fn with_profile_table(&mut self, index: usize, fun: impl FnOnce(&mut Profiles, &mut Table)) {
+        self.with_underlying_table(|values, array_of_tables| {
+            let table = array_of_tables
+                .get_mut(index)
+                .expect("invalid profile index");
+            fun(values, table)
+        })
+    }
+
+    pub fn add(&mut self, profile: Profile) {
+        self.with_underlying_table(|values, table| {
+            let mut profile_table = Table::new();
+            write_key(&mut profile_table, &profile.key);
+            profile_table["name"] = value(&profile.name);
+            if let Some(volume) = profile.volume {
+                profile_table["volume"] = value(volume as f64);
+            }
+            write_player(&mut profile_table, &profile.player);
+            write_gamepad(&mut profile_table, &profile.gamepad_button_mapping);
+            table.push(profile_table);
+            values.push(profile);
+        })
+    }
+
+    pub fn set_key(&mut self, index: usize, key: ProfileKey) {
+        self.with_profile_table(index, |values, table| {
+            write_key(table, &key);
+            values[index].key = key;
+        })
+    }
+
+    pub fn set_name(&mut self, index: usize, name: String) {
+        self.with_profile_table(index, |values, table| {
+            table["name"] = value(&name);
+            values[index].name = name;
+        })
+    }
+
+    pub fn set_volume(&mut self, index: usize, volume: Option<f32>) {
+        self.with_profile_table(index, |values, table| {
+            if let Some(volume) = volume {
+                table["volume"] = value(volume as f64);
+            } else {
+                table.remove("volume");
+            }
+            values[index].volume = volume;
+        })
+    }
+
+    pub fn set_player_options(&mut self, index: usize, player: PlayerOptions) {
+        self.with_profile_table(index, |values, table| {
+            write_player(table, &player);
+            values[index].player = player;
+        })
+    }
+
+    pub fn set_gamepad_button_mapping(
+        &mut self,
+        index: usize,
+        mapping: Vec<(GamepadButton, KeyCode)>,
+    ) {
+        self.with_profile_table(index, |values, table| {
+            write_gamepad(table, &mapping);
+            values[index].gamepad_button_mapping = mapping;
+        })
+    }
+
+    pub fn remove(&mut self, index: usize) {
+        self.with_underlying_table(|values, table| {
+            table.remove(index);
+            values.remove(index);
+        })
+    }
+}
+
+fn write_key(table: &mut Table, key: &ProfileKey) {
+    match key {
+        ProfileKey::Url(url) => {
+            table.remove("hash");
+            table["url"] = value(url.as_str());
+        }
+        ProfileKey::Hash(hash) => {
+            table.remove("url");
+            table["hash"] = value(hash);
+        }
+    }
+}
+
+fn write_player(table: &mut Table, player: &PlayerOptions) {
+    if *player == PlayerOptions::default() {
+        table.remove("player");
+        return;
+    }
+
+    // Reuse the existing table, so that only the changed options are touched.
+    let mut document = DocumentMut::new();
+    if let Some(existing) = table.get("player").and_then(Item::as_table) {
+        *document.as_table_mut() = existing.clone();
+    }
+    let mut player_document = DocumentHolder::new(player.clone(), document);
+    let mut writer = PlayerOptionsWriter::new(&mut player_document);
+    write_player_options(&mut writer, player);
+    table["player"] = player_document.document().as_item().clone();
+}
+
+fn write_gamepad(table: &mut Table, mapping: &[(GamepadButton, KeyCode)]) {
+    if mapping.is_empty() {
+        table.remove("gamepad");
+        return;
+    }
+
+    let mut gamepad = Table::new();
+    for (button, key_code) in mapping {
+        gamepad[&button.to_string()] = value(key_code.value() as i64);
+    }
+    table["gamepad"] = Item::Table(gamepad);
+}
+
+#[expect(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::profiles::read_profiles;
+    use ruffle_core::StageScaleMode;
+    use url::Url;
+
+    crate::define_serialization_test_helpers!(read_profiles, Profiles, ProfilesWriter);
+
+    #[test]
+    fn add_profile() {
+        test(
+            "",
+            |writer| {
+                writer.add(Profile::new(
+                    ProfileKey::Url(Url::parse("file:///home/user/example.swf").unwrap()),
+                    "example.swf".to_string(),
+                ))
+            },
+            "[[profile]]\nurl = \"file:///home/user/example.swf\"\nname = \"example.swf\"\n",
+        );
+        test(
+            "[[profile]]\nurl = \"file:///home/user/example.swf\"\n",
+            |writer| {
+                writer.add(Profile::new(
+                    ProfileKey::Hash("abcdef".to_string()),
+                    "Game".to_string(),
+                ))
+            },
+            "[[profile]]\nurl = \"file:///home/user/example.swf\"\n\n[[profile]]\nhash = \"abcdef\"\nname = \"Game\"\n",
+        );
+    }
+
+    #[test]
+    fn add_full_profile() {
+        let mut profiles = read_profiles("").result;
+        ProfilesWriter::new(&mut profiles).add(Profile {
+            key: ProfileKey::Url(Url::parse("https://example.com/game.swf").unwrap()),
+            name: "Game".to_string(),
+            player: PlayerOptions {
+                parameters: vec![("level".to_string(), "3".to_string())],
+                scale: Some(StageScaleMode::NoScale),
+                ..Default::default()
+            },
+            gamepad_button_mapping: vec![(GamepadButton::South, KeyCode::from_code(32))],
+            volume: Some(0.5),
+        });
+        check_roundtrip(&profiles);
+    }
+
+    #[test]
+    fn modify_profile() {
+        test(
+            "[[profile]]\nurl = \"file:///example.swf\"\n",
+            |writer| writer.set_name(0, "Custom Name".to_string()),
+            "[[profile]]\nurl = \"file:///example.swf\"\nname = \"Custom Name\"\n",
+        );
+        test(
+            "[[profile]]\nurl = \"file:///example.swf\"\n",
+            |writer| writer.set_key(0, ProfileKey::Hash("abcdef".to_string())),
+            "[[profile]]\nhash = \"abcdef\"\n",
+        );
+        test(
+            "[[profile]]\nurl = \"file:///example.swf\"\n",
+            |writer| writer.set_volume(0, Some(0.5)),
+            "[[profile]]\nurl = \"file:///example.swf\"\nvolume = 0.5\n",
+        );
+        test(
+            "[[profile]]\nurl = \"file:///example.swf\"\nvolume = 0.5\n",
+            |writer| writer.set_volume(0, None),
+            "[[profile]]\nurl = \"file:///example.swf\"\n",
+        );
+    }
+
+    #[test]
+    fn modify_player_options() {
+        let mut profiles = read_profiles(
+            "[[profile]]\nurl = \"file:///example.swf\"\n\n[profile.player]\nquality = \"low\"\n",
+        )
+        .result;
+        let mut writer = ProfilesWriter::new(&mut profiles);
+        writer.set_player_options(
+            0,
+            PlayerOptions {
+                frame_rate: Some(60.0),
+                ..Default::default()
+            },
+        );
+        writer.set_gamepad_button_mapping(0, vec![(GamepadButton::Start, KeyCode::ENTER)]);
+        check_roundtrip(&profiles);
+
+        let mut writer = ProfilesWriter::new(&mut profiles);
+        writer.set_player_options(0, PlayerOptions::default());
+        writer.set_gamepad_button_mapping(0, vec![]);
+        check_roundtrip(&profiles);
+    }
+
+    #[test]
+    fn remove_profile() {
+        test(
+            "[[profile]]\nurl = \"file:///a.swf\"\n\n[[profile]]\nurl = \"file:///b.swf\"\n",
+            |writer| writer.remove(1),
+            "[[profile]]\nurl = \"file:///a.swf\"\n",
+        );
+    }
+}
@@ -16,6 +16,7 @@ use std::fmt::Debug;
 use std::num::NonZeroU32;
 use std::rc::Rc;
 use std::sync::Arc;
+use std::time::Duration;
 use swf::{Color, Rectangle, Twips};
 
 pub struct BitmapCacheEntry {
@@ -25,6 +26,22 @@ pub struct BitmapCacheEntry {
     pub filters: Vec<Filter>,
 }
 
+/// Statistics about the work done by a render backend, as reported by [`RenderBackend::stats`].
+///
+/// Counters are cumulative over the lifetime of the backend, so that callers can sample them
+/// at whatever rate they need.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RenderStats {
+    /// Total time spent tessellating shapes.
+    pub tessellation_time: Duration,
+
+    /// Total number of shapes tessellated.
+    pub tessellated_shapes: u64,
+
+    /// Memory currently used by bitmaps, in bytes, if the backend keeps track of it.
+    pub bitmap_memory: Option<u64>,
+}
+
 pub trait RenderBackend: Any {
     fn viewport_dimensions(&self) -> ViewportDimensions;
     // Do not call this method directly - use `player.set_viewport_dimensions`,
@@ -104,6 +121,12 @@ pub trait RenderBackend: Any {
     fn create_context3d(&mut self, profile: Context3DProfile) -> Result<Box<dyn Context3D>, Error>;
 
     fn debug_info(&self) -> Cow<'static, str>;
+
+    /// Returns statistics about the work done by this backend so far.
+    fn stats(&self) -> RenderStats {
+        RenderStats::default()
+    }
+
     /// An internal name that is used to identify the render-backend.
     fn name(&self) -> &'static str;
 
@@ -17,7 +17,7 @@ use image::imageops::FilterType;
 use ruffle_render::backend::{
     BitmapCacheEntry, Context3D, Context3DProfile, PixelBenderOutput, PixelBenderTarget,
 };
-use ruffle_render::backend::{RenderBackend, ShapeHandle, ViewportDimensions};
+use ruffle_render::backend::{RenderBackend, RenderStats, ShapeHandle, ViewportDimensions};
 use ruffle_render::bitmap::{
     Bitmap, BitmapFormat, BitmapHandle, BitmapSource, PixelRegion, RgbaBufRead, SyncHandle,
 };
@@ -33,9 +33,10 @@ use std::any::Any;
 use std::borrow::Cow;
 use std::cell::Cell;
 use std::num::NonZeroU32;
-use std::sync::Arc;
+use std::sync::{Arc, Weak};
 use swf::Color;
 use tracing::instrument;
+use web_time::Instant;
 use wgpu::SubmissionIndex;
 
 /// Creates a wgpu instance with Ruffle's required configuration.
@@ -76,6 +77,9 @@ pub struct WgpuRenderBackend<T: RenderTarget> {
     pub(crate) offscreen_buffer_pool: Arc<BufferPool<wgpu::Buffer, BufferDimensions>>,
     dynamic_transforms: DynamicTransforms,
     active_frame: ActiveFrame,
+    /// Bitmaps created through this backend, to report their memory usage.
+    bitmaps: Vec<Weak<Texture>>,
+    stats: RenderStats,
 }
 
 impl WgpuRenderBackend<SwapChainTarget> {
@@ -248,6 +252,8 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
             offscreen_buffer_pool: Arc::new(offscreen_buffer_pool),
             dynamic_transforms: transforms,
             active_frame,
+            bitmaps: Vec::new(),
+            stats: RenderStats::default(),
         })
     }
 
@@ -258,9 +264,12 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
         scale: f32,
     ) -> Mesh {
         let shape_id = shape.id;
+        let tessellation_start = Instant::now();
         let lyon_mesh =
             self.shape_tessellator
                 .tessellate_shape_with_scale(shape, bitmap_source, scale);
+        self.stats.tessellation_time += tessellation_start.elapsed();
+        self.stats.tessellated_shapes += 1;
 
         let mut draws = Vec::with_capacity(lyon_mesh.draws.len());
         let mut uniform_buffer = BufferBuilder::new_for_uniform(&self.descriptors.limits);
@@ -456,6 +465,22 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
         Cow::Owned(result.join("\n"))
     }
 
+    fn stats(&self) -> RenderStats {
+        let bitmap_memory = self
+            .bitmaps
+            .iter()
+            .filter_map(Weak::upgrade)
+            .map(|texture| {
+                let size = texture.texture.size();
+                size.width as u64 * size.height as u64 * 4
+            })
+            .sum();
+        RenderStats {
+            bitmap_memory: Some(bitmap_memory),
+            ..self.stats.clone()
+        }
+    }
+
     fn name(&self) -> &'static str {
         if cfg!(target_family = "wasm") {
             let info = self.descriptors.adapter.get_info();
@@ -631,6 +656,7 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
         self.active_frame
             .submit_for_target(&self.descriptors, &self.target, frame_output);
         self.offscreen_texture_pool = TexturePool::new();
+        self.bitmaps.retain(|texture| texture.strong_count() > 0);
     }
 
     #[instrument(level = "debug", skip_all)]
@@ -679,14 +705,15 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
             extent,
         );
 
-        let handle = BitmapHandle(Arc::new(Texture {
+        let texture = Arc::new(Texture {
             texture,
             bind_linear: Default::default(),
             bind_nearest: Default::default(),
             copy_count: Cell::new(0),
-        }));
+        });
+        self.bitmaps.push(Arc::downgrade(&texture));
 
-        Ok(handle)
+        Ok(BitmapHandle(texture))
     }
 
     #[instrument(level = "debug", skip_all)]
@@ -1111,12 +1138,15 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
                     | wgpu::TextureUsages::RENDER_ATTACHMENT
                     | wgpu::TextureUsages::COPY_SRC,
             });
-        Ok(BitmapHandle(Arc::new(Texture {
+        let texture = Arc::new(Texture {
             texture,
             bind_linear: Default::default(),
             bind_nearest: Default::default(),
             copy_count: Cell::new(0),
-        })))
+        });
+        self.bitmaps.push(Arc::downgrade(&texture));
+
+        Ok(BitmapHandle(texture))
     }
 
     fn resolve_sync_handle(
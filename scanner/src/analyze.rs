@@ -2,10 +2,46 @@
 
 use crate::cli_options::AnalyzeOpt;
 use crate::file_results::{FileResults, Step};
+use std::collections::HashMap;
 use std::fs::File;
 
+/// Counts how many files each entry of a list was reported for
+#[derive(Default)]
+struct Ranking {
+    files: HashMap<String, usize>,
+}
+
+impl Ranking {
+    fn add(&mut self, entries: &[String]) {
+        for entry in entries {
+            *self.files.entry(entry.clone()).or_default() += 1;
+        }
+    }
+
+    /// Print the `top` entries that were reported for the most files
+    fn print(&self, title: &str, top: usize) {
+        if self.files.is_empty() {
+            return;
+        }
+
+        let mut ranked: Vec<_> = self.files.iter().collect();
+        ranked.sort_by(|(a_entry, a_files), (b_entry, b_files)| {
+            b_files.cmp(a_files).then_with(|| a_entry.cmp(b_entry))
+        });
+
+        println!("{title} ({} distinct):", ranked.len());
+
+        let digits = ranked[0].1.to_string().len() + 4;
+        for (entry, files) in ranked.into_iter().take(top) {
+            println!("{files:>digits$} {entry}");
+        }
+
+        println!();
+    }
+}
+
 /// Generate and print statistics related to a scan's results
-pub fn analyze(results: impl Iterator<Item = FileResults>) {
+pub fn analyze(results: impl Iterator<Item = FileResults>, top: usize) {
     let mut total = 0;
     let mut start = 0;
     let mut read = 0;
@@ -13,6 +49,12 @@ pub fn analyze(results: impl Iterator<Item = FileResults>) {
     let mut parse = 0;
     let mut execute = 0;
     let mut complete = 0;
+    let mut blank = 0;
+    let mut fetching = 0;
+
+    let mut stubs = Ranking::default();
+    let mut unsupported = Ranking::default();
+    let mut uncaught_errors = Ranking::default();
 
     for result in results {
         total += 1;
@@ -25,6 +67,19 @@ pub fn analyze(results: impl Iterator<Item = FileResults>) {
             Step::Execute => execute += 1,
             Step::Complete => complete += 1,
         }
+
+        if result.blank_frame == Some(true) {
+            blank += 1;
+        }
+
+        if !result.fetches.is_empty() {
+            fetching += 1;
+        }
+
+        stubs.add(&result.stubs);
+        unsupported.add(&result.unsupported_filters);
+        unsupported.add(&result.unsupported_codecs);
+        uncaught_errors.add(&result.uncaught_errors);
     }
 
     println!("Scanned {total} swf files.");
@@ -47,25 +102,34 @@ pub fn analyze(results: impl Iterator<Item = FileResults>) {
     println!("{parse:>digits$} movies failed to parse");
     println!("{execute:>digits$} movies failed to execute");
     println!("{complete:>digits$} movies completed without errors");
+    println!("{blank:>digits$} movies drew nothing in their last frame");
+    println!("{fetching:>digits$} movies tried to access the network");
     println!();
+
+    stubs.print("Stubs hit, by number of movies", top);
+    unsupported.print("Unsupported filters and codecs, by number of movies", top);
+    uncaught_errors.print("Uncaught errors, by number of movies", top);
 }
 
 pub fn analyze_main(opt: AnalyzeOpt) -> Result<(), std::io::Error> {
     let file = File::open(opt.input_path)?;
     let reader = csv::Reader::from_reader(file);
 
-    analyze(reader.into_deserialize::<FileResults>().map(|r| {
-        match r {
-            Ok(fr) => fr,
-            Err(e) => {
-                // Treat unparsable CSV rows as a scanner panic
-                FileResults {
-                    error: Some(format!("{e}")),
-                    ..FileResults::default()
+    analyze(
+        reader.into_deserialize::<FileResults>().map(|r| {
+            match r {
+                Ok(fr) => fr,
+                Err(e) => {
+                    // Treat unparsable CSV rows as a scanner panic
+                    FileResults {
+                        error: Some(format!("{e}")),
+                        ..FileResults::default()
+                    }
                 }
             }
-        }
-    }));
+        }),
+        opt.top,
+    );
 
     Ok(())
 }
@@ -35,6 +35,14 @@ pub struct ScanOpt {
     /// Filenames to ignore
     #[clap(short = 'i', long = "ignore", action = clap::ArgAction::Append)]
     pub ignore: Vec<String>,
+
+    /// The number of frames to run each movie for
+    #[clap(short = 'f', long = "frames", default_value = "1")]
+    pub frames: u32,
+
+    /// The number of entries to show in each ranking of the analysis
+    #[clap(short = 't', long = "top", default_value = "20")]
+    pub top: usize,
 }
 
 #[derive(Parser, Debug)]
@@ -42,6 +50,10 @@ pub struct AnalyzeOpt {
     /// The CSV file to reanalyze
     #[clap(name = "input")]
     pub input_path: PathBuf,
+
+    /// The number of entries to show in each ranking
+    #[clap(short = 't', long = "top", default_value = "20")]
+    pub top: usize,
 }
 
 #[derive(Parser, Debug)]
@@ -49,4 +61,8 @@ pub struct ExecuteReportOpt {
     /// The single SWF file to parse and run
     #[clap(name = "file")]
     pub input_path: PathBuf,
+
+    /// The number of frames to run the movie for
+    #[clap(short = 'f', long = "frames", default_value = "1")]
+    pub frames: u32,
 }
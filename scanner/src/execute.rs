@@ -3,35 +3,77 @@
 use crate::cli_options::ExecuteReportOpt;
 use crate::file_results::{AvmType, FileResults, Step};
 use crate::logging::{LOCAL_LOGGER, ScanLogBackend, ThreadLocalScanLogger};
+use crate::navigator::ScanNavigatorBackend;
+use crate::renderer::ScanRenderBackend;
+use crate::unsupported::UnsupportedFeatures;
 use ruffle_core::FloatDuration;
 use ruffle_core::PlayerBuilder;
+use ruffle_core::ViewportDimensions;
 use ruffle_core::backend::navigator::{NullExecutor, NullNavigatorBackend};
 use ruffle_core::limits::ExecutionLimit;
 use ruffle_core::swf::{decompress_swf, parse_swf};
 use ruffle_core::tag_utils::movie_from_path;
 use sha2::{Digest, Sha256};
+use std::cell::{Cell, RefCell};
 use std::io::{Write, stdout};
-use std::panic::catch_unwind;
+use std::panic::{AssertUnwindSafe, catch_unwind};
 use std::path::Path;
+use std::rc::Rc;
 use std::time::{Duration, Instant};
 
-fn execute_swf(file: &Path) {
+/// Everything that was observed while running a movie.
+///
+/// This is filled in as the movie runs, so that it survives panics.
+#[derive(Default)]
+struct ExecutionReport {
+    frames_run: u32,
+    stubs: Vec<String>,
+    uncaught_errors: Rc<RefCell<Vec<String>>>,
+    fetches: Rc<RefCell<Vec<String>>>,
+    blank_frame: Rc<Cell<Option<bool>>>,
+}
+
+fn execute_swf(file: &Path, frames: u32, report: &mut ExecutionReport) {
     let base_path = file.parent().unwrap();
-    let executor = NullExecutor::new();
+    let mut executor = NullExecutor::new();
     let movie = movie_from_path(file, None).unwrap();
     let frame_time = FloatDuration::from_millis(1000.0 / movie.frame_rate().to_f64());
+    let dimensions = ViewportDimensions {
+        width: movie.width().to_pixels() as u32,
+        height: movie.height().to_pixels() as u32,
+        scale_factor: 1.0,
+    };
+    let navigator = NullNavigatorBackend::with_base_path(base_path, &executor).unwrap();
     let player = PlayerBuilder::new()
-        .with_log(ScanLogBackend::new())
-        .with_navigator(NullNavigatorBackend::with_base_path(base_path, &executor).unwrap())
+        .with_log(ScanLogBackend::new(report.uncaught_errors.clone()))
+        .with_navigator(ScanNavigatorBackend::new(navigator, report.fetches.clone()))
+        .with_renderer(ScanRenderBackend::new(
+            dimensions,
+            report.blank_frame.clone(),
+        ))
         .with_max_execution_duration(Duration::from_secs(300))
         .with_movie(movie)
         .build();
 
     player.lock().unwrap().preload(&mut ExecutionLimit::none());
 
-    player.lock().unwrap().run_frame();
-    player.lock().unwrap().update_timers(frame_time);
-    //executor.poll_all().unwrap();
+    for _ in 0..frames {
+        {
+            let mut player = player.lock().unwrap();
+            player.run_frame();
+            player.update_timers(frame_time);
+
+            let mut stubs: Vec<_> = player.stubs().iter().map(ToString::to_string).collect();
+            stubs.sort();
+            report.stubs = stubs;
+        }
+
+        // Loads may need to lock the player.
+        executor.run();
+        report.frames_run += 1;
+    }
+
+    player.lock().unwrap().render();
 }
 
 fn checkpoint<W: Write>(
@@ -126,6 +168,14 @@ pub fn execute_report_main(execute_report_opt: ExecuteReportOpt) -> Result<(), s
                     true => AvmType::Avm2,
                     false => AvmType::Avm1,
                 });
+
+                let unsupported = UnsupportedFeatures::from_tags(&swf.tags);
+                file_result.unsupported_filters = unsupported
+                    .filters
+                    .into_iter()
+                    .map(str::to_string)
+                    .collect();
+                file_result.unsupported_codecs = unsupported.codecs.into_iter().collect();
             }
             Err(e) => {
                 file_result.error = Some(format!("Parse error: {e}"));
@@ -147,8 +197,19 @@ pub fn execute_report_main(execute_report_opt: ExecuteReportOpt) -> Result<(), s
     checkpoint(&mut file_result, &start, &mut writer)?;
     file_result.progress = Step::Execute;
 
-    //Run one frame of the movie in Ruffle.
-    if let Err(e) = catch_unwind(|| execute_swf(&file_path)) {
+    //Run the movie in Ruffle for the requested number of frames.
+    let mut report = ExecutionReport::default();
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        execute_swf(&file_path, execute_report_opt.frames, &mut report)
+    }));
+
+    file_result.frames_run = Some(report.frames_run);
+    file_result.stubs = report.stubs;
+    file_result.uncaught_errors = report.uncaught_errors.take();
+    file_result.fetches = report.fetches.take();
+    file_result.blank_frame = report.blank_frame.get();
+
+    if let Err(e) = result {
         match e.downcast::<String>() {
             Ok(e) => {
                 file_result.error = Some(format!("PANIC: {e}"));
@@ -126,6 +126,59 @@ pub struct FileResults {
     /// The AVM type of the movie.
     #[serde(rename = "AVM Version")]
     pub vm_type: Option<AvmType>,
+
+    /// The number of frames of the movie that were run.
+    #[serde(rename = "Frames Run", default)]
+    pub frames_run: Option<u32>,
+
+    /// The stubs that were encountered while running the movie.
+    #[serde(
+        rename = "Stubs",
+        default,
+        serialize_with = "into_lines",
+        deserialize_with = "from_lines"
+    )]
+    pub stubs: Vec<String>,
+
+    /// The errors thrown by ActionScript that the movie didn't catch.
+    #[serde(
+        rename = "Uncaught Errors",
+        default,
+        serialize_with = "into_lines",
+        deserialize_with = "from_lines"
+    )]
+    pub uncaught_errors: Vec<String>,
+
+    /// The filters placed on the timeline that Ruffle can't render.
+    #[serde(
+        rename = "Unsupported Filters",
+        default,
+        serialize_with = "into_lines",
+        deserialize_with = "from_lines"
+    )]
+    pub unsupported_filters: Vec<String>,
+
+    /// The audio and video codecs used by the movie that Ruffle can't decode.
+    #[serde(
+        rename = "Unsupported Codecs",
+        default,
+        serialize_with = "into_lines",
+        deserialize_with = "from_lines"
+    )]
+    pub unsupported_codecs: Vec<String>,
+
+    /// The URLs the movie tried to fetch or navigate to.
+    #[serde(
+        rename = "Fetches",
+        default,
+        serialize_with = "into_lines",
+        deserialize_with = "from_lines"
+    )]
+    pub fetches: Vec<String>,
+
+    /// Whether nothing was drawn in the last frame that was run.
+    #[serde(rename = "Blank Frame", default)]
+    pub blank_frame: Option<bool>,
 }
 
 impl Default for FileResults {
@@ -153,6 +206,13 @@ impl FileResults {
             use_gpu: None,
             use_network_sandbox: None,
             vm_type: None,
+            frames_run: None,
+            stubs: vec![],
+            uncaught_errors: vec![],
+            unsupported_filters: vec![],
+            unsupported_codecs: vec![],
+            fetches: vec![],
+            blank_frame: None,
         }
     }
 }
@@ -207,3 +267,21 @@ where
 
     d.deserialize_str(HexVisitor())
 }
+
+/// Formats a list as one entry per line
+fn into_lines<S>(list: &[String], s: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    s.serialize_str(&list.join("\n"))
+}
+
+/// Parses a list with one entry per line
+fn from_lines<'de, D>(d: D) -> Result<Vec<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let lines = String::deserialize(d)?;
+
+    Ok(lines.lines().map(str::to_string).collect())
+}
@@ -5,18 +5,30 @@ use ruffle_core::backend::log::LogBackend;
 use std::cell::RefCell;
 use std::rc::Rc;
 
-/// Log backend that specifically discards AVM trace output
-pub struct ScanLogBackend();
+/// Log backend that specifically discards AVM trace output, and records
+/// uncaught errors.
+pub struct ScanLogBackend {
+    uncaught_errors: Rc<RefCell<Vec<String>>>,
+}
 
 impl ScanLogBackend {
-    pub fn new() -> Self {
-        Self()
+    pub fn new(uncaught_errors: Rc<RefCell<Vec<String>>>) -> Self {
+        Self { uncaught_errors }
     }
 }
 
 impl LogBackend for ScanLogBackend {
     fn avm_trace(&self, _message: &str) {}
     fn avm_warning(&self, _message: &str) {}
+
+    fn avm_uncaught_error(&self, message: &str) {
+        // Stack traces would make otherwise identical errors unique.
+        let message = message.lines().next().unwrap_or_default().to_string();
+        let mut uncaught_errors = self.uncaught_errors.borrow_mut();
+        if !uncaught_errors.contains(&message) {
+            uncaught_errors.push(message);
+        }
+    }
 }
 
 thread_local! {
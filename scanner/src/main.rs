@@ -9,8 +9,11 @@ mod cli_options;
 mod execute;
 mod file_results;
 mod logging;
+mod navigator;
+mod renderer;
 mod scan;
 mod ser_bridge;
+mod unsupported;
 
 fn main() -> Result<(), std::io::Error> {
     let opt = Opt::parse();
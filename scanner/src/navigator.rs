@@ -0,0 +1,82 @@
+//! Navigator backend that records network activity
+
+use async_channel::{Receiver, Sender};
+use ruffle_core::backend::navigator::{
+    ErrorResponse, NavigationMethod, NavigatorBackend, NullNavigatorBackend, OwnedFuture, Request,
+    SuccessResponse,
+};
+use ruffle_core::indexmap::IndexMap;
+use ruffle_core::loader::Error;
+use ruffle_core::socket::{SocketAction, SocketHandle};
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+use url::{ParseError, Url};
+
+/// Navigator backend that records every URL a movie tries to fetch,
+/// navigate to or connect to, and otherwise behaves like the
+/// `NullNavigatorBackend`.
+pub struct ScanNavigatorBackend {
+    inner: NullNavigatorBackend,
+    fetches: Rc<RefCell<Vec<String>>>,
+}
+
+impl ScanNavigatorBackend {
+    pub fn new(inner: NullNavigatorBackend, fetches: Rc<RefCell<Vec<String>>>) -> Self {
+        Self { inner, fetches }
+    }
+
+    fn record(&self, fetch: String) {
+        let mut fetches = self.fetches.borrow_mut();
+        if !fetches.contains(&fetch) {
+            fetches.push(fetch);
+        }
+    }
+}
+
+impl NavigatorBackend for ScanNavigatorBackend {
+    fn navigate_to_url(
+        &self,
+        url: &str,
+        target: &str,
+        vars_method: Option<(NavigationMethod, IndexMap<String, String>)>,
+    ) {
+        self.record(format!("NAVIGATE {url}"));
+        self.inner.navigate_to_url(url, target, vars_method);
+    }
+
+    fn fetch(&self, request: Request) -> OwnedFuture<Box<dyn SuccessResponse>, ErrorResponse> {
+        let method = match request.method() {
+            NavigationMethod::Get => "GET",
+            NavigationMethod::Post => "POST",
+        };
+        self.record(format!("{method} {}", request.url()));
+        self.inner.fetch(request)
+    }
+
+    fn resolve_url(&self, url: &str) -> Result<Url, ParseError> {
+        self.inner.resolve_url(url)
+    }
+
+    fn spawn_future(&mut self, future: OwnedFuture<(), Error>) {
+        self.inner.spawn_future(future);
+    }
+
+    fn pre_process_url(&self, url: Url) -> Url {
+        self.inner.pre_process_url(url)
+    }
+
+    fn connect_socket(
+        &mut self,
+        host: String,
+        port: u16,
+        timeout: Duration,
+        handle: SocketHandle,
+        receiver: Receiver<Vec<u8>>,
+        sender: Sender<SocketAction>,
+    ) {
+        self.record(format!("SOCKET {host}:{port}"));
+        self.inner
+            .connect_socket(host, port, timeout, handle, receiver, sender);
+    }
+}
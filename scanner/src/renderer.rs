@@ -0,0 +1,156 @@
+//! Render backend that checks whether frames are blank
+
+use ruffle_render::backend::null::NullRenderer;
+use ruffle_render::backend::{
+    BitmapCacheEntry, Context3D, Context3DProfile, PixelBenderOutput, PixelBenderTarget,
+    RenderBackend, ShapeHandle, ViewportDimensions,
+};
+use ruffle_render::bitmap::{
+    Bitmap, BitmapHandle, BitmapSource, PixelRegion, RgbaBufRead, SyncHandle,
+};
+use ruffle_render::commands::{Command, CommandList};
+use ruffle_render::error::Error;
+use ruffle_render::pixel_bender::{PixelBenderShader, PixelBenderShaderHandle};
+use ruffle_render::pixel_bender_support::PixelBenderShaderArgument;
+use ruffle_render::quality::StageQuality;
+use ruffle_render::shape_utils::DistilledShape;
+use std::borrow::Cow;
+use std::cell::Cell;
+use std::num::NonZeroU32;
+use std::rc::Rc;
+use swf::Color;
+
+/// Render backend that records whether the last submitted frame drew
+/// anything, and otherwise behaves like the `NullRenderer`.
+pub struct ScanRenderBackend {
+    inner: NullRenderer,
+    blank_frame: Rc<Cell<Option<bool>>>,
+}
+
+impl ScanRenderBackend {
+    pub fn new(dimensions: ViewportDimensions, blank_frame: Rc<Cell<Option<bool>>>) -> Self {
+        Self {
+            inner: NullRenderer::new(dimensions),
+            blank_frame,
+        }
+    }
+}
+
+/// Whether a command list draws anything, ignoring the background color.
+fn draws_anything(commands: &CommandList) -> bool {
+    commands.commands.iter().any(|command| match command {
+        Command::RenderBitmap { .. }
+        | Command::RenderStage3D { .. }
+        | Command::RenderShape { .. }
+        | Command::DrawRect { .. }
+        | Command::DrawLine { .. }
+        | Command::DrawLineRect { .. } => true,
+        Command::RenderAlphaMask {
+            maskee_commands, ..
+        } => draws_anything(maskee_commands),
+        Command::Blend(commands, _) => draws_anything(commands),
+        Command::PushMask | Command::ActivateMask | Command::DeactivateMask | Command::PopMask => {
+            false
+        }
+    })
+}
+
+impl RenderBackend for ScanRenderBackend {
+    fn viewport_dimensions(&self) -> ViewportDimensions {
+        self.inner.viewport_dimensions()
+    }
+
+    fn set_viewport_dimensions(&mut self, dimensions: ViewportDimensions) {
+        self.inner.set_viewport_dimensions(dimensions);
+    }
+
+    fn register_shape(
+        &mut self,
+        shape: DistilledShape,
+        bitmap_source: &dyn BitmapSource,
+    ) -> ShapeHandle {
+        self.inner.register_shape(shape, bitmap_source)
+    }
+
+    fn render_offscreen(
+        &mut self,
+        handle: BitmapHandle,
+        commands: CommandList,
+        quality: StageQuality,
+        bounds: PixelRegion,
+    ) -> Option<Box<dyn SyncHandle>> {
+        self.inner
+            .render_offscreen(handle, commands, quality, bounds)
+    }
+
+    fn submit_frame(
+        &mut self,
+        clear: Color,
+        commands: CommandList,
+        cache_entries: Vec<BitmapCacheEntry>,
+    ) {
+        self.blank_frame.set(Some(!draws_anything(&commands)));
+        self.inner.submit_frame(clear, commands, cache_entries);
+    }
+
+    fn create_empty_texture(
+        &mut self,
+        width: NonZeroU32,
+        height: NonZeroU32,
+    ) -> Result<BitmapHandle, Error> {
+        self.inner.create_empty_texture(width, height)
+    }
+
+    fn register_bitmap(&mut self, bitmap: Bitmap<'_>) -> Result<BitmapHandle, Error> {
+        self.inner.register_bitmap(bitmap)
+    }
+
+    fn update_texture(
+        &mut self,
+        handle: &BitmapHandle,
+        bitmap: Bitmap<'_>,
+        region: PixelRegion,
+    ) -> Result<(), Error> {
+        self.inner.update_texture(handle, bitmap, region)
+    }
+
+    fn create_context3d(&mut self, profile: Context3DProfile) -> Result<Box<dyn Context3D>, Error> {
+        self.inner.create_context3d(profile)
+    }
+
+    fn debug_info(&self) -> Cow<'static, str> {
+        Cow::Borrowed("Renderer: Scan")
+    }
+
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    fn set_quality(&mut self, quality: StageQuality) {
+        self.inner.set_quality(quality);
+    }
+
+    fn compile_pixelbender_shader(
+        &mut self,
+        shader: PixelBenderShader,
+    ) -> Result<PixelBenderShaderHandle, Error> {
+        self.inner.compile_pixelbender_shader(shader)
+    }
+
+    fn run_pixelbender_shader(
+        &mut self,
+        handle: PixelBenderShaderHandle,
+        arguments: &[PixelBenderShaderArgument],
+        target: &PixelBenderTarget,
+    ) -> Result<PixelBenderOutput, Error> {
+        self.inner.run_pixelbender_shader(handle, arguments, target)
+    }
+
+    fn resolve_sync_handle(
+        &mut self,
+        handle: Box<dyn SyncHandle>,
+        with_rgba: RgbaBufRead,
+    ) -> Result<(), Error> {
+        self.inner.resolve_sync_handle(handle, with_rgba)
+    }
+}
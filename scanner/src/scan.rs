@@ -38,12 +38,22 @@ pub fn find_files(root: &Path, ignore: &[String]) -> Vec<DirEntry> {
     results
 }
 
-pub fn scan_file<P: AsRef<OsStr>>(exec_path: P, file: &DirEntry, name: &str) -> FileResults {
+pub fn scan_file<P: AsRef<OsStr>>(
+    exec_path: P,
+    file: &DirEntry,
+    name: &str,
+    frames: u32,
+) -> FileResults {
     let start = Instant::now();
     let mut file_results = FileResults::new(name);
 
     let subproc = Command::new(exec_path)
-        .args(["execute-report", &file.path().to_string_lossy()])
+        .args([
+            "execute-report",
+            "--frames",
+            &frames.to_string(),
+            &file.path().to_string_lossy(),
+        ])
         .output();
     match subproc {
         Ok(output) => {
@@ -68,6 +78,13 @@ pub fn scan_file<P: AsRef<OsStr>>(exec_path: P, file: &DirEntry, name: &str) ->
                             use_gpu,
                             use_network_sandbox,
                             vm_type,
+                            frames_run,
+                            stubs,
+                            uncaught_errors,
+                            unsupported_filters,
+                            unsupported_codecs,
+                            fetches,
+                            blank_frame,
                         } = child_results;
 
                         file_results.hash = hash;
@@ -85,6 +102,13 @@ pub fn scan_file<P: AsRef<OsStr>>(exec_path: P, file: &DirEntry, name: &str) ->
                         file_results.use_gpu = use_gpu;
                         file_results.use_network_sandbox = use_network_sandbox;
                         file_results.vm_type = vm_type;
+                        file_results.frames_run = frames_run;
+                        file_results.stubs = stubs;
+                        file_results.uncaught_errors = uncaught_errors;
+                        file_results.unsupported_filters = unsupported_filters;
+                        file_results.unsupported_codecs = unsupported_codecs;
+                        file_results.fetches = fetches;
+                        file_results.blank_frame = blank_frame;
                     }
                     Err(e) => {
                         file_results.error = Some(e.to_string());
@@ -135,7 +159,7 @@ pub fn scan_main(opt: ScanOpt) -> Result<(), std::io::Error> {
                 .strip_prefix(&opt.input_path)
                 .unwrap_or_else(|_| file.path())
                 .to_slash_lossy();
-            let result = scan_file(&binary_path, &file, &name);
+            let result = scan_file(&binary_path, &file, &name, opt.frames);
 
             progress.inc(1);
             progress.set_message(name.into_owned());
@@ -149,7 +173,7 @@ pub fn scan_main(opt: ScanOpt) -> Result<(), std::io::Error> {
             };
         });
 
-    analyze(result_iter);
+    analyze(result_iter, opt.top);
 
     Ok(())
 }
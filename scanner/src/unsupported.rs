@@ -0,0 +1,89 @@
+//! Detection of SWF features that Ruffle can't handle yet
+
+use std::collections::BTreeSet;
+use swf::{AudioCompression, Filter, SoundFormat, Tag, VideoCodec};
+
+/// Features used by a movie that Ruffle doesn't support.
+#[derive(Default)]
+pub struct UnsupportedFeatures {
+    pub filters: BTreeSet<&'static str>,
+    pub codecs: BTreeSet<String>,
+}
+
+impl UnsupportedFeatures {
+    /// Finds the unsupported filters and codecs used by the tags of a movie.
+    ///
+    /// Filters applied from ActionScript are not detected.
+    pub fn from_tags(tags: &[Tag<'_>]) -> Self {
+        let mut features = Self::default();
+        features.visit_tags(tags);
+        features
+    }
+
+    fn visit_tags(&mut self, tags: &[Tag<'_>]) {
+        for tag in tags {
+            match tag {
+                Tag::PlaceObject(place_object) => {
+                    for filter in place_object.filters.iter().flatten() {
+                        self.visit_filter(filter);
+                    }
+                }
+                Tag::DefineButton2(button) => {
+                    for record in &button.records {
+                        for filter in &record.filters {
+                            self.visit_filter(filter);
+                        }
+                    }
+                }
+                Tag::DefineSound(sound) => self.visit_sound_format(&sound.format),
+                Tag::SoundStreamHead(head) | Tag::SoundStreamHead2(head) => {
+                    self.visit_sound_format(&head.stream_format);
+                }
+                Tag::DefineVideoStream(video) => match video.codec {
+                    VideoCodec::None | VideoCodec::ScreenVideoV2 | VideoCodec::H264 => {
+                        self.codecs.insert(format!("Video: {:?}", video.codec));
+                    }
+                    VideoCodec::H263
+                    | VideoCodec::ScreenVideo
+                    | VideoCodec::Vp6
+                    | VideoCodec::Vp6WithAlpha => {}
+                },
+                Tag::DefineSprite(sprite) => self.visit_tags(&sprite.tags),
+                _ => {}
+            }
+        }
+    }
+
+    fn visit_filter(&mut self, filter: &Filter) {
+        let name = match filter {
+            Filter::ConvolutionFilter(_) => "ConvolutionFilter",
+            Filter::GradientGlowFilter(_) => "GradientGlowFilter",
+            Filter::GradientBevelFilter(_) => "GradientBevelFilter",
+            Filter::DropShadowFilter(_)
+            | Filter::BlurFilter(_)
+            | Filter::GlowFilter(_)
+            | Filter::BevelFilter(_)
+            | Filter::ColorMatrixFilter(_) => return,
+        };
+        self.filters.insert(name);
+    }
+
+    fn visit_sound_format(&mut self, format: &SoundFormat) {
+        match format.compression {
+            AudioCompression::Nellymoser16Khz
+            | AudioCompression::Nellymoser8Khz
+            | AudioCompression::Aac => {
+                self.codecs
+                    .insert(format!("Audio: {:?}", format.compression));
+            }
+            AudioCompression::UncompressedUnknownEndian
+            | AudioCompression::Adpcm
+            | AudioCompression::Mp3
+            | AudioCompression::Uncompressed
+            | AudioCompression::Nellymoser
+            | AudioCompression::Speex
+            | AudioCompression::G711ALawPCM
+            | AudioCompression::G711MuLawPCM => {}
+        }
+    }
+}
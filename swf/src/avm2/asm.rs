@@ -0,0 +1,302 @@
+//! A textual assembly form of ABC files.
+//!
+//! [`disassemble`] turns an [`AbcFile`](crate::avm2::types::AbcFile) into a
+//! listing that [`assemble`] parses back into an identical `AbcFile`, so that
+//! methods can be patched by hand or bytecode edge cases written directly.
+//!
+//! The listing is line based, and everything after a `;` is a comment.
+//! Constant pool entries, methods, metadata, instances, classes, scripts and
+//! method bodies are declared in order with their index, and refer to each
+//! other by index (`#3`):
+//!
+//! ```text
+//! abc 46.16
+//! string #1 "Test"
+//! namespace #1 package #2
+//! multiname #1 qname #1 #1
+//! method #0 return #0 name #0 flags 0x00
+//! end
+//! body #0 method #0 max_stack 1 locals 1 init_scope 0 max_scope 1
+//!   code
+//!     getlocal0
+//!     pushscope
+//!     pushtrue
+//!     iftrue L9
+//!     pushnull
+//!     pop
+//!   L9:
+//!     returnvoid
+//! end
+//! ```
+//!
+//! Branch targets and exception ranges are given as labels, or as plain
+//! offsets. Code that can't be represented exactly as instructions, like
+//! unknown opcodes or non-canonical integer encodings, is kept as `bytes`.
+
+mod assembler;
+mod disassembler;
+
+pub use assembler::assemble;
+pub use disassembler::disassemble;
+
+use crate::avm2::opcode::{OpCode, Operand};
+use crate::avm2::types::{DefaultValue, Index, Multiname, Namespace};
+
+fn namespace_kind(namespace: Namespace) -> (&'static str, Index<String>) {
+    match namespace {
+        Namespace::Namespace(name) => ("namespace", name),
+        Namespace::Package(name) => ("package", name),
+        Namespace::PackageInternal(name) => ("packageinternal", name),
+        Namespace::Protected(name) => ("protected", name),
+        Namespace::Explicit(name) => ("explicit", name),
+        Namespace::StaticProtected(name) => ("staticprotected", name),
+        Namespace::Private(name) => ("private", name),
+    }
+}
+
+fn namespace_from_kind(kind: &str, name: Index<String>) -> Option<Namespace> {
+    Some(match kind {
+        "namespace" => Namespace::Namespace(name),
+        "package" => Namespace::Package(name),
+        "packageinternal" => Namespace::PackageInternal(name),
+        "protected" => Namespace::Protected(name),
+        "explicit" => Namespace::Explicit(name),
+        "staticprotected" => Namespace::StaticProtected(name),
+        "private" => Namespace::Private(name),
+        _ => return None,
+    })
+}
+
+fn multiname_kind(multiname: &Multiname) -> &'static str {
+    match multiname {
+        Multiname::QName { .. } => "qname",
+        Multiname::QNameA { .. } => "qnamea",
+        Multiname::RTQName { .. } => "rtqname",
+        Multiname::RTQNameA { .. } => "rtqnamea",
+        Multiname::RTQNameL => "rtqnamel",
+        Multiname::RTQNameLA => "rtqnamela",
+        Multiname::Multiname { .. } => "multiname",
+        Multiname::MultinameA { .. } => "multinamea",
+        Multiname::MultinameL { .. } => "multinamel",
+        Multiname::MultinameLA { .. } => "multinamela",
+        Multiname::TypeName { .. } => "typename",
+    }
+}
+
+/// Returns the keyword of a default value and its constant pool index, if it has one.
+fn default_value_kind(value: DefaultValue) -> (&'static str, Option<u32>) {
+    match value {
+        DefaultValue::Int(i) => ("int", Some(i.0)),
+        DefaultValue::Uint(i) => ("uint", Some(i.0)),
+        DefaultValue::Double(i) => ("double", Some(i.0)),
+        DefaultValue::String(i) => ("string", Some(i.0)),
+        DefaultValue::True => ("true", None),
+        DefaultValue::False => ("false", None),
+        DefaultValue::Null => ("null", None),
+        DefaultValue::Undefined => ("undefined", None),
+        DefaultValue::Namespace(i) => ("namespace", Some(i.0)),
+        DefaultValue::Package(i) => ("package", Some(i.0)),
+        DefaultValue::PackageInternal(i) => ("packageinternal", Some(i.0)),
+        DefaultValue::Protected(i) => ("protected", Some(i.0)),
+        DefaultValue::Explicit(i) => ("explicit", Some(i.0)),
+        DefaultValue::StaticProtected(i) => ("staticprotected", Some(i.0)),
+        DefaultValue::Private(i) => ("private", Some(i.0)),
+    }
+}
+
+fn default_value_from_kind(kind: &str, index: Option<u32>) -> Option<DefaultValue> {
+    Some(match (kind, index) {
+        ("int", Some(i)) => DefaultValue::Int(Index::new(i)),
+        ("uint", Some(i)) => DefaultValue::Uint(Index::new(i)),
+        ("double", Some(i)) => DefaultValue::Double(Index::new(i)),
+        ("string", Some(i)) => DefaultValue::String(Index::new(i)),
+        ("true", None) => DefaultValue::True,
+        ("false", None) => DefaultValue::False,
+        ("null", None) => DefaultValue::Null,
+        ("undefined", None) => DefaultValue::Undefined,
+        ("namespace", Some(i)) => DefaultValue::Namespace(Index::new(i)),
+        ("package", Some(i)) => DefaultValue::Package(Index::new(i)),
+        ("packageinternal", Some(i)) => DefaultValue::PackageInternal(Index::new(i)),
+        ("protected", Some(i)) => DefaultValue::Protected(Index::new(i)),
+        ("explicit", Some(i)) => DefaultValue::Explicit(Index::new(i)),
+        ("staticprotected", Some(i)) => DefaultValue::StaticProtected(Index::new(i)),
+        ("private", Some(i)) => DefaultValue::Private(Index::new(i)),
+        _ => return None,
+    })
+}
+
+/// Encodes an instruction. Branch offsets are relative to the end of the instruction.
+///
+/// The operands must already be in range for their kinds.
+fn encode_instruction(opcode: OpCode, operands: &[i64], out: &mut Vec<u8>) {
+    out.push(opcode as u8);
+    for (kind, value) in opcode.operands().iter().zip(operands) {
+        match kind {
+            Operand::U8 => out.push(*value as u8),
+            Operand::Offset => encode_i24(*value as i32, out),
+            _ => encode_u30(*value as u32, out),
+        }
+    }
+}
+
+/// Encodes a `lookupswitch` with the default target followed by the case
+/// targets, all relative to the start of the instruction.
+fn encode_lookup_switch(targets: &[i64], out: &mut Vec<u8>) {
+    out.push(OpCode::LookupSwitch as u8);
+    encode_i24(targets[0] as i32, out);
+    encode_u30(targets.len() as u32 - 2, out);
+    for target in &targets[1..] {
+        encode_i24(*target as i32, out);
+    }
+}
+
+fn encode_u30(mut n: u32, out: &mut Vec<u8>) {
+    while n >= 0x80 {
+        out.push(n as u8 | 0x80);
+        n >>= 7;
+    }
+    out.push(n as u8);
+}
+
+fn encode_i24(n: i32, out: &mut Vec<u8>) {
+    out.extend_from_slice(&n.to_le_bytes()[..3]);
+}
+
+/// Escapes a constant pool string, which isn't necessarily valid UTF-8.
+fn escape_string(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len() + 2);
+    out.push('"');
+    for chunk in bytes.utf8_chunks() {
+        for c in chunk.valid().chars() {
+            match c {
+                '"' => out.push_str("\\\""),
+                '\\' => out.push_str("\\\\"),
+                '\n' => out.push_str("\\n"),
+                '\r' => out.push_str("\\r"),
+                '\t' => out.push_str("\\t"),
+                c if c.is_ascii_control() => out.push_str(&format!("\\x{:02x}", c as u8)),
+                c => out.push(c),
+            }
+        }
+        for byte in chunk.invalid() {
+            out.push_str(&format!("\\x{byte:02x}"));
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::read::Reader;
+    use crate::avm2::read::tests::read_abc_from_file;
+    use crate::avm2::write::Writer;
+
+    fn write_abc(abc: &crate::avm2::types::AbcFile) -> Vec<u8> {
+        let mut out = vec![];
+        Writer::new(&mut out).write(abc.clone()).unwrap();
+        out
+    }
+
+    #[test]
+    fn round_trip_test_files() {
+        for path in [
+            "tests/swfs/Avm2Dummy.swf",
+            "tests/swfs/Avm2DefaultValue.swf",
+        ] {
+            let data = read_abc_from_file(path);
+            let abc = Reader::new(&data).read().unwrap();
+
+            let listing = disassemble(&abc);
+            let assembled = assemble(&listing).unwrap_or_else(|e| panic!("{path}: {e}"));
+            // Both files are written the same way `Writer` does, so the
+            // original bytes come back out.
+            assert_eq!(write_abc(&assembled), data, "{path} did not round-trip");
+            // Disassembling again gives the same listing.
+            assert_eq!(disassemble(&assembled), listing);
+        }
+    }
+
+    #[test]
+    fn escape_strings() {
+        assert_eq!(escape_string(b"abc"), r#""abc""#);
+        assert_eq!(escape_string("a\"b\\\n☃".as_bytes()), r#""a\"b\\\n☃""#);
+        assert_eq!(escape_string(b"\x01\xff"), r#""\x01\xff""#);
+    }
+
+    #[test]
+    fn assemble_branches() {
+        let abc = assemble(
+            r#"
+            abc 46.16
+            method #0 return #0 name #0 flags 0x00
+            end
+            body #0 method #0 max_stack 1 locals 1 init_scope 0 max_scope 0
+              exception from start to after target handler type #0 name #0
+              code
+            start:
+              pushtrue
+              iftrue done
+              jump start
+              lookupswitch done start done
+            after:
+              bytes 02 02
+            handler:
+            done:
+              returnvoid
+            end
+            "#,
+        )
+        .unwrap();
+
+        let body = &abc.method_bodies[0];
+        assert_eq!(abc.methods[0].body, Some(Index::new(0)));
+        assert_eq!(
+            body.code,
+            [
+                0x26, // pushtrue
+                0x11, 17, 0, 0, // iftrue +17
+                0x10, 0xf7, 0xff, 0xff, // jump -9
+                0x1b, 13, 0, 0, 1, 0xf7, 0xff, 0xff, 13, 0, 0, // lookupswitch
+                0x02, 0x02, // bytes
+                0x47, // returnvoid
+            ]
+        );
+        assert_eq!(body.exceptions[0].from_offset, 0);
+        assert_eq!(body.exceptions[0].to_offset, 20);
+        assert_eq!(body.exceptions[0].target_offset, 22);
+    }
+
+    #[test]
+    fn non_canonical_code_is_kept() {
+        let abc = assemble(
+            "method #0 return #0 name #0 flags 0x00\nend\n\
+             body #0 method #0 max_stack 1 locals 2 init_scope 0 max_scope 0\n\
+             code\nbytes 62 81 00 ff\nend",
+        )
+        .unwrap();
+        // `getlocal` with a padded index, followed by an illegal opcode.
+        let listing = disassemble(&abc);
+        assert!(listing.contains("bytes 62 81 00"));
+        assert!(listing.contains("bytes ff"));
+        assert_eq!(
+            assemble(&listing).unwrap().method_bodies[0].code,
+            [0x62, 0x81, 0x00, 0xff]
+        );
+    }
+
+    #[test]
+    fn errors_have_line_numbers() {
+        let error = assemble("abc 46.16\nstring #2 \"skipped an index\"").unwrap_err();
+        assert_eq!(error.line, 2);
+
+        let error = assemble(
+            "method #0 return #0 name #0 flags 0x00\nend\n\
+             body #0 method #0 max_stack 1 locals 1 init_scope 0 max_scope 0\n\
+             code\njump nowhere\nend",
+        )
+        .unwrap_err();
+        assert_eq!(error.line, 5);
+    }
+}
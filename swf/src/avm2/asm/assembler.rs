@@ -0,0 +1,892 @@
+use super::{
+    default_value_from_kind, encode_instruction, encode_lookup_switch, namespace_from_kind,
+};
+use crate::avm2::opcode::{OpCode, Operand};
+use crate::avm2::types::*;
+use crate::error::AbcAssemblyError;
+use num_traits::FromPrimitive;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+type Result<T> = std::result::Result<T, AbcAssemblyError>;
+
+/// Assembles a listing produced by [`disassemble`](super::disassemble) into an
+/// ABC file.
+pub fn assemble(source: &str) -> Result<AbcFile> {
+    let mut lines = Vec::new();
+    let mut num_lines = 0;
+    for (i, text) in source.lines().enumerate() {
+        num_lines = i + 1;
+        let tokens = tokenize(text).map_err(|message| AbcAssemblyError {
+            line: i + 1,
+            message,
+        })?;
+        if !tokens.is_empty() {
+            lines.push(Tokens {
+                line: i + 1,
+                tokens,
+                pos: 0,
+            });
+        }
+    }
+
+    let mut assembler = Assembler {
+        lines: lines.into_iter(),
+        num_lines,
+        opcodes: (0..=u8::MAX)
+            .filter_map(OpCode::from_u8)
+            .map(|opcode| (opcode.mnemonic(), opcode))
+            .collect(),
+        abc: AbcFile {
+            major_version: 46,
+            minor_version: 16,
+            constant_pool: ConstantPool {
+                ints: vec![],
+                uints: vec![],
+                doubles: vec![],
+                strings: vec![],
+                namespaces: vec![],
+                namespace_sets: vec![],
+                multinames: vec![],
+            },
+            methods: vec![],
+            metadata: vec![],
+            instances: vec![],
+            classes: vec![],
+            scripts: vec![],
+            method_bodies: vec![],
+        },
+    };
+    assembler.assemble()?;
+    Ok(assembler.abc)
+}
+
+/// Splits a line into tokens, keeping quoted strings together and dropping comments.
+fn tokenize(text: &str) -> std::result::Result<Vec<&str>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = text.char_indices().peekable();
+    while let Some(&(start, c)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == ';' {
+            break;
+        } else if c == '"' {
+            chars.next();
+            let mut end = None;
+            while let Some((i, c)) = chars.next() {
+                match c {
+                    '\\' => {
+                        chars.next();
+                    }
+                    '"' => {
+                        end = Some(i + 1);
+                        break;
+                    }
+                    _ => (),
+                }
+            }
+            let end = end.ok_or_else(|| "Unterminated string".to_string())?;
+            tokens.push(&text[start..end]);
+        } else {
+            let mut end = text.len();
+            while let Some(&(i, c)) = chars.peek() {
+                if c.is_whitespace() || c == ';' {
+                    end = i;
+                    break;
+                }
+                chars.next();
+            }
+            tokens.push(&text[start..end]);
+        }
+    }
+    Ok(tokens)
+}
+
+/// Parses a quoted string token, which may contain escaped bytes.
+fn unescape(token: &str) -> Option<Vec<u8>> {
+    let inner = token.strip_prefix('"')?.strip_suffix('"')?;
+    let mut out = Vec::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            let mut buf = [0; 4];
+            out.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+            continue;
+        }
+        match chars.next()? {
+            '"' => out.push(b'"'),
+            '\\' => out.push(b'\\'),
+            'n' => out.push(b'\n'),
+            'r' => out.push(b'\r'),
+            't' => out.push(b'\t'),
+            'x' => {
+                let hex: String = chars.by_ref().take(2).collect();
+                if hex.len() != 2 {
+                    return None;
+                }
+                out.push(u8::from_str_radix(&hex, 16).ok()?);
+            }
+            _ => return None,
+        }
+    }
+    Some(out)
+}
+
+/// Parses a decimal or `0x` prefixed hexadecimal integer.
+fn parse_integer(token: &str) -> Option<i64> {
+    let (negative, digits) = match token.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, token),
+    };
+    let n = match digits.strip_prefix("0x") {
+        Some(hex) => i64::from_str_radix(hex, 16).ok()?,
+        None if digits.starts_with(|c: char| c.is_ascii_digit()) => digits.parse().ok()?,
+        None => return None,
+    };
+    Some(if negative { -n } else { n })
+}
+
+/// The tokens of a single line of the listing.
+struct Tokens<'a> {
+    line: usize,
+    tokens: Vec<&'a str>,
+    pos: usize,
+}
+
+impl<'a> Tokens<'a> {
+    fn error(&self, message: impl Into<String>) -> AbcAssemblyError {
+        AbcAssemblyError {
+            line: self.line,
+            message: message.into(),
+        }
+    }
+
+    fn peek(&self) -> Option<&'a str> {
+        self.tokens.get(self.pos).copied()
+    }
+
+    fn next(&mut self, what: &str) -> Result<&'a str> {
+        let token = self
+            .peek()
+            .ok_or_else(|| self.error(format!("Expected {what}")))?;
+        self.pos += 1;
+        Ok(token)
+    }
+
+    fn next_if_any(&mut self) -> Option<&'a str> {
+        let token = self.peek()?;
+        self.pos += 1;
+        Some(token)
+    }
+
+    fn expect(&mut self, keyword: &str) -> Result<()> {
+        match self.next(&format!("`{keyword}`"))? {
+            token if token == keyword => Ok(()),
+            token => Err(self.error(format!("Expected `{keyword}`, found `{token}`"))),
+        }
+    }
+
+    /// Parses an integer, optionally written as an index (`#3`).
+    fn integer<T: TryFrom<i64>>(&mut self, what: &str) -> Result<T> {
+        let token = self.next(what)?;
+        parse_integer(token.strip_prefix('#').unwrap_or(token))
+            .and_then(|n| T::try_from(n).ok())
+            .ok_or_else(|| self.error(format!("Invalid {what} `{token}`")))
+    }
+
+    fn index<T>(&mut self, what: &str) -> Result<Index<T>> {
+        let token = self.next(what)?;
+        token
+            .strip_prefix('#')
+            .and_then(parse_integer)
+            .and_then(|n| u32::try_from(n).ok())
+            .map(Index::new)
+            .ok_or_else(|| self.error(format!("Expected {what} index, found `{token}`")))
+    }
+
+    /// Parses the index of an entry being declared, which must be the next one.
+    fn declare(&mut self, what: &str, expected: usize) -> Result<()> {
+        let index: Index<()> = self.index(what)?;
+        if index.0 as usize != expected {
+            return Err(self.error(format!("Expected {what} #{expected}, found #{}", index.0)));
+        }
+        Ok(())
+    }
+
+    /// Parses the value of a keyword argument, like `max_stack 2`.
+    fn field<T: TryFrom<i64>>(&mut self, keyword: &str) -> Result<T> {
+        self.expect(keyword)?;
+        self.integer(keyword)
+    }
+
+    fn index_field<T>(&mut self, keyword: &str) -> Result<Index<T>> {
+        self.expect(keyword)?;
+        self.index(keyword)
+    }
+
+    fn token_field(&mut self, keyword: &str) -> Result<&'a str> {
+        self.expect(keyword)?;
+        self.next(keyword)
+    }
+
+    fn default_value(&mut self) -> Result<DefaultValue> {
+        let kind = self.next("default value")?;
+        let index = match self.peek() {
+            Some(token) if token.starts_with('#') => Some(self.index::<()>(kind)?.0),
+            _ => None,
+        };
+        default_value_from_kind(kind, index)
+            .ok_or_else(|| self.error(format!("Invalid default value `{kind}`")))
+    }
+
+    fn finish(&self) -> Result<()> {
+        match self.peek() {
+            Some(token) => Err(self.error(format!("Unexpected `{token}`"))),
+            None => Ok(()),
+        }
+    }
+}
+
+/// A branch target in code.
+#[derive(Clone, Copy)]
+enum Target<'a> {
+    Label(&'a str),
+
+    /// A raw offset. For branches, this is relative to the instruction.
+    Offset(i64),
+}
+
+impl<'a> Target<'a> {
+    fn parse(token: &'a str) -> Self {
+        match parse_integer(token) {
+            Some(offset) => Target::Offset(offset),
+            None => Target::Label(token),
+        }
+    }
+}
+
+enum CodeItem<'a> {
+    Label(&'a str),
+    Instruction {
+        opcode: OpCode,
+        operands: Vec<i64>,
+        target: Option<Target<'a>>,
+    },
+    LookupSwitch {
+        targets: Vec<Target<'a>>,
+    },
+    Bytes(Vec<u8>),
+}
+
+struct Assembler<'a> {
+    lines: std::vec::IntoIter<Tokens<'a>>,
+    num_lines: usize,
+    opcodes: HashMap<String, OpCode>,
+    abc: AbcFile,
+}
+
+impl<'a> Assembler<'a> {
+    fn next_line(&mut self) -> Result<Tokens<'a>> {
+        self.lines.next().ok_or_else(|| AbcAssemblyError {
+            line: self.num_lines,
+            message: "Unexpected end of listing".to_string(),
+        })
+    }
+
+    fn assemble(&mut self) -> Result<()> {
+        while let Some(mut tokens) = self.lines.next() {
+            let keyword = tokens.next("keyword")?;
+            let pool = &mut self.abc.constant_pool;
+            match keyword {
+                "abc" => {
+                    let version = tokens.next("version")?;
+                    let (major, minor) = version
+                        .split_once('.')
+                        .and_then(|(major, minor)| Some((major.parse().ok()?, minor.parse().ok()?)))
+                        .ok_or_else(|| tokens.error(format!("Invalid version `{version}`")))?;
+                    self.abc.major_version = major;
+                    self.abc.minor_version = minor;
+                }
+                "int" => {
+                    tokens.declare("int", pool.ints.len() + 1)?;
+                    pool.ints.push(tokens.integer("int")?);
+                }
+                "uint" => {
+                    tokens.declare("uint", pool.uints.len() + 1)?;
+                    pool.uints.push(tokens.integer("uint")?);
+                }
+                "double" => {
+                    tokens.declare("double", pool.doubles.len() + 1)?;
+                    let token = tokens.next("double")?;
+                    let value = match token.strip_prefix("0x") {
+                        Some(hex) => u64::from_str_radix(hex, 16).ok().map(f64::from_bits),
+                        None => f64::from_str(token).ok(),
+                    };
+                    pool.doubles.push(
+                        value.ok_or_else(|| tokens.error(format!("Invalid double `{token}`")))?,
+                    );
+                }
+                "string" => {
+                    tokens.declare("string", pool.strings.len() + 1)?;
+                    let token = tokens.next("string")?;
+                    pool.strings.push(
+                        unescape(token)
+                            .ok_or_else(|| tokens.error(format!("Invalid string {token}")))?,
+                    );
+                }
+                "namespace" => {
+                    tokens.declare("namespace", pool.namespaces.len() + 1)?;
+                    let kind = tokens.next("namespace kind")?;
+                    let name = tokens.index("string")?;
+                    pool.namespaces.push(
+                        namespace_from_kind(kind, name).ok_or_else(|| {
+                            tokens.error(format!("Invalid namespace kind `{kind}`"))
+                        })?,
+                    );
+                }
+                "nsset" => {
+                    tokens.declare("nsset", pool.namespace_sets.len() + 1)?;
+                    let mut set = Vec::new();
+                    while tokens.peek().is_some() {
+                        set.push(tokens.index("namespace")?);
+                    }
+                    pool.namespace_sets.push(set);
+                }
+                "multiname" => {
+                    tokens.declare("multiname", pool.multinames.len() + 1)?;
+                    let multiname = parse_multiname(&mut tokens)?;
+                    self.abc.constant_pool.multinames.push(multiname);
+                }
+                "method" => {
+                    tokens.declare("method", self.abc.methods.len())?;
+                    let method = self.parse_method(&mut tokens)?;
+                    self.abc.methods.push(method);
+                }
+                "metadata" => {
+                    tokens.declare("metadata", self.abc.metadata.len())?;
+                    let metadata = self.parse_metadata(&mut tokens)?;
+                    self.abc.metadata.push(metadata);
+                }
+                "instance" => {
+                    tokens.declare("instance", self.abc.instances.len())?;
+                    let instance = self.parse_instance(&mut tokens)?;
+                    self.abc.instances.push(instance);
+                }
+                "class" => {
+                    tokens.declare("class", self.abc.classes.len())?;
+                    let init_method = tokens.index_field("init")?;
+                    tokens.finish()?;
+                    let traits = self.parse_traits_block()?;
+                    self.abc.classes.push(Class {
+                        init_method,
+                        traits,
+                    });
+                }
+                "script" => {
+                    tokens.declare("script", self.abc.scripts.len())?;
+                    let init_method = tokens.index_field("init")?;
+                    tokens.finish()?;
+                    let traits = self.parse_traits_block()?;
+                    self.abc.scripts.push(Script {
+                        init_method,
+                        traits,
+                    });
+                }
+                "body" => {
+                    tokens.declare("body", self.abc.method_bodies.len())?;
+                    self.parse_method_body(&mut tokens)?;
+                }
+                _ => return Err(tokens.error(format!("Unknown keyword `{keyword}`"))),
+            }
+            tokens.finish()?;
+        }
+
+        if self.abc.instances.len() != self.abc.classes.len() {
+            return Err(AbcAssemblyError {
+                line: self.num_lines,
+                message: format!(
+                    "Found {} instances but {} classes",
+                    self.abc.instances.len(),
+                    self.abc.classes.len()
+                ),
+            });
+        }
+        Ok(())
+    }
+
+    fn parse_method(&mut self, tokens: &mut Tokens<'a>) -> Result<Method> {
+        let return_type = tokens.index_field("return")?;
+        let name = tokens.index_field("name")?;
+        let flags = MethodFlags::from_bits_retain(tokens.field("flags")?);
+        tokens.finish()?;
+
+        let mut params = Vec::new();
+        loop {
+            let mut tokens = self.next_line()?;
+            match tokens.next("`param` or `end`")? {
+                "param" => {
+                    let kind = tokens.index("multiname")?;
+                    let mut param = MethodParam {
+                        name: None,
+                        kind,
+                        default_value: None,
+                    };
+                    if tokens.peek() == Some("name") {
+                        param.name = Some(tokens.index_field("name")?);
+                    }
+                    if tokens.peek() == Some("default") {
+                        tokens.next("default")?;
+                        param.default_value = Some(tokens.default_value()?);
+                    }
+                    params.push(param);
+                }
+                "end" => break,
+                token => return Err(tokens.error(format!("Unexpected `{token}`"))),
+            }
+            tokens.finish()?;
+        }
+
+        Ok(Method {
+            name,
+            params,
+            return_type,
+            flags,
+            body: None,
+        })
+    }
+
+    fn parse_metadata(&mut self, tokens: &mut Tokens<'a>) -> Result<Metadata> {
+        let name = tokens.index_field("name")?;
+        tokens.finish()?;
+
+        let mut items = Vec::new();
+        loop {
+            let mut tokens = self.next_line()?;
+            match tokens.next("`item` or `end`")? {
+                "item" => items.push(MetadataItem {
+                    key: tokens.index("string")?,
+                    value: tokens.index("string")?,
+                }),
+                "end" => break,
+                token => return Err(tokens.error(format!("Unexpected `{token}`"))),
+            }
+            tokens.finish()?;
+        }
+
+        Ok(Metadata { name, items })
+    }
+
+    fn parse_instance(&mut self, tokens: &mut Tokens<'a>) -> Result<Instance> {
+        let mut instance = Instance {
+            name: tokens.index_field("name")?,
+            super_name: tokens.index_field("super")?,
+            is_sealed: false,
+            is_final: false,
+            is_interface: false,
+            protected_namespace: None,
+            interfaces: Vec::new(),
+            init_method: tokens.index_field("init")?,
+            traits: Vec::new(),
+        };
+        while let Some(token) = tokens.peek() {
+            match token {
+                "sealed" => instance.is_sealed = true,
+                "final" => instance.is_final = true,
+                "interface" => instance.is_interface = true,
+                "protected_ns" => {
+                    instance.protected_namespace = Some(tokens.index_field("protected_ns")?);
+                    continue;
+                }
+                _ => return Err(tokens.error(format!("Unexpected `{token}`"))),
+            }
+            tokens.pos += 1;
+        }
+
+        loop {
+            let mut tokens = self.next_line()?;
+            match tokens.next("`interface`, `trait` or `end`")? {
+                "interface" => instance.interfaces.push(tokens.index("multiname")?),
+                "trait" => instance.traits.push(parse_trait(&mut tokens)?),
+                "end" => break,
+                token => return Err(tokens.error(format!("Unexpected `{token}`"))),
+            }
+            tokens.finish()?;
+        }
+
+        Ok(instance)
+    }
+
+    fn parse_traits_block(&mut self) -> Result<Vec<Trait>> {
+        let mut traits = Vec::new();
+        loop {
+            let mut tokens = self.next_line()?;
+            match tokens.next("`trait` or `end`")? {
+                "trait" => traits.push(parse_trait(&mut tokens)?),
+                "end" => break,
+                token => return Err(tokens.error(format!("Unexpected `{token}`"))),
+            }
+            tokens.finish()?;
+        }
+        Ok(traits)
+    }
+
+    fn parse_method_body(&mut self, header: &mut Tokens<'a>) -> Result<()> {
+        let method: Index<Method> = header.index_field("method")?;
+        let mut body = MethodBody {
+            method,
+            max_stack: header.field("max_stack")?,
+            num_locals: header.field("locals")?,
+            init_scope_depth: header.field("init_scope")?,
+            max_scope_depth: header.field("max_scope")?,
+            code: Vec::new(),
+            exceptions: Vec::new(),
+            traits: Vec::new(),
+        };
+        header.finish()?;
+
+        // Exceptions can refer to labels in the code that follows them.
+        let mut exceptions = Vec::new();
+        loop {
+            let mut tokens = self.next_line()?;
+            match tokens.next("`trait`, `exception` or `code`")? {
+                "trait" => body.traits.push(parse_trait(&mut tokens)?),
+                "exception" => {
+                    let from = Target::parse(tokens.token_field("from")?);
+                    let to = Target::parse(tokens.token_field("to")?);
+                    let target = Target::parse(tokens.token_field("target")?);
+                    let type_name = tokens.index_field("type")?;
+                    let variable_name = tokens.index_field("name")?;
+                    exceptions.push((tokens.line, [from, to, target], type_name, variable_name));
+                }
+                "code" => {
+                    tokens.finish()?;
+                    break;
+                }
+                token => return Err(tokens.error(format!("Unexpected `{token}`"))),
+            }
+            tokens.finish()?;
+        }
+
+        let mut items = Vec::new();
+        loop {
+            let mut tokens = self.next_line()?;
+            if tokens.peek() == Some("end") {
+                tokens.next("end")?;
+                tokens.finish()?;
+                break;
+            }
+            let item = self.parse_code_item(&mut tokens)?;
+            tokens.finish()?;
+            items.push((tokens.line, item));
+        }
+        let labels = assemble_code(&items, &mut body.code)?;
+
+        for (line, offsets, type_name, variable_name) in exceptions {
+            let mut resolved = [0; 3];
+            for (offset, target) in resolved.iter_mut().zip(offsets) {
+                let error = |message: String| AbcAssemblyError { line, message };
+                *offset = match target {
+                    Target::Label(label) => *labels
+                        .get(label)
+                        .ok_or_else(|| error(format!("Unknown label `{label}`")))?
+                        as u32,
+                    Target::Offset(offset) => u32::try_from(offset)
+                        .map_err(|_| error(format!("Invalid offset {offset}")))?,
+                };
+            }
+            body.exceptions.push(Exception {
+                from_offset: resolved[0],
+                to_offset: resolved[1],
+                target_offset: resolved[2],
+                variable_name,
+                type_name,
+            });
+        }
+
+        // Bind the body to its method, like the reader does.
+        let body_index = Index::new(self.abc.method_bodies.len() as u32);
+        let method = self.abc.methods.get_mut(method.0 as usize).ok_or_else(|| {
+            header.error(format!(
+                "Method #{} is not declared before its body",
+                method.0
+            ))
+        })?;
+        if method.body.is_some() {
+            return Err(header.error(format!(
+                "Duplicate method body for method #{}",
+                body.method.0
+            )));
+        }
+        method.body = Some(body_index);
+        self.abc.method_bodies.push(body);
+        Ok(())
+    }
+
+    fn parse_code_item(&self, tokens: &mut Tokens<'a>) -> Result<CodeItem<'a>> {
+        let token = tokens.next("instruction")?;
+        if let Some(label) = token.strip_suffix(':') {
+            if label.is_empty() || parse_integer(label).is_some() || label.starts_with('-') {
+                return Err(tokens.error(format!("Invalid label `{label}`")));
+            }
+            return Ok(CodeItem::Label(label));
+        }
+
+        if token == "bytes" {
+            let mut bytes = Vec::new();
+            while let Some(token) = tokens.peek() {
+                let byte = u8::from_str_radix(token, 16)
+                    .map_err(|_| tokens.error(format!("Invalid byte `{token}`")))?;
+                bytes.push(byte);
+                tokens.pos += 1;
+            }
+            return Ok(CodeItem::Bytes(bytes));
+        }
+
+        let opcode = *self
+            .opcodes
+            .get(token)
+            .ok_or_else(|| tokens.error(format!("Unknown instruction `{token}`")))?;
+        if opcode == OpCode::LookupSwitch {
+            let mut targets = Vec::new();
+            while let Some(token) = tokens.peek() {
+                targets.push(Target::parse(token));
+                tokens.pos += 1;
+            }
+            if targets.len() < 2 {
+                return Err(tokens.error("`lookupswitch` needs a default and at least one case"));
+            }
+            return Ok(CodeItem::LookupSwitch { targets });
+        }
+
+        let mut operands = Vec::new();
+        let mut target = None;
+        for kind in opcode.operands() {
+            let value = match kind {
+                Operand::U8 => tokens.integer::<u8>("byte")?.into(),
+                Operand::Offset => {
+                    target = Some(Target::parse(tokens.next("branch target")?));
+                    0
+                }
+                _ => tokens.integer::<u32>("operand")?.into(),
+            };
+            operands.push(value);
+        }
+        Ok(CodeItem::Instruction {
+            opcode,
+            operands,
+            target,
+        })
+    }
+}
+
+fn parse_multiname(tokens: &mut Tokens<'_>) -> Result<Multiname> {
+    let kind = tokens.next("multiname kind")?;
+    Ok(match kind {
+        "qname" => Multiname::QName {
+            namespace: tokens.index("namespace")?,
+            name: tokens.index("string")?,
+        },
+        "qnamea" => Multiname::QNameA {
+            namespace: tokens.index("namespace")?,
+            name: tokens.index("string")?,
+        },
+        "rtqname" => Multiname::RTQName {
+            name: tokens.index("string")?,
+        },
+        "rtqnamea" => Multiname::RTQNameA {
+            name: tokens.index("string")?,
+        },
+        "rtqnamel" => Multiname::RTQNameL,
+        "rtqnamela" => Multiname::RTQNameLA,
+        "multiname" => Multiname::Multiname {
+            name: tokens.index("string")?,
+            namespace_set: tokens.index("nsset")?,
+        },
+        "multinamea" => Multiname::MultinameA {
+            name: tokens.index("string")?,
+            namespace_set: tokens.index("nsset")?,
+        },
+        "multinamel" => Multiname::MultinameL {
+            namespace_set: tokens.index("nsset")?,
+        },
+        "multinamela" => Multiname::MultinameLA {
+            namespace_set: tokens.index("nsset")?,
+        },
+        "typename" => {
+            let base_type = tokens.index("multiname")?;
+            let mut parameters = Vec::new();
+            while tokens.peek().is_some() {
+                parameters.push(tokens.index("multiname")?);
+            }
+            Multiname::TypeName {
+                base_type,
+                parameters,
+            }
+        }
+        _ => return Err(tokens.error(format!("Invalid multiname kind `{kind}`"))),
+    })
+}
+
+fn parse_trait(tokens: &mut Tokens<'_>) -> Result<Trait> {
+    let kind = tokens.next("trait kind")?;
+    let name = tokens.index("multiname")?;
+    let kind = match kind {
+        "slot" | "const" => {
+            let slot_id = tokens.field("id")?;
+            let type_name = tokens.index_field("type")?;
+            let value = if tokens.peek() == Some("value") {
+                tokens.next("value")?;
+                Some(tokens.default_value()?)
+            } else {
+                None
+            };
+            if kind == "slot" {
+                TraitKind::Slot {
+                    slot_id,
+                    type_name,
+                    value,
+                }
+            } else {
+                TraitKind::Const {
+                    slot_id,
+                    type_name,
+                    value,
+                }
+            }
+        }
+        "method" => TraitKind::Method {
+            disp_id: tokens.field("disp")?,
+            method: tokens.index_field("method")?,
+        },
+        "getter" => TraitKind::Getter {
+            disp_id: tokens.field("disp")?,
+            method: tokens.index_field("method")?,
+        },
+        "setter" => TraitKind::Setter {
+            disp_id: tokens.field("disp")?,
+            method: tokens.index_field("method")?,
+        },
+        "class" => TraitKind::Class {
+            slot_id: tokens.field("id")?,
+            class: tokens.index_field("class")?,
+        },
+        "function" => TraitKind::Function {
+            slot_id: tokens.field("id")?,
+            function: tokens.index_field("method")?,
+        },
+        _ => return Err(tokens.error(format!("Invalid trait kind `{kind}`"))),
+    };
+
+    let mut t = Trait {
+        name,
+        kind,
+        metadata: Vec::new(),
+        is_final: false,
+        is_override: false,
+    };
+    while let Some(token) = tokens.next_if_any() {
+        match token {
+            "final" => t.is_final = true,
+            "override" => t.is_override = true,
+            "metadata" => {
+                while tokens.peek().is_some_and(|token| token.starts_with('#')) {
+                    t.metadata.push(tokens.index("metadata")?);
+                }
+            }
+            _ => return Err(tokens.error(format!("Unexpected `{token}`"))),
+        }
+    }
+    Ok(t)
+}
+
+/// Assembles method body code, returning the offsets of its labels.
+fn assemble_code<'a>(
+    items: &[(usize, CodeItem<'a>)],
+    code: &mut Vec<u8>,
+) -> Result<HashMap<&'a str, usize>> {
+    // Branch offsets are always 24 bits, so the layout is known before the
+    // labels are resolved.
+    let mut labels = HashMap::new();
+    let mut offset = 0;
+    for (line, item) in items {
+        offset += match item {
+            CodeItem::Label(label) => {
+                if labels.insert(*label, offset).is_some() {
+                    return Err(AbcAssemblyError {
+                        line: *line,
+                        message: format!("Duplicate label `{label}`"),
+                    });
+                }
+                0
+            }
+            CodeItem::Instruction {
+                opcode, operands, ..
+            } => {
+                let mut encoded = Vec::new();
+                encode_instruction(*opcode, operands, &mut encoded);
+                encoded.len()
+            }
+            CodeItem::LookupSwitch { targets } => {
+                let mut encoded = Vec::new();
+                encode_lookup_switch(&vec![0; targets.len()], &mut encoded);
+                encoded.len()
+            }
+            CodeItem::Bytes(bytes) => bytes.len(),
+        };
+    }
+
+    for (line, item) in items {
+        let resolve = |target: Target<'_>, base: usize| {
+            let error = |message: String| AbcAssemblyError {
+                line: *line,
+                message,
+            };
+            let relative = match target {
+                Target::Label(label) => {
+                    let offset = labels
+                        .get(label)
+                        .ok_or_else(|| error(format!("Unknown label `{label}`")))?;
+                    *offset as i64 - base as i64
+                }
+                Target::Offset(offset) => offset,
+            };
+            if !(-0x80_0000..0x80_0000).contains(&relative) {
+                return Err(error(format!("Branch offset {relative} is out of range")));
+            }
+            Ok(relative)
+        };
+
+        match item {
+            CodeItem::Label(_) => (),
+            CodeItem::Instruction {
+                opcode,
+                operands,
+                target,
+            } => {
+                let mut operands = operands.clone();
+                if let Some(target) = target {
+                    let mut encoded = Vec::new();
+                    encode_instruction(*opcode, &operands, &mut encoded);
+                    let relative = resolve(*target, code.len() + encoded.len())?;
+                    for (kind, operand) in opcode.operands().iter().zip(&mut operands) {
+                        if *kind == Operand::Offset {
+                            *operand = relative;
+                        }
+                    }
+                }
+                encode_instruction(*opcode, &operands, code);
+            }
+            CodeItem::LookupSwitch { targets } => {
+                let base = code.len();
+                let targets = targets
+                    .iter()
+                    .map(|target| resolve(*target, base))
+                    .collect::<Result<Vec<_>>>()?;
+                encode_lookup_switch(&targets, code);
+            }
+            CodeItem::Bytes(bytes) => code.extend_from_slice(bytes),
+        }
+    }
+
+    Ok(labels)
+}
@@ -0,0 +1,608 @@
+use super::{
+    default_value_kind, encode_instruction, encode_lookup_switch, escape_string, multiname_kind,
+    namespace_kind,
+};
+use crate::avm2::opcode::{OpCode, Operand};
+use crate::avm2::types::*;
+use num_traits::FromPrimitive;
+use std::collections::BTreeSet;
+use std::fmt::{Result, Write};
+
+/// Disassembles an ABC file into an assembly listing that can be read back by
+/// [`assemble`](super::assemble).
+pub fn disassemble(abc: &AbcFile) -> String {
+    let mut disassembler = Disassembler {
+        abc,
+        out: String::new(),
+    };
+    disassembler
+        .write_abc()
+        .expect("Writing to a String can't fail");
+    disassembler.out
+}
+
+struct Disassembler<'a> {
+    abc: &'a AbcFile,
+    out: String,
+}
+
+/// A piece of method body code.
+struct Item {
+    offset: usize,
+    len: usize,
+    kind: ItemKind,
+}
+
+enum ItemKind {
+    Instruction {
+        opcode: OpCode,
+        operands: Vec<i64>,
+    },
+
+    /// The targets of a `lookupswitch`, relative to the start of the instruction.
+    LookupSwitch {
+        targets: Vec<i64>,
+    },
+
+    /// Code that can't be represented exactly as an instruction.
+    Bytes,
+}
+
+impl Disassembler<'_> {
+    fn write_abc(&mut self) -> Result {
+        let abc = self.abc;
+        writeln!(self.out, "abc {}.{}", abc.major_version, abc.minor_version)?;
+
+        let pool = &abc.constant_pool;
+        if !pool.ints.is_empty() || !pool.uints.is_empty() || !pool.doubles.is_empty() {
+            writeln!(self.out)?;
+        }
+        for (i, n) in pool.ints.iter().enumerate() {
+            writeln!(self.out, "int #{} {n}", i + 1)?;
+        }
+        for (i, n) in pool.uints.iter().enumerate() {
+            writeln!(self.out, "uint #{} {n}", i + 1)?;
+        }
+        for (i, n) in pool.doubles.iter().enumerate() {
+            // Print the bits so that every NaN survives the round trip.
+            writeln!(self.out, "double #{} {:#018x} ; {n}", i + 1, n.to_bits())?;
+        }
+
+        if !pool.strings.is_empty() {
+            writeln!(self.out)?;
+        }
+        for (i, s) in pool.strings.iter().enumerate() {
+            writeln!(self.out, "string #{} {}", i + 1, escape_string(s))?;
+        }
+
+        if !pool.namespaces.is_empty() || !pool.namespace_sets.is_empty() {
+            writeln!(self.out)?;
+        }
+        for (i, namespace) in pool.namespaces.iter().enumerate() {
+            let (kind, name) = namespace_kind(*namespace);
+            writeln!(self.out, "namespace #{} {kind} #{}", i + 1, name.0)?;
+        }
+        for (i, set) in pool.namespace_sets.iter().enumerate() {
+            write!(self.out, "nsset #{}", i + 1)?;
+            for namespace in set {
+                write!(self.out, " #{}", namespace.0)?;
+            }
+            writeln!(self.out)?;
+        }
+
+        if !pool.multinames.is_empty() {
+            writeln!(self.out)?;
+        }
+        for (i, multiname) in pool.multinames.iter().enumerate() {
+            self.write_multiname(i + 1, multiname)?;
+        }
+
+        for (i, method) in abc.methods.iter().enumerate() {
+            self.write_method(i, method)?;
+        }
+
+        for (i, metadata) in abc.metadata.iter().enumerate() {
+            writeln!(self.out, "\nmetadata #{i} name #{}", metadata.name.0)?;
+            for item in &metadata.items {
+                writeln!(self.out, "  item #{} #{}", item.key.0, item.value.0)?;
+            }
+            writeln!(self.out, "end")?;
+        }
+
+        for (i, instance) in abc.instances.iter().enumerate() {
+            self.write_instance(i, instance)?;
+        }
+
+        for (i, class) in abc.classes.iter().enumerate() {
+            writeln!(self.out, "\nclass #{i} init #{}", class.init_method.0)?;
+            self.write_traits(&class.traits)?;
+            writeln!(self.out, "end")?;
+        }
+
+        for (i, script) in abc.scripts.iter().enumerate() {
+            writeln!(self.out, "\nscript #{i} init #{}", script.init_method.0)?;
+            self.write_traits(&script.traits)?;
+            writeln!(self.out, "end")?;
+        }
+
+        for (i, body) in abc.method_bodies.iter().enumerate() {
+            self.write_method_body(i, body)?;
+        }
+
+        Ok(())
+    }
+
+    fn write_multiname(&mut self, index: usize, multiname: &Multiname) -> Result {
+        write!(self.out, "multiname #{index} {}", multiname_kind(multiname))?;
+        match multiname {
+            Multiname::QName { namespace, name } | Multiname::QNameA { namespace, name } => {
+                write!(self.out, " #{} #{}", namespace.0, name.0)?;
+            }
+            Multiname::RTQName { name } | Multiname::RTQNameA { name } => {
+                write!(self.out, " #{}", name.0)?;
+            }
+            Multiname::RTQNameL | Multiname::RTQNameLA => (),
+            Multiname::Multiname {
+                namespace_set,
+                name,
+            }
+            | Multiname::MultinameA {
+                namespace_set,
+                name,
+            } => {
+                write!(self.out, " #{} #{}", name.0, namespace_set.0)?;
+            }
+            Multiname::MultinameL { namespace_set } | Multiname::MultinameLA { namespace_set } => {
+                write!(self.out, " #{}", namespace_set.0)?;
+            }
+            Multiname::TypeName {
+                base_type,
+                parameters,
+            } => {
+                write!(self.out, " #{}", base_type.0)?;
+                for parameter in parameters {
+                    write!(self.out, " #{}", parameter.0)?;
+                }
+            }
+        }
+        let name = self.multiname_name(Index::new(index as u32), 0);
+        writeln!(self.out, " ; {name}")
+    }
+
+    fn write_method(&mut self, index: usize, method: &Method) -> Result {
+        writeln!(
+            self.out,
+            "\nmethod #{index} return #{} name #{} flags {:#04x}",
+            method.return_type.0,
+            method.name.0,
+            method.flags.bits()
+        )?;
+        for param in &method.params {
+            write!(self.out, "  param #{}", param.kind.0)?;
+            if let Some(name) = param.name {
+                write!(self.out, " name #{}", name.0)?;
+            }
+            if let Some(value) = param.default_value {
+                write!(self.out, " default {}", format_default_value(value))?;
+            }
+            writeln!(self.out)?;
+        }
+        writeln!(self.out, "end")
+    }
+
+    fn write_instance(&mut self, index: usize, instance: &Instance) -> Result {
+        write!(
+            self.out,
+            "\ninstance #{index} name #{} super #{} init #{}",
+            instance.name.0, instance.super_name.0, instance.init_method.0
+        )?;
+        if instance.is_sealed {
+            write!(self.out, " sealed")?;
+        }
+        if instance.is_final {
+            write!(self.out, " final")?;
+        }
+        if instance.is_interface {
+            write!(self.out, " interface")?;
+        }
+        if let Some(namespace) = instance.protected_namespace {
+            write!(self.out, " protected_ns #{}", namespace.0)?;
+        }
+        let name = self.multiname_name(instance.name, 0);
+        writeln!(self.out, " ; {name}")?;
+
+        for interface in &instance.interfaces {
+            writeln!(self.out, "  interface #{}", interface.0)?;
+        }
+        self.write_traits(&instance.traits)?;
+        writeln!(self.out, "end")
+    }
+
+    fn write_traits(&mut self, traits: &[Trait]) -> Result {
+        for t in traits {
+            let name = t.name.0;
+            match &t.kind {
+                TraitKind::Slot {
+                    slot_id,
+                    type_name,
+                    value,
+                } => self.write_slot_trait("slot", name, *slot_id, *type_name, *value)?,
+                TraitKind::Const {
+                    slot_id,
+                    type_name,
+                    value,
+                } => self.write_slot_trait("const", name, *slot_id, *type_name, *value)?,
+                TraitKind::Method { disp_id, method } => write!(
+                    self.out,
+                    "  trait method #{name} disp {disp_id} method #{}",
+                    method.0
+                )?,
+                TraitKind::Getter { disp_id, method } => write!(
+                    self.out,
+                    "  trait getter #{name} disp {disp_id} method #{}",
+                    method.0
+                )?,
+                TraitKind::Setter { disp_id, method } => write!(
+                    self.out,
+                    "  trait setter #{name} disp {disp_id} method #{}",
+                    method.0
+                )?,
+                TraitKind::Class { slot_id, class } => write!(
+                    self.out,
+                    "  trait class #{name} id {slot_id} class #{}",
+                    class.0
+                )?,
+                TraitKind::Function { slot_id, function } => write!(
+                    self.out,
+                    "  trait function #{name} id {slot_id} method #{}",
+                    function.0
+                )?,
+            }
+            if t.is_final {
+                write!(self.out, " final")?;
+            }
+            if t.is_override {
+                write!(self.out, " override")?;
+            }
+            if !t.metadata.is_empty() {
+                write!(self.out, " metadata")?;
+                for metadata in &t.metadata {
+                    write!(self.out, " #{}", metadata.0)?;
+                }
+            }
+            let name = self.multiname_name(t.name, 0);
+            writeln!(self.out, " ; {name}")?;
+        }
+        Ok(())
+    }
+
+    fn write_slot_trait(
+        &mut self,
+        kind: &str,
+        name: u32,
+        slot_id: u32,
+        type_name: Index<Multiname>,
+        value: Option<DefaultValue>,
+    ) -> Result {
+        write!(
+            self.out,
+            "  trait {kind} #{name} id {slot_id} type #{}",
+            type_name.0
+        )?;
+        if let Some(value) = value {
+            write!(self.out, " value {}", format_default_value(value))?;
+        }
+        Ok(())
+    }
+
+    fn write_method_body(&mut self, index: usize, body: &MethodBody) -> Result {
+        writeln!(
+            self.out,
+            "\nbody #{index} method #{} max_stack {} locals {} init_scope {} max_scope {}",
+            body.method.0,
+            body.max_stack,
+            body.num_locals,
+            body.init_scope_depth,
+            body.max_scope_depth
+        )?;
+        self.write_traits(&body.traits)?;
+
+        let code = &body.code;
+        let items = decode(code);
+
+        // Only offsets between items can be labelled, anything else is
+        // printed as a number.
+        let boundaries: BTreeSet<i64> = items
+            .iter()
+            .map(|item| item.offset as i64)
+            .chain([code.len() as i64])
+            .collect();
+        let mut labels = BTreeSet::new();
+        for exception in &body.exceptions {
+            labels.extend([
+                i64::from(exception.from_offset),
+                i64::from(exception.to_offset),
+                i64::from(exception.target_offset),
+            ]);
+        }
+        for item in &items {
+            match &item.kind {
+                ItemKind::Instruction { opcode, operands } if *opcode != OpCode::LookupSwitch => {
+                    let end = (item.offset + item.len) as i64;
+                    for (kind, value) in opcode.operands().iter().zip(operands) {
+                        if *kind == Operand::Offset {
+                            labels.insert(end + value);
+                        }
+                    }
+                }
+                ItemKind::LookupSwitch { targets } => {
+                    labels.extend(targets.iter().map(|target| item.offset as i64 + target));
+                }
+                _ => (),
+            }
+        }
+        labels.retain(|offset| boundaries.contains(offset));
+
+        let format_offset = |offset: u32| {
+            if labels.contains(&i64::from(offset)) {
+                format!("L{offset}")
+            } else {
+                offset.to_string()
+            }
+        };
+        for exception in &body.exceptions {
+            writeln!(
+                self.out,
+                "  exception from {} to {} target {} type #{} name #{}",
+                format_offset(exception.from_offset),
+                format_offset(exception.to_offset),
+                format_offset(exception.target_offset),
+                exception.type_name.0,
+                exception.variable_name.0
+            )?;
+        }
+
+        // Branch offsets are printed as labels where possible, and otherwise
+        // as the raw relative offset.
+        let format_branch = |base: usize, relative: i64| {
+            let target = base as i64 + relative;
+            if labels.contains(&target) {
+                format!("L{target}")
+            } else {
+                relative.to_string()
+            }
+        };
+
+        writeln!(self.out, "  code")?;
+        for item in &items {
+            if labels.contains(&(item.offset as i64)) {
+                writeln!(self.out, "  L{}:", item.offset)?;
+            }
+            match &item.kind {
+                ItemKind::Instruction { opcode, operands } => {
+                    write!(self.out, "    {}", opcode.mnemonic())?;
+                    let mut comments = Vec::new();
+                    for (kind, value) in opcode.operands().iter().zip(operands) {
+                        match kind {
+                            Operand::Offset => {
+                                let target = format_branch(item.offset + item.len, *value);
+                                write!(self.out, " {target}")?;
+                            }
+                            Operand::U8 | Operand::U30 => write!(self.out, " {value}")?,
+                            _ => {
+                                write!(self.out, " #{value}")?;
+                                comments.extend(self.describe(*kind, *value as u32));
+                            }
+                        }
+                    }
+                    if !comments.is_empty() {
+                        write!(self.out, " ; {}", comments.join(", "))?;
+                    }
+                }
+                ItemKind::LookupSwitch { targets } => {
+                    write!(self.out, "    lookupswitch")?;
+                    for target in targets {
+                        write!(self.out, " {}", format_branch(item.offset, *target))?;
+                    }
+                }
+                ItemKind::Bytes => {
+                    write!(self.out, "    bytes")?;
+                    for byte in &code[item.offset..item.offset + item.len] {
+                        write!(self.out, " {byte:02x}")?;
+                    }
+                }
+            }
+            writeln!(self.out)?;
+        }
+        if labels.contains(&(code.len() as i64)) {
+            writeln!(self.out, "  L{}:", code.len())?;
+        }
+        writeln!(self.out, "end")
+    }
+
+    /// Describes the constant pool entry that an operand refers to.
+    fn describe(&self, kind: Operand, index: u32) -> Option<String> {
+        let pool = &self.abc.constant_pool;
+        let i = (index as usize).checked_sub(1)?;
+        match kind {
+            Operand::Int => pool.ints.get(i).map(|n| n.to_string()),
+            Operand::Uint => pool.uints.get(i).map(|n| n.to_string()),
+            Operand::Double => pool.doubles.get(i).map(|n| n.to_string()),
+            Operand::String => pool.strings.get(i).map(|s| escape_string(s)),
+            Operand::Multiname => Some(self.multiname_name(Index::new(index), 0)),
+            _ => None,
+        }
+    }
+
+    fn string(&self, index: Index<String>) -> String {
+        match index.0.checked_sub(1) {
+            Some(i) => self.abc.constant_pool.strings.get(i as usize).map_or_else(
+                || "?".to_string(),
+                |s| String::from_utf8_lossy(s).into_owned(),
+            ),
+            None => "*".to_string(),
+        }
+    }
+
+    /// A readable name of a multiname, for comments.
+    fn multiname_name(&self, index: Index<Multiname>, depth: usize) -> String {
+        let pool = &self.abc.constant_pool;
+        let Some(multiname) = index
+            .0
+            .checked_sub(1)
+            .and_then(|i| pool.multinames.get(i as usize))
+        else {
+            return "*".to_string();
+        };
+
+        match multiname {
+            Multiname::QName { namespace, name } | Multiname::QNameA { namespace, name } => {
+                let namespace = namespace
+                    .0
+                    .checked_sub(1)
+                    .and_then(|i| pool.namespaces.get(i as usize))
+                    .map(|namespace| self.string(namespace_kind(*namespace).1))
+                    .unwrap_or_default();
+                if namespace.is_empty() || namespace == "*" {
+                    self.string(*name)
+                } else {
+                    format!("{namespace}::{}", self.string(*name))
+                }
+            }
+            Multiname::RTQName { name }
+            | Multiname::RTQNameA { name }
+            | Multiname::Multiname { name, .. }
+            | Multiname::MultinameA { name, .. } => self.string(*name),
+            Multiname::RTQNameL
+            | Multiname::RTQNameLA
+            | Multiname::MultinameL { .. }
+            | Multiname::MultinameLA { .. } => "<runtime name>".to_string(),
+            // Malformed files can contain type names that refer to themselves.
+            Multiname::TypeName { .. } if depth > 8 => "?".to_string(),
+            Multiname::TypeName {
+                base_type,
+                parameters,
+            } => {
+                let parameters: Vec<_> = parameters
+                    .iter()
+                    .map(|p| self.multiname_name(*p, depth + 1))
+                    .collect();
+                format!(
+                    "{}.<{}>",
+                    self.multiname_name(*base_type, depth + 1),
+                    parameters.join(", ")
+                )
+            }
+        }
+    }
+}
+
+fn format_default_value(value: DefaultValue) -> String {
+    match default_value_kind(value) {
+        (kind, Some(index)) => format!("{kind} #{index}"),
+        (kind, None) => kind.to_string(),
+    }
+}
+
+/// Splits method body code into instructions.
+fn decode(code: &[u8]) -> Vec<Item> {
+    let mut items = Vec::new();
+    let mut offset = 0;
+    while offset < code.len() {
+        let item = decode_item(code, offset);
+        offset += item.len;
+        items.push(item);
+    }
+    items
+}
+
+fn decode_item(code: &[u8], offset: usize) -> Item {
+    let Some(opcode) = OpCode::from_u8(code[offset]) else {
+        return Item {
+            offset,
+            len: 1,
+            kind: ItemKind::Bytes,
+        };
+    };
+
+    let mut pos = offset + 1;
+    let mut encoded = Vec::new();
+    let kind = if opcode == OpCode::LookupSwitch {
+        read_lookup_switch(code, &mut pos).map(|targets| {
+            encode_lookup_switch(&targets, &mut encoded);
+            ItemKind::LookupSwitch { targets }
+        })
+    } else {
+        opcode
+            .operands()
+            .iter()
+            .map(|kind| read_operand(code, &mut pos, *kind))
+            .collect::<Option<Vec<_>>>()
+            .map(|operands| {
+                encode_instruction(opcode, &operands, &mut encoded);
+                ItemKind::Instruction { opcode, operands }
+            })
+    };
+
+    match kind {
+        // Only keep instructions that assemble to the exact same bytes.
+        Some(kind) if encoded == code[offset..pos] => Item {
+            offset,
+            len: pos - offset,
+            kind,
+        },
+        Some(_) => Item {
+            offset,
+            len: pos - offset,
+            kind: ItemKind::Bytes,
+        },
+        // The instruction was truncated.
+        None => Item {
+            offset,
+            len: code.len() - offset,
+            kind: ItemKind::Bytes,
+        },
+    }
+}
+
+fn read_operand(code: &[u8], pos: &mut usize, kind: Operand) -> Option<i64> {
+    match kind {
+        Operand::U8 => {
+            let byte = *code.get(*pos)?;
+            *pos += 1;
+            Some(byte.into())
+        }
+        Operand::Offset => read_i24(code, pos).map(i64::from),
+        _ => read_u30(code, pos).map(i64::from),
+    }
+}
+
+fn read_lookup_switch(code: &[u8], pos: &mut usize) -> Option<Vec<i64>> {
+    let default = read_i24(code, pos)?;
+    let num_cases = read_u30(code, pos)?;
+    let mut targets = vec![i64::from(default)];
+    for _ in 0..=num_cases {
+        targets.push(read_i24(code, pos)?.into());
+    }
+    Some(targets)
+}
+
+fn read_u30(code: &[u8], pos: &mut usize) -> Option<u32> {
+    let mut n = 0u64;
+    for i in 0..5 {
+        let byte = *code.get(*pos)?;
+        *pos += 1;
+        n |= u64::from(byte & 0x7f) << (7 * i);
+        if byte & 0x80 == 0 {
+            break;
+        }
+    }
+    // Out of range values won't re-encode to the same bytes, and end up as raw bytes.
+    Some(n as u32)
+}
+
+fn read_i24(code: &[u8], pos: &mut usize) -> Option<i32> {
+    let bytes = code.get(*pos..*pos + 3)?;
+    *pos += 3;
+    Some(i32::from_le_bytes([bytes[0], bytes[1], bytes[2], 0]) << 8 >> 8)
+}
@@ -168,3 +168,106 @@ pub enum OpCode {
     TypeOf = 0x95,
     URShift = 0xa7,
 }
+
+/// The kind of an operand that follows an opcode in bytecode.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum Operand {
+    U8,
+    U30,
+    Int,
+    Uint,
+    Double,
+    String,
+    Namespace,
+    Multiname,
+    Method,
+    Class,
+    Exception,
+
+    /// A signed 24-bit branch offset, relative to the end of the instruction.
+    Offset,
+}
+
+impl OpCode {
+    /// The name of this opcode in assembly listings.
+    pub(crate) fn mnemonic(self) -> String {
+        format!("{self:?}").to_ascii_lowercase()
+    }
+
+    /// The operands that follow this opcode.
+    ///
+    /// `LookupSwitch` has a variable number of operands and returns an empty list.
+    pub(crate) fn operands(self) -> &'static [Operand] {
+        match self {
+            OpCode::ApplyType
+            | OpCode::BkptLine
+            | OpCode::Call
+            | OpCode::Construct
+            | OpCode::ConstructSuper
+            | OpCode::DebugLine
+            | OpCode::DecLocal
+            | OpCode::DecLocalI
+            | OpCode::GetGlobalSlot
+            | OpCode::GetLocal
+            | OpCode::GetOuterScope
+            | OpCode::GetSlot
+            | OpCode::IncLocal
+            | OpCode::IncLocalI
+            | OpCode::Kill
+            | OpCode::NewArray
+            | OpCode::NewObject
+            | OpCode::PushShort
+            | OpCode::SetGlobalSlot
+            | OpCode::SetLocal
+            | OpCode::SetSlot => &[Operand::U30],
+            OpCode::CallMethod | OpCode::HasNext2 => &[Operand::U30, Operand::U30],
+            OpCode::CallProperty
+            | OpCode::CallPropLex
+            | OpCode::CallPropVoid
+            | OpCode::CallSuper
+            | OpCode::CallSuperVoid
+            | OpCode::ConstructProp => &[Operand::Multiname, Operand::U30],
+            OpCode::CallStatic => &[Operand::Method, Operand::U30],
+            OpCode::AsType
+            | OpCode::Coerce
+            | OpCode::DeleteProperty
+            | OpCode::FindDef
+            | OpCode::FindProperty
+            | OpCode::FindPropStrict
+            | OpCode::GetDescendants
+            | OpCode::GetLex
+            | OpCode::GetProperty
+            | OpCode::GetSuper
+            | OpCode::InitProperty
+            | OpCode::IsType
+            | OpCode::SetProperty
+            | OpCode::SetSuper => &[Operand::Multiname],
+            OpCode::Debug => &[Operand::U8, Operand::String, Operand::U8, Operand::U30],
+            OpCode::DebugFile | OpCode::Dxns | OpCode::PushString => &[Operand::String],
+            OpCode::GetScopeObject | OpCode::PushByte => &[Operand::U8],
+            OpCode::IfEq
+            | OpCode::IfFalse
+            | OpCode::IfGe
+            | OpCode::IfGt
+            | OpCode::IfLe
+            | OpCode::IfLt
+            | OpCode::IfNe
+            | OpCode::IfNge
+            | OpCode::IfNgt
+            | OpCode::IfNle
+            | OpCode::IfNlt
+            | OpCode::IfStrictEq
+            | OpCode::IfStrictNe
+            | OpCode::IfTrue
+            | OpCode::Jump => &[Operand::Offset],
+            OpCode::NewCatch => &[Operand::Exception],
+            OpCode::NewClass => &[Operand::Class],
+            OpCode::NewFunction => &[Operand::Method],
+            OpCode::PushDouble => &[Operand::Double],
+            OpCode::PushInt => &[Operand::Int],
+            OpCode::PushNamespace => &[Operand::Namespace],
+            OpCode::PushUint => &[Operand::Uint],
+            _ => &[],
+        }
+    }
+}
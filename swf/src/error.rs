@@ -220,6 +220,22 @@ impl error::Error for AbcParseError {
     }
 }
 
+/// An error in an ABC assembly listing.
+#[derive(Debug)]
+pub struct AbcAssemblyError {
+    /// The line of the listing the error is on, starting from 1.
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for AbcAssemblyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl error::Error for AbcAssemblyError {}
+
 #[derive(Copy, Clone, Debug)]
 pub struct UnexpectedEof(pub(crate) ());
 
@@ -23,6 +23,16 @@ struct TestResponse {
     chunk_gotten: bool,
     status: u16,
     redirected: bool,
+
+    /// How many bytes of the body `next_chunk` returned so far.
+    position: usize,
+
+    /// The size of the chunks to stream the body in, or `None` to return it
+    /// all at once.
+    chunk_size: Option<usize>,
+
+    /// The number of body bytes after which streaming stalls, if any.
+    stall_after: Option<usize>,
 }
 
 impl SuccessResponse for TestResponse {
@@ -35,6 +45,12 @@ impl SuccessResponse for TestResponse {
     }
 
     fn body(self: Box<Self>) -> OwnedFuture<Vec<u8>, Error> {
+        if self
+            .stall_after
+            .is_some_and(|stall_after| stall_after < self.body.len())
+        {
+            return Box::pin(std::future::pending());
+        }
         Box::pin(async move { Ok(self.body) })
     }
 
@@ -51,13 +67,26 @@ impl SuccessResponse for TestResponse {
     }
 
     fn next_chunk(&mut self) -> OwnedFuture<Option<Vec<u8>>, Error> {
-        if !self.chunk_gotten {
-            self.chunk_gotten = true;
-            let body = self.body.clone();
-            Box::pin(async move { Ok(Some(body)) })
-        } else {
-            Box::pin(async move { Ok(None) })
+        let available = self.stall_after.map_or(self.body.len(), |stall_after| {
+            stall_after.min(self.body.len())
+        });
+
+        if self.chunk_gotten && self.position >= available {
+            if self.position < self.body.len() {
+                // Like a connection that hangs, the rest of the body never arrives.
+                return Box::pin(std::future::pending());
+            }
+            return Box::pin(async move { Ok(None) });
         }
+
+        let end = match self.chunk_size {
+            Some(chunk_size) => available.min(self.position + chunk_size),
+            None => available,
+        };
+        let chunk = self.body[self.position..end].to_vec();
+        self.position = end;
+        self.chunk_gotten = true;
+        Box::pin(async move { Ok(Some(chunk)) })
     }
 
     fn expected_length(&self) -> Result<Option<u64>, Error> {
@@ -76,6 +105,12 @@ impl SuccessResponse for TestResponse {
 ///
 /// These are formatted as query params, rather than domains/whole URLs, so that real/real-invalid
 /// URLs can be used in Flash Player when writing tests
+///
+/// Downloads of files can be slowed down with the following query params:
+/// * "debug-chunk-size=N" -> Streams the body in chunks of N bytes
+/// * "debug-stall-after=N" -> Stops streaming the body after N bytes, without ever finishing it
+///
+/// Requests with a "Range: bytes=N-" header get the file from byte N onwards, with status 206.
 pub struct TestNavigatorBackend {
     spawner: NullSpawner,
     relative_base_path: VfsPath,
@@ -129,6 +164,9 @@ impl NavigatorBackend for TestNavigatorBackend {
                     chunk_gotten: false,
                     status: 200,
                     redirected: false,
+                    position: 0,
+                    chunk_size: None,
+                    stall_after: None,
                 });
 
                 Ok(response)
@@ -186,6 +224,21 @@ impl NavigatorBackend for TestNavigatorBackend {
 
         let base_path = self.relative_base_path.clone();
 
+        let debug_param = |name: &str| {
+            url.query_pairs()
+                .find(|(key, _)| key == name)
+                .and_then(|(_, value)| value.parse::<usize>().ok())
+        };
+        let chunk_size = debug_param("debug-chunk-size").filter(|size| *size > 0);
+        let stall_after = debug_param("debug-stall-after");
+
+        let range_start = request
+            .headers()
+            .get("Range")
+            .and_then(|range| range.strip_prefix("bytes="))
+            .and_then(|range| range.strip_suffix('-'))
+            .and_then(|start| start.parse::<usize>().ok());
+
         Box::pin(async move {
             let path = if url.scheme() == "file" {
                 // Flash supports query parameters with local urls.
@@ -232,17 +285,26 @@ impl NavigatorBackend for TestNavigatorBackend {
                 path
             };
 
-            let body = read_bytes(&path).map_err(|error| ErrorResponse {
+            let mut body = read_bytes(&path).map_err(|error| ErrorResponse {
                 url: url.to_string(),
                 error: Error::FetchError(error.to_string()),
             })?;
 
+            let mut status = 0;
+            if let Some(start) = range_start.filter(|start| *start <= body.len()) {
+                body.drain(..start);
+                status = 206;
+            }
+
             let response: Box<dyn SuccessResponse> = Box::new(TestResponse {
                 url: url.to_string(),
                 body,
                 chunk_gotten: false,
-                status: 0,
+                status,
                 redirected: false,
+                position: 0,
+                chunk_size,
+                stall_after,
             });
 
             Ok(response)
@@ -19,6 +19,7 @@ pub struct PlayerOptions {
     version: Option<u8>,
     mode: Option<PlayerMode>,
     with_default_font: bool,
+    with_security_sandbox: bool,
 }
 
 impl PlayerOptions {
@@ -40,7 +41,8 @@ impl PlayerOptions {
             .with_player_version(self.version)
             // Assume flashplayerdebugger is used in tests
             .with_player_mode(self.mode.unwrap_or(PlayerMode::Debug))
-            .with_default_font(self.with_default_font);
+            .with_default_font(self.with_default_font)
+            .with_security_sandbox(self.with_security_sandbox);
 
         if self.with_video {
             #[cfg(feature = "ruffle_video_external")]
@@ -0,0 +1,40 @@
+package {
+    import flash.display.Sprite;
+    import flash.display.Stage;
+    import flash.events.Event;
+    import flash.events.TimerEvent;
+    import flash.utils.Timer;
+    import flash.utils.setInterval;
+
+    public class Child extends Sprite {
+        public var timer:Timer = new Timer(10);
+        public var timerTicks:int = 0;
+        public var intervalTicks:int = 0;
+        public var frames:int = 0;
+        public var stageFrames:int = 0;
+
+        public function start(stage:Stage):void {
+            timer.addEventListener(TimerEvent.TIMER, function(e:TimerEvent):void {
+                timerTicks++;
+            });
+            timer.start();
+            setInterval(function():void {
+                intervalTicks++;
+            }, 10);
+            addEventListener(Event.ENTER_FRAME, function(e:Event):void {
+                frames++;
+            });
+            stage.addEventListener(Event.ENTER_FRAME, function(e:Event):void {
+                stageFrames++;
+            });
+        }
+
+        public function ran():Boolean {
+            return timerTicks > 0 && intervalTicks > 0 && frames > 0 && stageFrames > 0;
+        }
+
+        public function counters():String {
+            return [timerTicks, intervalTicks, frames, stageFrames].join(",");
+        }
+    }
+}
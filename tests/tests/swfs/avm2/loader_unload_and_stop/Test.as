@@ -0,0 +1,77 @@
+package {
+    import flash.display.Loader;
+    import flash.display.Sprite;
+    import flash.events.Event;
+    import flash.events.IOErrorEvent;
+    import flash.net.URLRequest;
+
+    public class Test extends Sprite {
+        private var loader:Loader = new Loader();
+        private var child:Object;
+        private var counters:String;
+        private var closed:Boolean = false;
+        private var waitFrames:int = 0;
+        private var next:Function;
+
+        public function Test() {
+            addChild(loader);
+            loader.contentLoaderInfo.addEventListener(Event.OPEN, onOpen);
+            loader.contentLoaderInfo.addEventListener(Event.COMPLETE, onComplete);
+            loader.contentLoaderInfo.addEventListener(IOErrorEvent.IO_ERROR, function(e:IOErrorEvent):void {
+                trace("ioError: " + e.errorID);
+            });
+            addEventListener(Event.ENTER_FRAME, onEnterFrame);
+
+            // The download stalls, so it's still in progress when closing.
+            loader.load(new URLRequest("child.swf?debug-stall-after=16"));
+        }
+
+        private function wait(frames:int, then:Function):void {
+            waitFrames = frames;
+            next = then;
+        }
+
+        private function onEnterFrame(e:Event):void {
+            if (waitFrames > 0 && --waitFrames == 0) {
+                next();
+            }
+        }
+
+        private function onOpen(e:Event):void {
+            trace("open");
+            if (!closed) {
+                closed = true;
+                loader.close();
+                trace("close, content " + loader.content);
+                wait(3, loadAgain);
+            }
+        }
+
+        private function loadAgain():void {
+            trace("no events after close");
+            loader.load(new URLRequest("child.swf"));
+        }
+
+        private function onComplete(e:Event):void {
+            trace("complete");
+            child = loader.content;
+            child.start(stage);
+            wait(5, unloadChild);
+        }
+
+        private function unloadChild():void {
+            trace("timer running: " + child.timer.running);
+            trace("child ran: " + child.ran());
+            counters = child.counters();
+            loader.unloadAndStop();
+            trace("unloadAndStop, content " + loader.content);
+            trace("timer running: " + child.timer.running);
+            wait(5, checkStopped);
+        }
+
+        private function checkStopped():void {
+            trace("child stopped: " + (child.counters() == counters));
+            trace("done");
+        }
+    }
+}
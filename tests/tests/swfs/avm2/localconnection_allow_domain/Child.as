@@ -0,0 +1,23 @@
+package {
+    import flash.display.Sprite;
+    import flash.net.LocalConnection;
+
+    public class Child extends Sprite {
+        private var receiver:LocalConnection = new LocalConnection();
+
+        public function Child() {
+            receiver.client = {
+                greet: function(message:String):void {
+                    trace("child: greet " + message);
+                }
+            };
+            receiver.connect("_lc_child");
+            trace("child: connected");
+        }
+
+        public function allowSender(domain:String):void {
+            trace("child: allowDomain " + domain);
+            receiver.allowDomain(domain);
+        }
+    }
+}
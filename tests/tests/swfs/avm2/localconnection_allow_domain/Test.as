@@ -0,0 +1,45 @@
+package {
+    import flash.display.Loader;
+    import flash.display.Sprite;
+    import flash.events.Event;
+    import flash.events.StatusEvent;
+    import flash.net.LocalConnection;
+    import flash.net.URLRequest;
+
+    public class Test extends Sprite {
+        private var receiver:LocalConnection = new LocalConnection();
+        private var selfSender:LocalConnection = new LocalConnection();
+        private var childSender:LocalConnection = new LocalConnection();
+        private var loader:Loader = new Loader();
+
+        public function Test() {
+            // Movies of the same domain don't need to allow each other.
+            receiver.client = {
+                greet: function(message:String):void {
+                    trace("parent: greet " + message);
+                }
+            };
+            receiver.connect("_lc_parent");
+            selfSender.addEventListener(StatusEvent.STATUS, function(event:StatusEvent):void {
+                trace("selfSender: " + event.level);
+            });
+            selfSender.send("_lc_parent", "greet", "to self");
+
+            // The child is loaded from another domain.
+            childSender.addEventListener(StatusEvent.STATUS, onChildStatus);
+            loader.contentLoaderInfo.addEventListener(Event.COMPLETE, function(event:Event):void {
+                trace("parent: child loaded");
+                childSender.send("_lc_child", "greet", "before allowDomain");
+            });
+            loader.load(new URLRequest("http://example.com/child.swf"));
+        }
+
+        private function onChildStatus(event:StatusEvent):void {
+            trace("childSender: " + event.level);
+            if (event.level == "error") {
+                Object(loader.content).allowSender("localhost");
+                childSender.send("_lc_child", "greet", "after allowDomain");
+            }
+        }
+    }
+}
@@ -0,0 +1,54 @@
+package {
+    import flash.display.Sprite;
+    import flash.events.NetStatusEvent;
+    import flash.net.NetConnection;
+    import flash.net.NetStream;
+
+    // `stream.flv` only has script tags: `onMetaData` with a keyframe index,
+    // and an `onTick` call every second from 0 to 5.
+    public class Test extends Sprite {
+        private var stream:NetStream;
+        private var seeked:Boolean = false;
+
+        public function Test() {
+            var connection:NetConnection = new NetConnection();
+            connection.connect(null);
+
+            var stream:NetStream = new NetStream(connection);
+            stream.client = {
+                onMetaData: function(info:Object):void {
+                    trace("onMetaData: duration " + info.duration + ", " + info.keyframes.times.length + " keyframes");
+                },
+                onTick: function(second:Number):void {
+                    trace("onTick: " + second + ", time " + stream.time);
+                }
+            };
+            stream.addEventListener(NetStatusEvent.NET_STATUS, onNetStatus);
+            this.stream = stream;
+
+            trace("bufferTime: " + stream.bufferTime);
+            stream.bufferTime = 2;
+            trace("bufferTime: " + stream.bufferTime);
+
+            // The download stalls in the middle of the tag at 4 seconds.
+            stream.play("stream.flv?debug-chunk-size=64&debug-stall-after=360");
+        }
+
+        private function onNetStatus(event:NetStatusEvent):void {
+            var code:String = event.info.code;
+            if (code.indexOf("NetStream.Buffer.") == 0) {
+                trace(code + ": time " + stream.time + ", bufferLength " + stream.bufferLength +
+                    ", bytesLoaded " + stream.bytesLoaded + ", bytesTotal " + stream.bytesTotal);
+            } else {
+                trace(code);
+            }
+
+            if (code == "NetStream.Buffer.Empty" && !seeked) {
+                seeked = true;
+                // The keyframe at 5 seconds wasn't downloaded, so this
+                // needs a range request.
+                stream.seek(5);
+            }
+        }
+    }
+}
@@ -0,0 +1,58 @@
+package {
+    import flash.display.Sprite;
+    import flash.events.Event;
+    import flash.events.IOErrorEvent;
+    import flash.events.SecurityErrorEvent;
+    import flash.net.URLLoader;
+    import flash.net.URLRequest;
+    import flash.net.URLRequestHeader;
+    import flash.system.Security;
+
+    public class Test extends Sprite {
+        private var steps:Array;
+
+        public function Test() {
+            trace("sandboxType: " + Security.sandboxType);
+
+            var withHeader:URLRequest = new URLRequest("http://example.com/data.txt");
+            withHeader.requestHeaders = [new URLRequestHeader("X-Custom", "1")];
+
+            steps = [
+                // The policy file of example.com allows access from anywhere.
+                new URLRequest("http://example.com/data.txt"),
+                // ...but it doesn't allow sending custom headers.
+                withHeader,
+                // other.com doesn't have a policy file.
+                new URLRequest("http://other.com/data.txt"),
+                // Local-with-network movies can't load local files.
+                new URLRequest("data.txt")
+            ];
+            next();
+        }
+
+        private function next():void {
+            if (steps.length == 0) {
+                trace("done");
+                return;
+            }
+
+            var request:URLRequest = steps.shift();
+            trace("load " + request.url);
+            var loader:URLLoader = new URLLoader();
+            loader.addEventListener(Event.COMPLETE, function(e:Event):void {
+                trace("complete: " + loader.data);
+                next();
+            });
+            loader.addEventListener(IOErrorEvent.IO_ERROR, function(e:IOErrorEvent):void {
+                trace("ioError: " + e.errorID);
+                next();
+            });
+            loader.addEventListener(SecurityErrorEvent.SECURITY_ERROR, function(e:SecurityErrorEvent):void {
+                trace("securityError: " + e.errorID);
+                trace(e.text);
+                next();
+            });
+            loader.load(request);
+        }
+    }
+}
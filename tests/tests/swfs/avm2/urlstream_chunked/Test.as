@@ -0,0 +1,69 @@
+package {
+    import flash.display.Sprite;
+    import flash.errors.EOFError;
+    import flash.events.Event;
+    import flash.events.HTTPStatusEvent;
+    import flash.events.ProgressEvent;
+    import flash.net.URLRequest;
+    import flash.net.URLStream;
+
+    public class Test extends Sprite {
+        public function Test() {
+            readChunks();
+        }
+
+        private function listen(name:String, stream:URLStream):void {
+            stream.addEventListener(Event.OPEN, function(e:Event):void {
+                trace(name + ": open, bytesAvailable " + stream.bytesAvailable);
+            });
+            stream.addEventListener(HTTPStatusEvent.HTTP_STATUS, function(e:HTTPStatusEvent):void {
+                trace(name + ": httpStatus " + e.status);
+            });
+            stream.addEventListener(ProgressEvent.PROGRESS, function(e:ProgressEvent):void {
+                trace(name + ": progress " + e.bytesLoaded + "/" + e.bytesTotal + ", bytesAvailable " + stream.bytesAvailable);
+            });
+            stream.addEventListener(Event.COMPLETE, function(e:Event):void {
+                trace(name + ": complete, bytesAvailable " + stream.bytesAvailable);
+            });
+        }
+
+        // Data can be read as soon as each chunk arrives.
+        private function readChunks():void {
+            var stream:URLStream = new URLStream();
+            listen("readChunks", stream);
+            stream.addEventListener(ProgressEvent.PROGRESS, function(e:ProgressEvent):void {
+                trace("readChunks: read " + stream.readUTFBytes(stream.bytesAvailable));
+            });
+            stream.addEventListener(Event.COMPLETE, function(e:Event):void {
+                closeWhileLoading();
+            });
+            stream.load(new URLRequest("data.txt?debug-chunk-size=4"));
+        }
+
+        // Closing the stream stops the download, so no more events arrive.
+        private function closeWhileLoading():void {
+            var stream:URLStream = new URLStream();
+            listen("closeWhileLoading", stream);
+            stream.addEventListener(ProgressEvent.PROGRESS, function(e:ProgressEvent):void {
+                stream.close();
+                trace("closeWhileLoading: closed, connected " + stream.connected);
+                readPartialObject();
+            });
+            stream.load(new URLRequest("data.txt?debug-chunk-size=4"));
+        }
+
+        // An object that didn't fully arrive yet can't be read, and stays in the stream.
+        private function readPartialObject():void {
+            var stream:URLStream = new URLStream();
+            listen("readPartialObject", stream);
+            stream.addEventListener(ProgressEvent.PROGRESS, function(e:ProgressEvent):void {
+                try {
+                    trace("readPartialObject: read " + stream.readObject());
+                } catch (error:EOFError) {
+                    trace("readPartialObject: EOFError " + error.errorID);
+                }
+            });
+            stream.load(new URLRequest("object.amf?debug-chunk-size=5"));
+        }
+    }
+}
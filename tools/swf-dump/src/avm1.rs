@@ -0,0 +1,206 @@
+//! AVM1 action disassembly
+
+use serde::Serialize;
+use swf::avm1::read::Reader;
+use swf::avm1::types::{Action, CatchVar, Value};
+use swf::{Encoding, SwfStr};
+
+/// A single disassembled action.
+#[derive(Serialize, Debug)]
+pub struct ActionReport {
+    /// The offset of the action from the start of its action block.
+    pub offset: usize,
+
+    /// How deeply nested this action is in function, `with` and `try` bodies.
+    pub depth: usize,
+
+    pub action: String,
+}
+
+/// Disassembles a block of actions, along with the bodies of the functions defined in it.
+///
+/// Returns the actions that could be read, and the error that stopped the
+/// disassembly, if any.
+pub fn disassemble(data: &[u8], version: u8) -> (Vec<ActionReport>, Option<String>) {
+    let mut disassembler = Disassembler {
+        base: data,
+        version,
+        encoding: SwfStr::encoding_for_version(version),
+        actions: Vec::new(),
+    };
+    let error = disassembler.disassemble_block(data, 0).err();
+    (disassembler.actions, error)
+}
+
+struct Disassembler<'a> {
+    base: &'a [u8],
+    version: u8,
+    encoding: &'static Encoding,
+    actions: Vec<ActionReport>,
+}
+
+impl<'a> Disassembler<'a> {
+    fn offset_of(&self, data: &[u8]) -> usize {
+        data.as_ptr() as usize - self.base.as_ptr() as usize
+    }
+
+    fn push(&mut self, offset: usize, depth: usize, action: String) {
+        self.actions.push(ActionReport {
+            offset,
+            depth,
+            action,
+        });
+    }
+
+    fn disassemble_block(&mut self, block: &'a [u8], depth: usize) -> Result<(), String> {
+        let mut reader = Reader::new(block, self.version);
+        while !reader.get_ref().is_empty() {
+            let offset = self.offset_of(reader.get_ref());
+            let action = reader
+                .read_action()
+                .map_err(|e| format!("Invalid action at offset {offset}: {e}"))?;
+            let next_offset = self.offset_of(reader.get_ref());
+
+            match action {
+                Action::DefineFunction(function) => {
+                    let params: Vec<_> = function
+                        .params
+                        .iter()
+                        .map(|param| param.to_str_lossy(self.encoding))
+                        .collect();
+                    self.push(
+                        offset,
+                        depth,
+                        format!(
+                            "DefineFunction {}({})",
+                            function.name.to_str_lossy(self.encoding),
+                            params.join(", ")
+                        ),
+                    );
+                    self.disassemble_block(function.actions, depth + 1)?;
+                }
+                Action::DefineFunction2(function) => {
+                    let params: Vec<_> = function
+                        .params
+                        .iter()
+                        .map(|param| match param.register_index {
+                            Some(register) => {
+                                format!("r{register}:{}", param.name.to_str_lossy(self.encoding))
+                            }
+                            None => param.name.to_str_lossy(self.encoding).into_owned(),
+                        })
+                        .collect();
+                    self.push(
+                        offset,
+                        depth,
+                        format!(
+                            "DefineFunction2 {}({}) registers={} flags={:?}",
+                            function.name.to_str_lossy(self.encoding),
+                            params.join(", "),
+                            function.register_count,
+                            function.flags
+                        ),
+                    );
+                    self.disassemble_block(function.actions, depth + 1)?;
+                }
+                Action::Try(try_block) => {
+                    self.push(offset, depth, "Try".to_string());
+                    self.disassemble_block(try_block.try_body, depth + 1)?;
+                    if let Some((catch_var, catch_body)) = try_block.catch_body {
+                        let catch_var = match catch_var {
+                            CatchVar::Var(name) => name.to_str_lossy(self.encoding).into_owned(),
+                            CatchVar::Register(register) => format!("r{register}"),
+                        };
+                        self.push(
+                            self.offset_of(catch_body),
+                            depth,
+                            format!("Catch {catch_var}"),
+                        );
+                        self.disassemble_block(catch_body, depth + 1)?;
+                    }
+                    if let Some(finally_body) = try_block.finally_body {
+                        self.push(self.offset_of(finally_body), depth, "Finally".to_string());
+                        self.disassemble_block(finally_body, depth + 1)?;
+                    }
+                }
+                Action::With(with) => {
+                    self.push(offset, depth, "With".to_string());
+                    self.disassemble_block(with.actions, depth + 1)?;
+                }
+                Action::ConstantPool(pool) => {
+                    let strings: Vec<_> = pool
+                        .strings
+                        .iter()
+                        .enumerate()
+                        .map(|(i, s)| format!("{i}:{:?}", s.to_str_lossy(self.encoding)))
+                        .collect();
+                    self.push(offset, depth, format!("ConstantPool {}", strings.join(" ")));
+                }
+                Action::Push(push) => {
+                    let values: Vec<_> = push
+                        .values
+                        .iter()
+                        .map(|value| self.format_value(value))
+                        .collect();
+                    self.push(offset, depth, format!("Push {}", values.join(", ")));
+                }
+                Action::Jump(jump) => {
+                    let target = next_offset as isize + jump.offset as isize;
+                    self.push(offset, depth, format!("Jump {target}"));
+                }
+                Action::If(if_action) => {
+                    let target = next_offset as isize + if_action.offset as isize;
+                    self.push(offset, depth, format!("If {target}"));
+                }
+                action => self.push(offset, depth, format!("{action:?}")),
+            }
+        }
+        Ok(())
+    }
+
+    fn format_value(&self, value: &Value) -> String {
+        match value {
+            Value::Str(s) => format!("{:?}", s.to_str_lossy(self.encoding)),
+            Value::Register(register) => format!("r{register}"),
+            Value::ConstantPool(index) => format!("c{index}"),
+            value => format!("{value:?}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn disassemble_function() {
+        // function foo() { trace("test"); }
+        let data = [
+            0x9b, 0x08, 0x00, 0x66, 0x6f, 0x6f, 0x00, 0x00, 0x00, 0x0a, 0x00, 0x96, 0x06, 0x00,
+            0x00, 0x74, 0x65, 0x73, 0x74, 0x00, 0x26, 0x00,
+        ];
+        let (actions, error) = disassemble(&data, 5);
+        assert_eq!(error, None);
+
+        let actions: Vec<_> = actions
+            .iter()
+            .map(|a| (a.offset, a.depth, a.action.as_str()))
+            .collect();
+        assert_eq!(
+            actions,
+            [
+                (0, 0, "DefineFunction foo()"),
+                (11, 1, "Push \"test\""),
+                (20, 1, "Trace"),
+                (21, 0, "End"),
+            ]
+        );
+    }
+
+    #[test]
+    fn truncated_block() {
+        let (actions, error) = disassemble(&[0x26, 0x96, 0x06], 5);
+        assert_eq!(actions.len(), 1);
+        assert!(error.is_some());
+    }
+}
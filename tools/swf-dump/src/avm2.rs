@@ -0,0 +1,295 @@
+//! AVM2 class and method listings
+
+use serde::Serialize;
+use swf::avm2::read::Reader;
+use swf::avm2::types::{
+    AbcFile, DefaultValue, Index, Method, MethodFlags, Multiname, Namespace, Trait, TraitKind,
+};
+
+/// The contents of an ABC file.
+#[derive(Serialize, Debug)]
+pub struct AbcReport {
+    /// The name given by a `DoABC2` tag.
+    pub name: Option<String>,
+
+    /// The offset of the tag containing this ABC file.
+    pub tag_offset: usize,
+
+    pub major_version: u16,
+    pub minor_version: u16,
+    pub num_methods: usize,
+    pub num_method_bodies: usize,
+    pub classes: Vec<ClassReport>,
+
+    /// Traits of the scripts, excluding class definitions.
+    pub script_traits: Vec<TraitReport>,
+
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ClassReport {
+    pub name: String,
+    pub super_name: Option<String>,
+    pub interfaces: Vec<String>,
+    pub is_sealed: bool,
+    pub is_final: bool,
+    pub is_interface: bool,
+    pub constructor: String,
+    pub instance_traits: Vec<TraitReport>,
+    pub class_traits: Vec<TraitReport>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct TraitReport {
+    pub name: String,
+    pub kind: &'static str,
+
+    /// The type of a slot, or the signature of a method.
+    pub signature: String,
+}
+
+impl AbcReport {
+    pub fn new(data: &[u8], name: Option<String>, tag_offset: usize) -> Self {
+        match Reader::new(data).read() {
+            Ok(abc) => {
+                let names = Names { abc: &abc };
+                let classes = abc
+                    .instances
+                    .iter()
+                    .zip(&abc.classes)
+                    .map(|(instance, class)| ClassReport {
+                        name: names.multiname(instance.name),
+                        super_name: (instance.super_name.0 != 0)
+                            .then(|| names.multiname(instance.super_name)),
+                        interfaces: instance
+                            .interfaces
+                            .iter()
+                            .map(|interface| names.multiname(*interface))
+                            .collect(),
+                        is_sealed: instance.is_sealed,
+                        is_final: instance.is_final,
+                        is_interface: instance.is_interface,
+                        constructor: names.signature(instance.init_method),
+                        instance_traits: names.traits(&instance.traits),
+                        class_traits: names.traits(&class.traits),
+                    })
+                    .collect();
+                let script_traits = abc
+                    .scripts
+                    .iter()
+                    .flat_map(|script| &script.traits)
+                    .filter(|t| !matches!(t.kind, TraitKind::Class { .. }))
+                    .map(|t| names.trait_report(t))
+                    .collect();
+
+                Self {
+                    name,
+                    tag_offset,
+                    major_version: abc.major_version,
+                    minor_version: abc.minor_version,
+                    num_methods: abc.methods.len(),
+                    num_method_bodies: abc.method_bodies.len(),
+                    classes,
+                    script_traits,
+                    error: None,
+                }
+            }
+            Err(e) => Self {
+                name,
+                tag_offset,
+                major_version: 0,
+                minor_version: 0,
+                num_methods: 0,
+                num_method_bodies: 0,
+                classes: Vec::new(),
+                script_traits: Vec::new(),
+                error: Some(e.to_string()),
+            },
+        }
+    }
+}
+
+/// Resolves constant pool references into readable names.
+struct Names<'a> {
+    abc: &'a AbcFile,
+}
+
+impl Names<'_> {
+    fn string(&self, index: Index<String>) -> String {
+        match index.0.checked_sub(1) {
+            Some(i) => self
+                .abc
+                .constant_pool
+                .strings
+                .get(i as usize)
+                .map(|s| String::from_utf8_lossy(s).into_owned())
+                .unwrap_or_else(|| format!("<invalid string {}>", index.0)),
+            None => String::new(),
+        }
+    }
+
+    fn namespace(&self, index: Index<Namespace>) -> String {
+        let Some(namespace) = index
+            .0
+            .checked_sub(1)
+            .and_then(|i| self.abc.constant_pool.namespaces.get(i as usize))
+        else {
+            return "*".to_string();
+        };
+
+        match namespace {
+            Namespace::Namespace(name)
+            | Namespace::Package(name)
+            | Namespace::PackageInternal(name)
+            | Namespace::Protected(name)
+            | Namespace::Explicit(name)
+            | Namespace::StaticProtected(name) => self.string(*name),
+            Namespace::Private(_) => "private".to_string(),
+        }
+    }
+
+    fn multiname(&self, index: Index<Multiname>) -> String {
+        let Some(multiname) = index
+            .0
+            .checked_sub(1)
+            .and_then(|i| self.abc.constant_pool.multinames.get(i as usize))
+        else {
+            return "*".to_string();
+        };
+
+        match multiname {
+            Multiname::QName { namespace, name } | Multiname::QNameA { namespace, name } => {
+                let namespace = self.namespace(*namespace);
+                let name = self.string(*name);
+                if namespace.is_empty() {
+                    name
+                } else {
+                    format!("{namespace}::{name}")
+                }
+            }
+            Multiname::RTQName { name }
+            | Multiname::RTQNameA { name }
+            | Multiname::Multiname { name, .. }
+            | Multiname::MultinameA { name, .. } => self.string(*name),
+            Multiname::RTQNameL
+            | Multiname::RTQNameLA
+            | Multiname::MultinameL { .. }
+            | Multiname::MultinameLA { .. } => "<runtime name>".to_string(),
+            Multiname::TypeName {
+                base_type,
+                parameters,
+            } => {
+                let parameters: Vec<_> = parameters.iter().map(|p| self.multiname(*p)).collect();
+                format!("{}.<{}>", self.multiname(*base_type), parameters.join(", "))
+            }
+        }
+    }
+
+    fn default_value(&self, value: DefaultValue) -> String {
+        let pool = &self.abc.constant_pool;
+        match value {
+            DefaultValue::Int(i) => pool
+                .ints
+                .get((i.0 as usize).wrapping_sub(1))
+                .map_or_else(|| "?".to_string(), |v| v.to_string()),
+            DefaultValue::Uint(i) => pool
+                .uints
+                .get((i.0 as usize).wrapping_sub(1))
+                .map_or_else(|| "?".to_string(), |v| v.to_string()),
+            DefaultValue::Double(i) => pool
+                .doubles
+                .get((i.0 as usize).wrapping_sub(1))
+                .map_or_else(|| "?".to_string(), |v| v.to_string()),
+            DefaultValue::String(i) => format!("{:?}", self.string(i)),
+            DefaultValue::True => "true".to_string(),
+            DefaultValue::False => "false".to_string(),
+            DefaultValue::Null => "null".to_string(),
+            DefaultValue::Undefined => "undefined".to_string(),
+            DefaultValue::Namespace(ns)
+            | DefaultValue::Package(ns)
+            | DefaultValue::PackageInternal(ns)
+            | DefaultValue::Protected(ns)
+            | DefaultValue::Explicit(ns)
+            | DefaultValue::StaticProtected(ns)
+            | DefaultValue::Private(ns) => format!("namespace {:?}", self.namespace(ns)),
+        }
+    }
+
+    fn signature(&self, index: Index<Method>) -> String {
+        let Some(method) = self.abc.methods.get(index.0 as usize) else {
+            return format!("<invalid method {}>", index.0);
+        };
+
+        let mut params: Vec<_> = method
+            .params
+            .iter()
+            .enumerate()
+            .map(|(i, param)| {
+                let name = param
+                    .name
+                    .map(|name| self.string(name))
+                    .unwrap_or_else(|| format!("arg{i}"));
+                let mut param_str = format!("{name}:{}", self.multiname(param.kind));
+                if let Some(value) = &param.default_value {
+                    param_str.push_str(" = ");
+                    param_str.push_str(&self.default_value(*value));
+                }
+                param_str
+            })
+            .collect();
+        if method.flags.contains(MethodFlags::NEED_REST) {
+            params.push("...rest".to_string());
+        }
+
+        format!(
+            "({}):{}",
+            params.join(", "),
+            self.multiname(method.return_type)
+        )
+    }
+
+    fn trait_report(&self, t: &Trait) -> TraitReport {
+        let (kind, signature) = match &t.kind {
+            TraitKind::Slot {
+                type_name, value, ..
+            } => ("slot", self.slot_signature(*type_name, value.as_ref())),
+            TraitKind::Const {
+                type_name, value, ..
+            } => ("const", self.slot_signature(*type_name, value.as_ref())),
+            TraitKind::Method { method, .. } => ("method", self.signature(*method)),
+            TraitKind::Getter { method, .. } => ("getter", self.signature(*method)),
+            TraitKind::Setter { method, .. } => ("setter", self.signature(*method)),
+            TraitKind::Function { function, .. } => ("function", self.signature(*function)),
+            TraitKind::Class { class, .. } => {
+                let name = self
+                    .abc
+                    .instances
+                    .get(class.0 as usize)
+                    .map_or_else(|| "?".to_string(), |i| self.multiname(i.name));
+                ("class", name)
+            }
+        };
+
+        TraitReport {
+            name: self.multiname(t.name),
+            kind,
+            signature,
+        }
+    }
+
+    fn slot_signature(&self, type_name: Index<Multiname>, value: Option<&DefaultValue>) -> String {
+        match value {
+            Some(value) => format!(
+                "{} = {}",
+                self.multiname(type_name),
+                self.default_value(*value)
+            ),
+            None => self.multiname(type_name),
+        }
+    }
+
+    fn traits(&self, traits: &[Trait]) -> Vec<TraitReport> {
+        traits.iter().map(|t| self.trait_report(t)).collect()
+    }
+}
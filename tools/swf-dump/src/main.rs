@@ -0,0 +1,66 @@
+use crate::report::{Report, ReportOptions};
+use anyhow::{Context, Result};
+use clap::{Parser, ValueEnum};
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+
+mod avm1;
+mod avm2;
+mod report;
+mod text;
+
+#[derive(Parser, Debug)]
+#[clap(name = "swf-dump", version, about, author)]
+struct Opt {
+    /// The SWF file to inspect
+    #[clap(name = "file")]
+    input_path: PathBuf,
+
+    /// The output format
+    #[clap(short, long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+
+    /// Don't disassemble AVM1 actions
+    #[clap(long)]
+    skip_actions: bool,
+
+    /// Don't list the classes of AVM2 bytecode
+    #[clap(long)]
+    skip_abc: bool,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum Format {
+    /// Human-readable text
+    Text,
+
+    /// Machine-readable JSON
+    Json,
+}
+
+fn main() -> Result<()> {
+    let opt = Opt::parse();
+
+    let data = std::fs::read(&opt.input_path)
+        .with_context(|| format!("Couldn't read {}", opt.input_path.display()))?;
+    let swf_buf = swf::decompress_swf(&data[..]).context("Couldn't decompress SWF")?;
+    let report = Report::new(
+        &swf_buf,
+        ReportOptions {
+            actions: !opt.skip_actions,
+            abc: !opt.skip_abc,
+        },
+    );
+
+    let mut out = BufWriter::new(std::io::stdout().lock());
+    match opt.format {
+        Format::Text => text::write_report(&mut out, &report)?,
+        Format::Json => {
+            serde_json::to_writer_pretty(&mut out, &report)?;
+            writeln!(out)?;
+        }
+    }
+    out.flush()?;
+
+    Ok(())
+}
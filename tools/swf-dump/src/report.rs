@@ -0,0 +1,611 @@
+//! The contents of an SWF file, in a form that can be printed or serialized
+
+use crate::avm1::{self, ActionReport};
+use crate::avm2::AbcReport;
+use serde::Serialize;
+use swf::read::Reader;
+use swf::{CharacterId, Compression, Encoding, Header, SoundFormat, SwfBuf, SwfStr, Tag, TagCode};
+
+/// Which of the more expensive sections to include in a report.
+#[derive(Clone, Copy, Debug)]
+pub struct ReportOptions {
+    /// Disassemble AVM1 actions.
+    pub actions: bool,
+
+    /// List the contents of AVM2 bytecode.
+    pub abc: bool,
+}
+
+#[derive(Serialize, Debug)]
+pub struct Report {
+    pub header: HeaderReport,
+    pub tags: Vec<TagReport>,
+    pub characters: Vec<CharacterReport>,
+
+    /// The class linked to the main timeline.
+    pub document_class: Option<String>,
+
+    pub scenes: Vec<FrameLabelReport>,
+    pub frame_labels: Vec<FrameLabelReport>,
+    pub fonts: Vec<FontReport>,
+    pub sounds: Vec<SoundReport>,
+    pub actions: Vec<ActionBlockReport>,
+    pub abc: Vec<AbcReport>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct HeaderReport {
+    pub version: u8,
+    pub compression: String,
+    pub uncompressed_length: i32,
+    pub width: f64,
+    pub height: f64,
+    pub frame_rate: f32,
+    pub num_frames: u16,
+    pub background_color: Option<String>,
+    pub is_action_script_3: bool,
+    pub use_network_sandbox: bool,
+    pub has_metadata: bool,
+}
+
+#[derive(Serialize, Debug)]
+pub struct TagReport {
+    /// The offset of the tag in the decompressed file.
+    pub offset: usize,
+
+    pub code: u16,
+    pub name: String,
+
+    /// The length of the tag, excluding its header.
+    pub length: usize,
+
+    /// The character defined by this tag.
+    pub character_id: Option<CharacterId>,
+
+    pub error: Option<String>,
+
+    /// The tags of a `DefineSprite` timeline.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<TagReport>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct CharacterReport {
+    pub id: CharacterId,
+
+    /// The tag that defined this character.
+    pub kind: String,
+
+    pub export_name: Option<String>,
+    pub class_name: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct FrameLabelReport {
+    /// The sprite containing this label, or `None` for the main timeline.
+    pub timeline: Option<CharacterId>,
+
+    /// The frame number, starting at 1.
+    pub frame: u32,
+
+    pub label: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct FontReport {
+    pub id: CharacterId,
+    pub kind: String,
+    pub name: Option<String>,
+    pub is_bold: bool,
+    pub is_italic: bool,
+
+    /// The number of glyphs, or `None` for DefineFont4, which embeds a CFF font instead.
+    pub num_glyphs: Option<usize>,
+
+    pub has_layout: bool,
+}
+
+#[derive(Serialize, Debug)]
+pub struct SoundReport {
+    /// The character of an event sound, or `None` for a sound stream.
+    pub id: Option<CharacterId>,
+
+    /// The sprite of a sound stream, or `None` for the main timeline.
+    pub timeline: Option<CharacterId>,
+
+    pub kind: String,
+    pub format: SoundFormatReport,
+
+    /// The number of samples of an event sound, or the number of samples per frame of a sound stream.
+    pub num_samples: u32,
+}
+
+#[derive(Serialize, Debug)]
+pub struct SoundFormatReport {
+    pub compression: String,
+    pub sample_rate: u16,
+    pub is_stereo: bool,
+    pub is_16_bit: bool,
+}
+
+impl From<&SoundFormat> for SoundFormatReport {
+    fn from(format: &SoundFormat) -> Self {
+        Self {
+            compression: format!("{:?}", format.compression),
+            sample_rate: format.sample_rate,
+            is_stereo: format.is_stereo,
+            is_16_bit: format.is_16_bit,
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct ActionBlockReport {
+    /// Where this block of actions comes from, like `DoAction` or `DoInitAction(12)`.
+    pub source: String,
+
+    /// The sprite containing this block, or `None` for the main timeline.
+    pub timeline: Option<CharacterId>,
+
+    /// The frame of a `DoAction` block, starting at 1.
+    pub frame: Option<u32>,
+
+    /// The offset of the tag containing this block.
+    pub tag_offset: usize,
+
+    pub actions: Vec<ActionReport>,
+    pub error: Option<String>,
+}
+
+impl Report {
+    pub fn new(swf_buf: &SwfBuf, options: ReportOptions) -> Self {
+        let header = &swf_buf.header;
+        let stage_size = header.stage_size();
+        let mut builder = ReportBuilder {
+            version: header.version(),
+            encoding: SwfStr::encoding_for_version(header.version()),
+            options,
+            report: Report {
+                header: HeaderReport {
+                    version: header.version(),
+                    compression: format!("{:?}", header.compression()),
+                    uncompressed_length: header.uncompressed_len(),
+                    width: stage_size.width().to_pixels(),
+                    height: stage_size.height().to_pixels(),
+                    frame_rate: header.frame_rate().to_f32(),
+                    num_frames: header.num_frames(),
+                    background_color: header
+                        .background_color()
+                        .map(|c| format!("#{:02X}{:02X}{:02X}", c.r, c.g, c.b)),
+                    is_action_script_3: header.is_action_script_3(),
+                    use_network_sandbox: header.use_network_sandbox(),
+                    has_metadata: header.has_metadata(),
+                },
+                tags: Vec::new(),
+                characters: Vec::new(),
+                document_class: None,
+                scenes: Vec::new(),
+                frame_labels: Vec::new(),
+                fonts: Vec::new(),
+                sounds: Vec::new(),
+                actions: Vec::new(),
+                abc: Vec::new(),
+            },
+            exports: Vec::new(),
+            symbols: Vec::new(),
+        };
+
+        // `SwfBuf` only holds the data after the header, so find its length by writing it back.
+        let mut header_data = Vec::new();
+        let uncompressed_header = Header {
+            compression: Compression::None,
+            ..header.swf_header().clone()
+        };
+        let data_offset =
+            match swf::write::write_swf_raw_tags(&uncompressed_header, &[], &mut header_data) {
+                Ok(()) => header_data.len(),
+                Err(_) => 0,
+            };
+        builder.report.tags = builder.read_tags(&swf_buf.data, data_offset, None);
+        builder.finish()
+    }
+}
+
+struct ReportBuilder {
+    version: u8,
+    encoding: &'static Encoding,
+    options: ReportOptions,
+    report: Report,
+    exports: Vec<(CharacterId, String)>,
+    symbols: Vec<(CharacterId, String)>,
+}
+
+impl ReportBuilder {
+    fn string(&self, s: &SwfStr) -> String {
+        s.to_string_lossy(self.encoding)
+    }
+
+    /// Reads a list of tags, keeping track of where each of them is.
+    fn read_tags(
+        &mut self,
+        data: &[u8],
+        data_offset: usize,
+        timeline: Option<CharacterId>,
+    ) -> Vec<TagReport> {
+        let mut tags = Vec::new();
+        let mut frame = 1;
+        let mut remaining = data;
+        while !remaining.is_empty() {
+            let offset = data_offset + (data.len() - remaining.len());
+            let mut header_reader = Reader::new(remaining, self.version);
+            let Ok((code, length)) = header_reader.read_tag_code_and_length() else {
+                break;
+            };
+            let header_length = remaining.len() - header_reader.get_ref().len();
+
+            let mut tag_report = TagReport {
+                offset,
+                code,
+                name: TagCode::format(code),
+                length,
+                character_id: None,
+                error: None,
+                tags: Vec::new(),
+            };
+
+            let Some((tag_data, rest)) = remaining.split_at_checked(header_length + length) else {
+                tag_report.error = Some("Tag is truncated".to_string());
+                tags.push(tag_report);
+                break;
+            };
+            remaining = rest;
+
+            match Reader::new(tag_data, self.version).read_tag() {
+                Ok(tag) => self.visit_tag(
+                    tag,
+                    &mut tag_report,
+                    &tag_data[header_length..],
+                    offset + header_length,
+                    timeline,
+                    &mut frame,
+                ),
+                Err(e) => tag_report.error = Some(e.to_string()),
+            }
+
+            tags.push(tag_report);
+            if code == TagCode::End as u16 {
+                break;
+            }
+        }
+        tags
+    }
+
+    fn visit_tag(
+        &mut self,
+        tag: Tag,
+        tag_report: &mut TagReport,
+        body: &[u8],
+        body_offset: usize,
+        timeline: Option<CharacterId>,
+        frame: &mut u32,
+    ) {
+        tag_report.character_id = defined_character(&tag);
+        if let Some(id) = tag_report.character_id {
+            self.report.characters.push(CharacterReport {
+                id,
+                kind: tag_report.name.clone(),
+                export_name: None,
+                class_name: None,
+            });
+        }
+
+        match tag {
+            Tag::ShowFrame => *frame += 1,
+            Tag::DefineSprite(sprite) => {
+                // The sprite's own tags follow its ID and frame count.
+                tag_report.tags = self.read_tags(&body[4..], body_offset + 4, Some(sprite.id));
+            }
+            Tag::FrameLabel(frame_label) => self.report.frame_labels.push(FrameLabelReport {
+                timeline,
+                frame: *frame,
+                label: self.string(frame_label.label),
+            }),
+            Tag::DefineSceneAndFrameLabelData(data) => {
+                for scene in data.scenes {
+                    self.report.scenes.push(FrameLabelReport {
+                        timeline,
+                        frame: scene.frame_num + 1,
+                        label: self.string(scene.label),
+                    });
+                }
+                for frame_label in data.frame_labels {
+                    self.report.frame_labels.push(FrameLabelReport {
+                        timeline,
+                        frame: frame_label.frame_num + 1,
+                        label: self.string(frame_label.label),
+                    });
+                }
+            }
+            Tag::ExportAssets(assets) => {
+                for asset in assets {
+                    self.exports.push((asset.id, self.string(asset.name)));
+                }
+            }
+            Tag::ImportAssets { imports, .. } => {
+                for asset in imports {
+                    self.report.characters.push(CharacterReport {
+                        id: asset.id,
+                        kind: tag_report.name.clone(),
+                        export_name: Some(self.string(asset.name)),
+                        class_name: None,
+                    });
+                }
+            }
+            Tag::SymbolClass(links) => {
+                for link in links {
+                    self.symbols.push((link.id, self.string(link.class_name)));
+                }
+            }
+            Tag::DefineFont(font) => self.report.fonts.push(FontReport {
+                id: font.id,
+                kind: tag_report.name.clone(),
+                name: None,
+                is_bold: false,
+                is_italic: false,
+                num_glyphs: Some(font.glyphs.len()),
+                has_layout: false,
+            }),
+            Tag::DefineFont2(font) => self.report.fonts.push(FontReport {
+                id: font.id,
+                kind: tag_report.name.clone(),
+                name: Some(self.string(font.name)),
+                is_bold: font.flags.contains(swf::FontFlag::IS_BOLD),
+                is_italic: font.flags.contains(swf::FontFlag::IS_ITALIC),
+                num_glyphs: Some(font.glyphs.len()),
+                has_layout: font.layout.is_some(),
+            }),
+            Tag::DefineFont4(font) => self.report.fonts.push(FontReport {
+                id: font.id,
+                kind: tag_report.name.clone(),
+                name: Some(self.string(font.name)),
+                is_bold: font.is_bold,
+                is_italic: font.is_italic,
+                num_glyphs: None,
+                has_layout: false,
+            }),
+            Tag::DefineFontInfo(info) => {
+                let name = self.string(info.name);
+                if let Some(font) = self.report.fonts.iter_mut().find(|f| f.id == info.id) {
+                    font.name = Some(name);
+                    font.is_bold = info.flags.contains(swf::FontInfoFlag::IS_BOLD);
+                    font.is_italic = info.flags.contains(swf::FontInfoFlag::IS_ITALIC);
+                }
+            }
+            Tag::DefineSound(sound) => self.report.sounds.push(SoundReport {
+                id: Some(sound.id),
+                timeline: None,
+                kind: tag_report.name.clone(),
+                format: (&sound.format).into(),
+                num_samples: sound.num_samples,
+            }),
+            Tag::SoundStreamHead(head) | Tag::SoundStreamHead2(head) => {
+                self.report.sounds.push(SoundReport {
+                    id: None,
+                    timeline,
+                    kind: tag_report.name.clone(),
+                    format: (&head.stream_format).into(),
+                    num_samples: head.num_samples_per_block.into(),
+                })
+            }
+            Tag::DoAction(action_data) => {
+                self.action_block(
+                    "DoAction".to_string(),
+                    timeline,
+                    Some(*frame),
+                    tag_report.offset,
+                    action_data,
+                );
+            }
+            Tag::DoInitAction { id, action_data } => {
+                self.action_block(
+                    format!("DoInitAction({id})"),
+                    timeline,
+                    None,
+                    tag_report.offset,
+                    action_data,
+                );
+            }
+            Tag::DefineButton(button) | Tag::DefineButton2(button) => {
+                for action in &button.actions {
+                    self.action_block(
+                        format!("{}({}) {:?}", tag_report.name, button.id, action.conditions),
+                        timeline,
+                        None,
+                        tag_report.offset,
+                        action.action_data,
+                    );
+                }
+            }
+            Tag::PlaceObject(place_object) => {
+                for clip_action in place_object
+                    .clip_actions
+                    .iter()
+                    .flat_map(|clip_actions| &clip_actions.records)
+                {
+                    self.action_block(
+                        format!(
+                            "{}(depth {}) {:?}",
+                            tag_report.name, place_object.depth, clip_action.events
+                        ),
+                        timeline,
+                        Some(*frame),
+                        tag_report.offset,
+                        clip_action.action_data,
+                    );
+                }
+            }
+            Tag::DoAbc(data) if self.options.abc => {
+                self.report
+                    .abc
+                    .push(AbcReport::new(data, None, tag_report.offset));
+            }
+            Tag::DoAbc2(do_abc) if self.options.abc => {
+                let name = self.string(do_abc.name);
+                self.report
+                    .abc
+                    .push(AbcReport::new(do_abc.data, Some(name), tag_report.offset));
+            }
+            _ => {}
+        }
+    }
+
+    fn action_block(
+        &mut self,
+        source: String,
+        timeline: Option<CharacterId>,
+        frame: Option<u32>,
+        tag_offset: usize,
+        data: &[u8],
+    ) {
+        if !self.options.actions {
+            return;
+        }
+
+        let (actions, error) = avm1::disassemble(data, self.version);
+        self.report.actions.push(ActionBlockReport {
+            source,
+            timeline,
+            frame,
+            tag_offset,
+            actions,
+            error,
+        });
+    }
+
+    fn finish(mut self) -> Report {
+        for (id, name) in self.exports {
+            if let Some(character) = self.report.characters.iter_mut().find(|c| c.id == id) {
+                character.export_name = Some(name);
+            }
+        }
+        for (id, class_name) in self.symbols {
+            if id == 0 {
+                self.report.document_class = Some(class_name);
+            } else if let Some(character) = self.report.characters.iter_mut().find(|c| c.id == id) {
+                character.class_name = Some(class_name);
+            }
+        }
+        self.report
+    }
+}
+
+/// Returns the ID of the character defined by a tag.
+fn defined_character(tag: &Tag) -> Option<CharacterId> {
+    Some(match tag {
+        Tag::DefineBinaryData(data) => data.id,
+        Tag::DefineBits { id, .. } | Tag::DefineBitsJpeg2 { id, .. } => *id,
+        Tag::DefineBitsJpeg3(jpeg) => jpeg.id,
+        Tag::DefineBitsLossless(bitmap) => bitmap.id,
+        Tag::DefineButton(button) | Tag::DefineButton2(button) => button.id,
+        Tag::DefineEditText(edit_text) => edit_text.id(),
+        Tag::DefineFont(font) => font.id,
+        Tag::DefineFont2(font) => font.id,
+        Tag::DefineFont4(font) => font.id,
+        Tag::DefineMorphShape(morph_shape) => morph_shape.id,
+        Tag::DefineShape(shape) => shape.id,
+        Tag::DefineSound(sound) => sound.id,
+        Tag::DefineSprite(sprite) => sprite.id,
+        Tag::DefineText(text) | Tag::DefineText2(text) => text.id,
+        Tag::DefineVideoStream(video) => video.id,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OPTIONS: ReportOptions = ReportOptions {
+        actions: true,
+        abc: true,
+    };
+
+    fn read_report(data: &[u8]) -> Report {
+        Report::new(&swf::decompress_swf(data).unwrap(), OPTIONS)
+    }
+
+    #[test]
+    fn tag_offsets() {
+        let report = read_report(include_bytes!("../../../swf/tests/swfs/DefineSprite.swf"));
+        let names: Vec<_> = report.tags.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "FileAttributes",
+                "SetBackgroundColor",
+                "DefineSceneAndFrameLabelData",
+                "DefineSprite",
+                "PlaceObject2",
+                "ShowFrame",
+                "End"
+            ]
+        );
+        // The first tag directly follows the 21-byte header.
+        assert_eq!(report.tags[0].offset, 21);
+
+        let sprite = &report.tags[3];
+        assert_eq!(sprite.character_id, Some(1));
+        assert_eq!(sprite.tags.len(), 6);
+        assert_eq!(sprite.tags[0].offset, sprite.offset + 2 + 4);
+        assert_eq!(report.characters.len(), 1);
+    }
+
+    #[test]
+    fn lzma_tag_offsets() {
+        let report = read_report(include_bytes!("../../../swf/tests/swfs/lzma.swf"));
+        assert_eq!(report.header.compression, "Lzma");
+        // The 400x400 stage needs one byte less than 550x400.
+        assert_eq!(report.tags[0].offset, 20);
+        assert_eq!(report.fonts.len(), 1);
+        assert_eq!(report.fonts[0].name.as_deref(), Some("Times New Roman"));
+    }
+
+    #[test]
+    fn frame_labels() {
+        let report = read_report(include_bytes!("../../../swf/tests/swfs/FrameLabel-CS6.swf"));
+        let labels: Vec<_> = report
+            .frame_labels
+            .iter()
+            .map(|l| (l.timeline, l.frame, l.label.as_str()))
+            .collect();
+        assert_eq!(labels, [(None, 1, "test"), (None, 2, "anchor_tag")]);
+    }
+
+    #[test]
+    fn exports_and_actions() {
+        let report = read_report(include_bytes!(
+            "../../../swf/tests/swfs/ExportAssets-CS6.swf"
+        ));
+        assert_eq!(report.characters[1].export_name.as_deref(), Some("Test💯"));
+
+        let actions =
+            read_report(include_bytes!("../../../swf/tests/swfs/DoAction-CS6.swf")).actions;
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].frame, Some(1));
+        assert_eq!(actions[0].actions[0].action, "Push \"Testing!\"");
+    }
+
+    #[test]
+    fn abc_classes() {
+        let report = read_report(include_bytes!(
+            "../../../swf/tests/swfs/Avm2DefaultValue.swf"
+        ));
+        assert_eq!(report.abc.len(), 1);
+        let abc = &report.abc[0];
+        assert_eq!(abc.error, None);
+        assert_eq!(abc.classes[0].name, "Avm2DefaultValue");
+        assert_eq!(abc.classes[0].super_name.as_deref(), Some("Object"));
+        assert_eq!(abc.classes[0].class_traits[0].name, "defaultTrue");
+        assert_eq!(abc.classes[0].class_traits[0].signature, "Boolean = true");
+    }
+}
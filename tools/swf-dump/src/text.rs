@@ -0,0 +1,216 @@
+//! Human-readable output
+
+use crate::avm2::TraitReport;
+use crate::report::{FrameLabelReport, Report, TagReport};
+use std::io::{self, Write};
+
+pub fn write_report(out: &mut impl Write, report: &Report) -> io::Result<()> {
+    let header = &report.header;
+    writeln!(out, "== Header ==")?;
+    writeln!(out, "Version: {}", header.version)?;
+    writeln!(out, "Compression: {}", header.compression)?;
+    writeln!(out, "Uncompressed length: {}", header.uncompressed_length)?;
+    writeln!(out, "Stage size: {}x{}", header.width, header.height)?;
+    writeln!(out, "Frame rate: {}", header.frame_rate)?;
+    writeln!(out, "Frames: {}", header.num_frames)?;
+    if let Some(background_color) = &header.background_color {
+        writeln!(out, "Background color: {background_color}")?;
+    }
+    writeln!(out, "ActionScript 3: {}", header.is_action_script_3)?;
+    writeln!(out, "Network sandbox: {}", header.use_network_sandbox)?;
+    if let Some(document_class) = &report.document_class {
+        writeln!(out, "Document class: {document_class}")?;
+    }
+
+    writeln!(out, "\n== Tags ==")?;
+    write_tags(out, &report.tags, 0)?;
+
+    if !report.characters.is_empty() {
+        writeln!(out, "\n== Characters ==")?;
+        for character in &report.characters {
+            write!(out, "{:>5} {}", character.id, character.kind)?;
+            if let Some(export_name) = &character.export_name {
+                write!(out, " export={export_name:?}")?;
+            }
+            if let Some(class_name) = &character.class_name {
+                write!(out, " class={class_name}")?;
+            }
+            writeln!(out)?;
+        }
+    }
+
+    if !report.scenes.is_empty() {
+        writeln!(out, "\n== Scenes ==")?;
+        write_labels(out, &report.scenes)?;
+    }
+
+    if !report.frame_labels.is_empty() {
+        writeln!(out, "\n== Frame labels ==")?;
+        write_labels(out, &report.frame_labels)?;
+    }
+
+    if !report.fonts.is_empty() {
+        writeln!(out, "\n== Fonts ==")?;
+        for font in &report.fonts {
+            write!(
+                out,
+                "{:>5} {} {:?}",
+                font.id,
+                font.kind,
+                font.name.as_deref().unwrap_or_default()
+            )?;
+            if font.is_bold {
+                write!(out, " bold")?;
+            }
+            if font.is_italic {
+                write!(out, " italic")?;
+            }
+            if let Some(num_glyphs) = font.num_glyphs {
+                write!(out, " glyphs={num_glyphs}")?;
+            }
+            if font.has_layout {
+                write!(out, " layout")?;
+            }
+            writeln!(out)?;
+        }
+    }
+
+    if !report.sounds.is_empty() {
+        writeln!(out, "\n== Sounds ==")?;
+        for sound in &report.sounds {
+            let format = &sound.format;
+            match (sound.id, sound.timeline) {
+                (Some(id), _) => write!(out, "{id:>5} ")?,
+                (None, Some(timeline)) => write!(out, "sprite {timeline} ")?,
+                (None, None) => write!(out, "root ")?,
+            }
+            writeln!(
+                out,
+                "{} {} {}Hz {} {} samples={}",
+                sound.kind,
+                format.compression,
+                format.sample_rate,
+                if format.is_stereo { "stereo" } else { "mono" },
+                if format.is_16_bit { "16-bit" } else { "8-bit" },
+                sound.num_samples
+            )?;
+        }
+    }
+
+    for block in &report.actions {
+        write!(out, "\n== {}", block.source)?;
+        if let Some(timeline) = block.timeline {
+            write!(out, " in sprite {timeline}")?;
+        }
+        if let Some(frame) = block.frame {
+            write!(out, " on frame {frame}")?;
+        }
+        writeln!(out, " (tag at {:#x}) ==", block.tag_offset)?;
+        for action in &block.actions {
+            writeln!(
+                out,
+                "{:>6}  {:indent$}{}",
+                action.offset,
+                "",
+                action.action,
+                indent = action.depth * 2
+            )?;
+        }
+        if let Some(error) = &block.error {
+            writeln!(out, "Error: {error}")?;
+        }
+    }
+
+    for abc in &report.abc {
+        write!(out, "\n== ABC")?;
+        if let Some(name) = &abc.name {
+            write!(out, " {name:?}")?;
+        }
+        writeln!(out, " (tag at {:#x}) ==", abc.tag_offset)?;
+        if let Some(error) = &abc.error {
+            writeln!(out, "Error: {error}")?;
+            continue;
+        }
+        writeln!(
+            out,
+            "Version {}.{}, {} methods, {} method bodies",
+            abc.major_version, abc.minor_version, abc.num_methods, abc.num_method_bodies
+        )?;
+        for class in &abc.classes {
+            write!(
+                out,
+                "\n{} {}",
+                if class.is_interface {
+                    "interface"
+                } else {
+                    "class"
+                },
+                class.name
+            )?;
+            if let Some(super_name) = &class.super_name {
+                write!(out, " extends {super_name}")?;
+            }
+            if !class.interfaces.is_empty() {
+                write!(out, " implements {}", class.interfaces.join(", "))?;
+            }
+            writeln!(out)?;
+            writeln!(out, "  constructor{}", class.constructor)?;
+            write_traits(out, &class.class_traits, "static ")?;
+            write_traits(out, &class.instance_traits, "")?;
+        }
+        if !abc.script_traits.is_empty() {
+            writeln!(out, "\nscript")?;
+            write_traits(out, &abc.script_traits, "")?;
+        }
+    }
+
+    Ok(())
+}
+
+fn write_tags(out: &mut impl Write, tags: &[TagReport], depth: usize) -> io::Result<()> {
+    for tag in tags {
+        write!(
+            out,
+            "{:#010x} {:indent$}{} ({} bytes)",
+            tag.offset,
+            "",
+            tag.name,
+            tag.length,
+            indent = depth * 2
+        )?;
+        if let Some(id) = tag.character_id {
+            write!(out, " id={id}")?;
+        }
+        if let Some(error) = &tag.error {
+            write!(out, " ERROR: {error}")?;
+        }
+        writeln!(out)?;
+        write_tags(out, &tag.tags, depth + 1)?;
+    }
+    Ok(())
+}
+
+fn write_labels(out: &mut impl Write, labels: &[FrameLabelReport]) -> io::Result<()> {
+    for label in labels {
+        match label.timeline {
+            Some(timeline) => write!(out, "sprite {timeline} ")?,
+            None => write!(out, "root ")?,
+        }
+        writeln!(out, "frame {}: {:?}", label.frame, label.label)?;
+    }
+    Ok(())
+}
+
+fn write_traits(out: &mut impl Write, traits: &[TraitReport], prefix: &str) -> io::Result<()> {
+    for t in traits {
+        match t.kind {
+            "slot" => writeln!(out, "  {prefix}var {}:{}", t.name, t.signature)?,
+            "const" => writeln!(out, "  {prefix}const {}:{}", t.name, t.signature)?,
+            "getter" => writeln!(out, "  {prefix}get {}{}", t.name, t.signature)?,
+            "setter" => writeln!(out, "  {prefix}set {}{}", t.name, t.signature)?,
+            "class" => writeln!(out, "  {prefix}class {}", t.signature)?,
+            _ => writeln!(out, "  {prefix}function {}{}", t.name, t.signature)?,
+        }
+    }
+    Ok(())
+}
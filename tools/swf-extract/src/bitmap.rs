@@ -0,0 +1,81 @@
+use anyhow::{Context, Result, anyhow};
+use image::{ImageFormat, RgbaImage};
+use ruffle_render::bitmap::Bitmap;
+use ruffle_render::utils::{
+    JpegTagFormat, decode_define_bits_jpeg, decode_define_bits_jpeg_dimensions,
+    decode_define_bits_lossless, determine_jpeg_tag_format, remove_invalid_jpeg_data,
+    unmultiply_alpha_rgba,
+};
+use std::io::Cursor;
+
+pub struct ExtractedBitmap {
+    pub extension: &'static str,
+    pub data: Vec<u8>,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// An extracted bitmap, as referenced by the bitmap fills of shapes.
+pub struct BitmapFile {
+    /// The path of the bitmap, relative to the shape directory.
+    pub path: String,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Extracts the image of a DefineBits or DefineBitsJPEG tag.
+///
+/// Images without separate alpha data are kept in their original format, so
+/// that JPEGs aren't recompressed. Otherwise, the alpha is merged into a PNG.
+pub fn extract_jpeg(data: &[u8], alpha_data: Option<&[u8]>) -> Result<ExtractedBitmap> {
+    let format = determine_jpeg_tag_format(data);
+    let extension = match (format, alpha_data) {
+        (JpegTagFormat::Jpeg, None) => "jpg",
+        (JpegTagFormat::Png, _) => "png",
+        (JpegTagFormat::Gif, _) => "gif",
+        _ => {
+            let bitmap = decode_define_bits_jpeg(data, alpha_data)
+                .map_err(|e| anyhow!("Couldn't decode image: {e}"))?;
+            return encode_png(bitmap);
+        }
+    };
+
+    let (width, height) = decode_define_bits_jpeg_dimensions(data)
+        .map_err(|e| anyhow!("Couldn't decode image: {e}"))?;
+    let data = if format == JpegTagFormat::Jpeg {
+        remove_invalid_jpeg_data(data).into_owned()
+    } else {
+        data.to_vec()
+    };
+    Ok(ExtractedBitmap {
+        extension,
+        data,
+        width,
+        height,
+    })
+}
+
+pub fn extract_lossless(bits: &swf::DefineBitsLossless) -> Result<ExtractedBitmap> {
+    let bitmap =
+        decode_define_bits_lossless(bits).map_err(|e| anyhow!("Couldn't decode image: {e}"))?;
+    encode_png(bitmap)
+}
+
+fn encode_png(bitmap: Bitmap) -> Result<ExtractedBitmap> {
+    let bitmap = bitmap.to_rgba();
+    let (width, height) = (bitmap.width(), bitmap.height());
+    let mut rgba = bitmap.data().to_vec();
+    unmultiply_alpha_rgba(&mut rgba);
+
+    let image = RgbaImage::from_raw(width, height, rgba).context("Invalid image size")?;
+    let mut data = Cursor::new(Vec::new());
+    image
+        .write_to(&mut data, ImageFormat::Png)
+        .context("Couldn't encode PNG")?;
+    Ok(ExtractedBitmap {
+        extension: "png",
+        data: data.into_inner(),
+        width,
+        height,
+    })
+}
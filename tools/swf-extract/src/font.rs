@@ -0,0 +1,766 @@
+//! Conversion of embedded fonts into TrueType fonts.
+//!
+//! DefineFont4 tags already embed an OpenType font, but the glyphs of older
+//! font tags are SWF shapes. Those are made of quadratic curves like TrueType
+//! outlines, so they can be converted without loss of precision.
+
+use anyhow::{Result, bail};
+use swf::{
+    Encoding, Font, Font4, FontFlag, FontInfo, FontInfoFlag, FontLayout, FontV1, ShapeRecord,
+};
+
+/// The size of the em square of the TrueType fonts we create.
+///
+/// DefineFont and DefineFont2 glyphs use the same size, while DefineFont3
+/// glyphs are at 20 times the scale.
+const UNITS_PER_EM: i32 = 1024;
+
+/// Extracts a DefineFont2 or DefineFont3 tag as a TrueType font.
+pub fn extract_font(font: &Font, encoding: &'static Encoding) -> Result<(&'static str, Vec<u8>)> {
+    let scale = if font.version >= 3 { 20 } else { 1 };
+    let glyphs = font
+        .glyphs
+        .iter()
+        .map(|glyph| GlyphOutline {
+            code: glyph.code,
+            advance: font
+                .layout
+                .as_ref()
+                .map(|_| i32::from(glyph.advance) / scale),
+            records: &glyph.shape_records,
+        })
+        .collect();
+    let outlines = FontOutlines {
+        name: font.name.to_string_lossy(encoding),
+        is_bold: font.flags.contains(FontFlag::IS_BOLD),
+        is_italic: font.flags.contains(FontFlag::IS_ITALIC),
+        scale,
+        layout: font.layout.as_ref(),
+        glyphs,
+    };
+    Ok(("ttf", outlines.to_truetype()?))
+}
+
+/// Extracts a DefineFont tag as a TrueType font.
+///
+/// The codes of its glyphs come from a DefineFontInfo tag.
+pub fn extract_font_v1(
+    font: &FontV1,
+    font_info: Option<&FontInfo>,
+    encoding: &'static Encoding,
+) -> Result<(&'static str, Vec<u8>)> {
+    let Some(font_info) = font_info else {
+        bail!("Font has no DefineFontInfo tag with the codes of its glyphs");
+    };
+    let glyphs = font
+        .glyphs
+        .iter()
+        .zip(&font_info.code_table)
+        .map(|(records, &code)| GlyphOutline {
+            code,
+            advance: None,
+            records,
+        })
+        .collect();
+    let outlines = FontOutlines {
+        name: font_info.name.to_string_lossy(encoding),
+        is_bold: font_info.flags.contains(FontInfoFlag::IS_BOLD),
+        is_italic: font_info.flags.contains(FontInfoFlag::IS_ITALIC),
+        scale: 1,
+        layout: None,
+        glyphs,
+    };
+    Ok(("ttf", outlines.to_truetype()?))
+}
+
+/// Extracts the OpenType font embedded in a DefineFont4 tag.
+pub fn extract_font4(font: &Font4) -> Result<(&'static str, Vec<u8>)> {
+    let Some(data) = font.data else {
+        bail!("Font has no embedded font data");
+    };
+    // DefineFont4 fonts use CFF outlines, but check in case of TrueType outlines.
+    let extension = if data.starts_with(b"OTTO") {
+        "otf"
+    } else {
+        "ttf"
+    };
+    Ok((extension, data.to_vec()))
+}
+
+struct FontOutlines<'a> {
+    name: String,
+    is_bold: bool,
+    is_italic: bool,
+
+    /// The number of glyph units per unit of the TrueType font.
+    scale: i32,
+
+    layout: Option<&'a FontLayout>,
+    glyphs: Vec<GlyphOutline<'a>>,
+}
+
+struct GlyphOutline<'a> {
+    code: u16,
+
+    /// The advance of the glyph in font units, if the font has a layout.
+    advance: Option<i32>,
+
+    records: &'a [ShapeRecord],
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct GlyphPoint {
+    x: i16,
+    y: i16,
+    on_curve: bool,
+}
+
+/// A converted glyph, in TrueType coordinates.
+struct TrueTypeGlyph {
+    code: Option<u16>,
+    contours: Vec<Vec<GlyphPoint>>,
+    advance: u16,
+    bounds: Bounds,
+}
+
+#[derive(Clone, Copy, Default)]
+struct Bounds {
+    x_min: i16,
+    y_min: i16,
+    x_max: i16,
+    y_max: i16,
+}
+
+impl Bounds {
+    fn of_points<'a>(points: impl IntoIterator<Item = &'a GlyphPoint>) -> Self {
+        let mut points = points.into_iter();
+        let Some(first) = points.next() else {
+            return Self::default();
+        };
+        points.fold(
+            Bounds {
+                x_min: first.x,
+                y_min: first.y,
+                x_max: first.x,
+                y_max: first.y,
+            },
+            |bounds, point| Bounds {
+                x_min: bounds.x_min.min(point.x),
+                y_min: bounds.y_min.min(point.y),
+                x_max: bounds.x_max.max(point.x),
+                y_max: bounds.y_max.max(point.y),
+            },
+        )
+    }
+
+    fn union(self, other: Bounds) -> Bounds {
+        Bounds {
+            x_min: self.x_min.min(other.x_min),
+            y_min: self.y_min.min(other.y_min),
+            x_max: self.x_max.max(other.x_max),
+            y_max: self.y_max.max(other.y_max),
+        }
+    }
+}
+
+impl FontOutlines<'_> {
+    fn to_truetype(&self) -> Result<Vec<u8>> {
+        if self.glyphs.is_empty() {
+            bail!("Font has no embedded glyphs");
+        }
+        if self.glyphs.len() >= u16::MAX as usize {
+            bail!("Font has too many glyphs");
+        }
+
+        // Glyph 0 is the `.notdef` glyph, which is drawn for missing characters.
+        let mut glyphs = vec![TrueTypeGlyph {
+            code: None,
+            contours: Vec::new(),
+            advance: (UNITS_PER_EM / 2) as u16,
+            bounds: Bounds::default(),
+        }];
+        for glyph in &self.glyphs {
+            let contours = glyph_contours(glyph.records, self.scale);
+            let bounds = Bounds::of_points(contours.iter().flatten());
+            let advance = glyph
+                .advance
+                .unwrap_or_else(|| i32::from(bounds.x_max) + UNITS_PER_EM / 16);
+            glyphs.push(TrueTypeGlyph {
+                code: Some(glyph.code),
+                contours,
+                advance: advance.clamp(0, u16::MAX.into()) as u16,
+                bounds,
+            });
+        }
+
+        let bounds = glyphs
+            .iter()
+            .filter(|glyph| !glyph.contours.is_empty())
+            .map(|glyph| glyph.bounds)
+            .reduce(Bounds::union)
+            .unwrap_or_default();
+        let (ascent, descent, line_gap) = match self.layout {
+            Some(layout) => (
+                clamp_i16(i32::from(layout.ascent) / self.scale),
+                clamp_i16(i32::from(layout.descent) / self.scale),
+                clamp_i16(i32::from(layout.leading) / self.scale),
+            ),
+            None => (bounds.y_max.max(0), -bounds.y_min.min(0), 0),
+        };
+
+        let (glyf, loca) = write_glyf(&glyphs);
+        let tables = [
+            (*b"OS/2", self.write_os2(&glyphs, ascent, descent, line_gap)),
+            (*b"cmap", write_cmap(&glyphs)),
+            (*b"glyf", glyf),
+            (*b"head", self.write_head(bounds)),
+            (*b"hhea", write_hhea(&glyphs, ascent, descent, line_gap)),
+            (*b"hmtx", write_hmtx(&glyphs)),
+            (*b"loca", loca),
+            (*b"maxp", write_maxp(&glyphs)),
+            (*b"name", self.write_name()),
+            (*b"post", write_post()),
+        ];
+        Ok(write_font_file(&tables))
+    }
+
+    fn style_name(&self) -> &'static str {
+        match (self.is_bold, self.is_italic) {
+            (false, false) => "Regular",
+            (true, false) => "Bold",
+            (false, true) => "Italic",
+            (true, true) => "Bold Italic",
+        }
+    }
+
+    fn write_head(&self, bounds: Bounds) -> Vec<u8> {
+        let mut head = Vec::with_capacity(54);
+        put_u32(&mut head, 0x0001_0000); // version
+        put_u32(&mut head, 0x0001_0000); // fontRevision
+        put_u32(&mut head, 0); // checksumAdjustment, filled in later
+        put_u32(&mut head, 0x5F0F_3CF5); // magicNumber
+        put_u16(&mut head, 0b1011); // flags: baseline at y=0, lsb at x=0, integer scaling
+        put_u16(&mut head, UNITS_PER_EM as u16);
+        head.extend_from_slice(&[0; 16]); // created, modified
+        put_i16(&mut head, bounds.x_min);
+        put_i16(&mut head, bounds.y_min);
+        put_i16(&mut head, bounds.x_max);
+        put_i16(&mut head, bounds.y_max);
+        put_u16(
+            &mut head,
+            u16::from(self.is_bold) | (u16::from(self.is_italic) << 1),
+        ); // macStyle
+        put_u16(&mut head, 8); // lowestRecPPEM
+        put_i16(&mut head, 2); // fontDirectionHint
+        put_i16(&mut head, 1); // indexToLocFormat: 32-bit offsets
+        put_i16(&mut head, 0); // glyphDataFormat
+        head
+    }
+
+    fn write_os2(
+        &self,
+        glyphs: &[TrueTypeGlyph],
+        ascent: i16,
+        descent: i16,
+        line_gap: i16,
+    ) -> Vec<u8> {
+        let average_width =
+            glyphs.iter().map(|g| u32::from(g.advance)).sum::<u32>() / glyphs.len() as u32;
+        let codes = glyphs.iter().filter_map(|glyph| glyph.code);
+        let first_char = codes.clone().min().unwrap_or_default();
+        let last_char = codes.max().unwrap_or_default();
+        let em = UNITS_PER_EM as i16;
+
+        let mut os2 = Vec::with_capacity(96);
+        put_u16(&mut os2, 4); // version
+        put_i16(&mut os2, average_width as i16);
+        put_u16(&mut os2, if self.is_bold { 700 } else { 400 }); // usWeightClass
+        put_u16(&mut os2, 5); // usWidthClass: normal
+        put_u16(&mut os2, 0); // fsType: installable
+        for value in [
+            em * 2 / 3, // ySubscriptXSize
+            em * 2 / 3, // ySubscriptYSize
+            0,          // ySubscriptXOffset
+            em / 7,     // ySubscriptYOffset
+            em * 2 / 3, // ySuperscriptXSize
+            em * 2 / 3, // ySuperscriptYSize
+            0,          // ySuperscriptXOffset
+            em * 2 / 5, // ySuperscriptYOffset
+            em / 20,    // yStrikeoutSize
+            em / 4,     // yStrikeoutPosition
+            0,          // sFamilyClass
+        ] {
+            put_i16(&mut os2, value);
+        }
+        os2.extend_from_slice(&[0; 10]); // panose
+        os2.extend_from_slice(&[0; 16]); // ulUnicodeRange
+        os2.extend_from_slice(b"RUFL"); // achVendID
+        let fs_selection = match (self.is_bold, self.is_italic) {
+            (false, false) => 1 << 6,
+            (bold, italic) => u16::from(italic) | (u16::from(bold) << 5),
+        };
+        put_u16(&mut os2, fs_selection);
+        put_u16(&mut os2, first_char);
+        put_u16(&mut os2, last_char);
+        put_i16(&mut os2, ascent); // sTypoAscender
+        put_i16(&mut os2, -descent); // sTypoDescender
+        put_i16(&mut os2, line_gap); // sTypoLineGap
+        put_u16(&mut os2, ascent.max(0) as u16); // usWinAscent
+        put_u16(&mut os2, descent.max(0) as u16); // usWinDescent
+        put_u32(&mut os2, 1); // ulCodePageRange1: Latin 1
+        put_u32(&mut os2, 0); // ulCodePageRange2
+        put_i16(&mut os2, 0); // sxHeight
+        put_i16(&mut os2, 0); // sCapHeight
+        put_u16(&mut os2, 0); // usDefaultChar
+        put_u16(&mut os2, 0x20); // usBreakChar
+        put_u16(&mut os2, 1); // usMaxContext
+        os2
+    }
+
+    fn write_name(&self) -> Vec<u8> {
+        let family = self.name.trim_end_matches('\0');
+        let family = if family.is_empty() {
+            "Untitled"
+        } else {
+            family
+        };
+        let style = self.style_name();
+        let full_name = if style == "Regular" {
+            family.to_string()
+        } else {
+            format!("{family} {style}")
+        };
+        let postscript_name: String = format!("{family}-{}", style.replace(' ', ""))
+            .chars()
+            .filter(|c| c.is_ascii_graphic() && !"[](){}<>/%".contains(*c))
+            .collect();
+        let records = [
+            (1, family),
+            (2, style),
+            (3, full_name.as_str()),
+            (4, full_name.as_str()),
+            (6, postscript_name.as_str()),
+        ];
+
+        let mut strings = Vec::new();
+        let mut name = Vec::new();
+        put_u16(&mut name, 0); // format
+        put_u16(&mut name, records.len() as u16);
+        put_u16(&mut name, 6 + 12 * records.len() as u16); // stringOffset
+        for (name_id, value) in records {
+            let offset = strings.len();
+            for unit in value.encode_utf16() {
+                put_u16(&mut strings, unit);
+            }
+            put_u16(&mut name, 3); // platformID: Windows
+            put_u16(&mut name, 1); // encodingID: Unicode BMP
+            put_u16(&mut name, 0x0409); // languageID: English
+            put_u16(&mut name, name_id);
+            put_u16(&mut name, (strings.len() - offset) as u16);
+            put_u16(&mut name, offset as u16);
+        }
+        name.extend_from_slice(&strings);
+        name
+    }
+}
+
+/// Converts the shape records of a glyph into TrueType contours.
+///
+/// SWF glyphs have the Y axis pointing down, while TrueType glyphs have it pointing up.
+fn glyph_contours(records: &[ShapeRecord], scale: i32) -> Vec<Vec<GlyphPoint>> {
+    let point = |x: i32, y: i32, on_curve| GlyphPoint {
+        x: clamp_i16((f64::from(x) / f64::from(scale)).round() as i32),
+        y: clamp_i16((-f64::from(y) / f64::from(scale)).round() as i32),
+        on_curve,
+    };
+
+    let mut contours = Vec::new();
+    let mut contour: Vec<GlyphPoint> = Vec::new();
+    let (mut x, mut y) = (0, 0);
+    for record in records {
+        match record {
+            ShapeRecord::StyleChange(style_change) => {
+                if let Some(move_to) = style_change.move_to {
+                    contours.extend(finish_contour(std::mem::take(&mut contour)));
+                    x = move_to.x.get();
+                    y = move_to.y.get();
+                }
+            }
+            ShapeRecord::StraightEdge { delta } => {
+                if contour.is_empty() {
+                    contour.push(point(x, y, true));
+                }
+                x += delta.dx.get();
+                y += delta.dy.get();
+                contour.push(point(x, y, true));
+            }
+            ShapeRecord::CurvedEdge {
+                control_delta,
+                anchor_delta,
+            } => {
+                if contour.is_empty() {
+                    contour.push(point(x, y, true));
+                }
+                x += control_delta.dx.get();
+                y += control_delta.dy.get();
+                contour.push(point(x, y, false));
+                x += anchor_delta.dx.get();
+                y += anchor_delta.dy.get();
+                contour.push(point(x, y, true));
+            }
+        }
+    }
+    contours.extend(finish_contour(contour));
+    contours
+}
+
+/// TrueType contours are implicitly closed, so the closing point is dropped.
+fn finish_contour(mut contour: Vec<GlyphPoint>) -> Option<Vec<GlyphPoint>> {
+    if contour.len() > 1 && contour.first() == contour.last() {
+        contour.pop();
+    }
+    (contour.len() > 1).then_some(contour)
+}
+
+fn write_glyf(glyphs: &[TrueTypeGlyph]) -> (Vec<u8>, Vec<u8>) {
+    let mut glyf = Vec::new();
+    let mut loca = Vec::new();
+    for glyph in glyphs {
+        put_u32(&mut loca, glyf.len() as u32);
+        if glyph.contours.is_empty() {
+            continue;
+        }
+
+        put_i16(&mut glyf, glyph.contours.len() as i16);
+        put_i16(&mut glyf, glyph.bounds.x_min);
+        put_i16(&mut glyf, glyph.bounds.y_min);
+        put_i16(&mut glyf, glyph.bounds.x_max);
+        put_i16(&mut glyf, glyph.bounds.y_max);
+        let mut end_point = 0;
+        for contour in &glyph.contours {
+            end_point += contour.len();
+            put_u16(&mut glyf, (end_point - 1) as u16);
+        }
+        put_u16(&mut glyf, 0); // instructionLength
+
+        let points = glyph.contours.iter().flatten();
+        // Coordinates are always written as 16-bit deltas.
+        glyf.extend(points.clone().map(|point| u8::from(point.on_curve)));
+        let mut previous = 0;
+        for point in points.clone() {
+            put_i16(&mut glyf, point.x.wrapping_sub(previous));
+            previous = point.x;
+        }
+        let mut previous = 0;
+        for point in points {
+            put_i16(&mut glyf, point.y.wrapping_sub(previous));
+            previous = point.y;
+        }
+        pad(&mut glyf);
+    }
+    put_u32(&mut loca, glyf.len() as u32);
+    (glyf, loca)
+}
+
+fn write_cmap(glyphs: &[TrueTypeGlyph]) -> Vec<u8> {
+    let mut mappings: Vec<(u16, u16)> = glyphs
+        .iter()
+        .enumerate()
+        .filter_map(|(index, glyph)| Some((glyph.code?, index as u16)))
+        .filter(|(code, _)| *code != 0xFFFF)
+        .collect();
+    // The first glyph for a code wins.
+    mappings.sort_by_key(|(code, _)| *code);
+    mappings.dedup_by_key(|(code, _)| *code);
+
+    // Use one segment per character, followed by the required final segment.
+    let segments: Vec<(u16, u16)> = mappings
+        .into_iter()
+        .chain(std::iter::once((0xFFFF, 0)))
+        .collect();
+    let seg_count = segments.len() as u16;
+    let search_range = 2 * (1 << seg_count.ilog2());
+    let entry_selector = seg_count.ilog2() as u16;
+
+    let mut cmap = Vec::new();
+    put_u16(&mut cmap, 0); // version
+    put_u16(&mut cmap, 1); // numTables
+    put_u16(&mut cmap, 3); // platformID: Windows
+    put_u16(&mut cmap, 1); // encodingID: Unicode BMP
+    put_u32(&mut cmap, 12); // offset
+
+    put_u16(&mut cmap, 4); // format
+    put_u16(&mut cmap, 16 + 8 * seg_count); // length
+    put_u16(&mut cmap, 0); // language
+    put_u16(&mut cmap, seg_count * 2);
+    put_u16(&mut cmap, search_range);
+    put_u16(&mut cmap, entry_selector);
+    put_u16(&mut cmap, seg_count * 2 - search_range); // rangeShift
+    for (code, _) in &segments {
+        put_u16(&mut cmap, *code); // endCode
+    }
+    put_u16(&mut cmap, 0); // reservedPad
+    for (code, _) in &segments {
+        put_u16(&mut cmap, *code); // startCode
+    }
+    for (code, glyph) in &segments {
+        // The final segment maps to glyph 0.
+        let delta = if *code == 0xFFFF {
+            1
+        } else {
+            glyph.wrapping_sub(*code)
+        };
+        put_u16(&mut cmap, delta); // idDelta
+    }
+    for _ in &segments {
+        put_u16(&mut cmap, 0); // idRangeOffset
+    }
+    cmap
+}
+
+fn write_hhea(glyphs: &[TrueTypeGlyph], ascent: i16, descent: i16, line_gap: i16) -> Vec<u8> {
+    let max_advance = glyphs.iter().map(|g| g.advance).max().unwrap_or_default();
+    let outlined = || glyphs.iter().filter(|glyph| !glyph.contours.is_empty());
+    let min_lsb = outlined().map(|g| g.bounds.x_min).min().unwrap_or_default();
+    let min_rsb = outlined()
+        .map(|g| clamp_i16(i32::from(g.advance) - i32::from(g.bounds.x_max)))
+        .min()
+        .unwrap_or_default();
+    let max_extent = outlined().map(|g| g.bounds.x_max).max().unwrap_or_default();
+
+    let mut hhea = Vec::with_capacity(36);
+    put_u32(&mut hhea, 0x0001_0000); // version
+    put_i16(&mut hhea, ascent);
+    put_i16(&mut hhea, -descent);
+    put_i16(&mut hhea, line_gap);
+    put_u16(&mut hhea, max_advance);
+    put_i16(&mut hhea, min_lsb);
+    put_i16(&mut hhea, min_rsb);
+    put_i16(&mut hhea, max_extent);
+    put_i16(&mut hhea, 1); // caretSlopeRise
+    put_i16(&mut hhea, 0); // caretSlopeRun
+    put_i16(&mut hhea, 0); // caretOffset
+    hhea.extend_from_slice(&[0; 8]); // reserved
+    put_i16(&mut hhea, 0); // metricDataFormat
+    put_u16(&mut hhea, glyphs.len() as u16); // numberOfHMetrics
+    hhea
+}
+
+fn write_hmtx(glyphs: &[TrueTypeGlyph]) -> Vec<u8> {
+    let mut hmtx = Vec::with_capacity(glyphs.len() * 4);
+    for glyph in glyphs {
+        put_u16(&mut hmtx, glyph.advance);
+        put_i16(&mut hmtx, glyph.bounds.x_min);
+    }
+    hmtx
+}
+
+fn write_maxp(glyphs: &[TrueTypeGlyph]) -> Vec<u8> {
+    let max_points = glyphs
+        .iter()
+        .map(|g| g.contours.iter().map(Vec::len).sum::<usize>())
+        .max()
+        .unwrap_or_default();
+    let max_contours = glyphs.iter().map(|g| g.contours.len()).max().unwrap_or(0);
+
+    let mut maxp = Vec::with_capacity(32);
+    put_u32(&mut maxp, 0x0001_0000); // version
+    put_u16(&mut maxp, glyphs.len() as u16);
+    put_u16(&mut maxp, max_points.min(u16::MAX.into()) as u16);
+    put_u16(&mut maxp, max_contours.min(u16::MAX.into()) as u16);
+    put_u16(&mut maxp, 0); // maxCompositePoints
+    put_u16(&mut maxp, 0); // maxCompositeContours
+    put_u16(&mut maxp, 2); // maxZones
+    maxp.extend_from_slice(&[0; 16]); // Limits for hinting instructions, which we don't use.
+    maxp
+}
+
+fn write_post() -> Vec<u8> {
+    let mut post = Vec::with_capacity(32);
+    put_u32(&mut post, 0x0003_0000); // version: no glyph names
+    put_u32(&mut post, 0); // italicAngle
+    put_i16(&mut post, -(UNITS_PER_EM as i16) / 10); // underlinePosition
+    put_i16(&mut post, UNITS_PER_EM as i16 / 20); // underlineThickness
+    post.extend_from_slice(&[0; 20]); // isFixedPitch and memory usage
+    post
+}
+
+/// Writes the table directory followed by the tables, which must be sorted by tag.
+fn write_font_file(tables: &[([u8; 4], Vec<u8>)]) -> Vec<u8> {
+    let num_tables = tables.len() as u16;
+    let search_range = 16 * (1 << num_tables.ilog2());
+
+    let mut font = Vec::new();
+    put_u32(&mut font, 0x0001_0000); // sfntVersion: TrueType outlines
+    put_u16(&mut font, num_tables);
+    put_u16(&mut font, search_range);
+    put_u16(&mut font, num_tables.ilog2() as u16); // entrySelector
+    put_u16(&mut font, num_tables * 16 - search_range); // rangeShift
+
+    let mut offset = font.len() + tables.len() * 16;
+    for (tag, data) in tables {
+        font.extend_from_slice(tag);
+        put_u32(&mut font, checksum(data));
+        put_u32(&mut font, offset as u32);
+        put_u32(&mut font, data.len() as u32);
+        offset += data.len().next_multiple_of(4);
+    }
+
+    let mut head_offset = 0;
+    for (tag, data) in tables {
+        if tag == b"head" {
+            head_offset = font.len();
+        }
+        font.extend_from_slice(data);
+        pad(&mut font);
+    }
+
+    let adjustment = 0xB1B0_AFBAu32.wrapping_sub(checksum(&font));
+    font[head_offset + 8..head_offset + 12].copy_from_slice(&adjustment.to_be_bytes());
+    font
+}
+
+fn checksum(data: &[u8]) -> u32 {
+    let (words, rest) = data.as_chunks::<4>();
+    let mut last = [0; 4];
+    last[..rest.len()].copy_from_slice(rest);
+    words
+        .iter()
+        .chain(std::iter::once(&last))
+        .fold(0u32, |sum, word| {
+            sum.wrapping_add(u32::from_be_bytes(*word))
+        })
+}
+
+fn pad(data: &mut Vec<u8>) {
+    data.resize(data.len().next_multiple_of(4), 0);
+}
+
+fn clamp_i16(n: i32) -> i16 {
+    n.clamp(i16::MIN.into(), i16::MAX.into()) as i16
+}
+
+fn put_u16(out: &mut Vec<u8>, n: u16) {
+    out.extend_from_slice(&n.to_be_bytes());
+}
+
+fn put_i16(out: &mut Vec<u8>, n: i16) {
+    out.extend_from_slice(&n.to_be_bytes());
+}
+
+fn put_u32(out: &mut Vec<u8>, n: u32) {
+    out.extend_from_slice(&n.to_be_bytes());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use swf::{Point, PointDelta, StyleChangeData, Twips};
+
+    fn move_to(x: i32, y: i32) -> ShapeRecord {
+        ShapeRecord::StyleChange(Box::new(StyleChangeData {
+            move_to: Some(Point::new(Twips::new(x), Twips::new(y))),
+            fill_style_0: None,
+            fill_style_1: Some(1),
+            line_style: None,
+            new_styles: None,
+        }))
+    }
+
+    fn line_to(dx: i32, dy: i32) -> ShapeRecord {
+        ShapeRecord::StraightEdge {
+            delta: PointDelta::new(Twips::new(dx), Twips::new(dy)),
+        }
+    }
+
+    #[test]
+    fn convert_contours() {
+        let records = [
+            move_to(0, 0),
+            line_to(200, 0),
+            ShapeRecord::CurvedEdge {
+                control_delta: PointDelta::new(Twips::new(0), Twips::new(-200)),
+                anchor_delta: PointDelta::new(Twips::new(-200), Twips::new(0)),
+            },
+            line_to(0, 200),
+            move_to(40, -40),
+            line_to(20, 0),
+        ];
+        let point = |x, y, on_curve| GlyphPoint { x, y, on_curve };
+        assert_eq!(
+            glyph_contours(&records, 20),
+            [
+                vec![
+                    point(0, 0, true),
+                    point(10, 0, true),
+                    point(10, 10, false),
+                    point(0, 10, true),
+                ],
+                vec![point(2, 2, true), point(3, 2, true)],
+            ]
+        );
+    }
+
+    #[test]
+    fn font_file() {
+        let glyph = [
+            move_to(0, 0),
+            line_to(500, 0),
+            line_to(0, -700),
+            line_to(-500, 0),
+            line_to(0, 700),
+        ];
+        let outlines = FontOutlines {
+            name: "Test\0".to_string(),
+            is_bold: true,
+            is_italic: false,
+            scale: 1,
+            layout: None,
+            glyphs: vec![
+                GlyphOutline {
+                    code: 'A' as u16,
+                    advance: Some(600),
+                    records: &glyph,
+                },
+                GlyphOutline {
+                    code: ' ' as u16,
+                    advance: Some(300),
+                    records: &[],
+                },
+            ],
+        };
+        let font = outlines.to_truetype().unwrap();
+
+        // The whole font sums up to the magic number thanks to the checksum adjustment.
+        assert_eq!(checksum(&font), 0xB1B0_AFBA);
+        assert_eq!(&font[..6], &[0, 1, 0, 0, 0, 10]);
+
+        let table = |tag: &[u8; 4]| {
+            let entry = font[12..12 + 160]
+                .chunks(16)
+                .find(|entry| &entry[..4] == tag)
+                .unwrap();
+            let offset = u32::from_be_bytes(entry[8..12].try_into().unwrap()) as usize;
+            let length = u32::from_be_bytes(entry[12..16].try_into().unwrap()) as usize;
+            &font[offset..offset + length]
+        };
+        // Glyph 1 is a 500x700 rectangle, and glyph 2 is empty.
+        let loca = table(b"loca");
+        assert_eq!(loca.len(), 16);
+        assert_eq!(&loca[8..12], &loca[12..16]);
+        assert_eq!(&table(b"glyf")[..10], &[0, 1, 0, 0, 0, 0, 1, 244, 2, 188]);
+        assert_eq!(&table(b"hmtx")[4..], &[2, 88, 0, 0, 1, 44, 0, 0]);
+        // The cmap maps ' ' to glyph 2 and 'A' to glyph 1.
+        let cmap = table(b"cmap");
+        assert_eq!(&cmap[12 + 14..12 + 20], &[0, 32, 0, 65, 255, 255]);
+        assert_eq!(
+            &cmap[12 + 28..12 + 34],
+            &(-30i16)
+                .to_be_bytes()
+                .into_iter()
+                .chain((-64i16).to_be_bytes())
+                .chain(1u16.to_be_bytes())
+                .collect::<Vec<_>>()[..]
+        );
+    }
+}
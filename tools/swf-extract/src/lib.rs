@@ -0,0 +1,497 @@
+//! Extracts the assets embedded in an SWF file into standard file formats.
+//!
+//! Every extractable character becomes one file: bitmaps become PNG or JPEG
+//! images, sounds become WAV or MP3 files, shapes become SVG images, fonts
+//! become TrueType or OpenType fonts, videos become FLV files and text
+//! becomes plain text or HTML. Sound streams are extracted per timeline.
+//!
+//! Files are named after the class or export name of their character, if it
+//! has one, and after the character ID otherwise.
+
+mod bitmap;
+mod font;
+mod shape;
+mod sound;
+mod text;
+mod video;
+
+use crate::bitmap::BitmapFile;
+use crate::sound::SoundStream;
+use crate::video::VideoStream;
+use anyhow::Result;
+use ruffle_render::utils::glue_tables_to_jpeg;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use swf::{CharacterId, Encoding, FontInfo, Swf, SwfStr, Tag};
+
+/// The kind of an extracted asset.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AssetKind {
+    Image,
+    Sound,
+    Shape,
+    Font,
+    Video,
+    Text,
+    BinaryData,
+}
+
+impl AssetKind {
+    /// The directory that assets of this kind are written to.
+    pub fn directory(self) -> &'static str {
+        match self {
+            AssetKind::Image => "images",
+            AssetKind::Sound => "sounds",
+            AssetKind::Shape => "shapes",
+            AssetKind::Font => "fonts",
+            AssetKind::Video => "videos",
+            AssetKind::Text => "texts",
+            AssetKind::BinaryData => "binary",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Asset {
+    /// The character this asset was extracted from.
+    ///
+    /// For a sound stream, this is the sprite containing it, or `None` for
+    /// the main timeline.
+    pub id: Option<CharacterId>,
+
+    pub kind: AssetKind,
+
+    /// The file name of this asset, which is unique among assets of the same kind.
+    pub file_name: String,
+
+    pub data: Vec<u8>,
+}
+
+/// A character that couldn't be extracted.
+#[derive(Debug)]
+pub struct ExtractError {
+    pub id: Option<CharacterId>,
+    pub message: String,
+}
+
+impl fmt::Display for ExtractError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.id {
+            Some(id) => write!(f, "Character {id}: {}", self.message),
+            None => write!(f, "Main timeline: {}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for ExtractError {}
+
+#[derive(Debug, Default)]
+pub struct Extraction {
+    pub assets: Vec<Asset>,
+    pub errors: Vec<ExtractError>,
+}
+
+/// Extracts all assets of an SWF file.
+pub fn extract_assets(swf: &Swf) -> Extraction {
+    let mut extractor = Extractor {
+        encoding: SwfStr::encoding_for_version(swf.header.version()),
+        frame_rate: swf.header.frame_rate().to_f64(),
+        names: HashMap::new(),
+        jpeg_tables: None,
+        font_infos: HashMap::new(),
+        font_codes: HashMap::new(),
+        bitmaps: HashMap::new(),
+        videos: BTreeMap::new(),
+        file_names: HashSet::new(),
+        extraction: Extraction::default(),
+    };
+    extractor.collect(&swf.tags);
+    extractor.extract_timeline(&swf.tags, None);
+    extractor.finish()
+}
+
+struct Extractor<'a> {
+    encoding: &'static Encoding,
+    frame_rate: f64,
+
+    /// The class or export name of each named character.
+    names: HashMap<CharacterId, String>,
+
+    jpeg_tables: Option<&'a [u8]>,
+
+    /// The codes and names of `DefineFont` glyphs.
+    font_infos: HashMap<CharacterId, &'a FontInfo<'a>>,
+
+    /// The character code of each glyph of each font, for decoding static text.
+    font_codes: HashMap<CharacterId, Vec<u16>>,
+
+    /// The bitmaps extracted so far, which shapes may use as fills.
+    bitmaps: HashMap<CharacterId, BitmapFile>,
+
+    videos: BTreeMap<CharacterId, VideoStream<'a>>,
+
+    file_names: HashSet<(AssetKind, String)>,
+    extraction: Extraction,
+}
+
+impl<'a> Extractor<'a> {
+    /// Gathers the information that is needed before any character can be extracted.
+    ///
+    /// Names are usually assigned at the end of a frame, after the
+    /// characters they name have been defined.
+    fn collect(&mut self, tags: &'a [Tag<'a>]) {
+        for tag in tags {
+            match tag {
+                Tag::SymbolClass(links) => {
+                    for link in links {
+                        let name = link.class_name.to_string_lossy(self.encoding);
+                        self.names.insert(link.id, name);
+                    }
+                }
+                Tag::ExportAssets(exports) => {
+                    for export in exports {
+                        // Class names take precedence over export names.
+                        let name = export.name.to_string_lossy(self.encoding);
+                        self.names.entry(export.id).or_insert(name);
+                    }
+                }
+                Tag::JpegTables(jpeg_tables) => self.jpeg_tables = Some(jpeg_tables),
+                Tag::DefineFont2(font) => {
+                    let codes = font.glyphs.iter().map(|glyph| glyph.code).collect();
+                    self.font_codes.insert(font.id, codes);
+                }
+                Tag::DefineFontInfo(font_info) => {
+                    self.font_infos.insert(font_info.id, font_info);
+                    self.font_codes
+                        .insert(font_info.id, font_info.code_table.clone());
+                }
+                Tag::DefineSprite(sprite) => self.collect(&sprite.tags),
+                _ => {}
+            }
+        }
+    }
+
+    fn extract_timeline(&mut self, tags: &'a [Tag<'a>], timeline: Option<CharacterId>) {
+        let mut sound_stream = None;
+        for tag in tags {
+            match tag {
+                Tag::DefineBits { id, jpeg_data } => {
+                    let jpeg_data = glue_tables_to_jpeg(jpeg_data, self.jpeg_tables);
+                    self.add_bitmap(*id, bitmap::extract_jpeg(&jpeg_data, None));
+                }
+                Tag::DefineBitsJpeg2 { id, jpeg_data } => {
+                    self.add_bitmap(*id, bitmap::extract_jpeg(jpeg_data, None));
+                }
+                Tag::DefineBitsJpeg3(jpeg) => {
+                    let alpha_data = Some(jpeg.alpha_data).filter(|data| !data.is_empty());
+                    self.add_bitmap(jpeg.id, bitmap::extract_jpeg(jpeg.data, alpha_data));
+                }
+                Tag::DefineBitsLossless(bits) => {
+                    self.add_bitmap(bits.id, bitmap::extract_lossless(bits));
+                }
+                Tag::DefineSound(sound) => {
+                    self.add(
+                        Some(sound.id),
+                        AssetKind::Sound,
+                        sound::extract_sound(sound),
+                    );
+                }
+                Tag::SoundStreamHead(head) | Tag::SoundStreamHead2(head) => {
+                    sound_stream = Some(SoundStream::new(head));
+                }
+                Tag::SoundStreamBlock(block) => {
+                    if let Some(sound_stream) = &mut sound_stream {
+                        sound_stream.add_block(block);
+                    }
+                }
+                Tag::DefineShape(shape) => {
+                    let svg = shape::shape_to_svg(shape, &self.bitmaps);
+                    self.add(
+                        Some(shape.id),
+                        AssetKind::Shape,
+                        Ok(("svg", svg.into_bytes())),
+                    );
+                }
+                Tag::DefineFont(font) => {
+                    let font_info = self.font_infos.get(&font.id).copied();
+                    let result = font::extract_font_v1(font, font_info, self.encoding);
+                    self.add(Some(font.id), AssetKind::Font, result);
+                }
+                Tag::DefineFont2(font) => {
+                    let result = font::extract_font(font, self.encoding);
+                    self.add(Some(font.id), AssetKind::Font, result);
+                }
+                Tag::DefineFont4(font) => {
+                    self.add(Some(font.id), AssetKind::Font, font::extract_font4(font));
+                }
+                Tag::DefineText(static_text) | Tag::DefineText2(static_text) => {
+                    let text = text::static_text(static_text, &self.font_codes);
+                    self.add(
+                        Some(static_text.id),
+                        AssetKind::Text,
+                        Ok(("txt", text.into_bytes())),
+                    );
+                }
+                Tag::DefineEditText(edit_text) => {
+                    if let Some(result) = text::edit_text(edit_text, self.encoding) {
+                        self.add(Some(edit_text.id()), AssetKind::Text, Ok(result));
+                    }
+                }
+                Tag::DefineBinaryData(binary_data) => {
+                    self.add(
+                        Some(binary_data.id),
+                        AssetKind::BinaryData,
+                        Ok(("bin", binary_data.data.to_vec())),
+                    );
+                }
+                Tag::DefineVideoStream(video) => {
+                    self.videos.insert(video.id, VideoStream::new(video));
+                }
+                Tag::VideoFrame(frame) => {
+                    if let Some(video) = self.videos.get_mut(&frame.stream_id) {
+                        video.add_frame(frame);
+                    }
+                }
+                Tag::DefineSprite(sprite) => self.extract_timeline(&sprite.tags, Some(sprite.id)),
+                _ => {}
+            }
+        }
+
+        if let Some(sound_stream) = sound_stream.filter(|stream| !stream.is_empty()) {
+            self.add(timeline, AssetKind::Sound, sound_stream.extract());
+        }
+    }
+
+    fn finish(mut self) -> Extraction {
+        for (id, video) in std::mem::take(&mut self.videos) {
+            self.add(Some(id), AssetKind::Video, video.extract(self.frame_rate));
+        }
+        self.extraction
+    }
+
+    fn add_bitmap(&mut self, id: CharacterId, result: Result<bitmap::ExtractedBitmap>) {
+        let (width, height) = match &result {
+            Ok(bitmap) => (bitmap.width, bitmap.height),
+            Err(_) => (0, 0),
+        };
+        let result = result.map(|bitmap| (bitmap.extension, bitmap.data));
+        if let Some(file_name) = self.add(Some(id), AssetKind::Image, result) {
+            self.bitmaps.insert(
+                id,
+                BitmapFile {
+                    path: format!("../{}/{file_name}", AssetKind::Image.directory()),
+                    width,
+                    height,
+                },
+            );
+        }
+    }
+
+    /// Adds an extracted asset, returning its file name.
+    fn add(
+        &mut self,
+        id: Option<CharacterId>,
+        kind: AssetKind,
+        result: Result<(&'static str, Vec<u8>)>,
+    ) -> Option<String> {
+        match result {
+            Ok((extension, data)) => {
+                let file_name = self.file_name(id, kind, extension);
+                self.extraction.assets.push(Asset {
+                    id,
+                    kind,
+                    file_name: file_name.clone(),
+                    data,
+                });
+                Some(file_name)
+            }
+            Err(error) => {
+                self.extraction.errors.push(ExtractError {
+                    id,
+                    message: format!("{error:#}"),
+                });
+                None
+            }
+        }
+    }
+
+    fn file_name(&mut self, id: Option<CharacterId>, kind: AssetKind, extension: &str) -> String {
+        let stem = match id {
+            Some(id) => match self.names.get(&id) {
+                Some(name) => sanitize_file_name(name),
+                None => id.to_string(),
+            },
+            None => "main_timeline".to_string(),
+        };
+        let mut file_name = format!("{stem}.{extension}");
+        if !self.file_names.insert((kind, file_name.clone())) {
+            // Characters can share a name, for example when a class is
+            // exported under the name of another class.
+            file_name = format!("{stem}_{}.{extension}", id.unwrap_or_default());
+            self.file_names.insert((kind, file_name.clone()));
+        }
+        file_name
+    }
+}
+
+/// Replaces the characters of a class or export name that aren't safe to use in file names.
+fn sanitize_file_name(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || matches!(c, '.' | '_' | '-') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    // Don't create hidden files, or go up a directory.
+    name.trim_start_matches('.').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn extract(data: &[u8]) -> Extraction {
+        let swf_buf = swf::decompress_swf(data).unwrap();
+        let swf = swf::parse_swf(&swf_buf).unwrap();
+        extract_assets(&swf)
+    }
+
+    fn file_names(extraction: &Extraction, kind: AssetKind) -> Vec<&str> {
+        extraction
+            .assets
+            .iter()
+            .filter(|asset| asset.kind == kind)
+            .map(|asset| asset.file_name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn sanitize_names() {
+        assert_eq!(sanitize_file_name("com.example.Hero"), "com.example.Hero");
+        assert_eq!(sanitize_file_name("Hero$walk cycle"), "Hero_walk_cycle");
+        assert_eq!(sanitize_file_name("../secret"), "_secret");
+    }
+
+    #[test]
+    fn names_come_from_symbols() {
+        let tags = [
+            Tag::DefineBinaryData(swf::DefineBinaryData {
+                id: 1,
+                data: b"first",
+            }),
+            Tag::DefineBinaryData(swf::DefineBinaryData {
+                id: 2,
+                data: b"second",
+            }),
+            Tag::DefineBinaryData(swf::DefineBinaryData {
+                id: 3,
+                data: b"third",
+            }),
+            Tag::ExportAssets(vec![
+                swf::ExportedAsset {
+                    id: 1,
+                    name: SwfStr::from_utf8_str("exported"),
+                },
+                swf::ExportedAsset {
+                    id: 2,
+                    name: SwfStr::from_utf8_str("Level 1"),
+                },
+            ]),
+            Tag::SymbolClass(vec![swf::SymbolClassLink {
+                id: 1,
+                class_name: SwfStr::from_utf8_str("game.Level"),
+            }]),
+            Tag::ShowFrame,
+        ];
+        let mut data = Vec::new();
+        swf::write_swf(&swf::Header::default_with_swf_version(10), &tags, &mut data).unwrap();
+        let extraction = extract(&data);
+        assert_eq!(
+            file_names(&extraction, AssetKind::BinaryData),
+            ["game.Level.bin", "Level_1.bin", "3.bin"]
+        );
+    }
+
+    #[test]
+    fn extract_bitmaps() {
+        for data in [
+            &include_bytes!("../../../swf/tests/swfs/DefineBits-JpegTables-MX.swf")[..],
+            include_bytes!("../../../swf/tests/swfs/DefineBitsJpeg2-MX.swf"),
+            include_bytes!("../../../swf/tests/swfs/DefineBitsJpeg3.swf"),
+            include_bytes!("../../../swf/tests/swfs/DefineBitsLossless.swf"),
+            include_bytes!("../../../swf/tests/swfs/DefineBitsLossless2.swf"),
+        ] {
+            let extraction = extract(data);
+            assert!(extraction.errors.is_empty(), "{:?}", extraction.errors);
+            let image = extraction
+                .assets
+                .iter()
+                .find(|asset| asset.kind == AssetKind::Image)
+                .unwrap();
+            let format = image::guess_format(&image.data).unwrap();
+            match image.file_name.rsplit_once('.').unwrap().1 {
+                "png" => {
+                    assert_eq!(format, image::ImageFormat::Png);
+                    let decoded = image::load_from_memory(&image.data).unwrap();
+                    assert!(decoded.width() > 0 && decoded.height() > 0);
+                }
+                "jpg" => assert_eq!(format, image::ImageFormat::Jpeg),
+                extension => panic!("Unexpected extension {extension}"),
+            }
+        }
+    }
+
+    #[test]
+    fn extract_binary_data() {
+        let extraction = extract(include_bytes!(
+            "../../../swf/tests/swfs/DefineBinaryData.swf"
+        ));
+        let binary = &extraction.assets[0];
+        assert_eq!(binary.kind, AssetKind::BinaryData);
+        assert!(binary.file_name.ends_with(".bin"));
+        assert!(!binary.data.is_empty());
+    }
+
+    #[test]
+    fn extract_other_kinds() {
+        for (data, kind, extension) in [
+            (
+                &include_bytes!("../../../swf/tests/swfs/DefineShape.swf")[..],
+                AssetKind::Shape,
+                "svg",
+            ),
+            (
+                include_bytes!("../../../swf/tests/swfs/DefineSound.swf"),
+                AssetKind::Sound,
+                "wav",
+            ),
+            (
+                include_bytes!("../../../swf/tests/swfs/DefineFont2-CS6.swf"),
+                AssetKind::Font,
+                "ttf",
+            ),
+            (
+                include_bytes!("../../../swf/tests/swfs/DefineText2-MX.swf"),
+                AssetKind::Text,
+                "txt",
+            ),
+            (
+                include_bytes!("../../../swf/tests/swfs/DefineVideoStream.swf"),
+                AssetKind::Video,
+                "flv",
+            ),
+        ] {
+            let extraction = extract(data);
+            let names = file_names(&extraction, kind);
+            assert!(
+                names.iter().any(|name| name.ends_with(extension)),
+                "{names:?} {:?}",
+                extraction.errors
+            );
+        }
+    }
+}
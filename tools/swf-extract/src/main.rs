@@ -0,0 +1,44 @@
+use anyhow::{Context, Result};
+use clap::Parser;
+use std::path::PathBuf;
+use swf_extract::extract_assets;
+
+#[derive(Parser, Debug)]
+#[clap(name = "swf-extract", version, about, author)]
+struct Opt {
+    /// The SWF file to extract assets from
+    #[clap(name = "file")]
+    input_path: PathBuf,
+
+    /// The directory to write the assets to, which defaults to the name of the file without its extension
+    #[clap(short, long)]
+    output: Option<PathBuf>,
+}
+
+fn main() -> Result<()> {
+    let opt = Opt::parse();
+
+    let data = std::fs::read(&opt.input_path)
+        .with_context(|| format!("Couldn't read {}", opt.input_path.display()))?;
+    let swf_buf = swf::decompress_swf(&data[..]).context("Couldn't decompress SWF")?;
+    let swf = swf::parse_swf(&swf_buf).context("Couldn't parse SWF")?;
+    let extraction = extract_assets(&swf);
+
+    let output = opt
+        .output
+        .unwrap_or_else(|| opt.input_path.with_extension(""));
+    for asset in &extraction.assets {
+        let directory = output.join(asset.kind.directory());
+        std::fs::create_dir_all(&directory)
+            .with_context(|| format!("Couldn't create {}", directory.display()))?;
+        let path = directory.join(&asset.file_name);
+        std::fs::write(&path, &asset.data)
+            .with_context(|| format!("Couldn't write {}", path.display()))?;
+        println!("{}", path.display());
+    }
+    for error in &extraction.errors {
+        eprintln!("{error}");
+    }
+
+    Ok(())
+}
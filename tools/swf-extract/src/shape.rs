@@ -0,0 +1,376 @@
+use crate::bitmap::BitmapFile;
+use ruffle_render::shape_utils::{DistilledShape, DrawCommand, DrawPath, FillRule};
+use std::collections::HashMap;
+use std::fmt::Write;
+use swf::{
+    CharacterId, Color, FillStyle, Gradient, GradientInterpolation, GradientSpread, LineCapStyle,
+    LineJoinStyle, LineStyle, Matrix, Twips,
+};
+
+/// The half-size of the gradient square, in pixels.
+const GRADIENT_SIZE: f64 = 16384.0 / 20.0;
+
+/// Converts a shape into an SVG image.
+///
+/// Bitmap fills refer to the extracted bitmaps, which must have been
+/// extracted before the shape.
+pub fn shape_to_svg(shape: &swf::Shape, bitmaps: &HashMap<CharacterId, BitmapFile>) -> String {
+    let mut writer = SvgWriter {
+        defs: String::new(),
+        body: String::new(),
+        next_id: 0,
+        bitmaps,
+    };
+    for path in DistilledShape::from(shape).paths {
+        match path {
+            DrawPath::Fill {
+                style,
+                commands,
+                winding_rule,
+            } => writer.fill(style, &commands, winding_rule),
+            DrawPath::Stroke {
+                style,
+                is_closed,
+                commands,
+            } => writer.stroke(style, &commands, is_closed),
+        }
+    }
+
+    let bounds = &shape.shape_bounds;
+    let mut svg = format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" width="{width}" height="{height}" viewBox="{x} {y} {width} {height}">"#,
+        x = pixels(bounds.x_min),
+        y = pixels(bounds.y_min),
+        width = pixels(bounds.width()),
+        height = pixels(bounds.height()),
+    );
+    svg.push('\n');
+    if !writer.defs.is_empty() {
+        let _ = writeln!(svg, "<defs>\n{}</defs>", writer.defs);
+    }
+    svg.push_str(&writer.body);
+    svg.push_str("</svg>\n");
+    svg
+}
+
+struct SvgWriter<'a> {
+    defs: String,
+    body: String,
+    next_id: u32,
+    bitmaps: &'a HashMap<CharacterId, BitmapFile>,
+}
+
+impl SvgWriter<'_> {
+    fn fill(&mut self, style: &FillStyle, commands: &[DrawCommand], winding_rule: FillRule) {
+        let paint = self.paint(style);
+        let _ = write!(
+            self.body,
+            r#"<path d="{}" fill="{paint}""#,
+            path_data(commands)
+        );
+        if winding_rule == FillRule::EvenOdd {
+            self.body.push_str(r#" fill-rule="evenodd""#);
+        }
+        self.body.push_str("/>\n");
+    }
+
+    fn stroke(&mut self, style: &LineStyle, commands: &[DrawCommand], is_closed: bool) {
+        let paint = self.paint(style.fill_style());
+        let mut data = path_data(commands);
+        if is_closed {
+            data.push_str(" Z");
+        }
+        // Flash draws hairlines one pixel wide.
+        let width = match style.width() {
+            Twips::ZERO => 1.0,
+            width => width.to_pixels(),
+        };
+        let cap = match style.start_cap() {
+            LineCapStyle::Round => "round",
+            LineCapStyle::None => "butt",
+            LineCapStyle::Square => "square",
+        };
+        let _ = write!(
+            self.body,
+            r#"<path d="{data}" fill="none" stroke="{paint}" stroke-width="{}" stroke-linecap="{cap}""#,
+            number(width)
+        );
+        match style.join_style() {
+            LineJoinStyle::Round => self.body.push_str(r#" stroke-linejoin="round""#),
+            LineJoinStyle::Bevel => self.body.push_str(r#" stroke-linejoin="bevel""#),
+            LineJoinStyle::Miter(limit) => {
+                let _ = write!(
+                    self.body,
+                    r#" stroke-linejoin="miter" stroke-miterlimit="{}""#,
+                    number(limit.to_f64().max(1.0))
+                );
+            }
+        }
+        self.body.push_str("/>\n");
+    }
+
+    /// Returns the value of a `fill` or `stroke` attribute for a fill style,
+    /// defining a gradient or pattern if needed.
+    fn paint(&mut self, style: &FillStyle) -> String {
+        match style {
+            FillStyle::Color(color) => color_paint(*color),
+            FillStyle::LinearGradient(gradient) => {
+                let id = self.gradient(
+                    "linearGradient",
+                    gradient,
+                    format_args!(
+                        r#"x1="{}" y1="0" x2="{}" y2="0""#,
+                        -GRADIENT_SIZE, GRADIENT_SIZE
+                    ),
+                );
+                format!("url(#{id})")
+            }
+            FillStyle::RadialGradient(gradient) => {
+                let id = self.gradient(
+                    "radialGradient",
+                    gradient,
+                    format_args!(r#"cx="0" cy="0" r="{GRADIENT_SIZE}""#),
+                );
+                format!("url(#{id})")
+            }
+            FillStyle::FocalGradient {
+                gradient,
+                focal_point,
+            } => {
+                let id = self.gradient(
+                    "radialGradient",
+                    gradient,
+                    format_args!(
+                        r#"cx="0" cy="0" r="{GRADIENT_SIZE}" fx="{}" fy="0""#,
+                        number(focal_point.to_f64() * GRADIENT_SIZE)
+                    ),
+                );
+                format!("url(#{id})")
+            }
+            FillStyle::Bitmap {
+                id: bitmap_id,
+                matrix,
+                ..
+            } => {
+                let Some(bitmap) = self.bitmaps.get(bitmap_id) else {
+                    return "none".to_string();
+                };
+                let id = self.new_id();
+                // Bitmap fill matrices transform from pixels of the bitmap.
+                let _ = writeln!(
+                    self.defs,
+                    r#"<pattern id="{id}" patternUnits="userSpaceOnUse" width="{width}" height="{height}" patternTransform="{}"><image xlink:href="{}" width="{width}" height="{height}"/></pattern>"#,
+                    svg_matrix(matrix, 1.0),
+                    escape(&bitmap.path),
+                    width = bitmap.width,
+                    height = bitmap.height,
+                );
+                format!("url(#{id})")
+            }
+        }
+    }
+
+    fn gradient(
+        &mut self,
+        element: &str,
+        gradient: &Gradient,
+        attributes: std::fmt::Arguments,
+    ) -> String {
+        let id = self.new_id();
+        let spread = match gradient.spread {
+            GradientSpread::Pad => "pad",
+            GradientSpread::Reflect => "reflect",
+            GradientSpread::Repeat => "repeat",
+        };
+        let _ = write!(
+            self.defs,
+            r#"<{element} id="{id}" gradientUnits="userSpaceOnUse" gradientTransform="{}" spreadMethod="{spread}" {attributes}"#,
+            svg_matrix(&gradient.matrix, 20.0),
+        );
+        if gradient.interpolation == GradientInterpolation::LinearRgb {
+            self.defs.push_str(r#" color-interpolation="linearRGB""#);
+        }
+        self.defs.push_str(">\n");
+        for record in &gradient.records {
+            let _ = write!(
+                self.defs,
+                r#"<stop offset="{}" stop-color="{}""#,
+                number(f64::from(record.ratio) / 255.0),
+                hex_color(record.color)
+            );
+            if record.color.a != 255 {
+                let _ = write!(
+                    self.defs,
+                    r#" stop-opacity="{}""#,
+                    number(f64::from(record.color.a) / 255.0)
+                );
+            }
+            self.defs.push_str("/>\n");
+        }
+        let _ = writeln!(self.defs, "</{element}>");
+        id
+    }
+
+    fn new_id(&mut self) -> String {
+        self.next_id += 1;
+        format!("paint{}", self.next_id)
+    }
+}
+
+fn path_data(commands: &[DrawCommand]) -> String {
+    let mut data = String::new();
+    for command in commands {
+        if !data.is_empty() {
+            data.push(' ');
+        }
+        let _ = match command {
+            DrawCommand::MoveTo(point) => write!(data, "M{} {}", pixels(point.x), pixels(point.y)),
+            DrawCommand::LineTo(point) => write!(data, "L{} {}", pixels(point.x), pixels(point.y)),
+            DrawCommand::QuadraticCurveTo { control, anchor } => write!(
+                data,
+                "Q{} {} {} {}",
+                pixels(control.x),
+                pixels(control.y),
+                pixels(anchor.x),
+                pixels(anchor.y)
+            ),
+            DrawCommand::CubicCurveTo {
+                control_a,
+                control_b,
+                anchor,
+            } => write!(
+                data,
+                "C{} {} {} {} {} {}",
+                pixels(control_a.x),
+                pixels(control_a.y),
+                pixels(control_b.x),
+                pixels(control_b.y),
+                pixels(anchor.x),
+                pixels(anchor.y)
+            ),
+        };
+    }
+    data
+}
+
+/// Formats a matrix as an SVG transform in pixels.
+///
+/// `scale` is the number of units of the space the matrix transforms from
+/// per unit of the SVG element it is applied to. Gradient matrices transform
+/// from twips, while gradients are defined in pixels.
+fn svg_matrix(matrix: &Matrix, scale: f64) -> String {
+    format!(
+        "matrix({} {} {} {} {} {})",
+        number(matrix.a.to_f64() * scale / 20.0),
+        number(matrix.b.to_f64() * scale / 20.0),
+        number(matrix.c.to_f64() * scale / 20.0),
+        number(matrix.d.to_f64() * scale / 20.0),
+        pixels(matrix.tx),
+        pixels(matrix.ty),
+    )
+}
+
+fn color_paint(color: Color) -> String {
+    if color.a == 255 {
+        hex_color(color)
+    } else {
+        // `fill-opacity` would also apply to gradients, so use an RGBA color instead.
+        format!(
+            "rgba({},{},{},{})",
+            color.r,
+            color.g,
+            color.b,
+            number(f64::from(color.a) / 255.0)
+        )
+    }
+}
+
+fn hex_color(color: Color) -> String {
+    format!("#{:02x}{:02x}{:02x}", color.r, color.g, color.b)
+}
+
+fn pixels(twips: Twips) -> String {
+    number(twips.to_pixels())
+}
+
+/// Formats a number without trailing zeros or excessive precision.
+fn number(n: f64) -> String {
+    let n = (n * 1e6).round() / 1e6;
+    // Avoid printing `-0`.
+    if n == 0.0 {
+        "0".to_string()
+    } else {
+        n.to_string()
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use swf::{Point, Rectangle, ShapeFlag, ShapeRecord, ShapeStyles, StyleChangeData};
+
+    #[test]
+    fn square() {
+        let shape = swf::Shape {
+            version: 1,
+            id: 1,
+            shape_bounds: Rectangle {
+                x_min: Twips::ZERO,
+                x_max: Twips::from_pixels(10.0),
+                y_min: Twips::ZERO,
+                y_max: Twips::from_pixels(10.0),
+            },
+            edge_bounds: Default::default(),
+            flags: ShapeFlag::empty(),
+            styles: ShapeStyles {
+                fill_styles: vec![FillStyle::Color(Color::from_rgba(0x80ff0000))],
+                line_styles: vec![],
+            },
+            shape: vec![
+                ShapeRecord::StyleChange(Box::new(StyleChangeData {
+                    move_to: Some(Point::new(Twips::ZERO, Twips::ZERO)),
+                    fill_style_0: None,
+                    fill_style_1: Some(1),
+                    line_style: None,
+                    new_styles: None,
+                })),
+                ShapeRecord::StraightEdge {
+                    delta: swf::PointDelta::new(Twips::from_pixels(10.0), Twips::ZERO),
+                },
+                ShapeRecord::StraightEdge {
+                    delta: swf::PointDelta::new(Twips::ZERO, Twips::new(205)),
+                },
+                ShapeRecord::StraightEdge {
+                    delta: swf::PointDelta::new(Twips::from_pixels(-10.0), Twips::ZERO),
+                },
+                ShapeRecord::StraightEdge {
+                    delta: swf::PointDelta::new(Twips::ZERO, Twips::new(-205)),
+                },
+            ],
+        };
+        let svg = shape_to_svg(&shape, &HashMap::new());
+        assert_eq!(
+            svg,
+            concat!(
+                r#"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" width="10" height="10" viewBox="0 0 10 10">"#,
+                "\n",
+                r#"<path d="M0 0 L10 0 L10 10.25 L0 10.25 L0 0" fill="rgba(255,0,0,0.501961)" fill-rule="evenodd"/>"#,
+                "\n</svg>\n"
+            )
+        );
+    }
+
+    #[test]
+    fn format_numbers() {
+        assert_eq!(number(-0.0), "0");
+        assert_eq!(number(0.15), "0.15");
+        assert_eq!(number(1.0 / 3.0), "0.333333");
+        assert_eq!(pixels(Twips::new(-3)), "-0.15");
+    }
+}
@@ -0,0 +1,116 @@
+use anyhow::{Result, bail};
+use swf::{AudioCompression, Sound, SoundFormat, SoundStreamHead};
+
+/// Extracts a DefineSound tag as an MP3 or WAV file.
+pub fn extract_sound(sound: &Sound) -> Result<(&'static str, Vec<u8>)> {
+    match sound.format.compression {
+        // MP3 sounds start with the number of samples to skip.
+        AudioCompression::Mp3 => Ok(("mp3", sound.data.get(2..).unwrap_or_default().to_vec())),
+        _ => Ok(("wav", wav(&sound.format, sound.data)?)),
+    }
+}
+
+/// The blocks of a sound stream, collected over a timeline.
+pub struct SoundStream {
+    format: SoundFormat,
+    data: Vec<u8>,
+}
+
+impl SoundStream {
+    pub fn new(head: &SoundStreamHead) -> Self {
+        Self {
+            format: head.stream_format.clone(),
+            data: Vec::new(),
+        }
+    }
+
+    pub fn add_block(&mut self, block: &[u8]) {
+        match self.format.compression {
+            // MP3 blocks start with their number of samples and the number of samples to skip.
+            AudioCompression::Mp3 => self
+                .data
+                .extend_from_slice(block.get(4..).unwrap_or_default()),
+            _ => self.data.extend_from_slice(block),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn extract(self) -> Result<(&'static str, Vec<u8>)> {
+        match self.format.compression {
+            AudioCompression::Mp3 => Ok(("mp3", self.data)),
+            _ => Ok(("wav", wav(&self.format, &self.data)?)),
+        }
+    }
+}
+
+/// Wraps uncompressed PCM samples in a WAV file.
+fn wav(format: &SoundFormat, samples: &[u8]) -> Result<Vec<u8>> {
+    match format.compression {
+        // Flash always reads these samples as little-endian, like WAV does.
+        AudioCompression::Uncompressed | AudioCompression::UncompressedUnknownEndian => {}
+        compression => bail!("Can't extract {compression:?} sounds"),
+    }
+
+    let num_channels: u16 = if format.is_stereo { 2 } else { 1 };
+    let bits_per_sample: u16 = if format.is_16_bit { 16 } else { 8 };
+    let block_align = num_channels * bits_per_sample / 8;
+    let sample_rate = u32::from(format.sample_rate);
+    let data_len = samples.len() as u32;
+
+    let mut wav = Vec::with_capacity(44 + samples.len());
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+    wav.extend_from_slice(b"WAVE");
+    wav.extend_from_slice(b"fmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    wav.extend_from_slice(&num_channels.to_le_bytes());
+    wav.extend_from_slice(&sample_rate.to_le_bytes());
+    wav.extend_from_slice(&(sample_rate * u32::from(block_align)).to_le_bytes());
+    wav.extend_from_slice(&block_align.to_le_bytes());
+    wav.extend_from_slice(&bits_per_sample.to_le_bytes());
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_len.to_le_bytes());
+    wav.extend_from_slice(samples);
+    if samples.len() % 2 == 1 {
+        // RIFF chunks are padded to an even length.
+        wav.push(0);
+    }
+    Ok(wav)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wav_header() {
+        let format = SoundFormat {
+            compression: AudioCompression::Uncompressed,
+            sample_rate: 22050,
+            is_stereo: true,
+            is_16_bit: true,
+        };
+        let wav = wav(&format, &[1, 2, 3, 4]).unwrap();
+        assert_eq!(&wav[..4], b"RIFF");
+        assert_eq!(&wav[4..8], &40u32.to_le_bytes());
+        assert_eq!(&wav[22..24], &2u16.to_le_bytes());
+        assert_eq!(&wav[24..28], &22050u32.to_le_bytes());
+        assert_eq!(&wav[28..32], &88200u32.to_le_bytes());
+        assert_eq!(&wav[40..], &[4, 0, 0, 0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn compressed_sounds_are_rejected() {
+        let format = SoundFormat {
+            compression: AudioCompression::Adpcm,
+            sample_rate: 11025,
+            is_stereo: false,
+            is_16_bit: true,
+        };
+        assert!(wav(&format, &[]).is_err());
+    }
+}
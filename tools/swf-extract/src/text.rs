@@ -0,0 +1,83 @@
+use std::collections::HashMap;
+use swf::{CharacterId, EditText, Encoding, Text};
+
+/// Recovers the text of a DefineText tag from the glyph codes of its fonts.
+///
+/// Records on a different baseline start a new line.
+pub fn static_text(text: &Text, font_codes: &HashMap<CharacterId, Vec<u16>>) -> String {
+    let mut out = String::new();
+    let mut font_id = None;
+    let mut baseline = None;
+    for record in &text.records {
+        if record.font_id.is_some() {
+            font_id = record.font_id;
+        }
+        if let Some(y) = record.y_offset {
+            if baseline.is_some_and(|baseline| baseline != y) {
+                out.push('\n');
+            }
+            baseline = Some(y);
+        }
+
+        let codes = font_id.and_then(|id| font_codes.get(&id));
+        out.extend(record.glyphs.iter().map(|glyph| {
+            codes
+                .and_then(|codes| codes.get(glyph.index as usize))
+                .and_then(|&code| char::from_u32(code.into()))
+                .unwrap_or(char::REPLACEMENT_CHARACTER)
+        }));
+    }
+    out.push('\n');
+    out
+}
+
+/// Extracts the initial text of a DefineEditText tag, as HTML if the text field uses it.
+pub fn edit_text(
+    edit_text: &EditText,
+    encoding: &'static Encoding,
+) -> Option<(&'static str, Vec<u8>)> {
+    let text = edit_text.initial_text()?.to_string_lossy(encoding);
+    if edit_text.is_html() {
+        Some(("html", text.into_bytes()))
+    } else {
+        // Flash uses carriage returns as line breaks.
+        let text = text.replace("\r\n", "\n").replace('\r', "\n");
+        Some(("txt", text.into_bytes()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use swf::{GlyphEntry, Matrix, Rectangle, TextRecord, Twips};
+
+    fn record(font_id: Option<CharacterId>, y: Option<i32>, indices: &[u32]) -> TextRecord {
+        TextRecord {
+            font_id,
+            color: None,
+            x_offset: None,
+            y_offset: y.map(Twips::new),
+            height: None,
+            glyphs: indices
+                .iter()
+                .map(|&index| GlyphEntry { index, advance: 0 })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn lines_of_static_text() {
+        let font_codes = HashMap::from([(1, "HeloWrd!".encode_utf16().collect())]);
+        let text = Text {
+            id: 2,
+            bounds: Rectangle::default(),
+            matrix: Matrix::IDENTITY,
+            records: vec![
+                record(Some(1), Some(100), &[0, 1, 2, 2, 3]),
+                record(None, Some(100), &[7]),
+                record(None, Some(400), &[4, 3, 5, 2, 6, 7, 9]),
+            ],
+        };
+        assert_eq!(static_text(&text, &font_codes), "Hello!\nWorld!\u{FFFD}\n");
+    }
+}
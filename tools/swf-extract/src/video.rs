@@ -0,0 +1,142 @@
+use anyhow::{Result, bail};
+use flv_rs::{
+    CodecId, FlvWriter, FrameType, Header, MetaData, Tag, TagData, TypeFlags, VideoData,
+    VideoPacket,
+};
+use swf::{DefineVideoStream, VideoCodec, VideoFrame};
+
+/// The frames of a video stream, collected over the whole file.
+pub struct VideoStream<'a> {
+    stream: &'a DefineVideoStream,
+    frames: Vec<&'a VideoFrame<'a>>,
+}
+
+impl<'a> VideoStream<'a> {
+    pub fn new(stream: &'a DefineVideoStream) -> Self {
+        Self {
+            stream,
+            frames: Vec::new(),
+        }
+    }
+
+    pub fn add_frame(&mut self, frame: &'a VideoFrame<'a>) {
+        self.frames.push(frame);
+    }
+
+    /// Muxes the video frames into an FLV file, timed by the frame rate of the movie.
+    pub fn extract(self, frame_rate: f64) -> Result<(&'static str, Vec<u8>)> {
+        let codec_id = match self.stream.codec {
+            VideoCodec::H263 => CodecId::SorensonH263,
+            VideoCodec::ScreenVideo => CodecId::ScreenVideo,
+            VideoCodec::Vp6 => CodecId::On2Vp6,
+            VideoCodec::Vp6WithAlpha => CodecId::On2Vp6Alpha,
+            VideoCodec::ScreenVideoV2 => CodecId::ScreenVideo2,
+            codec => bail!("Can't extract {codec:?} video"),
+        };
+        if self.frames.is_empty() {
+            bail!("Video has no frames");
+        }
+        let frame_rate = if frame_rate > 0.0 { frame_rate } else { 1.0 };
+
+        // FLV stores how many pixels VP6 frames are cropped by, while SWF
+        // stores the size of the video.
+        let hadjust = ((16 - self.stream.width % 16) % 16) as u8;
+        let vadjust = ((16 - self.stream.height % 16) % 16) as u8;
+
+        let tags: Vec<_> = self
+            .frames
+            .iter()
+            .enumerate()
+            .map(|(i, frame)| {
+                let frame_type = if is_keyframe(self.stream.codec, frame.data).unwrap_or(i == 0) {
+                    FrameType::Keyframe
+                } else {
+                    FrameType::Interframe
+                };
+                let data = match codec_id {
+                    CodecId::On2Vp6 | CodecId::On2Vp6Alpha => VideoPacket::Vp6Data {
+                        hadjust,
+                        vadjust,
+                        data: frame.data,
+                    },
+                    _ => VideoPacket::Data(frame.data),
+                };
+                Tag {
+                    timestamp: (f64::from(frame.frame_num) * 1000.0 / frame_rate) as i32,
+                    stream_id: 0,
+                    data: TagData::Video(VideoData {
+                        frame_type,
+                        codec_id,
+                        data,
+                    }),
+                }
+            })
+            .collect();
+
+        let header = Header {
+            version: 1,
+            type_flags: TypeFlags::HAS_VIDEO,
+            data_offset: 9,
+        };
+        let mut metadata = MetaData {
+            duration: Some(self.frames.len() as f64 / frame_rate),
+            width: Some(self.stream.width.into()),
+            height: Some(self.stream.height.into()),
+            frame_rate: Some(frame_rate),
+            video_codec: Some(codec_id),
+            ..Default::default()
+        };
+        metadata.index_keyframes(&header, &tags)?;
+
+        let mut writer = FlvWriter::new(Vec::new());
+        header.write(&mut writer)?;
+        metadata.to_tag().write(&mut writer)?;
+        for tag in &tags {
+            tag.write(&mut writer)?;
+        }
+        Ok(("flv", writer.finish()?))
+    }
+}
+
+/// Reads whether a video frame is a keyframe from its picture header, if the codec has one.
+fn is_keyframe(codec: VideoCodec, data: &[u8]) -> Option<bool> {
+    match codec {
+        VideoCodec::H263 => {
+            // The picture type follows the start code, version, temporal
+            // reference and picture size, and an optional custom size.
+            let mut header = [0; 16];
+            let len = data.len().min(16);
+            header[..len].copy_from_slice(&data[..len]);
+            let bits = u128::from_be_bytes(header);
+            let bits_at =
+                |offset: u32, count: u32| (bits >> (128 - offset - count)) & ((1 << count) - 1);
+            let offset = match bits_at(30, 3) {
+                0 => 33 + 16,
+                1 => 33 + 32,
+                _ => 33,
+            };
+            Some(bits_at(offset, 2) == 0)
+        }
+        // VP6 frames start with a flag that is unset for keyframes.
+        VideoCodec::Vp6 => Some(data.first()? & 0x80 == 0),
+        // VP6 frames with alpha start with the offset of the alpha channel.
+        VideoCodec::Vp6WithAlpha => Some(data.get(3)? & 0x80 == 0),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn h263_keyframes() {
+        // Start code, version 0, temporal reference 1, 176x144, and then picture type.
+        let intra = [0x00, 0x00, 0x80, 0x05, 0x80];
+        let inter = [0x00, 0x00, 0x80, 0x05, 0xA0];
+        assert_eq!(is_keyframe(VideoCodec::H263, &intra), Some(true));
+        assert_eq!(is_keyframe(VideoCodec::H263, &inter), Some(false));
+        assert_eq!(is_keyframe(VideoCodec::Vp6, &[0x80]), Some(false));
+        assert_eq!(is_keyframe(VideoCodec::ScreenVideo, &[0x00]), None);
+    }
+}